@@ -0,0 +1,16 @@
+//! Embeds the git revision so records can name the build that wrote them.
+
+use std::process::Command;
+
+fn main() {
+    // Builds without a checkout (e.g. Docker) can pass it in
+    println!("cargo:rerun-if-env-changed=POLYBOT_GIT_VERSION");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+
+    let version = std::env::var("POLYBOT_GIT_VERSION").ok().or_else(|| {
+        let output = Command::new("git").args(["describe", "--always", "--dirty"]).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    });
+    println!("cargo:rustc-env=POLYBOT_GIT_VERSION={}", version.unwrap_or_else(|| "unknown".to_string()));
+}
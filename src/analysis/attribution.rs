@@ -0,0 +1,275 @@
+//! Performance attribution per matched pair.
+//!
+//! A pair can show fat spreads and still lose money: its legs fail to fill,
+//! fees eat the edge, or the venues resolve it differently so the hedge
+//! never held. Every detection, executed arb and failed execution is
+//! tallied per pair in [`ATTRIBUTION_FILE`]; settled P&L comes from joining
+//! the executions with the resolution history, so each pair's detected
+//! edge can be weighed against what it actually made.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use crate::arbitrage::Opportunity;
+use crate::monitor::resolution::Resolution;
+use crate::utils::state;
+use crate::utils::venue_constraints::PairedOrder;
+
+/// Default attribution ledger location.
+pub const ATTRIBUTION_FILE: &str = "data/attribution.json";
+
+/// Failure messages kept per pair.
+const MAX_FAILURES: usize = 20;
+
+/// One executed arb on a pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairExecution {
+    pub at: DateTime<Utc>,
+    /// Execution mode, e.g. "dry_run", "live"
+    pub mode: String,
+    pub yes_venue: String,
+    pub yes_price: f64,
+    pub no_venue: String,
+    pub no_price: f64,
+    /// Contracts on each leg
+    pub size: f64,
+    pub fees: f64,
+}
+
+impl PairExecution {
+    pub fn new(mode: &str, (yes_venue, no_venue): (&str, &str), order: &PairedOrder, fees: f64, at: DateTime<Utc>) -> Self {
+        Self {
+            at,
+            mode: mode.to_string(),
+            yes_venue: yes_venue.to_string(),
+            yes_price: order.price_a,
+            no_venue: no_venue.to_string(),
+            no_price: order.price_b,
+            size: order.size,
+            fees,
+        }
+    }
+
+    /// What both legs cost, fees excluded.
+    pub fn cost(&self) -> f64 {
+        self.size * (self.yes_price + self.no_price)
+    }
+
+    /// P&L once `resolution` settled the pair, fees included. The YES leg
+    /// pays what its venue's YES paid and the NO leg the complement on its
+    /// own venue, so a split resolution pays 0 or 2 per contract.
+    pub fn settled_pnl(&self, resolution: &Resolution) -> f64 {
+        let yes_payout = |venue: &str| match venue {
+            "kalshi" => resolution.kalshi.yes_payout(),
+            _ => resolution.polymarket.yes_payout(),
+        };
+        let payout = yes_payout(&self.yes_venue) + (1.0 - yes_payout(&self.no_venue));
+        self.size * payout - self.cost() - self.fees
+    }
+}
+
+/// Everything recorded on one pair.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PairActivity {
+    /// Detection passes that flagged the pair
+    pub detected: u64,
+    /// Net edge summed over those detections
+    pub total_net_edge: f64,
+    pub executions: Vec<PairExecution>,
+    pub failed: u64,
+    /// Most recent failures, newest last
+    pub failures: Vec<String>,
+}
+
+/// One pair's attribution.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PairReport {
+    pub market: String,
+    pub detected: u64,
+    pub avg_net_edge: f64,
+    pub executed: usize,
+    pub failed: u64,
+    pub fees: f64,
+    /// Executions on pairs that have resolved
+    pub settled: usize,
+    /// Settled P&L, fees included
+    pub pnl: f64,
+    /// Cost of executions still awaiting resolution
+    pub open_cost: f64,
+    /// Times the venues resolved the pair differently
+    pub divergences: usize,
+    pub last_failure: Option<String>,
+}
+
+impl PairReport {
+    /// Looks good on spreads but loses in practice: a positive detected
+    /// edge with negative settled P&L, or a settlement divergence.
+    pub fn is_suspect(&self) -> bool {
+        (self.avg_net_edge > 0.0 && self.pnl < 0.0) || self.divergences > 0
+    }
+}
+
+/// Persistent per-pair ledger.
+pub struct Attribution {
+    pairs: BTreeMap<String, PairActivity>,
+    file_path: Option<String>,
+}
+
+impl Attribution {
+    /// In-memory ledger.
+    pub fn new() -> Self {
+        Self { pairs: BTreeMap::new(), file_path: None }
+    }
+
+    /// Load from file, creating an empty ledger if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self { pairs: state::load(file_path), file_path: Some(file_path.to_string()) }
+    }
+
+    /// Count a detection. Call [`Attribution::save`] after a batch.
+    pub fn record_detection(&mut self, opportunity: &Opportunity) {
+        let pair = self.pairs.entry(opportunity.market.clone()).or_default();
+        pair.detected += 1;
+        pair.total_net_edge += opportunity.net_edge();
+    }
+
+    pub fn record_execution(&mut self, market: &str, execution: PairExecution) {
+        self.pairs.entry(market.to_string()).or_default().executions.push(execution);
+        self.save();
+    }
+
+    pub fn record_failure(&mut self, market: &str, error: &str) {
+        let pair = self.pairs.entry(market.to_string()).or_default();
+        pair.failed += 1;
+        pair.failures.push(error.to_string());
+        if pair.failures.len() > MAX_FAILURES {
+            pair.failures.remove(0);
+        }
+        self.save();
+    }
+
+    pub fn pairs(&self) -> &BTreeMap<String, PairActivity> {
+        &self.pairs
+    }
+
+    /// Attribution of every pair, worst settled P&L first. Only executions
+    /// in `mode` count when one is given.
+    pub fn report(&self, resolutions: &[Resolution], mode: Option<&str>) -> Vec<PairReport> {
+        let mut reports: Vec<PairReport> = self
+            .pairs
+            .iter()
+            .map(|(market, activity)| {
+                let resolved = resolutions.iter().find(|r| &r.name == market);
+                let executions: Vec<&PairExecution> =
+                    activity.executions.iter().filter(|e| mode.is_none_or(|m| e.mode == m)).collect();
+                let (mut settled, mut pnl, mut open_cost) = (0, 0.0, 0.0);
+                for execution in &executions {
+                    match resolved {
+                        Some(resolution) => {
+                            settled += 1;
+                            pnl += execution.settled_pnl(resolution);
+                        }
+                        None => open_cost += execution.cost() + execution.fees,
+                    }
+                }
+                PairReport {
+                    market: market.clone(),
+                    detected: activity.detected,
+                    avg_net_edge: if activity.detected == 0 { 0.0 } else { activity.total_net_edge / activity.detected as f64 },
+                    executed: executions.len(),
+                    failed: activity.failed,
+                    fees: executions.iter().map(|e| e.fees).sum(),
+                    settled,
+                    pnl,
+                    open_cost,
+                    divergences: resolutions.iter().filter(|r| &r.name == market && r.mismatched()).count(),
+                    last_failure: activity.failures.last().cloned(),
+                }
+            })
+            .collect();
+        reports.sort_by(|a, b| a.pnl.total_cmp(&b.pnl).then(b.divergences.cmp(&a.divergences)));
+        reports
+    }
+
+    pub fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.pairs) {
+                warn!("Failed to save attribution {}: {}", path, e);
+            }
+        }
+    }
+}
+
+impl Default for Attribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fees::FeeSchedule;
+    use crate::monitor::resolution::Outcome;
+
+    fn resolution(name: &str, polymarket: Outcome, kalshi: Outcome) -> Resolution {
+        Resolution {
+            name: name.to_string(),
+            polymarket_id: "111".to_string(),
+            kalshi_ticker: "KX-1".to_string(),
+            polymarket,
+            kalshi,
+            polymarket_seen_at: Utc::now(),
+            kalshi_seen_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pairs_are_attributed_detection_to_settlement() {
+        let fees = FeeSchedule::new();
+        let now = Utc::now();
+        let mut ledger = Attribution::new();
+        let btc = Opportunity::priced("BTC 100k", ("polymarket", 0.45), ("kalshi", 0.50), &fees, now).unwrap();
+        let eth = Opportunity::priced("ETH 5k", ("kalshi", 0.40), ("polymarket", 0.50), &fees, now).unwrap();
+        for _ in 0..3 {
+            ledger.record_detection(&btc);
+        }
+        ledger.record_detection(&eth);
+
+        let order = |a: f64, b: f64| PairedOrder { price_a: a, price_b: b, size: 10.0 };
+        ledger.record_execution("BTC 100k", PairExecution::new("live", ("polymarket", "kalshi"), &order(0.45, 0.50), 0.2, now));
+        ledger.record_execution("BTC 100k", PairExecution::new("dry_run", ("polymarket", "kalshi"), &order(0.45, 0.50), 0.2, now));
+        ledger.record_execution("ETH 5k", PairExecution::new("live", ("kalshi", "polymarket"), &order(0.40, 0.50), 0.1, now));
+        ledger.record_failure("ETH 5k", "Kalshi leg rejected");
+        ledger.record_failure("SOL 300", "timeout");
+
+        // BTC settled the same on both venues; ETH diverged against us
+        let resolutions = [
+            resolution("BTC 100k", Outcome::Yes, Outcome::Yes),
+            resolution("ETH 5k", Outcome::Yes, Outcome::No),
+        ];
+        let reports = ledger.report(&resolutions, Some("live"));
+        let names: Vec<&str> = reports.iter().map(|r| r.market.as_str()).collect();
+        assert_eq!(names, vec!["ETH 5k", "SOL 300", "BTC 100k"]);
+
+        // ETH: YES on Kalshi paid 0 and NO on Polymarket paid 0
+        let eth = &reports[0];
+        assert_eq!((eth.detected, eth.executed, eth.failed, eth.divergences), (1, 1, 1, 1));
+        assert!((eth.pnl - (-9.0 - 0.1)).abs() < 1e-9);
+        assert_eq!(eth.last_failure.as_deref(), Some("Kalshi leg rejected"));
+        assert!(eth.is_suspect());
+
+        let btc = &reports[2];
+        assert_eq!((btc.detected, btc.executed, btc.settled), (3, 1, 1));
+        assert!((btc.pnl - (10.0 - 9.5 - 0.2)).abs() < 1e-9);
+        assert!(!btc.is_suspect());
+
+        // Unresolved executions are open, across every mode
+        let open = ledger.report(&[], None);
+        let btc = open.iter().find(|r| r.market == "BTC 100k").unwrap();
+        assert_eq!((btc.executed, btc.settled), (2, 0));
+        assert!((btc.open_cost - 2.0 * 9.7).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,408 @@
+//! Opportunity capture analytics.
+//!
+//! Follows each opportunity acted upon from detection through execution to
+//! settlement, so slippage (detected vs executed edge) and capture ratio
+//! (realized P&L vs detected edge) show where edge is lost. Simulated
+//! executions are priced when they happen; live legs are priced from the
+//! fills the venue user streams report, and every record is settled when
+//! the resolution monitor sees its pair resolve.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::arbitrage::Opportunity;
+use crate::monitor::resolution::Resolution;
+use crate::utils::{state, Fill};
+
+/// Default capture log location.
+pub const CAPTURE_FILE: &str = "data/capture.json";
+
+/// One opportunity's journey from detection to settlement.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureRecord {
+    pub id: String,
+    pub market: String,
+    /// Execution mode, e.g. "dry_run", "paper", "live"
+    pub mode: String,
+    pub detected_at: DateTime<Utc>,
+    /// YES/NO leg prices seen by the detector
+    pub detected_yes: f64,
+    pub detected_no: f64,
+    /// Contracts acted upon
+    pub size: f64,
+    /// Fill prices, once executed
+    pub executed_yes: Option<f64>,
+    pub executed_no: Option<f64>,
+    /// P&L after settlement, fees included
+    pub realized_pnl: Option<f64>,
+    /// Venue of the YES leg, whose outcome it settles on
+    #[serde(default)]
+    pub yes_venue: Option<String>,
+    /// Estimated taker fees on `size`
+    #[serde(default)]
+    pub fees: f64,
+    /// Live leg orders, priced from their fills
+    #[serde(default)]
+    pub yes_order: Option<LegFills>,
+    #[serde(default)]
+    pub no_order: Option<LegFills>,
+}
+
+/// Fills of one live leg so far.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LegFills {
+    pub order_id: String,
+    pub size: f64,
+    /// Price times size, summed over fills
+    pub cost: f64,
+}
+
+impl LegFills {
+    fn new(order_id: &str) -> Self {
+        Self { order_id: order_id.to_string(), ..Default::default() }
+    }
+
+    /// Size-weighted average fill price, once anything filled.
+    fn price(&self) -> Option<f64> {
+        (self.size > 0.0).then(|| self.cost / self.size)
+    }
+}
+
+impl CaptureRecord {
+    /// Edge per contract at detection.
+    pub fn detected_edge(&self) -> f64 {
+        1.0 - self.detected_yes - self.detected_no
+    }
+
+    /// Edge per contract at the fill prices.
+    pub fn executed_edge(&self) -> Option<f64> {
+        Some(1.0 - self.executed_yes? - self.executed_no?)
+    }
+
+    /// Edge lost between detection and fill, per contract.
+    pub fn slippage(&self) -> Option<f64> {
+        Some(self.detected_edge() - self.executed_edge()?)
+    }
+
+    /// Realized P&L as a fraction of the detected dollar edge.
+    pub fn capture_ratio(&self) -> Option<f64> {
+        let expected = self.detected_edge() * self.size;
+        if expected <= 0.0 {
+            return None;
+        }
+        Some(self.realized_pnl? / expected)
+    }
+}
+
+/// Aggregate capture statistics for a group of records.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptureStats {
+    pub detected: usize,
+    pub executed: usize,
+    pub settled: usize,
+    pub avg_detected_edge: f64,
+    /// Average over executed records
+    pub avg_slippage: f64,
+    /// Total realized / total detected dollar edge, over settled records
+    pub capture_ratio: f64,
+}
+
+impl CaptureStats {
+    fn from_records(records: &[&CaptureRecord]) -> Self {
+        let detected = records.len();
+        let executed: Vec<f64> = records.iter().filter_map(|r| r.slippage()).collect();
+        let settled: Vec<&&CaptureRecord> = records.iter().filter(|r| r.realized_pnl.is_some()).collect();
+
+        let expected: f64 = settled.iter().map(|r| r.detected_edge() * r.size).sum();
+        let realized: f64 = settled.iter().filter_map(|r| r.realized_pnl).sum();
+
+        Self {
+            detected,
+            executed: executed.len(),
+            settled: settled.len(),
+            avg_detected_edge: mean(records.iter().map(|r| r.detected_edge())),
+            avg_slippage: mean(executed.iter().copied()),
+            capture_ratio: if expected > 0.0 { realized / expected } else { 0.0 },
+        }
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, n) = values.fold((0.0, 0usize), |(s, n), v| (s + v, n + 1));
+    if n == 0 { 0.0 } else { sum / n as f64 }
+}
+
+/// Persistent log of capture records.
+pub struct CaptureLog {
+    records: Vec<CaptureRecord>,
+    file_path: Option<String>,
+}
+
+impl CaptureLog {
+    /// In-memory log.
+    pub fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            file_path: None,
+        }
+    }
+
+    /// Load from file, creating an empty log if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self {
+            records: state::load(file_path),
+            file_path: Some(file_path.to_string()),
+        }
+    }
+
+    /// Record an opportunity being acted upon. Returns its ID.
+    pub fn record_detection(&mut self, opportunity: &Opportunity, mode: &str, size: f64) -> String {
+        let id = self.record(
+            &opportunity.market,
+            mode,
+            opportunity.detected_at,
+            (opportunity.yes_price, opportunity.no_price),
+            size,
+        );
+        if let Some(record) = self.records.iter_mut().find(|r| r.id == id) {
+            record.yes_venue = Some(opportunity.yes_venue.to_string());
+            record.fees = opportunity.fee * size;
+            self.save();
+        }
+        id
+    }
+
+    /// Record a detection by its YES/NO leg prices. Returns its ID.
+    pub fn record(&mut self, market: &str, mode: &str, detected_at: DateTime<Utc>, detected: (f64, f64), size: f64) -> String {
+        let id = Uuid::new_v4().to_string();
+        self.records.push(CaptureRecord {
+            id: id.clone(),
+            market: market.to_string(),
+            mode: mode.to_string(),
+            detected_at,
+            detected_yes: detected.0,
+            detected_no: detected.1,
+            size,
+            executed_yes: None,
+            executed_no: None,
+            realized_pnl: None,
+            yes_venue: None,
+            fees: 0.0,
+            yes_order: None,
+            no_order: None,
+        });
+        self.save();
+        id
+    }
+
+    /// Record YES/NO leg fill prices.
+    pub fn record_execution(&mut self, id: &str, yes: f64, no: f64) -> bool {
+        let Some(record) = self.records.iter_mut().find(|r| r.id == id) else {
+            return false;
+        };
+        record.executed_yes = Some(yes);
+        record.executed_no = Some(no);
+        self.save();
+        true
+    }
+
+    /// Attach the live YES/NO leg orders, whose fills then price the
+    /// execution (see [`CaptureLog::record_fill`]).
+    pub fn record_orders(&mut self, id: &str, yes_order_id: &str, no_order_id: &str) -> bool {
+        let Some(record) = self.records.iter_mut().find(|r| r.id == id) else {
+            return false;
+        };
+        record.yes_order = Some(LegFills::new(yes_order_id));
+        record.no_order = Some(LegFills::new(no_order_id));
+        self.save();
+        true
+    }
+
+    /// Price a live leg from one of its fills. False for fills of orders
+    /// not attached to a record.
+    pub fn record_fill(&mut self, fill: &Fill) -> bool {
+        let Some(record) = self.records.iter_mut().find(|r| {
+            [&r.yes_order, &r.no_order].into_iter().flatten().any(|leg| leg.order_id == fill.order_id)
+        }) else {
+            return false;
+        };
+        for leg in [&mut record.yes_order, &mut record.no_order].into_iter().flatten() {
+            if leg.order_id == fill.order_id {
+                leg.size += fill.size;
+                leg.cost += fill.price * fill.size;
+            }
+        }
+        // Executed once both legs have filled
+        if let (Some(yes), Some(no)) = (
+            record.yes_order.as_ref().and_then(LegFills::price),
+            record.no_order.as_ref().and_then(LegFills::price),
+        ) {
+            record.executed_yes = Some(yes);
+            record.executed_no = Some(no);
+        }
+        self.save();
+        true
+    }
+
+    /// Settle every executed record on the pair `resolution` resolved:
+    /// each YES contract pays out on its venue's outcome and each NO
+    /// contract on the other's, less the executed prices and fees. Returns
+    /// the records settled.
+    pub fn record_resolution(&mut self, resolution: &Resolution) -> usize {
+        let settled: Vec<(String, f64)> = self
+            .records
+            .iter()
+            .filter(|r| r.market == resolution.name && r.realized_pnl.is_none())
+            .filter_map(|r| {
+                let (yes_outcome, no_outcome) = match r.yes_venue.as_deref()? {
+                    "polymarket" => (resolution.polymarket, resolution.kalshi),
+                    _ => (resolution.kalshi, resolution.polymarket),
+                };
+                let payout = yes_outcome.yes_payout() + 1.0 - no_outcome.yes_payout();
+                let pnl = r.size * (payout - r.executed_yes? - r.executed_no?) - r.fees;
+                Some((r.id.clone(), pnl))
+            })
+            .collect();
+        for (id, pnl) in &settled {
+            self.record_settlement(id, *pnl);
+        }
+        settled.len()
+    }
+
+    /// Record realized P&L after settlement.
+    pub fn record_settlement(&mut self, id: &str, realized_pnl: f64) -> bool {
+        let Some(record) = self.records.iter_mut().find(|r| r.id == id) else {
+            return false;
+        };
+        record.realized_pnl = Some(realized_pnl);
+        self.save();
+        true
+    }
+
+    /// All records.
+    pub fn records(&self) -> &[CaptureRecord] {
+        &self.records
+    }
+
+    /// Statistics grouped by market.
+    pub fn stats_by_market(&self) -> BTreeMap<String, CaptureStats> {
+        self.group_by(|r| r.market.clone())
+    }
+
+    /// Statistics grouped by execution mode.
+    pub fn stats_by_mode(&self) -> BTreeMap<String, CaptureStats> {
+        self.group_by(|r| r.mode.clone())
+    }
+
+    fn group_by(&self, key: impl Fn(&CaptureRecord) -> String) -> BTreeMap<String, CaptureStats> {
+        let mut groups: BTreeMap<String, Vec<&CaptureRecord>> = BTreeMap::new();
+        for record in &self.records {
+            groups.entry(key(record)).or_default().push(record);
+        }
+        groups
+            .into_iter()
+            .map(|(k, records)| (k, CaptureStats::from_records(&records)))
+            .collect()
+    }
+
+    fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.records) {
+                warn!("Failed to save capture log {}: {}", path, e);
+            }
+        }
+    }
+}
+
+impl Default for CaptureLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opportunity(market: &str, yes: f64, no: f64) -> Opportunity {
+        Opportunity {
+            market: market.to_string(),
+            yes_venue: "kalshi",
+            yes_price: yes,
+            no_venue: "polymarket",
+            no_price: no,
+            fee: 0.0,
+            detected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_slippage_and_capture_ratio() {
+        let mut log = CaptureLog::new();
+        let a = log.record_detection(&opportunity("BTC 5PM", 0.45, 0.50), "live", 10.0);
+        let b = log.record_detection(&opportunity("BTC 5PM", 0.40, 0.50), "live", 10.0);
+        log.record_detection(&opportunity("ETH 5PM", 0.30, 0.33), "dry_run", 10.0);
+
+        // a: filled 1¢ worse on the YES leg, realized $0.30 of $0.50 detected
+        log.record_execution(&a, 0.46, 0.50);
+        log.record_settlement(&a, 0.30);
+        // b: filled at detected prices, realized full $1.00
+        log.record_execution(&b, 0.40, 0.50);
+        log.record_settlement(&b, 1.00);
+
+        let by_market = log.stats_by_market();
+        let btc = &by_market["BTC 5PM"];
+        assert_eq!((btc.detected, btc.executed, btc.settled), (2, 2, 2));
+        assert!((btc.avg_slippage - 0.005).abs() < 1e-9);
+        assert!((btc.capture_ratio - 1.30 / 1.50).abs() < 1e-9);
+
+        let by_mode = log.stats_by_mode();
+        assert_eq!(by_mode["dry_run"].executed, 0);
+        assert!(log.records()[0].capture_ratio().unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_live_legs_priced_by_fills_and_settled_on_resolution() {
+        use crate::monitor::resolution::Outcome;
+
+        let fill = |order_id: &str, price: f64, size: f64| Fill {
+            venue: "kalshi".to_string(),
+            order_id: order_id.to_string(),
+            trade_id: Uuid::new_v4().to_string(),
+            market: "KX-1".to_string(),
+            side: "BUY".to_string(),
+            price,
+            size,
+            filled_at: Utc::now(),
+        };
+        let mut log = CaptureLog::new();
+        let id = log.record_detection(&opportunity("BTC 5PM", 0.45, 0.50), "live", 10.0);
+        log.record_orders(&id, "yes-1", "no-1");
+        assert!(!log.record_fill(&fill("other", 0.45, 10.0)));
+
+        // Priced once both legs fill, at the size-weighted average
+        log.record_fill(&fill("yes-1", 0.45, 5.0));
+        log.record_fill(&fill("yes-1", 0.47, 5.0));
+        assert_eq!(log.records()[0].executed_yes, None);
+        log.record_fill(&fill("no-1", 0.50, 10.0));
+        assert!((log.records()[0].executed_yes.unwrap() - 0.46).abs() < 1e-9);
+
+        let resolution = |polymarket, kalshi| Resolution {
+            name: "BTC 5PM".to_string(),
+            polymarket_id: "111".to_string(),
+            kalshi_ticker: "KX-1".to_string(),
+            polymarket,
+            kalshi,
+            polymarket_seen_at: Utc::now(),
+            kalshi_seen_at: Utc::now(),
+        };
+        // YES on Kalshi, NO on Polymarket: a mismatch pays both legs
+        assert_eq!(log.record_resolution(&resolution(Outcome::No, Outcome::Yes)), 1);
+        assert!((log.records()[0].realized_pnl.unwrap() - 10.0 * (2.0 - 0.96)).abs() < 1e-9);
+        // Settled records aren't settled again
+        assert_eq!(log.record_resolution(&resolution(Outcome::Yes, Outcome::Yes)), 0);
+    }
+}
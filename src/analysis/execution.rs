@@ -0,0 +1,328 @@
+//! Execution quality of our live orders.
+//!
+//! Every live order is recorded with three prices: the decision price that
+//! made us trade (the followed fill, the copied trade), the limit we
+//! submitted and the prices it filled at. Slippage is measured from one to
+//! the next, positive when it cost us (paid more on a buy, got less on a
+//! sell), and aggregated per venue and market family into a scorecard of
+//! where our executions hold up and where edge leaks away.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use crate::arbitrage::market_matcher::family;
+use crate::kalshi::types::KalshiOrderRequest;
+use crate::polymarket::types::Order;
+use crate::utils::{state, Fill};
+
+/// Default execution log location.
+pub const EXECUTIONS_FILE: &str = "data/executions.json";
+
+/// One fill of a recorded order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionFill {
+    pub trade_id: String,
+    pub price: f64,
+    pub size: f64,
+    pub at: DateTime<Utc>,
+}
+
+/// A live order from decision to fills. Prices are of the contract traded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    pub order_id: String,
+    pub venue: String,
+    /// Token ID or ticker
+    pub market: String,
+    /// Side of a Kalshi ticker traded ("yes" or "no"); a Polymarket token
+    /// is one outcome already
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    pub family: String,
+    /// What placed the order, e.g. "copy_trade", "kalshi_follow"
+    pub source: String,
+    /// "BUY" or "SELL"
+    pub side: String,
+    pub decision_price: f64,
+    pub submitted_price: f64,
+    pub size: f64,
+    pub submitted_at: DateTime<Utc>,
+    #[serde(default)]
+    pub fills: Vec<ExecutionFill>,
+}
+
+impl ExecutionRecord {
+    /// +1 for buys, -1 for sells, so price moves against us are positive.
+    fn sign(&self) -> f64 {
+        if self.side.eq_ignore_ascii_case("sell") { -1.0 } else { 1.0 }
+    }
+
+    pub fn filled_size(&self) -> f64 {
+        self.fills.iter().map(|f| f.size).sum()
+    }
+
+    /// Size-weighted fill price.
+    pub fn avg_fill_price(&self) -> Option<f64> {
+        let size = self.filled_size();
+        (size > 0.0).then(|| self.fills.iter().map(|f| f.price * f.size).sum::<f64>() / size)
+    }
+
+    /// Price given up between deciding and submitting, per contract.
+    pub fn submit_slippage(&self) -> f64 {
+        self.sign() * (self.submitted_price - self.decision_price)
+    }
+
+    /// Price given up between submitting and filling, per contract.
+    pub fn fill_slippage(&self) -> Option<f64> {
+        Some(self.sign() * (self.avg_fill_price()? - self.submitted_price))
+    }
+
+    /// Price given up between deciding and filling, per contract.
+    pub fn total_slippage(&self) -> Option<f64> {
+        Some(self.sign() * (self.avg_fill_price()? - self.decision_price))
+    }
+}
+
+/// Execution quality of one venue and market family.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scorecard {
+    pub venue: String,
+    pub family: String,
+    pub orders: usize,
+    /// Orders with at least one fill
+    pub filled_orders: usize,
+    pub submitted_size: f64,
+    pub filled_size: f64,
+    /// Per contract submitted
+    pub avg_submit_slippage: f64,
+    /// Per contract filled
+    pub avg_fill_slippage: f64,
+    pub avg_total_slippage: f64,
+    /// Total slippage from decision to fill, in USD
+    pub slippage_usd: f64,
+}
+
+impl Scorecard {
+    fn from_records(venue: &str, family: &str, records: &[&ExecutionRecord]) -> Self {
+        let submitted_size: f64 = records.iter().map(|r| r.size).sum();
+        let filled: Vec<(&&ExecutionRecord, f64)> =
+            records.iter().map(|r| (r, r.filled_size())).filter(|(_, size)| *size > 0.0).collect();
+        let filled_size: f64 = filled.iter().map(|(_, size)| size).sum();
+        let weighted = |f: &dyn Fn(&ExecutionRecord) -> f64| -> f64 {
+            filled.iter().map(|(r, size)| f(r) * size).sum()
+        };
+        let slippage_usd = weighted(&|r| r.total_slippage().unwrap_or(0.0));
+        let per_filled = |total: f64| if filled_size > 0.0 { total / filled_size } else { 0.0 };
+
+        Self {
+            venue: venue.to_string(),
+            family: family.to_string(),
+            orders: records.len(),
+            filled_orders: filled.len(),
+            submitted_size,
+            filled_size,
+            avg_submit_slippage: if submitted_size > 0.0 {
+                records.iter().map(|r| r.submit_slippage() * r.size).sum::<f64>() / submitted_size
+            } else {
+                0.0
+            },
+            avg_fill_slippage: per_filled(weighted(&|r| r.fill_slippage().unwrap_or(0.0))),
+            avg_total_slippage: per_filled(slippage_usd),
+            slippage_usd,
+        }
+    }
+
+    /// Fraction of the submitted size that filled.
+    pub fn fill_rate(&self) -> f64 {
+        if self.submitted_size > 0.0 { self.filled_size / self.submitted_size } else { 0.0 }
+    }
+
+    /// Whether fills cost more than `max_slippage` per contract on average,
+    /// i.e. the family is worth reviewing before trading it further.
+    pub fn is_costly(&self, max_slippage: f64) -> bool {
+        self.filled_size > 0.0 && self.avg_total_slippage > max_slippage
+    }
+}
+
+/// Persistent log of our live orders and their fills.
+#[derive(Debug, Default)]
+pub struct ExecutionLog {
+    records: Vec<ExecutionRecord>,
+    file_path: Option<String>,
+}
+
+impl ExecutionLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load from file, starting empty if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self { records: state::load(file_path), file_path: Some(file_path.to_string()) }
+    }
+
+    /// Record an order the venue accepted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_submission(
+        &mut self,
+        order_id: &str,
+        venue: &str,
+        market: &str,
+        label: &str,
+        source: &str,
+        side: &str,
+        (decision_price, submitted_price): (f64, f64),
+        size: f64,
+        outcome: Option<&str>,
+    ) {
+        self.records.push(ExecutionRecord {
+            order_id: order_id.to_string(),
+            venue: venue.to_string(),
+            market: market.to_string(),
+            outcome: outcome.map(str::to_lowercase),
+            family: family(label),
+            source: source.to_string(),
+            side: side.to_uppercase(),
+            decision_price,
+            submitted_price,
+            size,
+            submitted_at: Utc::now(),
+            fills: Vec::new(),
+        });
+        self.save();
+    }
+
+    /// Record an accepted Kalshi order decided at `decision_price` of its side.
+    pub fn record_kalshi(&mut self, order_id: &str, order: &KalshiOrderRequest, source: &str, decision_price: f64) {
+        let submitted = order.side_price().unwrap_or(decision_price);
+        self.record_submission(
+            order_id,
+            "kalshi",
+            &order.ticker,
+            &order.ticker,
+            source,
+            &order.action,
+            (decision_price, submitted),
+            order.count as f64,
+            Some(&order.side),
+        );
+    }
+
+    /// Record an accepted Polymarket order on the market named `label`.
+    pub fn record_polymarket(&mut self, order_id: &str, order: &Order, label: &str, source: &str, decision_price: f64) {
+        let side = format!("{:?}", order.side);
+        self.record_submission(
+            order_id,
+            "polymarket",
+            &order.token_id,
+            label,
+            source,
+            &side,
+            (decision_price, order.price),
+            order.size,
+            None,
+        );
+    }
+
+    /// Add a fill to its order. False for orders not recorded here and
+    /// fills already added.
+    pub fn record_fill(&mut self, fill: &Fill) -> bool {
+        let Some(record) = self.records.iter_mut().find(|r| r.order_id == fill.order_id && r.venue == fill.venue) else {
+            return false;
+        };
+        if record.fills.iter().any(|f| f.trade_id == fill.trade_id) {
+            return false;
+        }
+        record.fills.push(ExecutionFill {
+            trade_id: fill.trade_id.clone(),
+            price: fill.price,
+            size: fill.size,
+            at: fill.filled_at,
+        });
+        self.save();
+        true
+    }
+
+    pub fn records(&self) -> &[ExecutionRecord] {
+        &self.records
+    }
+
+    /// Scorecards per venue and market family, cheapest executions first.
+    pub fn leaderboard(&self) -> Vec<Scorecard> {
+        let mut groups: BTreeMap<(&str, &str), Vec<&ExecutionRecord>> = BTreeMap::new();
+        for record in &self.records {
+            groups.entry((&record.venue, &record.family)).or_default().push(record);
+        }
+        let mut cards: Vec<Scorecard> = groups
+            .into_iter()
+            .map(|((venue, family), records)| Scorecard::from_records(venue, family, &records))
+            .collect();
+        cards.sort_by(|a, b| a.avg_total_slippage.total_cmp(&b.avg_total_slippage));
+        cards
+    }
+
+    fn save(&self) {
+        if let Some(path) = &self.file_path {
+            if let Err(e) = state::save(path, &self.records) {
+                warn!("Failed to save execution log: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(order_id: &str, trade_id: &str, price: f64, size: f64) -> Fill {
+        Fill {
+            venue: "kalshi".to_string(),
+            order_id: order_id.to_string(),
+            trade_id: trade_id.to_string(),
+            market: "KXBTCD-26JAN05-T100000 NO".to_string(),
+            side: "BUY".to_string(),
+            price,
+            size,
+            filled_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_slippage_per_venue_and_family() {
+        let mut log = ExecutionLog::new();
+        let ticker = "KXBTCD-26JAN05-T100000";
+        // Decided at 0.42, submitted a cent worse, filled in two parts
+        log.record_submission("a", "kalshi", ticker, ticker, "kalshi_follow", "buy", (0.42, 0.43), 10.0, Some("yes"));
+        // Filled better than decided
+        log.record_submission("b", "kalshi", ticker, ticker, "kalshi_follow", "buy", (0.50, 0.50), 10.0, Some("yes"));
+        // Never filled
+        log.record_submission("c", "polymarket", "token", "ETH Up/Down", "copy_trade", "SELL", (0.60, 0.60), 5.0, None);
+
+        assert!(log.record_fill(&fill("a", "t1", 0.43, 4.0)));
+        assert!(log.record_fill(&fill("a", "t2", 0.46, 6.0)));
+        assert!(!log.record_fill(&fill("a", "t2", 0.46, 6.0)), "replayed fill");
+        assert!(log.record_fill(&fill("b", "t3", 0.49, 10.0)));
+        assert!(!log.record_fill(&fill("z", "t4", 0.49, 10.0)), "not our order");
+
+        let a = &log.records()[0];
+        assert!((a.avg_fill_price().unwrap() - 0.448).abs() < 1e-9);
+        assert!((a.submit_slippage() - 0.01).abs() < 1e-9);
+        assert!((a.fill_slippage().unwrap() - 0.018).abs() < 1e-9);
+        assert!((a.total_slippage().unwrap() - 0.028).abs() < 1e-9);
+
+        let board = log.leaderboard();
+        assert_eq!(board.len(), 2);
+        // The unfilled family has no slippage yet, so it ranks first
+        assert_eq!((board[0].venue.as_str(), board[0].family.as_str()), ("polymarket", "ETH"));
+        assert_eq!(board[0].fill_rate(), 0.0);
+        let kalshi = &board[1];
+        assert_eq!((kalshi.family.as_str(), kalshi.orders, kalshi.filled_orders), ("KXBTCD", 2, 2));
+        // 10 x 0.028 lost on a, 10 x 0.01 saved on b
+        assert!((kalshi.slippage_usd - 0.18).abs() < 1e-9);
+        assert!((kalshi.avg_total_slippage - 0.009).abs() < 1e-9);
+        assert!((kalshi.avg_submit_slippage - 0.005).abs() < 1e-9);
+        assert!(kalshi.is_costly(0.005) && !kalshi.is_costly(0.01));
+    }
+}
@@ -0,0 +1,216 @@
+//! Opportunity heatmap.
+//!
+//! Buckets detected spreads by day of week and hour of day (US Eastern, the
+//! clock the hourly markets run on) per market family, so the bot can be
+//! scheduled for the windows where opportunities cluster.
+
+use chrono::{DateTime, Datelike, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use super::CaptureLog;
+use crate::utils::state;
+
+/// Default heatmap location.
+pub const HEATMAP_FILE: &str = "data/heatmap.json";
+
+const HOURS: usize = 24;
+const DAYS: usize = 7;
+
+/// Day labels in grid order.
+pub const DAY_NAMES: [&str; DAYS] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Spreads seen in one hour-of-week bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cell {
+    pub count: u32,
+    pub total_edge: f64,
+    pub max_edge: f64,
+}
+
+impl Cell {
+    fn add(&mut self, edge: f64) {
+        self.count += 1;
+        self.total_edge += edge;
+        self.max_edge = self.max_edge.max(edge);
+    }
+
+    fn merge(&mut self, other: &Cell) {
+        self.count += other.count;
+        self.total_edge += other.total_edge;
+        self.max_edge = self.max_edge.max(other.max_edge);
+    }
+
+    /// Average edge per detection.
+    pub fn avg_edge(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.total_edge / self.count as f64 }
+    }
+}
+
+/// Day-of-week x hour-of-day grid (Monday first).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grid {
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    fn new() -> Self {
+        Self { cells: vec![Cell::default(); DAYS * HOURS] }
+    }
+
+    /// Bucket for a weekday (0 = Monday) and hour.
+    pub fn cell(&self, day: usize, hour: usize) -> Cell {
+        self.cells.get(day * HOURS + hour).copied().unwrap_or_default()
+    }
+
+    fn cell_mut(&mut self, day: usize, hour: usize) -> &mut Cell {
+        &mut self.cells[day * HOURS + hour]
+    }
+
+    /// Largest detection count in any bucket (for scaling charts).
+    pub fn max_count(&self) -> u32 {
+        self.cells.iter().map(|c| c.count).max().unwrap_or(0)
+    }
+
+    /// The `n` buckets with the most total edge, best first.
+    pub fn best_windows(&self, n: usize) -> Vec<(Weekday, u32, Cell)> {
+        let mut windows: Vec<(Weekday, u32, Cell)> = self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.count > 0)
+            .filter_map(|(i, c)| {
+                let day = Weekday::try_from((i / HOURS) as u8).ok()?;
+                Some((day, (i % HOURS) as u32, *c))
+            })
+            .collect();
+        windows.sort_by(|a, b| b.2.total_edge.total_cmp(&a.2.total_edge));
+        windows.truncate(n);
+        windows
+    }
+}
+
+/// Market family for grouping, e.g. "BTC Up/Down 5PM ET" -> "BTC".
+pub fn market_family(market: &str) -> String {
+    market.split_whitespace().next().unwrap_or("").to_uppercase()
+}
+
+/// Persistent per-family opportunity heatmap.
+pub struct Heatmap {
+    families: BTreeMap<String, Grid>,
+    file_path: Option<String>,
+}
+
+impl Heatmap {
+    /// In-memory heatmap.
+    pub fn new() -> Self {
+        Self {
+            families: BTreeMap::new(),
+            file_path: None,
+        }
+    }
+
+    /// Load from file, creating an empty heatmap if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self {
+            families: state::load(file_path),
+            file_path: Some(file_path.to_string()),
+        }
+    }
+
+    /// Build from the detections in a capture log.
+    pub fn from_capture(log: &CaptureLog) -> Self {
+        let mut heatmap = Self::new();
+        for record in log.records() {
+            heatmap.record(&record.market, record.detected_at, record.detected_edge());
+        }
+        heatmap
+    }
+
+    /// Add one detected spread.
+    pub fn record(&mut self, market: &str, detected_at: DateTime<Utc>, edge: f64) {
+        let et = detected_at.with_timezone(&New_York);
+        let day = et.weekday().num_days_from_monday() as usize;
+        let hour = et.hour() as usize;
+        self.families
+            .entry(market_family(market))
+            .or_insert_with(Grid::new)
+            .cell_mut(day, hour)
+            .add(edge);
+    }
+
+    /// Known market families.
+    pub fn families(&self) -> Vec<&str> {
+        self.families.keys().map(String::as_str).collect()
+    }
+
+    /// Grid for one family.
+    pub fn grid(&self, family: &str) -> Option<&Grid> {
+        self.families.get(family)
+    }
+
+    /// All families combined.
+    pub fn combined(&self) -> Grid {
+        let mut combined = Grid::new();
+        for grid in self.families.values() {
+            for (total, cell) in combined.cells.iter_mut().zip(&grid.cells) {
+                total.merge(cell);
+            }
+        }
+        combined
+    }
+
+    /// Detections recorded across all families.
+    pub fn total_count(&self) -> u32 {
+        self.families.values().flat_map(|g| &g.cells).map(|c| c.count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.families.is_empty()
+    }
+
+    /// Persist to the file it was loaded from, if any.
+    pub fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.families) {
+                warn!("Failed to save heatmap {}: {}", path, e);
+            }
+        }
+    }
+}
+
+impl Default for Heatmap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_buckets_by_eastern_hour_and_family() {
+        let mut heatmap = Heatmap::new();
+        // Monday 2026-01-05 22:00 UTC = 17:00 ET
+        let at = Utc.with_ymd_and_hms(2026, 1, 5, 22, 0, 0).unwrap();
+        heatmap.record("BTC Up/Down 5PM ET", at, 0.03);
+        heatmap.record("BTC Up/Down 5PM ET", at, 0.05);
+        heatmap.record("ETH Up/Down 5PM ET", at + chrono::Duration::hours(1), 0.02);
+
+        assert_eq!(heatmap.families(), vec!["BTC", "ETH"]);
+        let btc = heatmap.grid("BTC").unwrap().cell(0, 17);
+        assert_eq!(btc.count, 2);
+        assert!((btc.avg_edge() - 0.04).abs() < 1e-9);
+        assert_eq!(btc.max_edge, 0.05);
+
+        let combined = heatmap.combined();
+        assert_eq!(combined.max_count(), 2);
+        let best = combined.best_windows(5);
+        assert_eq!(best.len(), 2);
+        assert_eq!((best[0].0, best[0].1), (Weekday::Mon, 17));
+        assert_eq!((best[1].0, best[1].1), (Weekday::Mon, 18));
+    }
+}
@@ -0,0 +1,312 @@
+//! Kalshi strike ladders.
+//!
+//! A Kalshi event holds every strike for one expiry ("above $94,000",
+//! "$94,000 to $94,499.99", ...). Read together the strikes give the
+//! market's implied distribution of the underlying at expiry, which prices
+//! Polymarket's coarser "above $X" strikes by interpolating between the
+//! adjacent Kalshi strikes. The bot writes the ladders of the events it
+//! trades to [`LADDERS_FILE`] for the TUI.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::kalshi::types::{KalshiEvent, KalshiMarket};
+use crate::kalshi::KalshiClient;
+use crate::utils::state;
+
+/// Latest ladders, written by the running bot.
+pub const LADDERS_FILE: &str = "data/ladders.json";
+
+/// How a Kalshi strike pays out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrikeType {
+    /// YES if the underlying settles above `floor`
+    Above,
+    /// YES if it settles below `cap`
+    Below,
+    /// YES if it settles from `floor` up to `cap`
+    Between,
+}
+
+impl StrikeType {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "greater" | "greater_or_equal" => Some(StrikeType::Above),
+            "less" | "less_or_equal" => Some(StrikeType::Below),
+            "between" => Some(StrikeType::Between),
+            _ => None,
+        }
+    }
+}
+
+/// One strike of the ladder (prices 0.0-1.0).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rung {
+    pub ticker: String,
+    pub strike_type: StrikeType,
+    pub floor: Option<f64>,
+    pub cap: Option<f64>,
+    pub yes_bid: Option<f64>,
+    pub yes_ask: Option<f64>,
+}
+
+impl Rung {
+    /// None for markets without a usable strike.
+    pub fn from_market(market: &KalshiMarket) -> Option<Self> {
+        let strike_type = StrikeType::parse(market.strike_type.as_deref()?)?;
+        let rung = Self {
+            ticker: market.ticker.clone(),
+            strike_type,
+            floor: market.floor_strike,
+            cap: market.cap_strike,
+            yes_bid: market.yes_bid.map(|c| c as f64 / 100.0),
+            yes_ask: market.yes_ask.map(|c| c as f64 / 100.0),
+        };
+        rung.strike().map(|_| rung)
+    }
+
+    /// Strike the rung is sorted and shown by.
+    pub fn strike(&self) -> Option<f64> {
+        match self.strike_type {
+            StrikeType::Above | StrikeType::Between => self.floor,
+            StrikeType::Below => self.cap,
+        }
+    }
+
+    /// YES mid, or whichever side is quoted.
+    pub fn mid(&self) -> Option<f64> {
+        crate::monitor::marker::mid_price(self.yes_bid, self.yes_ask)
+    }
+}
+
+/// Implied probability that the underlying settles at or below `strike`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CdfPoint {
+    pub strike: f64,
+    pub below: f64,
+}
+
+/// Every strike of one Kalshi event, lowest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrikeLadder {
+    pub event_ticker: String,
+    pub title: String,
+    pub rungs: Vec<Rung>,
+}
+
+impl StrikeLadder {
+    pub fn from_event(event: &KalshiEvent) -> Self {
+        let mut rungs: Vec<Rung> = event.markets.iter().filter_map(Rung::from_market).collect();
+        rungs.sort_by(|a, b| a.strike().unwrap_or(0.0).total_cmp(&b.strike().unwrap_or(0.0)));
+        Self { event_ticker: event.ticker.clone(), title: event.title.clone(), rungs }
+    }
+
+    /// Fetch the ladder of `event_ticker` in one call.
+    pub async fn fetch(kalshi: &KalshiClient, event_ticker: &str) -> Result<Self> {
+        Ok(Self::from_event(&kalshi.get_event(event_ticker).await?))
+    }
+
+    /// Implied CDF of the underlying from the rung mids, by strike.
+    ///
+    /// "Above" and "below" strikes give a point each; range buckets add up
+    /// from the bottom, starting from the lowest "below" tail. Noisy mids
+    /// are evened out so the CDF never decreases.
+    pub fn cdf(&self) -> Vec<CdfPoint> {
+        let mut points = Vec::new();
+        let mut cumulative = self
+            .rungs
+            .iter()
+            .filter(|r| r.strike_type == StrikeType::Below)
+            .min_by(|a, b| a.strike().unwrap_or(0.0).total_cmp(&b.strike().unwrap_or(0.0)))
+            .and_then(Rung::mid)
+            .unwrap_or(0.0);
+        for rung in &self.rungs {
+            let (Some(mid), Some(strike)) = (rung.mid(), rung.strike()) else {
+                continue;
+            };
+            match rung.strike_type {
+                StrikeType::Above => points.push(CdfPoint { strike, below: 1.0 - mid }),
+                StrikeType::Below => points.push(CdfPoint { strike, below: mid }),
+                StrikeType::Between => {
+                    cumulative += mid;
+                    if let Some(cap) = rung.cap {
+                        points.push(CdfPoint { strike: cap, below: cumulative });
+                    }
+                }
+            }
+        }
+        points.sort_by(|a, b| a.strike.total_cmp(&b.strike));
+        let mut floor = 0.0_f64;
+        for point in &mut points {
+            floor = floor.max(point.below.clamp(0.0, 1.0));
+            point.below = floor;
+        }
+        points
+    }
+
+    /// Fair YES price of "above `strike`", interpolated between the
+    /// adjacent strikes. None outside the ladder.
+    pub fn prob_above(&self, strike: f64) -> Option<f64> {
+        let cdf = self.cdf();
+        let upper = cdf.iter().position(|p| p.strike >= strike)?;
+        let hi = cdf[upper];
+        if hi.strike == strike || upper == 0 {
+            return (hi.strike == strike).then_some(1.0 - hi.below);
+        }
+        let lo = cdf[upper - 1];
+        let t = (strike - lo.strike) / (hi.strike - lo.strike);
+        Some(1.0 - (lo.below + t * (hi.below - lo.below)))
+    }
+}
+
+/// A Polymarket strike priced off the ladder.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FairValue {
+    /// Matched pair name
+    pub market: String,
+    pub strike: f64,
+    /// Interpolated YES probability
+    pub fair: f64,
+    /// Polymarket YES mid when the value was computed
+    pub poly_mid: Option<f64>,
+}
+
+impl FairValue {
+    /// Polymarket mid above fair value (positive = rich).
+    pub fn premium(&self) -> Option<f64> {
+        self.poly_mid.map(|mid| mid - self.fair)
+    }
+}
+
+/// A ladder as the bot last read it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LadderSnapshot {
+    pub at: DateTime<Utc>,
+    pub ladder: StrikeLadder,
+    pub cdf: Vec<CdfPoint>,
+    pub fair_values: Vec<FairValue>,
+}
+
+impl LadderSnapshot {
+    /// Price every pair in `matches` on this ladder's event whose name
+    /// carries a dollar strike, with Polymarket mids from `poly_mid`.
+    pub fn new(
+        ladder: StrikeLadder,
+        matches: &[MatchedMarket],
+        poly_mid: impl Fn(&str) -> Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let fair_values = matches
+            .iter()
+            .filter(|m| event_ticker(&m.kalshi.native) == ladder.event_ticker)
+            .filter_map(|m| {
+                let strike = parse_strike(&m.name)?;
+                let fair = ladder.prob_above(strike)?;
+                Some(FairValue { market: m.name.clone(), strike, fair, poly_mid: poly_mid(&m.name) })
+            })
+            .collect();
+        Self { at: now, cdf: ladder.cdf(), ladder, fair_values }
+    }
+}
+
+/// Event a Kalshi market ticker belongs to, e.g. "KXBTCD-26JAN0517" for
+/// "KXBTCD-26JAN0517-T94249.99".
+pub fn event_ticker(ticker: &str) -> &str {
+    ticker.rsplit_once('-').map_or(ticker, |(event, _)| event)
+}
+
+/// Dollar strike in a question like "Will Bitcoin be above $96,500 on
+/// January 5?" ("$96.5k" works too).
+pub fn parse_strike(question: &str) -> Option<f64> {
+    let rest = &question[question.find('$')? + 1..];
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit() || *c == ',' || *c == '.').collect();
+    let value: f64 = digits.replace(',', "").trim_end_matches('.').parse().ok()?;
+    let multiplier = match rest[digits.len()..].chars().next() {
+        Some('k' | 'K') => 1_000.0,
+        _ => 1.0,
+    };
+    Some(value * multiplier)
+}
+
+pub fn load_ladders(path: &str) -> Vec<LadderSnapshot> {
+    state::load(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn market(ticker: &str, strike_type: &str, floor: Option<f64>, cap: Option<f64>, bid: i32, ask: i32) -> KalshiMarket {
+        KalshiMarket {
+            ticker: ticker.to_string(),
+            strike_type: Some(strike_type.to_string()),
+            floor_strike: floor,
+            cap_strike: cap,
+            yes_bid: Some(bid),
+            yes_ask: Some(ask),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cdf_and_interpolated_fair_value() {
+        let event = KalshiEvent {
+            ticker: "KXBTCD-26JAN0517".to_string(),
+            title: "Bitcoin price on Jan 5".to_string(),
+            markets: vec![
+                market("T96000", "greater", Some(96000.0), None, 29, 31),
+                market("T94000", "greater", Some(94000.0), None, 69, 71),
+                market("T95000", "greater", Some(95000.0), None, 49, 51),
+                // Noisy quote above the 95k strike's
+                market("T95500", "greater", Some(95500.0), None, 51, 53),
+                KalshiMarket { ticker: "NOSTRIKE".to_string(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let ladder = StrikeLadder::from_event(&event);
+        assert_eq!(ladder.rungs.len(), 4);
+        assert_eq!(ladder.rungs[0].ticker, "T94000");
+
+        let cdf = ladder.cdf();
+        assert!((cdf[0].below - 0.30).abs() < 1e-9);
+        // The 95.5k point is held at the 95k level instead of dipping
+        assert!((cdf[2].below - cdf[1].below).abs() < 1e-9);
+        assert!(cdf.windows(2).all(|w| w[0].below <= w[1].below));
+
+        assert!((ladder.prob_above(95000.0).unwrap() - 0.50).abs() < 1e-9);
+        // Halfway between 94k (0.70) and 95k (0.50)
+        assert!((ladder.prob_above(94500.0).unwrap() - 0.60).abs() < 1e-9);
+        assert_eq!(ladder.prob_above(93000.0), None);
+        assert_eq!(ladder.prob_above(97000.0), None);
+
+        // Range buckets build the CDF from the bottom
+        let ranges = StrikeLadder::from_event(&KalshiEvent {
+            markets: vec![
+                market("B1", "less", None, Some(94000.0), 19, 21),
+                market("B2", "between", Some(94000.0), Some(95000.0), 29, 31),
+                market("B3", "between", Some(95000.0), Some(96000.0), 29, 31),
+            ],
+            ..event
+        });
+        let below: Vec<f64> = ranges.cdf().iter().map(|p| p.below).collect();
+        assert!(below.iter().zip([0.2, 0.5, 0.8]).all(|(a, b)| (a - b).abs() < 1e-9));
+
+        let pair = |name: &str, ticker: &str| MatchedMarket::new(name, "1", ticker);
+        let matches = [
+            pair("Bitcoin above $94,500 on January 5? (KXBTCD-26JAN0517-T94499.99)", "KXBTCD-26JAN0517-T94499.99"),
+            pair("Bitcoin above $94,500 on January 6? (KXBTCD-26JAN0617-T94499.99)", "KXBTCD-26JAN0617-T94499.99"),
+            pair("Bitcoin Up or Down - 5PM ET (KXBTCD-26JAN0517-T95000)", "KXBTCD-26JAN0517-T95000"),
+        ];
+        let snapshot = LadderSnapshot::new(ladder, &matches, |_| Some(0.64), Utc::now());
+        assert_eq!(snapshot.fair_values.len(), 1);
+        assert!((snapshot.fair_values[0].premium().unwrap() - 0.04).abs() < 1e-9);
+
+        assert_eq!(event_ticker("KXBTCD-26JAN0517-T94249.99"), "KXBTCD-26JAN0517");
+        assert_eq!(parse_strike("Will the price of Bitcoin be above $96,500 on January 5?"), Some(96500.0));
+        assert_eq!(parse_strike("BTC above $96.5k?"), Some(96500.0));
+        assert_eq!(parse_strike("Bitcoin Up or Down - 5PM ET"), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Leaderboard history.
+//!
+//! The refresh task only keeps the latest leaderboard in
+//! [`LEADERBOARD_FILE`](crate::polymarket::data_api::LEADERBOARD_FILE).
+//! Snapshots taken at most once per `LEADERBOARD_SNAPSHOT_SECS` are appended
+//! to [`LEADERBOARD_HISTORY_FILE`], so traders can be scored on how they
+//! hold up over months: one who is on the board every month is a better copy
+//! target than a one-hit wonder with a single lucky month.
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::polymarket::data_api::LeaderboardEntry;
+use crate::utils::state;
+
+/// Leaderboard snapshots, one per line.
+pub const LEADERBOARD_HISTORY_FILE: &str = "data/leaderboard_history.jsonl";
+
+/// Calendar months of history before a single-month trader counts as a
+/// one-hit wonder.
+const ONE_HIT_MIN_MONTHS: usize = 3;
+
+/// The leaderboard at one point in time, best first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaderboardSnapshot {
+    pub taken_at: DateTime<Utc>,
+    /// Leaderboard window, e.g. "monthly"
+    pub period: String,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// Where a trader stood in one snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Appearance {
+    pub taken_at: DateTime<Utc>,
+    /// 1-based
+    pub rank: usize,
+    pub name: String,
+    pub profit_and_loss: f64,
+    pub volume: f64,
+}
+
+/// How consistently a trader has stayed on the leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Consistency {
+    pub address: String,
+    /// Latest name seen
+    pub name: String,
+    /// Snapshots the trader appears in
+    pub appearances: usize,
+    /// Distinct calendar months the trader appears in
+    pub months: usize,
+    /// Distinct calendar months with any snapshot
+    pub months_observed: usize,
+    pub best_rank: usize,
+    pub avg_rank: f64,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+impl Consistency {
+    /// Share of observed months the trader was on the board (0-1).
+    pub fn score(&self) -> f64 {
+        if self.months_observed == 0 {
+            return 0.0;
+        }
+        self.months as f64 / self.months_observed as f64
+    }
+
+    /// On the board in a single month out of a long enough history.
+    pub fn is_one_hit_wonder(&self) -> bool {
+        self.months == 1 && self.months_observed >= ONE_HIT_MIN_MONTHS
+    }
+}
+
+/// Handle on the snapshot history file.
+#[derive(Debug, Clone)]
+pub struct LeaderboardHistory {
+    path: String,
+}
+
+impl LeaderboardHistory {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+
+    /// All snapshots, oldest first. Unreadable lines are skipped.
+    pub fn load(&self) -> Vec<LeaderboardSnapshot> {
+        state::read_lines(&self.path).iter().filter_map(|line| serde_json::from_str(line).ok()).collect()
+    }
+
+    /// When the newest snapshot was taken.
+    pub fn last_taken(&self) -> Option<DateTime<Utc>> {
+        self.load().last().map(|s| s.taken_at)
+    }
+
+    /// Append `snapshot`.
+    pub fn record(&self, snapshot: &LeaderboardSnapshot) -> Result<()> {
+        state::append_line(&self.path, &serde_json::to_string(snapshot)?)
+    }
+
+    /// Append a snapshot of `entries` unless one was taken less than
+    /// `min_gap_secs` before `now`. Returns whether one was recorded.
+    pub fn record_if_due(&self, period: &str, entries: &[LeaderboardEntry], min_gap_secs: u64, now: DateTime<Utc>) -> Result<bool> {
+        if let Some(last) = self.last_taken() {
+            if (now - last).num_seconds() < min_gap_secs as i64 {
+                return Ok(false);
+            }
+        }
+        let snapshot = LeaderboardSnapshot { taken_at: now, period: period.to_string(), entries: entries.to_vec() };
+        self.record(&snapshot)?;
+        Ok(true)
+    }
+}
+
+/// Every snapshot `address` appears in, oldest first.
+pub fn appearances(snapshots: &[LeaderboardSnapshot], address: &str) -> Vec<Appearance> {
+    snapshots
+        .iter()
+        .filter_map(|snapshot| {
+            let (i, entry) = snapshot
+                .entries
+                .iter()
+                .enumerate()
+                .find(|(_, e)| e.proxy_address.eq_ignore_ascii_case(address))?;
+            Some(Appearance {
+                taken_at: snapshot.taken_at,
+                rank: i + 1,
+                name: entry.name.clone(),
+                profit_and_loss: entry.profit_and_loss,
+                volume: entry.volume,
+            })
+        })
+        .collect()
+}
+
+/// Consistency of every trader in `snapshots`, most consistent first
+/// (ties broken by appearances, then average rank).
+pub fn consistency(snapshots: &[LeaderboardSnapshot]) -> Vec<Consistency> {
+    let month = |at: &DateTime<Utc>| (at.year(), at.month());
+    let months_observed = snapshots.iter().map(|s| month(&s.taken_at)).collect::<BTreeSet<_>>().len();
+
+    let mut by_trader: BTreeMap<String, Vec<(&LeaderboardSnapshot, usize, &LeaderboardEntry)>> = BTreeMap::new();
+    for snapshot in snapshots {
+        for (i, entry) in snapshot.entries.iter().enumerate() {
+            if entry.proxy_address.is_empty() {
+                continue;
+            }
+            by_trader.entry(entry.proxy_address.to_lowercase()).or_default().push((snapshot, i + 1, entry));
+        }
+    }
+
+    let mut traders: Vec<Consistency> = by_trader
+        .into_iter()
+        .map(|(address, seen)| {
+            let ranks: Vec<usize> = seen.iter().map(|(_, rank, _)| *rank).collect();
+            let (first, last) = (seen[0].0, seen[seen.len() - 1].0);
+            Consistency {
+                name: seen[seen.len() - 1].2.name.clone(),
+                appearances: seen.len(),
+                months: seen.iter().map(|(s, _, _)| month(&s.taken_at)).collect::<BTreeSet<_>>().len(),
+                months_observed,
+                best_rank: ranks.iter().copied().min().unwrap_or(0),
+                avg_rank: ranks.iter().sum::<usize>() as f64 / ranks.len() as f64,
+                first_seen: first.taken_at,
+                last_seen: last.taken_at,
+                address,
+            }
+        })
+        .collect();
+    traders.sort_by(|a, b| {
+        b.score()
+            .total_cmp(&a.score())
+            .then(b.appearances.cmp(&a.appearances))
+            .then(a.avg_rank.total_cmp(&b.avg_rank))
+    });
+    traders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(address: &str, pnl: f64) -> LeaderboardEntry {
+        serde_json::from_value(serde_json::json!({"name": address.to_uppercase(), "proxyAddress": address, "profitAndLoss": pnl}))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_snapshots_score_traders_across_months() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-leaderboard-{}.jsonl", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let history = LeaderboardHistory::new(&path);
+        let day = |m: u32, d: u32| Utc.with_ymd_and_hms(2026, m, d, 0, 0, 0).unwrap();
+
+        // At most one snapshot per gap
+        assert!(history.record_if_due("monthly", &[entry("0xaa", 900.0), entry("0xbb", 500.0)], 86_400, day(7, 1)).unwrap());
+        assert!(!history.record_if_due("monthly", &[entry("0xbb", 700.0)], 86_400, day(7, 1) + chrono::Duration::hours(3)).unwrap());
+        history.record_if_due("monthly", &[entry("0xcc", 800.0), entry("0xAA", 600.0)], 86_400, day(8, 1)).unwrap();
+        history.record_if_due("monthly", &[entry("0xaa", 950.0)], 86_400, day(9, 1)).unwrap();
+
+        let snapshots = history.load();
+        assert_eq!(snapshots.len(), 3);
+        let seen = appearances(&snapshots, "0xaa");
+        assert_eq!(seen.iter().map(|a| a.rank).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(seen[1].profit_and_loss, 600.0);
+
+        // The steady trader ranks first; single-month traders are one-hit wonders
+        let scores = consistency(&snapshots);
+        assert_eq!(scores.iter().map(|c| c.address.as_str()).collect::<Vec<_>>(), vec!["0xaa", "0xcc", "0xbb"]);
+        assert_eq!((scores[0].months, scores[0].score(), scores[0].best_rank), (3, 1.0, 1));
+        assert!((scores[0].avg_rank - 4.0 / 3.0).abs() < 1e-9);
+        assert!(!scores[0].is_one_hit_wonder());
+        assert!(scores[1].is_one_hit_wonder() && scores[2].is_one_hit_wonder());
+        let _ = std::fs::remove_file(&path);
+    }
+}
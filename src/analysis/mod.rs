@@ -1,5 +1,17 @@
 //! Analysis module for studying top traders and strategies.
 
+pub mod attribution;
+pub mod capture;
+pub mod execution;
+pub mod heatmap;
+pub mod ladder;
+pub mod leaderboard_history;
+pub mod rewards;
+pub mod screener;
 pub mod trader_analyzer;
 
+pub use attribution::Attribution;
+pub use capture::{CaptureLog, CaptureStats};
+pub use heatmap::Heatmap;
+pub use leaderboard_history::{LeaderboardHistory, LEADERBOARD_HISTORY_FILE};
 pub use trader_analyzer::TraderAnalyzer;
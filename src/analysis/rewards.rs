@@ -0,0 +1,260 @@
+//! Polymarket liquidity rewards tracker.
+//!
+//! Polymarket pays a daily pool per rewarded market to makers whose resting
+//! orders sit within `max_spread` of the midpoint and are at least
+//! `min_size` shares. An order scores `((v - s) / v)^2 * size`, `s` being
+//! its distance from the mid and `v` the max spread. A maker's bid and ask
+//! scores combine as the smaller of the two, or a third of the larger if
+//! that is more, and only two-sided quotes count once the mid leaves
+//! 0.10-0.90.
+//!
+//! Each pass scores our resting orders against the live book and accrues
+//! the time they qualified and our estimated share of the pool. Everyone
+//! else in the book is scored as one two-sided maker, so the share is an
+//! estimate, not what Polymarket will pay.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use crate::paper_trading::book_fill::{Depth, Level};
+use crate::utils::{state, OpenOrder};
+
+/// Default rewards tracker location.
+pub const REWARDS_FILE: &str = "data/rewards.json";
+/// Longest gap between passes that still counts as continuous quoting.
+const MAX_GAP_SECS: i64 = 300;
+/// Divisor on a one-sided maker's score.
+const SINGLE_SIDED_DIVISOR: f64 = 3.0;
+/// Outside this band of mids only two-sided quotes score.
+const ONE_SIDED_BAND: (f64, f64) = (0.10, 0.90);
+
+/// Reward program of one market.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RewardTerms {
+    /// Shares an order needs to score
+    pub min_size: f64,
+    /// Farthest an order may sit from the mid, in price
+    pub max_spread: f64,
+    /// USD paid out per day across all makers
+    pub daily_rate: f64,
+}
+
+impl RewardTerms {
+    /// Score of one order `distance` from the mid.
+    pub fn order_score(&self, distance: f64, size: f64) -> f64 {
+        if self.max_spread <= 0.0 || size < self.min_size || distance > self.max_spread {
+            return 0.0;
+        }
+        ((self.max_spread - distance) / self.max_spread).powi(2) * size
+    }
+
+    /// Score of one side's levels around `mid`.
+    pub fn side_score(&self, mid: f64, levels: &[Level]) -> f64 {
+        levels.iter().map(|l| self.order_score((l.price - mid).abs(), l.size)).sum()
+    }
+
+    /// A maker's combined score from its bid and ask scores.
+    pub fn combine(&self, mid: f64, bid: f64, ask: f64) -> f64 {
+        let two_sided = bid.min(ask);
+        if mid < ONE_SIDED_BAND.0 || mid > ONE_SIDED_BAND.1 {
+            two_sided
+        } else {
+            two_sided.max(bid.max(ask) / SINGLE_SIDED_DIVISOR)
+        }
+    }
+}
+
+/// What our quotes in one market have earned.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketRewards {
+    pub label: String,
+    pub terms: Option<RewardTerms>,
+    /// Time since we first quoted the market
+    pub tracked_secs: f64,
+    /// Time we had orders resting
+    pub quoted_secs: f64,
+    /// Time at least one order scored
+    pub qualifying_secs: f64,
+    /// Qualifying shares resting, integrated over time
+    pub qualifying_size_secs: f64,
+    /// Estimated rewards accrued, in USD
+    pub earned_usd: f64,
+    /// Our estimated share of the pool at the last pass
+    pub last_share: f64,
+    pub last_at: Option<DateTime<Utc>>,
+}
+
+impl MarketRewards {
+    /// Fraction of the tracked time our quotes scored.
+    pub fn uptime(&self) -> f64 {
+        if self.tracked_secs > 0.0 { self.qualifying_secs / self.tracked_secs } else { 0.0 }
+    }
+
+    /// Average qualifying shares while qualifying.
+    pub fn avg_qualifying_size(&self) -> f64 {
+        if self.qualifying_secs > 0.0 { self.qualifying_size_secs / self.qualifying_secs } else { 0.0 }
+    }
+
+    /// USD per day at the last pass's share.
+    pub fn daily_rate(&self) -> f64 {
+        self.terms.map_or(0.0, |t| t.daily_rate * self.last_share)
+    }
+}
+
+/// Reward accrual per Polymarket token, persisted between runs.
+#[derive(Debug, Default)]
+pub struct RewardsTracker {
+    markets: BTreeMap<String, MarketRewards>,
+    file_path: Option<String>,
+}
+
+impl RewardsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load from file, starting empty if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self { markets: state::load(file_path), file_path: Some(file_path.to_string()) }
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = &self.file_path {
+            if let Err(e) = state::save(path, &self.markets) {
+                warn!("Failed to save rewards tracker: {}", e);
+            }
+        }
+    }
+
+    /// Markets by token ID.
+    pub fn markets(&self) -> &BTreeMap<String, MarketRewards> {
+        &self.markets
+    }
+
+    pub fn get(&self, token: &str) -> Option<&MarketRewards> {
+        self.markets.get(token)
+    }
+
+    /// Tokens tracked so far.
+    pub fn tokens(&self) -> Vec<String> {
+        self.markets.keys().cloned().collect()
+    }
+
+    /// Score `ours` (our resting orders on `token`) against `book` at `at`
+    /// and accrue the time since the last pass. No orders and no terms
+    /// (an unrewarded market) still count towards the tracked time.
+    pub fn observe(
+        &mut self,
+        token: &str,
+        label: &str,
+        terms: Option<RewardTerms>,
+        book: Option<&Depth>,
+        ours: &[&OpenOrder],
+        at: DateTime<Utc>,
+    ) {
+        if ours.is_empty() && !self.markets.contains_key(token) {
+            return;
+        }
+        let entry = self.markets.entry(token.to_string()).or_default();
+        entry.label = label.to_string();
+        entry.terms = terms.or(entry.terms);
+        let elapsed = entry
+            .last_at
+            .map_or(0.0, |last| (at - last).min(Duration::seconds(MAX_GAP_SECS)).num_milliseconds().max(0) as f64 / 1000.0);
+        entry.last_at = Some(at);
+        entry.tracked_secs += elapsed;
+        entry.last_share = 0.0;
+        if ours.is_empty() {
+            return;
+        }
+        entry.quoted_secs += elapsed;
+
+        let mid = book.and_then(|b| Some((b.best_bid()? + b.best_ask()?) / 2.0));
+        let (Some(terms), Some(mid), Some(book)) = (entry.terms, mid, book) else { return };
+        let side = |buy: bool| -> Vec<Level> {
+            ours.iter()
+                .filter(|o| o.side.eq_ignore_ascii_case("BUY") == buy)
+                .map(|o| Level { price: o.price, size: o.size })
+                .collect()
+        };
+        let (our_bids, our_asks) = (side(true), side(false));
+        let ours_score = terms.combine(mid, terms.side_score(mid, &our_bids), terms.side_score(mid, &our_asks));
+        if ours_score <= 0.0 {
+            return;
+        }
+        // The book includes our own orders
+        let book_score = terms.combine(mid, terms.side_score(mid, &book.bids), terms.side_score(mid, &book.asks));
+        let share = ours_score / book_score.max(ours_score);
+        let qualifying: f64 = our_bids
+            .iter()
+            .chain(&our_asks)
+            .filter(|l| terms.order_score((l.price - mid).abs(), l.size) > 0.0)
+            .map(|l| l.size)
+            .sum();
+
+        entry.qualifying_secs += elapsed;
+        entry.qualifying_size_secs += qualifying * elapsed;
+        entry.earned_usd += terms.daily_rate * share * elapsed / 86_400.0;
+        entry.last_share = share;
+    }
+
+    /// Estimated rewards accrued across markets.
+    pub fn total_earned(&self) -> f64 {
+        self.markets.values().map(|m| m.earned_usd).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(side: &str, price: f64, size: f64) -> OpenOrder {
+        OpenOrder {
+            order_id: format!("{}-{}", side, price),
+            venue: "polymarket".to_string(),
+            market: "token".to_string(),
+            side: side.to_string(),
+            price,
+            size,
+            placed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_rewards_accrue_while_quotes_qualify() {
+        let terms = RewardTerms { min_size: 50.0, max_spread: 0.03, daily_rate: 86.4 };
+        assert!((terms.order_score(0.01, 100.0) - 100.0 * 4.0 / 9.0).abs() < 1e-9);
+        assert_eq!(terms.order_score(0.04, 100.0), 0.0);
+        assert_eq!(terms.order_score(0.0, 10.0), 0.0);
+        // One-sided counts a third inside the band, nothing outside it
+        assert!((terms.combine(0.5, 90.0, 0.0) - 30.0).abs() < 1e-9);
+        assert_eq!(terms.combine(0.95, 90.0, 0.0), 0.0);
+
+        let start = Utc::now();
+        let book = Depth::new(
+            vec![Level { price: 0.49, size: 100.0 }, Level { price: 0.48, size: 300.0 }],
+            vec![Level { price: 0.51, size: 100.0 }],
+        );
+        let (bid, ask) = (order("BUY", 0.49, 100.0), order("SELL", 0.51, 100.0));
+        let mut tracker = RewardsTracker::new();
+        tracker.observe("token", "BTC 5PM", Some(terms), Some(&book), &[], start);
+        assert!(tracker.get("token").is_none());
+
+        tracker.observe("token", "BTC 5PM", Some(terms), Some(&book), &[&bid, &ask], start);
+        tracker.observe("token", "BTC 5PM", Some(terms), Some(&book), &[&bid, &ask], start + Duration::seconds(100));
+        let market = tracker.get("token").unwrap();
+        // Our ask is the whole ask side, so the book scores no higher than us
+        assert!((market.last_share - 1.0).abs() < 1e-9);
+        assert!((market.earned_usd - 0.1).abs() < 1e-9);
+        assert_eq!(market.avg_qualifying_size(), 200.0);
+
+        // Without orders the time is tracked but earns nothing
+        tracker.observe("token", "BTC 5PM", None, None, &[], start + Duration::seconds(200));
+        let market = tracker.get("token").unwrap();
+        assert_eq!((market.tracked_secs, market.qualifying_secs), (200.0, 100.0));
+        assert!((market.uptime() - 0.5).abs() < 1e-9);
+        assert_eq!(market.daily_rate(), 0.0);
+    }
+}
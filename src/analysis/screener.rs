@@ -0,0 +1,267 @@
+//! Market liquidity screener.
+//!
+//! Ranks open markets on both venues by volume, open interest (Polymarket:
+//! resting liquidity), spread tightness and time to resolution, and
+//! suggests cross-venue pairs worth adding to the matcher.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::arbitrage::MarketMatcher;
+use crate::kalshi::types::KalshiMarket;
+use crate::polymarket::discovery::GammaMarket;
+use crate::utils::{timezone, MarketId};
+
+/// Score weights: volume, open interest, spread, time to resolution.
+const WEIGHTS: [f64; 4] = [0.35, 0.25, 0.25, 0.15];
+/// Spread at which the tightness score reaches zero.
+const WIDE_SPREAD: f64 = 0.10;
+/// Resolution window (hours) that scores best: soon, but not about to close.
+const IDEAL_HOURS: (f64, f64) = (1.0, 72.0);
+/// Title similarity needed to suggest a pair.
+const MIN_SIMILARITY: f64 = 0.3;
+/// Resolution times further apart than this cannot be the same event.
+const MAX_CLOSE_GAP_HOURS: i64 = 24;
+
+/// Words that say nothing about which event a market is on.
+const STOPWORDS: &[&str] = &["will", "the", "what", "who", "which", "and", "for", "before", "after", "than", "above", "below", "price"];
+
+/// An open market, scored.
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    pub venue: &'static str,
+    /// Kalshi ticker or Polymarket YES token ID
+    pub id: String,
+    pub title: String,
+    pub volume: f64,
+    /// Kalshi open interest, Polymarket resting liquidity
+    pub open_interest: f64,
+    /// YES ask - bid, if both sides are quoted
+    pub spread: Option<f64>,
+    pub closes_at: Option<DateTime<Utc>>,
+    pub hours_to_close: Option<f64>,
+    pub score: f64,
+}
+
+/// A suggested cross-venue pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct PairSuggestion {
+    pub kalshi_ticker: String,
+    pub kalshi_title: String,
+    pub polymarket_id: String,
+    pub polymarket_title: String,
+    /// Title word overlap (0-1)
+    pub similarity: f64,
+    /// Mean of both markets' scores
+    pub score: f64,
+}
+
+/// Resolution time; unlabelled times are on the configured clock.
+fn parse_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    timezone::parse_time(value?, timezone::current())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn candidate(
+    venue: &'static str,
+    id: String,
+    title: String,
+    volume: f64,
+    open_interest: f64,
+    bid_ask: (Option<f64>, Option<f64>),
+    closes_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Candidate {
+    let spread = match bid_ask {
+        (Some(bid), Some(ask)) if ask >= bid && bid > 0.0 => Some(ask - bid),
+        _ => None,
+    };
+    Candidate {
+        venue,
+        id,
+        title,
+        volume,
+        open_interest,
+        spread,
+        closes_at,
+        hours_to_close: closes_at.map(|t| (t - now).num_minutes() as f64 / 60.0),
+        score: 0.0,
+    }
+}
+
+/// Kalshi market as a candidate (prices in cents).
+pub fn from_kalshi(market: &KalshiMarket, now: DateTime<Utc>) -> Candidate {
+    let cents = |p: Option<i32>| p.map(|c| c as f64 / 100.0);
+    candidate(
+        "kalshi",
+        market.ticker.clone(),
+        market.title.clone(),
+        market.volume.unwrap_or(0) as f64,
+        market.open_interest.unwrap_or(0) as f64,
+        (cents(market.yes_bid), cents(market.yes_ask)),
+        parse_time(market.close_time.as_deref()),
+        now,
+    )
+}
+
+/// Gamma market as a candidate, or `None` without a tradable token.
+pub fn from_gamma(market: &GammaMarket, now: DateTime<Utc>) -> Option<Candidate> {
+    Some(candidate(
+        "polymarket",
+        market.yes_token_id()?,
+        market.question.clone(),
+        market.volume_num.unwrap_or(0.0),
+        market.liquidity_num.unwrap_or(0.0),
+        (market.best_bid, market.best_ask),
+        parse_time(market.end_date.as_deref()),
+        now,
+    ))
+}
+
+fn time_score(hours: Option<f64>) -> f64 {
+    match hours {
+        Some(h) if h <= 0.0 => 0.0,
+        Some(h) if h < IDEAL_HOURS.0 => h / IDEAL_HOURS.0,
+        Some(h) if h <= IDEAL_HOURS.1 => 1.0,
+        Some(h) => IDEAL_HOURS.1 / h,
+        None => 0.0,
+    }
+}
+
+/// Score candidates (0-1) and sort best first. Volume and open interest
+/// are log-scaled against the largest value on the same venue, since the
+/// venues report them in different units.
+pub fn rank(mut candidates: Vec<Candidate>) -> Vec<Candidate> {
+    let max_by_venue = |venue: &str, f: fn(&Candidate) -> f64| {
+        candidates.iter().filter(|c| c.venue == venue).map(f).fold(0.0, f64::max)
+    };
+    let maxima: Vec<(&'static str, f64, f64)> = ["kalshi", "polymarket"]
+        .into_iter()
+        .map(|v| (v, max_by_venue(v, |c| c.volume), max_by_venue(v, |c| c.open_interest)))
+        .collect();
+    let log_share = |value: f64, max: f64| if max > 0.0 { (1.0 + value).ln() / (1.0 + max).ln() } else { 0.0 };
+
+    for c in &mut candidates {
+        let (_, max_volume, max_oi) = maxima.iter().find(|(v, _, _)| *v == c.venue).copied().unwrap_or_default();
+        let spread = c.spread.map(|s| 1.0 - (s / WIDE_SPREAD).min(1.0)).unwrap_or(0.0);
+        let parts = [
+            log_share(c.volume, max_volume),
+            log_share(c.open_interest, max_oi),
+            spread,
+            time_score(c.hours_to_close),
+        ];
+        c.score = parts.iter().zip(WEIGHTS).map(|(p, w)| p * w).sum();
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates
+}
+
+fn words(title: &str) -> HashSet<String> {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2 && !STOPWORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Jaccard overlap of the meaningful words in two titles.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Suggest Kalshi/Polymarket pairs on the same event that the matcher does
+/// not already know, best first.
+pub fn suggest_pairs(ranked: &[Candidate], matcher: &MarketMatcher, limit: usize) -> Vec<PairSuggestion> {
+    let (kalshi, poly): (Vec<&Candidate>, Vec<&Candidate>) = ranked.iter().partition(|c| c.venue == "kalshi");
+    let mut suggestions = Vec::new();
+
+    for k in kalshi.iter().filter(|k| !matcher.is_matched(&MarketId::kalshi(&k.id))) {
+        let best = poly
+            .iter()
+            .filter(|p| !matcher.is_matched(&MarketId::polymarket(&p.id)))
+            .filter(|p| match (k.closes_at, p.closes_at) {
+                (Some(a), Some(b)) => (a - b).num_hours().abs() <= MAX_CLOSE_GAP_HOURS,
+                _ => false,
+            })
+            .map(|p| (similarity(&k.title, &p.title), p))
+            .filter(|(s, _)| *s >= MIN_SIMILARITY)
+            .max_by(|a, b| a.0.total_cmp(&b.0));
+
+        if let Some((similarity, p)) = best {
+            suggestions.push(PairSuggestion {
+                kalshi_ticker: k.id.clone(),
+                kalshi_title: k.title.clone(),
+                polymarket_id: p.id.clone(),
+                polymarket_title: p.title.clone(),
+                similarity,
+                score: (k.score + p.score) / 2.0,
+            });
+        }
+    }
+    suggestions.sort_by(|a, b| (b.similarity * b.score).total_cmp(&(a.similarity * a.score)));
+    suggestions.truncate(limit);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn kalshi(ticker: &str, title: &str, volume: i64, bid: i32, ask: i32, close: DateTime<Utc>) -> KalshiMarket {
+        KalshiMarket {
+            ticker: ticker.to_string(),
+            title: title.to_string(),
+            volume: Some(volume),
+            open_interest: Some(volume / 2),
+            yes_bid: Some(bid),
+            yes_ask: Some(ask),
+            close_time: Some(close.to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    fn gamma(token: &str, question: &str, volume: f64, close: DateTime<Utc>) -> GammaMarket {
+        GammaMarket {
+            question: question.to_string(),
+            clob_token_ids: Some(format!("[\"{}\", \"{}-no\"]", token, token)),
+            volume_num: Some(volume),
+            liquidity_num: Some(volume / 10.0),
+            best_bid: Some(0.48),
+            best_ask: Some(0.49),
+            end_date: Some(close.to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ranks_liquid_tight_markets_and_suggests_new_pairs() {
+        let now = Utc::now();
+        let close = now + Duration::hours(24);
+        let mut candidates = vec![
+            from_kalshi(&kalshi("FED-CUT", "Will the Fed cut rates in December?", 50_000, 40, 42, close), now),
+            from_kalshi(&kalshi("THIN", "Will it snow in Miami?", 10, 5, 60, close + Duration::days(200)), now),
+            from_kalshi(&kalshi("KNOWN", "Bitcoin above 100k?", 40_000, 50, 51, close), now),
+        ];
+        candidates.extend(from_gamma(&gamma("fed", "Fed rate cut in December?", 1_000_000.0, close), now));
+        candidates.extend(from_gamma(&gamma("snow", "Snow in Miami this year?", 100.0, close), now));
+
+        let ranked = rank(candidates);
+        assert_eq!(ranked.last().unwrap().id, "THIN");
+        assert!(ranked.iter().all(|c| (0.0..=1.0).contains(&c.score)));
+
+        let mut matcher = MarketMatcher::empty();
+        matcher.add_match(crate::arbitrage::market_matcher::MatchedMarket::new("BTC", "btc", "KNOWN"));
+        let pairs = suggest_pairs(&ranked, &matcher, 10);
+        // Miami titles overlap but resolve 200 days apart
+        assert_eq!(pairs.len(), 1);
+        assert_eq!((pairs[0].kalshi_ticker.as_str(), pairs[0].polymarket_id.as_str()), ("FED-CUT", "fed"));
+    }
+}
@@ -2,14 +2,19 @@
 //!
 //! Analyzes top performing traders on Polymarket to learn from their strategies.
 
-use anyhow::{Context, Result};
-use reqwest::Client;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::polymarket::data_api::DataApiClient;
+use crate::utils::fixtures::FixtureStore;
+
+pub use crate::polymarket::data_api::{LeaderboardEntry, TradeActivity, TraderPosition};
+
 /// Polymarket trader analyzer
 pub struct TraderAnalyzer {
-    http: Client,
+    data_api: DataApiClient,
 }
 
 /// Trader profile from leaderboard
@@ -23,62 +28,6 @@ pub struct TraderProfile {
     pub markets_traded: Option<i32>,
 }
 
-/// Trader position
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TraderPosition {
-    #[serde(default)]
-    pub market: String,
-    #[serde(default)]
-    pub outcome: String,
-    #[serde(default)]
-    pub size: f64,
-    #[serde(default)]
-    pub avg_price: f64,
-    #[serde(default)]
-    pub current_price: f64,
-    #[serde(rename = "tokenId", default)]
-    pub token_id: Option<String>,
-    #[serde(rename = "conditionId", default)]
-    pub condition_id: Option<String>,
-}
-
-/// Trader activity/trade
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TraderActivity {
-    #[serde(default)]
-    pub action: String,  // "buy" or "sell"
-    #[serde(default)]
-    pub market: String,
-    #[serde(default)]
-    pub outcome: String,
-    #[serde(default)]
-    pub size: f64,
-    #[serde(default)]
-    pub price: f64,
-    #[serde(default)]
-    pub timestamp: String,
-    #[serde(rename = "tokenId", default)]
-    pub token_id: Option<String>,
-}
-
-/// Leaderboard entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct LeaderboardEntry {
-    #[serde(default)]
-    pub name: String,
-    #[serde(default)]
-    pub proxy_address: String,
-    #[serde(default)]
-    pub profit_and_loss: f64,
-    #[serde(default)]
-    pub volume: f64,
-    #[serde(default)]
-    pub positions_value: f64,
-    #[serde(default)]
-    pub num_markets_won: i32,
-}
-
 /// Analysis result for a trader
 #[derive(Debug, Clone)]
 pub struct TraderAnalysis {
@@ -87,7 +36,7 @@ pub struct TraderAnalysis {
     pub total_profit: f64,
     pub total_volume: f64,
     pub positions: Vec<TraderPosition>,
-    pub recent_activity: Vec<TraderActivity>,
+    pub recent_activity: Vec<TradeActivity>,
     pub top_markets: Vec<String>,
     pub trading_frequency: String,
     pub avg_position_size: f64,
@@ -97,83 +46,39 @@ impl TraderAnalyzer {
     /// Create a new trader analyzer.
     pub fn new() -> Self {
         Self {
-            http: Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
+            data_api: DataApiClient::new(),
         }
     }
 
+    /// Use a fixture store for recording or replaying responses.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.data_api = self.data_api.with_fixtures(store);
+        self
+    }
+
     /// Get the leaderboard (top traders by profit).
     pub async fn get_leaderboard(&self, time_period: &str, limit: usize) -> Result<Vec<LeaderboardEntry>> {
-        // API: https://data-api.polymarket.com/v1/leaderboard?timePeriod=all&orderBy=VOL&limit=20&category=overall
-        let url = format!(
-            "https://data-api.polymarket.com/v1/leaderboard?timePeriod={}&orderBy=PNL&limit={}&category=overall",
-            time_period, limit
-        );
-        
-        debug!("Fetching leaderboard: {}", url);
-        
-        let response = self.http.get(&url).send().await
-            .context("Failed to fetch leaderboard")?;
-        
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            anyhow::bail!("Leaderboard request failed: {} - {}", status, text);
-        }
-        
-        let entries: Vec<LeaderboardEntry> = response.json().await
-            .context("Failed to parse leaderboard response")?;
-        
+        let entries = self.data_api.leaderboard(time_period, limit).await?;
         info!("Fetched {} leaderboard entries", entries.len());
         Ok(entries)
     }
 
     /// Get positions for a specific trader.
     pub async fn get_trader_positions(&self, address: &str, limit: usize) -> Result<Vec<TraderPosition>> {
-        let url = format!(
-            "https://data-api.polymarket.com/positions?user={}&limit={}",
-            address, limit
-        );
-        
         debug!("Fetching positions for {}", address);
-        
-        let response = self.http.get(&url).send().await
-            .context("Failed to fetch positions")?;
-        
-        if !response.status().is_success() {
-            warn!("Failed to fetch positions for {}", address);
-            return Ok(vec![]);
-        }
-        
-        let positions: Vec<TraderPosition> = response.json().await
-            .unwrap_or_else(|_| vec![]);
-        
-        Ok(positions)
+        Ok(self.data_api.positions(address, limit).await.unwrap_or_else(|e| {
+            warn!("Failed to fetch positions for {}: {}", address, e);
+            vec![]
+        }))
     }
 
     /// Get recent activity for a specific trader.
-    pub async fn get_trader_activity(&self, address: &str, limit: usize) -> Result<Vec<TraderActivity>> {
-        let url = format!(
-            "https://data-api.polymarket.com/activity?user={}&limit={}",
-            address, limit
-        );
-        
+    pub async fn get_trader_activity(&self, address: &str, limit: usize) -> Result<Vec<TradeActivity>> {
         debug!("Fetching activity for {}", address);
-        
-        let response = self.http.get(&url).send().await
-            .context("Failed to fetch activity")?;
-        
-        if !response.status().is_success() {
-            warn!("Failed to fetch activity for {}", address);
-            return Ok(vec![]);
-        }
-        
-        let activity: Vec<TraderActivity> = response.json().await
-            .unwrap_or_else(|_| vec![]);
-        
-        Ok(activity)
+        Ok(self.data_api.activity(address, None, limit).await.unwrap_or_else(|e| {
+            warn!("Failed to fetch activity for {}: {}", address, e);
+            vec![]
+        }))
     }
 
     /// Analyze a specific trader's strategy.
@@ -191,7 +96,7 @@ impl TraderAnalyzer {
         
         // Calculate metrics
         let total_position_value: f64 = positions.iter()
-            .map(|p| p.size * p.current_price)
+            .map(|p| p.size * p.cur_price)
             .sum();
         
         let avg_position_size = if positions.is_empty() {
@@ -203,7 +108,7 @@ impl TraderAnalyzer {
         // Identify top markets by position size
         let mut market_sizes: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
         for pos in &positions {
-            *market_sizes.entry(pos.market.clone()).or_insert(0.0) += pos.size;
+            *market_sizes.entry(pos.title.clone()).or_insert(0.0) += pos.size;
         }
         let mut top_markets: Vec<_> = market_sizes.into_iter().collect();
         top_markets.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
@@ -261,14 +166,25 @@ impl TraderAnalyzer {
     }
 }
 
+impl Default for TraderAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::utils::fixtures::FixtureMode;
+
     #[tokio::test]
     async fn test_get_leaderboard() {
-        let analyzer = TraderAnalyzer::new();
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/data_api");
+        let store = Arc::new(FixtureStore::new(dir, FixtureMode::Replay));
+        let analyzer = TraderAnalyzer::new().with_fixtures(store);
         let result = analyzer.get_leaderboard("monthly", 5).await;
         assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 2);
     }
 }
@@ -0,0 +1,246 @@
+//! Shared runtime settings and hot config reload.
+//!
+//! The running bot keeps its settings in an [`AppState`] that tasks read on
+//! every pass, so edits to the `.env` file can take effect without a
+//! restart. A [`ConfigWatcher`] notices the file changing and applies the
+//! settings listed in [`RELOADABLE`]: thresholds, sizes, poll intervals and
+//! notification routing. Anything else is refused with the reason, above
+//! all credentials and the dry-run switch, and keeps its running value
+//! until the bot is restarted.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::fs;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use crate::config::Config;
+use crate::notify::router::Router;
+
+/// File the configuration is loaded from.
+pub const ENV_FILE: &str = ".env";
+
+/// Copies one setting from freshly loaded settings into the running ones.
+type Apply = fn(&mut Config, &Config);
+
+/// Settings applied at runtime, by env var.
+pub const RELOADABLE: &[(&str, Apply)] = &[
+    ("MIN_PROFIT_THRESHOLD", |c, n| c.min_profit_threshold = n.min_profit_threshold),
+    ("MAX_POSITION_SIZE", |c, n| c.max_position_size = n.max_position_size),
+    ("INTENT_TTL_SECS", |c, n| c.intent_ttl_secs = n.intent_ttl_secs),
+    ("MARKET_COOLDOWN_SECS", |c, n| c.market_cooldown_secs = n.market_cooldown_secs),
+    ("KALSHI_FILL_POLL_SECS", |c, n| c.kalshi_fill_poll_secs = n.kalshi_fill_poll_secs),
+    ("COPY_POLL_SECS", |c, n| c.copy_poll_secs = n.copy_poll_secs),
+    ("UNIVERSE_REFRESH_SECS", |c, n| c.universe_refresh_secs = n.universe_refresh_secs),
+    ("LEADERBOARD_REFRESH_SECS", |c, n| c.leaderboard_refresh_secs = n.leaderboard_refresh_secs),
+    ("LEADERBOARD_SNAPSHOT_SECS", |c, n| c.leaderboard_snapshot_secs = n.leaderboard_snapshot_secs),
+    ("MARK_INTERVAL_SECS", |c, n| c.mark_interval_secs = n.mark_interval_secs),
+    ("BALANCE_POLL_SECS", |c, n| c.balance_poll_secs = n.balance_poll_secs),
+    ("RESOLUTION_POLL_SECS", |c, n| c.resolution_poll_secs = n.resolution_poll_secs),
+    ("RESOLUTION_WARNING_MINUTES", |c, n| c.resolution_warning_minutes = n.resolution_warning_minutes),
+    ("WATCHLIST_POLL_SECS", |c, n| c.watchlist_poll_secs = n.watchlist_poll_secs),
+    ("NOTIFY_ROUTES", |c, n| c.notify_routes = n.notify_routes.clone()),
+    ("NOTIFY_DEDUP_SECS", |c, n| c.notify_dedup_secs = n.notify_dedup_secs),
+    ("NOTIFY_MAX_PER_MINUTE", |c, n| c.notify_max_per_minute = n.notify_max_per_minute),
+];
+
+/// Parts of a key that mark it as a credential.
+const SECRET_MARKERS: &[&str] = &["KEY", "SECRET", "PASSWORD", "PASSPHRASE", "TOKEN", "EMAIL", "FUNDER", "SIGNATURE_TYPE", "PIN"];
+
+pub fn is_reloadable(key: &str) -> bool {
+    RELOADABLE.iter().any(|(k, _)| *k == key)
+}
+
+/// Why a change to `key` is not applied at runtime.
+pub fn refusal(key: &str) -> &'static str {
+    if key == "DRY_RUN" {
+        "switching between dry-run and live trading needs a restart"
+    } else if SECRET_MARKERS.iter().any(|m| key.contains(m)) {
+        "credentials are only read at startup"
+    } else {
+        "only read at startup"
+    }
+}
+
+/// One edited setting; `None` is unset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "(unset)".to_string());
+        write!(f, "{}: {} -> {}", self.key, value(&self.old), value(&self.new))
+    }
+}
+
+/// Settings that differ between two readings of the file, by key.
+pub fn diff(old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) -> Vec<Change> {
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    keys.into_iter()
+        .filter(|k| old.get(*k) != new.get(*k))
+        .map(|k| Change { key: k.clone(), old: old.get(k).cloned(), new: new.get(k).cloned() })
+        .collect()
+}
+
+/// Outcome of one reload.
+#[derive(Debug, Clone, Default)]
+pub struct Reload {
+    pub applied: Vec<Change>,
+    /// Changes left for a restart, with the reason
+    pub refused: Vec<(Change, &'static str)>,
+}
+
+/// Settings shared by the running tasks.
+#[derive(Debug)]
+pub struct AppState {
+    config: RwLock<Config>,
+}
+
+impl AppState {
+    pub fn new(config: Config) -> Self {
+        Self { config: RwLock::new(config) }
+    }
+
+    /// Snapshot of the current settings.
+    pub fn config(&self) -> Config {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Read one setting.
+    pub fn get<T>(&self, f: impl FnOnce(&Config) -> T) -> T {
+        f(&self.config.read().unwrap())
+    }
+
+    /// Copy the `keys` settings from `fresh`.
+    fn apply(&self, fresh: &Config, keys: &[&str]) {
+        let mut config = self.config.write().unwrap();
+        for (_, apply) in RELOADABLE.iter().filter(|(k, _)| keys.contains(k)) {
+            apply(&mut config, fresh);
+        }
+    }
+}
+
+/// Applies edits of the config file to an [`AppState`].
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: String,
+    modified: Option<SystemTime>,
+    /// The file as last read
+    seen: BTreeMap<String, String>,
+    /// Values applied since startup, over the environment (None = removed)
+    overrides: BTreeMap<String, Option<String>>,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`, taking its current contents as already applied.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            modified: modified(path),
+            seen: read_env_file(path).unwrap_or_default(),
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Apply the edits made since the last call, if the file changed. A
+    /// change that fails to parse rejects the whole edit.
+    pub fn poll(&mut self, state: &AppState) -> Result<Option<Reload>> {
+        let now = modified(&self.path);
+        if now == self.modified {
+            return Ok(None);
+        }
+        self.modified = now;
+        let file = read_env_file(&self.path)?;
+        let changes = diff(&self.seen, &file);
+        self.seen = file;
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        let (applied, refused): (Vec<Change>, Vec<Change>) = changes.into_iter().partition(|c| is_reloadable(&c.key));
+        let refused = refused.into_iter().map(|c| (c.clone(), refusal(&c.key))).collect();
+        if !applied.is_empty() {
+            let mut overrides = self.overrides.clone();
+            overrides.extend(applied.iter().map(|c| (c.key.clone(), c.new.clone())));
+            let fresh = load_with(&overrides)?;
+            self.overrides = overrides;
+            let keys: Vec<&str> = applied.iter().map(|c| c.key.as_str()).collect();
+            state.apply(&fresh, &keys);
+        }
+        Ok(Some(Reload { applied, refused }))
+    }
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Key/value pairs of a dotenv file; a missing file is empty.
+fn read_env_file(path: &str) -> Result<BTreeMap<String, String>> {
+    if fs::metadata(path).is_err() {
+        return Ok(BTreeMap::new());
+    }
+    let mut values = BTreeMap::new();
+    for item in dotenvy::from_path_iter(path).with_context(|| format!("Invalid {}", path))? {
+        let (key, value) = item.with_context(|| format!("Invalid {}", path))?;
+        values.insert(key, value);
+    }
+    Ok(values)
+}
+
+/// Settings from the environment with `overrides` applied, validated the
+/// way startup validates them. The process environment is left alone.
+fn load_with(overrides: &BTreeMap<String, Option<String>>) -> Result<Config> {
+    let mut vars: HashMap<String, String> = std::env::vars().collect();
+    for (key, value) in overrides {
+        match value {
+            Some(value) => vars.insert(key.clone(), value.clone()),
+            None => vars.remove(key),
+        };
+    }
+    Config::from_map(&vars).and_then(|config| Router::from_config(&config).map(|_| config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_plan() {
+        let file = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+        let old = file(&[("MIN_PROFIT_THRESHOLD", "0.02"), ("DRY_RUN", "true"), ("KALSHI_API_KEY", "a")]);
+        let new = file(&[("MIN_PROFIT_THRESHOLD", "0.03"), ("DRY_RUN", "false"), ("NOTIFY_ROUTES", "breaker=telegram")]);
+
+        let changes = diff(&old, &new);
+        let keys: Vec<&str> = changes.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, ["DRY_RUN", "KALSHI_API_KEY", "MIN_PROFIT_THRESHOLD", "NOTIFY_ROUTES"]);
+        assert_eq!(changes[2].to_string(), "MIN_PROFIT_THRESHOLD: 0.02 -> 0.03");
+        assert_eq!(changes[1].to_string(), "KALSHI_API_KEY: a -> (unset)");
+
+        assert!(is_reloadable("MIN_PROFIT_THRESHOLD") && is_reloadable("NOTIFY_ROUTES"));
+        assert!(!is_reloadable("DRY_RUN") && !is_reloadable("KALSHI_API_KEY"));
+        assert_eq!(refusal("DRY_RUN"), "switching between dry-run and live trading needs a restart");
+        assert_eq!(refusal("KALSHI_API_KEY"), "credentials are only read at startup");
+        assert_eq!(refusal("POLYMARKET_PRIVATE_KEY"), "credentials are only read at startup");
+        assert_eq!(refusal("KALSHI_SERIES"), "only read at startup");
+    }
+
+    #[test]
+    fn test_reload_overrides_leave_the_environment_alone() {
+        let overrides = |value: &str| BTreeMap::from([("MARKET_COOLDOWN_SECS".to_string(), Some(value.to_string()))]);
+        let fresh = load_with(&overrides("75")).unwrap();
+        assert_eq!(fresh.market_cooldown_secs, 75);
+        assert!(std::env::var("MARKET_COOLDOWN_SECS").is_err());
+        assert!(load_with(&overrides("soon")).is_err());
+
+        let removed = BTreeMap::from([("MARKET_COOLDOWN_SECS".to_string(), None)]);
+        assert_eq!(load_with(&removed).unwrap().market_cooldown_secs, 60);
+    }
+}
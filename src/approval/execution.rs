@@ -0,0 +1,212 @@
+//! Prioritised execution queue.
+//!
+//! Every order the bot places goes through one queue instead of calling
+//! the venue clients from wherever the trade was decided. Queued orders go
+//! out highest [`Priority`] first (arb legs before copy trades), oldest
+//! first within a priority, and each venue runs at most a bounded number of
+//! them at once: an arb takes a slot on both of its venues. An order still
+//! waiting is cancelled when a newer one for the same thing (same
+//! [`IntentKind::key`]) supersedes it; the superseded orders are returned
+//! so their intents can be marked.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{Notify, OwnedSemaphorePermit, Semaphore};
+use tracing::info;
+
+use super::queue::IntentKind;
+
+/// Default orders in flight per venue.
+pub const DEFAULT_VENUE_WORKERS: usize = 2;
+
+/// How often an idle dispatcher re-checks whether it should stop.
+const IDLE_CHECK: Duration = Duration::from_secs(1);
+
+/// Which queued orders go out first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    CopyTrade,
+    /// Arb legs: the edge is gone within seconds
+    Arbitrage,
+}
+
+impl Priority {
+    pub fn of(kind: &IntentKind) -> Self {
+        match kind {
+            IntentKind::Arbitrage { .. } => Priority::Arbitrage,
+            IntentKind::CopyTrade { .. } | IntentKind::KalshiFollow { .. } => Priority::CopyTrade,
+        }
+    }
+}
+
+/// An order waiting for a venue slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedOrder {
+    pub id: u64,
+    pub priority: Priority,
+    pub kind: IntentKind,
+    /// Approved intent the order came from, if any
+    pub intent_id: Option<String>,
+    /// When the trade was decided, e.g. the intent's creation
+    pub created_at: DateTime<Utc>,
+}
+
+/// Orders waiting for execution, dispatched by priority to bounded
+/// per-venue worker slots.
+pub struct ExecutionQueue {
+    pending: Mutex<Vec<QueuedOrder>>,
+    next_id: Mutex<u64>,
+    venues: Mutex<HashMap<String, Arc<Semaphore>>>,
+    workers_per_venue: usize,
+    wake: Notify,
+}
+
+impl ExecutionQueue {
+    /// At most `workers_per_venue` orders in flight on each venue.
+    pub fn new(workers_per_venue: usize) -> Self {
+        Self {
+            pending: Mutex::new(Vec::new()),
+            next_id: Mutex::new(1),
+            venues: Mutex::new(HashMap::new()),
+            workers_per_venue: workers_per_venue.max(1),
+            wake: Notify::new(),
+        }
+    }
+
+    /// Queue an order, cancelling any waiting order it supersedes. Returns
+    /// the new order's ID and the orders it superseded.
+    pub fn submit(&self, kind: IntentKind, intent_id: Option<String>, created_at: DateTime<Utc>) -> (u64, Vec<QueuedOrder>) {
+        let id = {
+            let mut next = self.next_id.lock().unwrap();
+            *next += 1;
+            *next - 1
+        };
+        let key = kind.key();
+        let mut pending = self.pending.lock().unwrap();
+        let (superseded, kept): (Vec<_>, Vec<_>) = pending.drain(..).partition(|o| o.kind.key() == key);
+        *pending = kept;
+        for old in &superseded {
+            info!("♻️  Queued order {} superseded before submission: {}", old.id, old.kind.summary());
+        }
+        pending.push(QueuedOrder { id, priority: Priority::of(&kind), kind, intent_id, created_at });
+        drop(pending);
+        self.wake.notify_one();
+        (id, superseded)
+    }
+
+    /// Waiting orders in dispatch order.
+    pub fn pending(&self) -> Vec<QueuedOrder> {
+        let mut pending = self.pending.lock().unwrap().clone();
+        pending.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+        pending
+    }
+
+    fn lane(&self, venue: &str) -> Arc<Semaphore> {
+        let mut venues = self.venues.lock().unwrap();
+        venues.entry(venue.to_string()).or_insert_with(|| Arc::new(Semaphore::new(self.workers_per_venue))).clone()
+    }
+
+    /// Take the first waiting order, in dispatch order, with a free slot on
+    /// each of its venues. The slots are held until the permits drop.
+    pub fn take_next(&self) -> Option<(QueuedOrder, Vec<OwnedSemaphorePermit>)> {
+        let mut pending = self.pending.lock().unwrap();
+        pending.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+        for index in 0..pending.len() {
+            let permits: Option<Vec<_>> = pending[index]
+                .kind
+                .venues()
+                .iter()
+                .map(|venue| self.lane(venue).try_acquire_owned().ok())
+                .collect();
+            if let Some(permits) = permits {
+                return Some((pending.remove(index), permits));
+            }
+        }
+        None
+    }
+
+    /// Dispatch orders to `handler` as slots free up, until `keep_running`
+    /// turns false. Each order runs on its own task.
+    pub async fn run<F, Fut>(self: Arc<Self>, handler: F, keep_running: impl Fn() -> bool)
+    where
+        F: Fn(QueuedOrder) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        while keep_running() {
+            while let Some((order, permits)) = self.take_next() {
+                let handler = handler.clone();
+                let queue = self.clone();
+                tokio::spawn(async move {
+                    handler(order).await;
+                    drop(permits);
+                    queue.wake.notify_one();
+                });
+            }
+            let _ = tokio::time::timeout(IDLE_CHECK, self.wake.notified()).await;
+        }
+    }
+}
+
+impl Default for ExecutionQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_VENUE_WORKERS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arb(market: &str, yes_price: f64) -> IntentKind {
+        IntentKind::Arbitrage {
+            market: market.to_string(),
+            yes_venue: "polymarket".to_string(),
+            yes_price,
+            no_venue: "kalshi".to_string(),
+            no_price: 0.50,
+            contracts: 10.0,
+        }
+    }
+
+    fn follow(trade_id: &str) -> IntentKind {
+        IntentKind::KalshiFollow {
+            trade_id: trade_id.to_string(),
+            ticker: "KX-1".to_string(),
+            side: "yes".to_string(),
+            action: "buy".to_string(),
+            price: 0.40,
+            count: 5,
+        }
+    }
+
+    #[test]
+    fn test_priority_supersession_and_venue_slots() {
+        let queue = ExecutionQueue::new(1);
+        let now = Utc::now();
+        let (first_follow, _) = queue.submit(follow("t1"), None, now);
+        let (stale, _) = queue.submit(arb("BTC", 0.45), Some("i1".to_string()), now);
+        queue.submit(follow("t2"), None, now);
+
+        // A requote on the same pair replaces the waiting arb
+        let (fresh, superseded) = queue.submit(arb("BTC", 0.44), Some("i2".to_string()), now);
+        assert_eq!(superseded.iter().map(|o| o.id).collect::<Vec<_>>(), vec![stale]);
+        let order: Vec<u64> = queue.pending().iter().map(|o| o.id).collect();
+        assert_eq!(order, vec![fresh, first_follow, first_follow + 2]);
+
+        // The arb takes the only Kalshi slot, so the follows wait for it
+        let (taken, arb_permits) = queue.take_next().unwrap();
+        assert_eq!((taken.id, taken.priority, arb_permits.len()), (fresh, Priority::Arbitrage, 2));
+        assert!(queue.take_next().is_none());
+        drop(arb_permits);
+
+        let (taken, follow_permits) = queue.take_next().unwrap();
+        assert_eq!(taken.id, first_follow);
+        assert!(queue.take_next().is_none());
+        drop(follow_permits);
+        assert_eq!(queue.take_next().unwrap().0.id, first_follow + 2);
+        assert!(queue.take_next().is_none() && queue.pending().is_empty());
+    }
+}
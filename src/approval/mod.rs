@@ -0,0 +1,15 @@
+//! Manual approval of order intents.
+//!
+//! In semi-auto mode the bot queues what it would trade (arb legs, copy
+//! trades) instead of executing, and a human approves or rejects each
+//! intent from the TUI or Telegram before it goes out. Intents not decided
+//! in time expire. Approved intents, and trades that need no approval, go
+//! out through a prioritised execution queue.
+
+pub mod execution;
+pub mod queue;
+pub mod telegram;
+
+pub use execution::{ExecutionQueue, Priority, QueuedOrder};
+pub use queue::{IntentKind, IntentQueue, IntentStatus, OrderIntent, INTENTS_FILE};
+pub use telegram::{ChatMessage, Decision, Polled, TelegramApprover};
@@ -0,0 +1,442 @@
+//! File-backed order intent queue.
+//!
+//! The queue lives in `data/intents.json` so the bot, the TUI and the
+//! Telegram poller share it; every change re-reads the file first so
+//! decisions made elsewhere are not overwritten.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::arbitrage::Opportunity;
+use crate::display;
+use crate::strategies::{kalshi_follow, CopyTrade, FollowTrade};
+use crate::utils::state;
+
+/// Default intent queue location.
+pub const INTENTS_FILE: &str = "data/intents.json";
+
+/// Decided intents kept for history.
+const MAX_HISTORY: usize = 200;
+
+/// What the bot wants to trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IntentKind {
+    /// Both legs of a YES/NO arb, same contract count on each
+    Arbitrage {
+        market: String,
+        yes_venue: String,
+        yes_price: f64,
+        no_venue: String,
+        no_price: f64,
+        contracts: f64,
+    },
+    /// Mirror a tracked trader's Polymarket trade
+    CopyTrade {
+        trader: String,
+        title: String,
+        asset: String,
+        side: String,
+        price: f64,
+        size_usd: f64,
+    },
+    /// Repeat a followed Kalshi account's fill on ours
+    KalshiFollow {
+        /// Followed fill, so each one gets its own intent
+        trade_id: String,
+        ticker: String,
+        side: String,
+        action: String,
+        price: f64,
+        count: i32,
+    },
+}
+
+impl IntentKind {
+    /// Arb intent for an opportunity.
+    pub fn arbitrage(opportunity: &Opportunity, contracts: f64) -> Self {
+        IntentKind::Arbitrage {
+            market: opportunity.market.clone(),
+            yes_venue: opportunity.yes_venue.to_string(),
+            yes_price: opportunity.yes_price,
+            no_venue: opportunity.no_venue.to_string(),
+            no_price: opportunity.no_price,
+            contracts,
+        }
+    }
+
+    /// Copy-trade intent.
+    pub fn copy_trade(trade: &CopyTrade) -> Self {
+        IntentKind::CopyTrade {
+            trader: trade.trader_address.clone(),
+            title: trade.label(),
+            asset: trade.asset.clone(),
+            side: trade.side.clone(),
+            price: trade.price,
+            size_usd: trade.our_size,
+        }
+    }
+
+    /// Kalshi follow intent.
+    pub fn kalshi_follow(trade: &FollowTrade) -> Self {
+        IntentKind::KalshiFollow {
+            trade_id: trade.trade_id.clone(),
+            ticker: trade.ticker.clone(),
+            side: trade.side.clone(),
+            action: trade.action.clone(),
+            price: trade.price,
+            count: trade.count,
+        }
+    }
+
+    /// Strategy the intent trades for, as recorded on trades.
+    pub fn strategy(&self) -> &'static str {
+        match self {
+            IntentKind::Arbitrage { .. } => "arbitrage",
+            IntentKind::CopyTrade { .. } => "copy_trade",
+            IntentKind::KalshiFollow { .. } => kalshi_follow::STRATEGY,
+        }
+    }
+
+    /// Venues the intent places orders on.
+    pub fn venues(&self) -> Vec<&str> {
+        match self {
+            IntentKind::Arbitrage { yes_venue, no_venue, .. } if yes_venue == no_venue => vec![yes_venue],
+            IntentKind::Arbitrage { yes_venue, no_venue, .. } => vec![yes_venue, no_venue],
+            IntentKind::CopyTrade { .. } => vec!["polymarket"],
+            IntentKind::KalshiFollow { .. } => vec!["kalshi"],
+        }
+    }
+
+    /// What an intent is about, ignoring price: a new quote on the same
+    /// market replaces nothing while an earlier intent is still pending.
+    pub fn key(&self) -> String {
+        match self {
+            IntentKind::Arbitrage { market, yes_venue, no_venue, .. } => {
+                format!("arb:{}:{}:{}", market, yes_venue, no_venue)
+            }
+            IntentKind::CopyTrade { asset, side, .. } => format!("copy:{}:{}", asset, side),
+            IntentKind::KalshiFollow { trade_id, .. } => format!("follow:{}", trade_id),
+        }
+    }
+
+    /// One-line description for the TUI and Telegram.
+    pub fn summary(&self) -> String {
+        match self {
+            IntentKind::Arbitrage { market, yes_venue, yes_price, no_venue, no_price, contracts } => format!(
+                "ARB {}: {} x YES {} @ {} + NO {} @ {} (edge {})",
+                market,
+                contracts,
+                yes_venue,
+                display::price(*yes_price),
+                no_venue,
+                display::price(*no_price),
+                display::percent(1.0 - yes_price - no_price)
+            ),
+            IntentKind::CopyTrade { trader, title, side, price, size_usd, .. } => format!(
+                "COPY {}: {} ${:.2} @ {} ({})",
+                title, side, size_usd, display::price(*price), &trader[..trader.len().min(10)]
+            ),
+            IntentKind::KalshiFollow { ticker, side, action, price, count, .. } => format!(
+                "FOLLOW {}: {} {} x {} @ {:.2}",
+                ticker, action.to_uppercase(), count, side.to_uppercase(), price
+            ),
+        }
+    }
+}
+
+/// Where an intent is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntentStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Expired,
+    /// Approved and waiting in the execution queue
+    Queued,
+    /// Order placed
+    Executed,
+    /// Refused by a pre-trade check before placement
+    Skipped,
+    /// Replaced by a newer order for the same market before placement
+    Superseded,
+    /// Placement failed
+    Failed,
+}
+
+impl IntentStatus {
+    /// Nothing more will happen to the intent.
+    pub fn is_final(&self) -> bool {
+        !matches!(self, IntentStatus::Pending | IntentStatus::Approved | IntentStatus::Queued)
+    }
+}
+
+/// A queued order intent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderIntent {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub kind: IntentKind,
+    pub status: IntentStatus,
+    /// Who decided, e.g. "tui", "telegram"
+    pub decided_by: Option<String>,
+    /// Why execution skipped or failed it
+    #[serde(default)]
+    pub outcome: Option<String>,
+}
+
+/// Queue of intents awaiting approval.
+pub struct IntentQueue {
+    intents: Vec<OrderIntent>,
+    file_path: Option<String>,
+}
+
+impl IntentQueue {
+    /// In-memory queue.
+    pub fn new() -> Self {
+        Self {
+            intents: Vec::new(),
+            file_path: None,
+        }
+    }
+
+    /// Load from file, starting empty if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self {
+            intents: state::load(file_path),
+            file_path: Some(file_path.to_string()),
+        }
+    }
+
+    /// Pick up changes made by other processes.
+    pub fn reload(&mut self) {
+        if let Some(ref path) = self.file_path {
+            self.intents = state::load(path);
+        }
+    }
+
+    /// Re-read, apply a change, and persist, holding the file lock
+    /// throughout so the bot and the TUI can't overwrite each other's
+    /// decisions.
+    fn update<R>(&mut self, f: impl FnOnce(&mut Vec<OrderIntent>) -> R) -> R {
+        let _lock = self.file_path.as_deref().and_then(|path| {
+            state::lock(path)
+                .map_err(|e| warn!("Failed to lock intent queue: {}", e))
+                .ok()
+        });
+        self.reload();
+        let result = f(&mut self.intents);
+        self.save();
+        result
+    }
+
+    /// Queue an intent for approval. Returns None if an intent for the same
+    /// market is already pending.
+    pub fn submit(&mut self, kind: IntentKind, ttl: Duration, now: DateTime<Utc>) -> Option<OrderIntent> {
+        self.update(|intents| {
+            let key = kind.key();
+            let duplicate = intents
+                .iter()
+                .any(|i| i.status == IntentStatus::Pending && i.kind.key() == key);
+            if duplicate {
+                return None;
+            }
+            let intent = OrderIntent {
+                id: Uuid::new_v4().to_string()[..8].to_string(),
+                created_at: now,
+                expires_at: now + ttl,
+                kind,
+                status: IntentStatus::Pending,
+                decided_by: None,
+                outcome: None,
+            };
+            info!("📝 Intent {} awaiting approval: {}", intent.id, intent.kind.summary());
+            intents.push(intent.clone());
+            Some(intent)
+        })
+    }
+
+    /// Approve or reject a pending, unexpired intent.
+    pub fn decide(&mut self, id: &str, approve: bool, by: &str, now: DateTime<Utc>) -> Result<(), String> {
+        self.update(|intents| {
+            let intent = intents
+                .iter_mut()
+                .find(|i| i.id == id)
+                .ok_or_else(|| format!("No intent {}", id))?;
+            if intent.status != IntentStatus::Pending {
+                return Err(format!("Intent {} is already {:?}", id, intent.status));
+            }
+            if now >= intent.expires_at {
+                intent.status = IntentStatus::Expired;
+                return Err(format!("Intent {} has expired", id));
+            }
+            intent.status = if approve { IntentStatus::Approved } else { IntentStatus::Rejected };
+            intent.decided_by = Some(by.to_string());
+            info!("{} intent {} via {}", if approve { "✅ Approved" } else { "❌ Rejected" }, id, by);
+            Ok(())
+        })
+    }
+
+    /// Expire stale pending intents, returning them.
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<OrderIntent> {
+        self.update(|intents| {
+            let mut expired = Vec::new();
+            for intent in intents.iter_mut() {
+                if intent.status == IntentStatus::Pending && now >= intent.expires_at {
+                    intent.status = IntentStatus::Expired;
+                    expired.push(intent.clone());
+                }
+            }
+            // Drop the oldest finished intents beyond the history limit
+            let finished = intents.iter().filter(|i| i.status.is_final()).count();
+            let mut excess = finished.saturating_sub(MAX_HISTORY);
+            intents.retain(|i| {
+                if excess > 0 && i.status.is_final() {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+            expired
+        })
+    }
+
+    /// Approved intents, marked queued so they are handed out once.
+    pub fn take_approved(&mut self) -> Vec<OrderIntent> {
+        self.update(|intents| {
+            intents
+                .iter_mut()
+                .filter(|i| i.status == IntentStatus::Approved)
+                .map(|i| {
+                    i.status = IntentStatus::Queued;
+                    i.clone()
+                })
+                .collect()
+        })
+    }
+
+    /// Record what execution did with a queued intent.
+    pub fn finish(&mut self, id: &str, status: IntentStatus, outcome: Option<String>) {
+        self.update(|intents| {
+            if let Some(intent) = intents.iter_mut().find(|i| i.id == id && i.status == IntentStatus::Queued) {
+                intent.status = status;
+                intent.outcome = outcome;
+            }
+        })
+    }
+
+    /// Intents awaiting a decision, oldest first.
+    pub fn pending(&self) -> Vec<&OrderIntent> {
+        self.intents.iter().filter(|i| i.status == IntentStatus::Pending).collect()
+    }
+
+    /// All intents, oldest first.
+    pub fn all(&self) -> &[OrderIntent] {
+        &self.intents
+    }
+
+    fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.intents) {
+                warn!("Failed to persist intent queue: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for IntentQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arb(market: &str) -> IntentKind {
+        IntentKind::Arbitrage {
+            market: market.to_string(),
+            yes_venue: "polymarket".to_string(),
+            yes_price: 0.45,
+            no_venue: "kalshi".to_string(),
+            no_price: 0.50,
+            contracts: 10.0,
+        }
+    }
+
+    #[test]
+    fn test_approval_lifecycle() {
+        let now = Utc::now();
+        let ttl = Duration::seconds(60);
+        let mut queue = IntentQueue::new();
+
+        let a = queue.submit(arb("BTC"), ttl, now).unwrap();
+        let mut requote = arb("BTC");
+        if let IntentKind::Arbitrage { yes_price, .. } = &mut requote {
+            *yes_price = 0.44;
+        }
+        assert!(queue.submit(requote, ttl, now).is_none(), "duplicate pending intent");
+        let b = queue.submit(arb("ETH"), ttl, now).unwrap();
+        let c = queue.submit(arb("SOL"), ttl, now).unwrap();
+
+        queue.decide(&a.id, true, "tui", now).unwrap();
+        queue.decide(&b.id, false, "telegram", now).unwrap();
+        assert!(queue.decide(&b.id, true, "tui", now).is_err());
+
+        // c goes stale and can no longer be approved
+        let later = now + Duration::seconds(61);
+        let expired = queue.expire(later);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, c.id);
+        assert!(queue.decide(&c.id, true, "tui", later).is_err());
+
+        let approved = queue.take_approved();
+        assert_eq!(approved.len(), 1);
+        assert_eq!(approved[0].id, a.id);
+        assert!(queue.take_approved().is_empty());
+        assert!(queue.pending().is_empty());
+
+        // Queued until execution reports back
+        let status = |queue: &IntentQueue| queue.all().iter().find(|i| i.id == a.id).unwrap().status;
+        assert_eq!(status(&queue), IntentStatus::Queued);
+        queue.finish(&a.id, IntentStatus::Skipped, Some("BTC is cooling down".to_string()));
+        assert_eq!(status(&queue), IntentStatus::Skipped);
+        queue.finish(&a.id, IntentStatus::Executed, None);
+        assert_eq!(status(&queue), IntentStatus::Skipped, "finished intents stay finished");
+    }
+
+    #[test]
+    fn test_concurrent_writers_keep_each_others_intents() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-intents-{}.json", Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let now = Utc::now();
+
+        // Stand-ins for the bot and the TUI, each with its own view of the file
+        let writers: Vec<_> = ["bot", "tui"]
+            .into_iter()
+            .map(|name| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut queue = IntentQueue::load(&path);
+                    for i in 0..20 {
+                        queue.submit(arb(&format!("{}-{}", name, i)), Duration::seconds(60), now).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(IntentQueue::load(&path).pending().len(), 40);
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(format!("{}.lock", path));
+    }
+}
@@ -0,0 +1,275 @@
+//! Telegram inline-button approvals and operator commands.
+//!
+//! Each intent is sent with Approve/Reject buttons; button presses are
+//! picked up by polling `getUpdates` and only accepted from the configured
+//! chat. The same poll picks up operator commands (`/status`, `/pause`,
+//! ...) sent as messages, accepted only from allowlisted chats and answered
+//! in the chat they came from. Everyone in those chats may look; what a
+//! sender may change is the role TELEGRAM_COMMAND_USERS gives their user
+//! ID, since anyone in a group chat can press a button or send a command.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, warn};
+
+use super::queue::OrderIntent;
+use crate::config::Config;
+use crate::utils::permissions::{Action, Permissions, Role};
+use crate::utils::{http_pool, timezone};
+
+/// An approve/reject decision from a button press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decision {
+    pub intent_id: String,
+    pub approve: bool,
+}
+
+/// Parse button callback data ("approve:<id>" / "reject:<id>").
+pub fn parse_decision(data: &str) -> Option<Decision> {
+    let (action, id) = data.split_once(':')?;
+    let approve = match action {
+        "approve" => true,
+        "reject" => false,
+        _ => return None,
+    };
+    if id.is_empty() {
+        return None;
+    }
+    Some(Decision {
+        intent_id: id.to_string(),
+        approve,
+    })
+}
+
+/// A message from an allowlisted chat, e.g. an operator command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub chat_id: i64,
+    /// Telegram user ID of the sender, if any
+    pub user_id: Option<i64>,
+    /// What the sender may do
+    pub role: Role,
+    pub text: String,
+}
+
+/// What one poll picked up.
+#[derive(Debug, Default)]
+pub struct Polled {
+    /// Each decision with the role of the user who pressed the button
+    pub decisions: Vec<(Decision, Role)>,
+    pub messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Updates {
+    #[serde(default)]
+    result: Vec<Update>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Update {
+    update_id: i64,
+    callback_query: Option<CallbackQuery>,
+    message: Option<Message>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    chat: Chat,
+    /// Missing for messages sent on behalf of a channel
+    from: Option<User>,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CallbackQuery {
+    id: String,
+    from: User,
+    data: Option<String>,
+    message: Option<CallbackMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CallbackMessage {
+    chat: Chat,
+}
+
+#[derive(Debug, Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+/// Telegram bot used to approve intents.
+pub struct TelegramApprover {
+    http: Client,
+    bot_token: String,
+    chat_id: String,
+    /// Chats whose messages are accepted as commands
+    command_chats: Vec<String>,
+    /// Role of each user ID
+    command_users: Vec<(String, Role)>,
+    /// Next update to fetch
+    offset: i64,
+}
+
+impl TelegramApprover {
+    /// Build from config, or `None` if Telegram is not configured.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let bot_token = config.telegram_bot_token.clone()?;
+        let chat_id = config.telegram_chat_id.clone()?;
+        let command_chats = if config.telegram_command_chats.is_empty() {
+            vec![chat_id.clone()]
+        } else {
+            config.telegram_command_chats.clone()
+        };
+        Some(Self {
+            http: http_pool::client(),
+            bot_token,
+            chat_id,
+            command_chats,
+            command_users: config.telegram_command_users.clone(),
+            offset: 0,
+        })
+    }
+
+    /// What a user may do; unlisted users only look.
+    fn role_of(&self, user_id: Option<i64>) -> Role {
+        user_id
+            .and_then(|id| self.command_users.iter().find(|(u, _)| *u == id.to_string()))
+            .map_or(Role::Viewer, |(_, role)| *role)
+    }
+
+    fn url(&self, method: &str) -> String {
+        format!("https://api.telegram.org/bot{}/{}", self.bot_token, method)
+    }
+
+    /// Ask for approval of an intent.
+    pub async fn send_intent(&self, intent: &OrderIntent) -> Result<()> {
+        let text = format!(
+            "📝 Approval needed ({})\n{}\nExpires {}",
+            intent.id,
+            intent.kind.summary(),
+            timezone::format(intent.expires_at, "%H:%M:%S %Z")
+        );
+        let response = self.http
+            .post(self.url("sendMessage"))
+            .json(&json!({
+                "chat_id": self.chat_id,
+                "text": text,
+                "reply_markup": {
+                    "inline_keyboard": [[
+                        { "text": "✅ Approve", "callback_data": format!("approve:{}", intent.id) },
+                        { "text": "❌ Reject", "callback_data": format!("reject:{}", intent.id) },
+                    ]]
+                }
+            }))
+            .send()
+            .await
+            .context("Failed to reach telegram")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("telegram rejected intent message: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Send `text` to a chat, e.g. a command's reply.
+    pub async fn reply(&self, chat_id: i64, text: &str) -> Result<()> {
+        let response = self.http
+            .post(self.url("sendMessage"))
+            .json(&json!({ "chat_id": chat_id, "text": text }))
+            .send()
+            .await
+            .context("Failed to reach telegram")?;
+        if !response.status().is_success() {
+            anyhow::bail!("telegram rejected reply: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Fetch button presses and allowlisted messages since the last poll.
+    pub async fn poll(&mut self) -> Result<Polled> {
+        let response = self.http
+            .post(self.url("getUpdates"))
+            .json(&json!({
+                "offset": self.offset,
+                "timeout": 0,
+                "allowed_updates": ["callback_query", "message"],
+            }))
+            .send()
+            .await
+            .context("Failed to reach telegram")?;
+        let updates: Updates = response.json().await.context("Failed to parse telegram updates")?;
+
+        let mut polled = Polled::default();
+        for update in updates.result {
+            self.offset = self.offset.max(update.update_id + 1);
+            if let Some(message) = update.message {
+                let text = message.text.unwrap_or_default();
+                if !text.starts_with('/') {
+                    continue;
+                }
+                if self.command_chats.contains(&message.chat.id.to_string()) {
+                    let user_id = message.from.map(|u| u.id);
+                    polled.messages.push(ChatMessage { chat_id: message.chat.id, user_id, role: self.role_of(user_id), text });
+                } else {
+                    warn!("Ignoring telegram command from chat {} (not in TELEGRAM_COMMAND_CHATS)", message.chat.id);
+                }
+                continue;
+            }
+            let Some(query) = update.callback_query else { continue };
+
+            let from_our_chat = query.message
+                .as_ref()
+                .is_some_and(|m| m.chat.id.to_string() == self.chat_id);
+            let decision = query.data.as_deref().and_then(parse_decision);
+            let role = self.role_of(Some(query.from.id));
+            let reply = match (&decision, from_our_chat) {
+                (Some(_), true) if !Permissions::new(role).allows(Action::Trade) => {
+                    warn!("Ignoring telegram decision from user {} (a {})", query.from.id, role);
+                    "Not permitted"
+                }
+                (Some(d), true) => {
+                    polled.decisions.push((d.clone(), role));
+                    if d.approve { "Approved" } else { "Rejected" }
+                }
+                _ => {
+                    debug!("Ignoring telegram callback {:?}", query.data);
+                    "Not accepted"
+                }
+            };
+
+            // Stop the button spinner; failure here is cosmetic
+            let _ = self.http
+                .post(self.url("answerCallbackQuery"))
+                .json(&json!({ "callback_query_id": query.id, "text": reply }))
+                .send()
+                .await;
+        }
+        Ok(polled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decision() {
+        assert_eq!(
+            parse_decision("approve:ab12cd34"),
+            Some(Decision { intent_id: "ab12cd34".to_string(), approve: true })
+        );
+        assert_eq!(parse_decision("reject:ab12cd34").map(|d| d.approve), Some(false));
+        assert_eq!(parse_decision("approve:"), None);
+        assert_eq!(parse_decision("delete:ab12cd34"), None);
+        assert_eq!(parse_decision("garbage"), None);
+    }
+}
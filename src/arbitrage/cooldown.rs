@@ -0,0 +1,80 @@
+//! Per-market cooldown after execution.
+//!
+//! Right after we trade a market the book we saw is stale: our own orders
+//! took the liquidity and the venues have not caught up. Until
+//! MARKET_COOLDOWN_SECS have passed the market is left out of detection and
+//! approved intents on it are held back, so the same edge is not hit again
+//! against quotes that no longer exist.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::utils::clock::{system_clock, SharedClock};
+
+/// Last execution time per market, shared by the detector and executor.
+#[derive(Debug)]
+pub struct Cooldowns {
+    executed: Mutex<HashMap<String, DateTime<Utc>>>,
+    clock: SharedClock,
+}
+
+impl Default for Cooldowns {
+    fn default() -> Self {
+        Self { executed: Mutex::new(HashMap::new()), clock: system_clock() }
+    }
+}
+
+impl Cooldowns {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Record a (paper or live) execution on `market` now.
+    pub fn executed(&self, market: &str) {
+        self.executed.lock().unwrap().insert(market.to_string(), self.clock.now());
+    }
+
+    /// Time left before `market` may trade again, None once `cooldown` has
+    /// passed since its last execution.
+    pub fn remaining(&self, market: &str, cooldown: Duration) -> Option<Duration> {
+        let executed = self.executed.lock().unwrap();
+        let left = *executed.get(market)? + cooldown - self.clock.now();
+        (left > Duration::zero()).then_some(left)
+    }
+
+    pub fn is_cooling(&self, market: &str, cooldown: Duration) -> bool {
+        self.remaining(market, cooldown).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::sync::Arc;
+    use std::time::Duration as StdDuration;
+
+    #[test]
+    fn test_market_cools_down_after_execution() {
+        let clock = Arc::new(MockClock::new(Utc::now()));
+        let cooldowns = Cooldowns::new().with_clock(clock.clone());
+        let cooldown = Duration::seconds(60);
+        assert!(!cooldowns.is_cooling("BTC", cooldown));
+
+        cooldowns.executed("BTC");
+        clock.advance(StdDuration::from_secs(20));
+        assert_eq!(cooldowns.remaining("BTC", cooldown), Some(Duration::seconds(40)));
+        assert!(!cooldowns.is_cooling("ETH", cooldown));
+        // A reload to zero lifts it at once
+        assert!(!cooldowns.is_cooling("BTC", Duration::zero()));
+        clock.advance(StdDuration::from_secs(40));
+        assert!(!cooldowns.is_cooling("BTC", cooldown));
+    }
+}
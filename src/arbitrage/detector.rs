@@ -1,13 +1,153 @@
 //! Arbitrage detector module.
 //!
 //! Monitors prices on both platforms and identifies profitable spreads.
+//! When one venue is down the other is still quoted and checked for
+//! single-venue arbs, with cross-venue detection suspended until it is back.
 
 use anyhow::Result;
-use tracing::{info, debug};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+use tracing::{info, debug, warn};
 
+use crate::backtest::{QuoteTape, QuoteTick};
+use crate::display;
+use crate::fees::{FeeSchedule, Liquidity};
 use crate::polymarket::PolymarketClient;
 use crate::kalshi::KalshiClient;
-use super::market_matcher::MarketMatcher;
+use crate::paper_trading::book_fill::{BookSnapshot, Depth};
+use crate::utils::state;
+use super::market_matcher::{MarketMatcher, MatchedMarket};
+use super::moves::MoveDetector;
+use super::prime::{self, PrimeReport, Readiness, PRIME_CONCURRENCY};
+use super::quality::{QualityGate, QualityMetrics};
+use super::venue_health::{VenueHealth, VenueOutage};
+
+/// Top-of-book for both outcomes of a binary market (0.0-1.0 scale).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BinaryQuote {
+    pub yes_bid: Option<f64>,
+    pub yes_ask: Option<f64>,
+    pub no_bid: Option<f64>,
+    pub no_ask: Option<f64>,
+}
+
+impl BinaryQuote {
+    /// Build from YES quotes only; the NO book mirrors the YES book.
+    pub fn from_yes(yes_bid: Option<f64>, yes_ask: Option<f64>) -> Self {
+        Self {
+            yes_bid,
+            yes_ask,
+            no_bid: yes_ask.map(|p| 1.0 - p),
+            no_ask: yes_bid.map(|p| 1.0 - p),
+        }
+    }
+}
+
+/// A detected arb: buy YES on one venue and NO on another (or the same)
+/// venue for less than the $1 the pair is guaranteed to pay out.
+#[derive(Debug, Clone, Serialize)]
+pub struct Opportunity {
+    /// Matched pair name
+    pub market: String,
+    pub yes_venue: &'static str,
+    pub yes_price: f64,
+    pub no_venue: &'static str,
+    pub no_price: f64,
+    /// Estimated taker fees per contract pair
+    pub fee: f64,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl Opportunity {
+    /// Arb on `market` at the given leg prices, with taker fees from
+    /// `fees`. Bails on an unknown venue.
+    pub fn priced(
+        market: &str,
+        (yes_venue, yes_price): (&str, f64),
+        (no_venue, no_price): (&str, f64),
+        fees: &FeeSchedule,
+        detected_at: DateTime<Utc>,
+    ) -> Result<Self> {
+        let venue = |name: &str| match name {
+            "polymarket" => Ok("polymarket"),
+            "kalshi" => Ok("kalshi"),
+            other => Err(anyhow::anyhow!("Unknown venue '{}'", other)),
+        };
+        let (yes_venue, no_venue) = (venue(yes_venue)?, venue(no_venue)?);
+        Ok(Self {
+            market: market.to_string(),
+            yes_venue,
+            yes_price,
+            no_venue,
+            no_price,
+            fee: fees.per_contract(yes_venue, market, Liquidity::Taker, yes_price)
+                + fees.per_contract(no_venue, market, Liquidity::Taker, no_price),
+            detected_at,
+        })
+    }
+
+    /// Cost of one YES + one NO contract.
+    pub fn cost(&self) -> f64 {
+        self.yes_price + self.no_price
+    }
+
+    /// Gross edge per contract pair at detection time.
+    pub fn edge(&self) -> f64 {
+        1.0 - self.cost()
+    }
+
+    /// Edge per contract pair after fees.
+    pub fn net_edge(&self) -> f64 {
+        self.edge() - self.fee
+    }
+
+    /// The same legs at fresh quotes, or None when a leg has no ask.
+    pub fn repriced(&self, poly: &BinaryQuote, kalshi: &BinaryQuote, fees: &FeeSchedule, now: DateTime<Utc>) -> Option<Self> {
+        let quote = |venue: &str| if venue == "kalshi" { kalshi } else { poly };
+        let yes_price = quote(self.yes_venue).yes_ask?;
+        let no_price = quote(self.no_venue).no_ask?;
+        Self::priced(&self.market, (self.yes_venue, yes_price), (self.no_venue, no_price), fees, now).ok()
+    }
+
+    /// Whether `fresh` keeps at least `min_fraction` of this opportunity's
+    /// net edge, and any edge at all.
+    pub fn holds_up(&self, fresh: &Opportunity, min_fraction: f64) -> bool {
+        fresh.net_edge() > 0.0 && fresh.net_edge() >= min_fraction * self.net_edge()
+    }
+}
+
+/// Check every YES/NO venue combination for a pair whose combined ask is
+/// below $1 by more than `min_profit` after fees.
+pub fn find_opportunities(
+    market: &str,
+    poly: &BinaryQuote,
+    kalshi: &BinaryQuote,
+    fees: &FeeSchedule,
+    min_profit: f64,
+    now: DateTime<Utc>,
+) -> Vec<Opportunity> {
+    let venues = [("polymarket", poly), ("kalshi", kalshi)];
+    let mut found = Vec::new();
+
+    for (yes_venue, yes_quote) in venues {
+        for (no_venue, no_quote) in venues {
+            let (Some(yes_price), Some(no_price)) = (yes_quote.yes_ask, no_quote.no_ask) else {
+                continue;
+            };
+            let Ok(opportunity) = Opportunity::priced(market, (yes_venue, yes_price), (no_venue, no_price), fees, now) else {
+                continue;
+            };
+            if opportunity.net_edge() > min_profit {
+                found.push(opportunity);
+            }
+        }
+    }
+    found
+}
 
 /// Analyzes market data for arbitrage opportunities.
 pub struct ArbitrageDetector {
@@ -15,10 +155,23 @@ pub struct ArbitrageDetector {
     poly_client: PolymarketClient,
     /// Kalshi client
     kalshi_client: KalshiClient,
-    /// Market matcher
-    matcher: MarketMatcher,
+    /// Market matcher (refreshed in place as the universe changes)
+    matcher: RwLock<MarketMatcher>,
     /// Minimum profit threshold (e.g., 0.02 for 2%)
-    min_profit: f64,
+    min_profit: Mutex<f64>,
+    /// Fees netted off each spread
+    fees: FeeSchedule,
+    /// Records every quote checked, for backtesting
+    tape: Option<QuoteTape>,
+    /// Where the Polymarket books read each pass are saved for paper fills
+    books_path: Option<String>,
+    books: Mutex<HashMap<String, BookSnapshot>>,
+    /// Keeps markets with unsound or too few quotes out of signals
+    quality: Mutex<QualityGate>,
+    /// Which venues are reachable
+    venues: Mutex<VenueHealth>,
+    /// Diffs each pass's books against the last to spot sudden moves
+    moves: Option<Arc<MoveDetector>>,
 }
 
 impl ArbitrageDetector {
@@ -32,54 +185,431 @@ impl ArbitrageDetector {
         Self {
             poly_client,
             kalshi_client,
-            matcher,
-            min_profit,
+            matcher: RwLock::new(matcher),
+            min_profit: Mutex::new(min_profit),
+            fees: FeeSchedule::new(),
+            tape: None,
+            books_path: None,
+            books: Mutex::new(HashMap::new()),
+            quality: Mutex::new(QualityGate::new(1)),
+            venues: Mutex::new(VenueHealth::new()),
+            moves: None,
         }
     }
 
-    /// Run a single detection pass across all matched markets.
-    pub async fn check_all_opportunities(&self) -> Result<()> {
-        let matches = self.matcher.get_all();
-        
-        for matched in matches {
-            self.check_opportunity(matched).await?;
+    /// Require `quotes` consecutive sane quotes before a market can signal.
+    pub fn with_warmup(self, quotes: u32) -> Self {
+        *self.quality.lock().unwrap() = QualityGate::new(quotes);
+        self
+    }
+
+    /// Reject Polymarket books whose hash hasn't changed for `secs` (0 = never).
+    pub fn with_stale_after(self, secs: u64) -> Self {
+        let mut quality = self.quality.lock().unwrap();
+        *quality = quality.clone().with_stale_after(secs);
+        drop(quality);
+        self
+    }
+
+    /// Change the minimum profit threshold of later passes.
+    pub fn set_min_profit(&self, min_profit: f64) {
+        *self.min_profit.lock().unwrap() = min_profit;
+    }
+
+    /// Data-quality rejects and markets still warming up.
+    pub fn quality_metrics(&self) -> QualityMetrics {
+        self.quality.lock().unwrap().metrics()
+    }
+
+    /// Net spreads with a specific fee schedule, keeping its pair names
+    /// resolved to the matched markets' legs.
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Self {
+        fees.set_pairs(self.matcher.read().unwrap().get_all());
+        self.fees = fees;
+        self
+    }
+
+    /// Record every quote checked to a tape.
+    pub fn with_tape(mut self, tape: QuoteTape) -> Self {
+        self.tape = Some(tape);
+        self
+    }
+
+    /// Save the Polymarket book of every pair checked to `path`.
+    pub fn with_books(mut self, path: &str) -> Self {
+        self.books_path = Some(path.to_string());
+        self
+    }
+
+    /// Report sudden book changes between passes to `moves`.
+    pub fn with_moves(mut self, moves: Arc<MoveDetector>) -> Self {
+        self.moves = Some(moves);
+        self
+    }
+
+    /// Modify the matched markets in place.
+    pub fn update_matcher<R>(&self, f: impl FnOnce(&mut MarketMatcher) -> R) -> R {
+        let mut matcher = self.matcher.write().unwrap();
+        let result = f(&mut matcher);
+        self.fees.set_pairs(matcher.get_all());
+        result
+    }
+
+    /// Snapshot of the currently matched markets.
+    pub fn matched_markets(&self) -> Vec<MatchedMarket> {
+        self.matcher.read().unwrap().get_all().into_iter().cloned().collect()
+    }
+
+    /// Venues that are down, with cross-venue detection suspended.
+    pub fn venue_outages(&self) -> Vec<VenueOutage> {
+        self.venues.lock().unwrap().outages()
+    }
+
+    /// Warm up before the first pass: fetch every pair's Kalshi market and
+    /// Polymarket book concurrently, caching tick sizes, saving the books
+    /// and seeding the move baseline. Pairs whose Kalshi market has closed
+    /// are dropped.
+    pub async fn prime(&self) -> PrimeReport {
+        let matches = self.matched_markets();
+        let started = Instant::now();
+        let metadata = async {
+            let markets: Vec<_> = stream::iter(&matches)
+                .map(|m| self.kalshi_client.get_market(&m.kalshi.native))
+                .buffered(PRIME_CONCURRENCY)
+                .collect()
+                .await;
+            (markets, started.elapsed())
+        };
+        let books = async {
+            let books: Vec<_> = stream::iter(&matches)
+                .map(|m| self.poly_client.get_orderbook(&m.polymarket.native))
+                .buffered(PRIME_CONCURRENCY)
+                .collect()
+                .await;
+            (books, started.elapsed())
+        };
+        let ((markets, metadata_elapsed), (books, books_elapsed)) = tokio::join!(metadata, books);
+
+        let now = Utc::now();
+        let mut metadata = Readiness::new("kalshi metadata", metadata_elapsed);
+        let mut order_books = Readiness::new("order books", books_elapsed);
+        let mut tick_sizes = Readiness::new("tick sizes", books_elapsed);
+        let mut validation = Readiness::new("matched markets", started.elapsed());
+        let mut dropped = Vec::new();
+        for ((matched, market), book) in matches.iter().zip(markets).zip(books) {
+            match metadata.record(market) {
+                Some(market) if prime::is_closed(&market) => {
+                    validation.fail(&format!("{} is {} on Kalshi", matched.name, market.status));
+                    dropped.push(matched.name.clone());
+                }
+                Some(_) => validation.ok += 1,
+                None => validation.fail(&format!("{} could not be checked", matched.name)),
+            }
+            let Some(book) = order_books.record(book) else {
+                tick_sizes.fail(&format!("no book for {}", matched.name));
+                continue;
+            };
+            self.poly_client.cache_constraints(&matched.polymarket.native, &book);
+            tick_sizes.ok += 1;
+            let depth = Depth::from_polymarket(&book);
+            if let Some(moves) = &self.moves {
+                moves.observe(&matched.name, "polymarket", &BinaryQuote::from_yes(depth.best_bid(), depth.best_ask()), Some(&depth), now);
+            }
+            if self.books_path.is_some() {
+                let snapshot = BookSnapshot { at: now, venue: "polymarket".to_string(), depth };
+                self.books.lock().unwrap().insert(matched.name.clone(), snapshot);
+            }
+        }
+
+        if !dropped.is_empty() {
+            self.update_matcher(|m| m.retain(|pair| !dropped.contains(&pair.name)));
+        }
+        if let Some(path) = &self.books_path {
+            if let Err(e) = state::save(path, &*self.books.lock().unwrap()) {
+                warn!("Failed to save order books {}: {}", path, e);
+            }
+        }
+        PrimeReport { subsystems: vec![metadata, order_books, tick_sizes, validation], dropped }
+    }
+
+    /// Run a single detection pass across all matched markets. A venue that
+    /// is down is probed with the first pair only and the others are checked
+    /// on the healthy venue alone; the pass fails only when no venue answers.
+    pub async fn check_all_opportunities(&self) -> Result<Vec<Opportunity>> {
+        let matches = self.matched_markets();
+        let mut found = Vec::new();
+        let (poly_down, kalshi_down) = {
+            let venues = self.venues.lock().unwrap();
+            (venues.is_down("polymarket"), venues.is_down("kalshi"))
+        };
+        let (mut poly_count, mut kalshi_count) = (FetchCount::default(), FetchCount::default());
+
+        for (i, matched) in matches.iter().enumerate() {
+            debug!("Checking opportunity: {}", matched.name);
+            let poly = if !poly_down || i == 0 {
+                poly_count.record(self.fetch_polymarket(matched).await)
+            } else {
+                None
+            };
+            let kalshi = if !kalshi_down || i == 0 {
+                kalshi_count.record(self.fetch_kalshi(matched).await)
+            } else {
+                None
+            };
+            match (poly, kalshi) {
+                (Some(poly), Some(kalshi)) => found.extend(self.evaluate(matched, Some(poly), Some(kalshi))),
+                (Some(poly), None) if kalshi_down => found.extend(self.evaluate(matched, Some(poly), None)),
+                (None, Some(kalshi)) if poly_down => found.extend(self.evaluate(matched, None, Some(kalshi))),
+                (poly, kalshi) => {
+                    let mut quality = self.quality.lock().unwrap();
+                    if poly.is_none() && !poly_down {
+                        quality.fetch_failed(&matched.name, "polymarket");
+                    }
+                    if kalshi.is_none() && !kalshi_down {
+                        quality.fetch_failed(&matched.name, "kalshi");
+                    }
+                }
+            }
+        }
+
+        {
+            let now = Utc::now();
+            let mut venues = self.venues.lock().unwrap();
+            venues.record_pass("polymarket", poly_count.ok, poly_count.failed, poly_count.error.clone(), now);
+            venues.record_pass("kalshi", kalshi_count.ok, kalshi_count.failed, kalshi_count.error.clone(), now);
+        }
+        if poly_count.ok + kalshi_count.ok == 0 && poly_count.failed + kalshi_count.failed > 0 {
+            let error = poly_count.error.or(kalshi_count.error).unwrap_or_default();
+            anyhow::bail!("Neither venue answered: {}", error);
+        }
+
+        if let Some(moves) = &self.moves {
+            moves.retain(&matches.iter().map(|m| m.name.clone()).collect::<Vec<_>>());
+        }
+        if let Some(path) = &self.books_path {
+            let mut books = self.books.lock().unwrap();
+            books.retain(|name, _| matches.iter().any(|m| &m.name == name));
+            if let Err(e) = state::save(path, &*books) {
+                warn!("Failed to save order books {}: {}", path, e);
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Polymarket top-of-book for a pair, with its depth and book hash. NO
+    /// is derived from the mirrored YES book. Also records whether the
+    /// token is neg-risk, which sets its fee rates.
+    async fn fetch_polymarket(&self, matched: &MatchedMarket) -> Result<(BinaryQuote, Depth, String)> {
+        let token_id = &matched.polymarket.native;
+        let neg_risk = self.poly_client.is_neg_risk(token_id).await?;
+        self.fees.set_neg_risk(token_id, neg_risk);
+        let book = self.poly_client.get_orderbook(token_id).await?;
+        let depth = Depth::from_polymarket(&book);
+        let poly = BinaryQuote::from_yes(depth.best_bid(), depth.best_ask());
+        Ok((poly, depth, book.hash))
+    }
+
+    /// Kalshi top-of-book for a pair. Kalshi quotes both sides directly;
+    /// fall back to the mirror if not.
+    async fn fetch_kalshi(&self, matched: &MatchedMarket) -> Result<BinaryQuote> {
+        let market = self.kalshi_client.get_market(&matched.kalshi.native).await?;
+        let cents = |p: Option<i32>| p.map(|c| c as f64 / 100.0);
+        let mirrored = BinaryQuote::from_yes(cents(market.yes_bid), cents(market.yes_ask));
+        Ok(BinaryQuote {
+            no_bid: cents(market.no_bid).or(mirrored.no_bid),
+            no_ask: cents(market.no_ask).or(mirrored.no_ask),
+            ..mirrored
+        })
+    }
+
+    /// Fetch top-of-book on both venues for a matched pair, along with the
+    /// Polymarket depth and book hash.
+    async fn fetch_quotes(&self, matched: &MatchedMarket) -> Result<(BinaryQuote, BinaryQuote, Depth, String)> {
+        let poly = self.fetch_polymarket(matched).await;
+        let (poly, depth, hash) = poly.inspect_err(|_| self.quality.lock().unwrap().fetch_failed(&matched.name, "polymarket"))?;
+        let kalshi = self.fetch_kalshi(matched).await;
+        let kalshi = kalshi.inspect_err(|_| self.quality.lock().unwrap().fetch_failed(&matched.name, "kalshi"))?;
+        Ok((poly, kalshi, depth, hash))
+    }
+
+    /// Re-fetch both legs of `detected` right before it is executed.
+    /// Returns the repriced opportunity if it kept at least `min_fraction`
+    /// of the detected net edge, None if the edge evaporated.
+    pub async fn revalidate(&self, detected: &Opportunity, min_fraction: f64) -> Result<Option<Opportunity>> {
+        let matched = self.matched_markets().into_iter().find(|m| m.name == detected.market)
+            .ok_or_else(|| anyhow::anyhow!("{} is no longer matched", detected.market))?;
+        let (poly, kalshi, _, _) = self.fetch_quotes(&matched).await?;
+        let fresh = detected.repriced(&poly, &kalshi, &self.fees, Utc::now())
+            .filter(|fresh| detected.holds_up(fresh, min_fraction));
+        self.quality.lock().unwrap().revalidated(&detected.market, fresh.is_some());
+        if fresh.is_none() {
+            info!(
+                "💨 Edge on {} evaporated before execution (detected {} net)",
+                detected.market,
+                display::percent(detected.net_edge())
+            );
         }
-        
-        Ok(())
+        Ok(fresh)
     }
 
     /// Check for arbitrage on a specific matched pair.
-    pub async fn check_opportunity(&self, matched: &crate::arbitrage::market_matcher::MatchedMarket) -> Result<()> {
+    pub async fn check_opportunity(&self, matched: &MatchedMarket) -> Result<Vec<Opportunity>> {
         debug!("Checking opportunity: {}", matched.name);
+        let (poly, kalshi, depth, hash) = self.fetch_quotes(matched).await?;
+        Ok(self.evaluate(matched, Some((poly, depth, hash)), Some(kalshi)))
+    }
 
-        // Get prices from Polymarket
-        let (poly_bid, poly_ask) = self.poly_client.get_best_prices(&matched.polymarket_id).await?;
-        
-        // Get prices from Kalshi
-        let (kalshi_bid, kalshi_ask) = self.kalshi_client.get_best_prices(&matched.kalshi_ticker).await?;
-
-        // 1. Buy Kalshi, Sell Polymarket
-        if let (Some(k_ask), Some(p_bid)) = (kalshi_ask, poly_bid) {
-            let spread = p_bid - k_ask;
-            if spread > self.min_profit {
-                info!(
-                    "🔥 ARB OPPORTUNITY FOUND: Buy Kalshi @ {:.3}, Sell Poly @ {:.3} | Spread: {:.2}% ({})",
-                    k_ask, p_bid, spread * 100.0, matched.name
-                );
+    /// Record a pair's quotes and find its opportunities. With one venue
+    /// missing only that venue's own YES + NO is checked.
+    fn evaluate(
+        &self,
+        matched: &MatchedMarket,
+        poly: Option<(BinaryQuote, Depth, String)>,
+        kalshi: Option<BinaryQuote>,
+    ) -> Vec<Opportunity> {
+        let now = Utc::now();
+        let single_venue = poly.is_none() || kalshi.is_none();
+        let (poly, hash) = match poly {
+            Some((poly, depth, hash)) => {
+                if let Some(moves) = &self.moves {
+                    moves.observe(&matched.name, "polymarket", &poly, Some(&depth), now);
+                }
+                if self.books_path.is_some() {
+                    let snapshot = BookSnapshot { at: now, venue: "polymarket".to_string(), depth };
+                    self.books.lock().unwrap().insert(matched.name.clone(), snapshot);
+                }
+                (Some(poly), Some(hash))
             }
+            None => (None, None),
+        };
+        if let (Some(moves), Some(kalshi)) = (&self.moves, &kalshi) {
+            moves.observe(&matched.name, "kalshi", kalshi, None, now);
         }
 
-        // 2. Buy Polymarket, Sell Kalshi
-        if let (Some(p_ask), Some(k_bid)) = (poly_ask, kalshi_bid) {
-            let spread = k_bid - p_ask;
-            if spread > self.min_profit {
-                info!(
-                    "🔥 ARB OPPORTUNITY FOUND: Buy Poly @ {:.3}, Sell Kalshi @ {:.3} | Spread: {:.2}% ({})",
-                    p_ask, k_bid, spread * 100.0, matched.name
-                );
+        if let Some(tape) = &self.tape {
+            tape.record(&QuoteTick {
+                at: now,
+                market: matched.name.clone(),
+                poly: poly.unwrap_or_default(),
+                kalshi: kalshi.unwrap_or_default(),
+                legs: Some(matched.key()),
+            });
+        }
+        {
+            let mut quality = self.quality.lock().unwrap();
+            if let Some(hash) = &hash {
+                if !quality.observe_book(&matched.name, "polymarket", hash, now) {
+                    return Vec::new();
+                }
+            }
+            let sane = match (&poly, &kalshi) {
+                (Some(poly), Some(kalshi)) => quality.observe(&matched.name, poly, kalshi),
+                (Some(poly), None) => quality.observe_venue(&matched.name, "polymarket", poly),
+                (None, Some(kalshi)) => quality.observe_venue(&matched.name, "kalshi", kalshi),
+                (None, None) => false,
+            };
+            if !sane {
+                return Vec::new();
+            }
+        }
+
+        let (poly, kalshi) = (poly.unwrap_or_default(), kalshi.unwrap_or_default());
+        let found = find_opportunities(&matched.name, &poly, &kalshi, &self.fees, *self.min_profit.lock().unwrap(), now);
+        for opp in &found {
+            info!(
+                "🔥 ARB OPPORTUNITY FOUND{}: Buy YES {} @ {} + NO {} @ {} | Edge: {} ({} net of fees) ({})",
+                if single_venue { " (single venue)" } else { "" },
+                opp.yes_venue,
+                display::price(opp.yes_price),
+                opp.no_venue,
+                display::price(opp.no_price),
+                display::percent(opp.edge()),
+                display::percent(opp.net_edge()),
+                opp.market
+            );
+        }
+        found
+    }
+}
+
+/// Fetch outcomes for one venue over a pass.
+#[derive(Debug, Default)]
+struct FetchCount {
+    ok: usize,
+    failed: usize,
+    error: Option<String>,
+}
+
+impl FetchCount {
+    fn record<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.ok += 1;
+                Some(value)
+            }
+            Err(e) => {
+                self.failed += 1;
+                self.error = Some(e.to_string());
+                None
             }
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fees::Rates;
+
+    #[test]
+    fn test_checks_all_yes_no_combinations() {
+        // Poly YES 0.45/0.47 -> NO 0.53/0.55
+        let poly = BinaryQuote::from_yes(Some(0.45), Some(0.47));
+        let kalshi = BinaryQuote {
+            yes_bid: Some(0.55),
+            yes_ask: Some(0.57),
+            no_bid: Some(0.41),
+            no_ask: Some(0.43),
+        };
+
+        let fees = FeeSchedule::new().with_override("kalshi", "", Rates { maker: 0.0, taker: 0.0 });
+        let found = find_opportunities("BTC", &poly, &kalshi, &fees, 0.02, Utc::now());
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].yes_venue, found[0].no_venue), ("polymarket", "kalshi"));
+        assert!((found[0].edge() - 0.10).abs() < 1e-9);
+
+        // A YES-only comparison would have missed a NO-side dislocation
+        let kalshi_no_only = BinaryQuote { no_ask: Some(0.40), ..BinaryQuote::from_yes(Some(0.52), Some(0.60)) };
+        let found = find_opportunities("BTC", &BinaryQuote::from_yes(Some(0.50), Some(0.55)), &kalshi_no_only, &fees, 0.02, Utc::now());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].no_venue, "kalshi");
+
+        // Kalshi's taker fee eats a thin spread: 3% gross, ~1.7% fee on the NO leg
+        let thin = BinaryQuote { no_ask: Some(0.50), ..BinaryQuote::from_yes(Some(0.45), Some(0.60)) };
+        let poly = BinaryQuote::from_yes(Some(0.45), Some(0.47));
+        assert_eq!(find_opportunities("BTC", &poly, &thin, &fees, 0.02, Utc::now()).len(), 1);
+        let found = find_opportunities("BTC", &poly, &thin, &FeeSchedule::new(), 0.02, Utc::now());
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_repriced_edge_must_hold_up() {
+        let fees = FeeSchedule::new().with_override("kalshi", "", Rates { maker: 0.0, taker: 0.0 });
+        let detected = Opportunity::priced("BTC", ("polymarket", 0.47), ("kalshi", 0.43), &fees, Utc::now()).unwrap();
+        assert!((detected.net_edge() - 0.10).abs() < 1e-9);
+        assert!(Opportunity::priced("BTC", ("binance", 0.47), ("kalshi", 0.43), &fees, Utc::now()).is_err());
+
+        let poly = BinaryQuote::from_yes(Some(0.45), Some(0.49));
+        let kalshi = BinaryQuote { no_ask: Some(0.45), ..BinaryQuote::from_yes(Some(0.50), Some(0.56)) };
+        let fresh = detected.repriced(&poly, &kalshi, &fees, Utc::now()).unwrap();
+        assert_eq!((fresh.yes_price, fresh.no_price), (0.49, 0.45));
+        // 6% left of 10%
+        assert!(detected.holds_up(&fresh, 0.5));
+        assert!(!detected.holds_up(&fresh, 0.75));
 
-        Ok(())
+        // A leg with no ask left can't be repriced
+        let pulled = BinaryQuote { no_ask: None, ..kalshi };
+        assert!(detected.repriced(&poly, &pulled, &fees, Utc::now()).is_none());
     }
 }
@@ -3,10 +3,19 @@
 //! Handles the execution of trades on both platforms.
 
 use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
 use tracing::info;
 
+use super::detector::Opportunity;
+use crate::analysis::CaptureLog;
+use crate::fees::FeeSchedule;
 use crate::polymarket::PolymarketClient;
 use crate::kalshi::KalshiClient;
+use crate::utils::throttle::{self, OrderAction};
+use crate::utils::venue_constraints::{self, PairedOrder, Sizing, VenueConstraints};
+use crate::utils::{Fill, FillRouter};
 
 /// Executes arbitrage trades.
 pub struct TradeExecutor {
@@ -15,6 +24,12 @@ pub struct TradeExecutor {
     #[allow(dead_code)]
     kalshi_client: KalshiClient,
     dry_run: bool,
+    /// Detection-to-settlement record of every opportunity acted upon
+    capture: Option<Arc<Mutex<CaptureLog>>>,
+    /// Fills pushed by the venue user streams
+    fills: Option<Arc<FillRouter>>,
+    /// Fees charged on each leg
+    fees: FeeSchedule,
 }
 
 impl TradeExecutor {
@@ -24,29 +39,114 @@ impl TradeExecutor {
             poly_client,
             kalshi_client,
             dry_run,
+            capture: None,
+            fills: None,
+            fees: FeeSchedule::new(),
         }
     }
 
-    /// Execute an arbitrage trade.
+    /// Record executions in a capture log.
+    pub fn with_capture(mut self, capture: Arc<Mutex<CaptureLog>>) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Charge fees from a specific schedule.
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Confirm fills from the venue user streams instead of polling.
+    pub fn with_fills(mut self, router: Arc<FillRouter>) -> Self {
+        self.fills = Some(router);
+        self
+    }
+
+    /// Subscribe to fills before placing an order, then wait on it with
+    /// [`crate::utils::fills::wait_for_fill`].
+    pub fn subscribe_fills(&self) -> Option<broadcast::Receiver<Fill>> {
+        self.fills.as_ref().map(|router| router.subscribe())
+    }
+
+    /// Act on a detected opportunity. Returns the capture record ID when a
+    /// capture log is attached, for recording settlement later.
+    pub async fn execute(&self, opportunity: &Opportunity, sizing: Sizing) -> Result<Option<String>> {
+        let order = self
+            .execute_arb(
+                &opportunity.market,
+                opportunity.yes_venue,
+                opportunity.no_venue,
+                opportunity.yes_price,
+                opportunity.no_price,
+                sizing,
+            )
+            .await?;
+
+        Ok(self.record_capture(opportunity, &order))
+    }
+
+    /// Capture log mode of this executor's fills.
+    pub fn mode(&self) -> &'static str {
+        if self.dry_run { "dry_run" } else { "live" }
+    }
+
+    /// Record an arb executed on `opportunity` in the capture log. Returns
+    /// the record ID when a log is attached. A dry run fills at the
+    /// submitted prices; live legs are priced by their fills once their
+    /// orders are attached with [`CaptureLog::record_orders`].
+    pub fn record_capture(&self, opportunity: &Opportunity, order: &PairedOrder) -> Option<String> {
+        let capture = self.capture.as_ref()?;
+        let mut log = capture.lock().unwrap();
+        let id = log.record_detection(opportunity, self.mode(), order.size);
+        if self.dry_run {
+            log.record_execution(&id, order.price_a, order.price_b);
+        }
+        Some(id)
+    }
+
+    /// Execute an arbitrage trade: buy YES on `side_a` and NO on `side_b`.
+    ///
+    /// Both legs get the same contract count; a USD size is converted at the
+    /// combined price and the USD cost of each leg follows from its price.
     pub async fn execute_arb(
         &self,
-        _side_a: &str, // e.g., "Polymarket"
-        _side_b: &str, // e.g., "Kalshi"
-        _price_a: f64,
-        _price_b: f64,
-        _quantity: i32,
-    ) -> Result<()> {
+        market: &str,
+        side_a: &str, // e.g., "polymarket"
+        side_b: &str, // e.g., "kalshi"
+        price_a: f64,
+        price_b: f64,
+        sizing: Sizing,
+    ) -> Result<PairedOrder> {
+        // Both legs must be valid on their venue at the same size
+        let order = venue_constraints::fit_pair(
+            &VenueConstraints::for_venue(side_a)?,
+            &VenueConstraints::for_venue(side_b)?,
+            price_a,
+            price_b,
+            sizing.contracts(price_a + price_b),
+        )?;
+        let fees = self.fees.arb_fee(market, (side_a, order.price_a), (side_b, order.price_b), order.size);
+        let net_profit = order.size - order.cost() - fees;
         if self.dry_run {
-            info!("DRY RUN: Executing arbitrage trade...");
-            return Ok(());
+            info!(
+                "DRY RUN: Executing arbitrage trade: {} x YES {} @ {:.2} / NO {} @ {:.2} (${:.2} + ${:.2} fees, net ${:.2})",
+                order.size, side_a, order.price_a, side_b, order.price_b, order.cost(), fees, net_profit
+            );
+            return Ok(order);
         }
 
+        // The clients take each leg's slot as it goes out; make sure both
+        // have one first so the second leg isn't refused after the first
+        throttle::global().check_all(&[(side_a, OrderAction::Place), (side_b, OrderAction::Place)], Instant::now())?;
+
         // TODO: Implementation for real trade execution
         // 1. Submit Buy order
         // 2. Submit Sell order (almost) simultaneously
-        // 3. Monitor for fills
+        // 3. Confirm fills via subscribe_fills() / wait_for_fill
+        // 4. Attach both order IDs to the capture record (record_orders)
         
         info!("Real trade execution not yet implemented - safety first!");
-        Ok(())
+        Ok(order)
     }
 }
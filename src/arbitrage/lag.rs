@@ -0,0 +1,154 @@
+//! Latency arbitrage signal.
+//!
+//! Kalshi often reprices a pair before Polymarket does. Recent one-minute
+//! Kalshi candles of each matched ticker give a short-term fair value (the
+//! latest close) and momentum (how far it moved over the window). When
+//! Kalshi has moved by at least the threshold and Polymarket's mid still
+//! sits that far behind, in the direction of the move, Polymarket is
+//! lagging: a chance to trade there before it catches up, rather than a
+//! static spread between two settled prices.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+
+use crate::display;
+use crate::kalshi::types::KalshiCandlestick;
+
+/// Minutes of Kalshi candles the momentum is measured over.
+pub const LAG_WINDOW_MINUTES: i64 = 5;
+
+/// Kalshi's short-term view of a market.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairValue {
+    /// Latest YES close (0.0-1.0)
+    pub fair: f64,
+    /// Change of the YES close over the candles
+    pub momentum: f64,
+    /// End of the latest candle
+    pub at: DateTime<Utc>,
+}
+
+/// Fair value and momentum from candles, oldest to newest or not. None
+/// without two candles that have a close.
+pub fn fair_value(candles: &[KalshiCandlestick]) -> Option<FairValue> {
+    let mut closes: Vec<(i64, f64)> = candles.iter().filter_map(|c| Some((c.end_period_ts, c.close()?))).collect();
+    closes.sort_by_key(|(ts, _)| *ts);
+    if closes.len() < 2 {
+        return None;
+    }
+    let (&(_, first), &(last_ts, last)) = (closes.first()?, closes.last()?);
+    Some(FairValue { fair: last, momentum: last - first, at: Utc.timestamp_opt(last_ts, 0).single()? })
+}
+
+/// Polymarket trailing a Kalshi move.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LagSignal {
+    /// Matched pair name
+    pub market: String,
+    pub kalshi_ticker: String,
+    pub kalshi_fair: f64,
+    pub kalshi_momentum: f64,
+    pub poly_mid: f64,
+    /// Kalshi fair value less the Polymarket mid
+    pub lag: f64,
+    pub at: DateTime<Utc>,
+}
+
+impl LagSignal {
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: Kalshi {} ({}{} in {}m), Polymarket still {} - {} YES on Polymarket",
+            self.market,
+            display::price(self.kalshi_fair),
+            if self.kalshi_momentum >= 0.0 { "+" } else { "-" },
+            display::price(self.kalshi_momentum.abs()),
+            LAG_WINDOW_MINUTES,
+            display::price(self.poly_mid),
+            if self.lag > 0.0 { "buy" } else { "sell" },
+        )
+    }
+}
+
+/// Flags markets where Polymarket lags, once per episode: a market is
+/// flagged again only after it has caught up.
+#[derive(Debug, Clone)]
+pub struct LagWatch {
+    /// Smallest Kalshi move and Polymarket gap that count (0 = off)
+    threshold: f64,
+    lagging: HashSet<String>,
+}
+
+impl LagWatch {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold, lagging: HashSet::new() }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.threshold > 0.0
+    }
+
+    /// Compare Kalshi's fair value for `market` with the Polymarket mid.
+    /// Returns a signal when the market starts lagging.
+    pub fn check(&mut self, market: &str, kalshi_ticker: &str, fair: &FairValue, poly_mid: f64) -> Option<LagSignal> {
+        let lag = fair.fair - poly_mid;
+        let lagging = self.is_enabled()
+            && fair.momentum.abs() >= self.threshold
+            && lag.abs() >= self.threshold
+            && lag.signum() == fair.momentum.signum();
+        if !lagging {
+            self.lagging.remove(market);
+            return None;
+        }
+        if !self.lagging.insert(market.to_string()) {
+            return None;
+        }
+        Some(LagSignal {
+            market: market.to_string(),
+            kalshi_ticker: kalshi_ticker.to_string(),
+            kalshi_fair: fair.fair,
+            kalshi_momentum: fair.momentum,
+            poly_mid,
+            lag,
+            at: fair.at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kalshi::types::KalshiOhlc;
+
+    fn candle(minute: i64, bid: i32, ask: i32) -> KalshiCandlestick {
+        let close = |c| KalshiOhlc { close: Some(c), ..KalshiOhlc::default() };
+        KalshiCandlestick { end_period_ts: 1_767_000_000 + minute * 60, yes_bid: close(bid), yes_ask: close(ask), ..Default::default() }
+    }
+
+    #[test]
+    fn test_polymarket_lagging_a_kalshi_move_is_flagged_once() {
+        // Kalshi rallies from 0.45 to 0.55 mid over five minutes
+        let candles = vec![candle(4, 54, 56), candle(0, 44, 46), candle(2, 49, 51)];
+        let fair = fair_value(&candles).unwrap();
+        assert!((fair.fair - 0.55).abs() < 1e-9 && (fair.momentum - 0.10).abs() < 1e-9);
+        assert_eq!(fair.at.timestamp(), 1_767_000_240);
+        assert_eq!(fair_value(&candles[..1]), None);
+
+        let mut watch = LagWatch::new(0.03);
+        // Polymarket already followed
+        assert_eq!(watch.check("BTC", "KXBTC-1", &fair, 0.54), None);
+        // Polymarket still near the old price
+        let signal = watch.check("BTC", "KXBTC-1", &fair, 0.47).unwrap();
+        assert!((signal.lag - 0.08).abs() < 1e-9);
+        assert!(signal.summary().contains("buy YES on Polymarket"));
+        // Still lagging: not flagged again until it catches up
+        assert_eq!(watch.check("BTC", "KXBTC-1", &fair, 0.48), None);
+        assert_eq!(watch.check("BTC", "KXBTC-1", &fair, 0.55), None);
+        assert!(watch.check("BTC", "KXBTC-1", &fair, 0.47).is_some());
+
+        // A gap against the move is a static spread, not a lag
+        let falling = FairValue { fair: 0.45, momentum: -0.10, at: fair.at };
+        assert_eq!(watch.check("ETH", "KXETH-1", &falling, 0.40), None);
+        assert!(LagWatch::new(0.0).check("ETH", "KXETH-1", &falling, 0.60).is_none());
+    }
+}
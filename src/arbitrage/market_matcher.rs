@@ -8,23 +8,64 @@
 //!
 //! For arbitrage, we compare implied probabilities between platforms.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::utils::{state, MarketId};
+
+/// Pairs found by the last universe refresh, so a restarted bot trades
+/// them before its first refresh.
+pub const MATCHED_MARKETS_FILE: &str = "data/state/matched_markets.json";
+
+/// Market family of a Kalshi ticker or market name: the series prefix,
+/// e.g. "KXBTCD-26JAN05-T100000" -> "KXBTCD", or else the first word,
+/// e.g. "ETH Up/Down" -> "ETH".
+pub fn family(market: &str) -> String {
+    market.split(|c: char| c.is_whitespace() || c == '-').next().unwrap_or("").to_uppercase()
+}
+
 /// Represents a matched pair of markets on different platforms.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchedMarket {
     /// Human-readable name for the market pair.
     pub name: String,
-    /// Polymarket token ID (the clobTokenId for YES outcome).
-    pub polymarket_id: String,
-    /// Kalshi market ticker.
-    pub kalshi_ticker: String,
+    /// Polymarket market (the clobTokenId for YES outcome).
+    pub polymarket: MarketId,
+    /// Kalshi market.
+    pub kalshi: MarketId,
+}
+
+impl MatchedMarket {
+    /// Pair a Polymarket YES token with a Kalshi ticker.
+    pub fn new(name: &str, token_id: &str, kalshi_ticker: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            polymarket: MarketId::polymarket(token_id),
+            kalshi: MarketId::kalshi(kalshi_ticker),
+        }
+    }
+
+    /// Both legs, which together identify the pair.
+    pub fn key(&self) -> (MarketId, MarketId) {
+        (self.polymarket.clone(), self.kalshi.clone())
+    }
+
+    /// Whether either leg is `market`.
+    pub fn involves(&self, market: &MarketId) -> bool {
+        &self.polymarket == market || &self.kalshi == market
+    }
+
+    /// Family of the pair: the series of its Kalshi ticker, which names
+    /// the underlying however the pair itself is worded.
+    pub fn family(&self) -> String {
+        family(&self.kalshi.native)
+    }
 }
 
 /// Market matcher that maps equivalent markets.
 pub struct MarketMatcher {
-    /// Map from Polymarket ID to matched market.
-    matches: HashMap<String, MatchedMarket>,
+    /// Matched markets by both legs, since one market can be in several pairs.
+    matches: HashMap<(MarketId, MarketId), MatchedMarket>,
 }
 
 impl MarketMatcher {
@@ -42,58 +83,89 @@ impl MarketMatcher {
         // ---------------------------------------------------------------------
 
         // 1. Bitcoin Up or Down - 3PM ET (resolves 4PM ET)
-        let btc_3pm = MatchedMarket {
-            name: "BTC Up/Down 3PM ET (Jan 5)".to_string(),
-            polymarket_id: "19624172204178867270299534492363892804243098884958805437588142691739650752818".to_string(),
-            // Using 5pm EST Kalshi market for comparison
-            kalshi_ticker: "KXBTCD-26JAN0517-T94249.99".to_string(),
-        };
-        matches.insert(btc_3pm.polymarket_id.clone(), btc_3pm);
+        // Using 5pm EST Kalshi market for comparison
+        let btc_3pm = MatchedMarket::new(
+            "BTC Up/Down 3PM ET (Jan 5)",
+            "19624172204178867270299534492363892804243098884958805437588142691739650752818",
+            "KXBTCD-26JAN0517-T94249.99",
+        );
+        matches.insert(btc_3pm.key(), btc_3pm);
 
         // 2. Bitcoin Up or Down - 5PM ET (resolves 6PM ET)
-        let btc_5pm = MatchedMarket {
-            name: "BTC Up/Down 5PM ET (Jan 5)".to_string(),
-            polymarket_id: "64331692285920497167043827511734089895966734302171910924386164102158120192515".to_string(),
-            kalshi_ticker: "KXBTCD-26JAN0517-T94249.99".to_string(),
-        };
-        matches.insert(btc_5pm.polymarket_id.clone(), btc_5pm);
+        let btc_5pm = MatchedMarket::new(
+            "BTC Up/Down 5PM ET (Jan 5)",
+            "64331692285920497167043827511734089895966734302171910924386164102158120192515",
+            "KXBTCD-26JAN0517-T94249.99",
+        );
+        matches.insert(btc_5pm.key(), btc_5pm);
 
         // 3. Bitcoin Up or Down - 8PM ET (resolves 9PM ET)
-        let btc_8pm = MatchedMarket {
-            name: "BTC Up/Down 8PM ET (Jan 5)".to_string(),
-            polymarket_id: "63501553680907011398404492052704199683744111807521098612450074584385523964810".to_string(),
-            kalshi_ticker: "KXBTCD-26JAN0517-T94249.99".to_string(),
-        };
-        matches.insert(btc_8pm.polymarket_id.clone(), btc_8pm);
+        let btc_8pm = MatchedMarket::new(
+            "BTC Up/Down 8PM ET (Jan 5)",
+            "63501553680907011398404492052704199683744111807521098612450074584385523964810",
+            "KXBTCD-26JAN0517-T94249.99",
+        );
+        matches.insert(btc_8pm.key(), btc_8pm);
 
         // 4. Bitcoin Up or Down - 11PM ET (resolves 12AM ET next day)
-        let btc_11pm = MatchedMarket {
-            name: "BTC Up/Down 11PM ET (Jan 5)".to_string(),
-            polymarket_id: "11322761507222986303977493587384536158539335638025200075639546051812934376948".to_string(),
-            kalshi_ticker: "KXBTCD-26JAN0517-T94249.99".to_string(),
-        };
-        matches.insert(btc_11pm.polymarket_id.clone(), btc_11pm);
+        let btc_11pm = MatchedMarket::new(
+            "BTC Up/Down 11PM ET (Jan 5)",
+            "11322761507222986303977493587384536158539335638025200075639546051812934376948",
+            "KXBTCD-26JAN0517-T94249.99",
+        );
+        matches.insert(btc_11pm.key(), btc_11pm);
 
         Self { matches }
     }
 
+    /// Create a matcher with no pairs (e.g. when the universe comes from config).
+    pub fn empty() -> Self {
+        Self { matches: HashMap::new() }
+    }
+
+    /// Built-in pairs plus those the last universe refresh saved, for tools
+    /// that run without a detector.
+    pub fn known() -> Self {
+        let mut matcher = Self::new();
+        for pair in state::load::<Vec<MatchedMarket>>(MATCHED_MARKETS_FILE) {
+            matcher.add_match(pair);
+        }
+        matcher
+    }
+
     /// Get all matched markets.
     pub fn get_all(&self) -> Vec<&MatchedMarket> {
         self.matches.values().collect()
     }
 
-    /// Get a matched market by Polymarket ID.
-    pub fn get_by_polymarket(&self, id: &str) -> Option<&MatchedMarket> {
-        self.matches.get(id)
+    /// Every matched pair with `market` as a leg.
+    pub fn pairs_with(&self, market: &MarketId) -> Vec<&MatchedMarket> {
+        self.matches.values().filter(|m| m.involves(market)).collect()
+    }
+
+    /// Whether `market` is a leg of any matched pair.
+    pub fn is_matched(&self, market: &MarketId) -> bool {
+        self.matches.values().any(|m| m.involves(market))
     }
 
-    /// Get a matched market by Kalshi ticker.
-    pub fn get_by_kalshi(&self, ticker: &str) -> Option<&MatchedMarket> {
-        self.matches.values().find(|m| m.kalshi_ticker == ticker)
+    /// Whether this exact pair is matched.
+    pub fn contains(&self, pair: &MatchedMarket) -> bool {
+        self.matches.contains_key(&pair.key())
     }
 
-    /// Add a new matched market.
+    /// Add a new matched market, replacing one with the same legs.
     pub fn add_match(&mut self, matched: MatchedMarket) {
-        self.matches.insert(matched.polymarket_id.clone(), matched);
+        self.matches.insert(matched.key(), matched);
+    }
+
+    /// Keep only the matched markets for which `keep` returns true.
+    pub fn retain(&mut self, mut keep: impl FnMut(&MatchedMarket) -> bool) {
+        self.matches.retain(|_, m| keep(m));
+    }
+}
+
+impl Default for MarketMatcher {
+    fn default() -> Self {
+        Self::new()
     }
 }
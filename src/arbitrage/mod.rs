@@ -2,10 +2,24 @@
 //!
 //! Contains logic for detecting and executing arbitrage opportunities.
 
+pub mod cooldown;
 pub mod detector;
 pub mod executor;
+pub mod lag;
 pub mod market_matcher;
+pub mod moves;
+pub mod mutes;
+pub mod prime;
+pub mod quality;
+pub mod universe;
+pub mod venue_health;
 
-pub use detector::ArbitrageDetector;
+pub use cooldown::Cooldowns;
+pub use detector::{ArbitrageDetector, BinaryQuote, Opportunity};
 pub use executor::TradeExecutor;
+pub use lag::{LagSignal, LagWatch};
 pub use market_matcher::MarketMatcher;
+pub use moves::{MarketMove, MoveDetector};
+pub use mutes::MuteList;
+pub use universe::SeriesUniverse;
+pub use venue_health::{VenueHealth, VenueOutage};
@@ -0,0 +1,189 @@
+//! Market move detection.
+//!
+//! Each detection pass diffs a market's top of book against the previous
+//! pass on the same venue. A best bid or ask jumping by at least the jump
+//! threshold, or the resting size near the top shrinking by at least the
+//! pull threshold (liquidity pulled), is a "market moving" event. Events
+//! are broadcast to subscribers, e.g. the detector loop re-checking sooner
+//! while markets move, or a quoting strategy stepping back.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+
+use super::detector::BinaryQuote;
+use crate::display;
+use crate::paper_trading::book_fill::{Depth, Level};
+
+/// Buffered moves per subscriber before slow receivers start lagging.
+const MOVE_CHANNEL_CAPACITY: usize = 256;
+
+/// Book levels per side counted as liquidity near the top.
+const TOP_LEVELS: usize = 3;
+
+/// What changed between two snapshots of a book.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MoveKind {
+    /// The best YES bid or ask jumped
+    TopJump { side: String, from: f64, to: f64 },
+    /// Resting size near the top shrank
+    LiquidityPull { from: f64, to: f64 },
+}
+
+/// A sudden change in one venue's book for a matched pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketMove {
+    /// Matched pair name
+    pub market: String,
+    pub venue: String,
+    #[serde(flatten)]
+    pub kind: MoveKind,
+    pub at: DateTime<Utc>,
+}
+
+impl MarketMove {
+    pub fn summary(&self) -> String {
+        match &self.kind {
+            MoveKind::TopJump { side, from, to } => format!(
+                "{} {} {} jumped {} → {}",
+                self.market, self.venue, side, display::price(*from), display::price(*to)
+            ),
+            MoveKind::LiquidityPull { from, to } => format!(
+                "{} {} liquidity pulled: {:.0} → {:.0} contracts near the top",
+                self.market, self.venue, from, to
+            ),
+        }
+    }
+}
+
+/// Top of book kept from the previous pass.
+#[derive(Debug, Clone, Copy)]
+struct Top {
+    bid: Option<f64>,
+    ask: Option<f64>,
+    liquidity: Option<f64>,
+}
+
+/// Diffs consecutive book snapshots and broadcasts the moves found.
+pub struct MoveDetector {
+    /// Smallest price jump of the best bid or ask that counts (0 = off)
+    jump: f64,
+    /// Smallest share of near-top size pulled that counts (0 = off)
+    pull: f64,
+    last: Mutex<HashMap<(String, String), Top>>,
+    sender: broadcast::Sender<MarketMove>,
+}
+
+impl MoveDetector {
+    pub fn new(jump: f64, pull: f64) -> Self {
+        let (sender, _) = broadcast::channel(MOVE_CHANNEL_CAPACITY);
+        Self { jump, pull, last: Mutex::new(HashMap::new()), sender }
+    }
+
+    /// Receive moves as they are detected.
+    pub fn subscribe(&self) -> broadcast::Receiver<MarketMove> {
+        self.sender.subscribe()
+    }
+
+    /// Compare `venue`'s latest quote on `market`, and its depth when
+    /// known, with the previous one. Returns and broadcasts the moves.
+    pub fn observe(
+        &self,
+        market: &str,
+        venue: &str,
+        quote: &BinaryQuote,
+        depth: Option<&Depth>,
+        now: DateTime<Utc>,
+    ) -> Vec<MarketMove> {
+        let top = Top { bid: quote.yes_bid, ask: quote.yes_ask, liquidity: depth.map(near_top_size) };
+        let previous = self.last.lock().unwrap().insert((market.to_string(), venue.to_string()), top);
+        let Some(previous) = previous else {
+            return Vec::new();
+        };
+
+        let mut kinds = Vec::new();
+        if self.jump > 0.0 {
+            for (side, from, to) in [("bid", previous.bid, top.bid), ("ask", previous.ask, top.ask)] {
+                if let (Some(from), Some(to)) = (from, to) {
+                    if (to - from).abs() >= self.jump - 1e-9 {
+                        kinds.push(MoveKind::TopJump { side: side.to_string(), from, to });
+                    }
+                }
+            }
+        }
+        if self.pull > 0.0 {
+            if let (Some(from), Some(to)) = (previous.liquidity, top.liquidity) {
+                if from > 0.0 && (from - to) / from >= self.pull {
+                    kinds.push(MoveKind::LiquidityPull { from, to });
+                }
+            }
+        }
+
+        let moves: Vec<MarketMove> = kinds
+            .into_iter()
+            .map(|kind| MarketMove { market: market.to_string(), venue: venue.to_string(), kind, at: now })
+            .collect();
+        for m in &moves {
+            // No subscribers is fine
+            let _ = self.sender.send(m.clone());
+        }
+        moves
+    }
+
+    /// Forget markets no longer watched.
+    pub fn retain(&self, markets: &[String]) {
+        self.last.lock().unwrap().retain(|(market, _), _| markets.contains(market));
+    }
+}
+
+/// Contracts resting on the best few levels of both sides.
+fn near_top_size(depth: &Depth) -> f64 {
+    let side = |levels: &[Level]| levels.iter().take(TOP_LEVELS).map(|l| l.size).sum::<f64>();
+    side(&depth.bids) + side(&depth.asks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn depth(bid_size: f64, ask_size: f64) -> Depth {
+        Depth::new(vec![Level { price: 0.48, size: bid_size }], vec![Level { price: 0.50, size: ask_size }])
+    }
+
+    #[test]
+    fn test_jumps_and_pulls_are_broadcast() {
+        let detector = MoveDetector::new(0.05, 0.5);
+        let mut receiver = detector.subscribe();
+        let now = Utc::now();
+
+        // The first snapshot has nothing to compare with
+        let quiet = BinaryQuote::from_yes(Some(0.48), Some(0.50));
+        assert!(detector.observe("BTC", "polymarket", &quiet, Some(&depth(100.0, 100.0)), now).is_empty());
+        // Small drift and a modest size change are not moves
+        let drift = BinaryQuote::from_yes(Some(0.49), Some(0.51));
+        assert!(detector.observe("BTC", "polymarket", &drift, Some(&depth(80.0, 70.0)), now).is_empty());
+
+        // The ask jumps 6¢ and most of the size is pulled
+        let jumped = BinaryQuote::from_yes(Some(0.49), Some(0.57));
+        let moves = detector.observe("BTC", "polymarket", &jumped, Some(&depth(20.0, 30.0)), now);
+        assert_eq!(
+            moves.iter().map(|m| m.kind.clone()).collect::<Vec<_>>(),
+            vec![
+                MoveKind::TopJump { side: "ask".to_string(), from: 0.51, to: 0.57 },
+                MoveKind::LiquidityPull { from: 150.0, to: 50.0 },
+            ]
+        );
+        assert_eq!(receiver.try_recv().unwrap(), moves[0]);
+        assert_eq!(receiver.try_recv().unwrap(), moves[1]);
+
+        // Venues are diffed separately, and quotes without depth skip the pull check
+        let kalshi = BinaryQuote::from_yes(Some(0.40), Some(0.45));
+        assert!(detector.observe("BTC", "kalshi", &kalshi, None, now).is_empty());
+        let moves = detector.observe("BTC", "kalshi", &BinaryQuote::from_yes(Some(0.30), Some(0.45)), None, now);
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].summary().contains("bid jumped"));
+    }
+}
@@ -0,0 +1,183 @@
+//! Muted and snoozed markets.
+//!
+//! A matched pair known to be broken (e.g. the venues resolve on different
+//! criteria) can be muted, which keeps it out of detection altogether, or
+//! snoozed, which holds back its alerts for a few hours while it still
+//! trades. Either way the pair stays matched. The list lives in
+//! `data/state/mutes.json`, shared by the bot, the TUI and `polybot mutes`.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use crate::utils::state;
+
+/// Mute list shared with the bot, TUI and CLI.
+pub const MUTES_FILE: &str = "data/state/mutes.json";
+
+/// Mute and snooze settings for one market.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketMute {
+    /// Matched pair name
+    pub market: String,
+    /// Left out of detection until unmuted
+    pub muted: bool,
+    /// Alerts held back until then
+    pub snoozed_until: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+    /// Who last changed it
+    pub by: String,
+    pub at: DateTime<Utc>,
+}
+
+impl MarketMute {
+    pub fn is_snoozed(&self, now: DateTime<Utc>) -> bool {
+        self.snoozed_until.is_some_and(|until| until > now)
+    }
+
+    /// No alerts for this market right now.
+    pub fn is_silenced(&self, now: DateTime<Utc>) -> bool {
+        self.muted || self.is_snoozed(now)
+    }
+}
+
+/// Persistent list of muted and snoozed markets.
+#[derive(Debug)]
+pub struct MuteList {
+    path: String,
+    /// Serializes read-modify-write from concurrent tasks
+    lock: Mutex<()>,
+}
+
+impl MuteList {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string(), lock: Mutex::new(()) }
+    }
+
+    /// Every entry, including lapsed snoozes.
+    pub fn entries(&self) -> Vec<MarketMute> {
+        state::load(&self.path)
+    }
+
+    /// Entries muted or snoozed at `now`.
+    pub fn active(&self, now: DateTime<Utc>) -> Vec<MarketMute> {
+        self.entries().into_iter().filter(|m| m.is_silenced(now)).collect()
+    }
+
+    pub fn get(&self, market: &str) -> Option<MarketMute> {
+        self.entries().into_iter().find(|m| m.market == market)
+    }
+
+    /// Markets left out of detection.
+    pub fn muted(&self) -> BTreeSet<String> {
+        self.entries().into_iter().filter(|m| m.muted).map(|m| m.market).collect()
+    }
+
+    /// Markets whose alerts are held back at `now`.
+    pub fn silenced(&self, now: DateTime<Utc>) -> BTreeSet<String> {
+        self.active(now).into_iter().map(|m| m.market).collect()
+    }
+
+    /// Keep `market` out of detection until unmuted.
+    pub fn mute(&self, market: &str, by: &str, reason: Option<&str>, now: DateTime<Utc>) -> Result<MarketMute> {
+        self.update(market, by, reason, now, |m| m.muted = true)
+    }
+
+    /// Hold back alerts for `market` for `hours`.
+    pub fn snooze(&self, market: &str, hours: u64, by: &str, reason: Option<&str>, now: DateTime<Utc>) -> Result<MarketMute> {
+        let until = now + Duration::hours(hours as i64);
+        self.update(market, by, reason, now, |m| m.snoozed_until = Some(until))
+    }
+
+    /// Put `market` back into detection. False if it wasn't muted.
+    pub fn unmute(&self, market: &str, by: &str, now: DateTime<Utc>) -> Result<bool> {
+        if !self.get(market).is_some_and(|m| m.muted) {
+            return Ok(false);
+        }
+        self.update(market, by, None, now, |m| m.muted = false)?;
+        Ok(true)
+    }
+
+    /// Resume alerts for `market`. False if it wasn't snoozed.
+    pub fn unsnooze(&self, market: &str, by: &str, now: DateTime<Utc>) -> Result<bool> {
+        if !self.get(market).is_some_and(|m| m.is_snoozed(now)) {
+            return Ok(false);
+        }
+        self.update(market, by, None, now, |m| m.snoozed_until = None)?;
+        Ok(true)
+    }
+
+    /// Apply `change` to the entry for `market`, dropping entries (and lapsed
+    /// snoozes) that no longer silence anything.
+    fn update(
+        &self,
+        market: &str,
+        by: &str,
+        reason: Option<&str>,
+        now: DateTime<Utc>,
+        change: impl FnOnce(&mut MarketMute),
+    ) -> Result<MarketMute> {
+        let _guard = self.lock.lock().unwrap();
+        let mut entries = self.entries();
+        let index = match entries.iter().position(|m| m.market == market) {
+            Some(index) => index,
+            None => {
+                entries.push(MarketMute {
+                    market: market.to_string(),
+                    muted: false,
+                    snoozed_until: None,
+                    reason: None,
+                    by: by.to_string(),
+                    at: now,
+                });
+                entries.len() - 1
+            }
+        };
+        let entry = &mut entries[index];
+        change(entry);
+        entry.by = by.to_string();
+        entry.at = now;
+        if let Some(reason) = reason {
+            entry.reason = Some(reason.to_string());
+        }
+        let entry = entry.clone();
+        entries.retain(|m| m.is_silenced(now));
+        state::save(&self.path, &entries)?;
+        Ok(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mute_and_snooze() {
+        let path = std::env::temp_dir().join(format!("mutes-{}.json", uuid::Uuid::new_v4()));
+        let mutes = MuteList::new(path.to_str().unwrap());
+        let now = Utc::now();
+
+        mutes.mute("BTC 100k", "ops", Some("Kalshi settles on a different index"), now).unwrap();
+        let snoozed = mutes.snooze("ETH 5k", 4, "ops", None, now).unwrap();
+        assert_eq!(snoozed.snoozed_until, Some(now + Duration::hours(4)));
+        assert_eq!(mutes.muted(), BTreeSet::from(["BTC 100k".to_string()]));
+        assert_eq!(mutes.silenced(now).len(), 2);
+        // Snoozes lapse on their own
+        assert_eq!(mutes.silenced(now + Duration::hours(5)), BTreeSet::from(["BTC 100k".to_string()]));
+
+        // Snoozing a muted market keeps its reason and mute
+        let both = mutes.snooze("BTC 100k", 1, "ops", None, now).unwrap();
+        assert!(both.muted && both.is_snoozed(now));
+        assert_eq!(both.reason.as_deref(), Some("Kalshi settles on a different index"));
+
+        assert!(mutes.unmute("BTC 100k", "ops", now).unwrap());
+        assert!(!mutes.unmute("BTC 100k", "ops", now).unwrap());
+        assert!(mutes.muted().is_empty());
+        assert!(mutes.unsnooze("ETH 5k", "ops", now).unwrap());
+        assert!(mutes.unsnooze("BTC 100k", "ops", now).unwrap());
+        assert!(mutes.entries().is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,119 @@
+//! Warm start.
+//!
+//! Before the first detection pass every matched pair's Kalshi market and
+//! Polymarket book are fetched concurrently, so the first minutes after a
+//! restart aren't spent on cold caches and fresh connections one pair at a
+//! time. Priming fills the shared tick size cache, the saved order books
+//! and the move baseline, and drops pairs whose Kalshi market has already
+//! closed. Each subsystem reports how ready it is.
+
+use std::time::Duration;
+
+use crate::kalshi::types::KalshiMarket;
+
+/// Requests in flight per venue while priming.
+pub const PRIME_CONCURRENCY: usize = 8;
+
+/// Kalshi statuses after which a market no longer trades.
+const KALSHI_CLOSED: [&str; 4] = ["closed", "settled", "finalized", "determined"];
+
+/// Whether a Kalshi market has stopped trading, so its pair is dead.
+pub fn is_closed(market: &KalshiMarket) -> bool {
+    KALSHI_CLOSED.contains(&market.status.to_lowercase().as_str())
+}
+
+/// How much of one subsystem was primed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Readiness {
+    pub name: String,
+    pub ok: usize,
+    pub failed: usize,
+    /// First failure, for the log
+    pub error: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Readiness {
+    pub fn new(name: &str, elapsed: Duration) -> Self {
+        Self { name: name.to_string(), elapsed, ..Self::default() }
+    }
+
+    /// Count a fetch, returning its value on success.
+    pub fn record<T>(&mut self, result: anyhow::Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.ok += 1;
+                Some(value)
+            }
+            Err(e) => {
+                self.fail(&e.to_string());
+                None
+            }
+        }
+    }
+
+    pub fn fail(&mut self, error: &str) {
+        self.failed += 1;
+        self.error.get_or_insert_with(|| error.to_string());
+    }
+
+    /// Everything primed.
+    pub fn is_ready(&self) -> bool {
+        self.failed == 0
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{}: {}/{} in {:.1}s",
+            self.name,
+            self.ok,
+            self.ok + self.failed,
+            self.elapsed.as_secs_f64()
+        );
+        if let Some(error) = &self.error {
+            summary.push_str(&format!(" (first failure: {})", error));
+        }
+        summary
+    }
+}
+
+/// Outcome of priming.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrimeReport {
+    pub subsystems: Vec<Readiness>,
+    /// Pairs dropped because their Kalshi market has closed
+    pub dropped: Vec<String>,
+}
+
+impl PrimeReport {
+    /// Every subsystem fully primed.
+    pub fn is_ready(&self) -> bool {
+        self.subsystems.iter().all(Readiness::is_ready)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness_counts_fetches_and_keeps_the_first_failure() {
+        let mut books = Readiness::new("order books", Duration::from_millis(1500));
+        assert_eq!(books.record(Ok(1)), Some(1));
+        assert_eq!(books.record::<i32>(Err(anyhow::anyhow!("timed out"))), None);
+        books.fail("404");
+        assert!(!books.is_ready());
+        assert_eq!(books.summary(), "order books: 1/3 in 1.5s (first failure: timed out)");
+
+        let metadata = Readiness { ok: 2, ..Readiness::new("kalshi metadata", Duration::ZERO) };
+        let report = PrimeReport { subsystems: vec![metadata.clone()], dropped: Vec::new() };
+        assert!(report.is_ready());
+        assert!(!PrimeReport { subsystems: vec![metadata, books], dropped: Vec::new() }.is_ready());
+
+        let market = |status: &str| -> KalshiMarket {
+            serde_json::from_str(&format!(r#"{{"ticker": "KX-1", "status": "{}"}}"#, status)).unwrap()
+        };
+        assert!(!is_closed(&market("active")));
+        assert!(is_closed(&market("Settled")));
+    }
+}
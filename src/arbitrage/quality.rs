@@ -0,0 +1,275 @@
+//! Quote data-quality gate.
+//!
+//! A market only produces signals once its quotes have come back sane for
+//! a number of consecutive fetches: every price strictly between 0 and 1,
+//! no bid above its ask, at least one ask to trade against and a
+//! Polymarket book whose hash has changed within the staleness limit (a
+//! book frozen for that long is more likely a stuck feed than a resting
+//! arb). A bad quote or a failed fetch starts the warm-up over. Rejects are counted by
+//! reason for the metrics the bot writes to [`QUALITY_FILE`], along with
+//! how often opportunities evaporate between detection and execution.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use tracing::{debug, info, warn};
+
+use super::detector::BinaryQuote;
+
+/// Latest data-quality metrics, written by the running bot.
+pub const QUALITY_FILE: &str = "data/quality.json";
+
+/// Why a quote was not trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reject {
+    /// The quote fetch failed
+    FetchFailed,
+    /// A price at or outside 0-1
+    OutOfRange,
+    /// A bid above its ask
+    Crossed,
+    /// No ask on either outcome
+    Empty,
+    /// A book unchanged for longer than the staleness limit
+    Stale,
+}
+
+impl fmt::Display for Reject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Reject::FetchFailed => "fetch failed",
+            Reject::OutOfRange => "price out of range",
+            Reject::Crossed => "crossed book",
+            Reject::Empty => "empty book",
+            Reject::Stale => "stale book",
+        })
+    }
+}
+
+/// First problem with a venue's quote, if any.
+pub fn check(quote: &BinaryQuote) -> Option<Reject> {
+    let prices = [quote.yes_bid, quote.yes_ask, quote.no_bid, quote.no_ask];
+    if prices.iter().flatten().any(|p| !(*p > 0.0 && *p < 1.0)) {
+        return Some(Reject::OutOfRange);
+    }
+    let crossed = |bid: Option<f64>, ask: Option<f64>| bid.zip(ask).is_some_and(|(b, a)| b > a);
+    if crossed(quote.yes_bid, quote.yes_ask) || crossed(quote.no_bid, quote.no_ask) {
+        return Some(Reject::Crossed);
+    }
+    if quote.yes_ask.is_none() && quote.no_ask.is_none() {
+        return Some(Reject::Empty);
+    }
+    None
+}
+
+/// Reject counts since the bot started.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QualityMetrics {
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Consecutive sane quotes required
+    pub warmup: u32,
+    /// Markets checked that are not yet eligible
+    pub warming: Vec<String>,
+    /// Rejects by venue and reason, e.g. "kalshi crossed"
+    pub rejects: BTreeMap<String, u64>,
+    /// Rejects by market
+    pub by_market: BTreeMap<String, u64>,
+    /// Opportunities re-checked right before execution
+    #[serde(default)]
+    pub revalidated: u64,
+    /// Of those, the ones whose edge had evaporated
+    #[serde(default)]
+    pub evaporated: u64,
+    /// Evaporated opportunities by market
+    #[serde(default)]
+    pub evaporated_by_market: BTreeMap<String, u64>,
+}
+
+impl QualityMetrics {
+    pub fn total_rejects(&self) -> u64 {
+        self.rejects.values().sum()
+    }
+
+    /// Share of re-checked opportunities that evaporated before execution.
+    pub fn evaporation_rate(&self) -> f64 {
+        if self.revalidated > 0 { self.evaporated as f64 / self.revalidated as f64 } else { 0.0 }
+    }
+}
+
+/// Per-market warm-up streaks.
+#[derive(Debug, Clone)]
+pub struct QualityGate {
+    warmup: u32,
+    streaks: HashMap<String, u32>,
+    /// Longest a book may go unchanged; None = no limit
+    stale_after: Option<Duration>,
+    /// Last book hash per market and venue, and since when it has been seen
+    hashes: HashMap<(String, String), (String, DateTime<Utc>)>,
+    metrics: QualityMetrics,
+}
+
+impl QualityGate {
+    /// Require `warmup` consecutive sane quotes (at least one).
+    pub fn new(warmup: u32) -> Self {
+        let warmup = warmup.max(1);
+        Self {
+            warmup,
+            streaks: HashMap::new(),
+            stale_after: None,
+            hashes: HashMap::new(),
+            metrics: QualityMetrics { warmup, ..Default::default() },
+        }
+    }
+
+    /// Reject books whose hash hasn't changed for `secs` (0 = never).
+    pub fn with_stale_after(mut self, secs: u64) -> Self {
+        self.stale_after = (secs > 0).then(|| Duration::seconds(secs as i64));
+        self
+    }
+
+    /// Record the hash of `venue`'s book for `market` at `now`, returning
+    /// false (and rejecting the market) when it has stayed the same for
+    /// longer than the staleness limit. Empty hashes are not tracked.
+    pub fn observe_book(&mut self, market: &str, venue: &str, hash: &str, now: DateTime<Utc>) -> bool {
+        if hash.is_empty() {
+            return true;
+        }
+        let key = (market.to_string(), venue.to_string());
+        let since = match self.hashes.get(&key) {
+            Some((last, since)) if last == hash => *since,
+            _ => {
+                self.hashes.insert(key, (hash.to_string(), now));
+                now
+            }
+        };
+        if self.stale_after.is_some_and(|limit| now - since > limit) {
+            self.reject(market, venue, Reject::Stale);
+            return false;
+        }
+        true
+    }
+
+    /// Record both venues' quotes for `market`, returning whether it may
+    /// produce signals.
+    pub fn observe(&mut self, market: &str, poly: &BinaryQuote, kalshi: &BinaryQuote) -> bool {
+        let problem = [("polymarket", poly), ("kalshi", kalshi)]
+            .into_iter()
+            .find_map(|(venue, quote)| check(quote).map(|reason| (venue, reason)));
+        if let Some((venue, reason)) = problem {
+            self.reject(market, venue, reason);
+            return false;
+        }
+        self.advance(market)
+    }
+
+    /// Record one venue's quote for `market` while the other venue is
+    /// down. Shares the pair's warm-up streak.
+    pub fn observe_venue(&mut self, market: &str, venue: &str, quote: &BinaryQuote) -> bool {
+        if let Some(reason) = check(quote) {
+            self.reject(market, venue, reason);
+            return false;
+        }
+        self.advance(market)
+    }
+
+    /// Count a pre-execution re-check of an opportunity on `market`.
+    pub fn revalidated(&mut self, market: &str, held_up: bool) {
+        self.metrics.revalidated += 1;
+        if !held_up {
+            self.metrics.evaporated += 1;
+            *self.metrics.evaporated_by_market.entry(market.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// A quote fetch for `market` failed.
+    pub fn fetch_failed(&mut self, market: &str, venue: &str) {
+        self.reject(market, venue, Reject::FetchFailed);
+    }
+
+    pub fn is_eligible(&self, market: &str) -> bool {
+        self.streaks.get(market).is_some_and(|s| *s >= self.warmup)
+    }
+
+    /// Snapshot of the metrics.
+    pub fn metrics(&self) -> QualityMetrics {
+        let mut warming: Vec<String> =
+            self.streaks.iter().filter(|(_, s)| **s < self.warmup).map(|(m, _)| m.clone()).collect();
+        warming.sort();
+        QualityMetrics { updated_at: Some(Utc::now()), warming, ..self.metrics.clone() }
+    }
+
+    fn advance(&mut self, market: &str) -> bool {
+        let streak = self.streaks.entry(market.to_string()).or_insert(0);
+        *streak += 1;
+        if *streak == self.warmup && self.warmup > 1 {
+            info!("✅ {} warmed up after {} clean quotes", market, self.warmup);
+        }
+        *streak >= self.warmup
+    }
+
+    fn reject(&mut self, market: &str, venue: &str, reason: Reject) {
+        if self.streaks.insert(market.to_string(), 0).unwrap_or(0) >= self.warmup {
+            warn!("🧪 {} gated out: {} {}", market, venue, reason);
+        } else {
+            debug!("🧪 {} quote rejected: {} {}", market, venue, reason);
+        }
+        *self.metrics.rejects.entry(format!("{} {}", venue, reason)).or_insert(0) += 1;
+        *self.metrics.by_market.entry(market.to_string()).or_insert(0) += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warm_up_and_rejects() {
+        let good = BinaryQuote::from_yes(Some(0.45), Some(0.47));
+        assert_eq!(check(&good), None);
+        assert_eq!(check(&BinaryQuote::from_yes(Some(0.50), Some(0.48))), Some(Reject::Crossed));
+        assert_eq!(check(&BinaryQuote::from_yes(Some(0.0), Some(0.47))), Some(Reject::OutOfRange));
+        assert_eq!(check(&BinaryQuote { yes_ask: Some(1.0), ..good }), Some(Reject::OutOfRange));
+        assert_eq!(check(&BinaryQuote::default()), Some(Reject::Empty));
+        // A one-sided book is still tradable
+        assert_eq!(check(&BinaryQuote::from_yes(None, Some(0.47))), None);
+
+        let mut gate = QualityGate::new(3);
+        assert!(!gate.observe("BTC", &good, &good));
+        assert!(!gate.observe("BTC", &good, &good));
+        assert!(gate.observe("BTC", &good, &good));
+
+        // A crossed Kalshi book restarts the warm-up
+        let crossed = BinaryQuote::from_yes(Some(0.50), Some(0.48));
+        assert!(!gate.observe("BTC", &good, &crossed));
+        assert!(!gate.observe("BTC", &good, &good));
+        gate.fetch_failed("ETH", "polymarket");
+        assert!(!gate.is_eligible("ETH"));
+
+        let metrics = gate.metrics();
+        assert_eq!(metrics.total_rejects(), 2);
+        assert_eq!(metrics.rejects["kalshi crossed book"], 1);
+        assert_eq!(metrics.by_market["BTC"], 1);
+        assert_eq!(metrics.warming, vec!["BTC".to_string(), "ETH".to_string()]);
+
+        gate.revalidated("BTC", true);
+        gate.revalidated("BTC", false);
+        let metrics = gate.metrics();
+        assert_eq!((metrics.revalidated, metrics.evaporated), (2, 1));
+        assert_eq!(metrics.evaporation_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_stale_books_are_rejected() {
+        // A book frozen past the limit is rejected until its hash moves
+        let start = Utc::now();
+        let mut gate = QualityGate::new(1).with_stale_after(60);
+        assert!(gate.observe_book("BTC", "polymarket", "abc", start));
+        assert!(gate.observe_book("BTC", "polymarket", "abc", start + Duration::seconds(60)));
+        assert!(!gate.observe_book("BTC", "polymarket", "abc", start + Duration::seconds(61)));
+        assert!(gate.observe_book("BTC", "polymarket", "def", start + Duration::seconds(62)));
+        assert!(gate.observe_book("ETH", "polymarket", "", start + Duration::seconds(999)));
+        assert_eq!(gate.metrics().rejects["polymarket stale book"], 1);
+    }
+}
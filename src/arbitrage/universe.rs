@@ -0,0 +1,268 @@
+//! Detection universe module.
+//!
+//! Builds the set of tradable market pairs from configured Kalshi series
+//! (e.g. KXBTCD, KXETHD) instead of a hand-maintained list. Each refresh
+//! enumerates the series' open markets, attempts to pair them with a
+//! Polymarket market on the same underlying, strike and expiry, and drops
+//! pairs whose Kalshi market is no longer open. Only "above $X" strikes
+//! pair: "Up or Down" markets are priced against the opening price, which
+//! no Kalshi strike refers to, so buying one against the other is no hedge.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use tracing::{debug, info};
+
+use super::market_matcher::{MarketMatcher, MatchedMarket};
+use crate::analysis::ladder::{parse_strike, Rung, StrikeType};
+use crate::kalshi::types::KalshiMarket;
+use crate::kalshi::KalshiClient;
+use crate::polymarket::types::{Market, OutcomeEvent};
+use crate::polymarket::PolymarketClient;
+use crate::utils::clock::{system_clock, SharedClock};
+use crate::utils::{timezone, MarketId};
+
+/// Maximum distance between the two venues' expiries for a pair to match.
+const MAX_EXPIRY_GAP_MINUTES: i64 = 30;
+
+/// Largest difference between two strikes that are the same threshold:
+/// Kalshi writes "above $94,250" as a floor of 94,249.99.
+const STRIKE_TOLERANCE: f64 = 1.0;
+
+/// Search keywords for the underlying of a Kalshi series.
+pub fn keywords_for_series(series: &str) -> &'static [&'static str] {
+    let series = series.to_uppercase();
+    if series.starts_with("KXBTC") {
+        &["bitcoin", "btc"]
+    } else if series.starts_with("KXETH") {
+        &["ethereum", "eth"]
+    } else if series.starts_with("KXSOL") {
+        &["solana", "sol"]
+    } else if series.starts_with("KXXRP") {
+        &["xrp"]
+    } else {
+        &[]
+    }
+}
+
+/// Polymarket slug name for the underlying of a Kalshi series.
+pub fn slug_name_for_series(series: &str) -> Option<&'static str> {
+    keywords_for_series(series).first().copied()
+}
+
+/// Resolution time; unlabelled times are on the configured clock.
+fn parse_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    timezone::parse_time(value?, timezone::current())
+}
+
+/// Threshold of a Kalshi "above" strike, from its strike fields or else the
+/// ticker ("KXBTCD-26JAN0517-T94249.99"). None for ranges and "below" strikes.
+pub fn kalshi_strike(market: &KalshiMarket) -> Option<f64> {
+    if let Some(rung) = Rung::from_market(market) {
+        return (rung.strike_type == StrikeType::Above).then_some(rung.floor).flatten();
+    }
+    let (_, suffix) = market.ticker.rsplit_once('-')?;
+    suffix.strip_prefix('T')?.parse().ok()
+}
+
+/// Threshold of a Polymarket "above $X" market, from its question or its
+/// outcome title within the event. None for anything else, such as "Up or
+/// Down" markets.
+pub fn polymarket_strike(market: &Market) -> Option<f64> {
+    if !market.question.to_lowercase().contains("above") {
+        return None;
+    }
+    parse_strike(&market.question).or_else(|| {
+        let title = market.group_item_title.as_deref()?;
+        parse_strike(&format!("${}", title.trim_start_matches('$')))
+    })
+}
+
+/// Pair Kalshi series markets with Polymarket markets on the same
+/// underlying and strike whose expiry falls within the allowed gap.
+pub fn auto_match(series: &str, kalshi_markets: &[KalshiMarket], poly_markets: &[Market]) -> Vec<MatchedMarket> {
+    let keywords = keywords_for_series(series);
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+
+    let mut pairs = Vec::new();
+    for k in kalshi_markets {
+        let Some(k_close) = parse_time(k.close_time.as_deref()) else {
+            continue;
+        };
+        let Some(k_strike) = kalshi_strike(k) else {
+            debug!("Skipping {}: not an \"above\" strike", k.ticker);
+            continue;
+        };
+
+        let best = poly_markets
+            .iter()
+            // Categorical markets have no single YES side to pair with Kalshi
+            .filter(|p| p.active && !p.closed && p.is_binary())
+            .filter(|p| {
+                let question = p.question.to_lowercase();
+                keywords.iter().any(|kw| question.contains(kw))
+            })
+            .filter(|p| polymarket_strike(p).is_some_and(|strike| (strike - k_strike).abs() <= STRIKE_TOLERANCE))
+            .filter_map(|p| {
+                let p_end = parse_time(p.end_date_iso.as_deref())?;
+                let gap = (p_end - k_close).num_minutes().abs();
+                (gap <= MAX_EXPIRY_GAP_MINUTES).then_some((gap, p))
+            })
+            .min_by_key(|(gap, _)| *gap);
+
+        if let Some((_, p)) = best {
+            if let Some(token) = p.yes_token() {
+                pairs.push(MatchedMarket::new(&format!("{} ({})", p.question, k.ticker), &token.token_id, &k.ticker));
+            }
+        }
+    }
+    pairs
+}
+
+/// Refreshes the matcher from a list of Kalshi series.
+pub struct SeriesUniverse {
+    series: Vec<String>,
+    /// Time source for the window of events discovered
+    clock: SharedClock,
+}
+
+impl SeriesUniverse {
+    /// Create a universe over the given series tickers.
+    pub fn new(series: Vec<String>) -> Self {
+        Self { series, clock: system_clock() }
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Whether any series is configured.
+    pub fn is_empty(&self) -> bool {
+        self.series.is_empty()
+    }
+
+    /// Whether a Kalshi ticker belongs to one of our series.
+    pub fn owns(&self, kalshi_ticker: &str) -> bool {
+        self.series.iter().any(|s| kalshi_ticker.starts_with(&format!("{}-", s)))
+    }
+
+    /// Fetch open markets for every series and compute the current pairs,
+    /// along with the Polymarket events they were found in.
+    pub async fn discover(&self, kalshi: &KalshiClient, poly: &PolymarketClient) -> Result<(Vec<MatchedMarket>, Vec<OutcomeEvent>)> {
+        let coins: Vec<&str> = self.series.iter()
+            .filter_map(|s| slug_name_for_series(s))
+            .collect();
+        let events = poly.discover_crypto_events(&coins, self.clock.now()).await?;
+        let poly_markets: Vec<Market> = events.iter().flat_map(|e| e.markets.iter().cloned()).collect();
+        let mut pairs = Vec::new();
+
+        for series in &self.series {
+            let markets = kalshi.get_series_markets(series).await?;
+            debug!("Series {}: {} open markets", series, markets.len());
+            pairs.extend(auto_match(series, &markets, &poly_markets));
+        }
+        Ok((pairs, events))
+    }
+
+    /// Apply discovered pairs: add new ones and prune series pairs that
+    /// are no longer open. Manually configured pairs are left untouched.
+    pub fn apply(&self, matcher: &mut MarketMatcher, pairs: Vec<MatchedMarket>) -> (usize, usize) {
+        let live: HashSet<&MarketId> = pairs.iter().map(|p| &p.kalshi).collect();
+        let before = matcher.get_all().len();
+        matcher.retain(|m| !self.owns(&m.kalshi.native) || live.contains(&m.kalshi));
+        let removed = before - matcher.get_all().len();
+
+        let mut added = 0;
+        for pair in pairs {
+            if !matcher.contains(&pair) {
+                added += 1;
+            }
+            matcher.add_match(pair);
+        }
+
+        if added > 0 || removed > 0 {
+            info!("🌐 Universe refreshed: +{} new pairs, -{} expired", added, removed);
+        }
+        (added, removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::types::Token;
+
+    fn poly(question: &str, end: &str, token: &str) -> Market {
+        Market {
+            condition_id: format!("cond-{}", token),
+            question: question.to_string(),
+            description: None,
+            end_date_iso: Some(end.to_string()),
+            active: true,
+            closed: false,
+            tokens: vec![
+                Token { token_id: token.to_string(), outcome: "Up".to_string(), price: None },
+                Token { token_id: format!("{}-down", token), outcome: "Down".to_string(), price: None },
+            ],
+            neg_risk: false,
+            event_slug: None,
+            group_item_title: None,
+        }
+    }
+
+    #[test]
+    fn test_auto_match_by_underlying_strike_and_expiry() {
+        let kalshi = |ticker: &str| KalshiMarket {
+            ticker: ticker.to_string(),
+            close_time: Some("2026-01-05T22:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let kalshi_markets = vec![
+            kalshi("KXBTCD-26JAN0517-T94249.99"),
+            kalshi("KXBTCD-26JAN0517-T94749.99"),
+            // A range bucket has no "above" counterpart
+            kalshi("KXBTCD-26JAN0517-B94500"),
+        ];
+        let poly_markets = vec![
+            poly("Will Ethereum be above $94,250 on January 5?", "2026-01-05T22:00:00Z", "eth"),
+            poly("Will Bitcoin be above $94,250 on January 4?", "2026-01-04T22:00:00Z", "btc-yesterday"),
+            poly("Will Bitcoin be above $94,250 on January 5?", "2026-01-05T22:00:00Z", "btc94"),
+            poly("Will Bitcoin be above $96,000 on January 5?", "2026-01-05T22:00:00Z", "btc96"),
+            // Priced against the opening price: not the same threshold as any strike
+            poly("Bitcoin Up or Down - 4PM ET", "2026-01-05T22:00:00Z", "btc-up"),
+        ];
+
+        let pairs = auto_match("KXBTCD", &kalshi_markets, &poly_markets);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].polymarket, MarketId::polymarket("btc94"));
+        assert_eq!(pairs[0].kalshi, MarketId::kalshi("KXBTCD-26JAN0517-T94249.99"));
+
+        let mut titled = poly("Bitcoin above ___ on January 5?", "2026-01-05T22:00:00Z", "btc947");
+        titled.group_item_title = Some("94,750".to_string());
+        assert_eq!(polymarket_strike(&titled), Some(94_750.0));
+        assert_eq!(auto_match("KXBTCD", &kalshi_markets, &[titled]).len(), 1);
+    }
+
+    #[test]
+    fn test_apply_keeps_manual_pairs() {
+        let universe = SeriesUniverse::new(vec!["KXBTCD".to_string()]);
+        let mut matcher = MarketMatcher::empty();
+        matcher.add_match(MatchedMarket::new("manual", "manual", "INXD-26JAN05-B5000"));
+        matcher.add_match(MatchedMarket::new("expired", "old", "KXBTCD-26JAN0416-T90000"));
+
+        let (added, removed) = universe.apply(&mut matcher, vec![
+            MatchedMarket::new("new", "new", "KXBTCD-26JAN0517-T94249.99"),
+            // The same token paired with a second strike is its own pair
+            MatchedMarket::new("new 95k", "new", "KXBTCD-26JAN0517-T94999.99"),
+        ]);
+
+        assert_eq!((added, removed), (2, 1));
+        assert!(matcher.is_matched(&MarketId::polymarket("manual")));
+        assert!(!matcher.is_matched(&MarketId::polymarket("old")));
+        assert_eq!(matcher.pairs_with(&MarketId::polymarket("new")).len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+//! Venue outage tracking for the detector.
+//!
+//! A venue whose every quote fetch fails for [`DOWN_AFTER_PASSES`] passes in
+//! a row is marked down. While it is down the detector keeps quoting the
+//! healthy venue (recording prices and checking its own YES + NO parity)
+//! with cross-venue detection suspended, and probes the down venue with a
+//! single fetch per pass; one successful fetch brings it back.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Consecutive all-failed passes before a venue is marked down.
+pub const DOWN_AFTER_PASSES: u32 = 2;
+
+/// A venue the detector cannot reach.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueOutage {
+    pub venue: String,
+    pub since: DateTime<Utc>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Tally {
+    /// Consecutive passes in which every fetch failed
+    failed_passes: u32,
+    first_failure: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+/// Fetch outcomes per venue across detection passes.
+#[derive(Debug, Clone, Default)]
+pub struct VenueHealth {
+    venues: BTreeMap<String, Tally>,
+}
+
+impl VenueHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how `venue`'s fetches went in one pass. Passes without any
+    /// fetch leave its state alone.
+    pub fn record_pass(&mut self, venue: &str, ok: usize, failed: usize, error: Option<String>, now: DateTime<Utc>) {
+        let tally = self.venues.entry(venue.to_string()).or_default();
+        if ok > 0 {
+            *tally = Tally::default();
+        } else if failed > 0 {
+            tally.failed_passes += 1;
+            tally.first_failure.get_or_insert(now);
+            tally.last_error = error.or(tally.last_error.take());
+        }
+    }
+
+    pub fn is_down(&self, venue: &str) -> bool {
+        self.venues.get(venue).is_some_and(|t| t.failed_passes >= DOWN_AFTER_PASSES)
+    }
+
+    /// Venues currently down, by name.
+    pub fn outages(&self) -> Vec<VenueOutage> {
+        self.venues
+            .iter()
+            .filter(|(venue, _)| self.is_down(venue))
+            .map(|(venue, tally)| VenueOutage {
+                venue: venue.clone(),
+                since: tally.first_failure.unwrap_or_else(Utc::now),
+                last_error: tally.last_error.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_venue_goes_down_after_failed_passes_and_recovers() {
+        let mut health = VenueHealth::new();
+        let now = Utc::now();
+        health.record_pass("kalshi", 0, 12, Some("timeout".to_string()), now);
+        assert!(!health.is_down("kalshi"));
+        // A pass with some successes is not an outage
+        health.record_pass("kalshi", 3, 9, None, now);
+        health.record_pass("kalshi", 0, 12, Some("timeout".to_string()), now);
+        health.record_pass("kalshi", 0, 1, Some("503".to_string()), now + chrono::Duration::seconds(10));
+        assert!(health.is_down("kalshi"));
+        assert!(!health.is_down("polymarket"));
+
+        let outages = health.outages();
+        assert_eq!(outages.len(), 1);
+        assert_eq!((outages[0].since, outages[0].last_error.as_deref()), (now, Some("503")));
+
+        // Skipped passes change nothing; one successful probe brings it back
+        health.record_pass("kalshi", 0, 0, None, now);
+        assert!(health.is_down("kalshi"));
+        health.record_pass("kalshi", 1, 0, None, now);
+        assert!(health.outages().is_empty());
+    }
+}
@@ -0,0 +1,22 @@
+//! Backtesting against recorded quotes.
+//!
+//! The detector can record every quote it sees to a tape; the simulator
+//! replays a tape with a given set of strategy parameters, the sweep runs
+//! it across a parameter grid, and walk-forward validation checks the
+//! tuned parameters out of sample. A replay paces the simulator through
+//! the tape so a past session can be watched, and a trade replay reprices
+//! a single past trade against the tape around it.
+
+pub mod replay;
+pub mod simulator;
+pub mod sweep;
+pub mod tape;
+pub mod trade_replay;
+pub mod walk_forward;
+
+pub use replay::{Replay, ReplaySpeed};
+pub use simulator::{BacktestParams, BacktestResult, SimEvent};
+pub use sweep::SweepGrid;
+pub use tape::{QuoteTape, QuoteTick};
+pub use trade_replay::{TradeReplay, WhatIf};
+pub use walk_forward::{WalkForward, WalkForwardReport};
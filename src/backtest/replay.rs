@@ -0,0 +1,187 @@
+//! Paced replay of a quote tape.
+//!
+//! Steps the simulator through the tape in tape time scaled by a speed
+//! (1x, 10x, ...) or as fast as possible, so a past session can be watched
+//! unfold in the terminal or the TUI. Quiet stretches longer than
+//! [`MAX_GAP`] are skipped rather than waited out.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::fmt;
+
+use super::simulator::{BacktestParams, BacktestResult, SimEvent, Simulation};
+use super::tape::QuoteTick;
+use crate::fees::FeeSchedule;
+
+/// Longest stretch of tape time without quotes that is played out.
+pub const MAX_GAP: Duration = Duration::seconds(5);
+/// Ticks replayed per advance at max speed.
+const MAX_SPEED_BATCH: usize = 500;
+
+/// How fast tape time passes relative to wall time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplaySpeed {
+    Multiplier(f64),
+    /// No waiting between ticks
+    Max,
+}
+
+impl ReplaySpeed {
+    /// Parse "1x", "10x", "2.5" or "max".
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim().to_lowercase();
+        if value == "max" {
+            return Ok(ReplaySpeed::Max);
+        }
+        let multiplier: f64 = value.trim_end_matches('x').parse().context("Speed must be e.g. 1x, 10x or max")?;
+        if multiplier <= 0.0 {
+            anyhow::bail!("Speed must be positive");
+        }
+        Ok(ReplaySpeed::Multiplier(multiplier))
+    }
+}
+
+impl fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplaySpeed::Multiplier(m) => write!(f, "{}x", m),
+            ReplaySpeed::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// A replayed tick and what the simulator did on it.
+#[derive(Debug, Clone)]
+pub struct ReplayStep {
+    pub tick: QuoteTick,
+    pub event: Option<SimEvent>,
+}
+
+/// A tape being replayed.
+#[derive(Debug, Clone)]
+pub struct Replay {
+    ticks: Vec<QuoteTick>,
+    sim: Simulation,
+    fees: FeeSchedule,
+    speed: ReplaySpeed,
+    /// Next tick to replay
+    next: usize,
+    /// Tape time reached
+    cursor: Option<DateTime<Utc>>,
+}
+
+impl Replay {
+    /// Replay `ticks` (oldest first) with `params`.
+    pub fn new(ticks: Vec<QuoteTick>, fees: FeeSchedule, params: &BacktestParams, speed: ReplaySpeed) -> Self {
+        let sim = Simulation::new(&ticks, params);
+        let cursor = ticks.first().map(|t| t.at);
+        Self { ticks, sim, fees, speed, next: 0, cursor }
+    }
+
+    /// Fees the simulator charges.
+    pub fn fees(&self) -> &FeeSchedule {
+        &self.fees
+    }
+
+    pub fn speed(&self) -> ReplaySpeed {
+        self.speed
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.ticks.len()
+    }
+
+    /// Fraction of the tape replayed.
+    pub fn progress(&self) -> f64 {
+        if self.ticks.is_empty() { 1.0 } else { self.next as f64 / self.ticks.len() as f64 }
+    }
+
+    /// Tape time reached.
+    pub fn now(&self) -> Option<DateTime<Utc>> {
+        self.cursor
+    }
+
+    pub fn result(&self) -> &BacktestResult {
+        self.sim.result()
+    }
+
+    /// Move tape time forward by `wall` times the speed, replaying every
+    /// tick passed. At max speed a fixed batch is replayed instead.
+    pub fn advance(&mut self, wall: std::time::Duration) -> Vec<ReplayStep> {
+        let until = match (self.speed, self.ticks.get(self.next)) {
+            (_, None) => return Vec::new(),
+            (ReplaySpeed::Max, _) => None,
+            (ReplaySpeed::Multiplier(m), Some(next)) => {
+                let elapsed = Duration::milliseconds((wall.as_secs_f64() * m * 1000.0) as i64);
+                let mut cursor = self.cursor.unwrap_or(next.at) + elapsed;
+                // Skip dead air up to shortly before the next quote
+                if next.at - cursor > MAX_GAP {
+                    cursor = next.at - MAX_GAP;
+                }
+                self.cursor = Some(cursor);
+                Some(cursor)
+            }
+        };
+
+        let mut steps = Vec::new();
+        while let Some(tick) = self.ticks.get(self.next) {
+            match until {
+                Some(until) if tick.at > until => break,
+                None if steps.len() >= MAX_SPEED_BATCH => break,
+                _ => {}
+            }
+            let event = self.sim.step(&self.ticks, self.next, &self.fees);
+            steps.push(ReplayStep { tick: tick.clone(), event });
+            self.next += 1;
+            if until.is_none() {
+                self.cursor = Some(tick.at);
+            }
+        }
+        steps
+    }
+}
+
+/// Text progress bar, e.g. "[#####-----]  50%".
+pub fn progress_bar(fraction: f64, width: usize) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let filled = (fraction * width as f64).round() as usize;
+    format!("[{}{}] {:>3.0}%", "#".repeat(filled), "-".repeat(width - filled), fraction * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::BinaryQuote;
+
+    fn tick(secs: i64) -> QuoteTick {
+        let start: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        QuoteTick {
+            at: start + Duration::seconds(secs),
+            market: "BTC".to_string(),
+            poly: BinaryQuote::from_yes(Some(0.44), Some(0.45)),
+            kalshi: BinaryQuote { no_ask: Some(0.50), ..Default::default() },
+            legs: None,
+        }
+    }
+
+    #[test]
+    fn test_paced_replay_matches_batch_run() {
+        let ticks: Vec<QuoteTick> = [0, 1, 2, 3, 600, 601].into_iter().map(tick).collect();
+        let params = BacktestParams { min_profit: 0.0, size: 10.0, latency_ms: 0 };
+        let fees = FeeSchedule::new();
+        let mut replay = Replay::new(ticks.clone(), fees.clone(), &params, ReplaySpeed::parse("2x").unwrap());
+
+        // 1s of wall time is 2s of tape time
+        assert_eq!(replay.advance(std::time::Duration::from_secs(1)).len(), 3);
+        assert_eq!(replay.advance(std::time::Duration::from_secs(1)).len(), 1);
+        // The 10 minute gap is skipped, not waited out
+        assert!(replay.advance(std::time::Duration::from_secs(1)).is_empty());
+        assert_eq!(replay.advance(std::time::Duration::from_secs(3)).len(), 2);
+        assert!(replay.is_done());
+        assert_eq!(*replay.result(), super::super::simulator::run(&ticks, &fees, &params));
+
+        assert_eq!(ReplaySpeed::parse("MAX").unwrap(), ReplaySpeed::Max);
+        assert!(ReplaySpeed::parse("0x").is_err());
+        assert_eq!(progress_bar(0.5, 10), "[#####-----]  50%");
+    }
+}
@@ -0,0 +1,189 @@
+//! Replays a quote tape through the arbitrage detector.
+//!
+//! Each detection is executed `latency_ms` later at whatever the book shows
+//! then, so edge that disappears while orders are in flight is missed or
+//! lost rather than counted. Fills are assumed at top of book for the full
+//! size, since the tape does not record depth.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::tape::QuoteTick;
+use crate::arbitrage::detector::find_opportunities;
+use crate::arbitrage::Opportunity;
+use crate::fees::{FeeSchedule, Liquidity};
+
+/// One point in the strategy's parameter space.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BacktestParams {
+    /// Net edge per contract pair required to act
+    pub min_profit: f64,
+    /// Contract pairs per trade
+    pub size: f64,
+    /// Delay between detection and both legs filling
+    pub latency_ms: u64,
+}
+
+/// Outcome of replaying a tape with one set of parameters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BacktestResult {
+    pub params: Option<BacktestParams>,
+    pub detected: usize,
+    pub filled: usize,
+    /// Detections with no quote on a leg by execution time
+    pub missed: usize,
+    pub wins: usize,
+    /// P&L at settlement, fees included
+    pub pnl: f64,
+    pub fees: f64,
+    /// Cash spent buying both legs
+    pub capital: f64,
+    /// Largest peak-to-trough fall in cumulative P&L
+    pub max_drawdown: f64,
+}
+
+impl BacktestResult {
+    /// P&L per dollar deployed.
+    pub fn return_on_capital(&self) -> f64 {
+        if self.capital > 0.0 { self.pnl / self.capital } else { 0.0 }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.filled > 0 { self.wins as f64 / self.filled as f64 } else { 0.0 }
+    }
+}
+
+/// What one tick of a replay did.
+#[derive(Debug, Clone)]
+pub enum SimEvent {
+    /// Both legs filled; `fill` holds the execution prices and time
+    Filled { fill: Opportunity, size: f64, pnl: f64 },
+    /// A leg had no quote by execution time
+    Missed { market: String },
+}
+
+/// Replay state, advanced one tick at a time.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    params: BacktestParams,
+    /// Tick indices per market, oldest first
+    by_market: HashMap<String, Vec<usize>>,
+    /// A market is busy until its in-flight legs fill
+    busy_until: HashMap<String, DateTime<Utc>>,
+    result: BacktestResult,
+    cumulative: f64,
+    peak: f64,
+}
+
+impl Simulation {
+    /// Prepare to replay `ticks` (oldest first) with `params`.
+    pub fn new(ticks: &[QuoteTick], params: &BacktestParams) -> Self {
+        let mut by_market: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, tick) in ticks.iter().enumerate() {
+            by_market.entry(tick.market.clone()).or_default().push(i);
+        }
+        Self {
+            params: *params,
+            by_market,
+            busy_until: HashMap::new(),
+            result: BacktestResult { params: Some(*params), ..Default::default() },
+            cumulative: 0.0,
+            peak: 0.0,
+        }
+    }
+
+    /// Process `ticks[i]`; ticks must be stepped in order.
+    pub fn step(&mut self, ticks: &[QuoteTick], i: usize, fees: &FeeSchedule) -> Option<SimEvent> {
+        let tick = &ticks[i];
+        if self.busy_until.get(tick.market.as_str()).is_some_and(|until| tick.at < *until) {
+            return None;
+        }
+        let found = find_opportunities(&tick.market, &tick.poly, &tick.kalshi, fees, self.params.min_profit, tick.at);
+        let best = found.into_iter().max_by(|a, b| a.net_edge().total_cmp(&b.net_edge()))?;
+        self.result.detected += 1;
+
+        let fill_at = tick.at + Duration::milliseconds(self.params.latency_ms as i64);
+        self.busy_until.insert(tick.market.clone(), fill_at);
+        let book = &self.by_market[tick.market.as_str()];
+        let fill_tick = book[book.partition_point(|&j| ticks[j].at < fill_at)..].first().map(|&j| &ticks[j]);
+        let prices = fill_tick.and_then(|t| Some((t.quote(best.yes_venue).yes_ask?, t.quote(best.no_venue).no_ask?)));
+        let Some((yes, no)) = prices else {
+            self.result.missed += 1;
+            return Some(SimEvent::Missed { market: tick.market.clone() });
+        };
+
+        let size = self.params.size;
+        let fee = fees.fee(best.yes_venue, &tick.market, Liquidity::Taker, size, yes)
+            + fees.fee(best.no_venue, &tick.market, Liquidity::Taker, size, no);
+        let pnl = (1.0 - yes - no) * size - fee;
+        let result = &mut self.result;
+        result.filled += 1;
+        result.wins += (pnl > 0.0) as usize;
+        result.pnl += pnl;
+        result.fees += fee;
+        result.capital += (yes + no) * size;
+
+        self.cumulative += pnl;
+        self.peak = self.peak.max(self.cumulative);
+        result.max_drawdown = result.max_drawdown.max(self.peak - self.cumulative);
+
+        let fill = Opportunity { yes_price: yes, no_price: no, detected_at: fill_at, ..best };
+        Some(SimEvent::Filled { fill, size, pnl })
+    }
+
+    /// Totals so far.
+    pub fn result(&self) -> &BacktestResult {
+        &self.result
+    }
+}
+
+/// Replay `ticks` (oldest first) with `params`.
+pub fn run(ticks: &[QuoteTick], fees: &FeeSchedule, params: &BacktestParams) -> BacktestResult {
+    let mut sim = Simulation::new(ticks, params);
+    for i in 0..ticks.len() {
+        sim.step(ticks, i, fees);
+    }
+    sim.result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::BinaryQuote;
+
+    fn tick(secs: i64, poly_yes_ask: f64, kalshi_no_ask: f64) -> QuoteTick {
+        let start: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        QuoteTick {
+            at: start + Duration::seconds(secs),
+            market: "BTC".to_string(),
+            poly: BinaryQuote::from_yes(Some(poly_yes_ask - 0.01), Some(poly_yes_ask)),
+            kalshi: BinaryQuote { no_ask: Some(kalshi_no_ask), ..Default::default() },
+            legs: None,
+        }
+    }
+
+    #[test]
+    fn test_latency_fills_at_later_quotes() {
+        // 5c spread at t=0 that has closed to 1c by t=1
+        let ticks = vec![tick(0, 0.45, 0.50), tick(1, 0.49, 0.50), tick(2, 0.49, 0.50)];
+        let fees = FeeSchedule::new();
+
+        let kalshi_fee = fees.fee("kalshi", "BTC", Liquidity::Taker, 10.0, 0.50);
+
+        // Only the 5c spread clears Kalshi's fee
+        let instant = run(&ticks, &fees, &BacktestParams { min_profit: 0.0, size: 10.0, latency_ms: 0 });
+        assert_eq!((instant.detected, instant.filled, instant.wins), (1, 1, 1));
+        assert!((instant.pnl - (0.50 - kalshi_fee)).abs() < 1e-9);
+
+        let slow = run(&ticks, &fees, &BacktestParams { min_profit: 0.03, size: 10.0, latency_ms: 1000 });
+        assert_eq!((slow.detected, slow.filled, slow.missed), (1, 1, 0));
+        // Filled at the t=1 book: 1c gross edge less Kalshi's fee on the NO leg
+        assert!((slow.pnl - (0.10 - kalshi_fee)).abs() < 1e-9);
+        assert!(slow.pnl < 0.0 && slow.max_drawdown > 0.0);
+
+        // Nothing left on the tape to fill against
+        let late = run(&ticks, &fees, &BacktestParams { min_profit: 0.03, size: 10.0, latency_ms: 5000 });
+        assert_eq!((late.filled, late.missed), (0, 1));
+    }
+}
@@ -0,0 +1,97 @@
+//! Parameter sweeps.
+//!
+//! Runs the simulator for every combination in a grid across worker
+//! threads and ranks the results by P&L, so thresholds can be tuned from
+//! recorded data instead of guessed.
+
+use anyhow::{Context, Result};
+use std::str::FromStr;
+use std::thread;
+
+use super::simulator::{self, BacktestParams, BacktestResult};
+use super::tape::QuoteTick;
+use crate::fees::FeeSchedule;
+
+/// Values to try for each parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepGrid {
+    pub min_profit: Vec<f64>,
+    pub size: Vec<f64>,
+    pub latency_ms: Vec<u64>,
+}
+
+impl SweepGrid {
+    /// Every combination of the grid's values.
+    pub fn params(&self) -> Vec<BacktestParams> {
+        let mut params = Vec::new();
+        for &min_profit in &self.min_profit {
+            for &size in &self.size {
+                for &latency_ms in &self.latency_ms {
+                    params.push(BacktestParams { min_profit, size, latency_ms });
+                }
+            }
+        }
+        params
+    }
+}
+
+/// Parse a comma-separated list of values, e.g. "0.01,0.02,0.03".
+pub fn parse_values<T: FromStr>(value: &str) -> Result<Vec<T>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse().ok().with_context(|| format!("Invalid value '{}'", v)))
+        .collect()
+}
+
+/// Backtest every point in `grid` on up to `threads` threads. Results are
+/// ranked by P&L, ties going to the smaller drawdown.
+pub fn run(ticks: &[QuoteTick], fees: &FeeSchedule, grid: &SweepGrid, threads: usize) -> Vec<BacktestResult> {
+    let params = grid.params();
+    let chunk = params.len().div_ceil(threads.max(1)).max(1);
+    let mut results: Vec<BacktestResult> = thread::scope(|scope| {
+        let workers: Vec<_> = params
+            .chunks(chunk)
+            .map(|batch| scope.spawn(move || batch.iter().map(|p| simulator::run(ticks, fees, p)).collect::<Vec<_>>()))
+            .collect();
+        workers.into_iter().flat_map(|w| w.join().expect("backtest worker panicked")).collect()
+    });
+    results.sort_by(|a, b| b.pnl.total_cmp(&a.pnl).then(a.max_drawdown.total_cmp(&b.max_drawdown)));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::BinaryQuote;
+    use chrono::Utc;
+
+    #[test]
+    fn test_grid_is_swept_and_ranked() {
+        let grid = SweepGrid {
+            min_profit: parse_values("0.01, 0.2").unwrap(),
+            size: parse_values("10,100").unwrap(),
+            latency_ms: parse_values("0").unwrap(),
+        };
+        assert_eq!(grid.params().len(), 4);
+        assert!(parse_values::<f64>("0.01,abc").is_err());
+
+        // A fee-free 10c spread on Polymarket alone
+        let ticks = vec![QuoteTick {
+            at: Utc::now(),
+            market: "BTC".to_string(),
+            poly: BinaryQuote { yes_ask: Some(0.40), no_ask: Some(0.50), ..Default::default() },
+            kalshi: BinaryQuote::default(),
+            legs: None,
+        }];
+        let results = run(&ticks, &FeeSchedule::new(), &grid, 3);
+        assert_eq!(results.len(), 4);
+        // Largest size that clears the threshold ranks first; the 20c
+        // threshold never trades
+        let best = results[0].params.unwrap();
+        assert_eq!((best.min_profit, best.size), (0.01, 100.0));
+        assert!((results[0].pnl - 10.0).abs() < 1e-9);
+        assert!(results.iter().filter(|r| r.params.unwrap().min_profit == 0.2).all(|r| r.filled == 0));
+    }
+}
@@ -0,0 +1,77 @@
+//! Recorded quote tape.
+//!
+//! One JSON object per line, each holding both venues' top of book for a
+//! matched pair at the time the detector checked it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::arbitrage::BinaryQuote;
+use crate::utils::{state, MarketId};
+
+/// Default tape location.
+pub const QUOTE_TAPE_FILE: &str = "data/quotes.jsonl";
+
+/// Both venues' quotes for one matched pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteTick {
+    pub at: DateTime<Utc>,
+    pub market: String,
+    pub poly: BinaryQuote,
+    pub kalshi: BinaryQuote,
+    /// Polymarket and Kalshi markets quoted, as the pair name alone may be
+    /// reused across pairs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legs: Option<(MarketId, MarketId)>,
+}
+
+impl QuoteTick {
+    /// Quote on `venue` ("polymarket" or "kalshi").
+    pub fn quote(&self, venue: &str) -> &BinaryQuote {
+        if venue == "kalshi" {
+            &self.kalshi
+        } else {
+            &self.poly
+        }
+    }
+}
+
+/// Quote tape file.
+pub struct QuoteTape {
+    path: String,
+    /// Serializes appends from concurrent tasks
+    lock: Mutex<()>,
+}
+
+impl QuoteTape {
+    /// Append to `path`, creating it on first write.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Append a tick.
+    pub fn record(&self, tick: &QuoteTick) {
+        let _guard = self.lock.lock().unwrap();
+        let result = serde_json::to_string(tick)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| state::append_line(&self.path, &line));
+        if let Err(e) = result {
+            warn!("Failed to write quote tape {}: {}", self.path, e);
+        }
+    }
+
+    /// Every tick on the tape, oldest first. Unreadable lines are skipped.
+    pub fn load(&self) -> Vec<QuoteTick> {
+        let mut ticks: Vec<QuoteTick> = state::read_lines(&self.path)
+            .iter()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        ticks.sort_by_key(|t| t.at);
+        ticks
+    }
+}
@@ -0,0 +1,170 @@
+//! Post-trade review against the quote tape.
+//!
+//! Rebuilds what the market looked like around a past trade from the
+//! recorded quotes: the prices its outcome traded at on its venue in the
+//! surrounding minutes, and what the trade would have made had it been
+//! placed earlier or later, or sized differently. The exit is held fixed
+//! (the trade's own exit, or the latest bid on the tape for an open one).
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::tape::QuoteTick;
+use crate::fees::{FeeSchedule, Liquidity};
+use crate::paper_trading::{PaperTrade, Side};
+use crate::risk::netting::split_outcome;
+
+/// The trade's outcome on its venue at one tick.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PricePoint {
+    pub at: DateTime<Utc>,
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+}
+
+/// Result of one what-if variation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WhatIf {
+    /// Entry moved by this much from the actual fill
+    pub delay: Duration,
+    pub size_usd: f64,
+    /// Price available at the moved entry
+    pub entry: f64,
+    pub contracts: f64,
+    /// P&L at the fixed exit, entry and exit fees included
+    pub pnl: f64,
+}
+
+/// A past trade and the tape around it.
+#[derive(Debug, Clone)]
+pub struct TradeReplay {
+    pub trade: PaperTrade,
+    /// Matched pair the trade's outcome belongs to
+    pub market: String,
+    /// The trade's outcome is YES
+    pub yes: bool,
+    /// Ticks from `window` before the trade to `window` after, oldest first
+    pub points: Vec<PricePoint>,
+    /// Exit the what-ifs are priced at
+    pub exit: Option<f64>,
+}
+
+impl TradeReplay {
+    /// Reconstruct `trade` from `ticks` (the whole tape, oldest first).
+    pub fn new(trade: PaperTrade, ticks: &[QuoteTick], window: Duration) -> Self {
+        let (market, yes) = split_outcome(&trade.market);
+        let market = market.to_string();
+        let point = |tick: &QuoteTick| {
+            let quote = tick.quote(&trade.platform);
+            let (bid, ask) = if yes { (quote.yes_bid, quote.yes_ask) } else { (quote.no_bid, quote.no_ask) };
+            PricePoint { at: tick.at, bid, ask }
+        };
+        let all: Vec<PricePoint> = ticks.iter().filter(|t| t.market == market).map(point).collect();
+        let exit = trade.exit_price.or_else(|| all.iter().rev().find_map(|p| p.bid));
+        let (from, to) = (trade.timestamp - window, trade.timestamp + window);
+        let points = all.into_iter().filter(|p| p.at >= from && p.at <= to).collect();
+        Self { trade, market, yes, points, exit }
+    }
+
+    /// Contracts the trade bought or sold.
+    pub fn contracts(&self) -> f64 {
+        self.trade.filled_contracts()
+    }
+
+    /// Price the trade's side could have filled at on `point`.
+    fn entry_price(&self, point: &PricePoint) -> Option<f64> {
+        match self.trade.side {
+            Side::Buy => point.ask,
+            Side::Sell => point.bid,
+        }
+    }
+
+    /// Quote in force when the trade was placed.
+    pub fn quote_at_entry(&self) -> Option<&PricePoint> {
+        self.points.iter().rev().find(|p| p.at <= self.trade.timestamp)
+    }
+
+    /// Fill price minus the quote in force, against the trade (positive =
+    /// paid more than quoted for a buy).
+    pub fn slippage(&self) -> Option<f64> {
+        let quoted = self.entry_price(self.quote_at_entry()?)?;
+        Some(match self.trade.side {
+            Side::Buy => self.trade.entry_price - quoted,
+            Side::Sell => quoted - self.trade.entry_price,
+        })
+    }
+
+    /// Best entry price in the window and when it was available.
+    pub fn best_entry(&self) -> Option<(DateTime<Utc>, f64)> {
+        let entries = self.points.iter().filter_map(|p| Some((p.at, self.entry_price(p)?)));
+        match self.trade.side {
+            Side::Buy => entries.min_by(|a, b| a.1.total_cmp(&b.1)),
+            Side::Sell => entries.max_by(|a, b| a.1.total_cmp(&b.1)),
+        }
+    }
+
+    /// The trade placed `delay` later (earlier when negative) for
+    /// `size_usd`, filled at the first quote at or after that time.
+    pub fn what_if(&self, fees: &FeeSchedule, delay: Duration, size_usd: f64) -> Option<WhatIf> {
+        let at = self.trade.timestamp + delay;
+        let entry = self.points.iter().filter(|p| p.at >= at).find_map(|p| self.entry_price(p))?;
+        let exit = self.exit?;
+        if entry <= 0.0 {
+            return None;
+        }
+        let contracts = size_usd / entry;
+        let gross = match self.trade.side {
+            Side::Buy => contracts * (exit - entry),
+            Side::Sell => contracts * (entry - exit),
+        };
+        let fee = |price| fees.fee(&self.trade.platform, &self.market, Liquidity::Taker, contracts, price);
+        Some(WhatIf { delay, size_usd, entry, contracts, pnl: gross - fee(entry) - fee(exit) })
+    }
+
+    /// Every combination of `delays` and `sizes`, skipping those the tape
+    /// has no quote for.
+    pub fn scenarios(&self, fees: &FeeSchedule, delays: &[Duration], sizes: &[f64]) -> Vec<WhatIf> {
+        delays
+            .iter()
+            .flat_map(|delay| sizes.iter().filter_map(move |size| self.what_if(fees, *delay, *size)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::BinaryQuote;
+
+    #[test]
+    fn test_replay_reprices_timing_and_size() {
+        let start = DateTime::parse_from_rfc3339("2026-01-05T21:00:00Z").unwrap().with_timezone(&Utc);
+        let tick = |minute: i64, yes_ask: f64| QuoteTick {
+            at: start + Duration::minutes(minute),
+            market: "BTC 5PM".to_string(),
+            poly: BinaryQuote::from_yes(Some(yes_ask - 0.02), Some(yes_ask)),
+            kalshi: BinaryQuote::from_yes(Some(0.40), Some(0.42)),
+            legs: None,
+        };
+        let ticks = vec![tick(-30, 0.30), tick(-2, 0.48), tick(0, 0.50), tick(1, 0.45), tick(3, 0.55), tick(30, 0.60)];
+        let mut trade = PaperTrade::new("BTC 5PM YES", "BTC", "Hourly", "polymarket", Side::Buy, 51.0, 0.51, "manual", 0.5);
+        trade.timestamp = start;
+        trade.close(0.60);
+
+        let replay = TradeReplay::new(trade, &ticks, Duration::minutes(5));
+        assert_eq!((replay.market.as_str(), replay.yes), ("BTC 5PM", true));
+        assert_eq!(replay.points.len(), 4);
+        assert!((replay.contracts() - 100.0).abs() < 1e-9);
+        assert!((replay.slippage().unwrap() - 0.01).abs() < 1e-9);
+        assert_eq!(replay.best_entry(), Some((start + Duration::minutes(1), 0.45)));
+
+        // Polymarket charges no fee, so a minute later buys 100 contracts at 0.45
+        let fees = FeeSchedule::new();
+        let later = replay.what_if(&fees, Duration::minutes(1), 45.0).unwrap();
+        assert!((later.contracts - 100.0).abs() < 1e-9);
+        assert!((later.pnl - 15.0).abs() < 1e-9);
+        assert!(replay.what_if(&fees, Duration::minutes(10), 45.0).is_none());
+        let grid = replay.scenarios(&fees, &[Duration::minutes(-2), Duration::zero()], &[25.0, 50.0]);
+        assert_eq!(grid.len(), 4);
+        assert!(grid[1].pnl > grid[0].pnl);
+    }
+}
@@ -1,11 +1,10 @@
 use anyhow::{Context, Result};
 use ethers::signers::{LocalWallet, Signer};
-use reqwest::Client;
 use serde::Deserialize;
 use std::io::{self, Write};
-use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 struct CreateKeyResponse {
     api_key: String,
     secret: String,
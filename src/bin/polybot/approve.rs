@@ -0,0 +1,40 @@
+//! Approves the Polymarket exchange contracts to trade from an EOA.
+
+use anyhow::{Context, Result};
+use ethers::types::U256;
+
+use polymarket_kalshi_arbitrage_bot::chain::{ensure_allowances, usdc_units, TxManager};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+
+#[derive(clap::Args)]
+#[group(required = true, multiple = false)]
+pub struct Args {
+    /// USDC the exchanges may spend
+    #[arg(long, value_name = "USDC")]
+    amount: Option<f64>,
+    /// Let the exchanges spend any amount
+    #[arg(long)]
+    unlimited: bool,
+}
+
+pub async fn run(args: Args) -> Result<()> {
+    let allowance = match args.amount {
+        Some(usd) if usd <= 0.0 || !usd.is_finite() => anyhow::bail!("The amount must be positive"),
+        Some(usd) => usdc_units(usd),
+        None => U256::MAX,
+    };
+
+    let config = Config::from_env()?;
+    if config.polymarket_signature_type.is_proxy() {
+        anyhow::bail!("Proxy wallets are approved by Polymarket when the proxy is created");
+    }
+    let tx = TxManager::from_config(&config)?.context("POLYMARKET_PRIVATE_KEY is required to approve")?;
+    let sent = ensure_allowances(&tx, allowance).await?;
+    for hash in &sent {
+        println!("✅ Approved ({:?})", hash);
+    }
+    if sent.is_empty() {
+        println!("Exchange allowances already in place");
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Prints performance attribution per matched pair: detections, executed
+//! and failed arbs, fees, settled P&L and settlement divergences, worst
+//! first, flagging pairs whose spreads look good but lose in practice.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::analysis::attribution::{Attribution, PairReport, ATTRIBUTION_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::monitor::resolution::{load_recent_resolutions, RESOLUTIONS_FILE};
+use polymarket_kalshi_arbitrage_bot::utils::run_info;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Only executions in this mode (live or dry_run)
+    #[arg(long)]
+    mode: Option<String>,
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        display::init(&config);
+    }
+    let Args { mode, json } = args;
+
+    let ledger = Attribution::load(ATTRIBUTION_FILE);
+    let resolutions = load_recent_resolutions(RESOLUTIONS_FILE, usize::MAX);
+    let reports = ledger.report(&resolutions, mode.as_deref());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(&reports)?)?);
+        return Ok(());
+    }
+    if reports.is_empty() {
+        println!("No pair activity recorded in {}", ATTRIBUTION_FILE);
+        return Ok(());
+    }
+    print_table(&reports, mode.as_deref());
+    Ok(())
+}
+
+fn print_table(reports: &[PairReport], mode: Option<&str>) {
+    println!("📈 Pair attribution ({} executions)", mode.unwrap_or("all"));
+    println!(
+        "{:<32} {:>8} {:>9} {:>8} {:>6} {:>9} {:>7} {:>11} {:>10} {:>6}",
+        "Pair", "Detected", "Avg edge", "Executed", "Failed", "Fees", "Settled", "P&L", "Open", "Diverg"
+    );
+    for r in reports {
+        println!(
+            "{:<32} {:>8} {:>9} {:>8} {:>6} {:>9} {:>7} {:>11} {:>10} {:>6}{}",
+            r.market,
+            r.detected,
+            display::percent(r.avg_net_edge),
+            r.executed,
+            r.failed,
+            format!("${:.2}", r.fees),
+            r.settled,
+            format!("${:+.2}", r.pnl),
+            format!("${:.2}", r.open_cost),
+            r.divergences,
+            if r.is_suspect() { "  ⚠️ prune?" } else { "" },
+        );
+    }
+    let suspect: Vec<&str> = reports.iter().filter(|r| r.is_suspect()).map(|r| r.market.as_str()).collect();
+    if !suspect.is_empty() {
+        println!("\n⚠️  Losing despite their spreads, or diverged at settlement: {}", suspect.join(", "));
+        println!("   Mute them with `polybot mutes mute MARKET` to stop trading them");
+    }
+    println!("{}", run_info::current().summary());
+}
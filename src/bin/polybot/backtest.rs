@@ -0,0 +1,272 @@
+//! Backtests the arbitrage strategy against the recorded quote tape.
+//!
+//! Parameter lists are comma-separated, e.g. `--min-profit 0.005,0.01,0.02`.
+//! `replay` uses the first value of each list and plays the tape back at
+//! `--speed`; `--tui` watches it in the dashboard instead. Record a tape by
+//! running the bot with RECORD_QUOTES=true.
+
+use anyhow::{Context, Result};
+use chrono::Duration;
+use std::io::Write;
+
+use polymarket_kalshi_arbitrage_bot::arbitrage::market_matcher::MarketMatcher;
+use polymarket_kalshi_arbitrage_bot::backtest::replay::progress_bar;
+use polymarket_kalshi_arbitrage_bot::backtest::sweep::{self, parse_values};
+use polymarket_kalshi_arbitrage_bot::backtest::tape::QUOTE_TAPE_FILE;
+use polymarket_kalshi_arbitrage_bot::backtest::{
+    BacktestParams, BacktestResult, QuoteTape, QuoteTick, Replay, ReplaySpeed, SimEvent, SweepGrid, WalkForward,
+    WalkForwardReport,
+};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::fees::FeeSchedule;
+use polymarket_kalshi_arbitrage_bot::tui::{self, App};
+use polymarket_kalshi_arbitrage_bot::utils::{run_info, timezone};
+
+/// Wall time between replay steps outside the TUI
+const REPLAY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Rank every combination of the parameter lists
+    Sweep(Options),
+    /// Pick parameters on a train window and score them on the next test window
+    WalkForward {
+        #[arg(long, default_value_t = 72, value_parser = clap::value_parser!(i64).range(1..))]
+        train_hours: i64,
+        #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(i64).range(1..))]
+        test_hours: i64,
+        #[command(flatten)]
+        options: Options,
+    },
+    /// Play the tape back with the first value of each parameter list
+    Replay {
+        /// 1x, 10x or max
+        #[arg(long, default_value = "10x")]
+        speed: String,
+        /// Watch the replay in the dashboard
+        #[arg(long)]
+        tui: bool,
+        #[command(flatten)]
+        options: Options,
+    },
+}
+
+#[derive(clap::Args)]
+pub struct Options {
+    #[arg(long, default_value = QUOTE_TAPE_FILE)]
+    tape: String,
+    /// Minimum net edges to try
+    #[arg(long, value_name = "LIST", default_value = "0.005,0.01,0.02,0.03")]
+    min_profit: String,
+    /// Order sizes in USD to try
+    #[arg(long, value_name = "LIST", default_value = "10,50,100")]
+    size: String,
+    /// Fill latencies to try
+    #[arg(long, value_name = "LIST", default_value = "0,250,1000,3000")]
+    latency_ms: String,
+    /// Worker threads (default: one per core)
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Parameter sets listed by a sweep
+    #[arg(long, default_value_t = 20)]
+    top: usize,
+    #[arg(long)]
+    json: bool,
+}
+
+fn print_header(first: &str) {
+    println!(
+        "{:<5}{:>8} {:>7} {:>8} {:>8} {:>6} {:>6} {:>6} {:>10} {:>8} {:>9}",
+        first, "MinEdge", "Size", "Latency", "Detected", "Filled", "Missed", "Win %", "P&L", "RoC", "Drawdown"
+    );
+}
+
+fn print_row(prefix: &str, r: &BacktestResult) {
+    let Some(p) = r.params else { return };
+    println!(
+        "{}{:>8} {:>7.0} {:>6}ms {:>8} {:>6} {:>6} {:>5.0}% {:>+10.2} {:>8} {:>9.2}",
+        prefix,
+        display::percent(p.min_profit),
+        p.size,
+        p.latency_ms,
+        r.detected,
+        r.filled,
+        r.missed,
+        r.win_rate() * 100.0,
+        r.pnl,
+        display::percent(r.return_on_capital()),
+        r.max_drawdown,
+    );
+}
+
+fn print_walk_forward(report: &WalkForwardReport) {
+    for (i, fold) in report.folds.iter().enumerate() {
+        println!(
+            "\nFold {}: train {} → test {} to {}",
+            i + 1,
+            timezone::format(fold.train_start, "%m-%d %H:%M"),
+            timezone::format(fold.test_start, "%m-%d %H:%M"),
+            timezone::format(fold.test_end, "%m-%d %H:%M"),
+        );
+        print_header("");
+        print_row("IS   ", &fold.in_sample);
+        print_row("OOS  ", &fold.out_of_sample);
+    }
+    println!(
+        "\nIn-sample P&L {:+.2} over {:.0}h, out-of-sample {:+.2} over {:.0}h",
+        report.in_sample_pnl, report.in_sample_hours, report.out_of_sample_pnl, report.out_of_sample_hours
+    );
+    match report.efficiency() {
+        Some(e) if e < 0.5 => println!("⚠️  Walk-forward efficiency {:.0}%: thresholds look overfit", e * 100.0),
+        Some(e) => println!("✅ Walk-forward efficiency {:.0}%", e * 100.0),
+        None => println!("No in-sample profit to compare against"),
+    }
+}
+
+/// Play the tape back in the terminal, printing fills as they happen.
+fn replay_in_terminal(mut replay: Replay, json: bool) -> Result<()> {
+    let mut last = std::time::Instant::now();
+    while !replay.is_done() {
+        std::thread::sleep(REPLAY_INTERVAL);
+        let steps = replay.advance(last.elapsed());
+        last = std::time::Instant::now();
+        if !json {
+            for step in &steps {
+                let Some(event) = &step.event else { continue };
+                let line = match event {
+                    SimEvent::Filled { fill, size, pnl } => format!(
+                        "✅ {} {} YES {} @ {} + NO {} @ {} x{:.0} → {:+.2}",
+                        timezone::format(step.tick.at, "%m-%d %H:%M:%S"),
+                        fill.market,
+                        fill.yes_venue,
+                        display::price(fill.yes_price),
+                        fill.no_venue,
+                        display::price(fill.no_price),
+                        size,
+                        pnl
+                    ),
+                    SimEvent::Missed { market } => {
+                        format!("❌ {} {} gone before the fill", timezone::format(step.tick.at, "%m-%d %H:%M:%S"), market)
+                    }
+                };
+                eprint!("\r\x1b[2K");
+                println!("{}", line);
+            }
+        }
+        let at = replay.now().map(|t| timezone::format(t, "%Y-%m-%d %H:%M:%S")).unwrap_or_default();
+        eprint!("\r\x1b[2K⏪ {} {} {} · P&L {:+.2}", replay.speed(), at, progress_bar(replay.progress(), 30), replay.result().pnl);
+        std::io::stderr().flush()?;
+    }
+    eprintln!();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(replay.result())?)?);
+        return Ok(());
+    }
+    println!("   {}", run_info::current().summary());
+    print_header("");
+    print_row("     ", replay.result());
+    Ok(())
+}
+
+/// First value of a parameter list.
+fn first_value<T: std::str::FromStr>(list: &str, name: &str) -> Result<T> {
+    parse_values(list)
+        .with_context(|| format!("Invalid {}", name))?
+        .into_iter()
+        .next()
+        .with_context(|| format!("{} needs a value", name))
+}
+
+fn replay(ticks: Vec<QuoteTick>, fees: FeeSchedule, grid: (&str, &str, &str), speed: &str, in_tui: bool, json: bool) -> Result<()> {
+    let params = BacktestParams {
+        min_profit: first_value(grid.0, "--min-profit")?,
+        size: first_value(grid.1, "--size")?,
+        latency_ms: first_value(grid.2, "--latency-ms")?,
+    };
+    let speed = ReplaySpeed::parse(speed).context("Invalid --speed")?;
+    if !json {
+        println!(
+            "⏪ Replaying {} quotes ({} to {}) at {}",
+            ticks.len(),
+            timezone::format(ticks[0].at, "%Y-%m-%d %H:%M"),
+            timezone::format(ticks[ticks.len() - 1].at, "%Y-%m-%d %H:%M"),
+            speed,
+        );
+    }
+    let replay = Replay::new(ticks, fees, &params, speed);
+    if !in_tui {
+        return replay_in_terminal(replay, json);
+    }
+
+    let app = tui::run(App::replay(replay))?;
+    if let Some(replay) = &app.replay {
+        print_header("");
+        print_row("     ", replay.result());
+    }
+    Ok(())
+}
+
+pub fn run(command: Command) -> Result<()> {
+    let (options, walk_forward, replay_at) = match command {
+        Command::Sweep(options) => (options, None, None),
+        Command::WalkForward { train_hours, test_hours, options } => (options, Some((train_hours, test_hours)), None),
+        Command::Replay { speed, tui, options } => (options, None, Some((speed, tui))),
+    };
+    let Options { tape, min_profit, size, latency_ms, threads, top, json } = options;
+    let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+
+    let config = Config::from_env()?;
+    display::init(&config);
+    let fees = FeeSchedule::from_config(&config)?;
+    fees.set_pairs(MarketMatcher::known().get_all());
+    let ticks = QuoteTape::new(&tape).load();
+    if ticks.is_empty() {
+        anyhow::bail!("No quotes in {}; run the bot with RECORD_QUOTES=true to record some", tape);
+    }
+    if let Some((speed, in_tui)) = replay_at {
+        return replay(ticks, fees, (&min_profit, &size, &latency_ms), &speed, in_tui, json);
+    }
+
+    let grid = SweepGrid {
+        min_profit: parse_values(&min_profit).context("Invalid --min-profit")?,
+        size: parse_values(&size).context("Invalid --size")?,
+        latency_ms: parse_values(&latency_ms).context("Invalid --latency-ms")?,
+    };
+
+    if let Some((train_hours, test_hours)) = walk_forward {
+        let walk = WalkForward { train: Duration::hours(train_hours), test: Duration::hours(test_hours) };
+        let report = walk.run(&ticks, &fees, &grid, threads);
+        if json {
+            println!("{}", serde_json::to_string_pretty(&run_info::stamped(&report)?)?);
+        } else if report.folds.is_empty() {
+            println!("Tape too short for one {}h train + {}h test fold", train_hours, test_hours);
+        } else {
+            println!("🚶 Walk-forward over {} quotes, {} folds", ticks.len(), report.folds.len());
+            println!("   {}", run_info::current().summary());
+            print_walk_forward(&report);
+        }
+        return Ok(());
+    }
+
+    let results = sweep::run(&ticks, &fees, &grid, threads);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(&results)?)?);
+        return Ok(());
+    }
+
+    println!(
+        "🧪 {} parameter sets over {} quotes ({} to {})",
+        results.len(),
+        ticks.len(),
+        timezone::format(ticks[0].at, "%Y-%m-%d %H:%M"),
+        timezone::format(ticks[ticks.len() - 1].at, "%Y-%m-%d %H:%M"),
+    );
+    println!("   {}", run_info::current().summary());
+    print_header("Rank");
+    for (rank, r) in results.iter().take(top).enumerate() {
+        print_row(&format!("{:>4} ", rank + 1), r);
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! Prints USDC on Polygon, Polymarket collateral and the Kalshi balance,
+//! flagging venues below the configured arbitrage float.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::chain::TxManager;
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::money::{Money, MoneyDisplay};
+use polymarket_kalshi_arbitrage_bot::monitor::Balances;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+
+pub async fn run() -> Result<()> {
+    let config = Config::from_env()?;
+    let money = MoneyDisplay::from_config(&config);
+    let poly = PolymarketClient::new(&config)?;
+    let kalshi = KalshiClient::new(&config)?;
+    let chain = TxManager::from_config(&config)?;
+    let wallet_is_collateral = !config.polymarket_signature_type.is_proxy();
+
+    let balances = Balances::fetch(&poly, &kalshi, chain.as_ref(), wallet_is_collateral).await;
+    let row = |name: &str, balance: Option<Money>| {
+        let shown = balance.map(|b| money.fmt(b.amount)).unwrap_or_else(|| "unavailable".to_string());
+        println!("{:<24} {:>14}", name, shown);
+    };
+
+    println!("💰 Balances");
+    row("Polygon wallet (USDC)", balances.polygon_usdc);
+    row("Polymarket collateral", balances.polymarket);
+    row("Kalshi", balances.kalshi);
+    println!("{:<24} {:>14}", "Total", money.fmt(balances.total()));
+    if wallet_is_collateral && balances.polygon_usdc.is_some() {
+        println!("(wallet USDC is the Polymarket collateral and is counted once)");
+    }
+
+    if config.min_venue_float > 0.0 {
+        for (venue, balance) in balances.below_float(config.min_venue_float) {
+            println!(
+                "⚠️  {} below float: {} available, {} needed",
+                venue, money.fmt(balance.amount), money.fmt(config.min_venue_float)
+            );
+        }
+    }
+    Ok(())
+}
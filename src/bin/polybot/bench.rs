@@ -0,0 +1,91 @@
+//! Measures latency and jitter to the Polymarket and Kalshi endpoints from
+//! this machine, to help decide where to host the bot.
+
+use anyhow::Result;
+use std::time::Duration;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::monitor::latency::{self, default_endpoints, EndpointReport};
+use polymarket_kalshi_arbitrage_bot::utils::fixtures::FixtureStore;
+use polymarket_kalshi_arbitrage_bot::utils::run_info;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Probes per endpoint
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u16).range(1..))]
+    rounds: u16,
+    /// Pause between probes
+    #[arg(long, default_value_t = 200)]
+    pause_ms: u64,
+    /// Only this venue's endpoints (polymarket or kalshi)
+    #[arg(long)]
+    venue: Option<String>,
+    #[arg(long)]
+    json: bool,
+}
+
+fn ms(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.0}ms", v)).unwrap_or_else(|| "-".to_string())
+}
+
+fn print_report(report: &EndpointReport) {
+    let s = &report.stats;
+    let name = format!("{} {}", report.endpoint.venue, report.endpoint.name);
+    if s.samples == 0 {
+        println!("   {:<22} unreachable: {}", name, report.last_error.as_deref().unwrap_or("no response"));
+        return;
+    }
+    println!(
+        "   {:<22} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>6}",
+        name,
+        ms(report.cold_ms),
+        ms(Some(s.min)),
+        ms(Some(s.p50)),
+        ms(Some(s.p90)),
+        ms(Some(s.p99)),
+        ms(Some(s.max)),
+        ms(Some(s.jitter)),
+        s.errors,
+    );
+}
+
+pub async fn run(args: Args) -> Result<()> {
+    let (rounds, pause_ms, json) = (usize::from(args.rounds), args.pause_ms, args.json);
+    let venue = args.venue.map(|v| v.to_lowercase());
+    let endpoints: Vec<_> = default_endpoints()
+        .into_iter()
+        .filter(|e| venue.as_ref().is_none_or(|v| &e.venue == v))
+        .collect();
+    if endpoints.is_empty() {
+        anyhow::bail!("Unknown venue '{}': expected polymarket or kalshi", venue.unwrap_or_default());
+    }
+
+    // FIXTURE_MODE=record saves the probes, replay reports them offline
+    let fixtures = FixtureStore::from_config(&Config::from_env()?)?;
+
+    let info = run_info::current();
+    if !json {
+        println!("⏱️  {} rounds per endpoint from {}", rounds, info.hostname);
+        println!(
+            "   {:<22} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>7} {:>6}",
+            "Endpoint", "Cold", "Min", "p50", "p90", "p99", "Max", "Jitter", "Errors"
+        );
+    }
+    // One endpoint at a time so probes don't compete for bandwidth
+    let mut reports = Vec::new();
+    for endpoint in &endpoints {
+        let report = latency::bench(endpoint, rounds, Duration::from_millis(pause_ms), fixtures.as_deref()).await;
+        if !json {
+            print_report(&report);
+        }
+        reports.push(report);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(&reports)?)?);
+        return Ok(());
+    }
+    println!("   Cold is the first request including DNS, TCP and TLS; websocket rows time a full connect.");
+    println!("   {}", info.summary());
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Prints opportunity capture statistics from the capture log, the paper
+//! results of strategies run in shadow mode, and the most fruitful
+//! hour-of-week windows from the opportunity heatmap.
+
+use anyhow::Result;
+use polymarket_kalshi_arbitrage_bot::analysis::capture::{CaptureLog, CaptureStats, CAPTURE_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::heatmap::{Grid, Heatmap, HEATMAP_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Profile};
+use std::collections::BTreeMap;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Capture log to read
+    #[arg(default_value = CAPTURE_FILE)]
+    path: String,
+}
+
+/// Windows listed per family.
+const TOP_WINDOWS: usize = 5;
+
+fn print_table(title: &str, stats: &BTreeMap<String, CaptureStats>) {
+    println!("\n{}", title);
+    println!(
+        "{:<40} {:>8} {:>8} {:>8} {:>10} {:>10} {:>9}",
+        "", "Detected", "Executed", "Settled", "Avg edge", "Slippage", "Capture"
+    );
+    for (key, s) in stats {
+        println!(
+            "{:<40} {:>8} {:>8} {:>8} {:>10} {:>10} {:>8.0}%",
+            key,
+            s.detected,
+            s.executed,
+            s.settled,
+            display::percent(s.avg_detected_edge),
+            display::percent(s.avg_slippage),
+            s.capture_ratio * 100.0
+        );
+    }
+}
+
+fn print_windows(title: &str, grid: &Grid) {
+    println!("\n{}", title);
+    println!("{:<12} {:>8} {:>10} {:>10}", "Window (ET)", "Spreads", "Avg edge", "Max edge");
+    for (day, hour, cell) in grid.best_windows(TOP_WINDOWS) {
+        println!(
+            "{:<12} {:>8} {:>10} {:>10}",
+            format!("{} {:02}:00", day, hour),
+            cell.count,
+            display::percent(cell.avg_edge()),
+            display::percent(cell.max_edge)
+        );
+    }
+}
+
+/// Paper results of every shadow profile, to weigh against the live
+/// capture stats before promoting a strategy.
+fn print_shadow_profiles() {
+    let profiles: Vec<Profile> = Profile::list().into_iter().filter(|p| p.name.starts_with("shadow-")).collect();
+    if profiles.is_empty() {
+        return;
+    }
+    println!("\n🕶️  Shadow strategies");
+    println!("{:<28} {:>7} {:>9} {:>12} {:>12}", "Profile", "Closed", "Win rate", "Realized", "Total P&L");
+    for profile in profiles {
+        let summary = PaperTradingEngine::snapshot_of(&profile).summary();
+        println!(
+            "{:<28} {:>7} {:>9} {:>12} {:>12}",
+            profile.name,
+            summary.total_trades,
+            display::percent(summary.win_rate),
+            format!("${:+.2}", summary.realized_pnl),
+            format!("${:+.2}", summary.total_pnl)
+        );
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        display::init(&config);
+    }
+    let path = args.path;
+    let log = CaptureLog::load(&path);
+
+    if !log.records().is_empty() {
+        println!("📊 Opportunity capture ({} records)", log.records().len());
+        print_table("By execution mode", &log.stats_by_mode());
+        print_table("By market", &log.stats_by_market());
+    } else {
+        println!("No capture records in {}", path);
+    }
+    print_shadow_profiles();
+
+    // The bot's detection heatmap, or the capture log's detections without one
+    let mut heatmap = Heatmap::load(HEATMAP_FILE);
+    if heatmap.is_empty() {
+        heatmap = Heatmap::from_capture(&log);
+    }
+    if heatmap.is_empty() {
+        return Ok(());
+    }
+
+    println!("\n🔥 Most fruitful windows");
+    print_windows("All markets", &heatmap.combined());
+    for family in heatmap.families() {
+        if let Some(grid) = heatmap.grid(family) {
+            print_windows(family, grid);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Settings schema and checks.
+//!
+//! `config schema` prints every setting the bot reads, with its default and
+//! documentation, as an annotated `.env.example` (or JSON with `--json`).
+//! `config check` validates `.env` and the environment the way startup
+//! does, then loads the settings.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::app_state::ENV_FILE;
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::config_schema::{self, SETTINGS};
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Print every setting as an annotated .env.example
+    Schema {
+        /// Print the settings as JSON instead
+        #[arg(long)]
+        json: bool,
+    },
+    /// Validate .env and the environment, then load the settings
+    Check,
+}
+
+pub fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Schema { json: false } => print!("{}", config_schema::env_example()),
+        Command::Schema { json: true } => println!("{}", serde_json::to_string_pretty(SETTINGS)?),
+        Command::Check => {
+            config_schema::check_environment(ENV_FILE)?;
+            let config = Config::from_env()?;
+            println!("✅ {} is valid (settings fingerprint {})", ENV_FILE, config.fingerprint());
+        }
+    }
+    Ok(())
+}
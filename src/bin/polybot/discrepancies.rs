@@ -0,0 +1,116 @@
+//! Settlement and fill discrepancies.
+//!
+//! Lists the ledger the bot writes when the venues resolve a pair
+//! differently or a fill doesn't match our order, and acknowledges entries
+//! so trading on the affected market family resumes.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::risk::discrepancies::{Discrepancy, DiscrepancyLedger, DISCREPANCIES_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::utils::{run_info, timezone};
+
+/// Lists open entries (or all of them) without a subcommand.
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Include acknowledged entries
+    #[arg(long)]
+    all: bool,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// One entry with its evidence
+    Show {
+        /// Entry ID or a unique prefix of it
+        id: String,
+    },
+    /// Acknowledge an entry so trading on its family resumes
+    Ack {
+        /// Entry ID
+        id: String,
+        /// Who is acknowledging
+        #[arg(long)]
+        by: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        timezone::init(&config);
+    }
+    let ledger = DiscrepancyLedger::new(DISCREPANCIES_FILE);
+    match args.command {
+        Some(Command::Show { id }) => {
+            let entry = find(&ledger, &id)?;
+            println!("{}", serde_json::to_string_pretty(&entry)?);
+            Ok(())
+        }
+        Some(Command::Ack { id, by, note }) => {
+            let entry = ledger.acknowledge(&id, &by, note.as_deref())?;
+            println!("✅ Acknowledged {} on {}; trading on it resumes on the bot's next pass", entry.id, entry.market);
+            Ok(())
+        }
+        None => {
+            let Args { all, json, .. } = args;
+            let entries: Vec<Discrepancy> =
+                ledger.entries().into_iter().rev().filter(|d| all || d.is_open()).collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&run_info::stamped(&entries)?)?);
+                return Ok(());
+            }
+            list(&entries, all);
+            Ok(())
+        }
+    }
+}
+
+fn find(ledger: &DiscrepancyLedger, id: &str) -> Result<Discrepancy> {
+    let mut matching: Vec<Discrepancy> = ledger.entries().into_iter().filter(|d| d.id.starts_with(id)).collect();
+    match matching.len() {
+        1 => Ok(matching.remove(0)),
+        0 => anyhow::bail!("No discrepancy {}", id),
+        _ => anyhow::bail!("Discrepancy ID '{}' is ambiguous", id),
+    }
+}
+
+fn list(entries: &[Discrepancy], all: bool) {
+    if entries.is_empty() {
+        println!("{}", if all { "No discrepancies recorded" } else { "No open discrepancies" });
+        return;
+    }
+    println!("🚨 {:<8} {:<16} {:<10} {:<28} {:<8} Summary", "ID", "Opened", "Kind", "Market", "Family");
+    for d in entries {
+        println!(
+            "   {:<8} {:<16} {:<10} {:<28} {:<8} {}",
+            d.id,
+            timezone::format(d.opened_at, "%Y-%m-%d %H:%M"),
+            format!("{:?}", d.kind).to_lowercase(),
+            d.market,
+            d.family.as_deref().unwrap_or("-"),
+            d.summary,
+        );
+        println!(
+            "     evidence: {} quotes, {} orders, {} events{}",
+            d.evidence.quotes.len(),
+            d.evidence.orders.len(),
+            d.evidence.events.len(),
+            if d.evidence.resolution.is_some() { ", resolution" } else { "" },
+        );
+        if let Some(ack) = &d.acknowledged {
+            println!(
+                "     acknowledged by {} at {}{}",
+                ack.by,
+                timezone::format(ack.at, "%Y-%m-%d %H:%M"),
+                ack.note.as_ref().map(|n| format!(": {}", n)).unwrap_or_default()
+            );
+        }
+    }
+    println!("   {}", run_info::current().summary());
+}
@@ -0,0 +1,65 @@
+//! Leaderboard of our live executions per venue and market family.
+//!
+//! Shows how far fills landed from the prices that made us trade: decision
+//! to submitted limit, limit to fill, and the total in price and dollars.
+//! Families whose fills cost more than the tolerance are flagged for review.
+//! The log is written by the bot as it places live orders.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::analysis::execution::{ExecutionLog, EXECUTIONS_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Slippage per contract tolerated before a family is flagged
+    #[arg(long, value_name = "PRICE", default_value_t = 0.01)]
+    max_slippage: f64,
+    #[arg(long, value_name = "PATH", default_value = EXECUTIONS_FILE)]
+    file: String,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        display::init(&config);
+    }
+    let Args { max_slippage, file } = args;
+
+    let log = ExecutionLog::load(&file);
+    let board = log.leaderboard();
+    if board.is_empty() {
+        println!("No live executions recorded in {}", file);
+        return Ok(());
+    }
+
+    println!("🏁 Execution quality, best first (slippage per contract, positive costs us)");
+    println!(
+        "   {:<10} {:<12} {:>6} {:>7} {:>9} {:>9} {:>9} {:>10}",
+        "Venue", "Family", "Orders", "Filled", "Submit", "Fill", "Total", "Cost $"
+    );
+    for card in &board {
+        println!(
+            "   {:<10} {:<12} {:>6} {:>7} {:>9} {:>9} {:>9} {:>+10.2}{}",
+            card.venue,
+            card.family,
+            card.orders,
+            display::percent(card.fill_rate()),
+            display::price(card.avg_submit_slippage),
+            display::price(card.avg_fill_slippage),
+            display::price(card.avg_total_slippage),
+            card.slippage_usd,
+            if card.is_costly(max_slippage) { "  ⚠️ review" } else { "" }
+        );
+    }
+    let costly = board.iter().filter(|c| c.is_costly(max_slippage)).count();
+    if costly > 0 {
+        println!(
+            "\n{} of {} families give up more than {} per contract between decision and fill",
+            costly,
+            board.len(),
+            display::price(max_slippage)
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Operator commands for the bot: `polybot <command>`, with `polybot help
+//! <command>` for the options of each.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod approve;
+mod attribution;
+mod backtest;
+mod balances;
+mod bench;
+mod capture_report;
+mod config;
+mod discrepancies;
+mod executions;
+mod mutes;
+mod netting;
+mod observe;
+mod paper;
+mod profiles;
+mod redeem;
+mod rehearse;
+mod replay_trade;
+mod rewards;
+mod risk;
+mod screen;
+mod state;
+mod tags;
+mod traders;
+
+#[derive(Parser)]
+#[command(name = "polybot", version, about = "Polymarket-Kalshi arbitrage bot tools")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Approve the Polymarket exchange contracts to trade from an EOA
+    Approve(approve::Args),
+    /// Performance attribution per matched pair
+    Attribution(attribution::Args),
+    /// Backtest the arbitrage strategy against the recorded quote tape
+    #[command(subcommand)]
+    Backtest(backtest::Command),
+    /// USDC on Polygon, Polymarket collateral and the Kalshi balance
+    Balances,
+    /// Latency and jitter to the venue endpoints from this machine
+    Bench(bench::Args),
+    /// Opportunity capture, shadow strategies and the most fruitful windows
+    CaptureReport(capture_report::Args),
+    /// Settings schema and checks
+    #[command(subcommand)]
+    Config(config::Command),
+    /// Settlement and fill discrepancies
+    Discrepancies(discrepancies::Args),
+    /// Leaderboard of our live executions per venue and market family
+    Executions(executions::Args),
+    /// Muted and snoozed markets
+    Mutes(mutes::Args),
+    /// Net exposure per question across strategies and venues
+    Netting(netting::Args),
+    /// Read-only TUI for a running bot
+    Observe(observe::Args),
+    /// Paper portfolio maintenance
+    #[command(subcommand)]
+    Paper(paper::Command),
+    /// Named paper portfolios
+    Profiles(profiles::Args),
+    /// Redeem resolved Polymarket positions for USDC
+    Redeem(redeem::Args),
+    /// Rehearse live trading end to end without submitting anything
+    Rehearse,
+    /// Review a past paper trade against the recorded quote tape
+    ReplayTrade(replay_trade::Args),
+    /// Liquidity rewards earned by our resting Polymarket orders
+    Rewards(rewards::Args),
+    /// Monte Carlo risk report for the current strategy mix
+    Risk(risk::Args),
+    /// Rank open markets by liquidity and suggest pairs for the matcher
+    Screen(screen::Args),
+    /// Move a paper-trading experiment between machines
+    #[command(subcommand)]
+    State(state::Command),
+    /// Per-tag review of paper trades and live fills
+    Tags(tags::Args),
+    /// Leaderboard traders scored on history
+    Traders(traders::Args),
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Approve(args) => approve::run(args).await,
+        Command::Attribution(args) => attribution::run(args),
+        Command::Backtest(command) => backtest::run(command),
+        Command::Balances => balances::run().await,
+        Command::Bench(args) => bench::run(args).await,
+        Command::CaptureReport(args) => capture_report::run(args),
+        Command::Config(command) => config::run(command),
+        Command::Discrepancies(args) => discrepancies::run(args),
+        Command::Executions(args) => executions::run(args),
+        Command::Mutes(args) => mutes::run(args),
+        Command::Netting(args) => netting::run(args),
+        Command::Observe(args) => observe::run(args),
+        Command::Paper(command) => paper::run(command),
+        Command::Profiles(args) => profiles::run(args),
+        Command::Redeem(args) => redeem::run(args).await,
+        Command::Rehearse => rehearse::run().await,
+        Command::ReplayTrade(args) => replay_trade::run(args),
+        Command::Rewards(args) => rewards::run(args),
+        Command::Risk(args) => risk::run(args),
+        Command::Screen(args) => screen::run(args).await,
+        Command::State(command) => state::run(command),
+        Command::Tags(args) => tags::run(args),
+        Command::Traders(args) => traders::run(args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parses_subcommands_and_their_options() {
+        let cli = Cli::try_parse_from(["polybot", "paper", "reset", "--balance", "5000", "--yes"]).unwrap();
+        assert!(matches!(cli.command, Command::Paper(paper::Command::Reset { balance: Some(b), yes: true, .. }) if b == 5000.0));
+        let cli = Cli::try_parse_from(["polybot", "traders", "history", "0xabc"]).unwrap();
+        assert!(matches!(cli.command, Command::Traders(traders::Args { history: Some(traders::History::History { .. }), .. })));
+        assert!(Cli::try_parse_from(["polybot", "bench", "--rounds", "0"]).is_err());
+        assert!(Cli::try_parse_from(["polybot", "screen", "--bogus"]).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Muted and snoozed markets.
+//!
+//! Mutes a matched pair so the bot stops trading it, or snoozes its alerts
+//! for a few hours, without removing the pair. Changes are picked up on the
+//! bot's next detection pass.
+
+use anyhow::Result;
+use chrono::Utc;
+
+use polymarket_kalshi_arbitrage_bot::arbitrage::mutes::{MarketMute, MuteList, MUTES_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::utils::{run_info, timezone};
+
+/// Lists the markets muted or snoozed now without a subcommand.
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Stop trading and alerting on a market
+    Mute {
+        market: String,
+        #[command(flatten)]
+        by: By,
+    },
+    /// Silence a market's alerts for a few hours
+    Snooze {
+        market: String,
+        #[arg(long)]
+        hours: u64,
+        #[command(flatten)]
+        by: By,
+    },
+    /// Trade a muted market again
+    Unmute {
+        market: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+    /// Resume a snoozed market's alerts
+    Unsnooze {
+        market: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+}
+
+/// Who made a change, and why.
+#[derive(clap::Args)]
+struct By {
+    #[arg(long, default_value = "cli")]
+    by: String,
+    #[arg(long)]
+    reason: Option<String>,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        timezone::init(&config);
+    }
+    let mutes = MuteList::new(MUTES_FILE);
+    let now = Utc::now();
+    match args.command {
+        None => {
+            let active = mutes.active(now);
+            if args.json {
+                println!("{}", serde_json::to_string_pretty(&run_info::stamped(&active)?)?);
+            } else {
+                list(&active);
+            }
+        }
+        Some(Command::Mute { market, by }) => {
+            mutes.mute(&market, &by.by, by.reason.as_deref(), now)?;
+            println!("🔇 Muted {}: the bot stops trading and alerting on it", market);
+        }
+        Some(Command::Snooze { market, hours, by }) => {
+            let entry = mutes.snooze(&market, hours, &by.by, by.reason.as_deref(), now)?;
+            let until = entry.snoozed_until.unwrap_or(now);
+            println!("💤 Alerts for {} snoozed until {}", market, timezone::format(until, "%Y-%m-%d %H:%M %Z"));
+        }
+        Some(Command::Unmute { market, by }) if mutes.unmute(&market, &by, now)? => println!("🔊 Unmuted {}", market),
+        Some(Command::Unmute { market, .. }) => anyhow::bail!("{} is not muted", market),
+        Some(Command::Unsnooze { market, by }) if mutes.unsnooze(&market, &by, now)? => {
+            println!("🔔 Alerts for {} resumed", market)
+        }
+        Some(Command::Unsnooze { market, .. }) => anyhow::bail!("{} is not snoozed", market),
+    }
+    Ok(())
+}
+
+fn list(active: &[MarketMute]) {
+    if active.is_empty() {
+        println!("No muted or snoozed markets");
+        return;
+    }
+    let now = Utc::now();
+    println!("🔇 {:<32} {:<6} {:<18} {:<12} Reason", "Market", "Muted", "Snoozed until", "By");
+    for m in active {
+        println!(
+            "   {:<32} {:<6} {:<18} {:<12} {}",
+            m.market,
+            if m.muted { "yes" } else { "no" },
+            m.snoozed_until
+                .filter(|_| m.is_snoozed(now))
+                .map(|t| timezone::format(t, "%Y-%m-%d %H:%M"))
+                .unwrap_or_else(|| "-".to_string()),
+            m.by,
+            m.reason.as_deref().unwrap_or("-"),
+        );
+    }
+    println!("   {}", run_info::current().summary());
+}
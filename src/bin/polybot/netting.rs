@@ -0,0 +1,76 @@
+//! Net exposure per question across strategies and venues.
+//!
+//! Groups every paper portfolio's positions and the live fills in the audit
+//! log by the question they pay out on, showing the net delta and what the
+//! whole book makes or loses if each question resolves YES or NO.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::arbitrage::MarketMatcher;
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::money::MoneyDisplay;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Profile};
+use polymarket_kalshi_arbitrage_bot::risk::{Netting, QuestionExposure};
+use polymarket_kalshi_arbitrage_bot::utils::audit_log::AUDIT_FILE;
+use polymarket_kalshi_arbitrage_bot::utils::{run_info, AuditLog};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Only this paper portfolio
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let json = args.json;
+    // Without --profile every portfolio counts, as they share the venues
+    let profiles = match args.profile {
+        Some(name) => vec![Profile::named(&name)?],
+        None => Profile::list().into_iter().filter(Profile::exists).collect(),
+    };
+    let mut netting = Netting::new().with_matches(&MarketMatcher::new());
+    for profile in &profiles {
+        let engine = PaperTradingEngine::snapshot_of(profile);
+        netting.add_paper(&engine.portfolio, engine.trade_log.get_all());
+    }
+    let mut fills = AuditLog::new(AUDIT_FILE).recent(usize::MAX);
+    fills.reverse();
+    netting.add_fills(&fills);
+
+    let questions: Vec<&QuestionExposure> = netting.questions().collect();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(&questions)?)?);
+        return Ok(());
+    }
+    if questions.is_empty() {
+        println!("No open positions");
+        return Ok(());
+    }
+
+    let money = Config::from_env().map(|c| MoneyDisplay::from_config(&c)).unwrap_or_default();
+    println!(
+        "🧮 {:<32} {:>9} {:>9} {:>9} {:>12} {:>12} {:>12}",
+        "Question", "YES", "NO", "Net Δ", "Cost", "If YES", "If NO"
+    );
+    for q in &questions {
+        println!(
+            "   {:<32} {:>9.1} {:>9.1} {:>+9.1} {:>12} {:>12} {:>12}",
+            q.question,
+            q.yes_shares,
+            q.no_shares,
+            q.net_delta(),
+            money.fmt(q.cost),
+            money.fmt_signed(q.pnl_if_yes()),
+            money.fmt_signed(q.pnl_if_no()),
+        );
+        let strategies: Vec<String> = q.strategies.iter().map(|(s, d)| format!("{} {:+.1}", s, d)).collect();
+        let venues: Vec<&str> = q.venues.iter().map(String::as_str).collect();
+        println!("     {} · {}", venues.join(", "), strategies.join(", "));
+    }
+    let worst: f64 = questions.iter().map(|q| q.worst_loss()).sum();
+    println!("   Worst case if every question resolves against the book: {}", money.fmt(-worst));
+    println!("   {}", run_info::current().summary());
+    Ok(())
+}
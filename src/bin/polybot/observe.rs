@@ -0,0 +1,27 @@
+//! Read-only observer for a running bot.
+//!
+//! Renders the TUI dashboards from the bot's shared state files in `data/`,
+//! reloading every few seconds. Trading and settings keys are disabled and
+//! nothing is ever written, so it is safe to run alongside the bot on a
+//! shared deployment.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::paper_trading::Profile;
+use polymarket_kalshi_arbitrage_bot::tui::{self, App};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Paper portfolio to show instead of PAPER_PROFILE's
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut app = App::observer();
+    if let Some(name) = args.profile {
+        app.switch_profile(Profile::named(&name)?)?;
+    }
+    tui::run(app)?;
+    Ok(())
+}
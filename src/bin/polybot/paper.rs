@@ -0,0 +1,69 @@
+//! Paper portfolio maintenance.
+//!
+//! `reset` starts a paper portfolio over with a fresh balance: positions and
+//! P&L are cleared and the old trade log is archived next to the current one.
+//! The balance defaults to PAPER_BALANCE. Stop the bot and any TUI trading
+//! the profile first, or they will write their copy back.
+
+use anyhow::Result;
+use std::io::{self, Write};
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::paper_trading::engine::DEFAULT_BALANCE;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Profile};
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Start a portfolio over with a fresh balance, archiving its trade log
+    Reset {
+        /// Portfolio to reset (default PAPER_PROFILE)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+        /// Starting balance (default PAPER_BALANCE)
+        #[arg(long)]
+        balance: Option<f64>,
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+pub fn run(command: Command) -> Result<()> {
+    let Command::Reset { profile, balance, yes: confirmed } = command;
+    let config = Config::from_env().ok();
+    let profile = match (profile, &config) {
+        (Some(name), _) => Profile::named(&name)?,
+        (None, Some(config)) => Profile::from_config(config)?,
+        (None, None) => Profile::default(),
+    };
+    let balance = balance.unwrap_or_else(|| config.as_ref().map_or(DEFAULT_BALANCE, |c| c.paper_balance));
+    if !balance.is_finite() || balance <= 0.0 {
+        anyhow::bail!("--balance must be positive");
+    }
+    if !profile.exists() {
+        anyhow::bail!("Portfolio '{}' does not exist ({}): create it with `polybot profiles new`", profile.name, profile.portfolio_file);
+    }
+
+    let mut engine = PaperTradingEngine::for_profile(&profile, balance)?;
+    let summary = engine.summary();
+    println!(
+        "🧹 Resetting '{}': ${:.2} value, {} positions, {} trades -> ${:.2}",
+        profile.name, summary.total_value, summary.positions_count, summary.total_trades, balance
+    );
+    if !confirmed {
+        print!("Continue? [y/N] ");
+        io::stdout().flush()?;
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+    match engine.update(|engine| engine.reset_to(balance))? {
+        Some(archive) => println!("📦 Trade log archived to {}", archive),
+        None => println!("📦 No trade log to archive"),
+    }
+    println!("✅ Portfolio '{}' reset to ${:.2}", profile.name, balance);
+    Ok(())
+}
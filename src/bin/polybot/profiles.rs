@@ -0,0 +1,73 @@
+//! Named paper portfolios.
+//!
+//! Each profile is a separate paper portfolio and trade log, so experiments
+//! can run side by side. Select one with PAPER_PROFILE or `tui --profile`.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::monitor::marker::{load_marks, MARKS_FILE};
+use polymarket_kalshi_arbitrage_bot::paper_trading::engine::DEFAULT_BALANCE;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Profile};
+
+/// Lists the value and P&L of every portfolio without a subcommand.
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Create a portfolio
+    New {
+        name: String,
+        /// Starting balance (default PAPER_BALANCE)
+        #[arg(long)]
+        balance: Option<f64>,
+    },
+}
+
+pub fn run(args: Args) -> Result<()> {
+    match args.command {
+        None => list(),
+        Some(Command::New { name, balance }) => {
+            let profile = Profile::named(&name)?;
+            let balance = balance.unwrap_or_else(|| Config::from_env().map_or(DEFAULT_BALANCE, |c| c.paper_balance));
+            if profile.exists() {
+                anyhow::bail!("Portfolio '{}' already exists ({})", profile.name, profile.portfolio_file);
+            }
+            PaperTradingEngine::for_profile(&profile, balance)?;
+            println!("📁 Created portfolio '{}' with ${:.2} at {}", profile.name, balance, profile.portfolio_file);
+            Ok(())
+        }
+    }
+}
+
+fn list() -> Result<()> {
+    let marks = load_marks(MARKS_FILE);
+    println!(
+        "📁 {:<20} {:>12} {:>12} {:>8} {:>10} {:>12} {:>9} {:>7}",
+        "Portfolio", "Value", "Gross P&L", "P&L %", "Fees", "Net P&L", "Positions", "Trades"
+    );
+    for profile in Profile::list() {
+        if !profile.exists() {
+            continue;
+        }
+        let mut engine = PaperTradingEngine::snapshot_of(&profile);
+        engine.portfolio.update_prices(&marks);
+        let summary = engine.summary();
+        println!(
+            "   {:<20} {:>12.2} {:>+12.2} {:>7.1}% {:>10.2} {:>+12.2} {:>9} {:>7}",
+            profile.name,
+            summary.total_value,
+            summary.total_pnl,
+            summary.pnl_percent,
+            summary.fees_paid,
+            summary.net_pnl,
+            summary.positions_count,
+            summary.total_trades,
+        );
+    }
+    Ok(())
+}
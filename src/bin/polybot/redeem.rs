@@ -0,0 +1,28 @@
+//! Redeems resolved Polymarket positions for USDC.
+
+use anyhow::{Context, Result};
+
+use polymarket_kalshi_arbitrage_bot::chain::{redeem_positions, TxManager};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Condition IDs of the resolved markets
+    #[arg(required = true, value_name = "CONDITION_ID")]
+    conditions: Vec<String>,
+}
+
+pub async fn run(args: Args) -> Result<()> {
+    let conditions = args.conditions;
+
+    let config = Config::from_env()?;
+    if config.polymarket_signature_type.is_proxy() {
+        anyhow::bail!("Positions held by a proxy wallet are redeemed through the proxy, not the signing key");
+    }
+    let tx = TxManager::from_config(&config)?.context("POLYMARKET_PRIVATE_KEY is required to redeem")?;
+    for condition in &conditions {
+        let hash = redeem_positions(&tx, condition).await?;
+        println!("✅ Redeemed {} ({:?})", condition, hash);
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! Rehearses live trading end to end without submitting anything: checks
+//! credentials, discovers markets, runs one detection pass and the risk
+//! checks, then builds and signs the orders for the best pair and prints
+//! the exact requests that would have been sent.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::rehearsal::Rehearsal;
+
+pub async fn run() -> Result<()> {
+    let config = Config::from_env()?;
+    let rehearsal = Rehearsal::run(&config).await?;
+
+    println!("🎭 Rehearsal (nothing is submitted)");
+    for step in &rehearsal.steps {
+        println!("{}", step);
+    }
+    for (venue, request) in &rehearsal.payloads {
+        println!();
+        println!("── {} ── {} {}", venue, request.method, request.url);
+        for (name, value) in request.redacted_headers() {
+            println!("{}: {}", name, value);
+        }
+        match serde_json::from_str::<serde_json::Value>(&request.body) {
+            Ok(body) => println!("{}", serde_json::to_string_pretty(&body)?),
+            Err(_) => println!("{}", request.body),
+        }
+    }
+
+    if !rehearsal.passed() {
+        anyhow::bail!("Rehearsal failed: live trading would not reach order submission");
+    }
+    println!();
+    println!("✅ Live trading would have submitted {} orders", rehearsal.payloads.len());
+    Ok(())
+}
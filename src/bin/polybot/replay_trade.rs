@@ -0,0 +1,135 @@
+//! Reviews a past paper trade against the recorded quote tape.
+//!
+//! Shows the prices the trade's outcome had on its venue in the minutes
+//! around the fill, and what the trade would have made entered earlier or
+//! later, or at other sizes, with the exit held fixed. The trade is looked
+//! up in every paper portfolio. Record a tape by running the bot with
+//! RECORD_QUOTES=true.
+
+use anyhow::{Context, Result};
+use chrono::Duration;
+
+use polymarket_kalshi_arbitrage_bot::backtest::sweep::parse_values;
+use polymarket_kalshi_arbitrage_bot::backtest::tape::QUOTE_TAPE_FILE;
+use polymarket_kalshi_arbitrage_bot::backtest::{QuoteTape, TradeReplay};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::fees::FeeSchedule;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTrade, Profile, TradeLog};
+use polymarket_kalshi_arbitrage_bot::utils::timezone;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Trade ID or a unique prefix of it
+    id: String,
+    /// Minutes of quotes shown either side of the fill
+    #[arg(long, value_name = "MIN", default_value_t = 10)]
+    window: i64,
+    /// Entry delays to try, in minutes
+    #[arg(long, value_name = "LIST", default_value = "-5,-2,-1,0,1,2,5", allow_hyphen_values = true)]
+    delays: String,
+    /// Sizes to try, as multiples of the trade's size
+    #[arg(long, value_name = "LIST", default_value = "0.5,1,2")]
+    sizes: String,
+    #[arg(long, value_name = "PATH", default_value = QUOTE_TAPE_FILE)]
+    tape: String,
+}
+
+/// Price rows shown at most; longer windows are sampled evenly
+const MAX_ROWS: usize = 30;
+
+fn quote(price: Option<f64>) -> String {
+    price.map(display::price).unwrap_or_else(|| "-".to_string())
+}
+
+fn find_trade(id: &str) -> Result<PaperTrade> {
+    let matches: Vec<PaperTrade> = Profile::list()
+        .iter()
+        .flat_map(|p| TradeLog::snapshot(&p.trades_file).get_all().to_vec())
+        .filter(|t| t.id.starts_with(id))
+        .collect();
+    match matches.len() {
+        0 => anyhow::bail!("No paper trade with ID {}", id),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        n => anyhow::bail!("{} trades start with {}; give more of the ID", n, id),
+    }
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let fees = match Config::from_env() {
+        Ok(config) => {
+            display::init(&config);
+            FeeSchedule::from_config(&config)?
+        }
+        Err(_) => FeeSchedule::new(),
+    };
+    let Args { id, window, delays, sizes, tape } = args;
+    let delays: Vec<Duration> =
+        parse_values::<i64>(&delays).context("Invalid --delays")?.into_iter().map(Duration::minutes).collect();
+    let multiples: Vec<f64> = parse_values(&sizes).context("Invalid --sizes")?;
+
+    let trade = find_trade(&id)?;
+    let replay = TradeReplay::new(trade, &QuoteTape::new(&tape).load(), Duration::minutes(window));
+    let trade = &replay.trade;
+
+    println!("🔁 Trade {} ({})", trade.id, trade.strategy);
+    println!(
+        "   {} {} {} on {} @ {} for ${:.2} ({:.2} contracts)",
+        timezone::format(trade.timestamp, "%Y-%m-%d %H:%M:%S"),
+        trade.side,
+        trade.market,
+        trade.platform,
+        display::price(trade.entry_price),
+        trade.filled_size(),
+        replay.contracts()
+    );
+    match (trade.exit_price, trade.pnl) {
+        (Some(exit), Some(pnl)) => println!("   Closed @ {}: P&L ${:+.2}", display::price(exit), pnl - trade.fee.unwrap_or(0.0)),
+        _ => println!("   Open; what-ifs are marked at {}", quote(replay.exit)),
+    }
+
+    if replay.points.is_empty() {
+        println!("\nNo recorded quotes for {} within {} minutes of the trade in {}", replay.market, window, tape);
+        return Ok(());
+    }
+
+    println!("\n📈 {} {} on {} (±{} min)", replay.market, if replay.yes { "YES" } else { "NO" }, trade.platform, window);
+    println!("   {:>8} {:>8} {:>8}", "Offset", "Bid", "Ask");
+    let step = replay.points.len().div_ceil(MAX_ROWS);
+    let entry_at = replay.quote_at_entry().map(|p| p.at);
+    for point in replay.points.iter().step_by(step) {
+        let offset = (point.at - trade.timestamp).num_seconds();
+        println!(
+            "   {:>+7}s {:>8} {:>8}{}",
+            offset,
+            quote(point.bid),
+            quote(point.ask),
+            if Some(point.at) == entry_at { "  ← quote at entry" } else { "" }
+        );
+    }
+    if let Some(slippage) = replay.slippage() {
+        println!("   Paid {} vs the quote at entry", display::price(slippage));
+    }
+    if let Some((at, price)) = replay.best_entry() {
+        println!("   Best entry in the window: {} at {:+}s", display::price(price), (at - trade.timestamp).num_seconds());
+    }
+
+    let sizes: Vec<f64> = multiples.iter().map(|m| m * trade.filled_size()).collect();
+    let scenarios = replay.scenarios(&fees, &delays, &sizes);
+    if scenarios.is_empty() {
+        return Ok(());
+    }
+    println!("\n🧪 What if (exit fixed at {})", quote(replay.exit));
+    println!("   {:>7} {:>9} {:>8} {:>10} {:>10}", "Delay", "Size", "Entry", "Contracts", "P&L");
+    for w in scenarios {
+        println!(
+            "   {:>+6}m {:>9} {:>8} {:>10.2} {:>+10.2}",
+            w.delay.num_minutes(),
+            format!("${:.2}", w.size_usd),
+            display::price(w.entry),
+            w.contracts,
+            w.pnl
+        );
+    }
+    Ok(())
+}
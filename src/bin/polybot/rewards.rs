@@ -0,0 +1,59 @@
+//! Prints the liquidity rewards our resting Polymarket orders have earned,
+//! as estimated by the running bot: how long each market's quotes
+//! qualified, their average qualifying size, our share of the pool and the
+//! rewards accrued.
+
+use anyhow::Result;
+use polymarket_kalshi_arbitrage_bot::analysis::rewards::{RewardsTracker, REWARDS_FILE};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Rewards file the bot writes
+    #[arg(default_value = REWARDS_FILE)]
+    path: String,
+}
+
+/// Longest market label shown.
+const LABEL_WIDTH: usize = 44;
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        display::init(&config);
+    }
+    let path = args.path;
+    let tracker = RewardsTracker::load(&path);
+    if tracker.markets().is_empty() {
+        println!("No rewards tracked yet in {} - the bot scores resting Polymarket orders as it runs", path);
+        return Ok(());
+    }
+
+    let mut markets: Vec<_> = tracker.markets().values().collect();
+    markets.sort_by(|a, b| b.earned_usd.total_cmp(&a.earned_usd));
+    println!("🎁 Liquidity rewards (estimated)");
+    println!(
+        "{:<44} {:>8} {:>8} {:>9} {:>7} {:>9} {:>9}",
+        "Market", "Tracked", "Uptime", "Avg size", "Share", "$/day", "Earned"
+    );
+    for m in markets {
+        let label: String = m.label.chars().take(LABEL_WIDTH).collect();
+        let tracked = format!("{:.1}h", m.tracked_secs / 3600.0);
+        if m.terms.is_none() {
+            println!("{:<44} {:>8} {:>8}", label, tracked, "no rewards");
+            continue;
+        }
+        println!(
+            "{:<44} {:>8} {:>8} {:>9.0} {:>7} {:>9.2} {:>9.2}",
+            label,
+            tracked,
+            display::percent(m.uptime()),
+            m.avg_qualifying_size(),
+            display::percent(m.last_share),
+            m.daily_rate(),
+            m.earned_usd
+        );
+    }
+    println!("{:<44} {:>62.2}", "Total", tracker.total_earned());
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Monte Carlo risk report for the current strategy mix.
+//!
+//! Resamples closed paper trades to estimate the spread of outcomes over
+//! the horizon: value at risk, probability of ruin and expected drawdown.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::money::MoneyDisplay;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Profile};
+use polymarket_kalshi_arbitrage_bot::risk::allocator::ALLOCATION_FILE;
+use polymarket_kalshi_arbitrage_bot::risk::{CapitalAllocator, MonteCarlo};
+use polymarket_kalshi_arbitrage_bot::utils::run_info;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Horizon in days
+    #[arg(long)]
+    days: Option<u32>,
+    /// Simulated paths
+    #[arg(long)]
+    paths: Option<usize>,
+    /// Seed, for a repeatable run
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Paper portfolio to simulate (default PAPER_PROFILE)
+    #[arg(long, value_name = "NAME")]
+    profile: Option<String>,
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    let mut mc = MonteCarlo::new();
+    if let Some(days) = args.days {
+        mc = mc.with_days(days);
+    }
+    if let Some(paths) = args.paths {
+        mc = mc.with_paths(paths);
+    }
+    if let Some(seed) = args.seed {
+        mc = mc.with_seed(seed);
+    }
+    let json = args.json;
+
+    let config = Config::from_env()?;
+    let money = MoneyDisplay::from_config(&config);
+    let profile = match args.profile {
+        Some(name) => Profile::named(&name)?,
+        None => Profile::from_config(&config)?,
+    };
+    let engine = PaperTradingEngine::snapshot_of(&profile);
+    let equity = engine.portfolio.total_value();
+    let allocator = CapitalAllocator::from_config(&config, equity).map(|a| a.with_state_file(ALLOCATION_FILE));
+
+    let Some(report) = mc.simulate(engine.trade_log.get_all(), allocator.as_ref(), equity) else {
+        println!("No closed trades to simulate yet");
+        return Ok(());
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&run_info::stamped(&report)?)?);
+        return Ok(());
+    }
+
+    println!(
+        "🎲 {}-day Monte Carlo: {} paths from {} at {:.1} trades/day",
+        report.days, report.paths, money.fmt(report.starting_equity), report.trades_per_day
+    );
+    println!("   {}", run_info::current().summary());
+    if allocator.is_some() {
+        for (strategy, scale) in &report.strategy_scale {
+            println!("   {:<16} x{:.2} of historical P&L", strategy, scale);
+        }
+    }
+    println!("{:<22} {:>14}", "Expected P&L", money.fmt_signed(report.expected_pnl));
+    println!("{:<22} {:>14}", "Median P&L", money.fmt_signed(report.median_pnl));
+    println!("{:<22} {:>14}", "5th percentile", money.fmt_signed(report.p5_pnl));
+    println!("{:<22} {:>14}", "95th percentile", money.fmt_signed(report.p95_pnl));
+    println!("{:<22} {:>14}", "VaR (95%)", money.fmt(report.var_95));
+    println!("{:<22} {:>14}", "CVaR (95%)", money.fmt(report.cvar_95));
+    println!("{:<22} {:>14}", "Expected max drawdown", money.fmt(report.expected_drawdown));
+    println!("{:<22} {:>14}", "95th pct drawdown", money.fmt(report.p95_drawdown));
+    println!(
+        "{:<22} {:>13.1}%  (equity below {})",
+        "Probability of ruin", report.prob_ruin * 100.0, money.fmt(report.ruin_level)
+    );
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Ranks open markets on both venues by liquidity and suggests cross-venue
+//! pairs worth adding to the matcher.
+
+use anyhow::Result;
+use chrono::Utc;
+
+use polymarket_kalshi_arbitrage_bot::analysis::screener::{self, Candidate, PairSuggestion};
+use polymarket_kalshi_arbitrage_bot::arbitrage::MarketMatcher;
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::utils::run_info;
+
+#[derive(clap::Args)]
+pub struct Args {
+    #[arg(long)]
+    json: bool,
+    /// Markets fetched per venue
+    #[arg(long, default_value_t = 500)]
+    limit: usize,
+    /// Rows shown per section
+    #[arg(long, default_value_t = 25)]
+    top: usize,
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(width - 1).collect::<String>())
+    }
+}
+
+fn print_markets(ranked: &[Candidate], top: usize) {
+    println!("\n🏆 Top markets");
+    println!(
+        "{:<6} {:<11} {:<48} {:>12} {:>10} {:>7} {:>8}",
+        "Score", "Venue", "Market", "Volume", "OI/Liq", "Spread", "Closes"
+    );
+    for c in ranked.iter().take(top) {
+        println!(
+            "{:<6.2} {:<11} {:<48} {:>12.0} {:>10.0} {:>7} {:>8}",
+            c.score,
+            c.venue,
+            truncate(&c.title, 48),
+            c.volume,
+            c.open_interest,
+            c.spread.map(display::price).unwrap_or_else(|| "-".to_string()),
+            c.hours_to_close.map(|h| format!("{:.0}h", h)).unwrap_or_else(|| "-".to_string()),
+        );
+    }
+}
+
+fn print_pairs(pairs: &[PairSuggestion]) {
+    println!("\n🔗 Suggested pairs (not yet in the matcher)");
+    if pairs.is_empty() {
+        println!("  none");
+    }
+    for p in pairs {
+        println!(
+            "  {:.0}% match, score {:.2}: {} <-> {}\n      kalshi {} | polymarket {}",
+            p.similarity * 100.0,
+            p.score,
+            truncate(&p.kalshi_title, 40),
+            truncate(&p.polymarket_title, 40),
+            p.kalshi_ticker,
+            p.polymarket_id,
+        );
+    }
+}
+
+pub async fn run(args: Args) -> Result<()> {
+    let Args { json, limit, top } = args;
+
+    let config = Config::from_env()?;
+    display::init(&config);
+    let kalshi = KalshiClient::new(&config)?;
+    let poly = PolymarketClient::new(&config)?;
+    let now = Utc::now();
+
+    let mut candidates: Vec<Candidate> = kalshi
+        .get_open_markets(limit)
+        .await?
+        .iter()
+        .map(|m| screener::from_kalshi(m, now))
+        .collect();
+    candidates.extend(
+        poly.get_active_gamma_markets(limit)
+            .await?
+            .iter()
+            .filter_map(|m| screener::from_gamma(m, now)),
+    );
+
+    let ranked = screener::rank(candidates);
+    let pairs = screener::suggest_pairs(&ranked, &MarketMatcher::new(), top);
+
+    if json {
+        let output = serde_json::json!({
+            "generated_at": now,
+            "markets": ranked.iter().take(top).collect::<Vec<_>>(),
+            "pairs": pairs,
+            "run": run_info::current(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!("🔎 Screened {} open markets", ranked.len());
+        print_markets(&ranked, top);
+        print_pairs(&pairs);
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Moves a paper-trading experiment between machines.
+//!
+//! `export` bundles portfolios, trade logs, matched markets, processed copy
+//! trades and stats into one file; `import` unpacks it into this machine's
+//! `data/`. Import refuses to replace existing files unless `--force` is
+//! given, and then moves them aside to `<file>.pre-import-<timestamp>`.
+//! Stop the bot before importing, or it will write its own state back.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::utils::bundle::Bundle;
+use polymarket_kalshi_arbitrage_bot::utils::run_info;
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Bundle this machine's state into one file
+    Export {
+        /// Bundle to write (default polybot-state-<timestamp>.json)
+        file: Option<String>,
+    },
+    /// Unpack a bundle into data/
+    Import {
+        file: String,
+        /// Move existing files aside instead of refusing
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+pub fn run(command: Command) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        run_info::init(&config);
+    }
+    match command {
+        Command::Export { file: Some(file) } => export(&file),
+        Command::Export { file: None } => {
+            export(&format!("polybot-state-{}.json", chrono::Utc::now().format("%Y%m%dT%H%M%S")))
+        }
+        Command::Import { file, force } => import(&file, force),
+    }
+}
+
+fn export(file: &str) -> Result<()> {
+    let bundle = Bundle::collect(Path::new("."))?;
+    if bundle.files.is_empty() {
+        anyhow::bail!("Nothing to export: no state found under data/");
+    }
+    bundle.save(file)?;
+    for path in bundle.files.keys() {
+        println!("   {}", path);
+    }
+    println!("📦 Exported {} files to {}", bundle.files.len(), file);
+    Ok(())
+}
+
+fn import(file: &str, force: bool) -> Result<()> {
+    let bundle = Bundle::load(file)?;
+    println!(
+        "📦 {} files exported {} from {} ({})",
+        bundle.files.len(),
+        bundle.exported_at.format("%Y-%m-%d %H:%M UTC"),
+        bundle.exported_by.hostname,
+        bundle.exported_by.version,
+    );
+    let conflicts = bundle.conflicts(Path::new("."));
+    if !conflicts.is_empty() && !force {
+        anyhow::bail!("These files already exist, pass --force to move them aside and import:\n   {}", conflicts.join("\n   "));
+    }
+    let written = bundle.unpack(Path::new(".")).context("Import failed")?;
+    for path in &written {
+        println!("   {}{}", path, if conflicts.contains(path) { "  (previous moved aside)" } else { "" });
+    }
+    println!("✅ Imported {} files", written.len());
+    Ok(())
+}
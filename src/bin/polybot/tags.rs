@@ -0,0 +1,83 @@
+//! Per-tag review of paper trades and live fills.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTrade, Profile, TradeLog};
+use polymarket_kalshi_arbitrage_bot::utils::annotations::{normalize_tag, ANNOTATIONS_FILE};
+use polymarket_kalshi_arbitrage_bot::utils::audit_log::AUDIT_FILE;
+use polymarket_kalshi_arbitrage_bot::utils::{timezone, Annotations, AuditLog};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// Trades carrying this tag, with notes (default: totals for every tag)
+    tag: Option<String>,
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        display::init(&config);
+    }
+    let annotations = Annotations::load(ANNOTATIONS_FILE);
+    // Trade IDs are unique, so tags span every paper portfolio
+    let trades: Vec<PaperTrade> = Profile::list()
+        .iter()
+        .flat_map(|p| TradeLog::snapshot(&p.trades_file).get_all().to_vec())
+        .collect();
+    let fills = AuditLog::new(AUDIT_FILE).recent(usize::MAX);
+
+    match args.tag {
+        None => {
+            let report = annotations.report(&trades, &fills);
+            if report.is_empty() {
+                println!("No tagged trades yet");
+                return Ok(());
+            }
+            println!("🏷  {:<20} {:>6} {:>6} {:>8} {:>10} {:>6} {:>12}", "Tag", "Trades", "Closed", "Win %", "P&L", "Fills", "Live $");
+            for (tag, stats) in report {
+                let win_rate = if stats.closed > 0 {
+                    format!("{:.0}%", stats.wins as f64 / stats.closed as f64 * 100.0)
+                } else {
+                    "-".to_string()
+                };
+                println!(
+                    "   {:<20} {:>6} {:>6} {:>8} {:>+10.2} {:>6} {:>12.2}",
+                    tag, stats.trades, stats.closed, win_rate, stats.pnl, stats.live_fills, stats.live_notional
+                );
+            }
+        }
+        Some(tag) => {
+            let tag = normalize_tag(&tag);
+            let show = |id: &str, summary: String| {
+                println!("{}", summary);
+                if let Some(notes) = annotations.get(id).and_then(|a| a.notes.as_deref()) {
+                    println!("    📝 {}", notes);
+                }
+            };
+            for trade in trades.iter().filter(|t| annotations.has_tag(&t.id, &tag)) {
+                show(&trade.id, format!(
+                    "{} {:<24} {:?} ${:.2} @ {}  P&L {}",
+                    timezone::format(trade.timestamp, "%Y-%m-%d %H:%M"),
+                    trade.market,
+                    trade.side,
+                    trade.filled_size(),
+                    display::price(trade.entry_price),
+                    trade.pnl.map(|p| format!("{:+.2}", p)).unwrap_or_else(|| "open".to_string()),
+                ));
+            }
+            for fill in fills.iter().filter(|e| e.kind == "fill" && annotations.has_tag(&e.order_id, &tag)) {
+                show(&fill.order_id, format!(
+                    "{} {:<24} {} {:.1} @ {}  live {}",
+                    timezone::format(fill.at, "%Y-%m-%d %H:%M"),
+                    fill.market,
+                    fill.side,
+                    fill.size,
+                    display::price(fill.price),
+                    fill.venue,
+                ));
+            }
+        }
+    }
+    Ok(())
+}
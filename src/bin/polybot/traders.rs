@@ -0,0 +1,105 @@
+//! Leaderboard traders scored on history.
+//!
+//! Reads the snapshots the bot appends on each leaderboard refresh and ranks
+//! traders by the share of months they stayed on the board, or shows one
+//! trader's rank snapshot by snapshot.
+
+use anyhow::Result;
+
+use polymarket_kalshi_arbitrage_bot::analysis::leaderboard_history::{
+    self, Appearance, Consistency, LeaderboardHistory, LEADERBOARD_HISTORY_FILE,
+};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::utils::{run_info, timezone};
+
+/// Ranks traders by consistency without a subcommand.
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    pub history: Option<History>,
+    /// Traders listed
+    #[arg(long, default_value_t = 25)]
+    top: usize,
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(clap::Subcommand)]
+pub enum History {
+    /// One trader's rank, snapshot by snapshot
+    History { address: String },
+}
+
+pub fn run(args: Args) -> Result<()> {
+    if let Ok(config) = Config::from_env() {
+        timezone::init(&config);
+    }
+    let snapshots = LeaderboardHistory::new(LEADERBOARD_HISTORY_FILE).load();
+    let address = args.history.map(|History::History { address }| address);
+    let Args { top, json, .. } = args;
+
+    if snapshots.is_empty() {
+        println!("No leaderboard history yet: set LEADERBOARD_REFRESH_SECS and let the bot run");
+        return Ok(());
+    }
+    match address {
+        Some(address) => {
+            let seen = leaderboard_history::appearances(&snapshots, &address);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&run_info::stamped(&seen)?)?);
+            } else {
+                history(&address, &seen, snapshots.len());
+            }
+        }
+        None => {
+            let mut scores = leaderboard_history::consistency(&snapshots);
+            scores.truncate(top);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&run_info::stamped(&scores)?)?);
+            } else {
+                list(&scores, snapshots.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn list(scores: &[Consistency], snapshots: usize) {
+    let months = scores.first().map(|c| c.months_observed).unwrap_or(0);
+    println!("🏆 Leaderboard consistency over {} snapshots in {} months", snapshots, months);
+    println!("   {:<44} {:<20} {:>7} {:>6} {:>5} {:>8} Last seen", "Address", "Name", "Months", "Seen", "Best", "Avg rank");
+    for c in scores {
+        println!(
+            "   {:<44} {:<20} {:>7} {:>6} {:>5} {:>8.1} {}{}",
+            c.address,
+            c.name,
+            format!("{}/{}", c.months, c.months_observed),
+            c.appearances,
+            c.best_rank,
+            c.avg_rank,
+            timezone::format(c.last_seen, "%Y-%m-%d"),
+            if c.is_one_hit_wonder() { "  one-hit wonder" } else { "" },
+        );
+    }
+    println!("   {}", run_info::current().summary());
+}
+
+fn history(address: &str, seen: &[Appearance], snapshots: usize) {
+    if seen.is_empty() {
+        println!("{} is in none of the {} leaderboard snapshots", address, snapshots);
+        return;
+    }
+    println!("📜 {} on the leaderboard in {} of {} snapshots", address, seen.len(), snapshots);
+    println!("   {:<18} {:>5} {:<20} {:>14} {:>14}", "Taken", "Rank", "Name", "P&L", "Volume");
+    for a in seen {
+        println!(
+            "   {:<18} {:>5} {:<20} {:>14.2} {:>14.2}",
+            timezone::format(a.taken_at, "%Y-%m-%d %H:%M"),
+            a.rank,
+            a.name,
+            a.profit_and_loss,
+            a.volume,
+        );
+    }
+    println!("   {}", run_info::current().summary());
+}
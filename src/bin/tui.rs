@@ -1,55 +1,40 @@
 //! TUI binary entry point.
+//!
+//! Usage: tui [--profile NAME]
 
 use std::io;
-use std::time::Duration;
 
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use ratatui::{backend::CrosstermBackend, Terminal};
-
-use polymarket_kalshi_arbitrage_bot::tui::{app::App, events, ui};
+use polymarket_kalshi_arbitrage_bot::paper_trading::Profile;
+use polymarket_kalshi_arbitrage_bot::tui::{self, App};
+use polymarket_kalshi_arbitrage_bot::utils::audit_log::{AuditLog, AUDIT_FILE};
 
 fn main() -> io::Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    // Create app state
-    let mut app = App::new();
-
-    // Main loop
-    loop {
-        // Draw UI
-        terminal.draw(|frame| ui::draw(frame, &app))?;
-
-        // Handle events with 100ms timeout
-        if let Some(event) = events::poll_event(Duration::from_millis(100)) {
-            events::handle_key_event(&mut app, event);
-        }
-
-        // Check if we should quit
-        if app.should_quit {
-            break;
-        }
+    let mut app = App::new().map_err(|e| io::Error::other(format!("{:#}", e)))?;
+    if let Some(profile) = profile_arg()? {
+        app.switch_profile(profile).map_err(|e| io::Error::other(format!("{:#}", e)))?;
     }
-
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    let app = tui::run(app)?;
 
     println!("Thanks for using Polymarket-Kalshi Arbitrage Bot!");
-    println!("Final balance: ${:.2}", app.engine.portfolio.total_value());
+    println!("Final balance: {}", app.money.fmt(app.engine.portfolio.total_value()));
+    if !app.read_only {
+        let mut session = app.engine.session.clone();
+        session.update(&app.engine.portfolio, app.engine.trade_log.get_all().len(), chrono::Utc::now());
+        AuditLog::new(AUDIT_FILE).record_session(&session.summary());
+        println!("Session P&L: {} (all-time {})", app.money.fmt_signed(session.pnl()), app.money.fmt_signed(session.all_time_pnl));
+    }
 
     Ok(())
 }
+
+/// Paper portfolio given with `--profile`, overriding PAPER_PROFILE.
+fn profile_arg() -> io::Result<Option<Profile>> {
+    let mut args = std::env::args().skip(1);
+    match (args.next().as_deref(), args.next()) {
+        (None, _) => Ok(None),
+        (Some("--profile"), Some(name)) => Profile::named(&name)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e.to_string())),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "Usage: tui [--profile NAME]")),
+    }
+}
@@ -3,7 +3,7 @@ use polymarket_kalshi_arbitrage_bot::config::Config;
 use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
 use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
 use dotenvy::dotenv;
-use tracing::{info, Level};
+use tracing::Level;
 use tracing_subscriber::FmtSubscriber;
 
 #[tokio::main]
@@ -11,7 +11,7 @@ async fn main() -> Result<()> {
     dotenv().ok();
     
     // Initialize logging with INFO level
-    let subscriber = FmtSubscriber::builder()
+    FmtSubscriber::builder()
         .with_max_level(Level::INFO)
         .with_target(false)
         .init();
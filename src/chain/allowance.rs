@@ -0,0 +1,143 @@
+//! Token approvals, balances and position redemption.
+//!
+//! Trading from an EOA needs USDC approved for, and conditional tokens
+//! operator-approved to, each Polymarket exchange contract. Proxy wallet
+//! accounts are approved by Polymarket when the proxy is created. The bot
+//! only checks approvals; granting them is left to `polybot approve`,
+//! for an amount of USDC the operator chooses. Resolved positions are
+//! redeemed for USDC through the conditional tokens contract.
+
+use anyhow::{Context, Result};
+use ethers::abi::{encode, Token};
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Bytes, TransactionRequest, H256, U256};
+use ethers::utils::id;
+use std::time::Duration;
+use tracing::info;
+
+use super::tx_manager::TxManager;
+use crate::money::Money;
+
+/// USDC.e on Polygon.
+pub const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+/// Gnosis conditional tokens (CTF).
+pub const CTF_ADDRESS: &str = "0x4D97DCd97eC945f40cF65F87097ACe5EA0476045";
+/// Contracts that move funds on our behalf when orders match.
+pub const SPENDERS: [(&str, &str); 3] = [
+    ("CTF Exchange", "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"),
+    ("Neg Risk Exchange", "0xC5d563A36AE78145C45a50134d48A1215220f80a"),
+    ("Neg Risk Adapter", "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296"),
+];
+/// USDC base units per dollar.
+const USDC_UNIT: f64 = 1_000_000.0;
+const CONFIRM_POLL: Duration = Duration::from_secs(5);
+
+/// ABI-encode a call to `signature` with `args`.
+pub fn calldata(signature: &str, args: &[Token]) -> Bytes {
+    [&id(signature)[..], &encode(args)].concat().into()
+}
+
+fn address(value: &str) -> Address {
+    value.parse().expect("valid contract address")
+}
+
+async fn read(tx: &TxManager, to: Address, data: Bytes) -> Result<Bytes> {
+    let call: TypedTransaction = TransactionRequest::new().to(to).data(data).into();
+    tx.provider().call(&call, None).await.context("Contract read failed")
+}
+
+/// USDC held by `owner` on Polygon.
+pub async fn usdc_balance(tx: &TxManager, owner: Address) -> Result<Money> {
+    let balance = read(tx, address(USDC_ADDRESS), calldata("balanceOf(address)", &[Token::Address(owner)])).await?;
+    Ok(Money::usdc(U256::from_big_endian(&balance).low_u128() as f64 / USDC_UNIT))
+}
+
+/// USDC base units for a dollar amount.
+pub fn usdc_units(usd: f64) -> U256 {
+    U256::from((usd.max(0.0) * USDC_UNIT).round() as u128)
+}
+
+/// USDC `owner` lets `spender` move, and whether its conditional tokens
+/// are operator-approved to it.
+async fn allowances(tx: &TxManager, owner: Address, spender: Address) -> Result<(U256, bool)> {
+    let usdc = read(tx, address(USDC_ADDRESS), calldata("allowance(address,address)", &[Token::Address(owner), Token::Address(spender)])).await?;
+    let ctf = read(tx, address(CTF_ADDRESS), calldata("isApprovedForAll(address,address)", &[Token::Address(owner), Token::Address(spender)])).await?;
+    Ok((U256::from_big_endian(&usdc), !U256::from_big_endian(&ctf).is_zero()))
+}
+
+/// Approvals the exchange contracts still need, e.g. "USDC for CTF
+/// Exchange". Read-only.
+pub async fn missing_allowances(tx: &TxManager) -> Result<Vec<String>> {
+    let mut missing = Vec::new();
+    for (name, spender) in SPENDERS {
+        let (usdc, ctf) = allowances(tx, tx.address(), address(spender)).await?;
+        if usdc.is_zero() {
+            missing.push(format!("USDC for {}", name));
+        }
+        if !ctf {
+            missing.push(format!("conditional tokens for {}", name));
+        }
+    }
+    Ok(missing)
+}
+
+/// Approve `usdc_allowance` USDC base units and conditional tokens for
+/// every exchange contract that is not approved for that much yet.
+/// Returns the confirmed approval transactions.
+pub async fn ensure_allowances(tx: &TxManager, usdc_allowance: U256) -> Result<Vec<H256>> {
+    let (usdc, ctf) = (address(USDC_ADDRESS), address(CTF_ADDRESS));
+    let mut sent = Vec::new();
+
+    for (name, spender) in SPENDERS {
+        let spender = address(spender);
+        let (allowance, approved) = allowances(tx, tx.address(), spender).await?;
+
+        if allowance < usdc_allowance {
+            let data = calldata("approve(address,uint256)", &[Token::Address(spender), Token::Uint(usdc_allowance)]);
+            sent.push(tx.send_and_confirm(&format!("USDC approval for {}", name), usdc, data, CONFIRM_POLL).await?);
+        }
+
+        if !approved {
+            let data = calldata("setApprovalForAll(address,bool)", &[Token::Address(spender), Token::Bool(true)]);
+            sent.push(tx.send_and_confirm(&format!("CTF approval for {}", name), ctf, data, CONFIRM_POLL).await?);
+        }
+    }
+
+    if sent.is_empty() {
+        info!("⛓ Exchange allowances already in place");
+    }
+    Ok(sent)
+}
+
+/// Redeem a resolved binary market's positions for USDC.
+pub async fn redeem_positions(tx: &TxManager, condition_id: &str) -> Result<H256> {
+    let condition: H256 = condition_id.parse().context("Invalid condition ID")?;
+    let data = calldata(
+        "redeemPositions(address,bytes32,bytes32,uint256[])",
+        &[
+            Token::Address(address(USDC_ADDRESS)),
+            Token::FixedBytes(H256::zero().as_bytes().to_vec()),
+            Token::FixedBytes(condition.as_bytes().to_vec()),
+            // Index sets for the YES and NO outcome slots
+            Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+        ],
+    );
+    tx.send_and_confirm(&format!("redeem {}", condition_id), address(CTF_ADDRESS), data, CONFIRM_POLL).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calldata_and_usdc_units() {
+        assert_eq!(usdc_units(250.5), U256::from(250_500_000u64));
+        assert_eq!(usdc_units(-1.0), U256::zero());
+
+        let data = calldata("approve(address,uint256)", &[Token::Address(Address::zero()), Token::Uint(U256::MAX)]);
+        // Well-known ERC-20 approve selector
+        assert_eq!(&data[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+        assert_eq!(data.len(), 4 + 64);
+    }
+}
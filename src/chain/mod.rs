@@ -0,0 +1,13 @@
+//! On-chain operations on Polygon.
+//!
+//! Provides a transaction manager (nonces, gas, stuck transactions) sending
+//! through a health-scored pool of RPCs, and the approvals and redemptions
+//! built on it.
+
+pub mod allowance;
+pub mod rpc;
+pub mod tx_manager;
+
+pub use allowance::{ensure_allowances, missing_allowances, redeem_positions, usdc_balance, usdc_units};
+pub use rpc::{RpcHealth, RpcPool};
+pub use tx_manager::{GasPolicy, TxManager, TxOutcome};
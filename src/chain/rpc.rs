@@ -0,0 +1,305 @@
+//! Polygon RPC pool with health scoring.
+//!
+//! Public RPCs such as polygon-rpc.com are frequently throttled, so calls go
+//! to the healthiest of the configured RPCs: the lowest recent latency,
+//! penalised for consecutive errors, skipping RPCs that rate limited us
+//! (until a cooldown passes) or whose head block lags the others. A failed
+//! connection or a rate limit moves the call on to the next RPC. Health is
+//! shared by every pool in the process and refreshed by [`RpcPool::check_health`].
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ethers::providers::{Http, HttpClientError, JsonRpcClient};
+use ethers::types::U64;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::utils::endpoints::{self, POLYGON_RPC};
+use crate::utils::http_pool;
+
+/// Latency assumed for an RPC that hasn't answered yet.
+const UNKNOWN_LATENCY_MS: f64 = 250.0;
+/// Score added per consecutive error.
+const ERROR_PENALTY_MS: f64 = 500.0;
+/// Weight of the newest sample in the latency average.
+const LATENCY_SMOOTHING: f64 = 0.3;
+/// How long a rate-limited RPC is left alone.
+const RATE_LIMIT_COOLDOWN_SECS: i64 = 60;
+/// Blocks behind the best head before an RPC counts as lagging.
+const MAX_BLOCK_LAG: u64 = 5;
+
+static HEALTH: OnceLock<Mutex<HashMap<String, RpcHealth>>> = OnceLock::new();
+
+fn health() -> &'static Mutex<HashMap<String, RpcHealth>> {
+    HEALTH.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Recent behaviour of one RPC.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RpcHealth {
+    /// Smoothed round trip
+    pub latency_ms: Option<f64>,
+    pub requests: u64,
+    pub errors: u64,
+    pub consecutive_errors: u32,
+    pub rate_limited_until: Option<DateTime<Utc>>,
+    /// Head block at the last health check
+    pub block: Option<u64>,
+    pub lagging: bool,
+}
+
+impl RpcHealth {
+    /// Lower is better; `None` while rate limited or lagging.
+    pub fn score(&self, now: DateTime<Utc>) -> Option<f64> {
+        if self.lagging || self.rate_limited_until.is_some_and(|until| until > now) {
+            return None;
+        }
+        Some(self.latency_ms.unwrap_or(UNKNOWN_LATENCY_MS) + self.consecutive_errors as f64 * ERROR_PENALTY_MS)
+    }
+
+    fn succeeded(&mut self, ms: f64) {
+        self.requests += 1;
+        self.consecutive_errors = 0;
+        self.latency_ms = Some(match self.latency_ms {
+            Some(avg) => avg + LATENCY_SMOOTHING * (ms - avg),
+            None => ms,
+        });
+    }
+
+    fn failed(&mut self, rate_limited: bool, now: DateTime<Utc>) {
+        self.requests += 1;
+        self.errors += 1;
+        self.consecutive_errors += 1;
+        if rate_limited {
+            self.rate_limited_until = Some(now + Duration::seconds(RATE_LIMIT_COOLDOWN_SECS));
+        }
+    }
+}
+
+/// How a call went, for scoring and retries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Ok,
+    /// The RPC never got the request; safe to retry elsewhere
+    Unreachable,
+    RateLimited,
+    /// Timed out or failed after it may have been processed
+    Failed,
+    /// The RPC answered with an error about the call itself
+    Rejected,
+}
+
+fn classify<R>(result: &Result<R, HttpClientError>) -> Outcome {
+    let text = match result {
+        Ok(_) => return Outcome::Ok,
+        Err(HttpClientError::ReqwestError(e)) if e.is_connect() => return Outcome::Unreachable,
+        Err(HttpClientError::ReqwestError(e)) if e.status().is_some_and(|s| s.as_u16() == 429) => {
+            return Outcome::RateLimited
+        }
+        Err(HttpClientError::ReqwestError(_)) => return Outcome::Failed,
+        Err(HttpClientError::JsonRpcError(e)) if e.code == -32005 => return Outcome::RateLimited,
+        Err(HttpClientError::JsonRpcError(e)) => e.message.to_lowercase(),
+        // Throttled RPCs often answer with a plain-text or HTML error page
+        Err(HttpClientError::SerdeJson { text, .. }) => text.to_lowercase(),
+    };
+    if ["rate limit", "too many requests", "limit exceeded", "429"].iter().any(|s| text.contains(s)) {
+        Outcome::RateLimited
+    } else if matches!(result, Err(HttpClientError::SerdeJson { .. })) {
+        Outcome::Failed
+    } else {
+        Outcome::Rejected
+    }
+}
+
+/// Host of an RPC URL, for logs (paths often hold API keys).
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?']).next().unwrap_or(rest)
+}
+
+/// Transports to every configured RPC, used healthiest first.
+#[derive(Debug)]
+pub struct RpcPool {
+    nodes: Vec<(String, Http)>,
+}
+
+impl RpcPool {
+    /// Pool over `urls`, primary first. Fails on a malformed URL.
+    pub fn new(urls: &[String]) -> anyhow::Result<Self> {
+        let mut nodes = Vec::new();
+        for url in urls {
+            let url = url.trim_end_matches('/').to_string();
+            let parsed = reqwest::Url::parse(&url).map_err(|e| anyhow::anyhow!("Invalid RPC URL: {}", e))?;
+            nodes.push((url, Http::new_with_client(parsed, http_pool::client())));
+        }
+        if nodes.is_empty() {
+            anyhow::bail!("No RPC URL configured");
+        }
+        Ok(Self { nodes })
+    }
+
+    /// POLYGON_RPC_URL followed by POLYGON_RPC_FALLBACKS.
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let mut urls = vec![config.polygon_rpc_url.clone()];
+        urls.extend(config.polygon_rpc_fallbacks.iter().cloned());
+        Self::new(&urls)
+    }
+
+    /// Node indices, best first. Unavailable RPCs come last (in list
+    /// order) so a call still goes somewhere when all are throttled.
+    fn ranked(&self) -> Vec<usize> {
+        let now = Utc::now();
+        let health = health().lock().unwrap();
+        let score = |i: usize| health.get(&self.nodes[i].0).map_or(Some(UNKNOWN_LATENCY_MS), |h| h.score(now));
+        let mut order: Vec<(usize, Option<f64>)> = (0..self.nodes.len()).map(|i| (i, score(i))).collect();
+        order.sort_by(|(ia, a), (ib, b)| match (a, b) {
+            (Some(a), Some(b)) => a.total_cmp(b).then(ia.cmp(ib)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => ia.cmp(ib),
+        });
+        order.into_iter().map(|(i, _)| i).collect()
+    }
+
+    fn record(&self, index: usize, outcome: Outcome, ms: f64) {
+        let url = &self.nodes[index].0;
+        let mut health = health().lock().unwrap();
+        let entry = health.entry(url.clone()).or_default();
+        match outcome {
+            // An answer, even an error about the call, means the RPC is up
+            Outcome::Ok | Outcome::Rejected => entry.succeeded(ms),
+            Outcome::RateLimited => {
+                warn!("🐢 RPC {} is rate limiting; backing off {}s", host(url), RATE_LIMIT_COOLDOWN_SECS);
+                entry.failed(true, Utc::now());
+            }
+            Outcome::Unreachable | Outcome::Failed => entry.failed(false, Utc::now()),
+        }
+    }
+
+    /// Point the System panel at the preferred RPC.
+    fn publish_preferred(&self) {
+        if let Some(&best) = self.ranked().first() {
+            let url = &self.nodes[best].0;
+            if endpoints::global().set_active(POLYGON_RPC, url) {
+                info!("🔀 Preferring RPC {}", host(url));
+            }
+        }
+    }
+
+    /// Time `eth_blockNumber` on every RPC and flag those lagging the best
+    /// head block.
+    pub async fn check_health(&self) {
+        let mut blocks = Vec::new();
+        for (index, (url, http)) in self.nodes.iter().enumerate() {
+            let started = Instant::now();
+            let result: Result<U64, _> = http.request("eth_blockNumber", ()).await;
+            let ms = started.elapsed().as_secs_f64() * 1000.0;
+            let outcome = classify(&result);
+            self.record(index, outcome, ms);
+            match result {
+                Ok(block) => blocks.push((url.clone(), block.as_u64())),
+                Err(e) => debug!("RPC health check failed for {}: {}", host(url), e),
+            }
+        }
+
+        let best = blocks.iter().map(|(_, b)| *b).max().unwrap_or(0);
+        let mut health = health().lock().unwrap();
+        for (url, block) in blocks {
+            let entry = health.entry(url.clone()).or_default();
+            let lagging = best.saturating_sub(block) > MAX_BLOCK_LAG;
+            if lagging && !entry.lagging {
+                warn!("⏳ RPC {} is {} blocks behind", host(&url), best - block);
+            }
+            entry.block = Some(block);
+            entry.lagging = lagging;
+        }
+        drop(health);
+        self.publish_preferred();
+    }
+
+    /// Health of every RPC in the pool, by host.
+    pub fn health(&self) -> Vec<(String, RpcHealth)> {
+        let health = health().lock().unwrap();
+        self.nodes
+            .iter()
+            .map(|(url, _)| (host(url).to_string(), health.get(url).cloned().unwrap_or_default()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for RpcPool {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, Self::Error>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        let params = serde_json::to_value(params)
+            .map_err(|err| HttpClientError::SerdeJson { err, text: String::new() })?;
+        let order = self.ranked();
+        let mut last = None;
+        for index in order {
+            let (url, http) = &self.nodes[index];
+            let started = Instant::now();
+            let result: Result<R, _> = http.request::<Value, R>(method, params.clone()).await;
+            let outcome = classify(&result);
+            self.record(index, outcome, started.elapsed().as_secs_f64() * 1000.0);
+            if !matches!(outcome, Outcome::Unreachable | Outcome::RateLimited) {
+                if outcome == Outcome::Failed {
+                    self.publish_preferred();
+                }
+                return result;
+            }
+            debug!("RPC {} unavailable for {}, trying the next one", host(url), method);
+            last = Some(result);
+        }
+        self.publish_preferred();
+        last.expect("pool has at least one RPC")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefers_fast_healthy_rpcs() {
+        let urls: Vec<String> = ["a", "b", "c"]
+            .iter()
+            .map(|n| format!("https://{}-{}.rpc.test/key", n, uuid::Uuid::new_v4()))
+            .collect();
+        let pool = RpcPool::new(&urls).unwrap();
+
+        // Unknown RPCs keep list order; a fast one moves ahead
+        assert_eq!(pool.ranked(), vec![0, 1, 2]);
+        pool.record(2, Outcome::Ok, 40.0);
+        pool.record(1, Outcome::Ok, 400.0);
+        assert_eq!(pool.ranked(), vec![2, 0, 1]);
+
+        // Errors are penalised; rate limits sideline the RPC entirely
+        pool.record(2, Outcome::Failed, 0.0);
+        assert_eq!(pool.ranked(), vec![0, 1, 2]);
+        pool.record(0, Outcome::RateLimited, 0.0);
+        assert_eq!(pool.ranked(), vec![1, 2, 0]);
+        let health = pool.health();
+        assert!(health[0].1.rate_limited_until.is_some());
+        assert_eq!(health[2].1.latency_ms, Some(40.0));
+        assert!(!health[0].0.contains("key"));
+
+        let mut lagging = RpcHealth { lagging: true, ..Default::default() };
+        assert_eq!(lagging.score(Utc::now()), None);
+        lagging.lagging = false;
+        lagging.succeeded(100.0);
+        lagging.succeeded(200.0);
+        assert!((lagging.score(Utc::now()).unwrap() - 130.0).abs() < 1e-9);
+    }
+}
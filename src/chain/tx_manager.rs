@@ -0,0 +1,332 @@
+//! Transaction manager for on-chain operations.
+//!
+//! Tracks the account nonce locally so concurrent sends never collide,
+//! prices transactions with EIP-1559 fees capped by config, watches pending
+//! transactions for receipts and re-broadcasts stuck ones at the same nonce
+//! with bumped fees.
+
+use anyhow::{Context, Result};
+use ethers::providers::{Middleware, Provider};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, BlockNumber, Bytes, Eip1559TransactionRequest, H256, U256};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use super::rpc::RpcPool;
+use crate::config::Config;
+
+/// Polygon mainnet.
+const CHAIN_ID: u64 = 137;
+/// Nodes reject replacements that raise fees by less than 10%.
+const BUMP_PERCENT: u64 = 15;
+/// Headroom over the node's gas estimate.
+const GAS_BUFFER_PERCENT: u64 = 20;
+const GWEI: f64 = 1e9;
+
+/// EIP-1559 fee limits.
+#[derive(Debug, Clone, Copy)]
+pub struct GasPolicy {
+    /// Highest max fee per gas ever offered (wei)
+    pub max_fee_cap: U256,
+    /// Highest priority fee ever offered (wei)
+    pub max_priority_cap: U256,
+    /// Pending this long without a receipt counts as stuck
+    pub stuck_after: Duration,
+}
+
+impl GasPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_fee_cap: gwei(config.gas_max_fee_gwei),
+            max_priority_cap: gwei(config.gas_max_priority_gwei),
+            stuck_after: Duration::from_secs(config.tx_stuck_secs),
+        }
+    }
+
+    /// Clamp estimated fees to the caps, keeping priority <= max fee.
+    pub fn cap(&self, (max_fee, priority): (U256, U256)) -> (U256, U256) {
+        let max_fee = max_fee.min(self.max_fee_cap);
+        (max_fee, priority.min(self.max_priority_cap).min(max_fee))
+    }
+
+    /// Fees for replacing a stuck transaction: at least `BUMP_PERCENT` over
+    /// what it paid and no less than the current estimate. `None` once the
+    /// caps leave no room for a valid replacement.
+    pub fn replacement(&self, paid: (U256, U256), estimate: (U256, U256)) -> Option<(U256, U256)> {
+        let bump = |v: U256| v + v * BUMP_PERCENT / 100;
+        let wanted = (bump(paid.0).max(estimate.0), bump(paid.1).max(estimate.1));
+        let capped = self.cap(wanted);
+        // Both fees must rise by the minimum bump to be accepted
+        (capped.0 >= bump(paid.0) && capped.1 >= bump(paid.1)).then_some(capped)
+    }
+}
+
+fn gwei(value: f64) -> U256 {
+    U256::from((value * GWEI) as u128)
+}
+
+/// Outcome of a transaction the manager was watching.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxOutcome {
+    Confirmed { label: String, hash: H256 },
+    Reverted { label: String, hash: H256 },
+}
+
+/// Where a pending transaction stands after a check.
+enum Check {
+    Mined(TxOutcome),
+    /// No receipt yet
+    Waiting(PendingTx),
+    /// The RPC failed; keep waiting on it
+    Failed(PendingTx, anyhow::Error),
+}
+
+/// A sent transaction awaiting a receipt.
+#[derive(Debug, Clone)]
+struct PendingTx {
+    label: String,
+    request: Eip1559TransactionRequest,
+    /// Every hash broadcast at this nonce; any one of them may be mined
+    hashes: Vec<H256>,
+    fees: (U256, U256),
+    sent_at: Instant,
+}
+
+/// Sends and tracks transactions from one account.
+pub struct TxManager {
+    provider: Provider<RpcPool>,
+    wallet: LocalWallet,
+    policy: GasPolicy,
+    /// Next nonce to use; fetched from the chain on first send
+    nonce: Mutex<Option<U256>>,
+    pending: Mutex<Vec<PendingTx>>,
+}
+
+impl TxManager {
+    /// Manager sending through the healthiest of `rpc_urls`.
+    pub fn new(rpc_urls: &[String], private_key: &str, policy: GasPolicy) -> Result<Self> {
+        let provider = Provider::new(RpcPool::new(rpc_urls).context("Invalid POLYGON_RPC_URL")?);
+        let wallet = private_key
+            .parse::<LocalWallet>()
+            .context("Failed to parse private key")?
+            .with_chain_id(CHAIN_ID);
+        Ok(Self {
+            provider,
+            wallet,
+            policy,
+            nonce: Mutex::new(None),
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Manager for the Polymarket wallet, or `None` without a private key.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        if config.polymarket_private_key.is_empty() {
+            return Ok(None);
+        }
+        let mut rpc_urls = vec![config.polygon_rpc_url.clone()];
+        rpc_urls.extend(config.polygon_rpc_fallbacks.iter().cloned());
+        Self::new(&rpc_urls, &config.polymarket_private_key, GasPolicy::from_config(config)).map(Some)
+    }
+
+    pub fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    pub fn provider(&self) -> &Provider<RpcPool> {
+        &self.provider
+    }
+
+    /// Number of transactions still awaiting a receipt.
+    pub async fn pending_count(&self) -> usize {
+        self.pending.lock().await.len()
+    }
+
+    /// Current fee estimate, capped by policy.
+    async fn fees(&self) -> Result<(U256, U256)> {
+        let estimate = self.provider
+            .estimate_eip1559_fees(None)
+            .await
+            .context("Failed to estimate gas fees")?;
+        Ok(self.policy.cap(estimate))
+    }
+
+    /// Reserve the next nonce.
+    async fn next_nonce(&self) -> Result<U256> {
+        let mut nonce = self.nonce.lock().await;
+        let next = match *nonce {
+            Some(n) => n,
+            None => self.provider
+                .get_transaction_count(self.address(), Some(BlockNumber::Pending.into()))
+                .await
+                .context("Failed to fetch nonce")?,
+        };
+        *nonce = Some(next + 1);
+        Ok(next)
+    }
+
+    /// Forget the local nonce so the next send re-reads it from the chain.
+    async fn resync_nonce(&self) {
+        *self.nonce.lock().await = None;
+    }
+
+    async fn broadcast(&self, request: &Eip1559TransactionRequest) -> Result<H256> {
+        let tx: TypedTransaction = request.clone().into();
+        let signature = self.wallet.sign_transaction(&tx).await.context("Failed to sign transaction")?;
+        let pending = self.provider
+            .send_raw_transaction(tx.rlp_signed(&signature))
+            .await
+            .context("Failed to send transaction")?;
+        Ok(pending.tx_hash())
+    }
+
+    /// Send a contract call, returning its hash once broadcast.
+    pub async fn send(&self, label: &str, to: Address, data: Bytes) -> Result<H256> {
+        let mut request = Eip1559TransactionRequest::new()
+            .from(self.address())
+            .to(to)
+            .data(data)
+            .chain_id(CHAIN_ID);
+        let gas = self.provider
+            .estimate_gas(&request.clone().into(), None)
+            .await
+            .with_context(|| format!("Gas estimate failed for {}", label))?;
+        let fees = self.fees().await?;
+        request = request
+            .gas(gas + gas * GAS_BUFFER_PERCENT / 100)
+            .max_fee_per_gas(fees.0)
+            .max_priority_fee_per_gas(fees.1)
+            .nonce(self.next_nonce().await?);
+
+        let hash = match self.broadcast(&request).await {
+            Ok(hash) => hash,
+            Err(e) => {
+                // Another process may have used the nonce; start over from the chain
+                self.resync_nonce().await;
+                return Err(e);
+            }
+        };
+        info!("⛓ Sent {} ({:?}), nonce {}", label, hash, request.nonce.unwrap_or_default());
+        self.pending.lock().await.push(PendingTx {
+            label: label.to_string(),
+            request,
+            hashes: vec![hash],
+            fees,
+            sent_at: Instant::now(),
+        });
+        Ok(hash)
+    }
+
+    /// Check pending transactions: report mined ones and replace any that
+    /// have been stuck longer than the policy allows. A transaction whose
+    /// check fails stays pending; the first error is returned only when
+    /// nothing else was learned.
+    pub async fn poll(&self) -> Result<Vec<TxOutcome>> {
+        let mut pending = self.pending.lock().await;
+        let mut outcomes = Vec::new();
+        let mut still_pending = Vec::new();
+        let mut error = None;
+
+        for tx in pending.clone() {
+            match self.check(tx).await {
+                Check::Mined(outcome) => outcomes.push(outcome),
+                Check::Waiting(tx) => still_pending.push(tx),
+                Check::Failed(tx, e) => {
+                    warn!("⛓ Checking {} failed: {}", tx.label, e);
+                    error.get_or_insert(e);
+                    still_pending.push(tx);
+                }
+            }
+        }
+
+        *pending = still_pending;
+        match error {
+            Some(e) if outcomes.is_empty() => Err(e),
+            _ => Ok(outcomes),
+        }
+    }
+
+    /// Look for a receipt for `tx`, replacing it if stuck.
+    async fn check(&self, mut tx: PendingTx) -> Check {
+        for hash in &tx.hashes {
+            match self.provider.get_transaction_receipt(*hash).await {
+                Ok(Some(r)) => {
+                    let (label, hash) = (tx.label.clone(), r.transaction_hash);
+                    return Check::Mined(if r.status == Some(1u64.into()) {
+                        TxOutcome::Confirmed { label, hash }
+                    } else {
+                        TxOutcome::Reverted { label, hash }
+                    });
+                }
+                Ok(None) => {}
+                Err(e) => return Check::Failed(tx, e.into()),
+            }
+        }
+
+        if tx.sent_at.elapsed() >= self.policy.stuck_after {
+            let market = match self.fees().await {
+                Ok(fees) => fees,
+                Err(e) => return Check::Failed(tx, e),
+            };
+            match self.policy.replacement(tx.fees, market) {
+                Some(fees) => {
+                    tx.request = tx.request.max_fee_per_gas(fees.0).max_priority_fee_per_gas(fees.1);
+                    match self.broadcast(&tx.request).await {
+                        Ok(hash) => {
+                            warn!("⛓ Replaced stuck {} with {:?} at {} gwei", tx.label, hash, fees.0.as_u128() as f64 / GWEI);
+                            tx.hashes.push(hash);
+                            tx.fees = fees;
+                        }
+                        Err(e) => warn!("⛓ Replacing stuck {} failed: {}", tx.label, e),
+                    }
+                }
+                None => warn!("⛓ {} is stuck but fees are at the configured cap", tx.label),
+            }
+            tx.sent_at = Instant::now();
+        }
+        Check::Waiting(tx)
+    }
+
+    /// Send a call and wait for it to be mined, replacing it if stuck.
+    pub async fn send_and_confirm(&self, label: &str, to: Address, data: Bytes, poll_every: Duration) -> Result<H256> {
+        let hash = self.send(label, to, data).await?;
+        loop {
+            tokio::time::sleep(poll_every).await;
+            for outcome in self.poll().await? {
+                match outcome {
+                    TxOutcome::Confirmed { label: l, hash: h } if l == label => return Ok(h),
+                    TxOutcome::Reverted { label: l, hash: h } if l == label => {
+                        anyhow::bail!("{} reverted ({:?}, first sent as {:?})", label, h, hash)
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fees_are_capped_and_replacements_bumped() {
+        let policy = GasPolicy {
+            max_fee_cap: gwei(200.0),
+            max_priority_cap: gwei(50.0),
+            stuck_after: Duration::from_secs(60),
+        };
+        assert_eq!(policy.cap((gwei(300.0), gwei(80.0))), (gwei(200.0), gwei(50.0)));
+        assert_eq!(policy.cap((gwei(20.0), gwei(30.0))), (gwei(20.0), gwei(20.0)));
+
+        // Bumped 15% over what was paid when the market has not moved
+        let paid = (gwei(100.0), gwei(30.0));
+        assert_eq!(policy.replacement(paid, (gwei(90.0), gwei(25.0))), Some((gwei(115.0), gwei(34.5))));
+        // Follows the market when it moved further
+        assert_eq!(policy.replacement(paid, (gwei(150.0), gwei(40.0))), Some((gwei(150.0), gwei(40.0))));
+        // No valid replacement once the caps bind
+        assert_eq!(policy.replacement((gwei(190.0), gwei(30.0)), (gwei(100.0), gwei(30.0))), None);
+    }
+}
@@ -4,8 +4,22 @@
 
 use anyhow::{Context, Result};
 use dotenvy::dotenv;
+use sha2::{Digest, Sha256};
+use chrono_tz::Tz;
+use std::collections::HashMap;
 use std::env;
 
+use crate::display::{PriceFormat, Rounding};
+use crate::money::Currency;
+use crate::notify::email::SmtpTls;
+use crate::polymarket::signer::SignatureType;
+use crate::risk::allocator::RebalanceRule;
+use crate::risk::inventory::SkewFunction;
+use crate::tui::i18n::Locale;
+use crate::utils::open_orders::SelfTradePolicy;
+use crate::utils::permissions::{self, Role};
+use crate::utils::timezone;
+
 /// Bot configuration loaded from environment variables.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -14,19 +28,249 @@ pub struct Config {
     pub polymarket_secret: String,
     pub polymarket_passphrase: String,
     pub polymarket_private_key: String,
+    /// How orders are signed: EOA, POLY_PROXY (email/Magic) or POLY_GNOSIS_SAFE
+    pub polymarket_signature_type: SignatureType,
+    /// Proxy wallet holding the funds (required for proxy signature types)
+    pub polymarket_funder: String,
     pub polygon_rpc_url: String,
+    /// Highest EIP-1559 max fee offered for on-chain transactions
+    pub gas_max_fee_gwei: f64,
+    /// Highest priority fee offered
+    pub gas_max_priority_gwei: f64,
+    /// Seconds without a receipt before a transaction is re-sent with higher fees
+    pub tx_stuck_secs: u64,
+    /// Balance each venue needs for arbitrage; alert below it (0 = off)
+    pub min_venue_float: f64,
+    /// How often balances are refreshed
+    pub balance_poll_secs: u64,
+    /// How often matched pairs are checked for resolution
+    pub resolution_poll_secs: u64,
+    /// Minutes before a position's market closes to warn about it (0 = off)
+    pub resolution_warning_minutes: u64,
+    /// How long a snooze from the TUI holds back a market's alerts
+    pub snooze_hours: u64,
 
     // Kalshi settings
     pub kalshi_email: Option<String>,
     pub kalshi_password: Option<String>,
     pub kalshi_api_key: Option<String>,
     pub kalshi_api_secret: Option<String>,
+    /// Seconds between polls of our Kalshi fills
+    pub kalshi_fill_poll_secs: u64,
+    /// Second Kalshi account whose fills are mirrored (unset = off)
+    pub kalshi_follow_api_key: Option<String>,
+    pub kalshi_follow_api_secret: Option<String>,
+    /// Our contracts per contract the followed account trades
+    pub kalshi_follow_scale: f64,
+    /// Cap on contracts per mirrored fill
+    pub kalshi_follow_max_contracts: i32,
+
+    // Copy trading
+    /// Polymarket traders whose trades are copied (empty = off)
+    pub copy_traders: Vec<String>,
+    pub copy_poll_secs: u64,
 
     // Bot settings
     pub min_profit_threshold: f64,
     pub max_position_size: f64,
     pub dry_run: bool,
     pub log_level: String,
+    /// Append every quote the detector checks to the backtest tape
+    pub record_quotes: bool,
+    /// Consecutive sane quotes a market needs before it can signal
+    pub warmup_quotes: u32,
+    /// Seconds a Polymarket book may go unchanged before its quotes are
+    /// rejected as stale (0 = never)
+    pub stale_book_secs: u64,
+    /// Share of the detected net edge an arb must keep when re-checked
+    /// right before execution (0 = no re-check)
+    pub revalidate_edge_fraction: f64,
+    /// Seconds a market sits out detection and execution after we trade it
+    pub market_cooldown_secs: u64,
+    /// Best bid or ask change between passes that counts as the market
+    /// moving, e.g. 0.05 (0 = off)
+    pub move_jump: f64,
+    /// Share of the size near the top pulled between passes that counts as
+    /// the market moving, e.g. 0.5 (0 = off)
+    pub move_liquidity_pull: f64,
+    /// Kalshi move over recent candles, and Polymarket gap behind it, that
+    /// flag Polymarket as lagging, e.g. 0.03 (0 = off)
+    pub lag_threshold: f64,
+    /// Fee rate overrides, e.g. "kalshi:KXBTCD=0.035/0;polymarket=200/0"
+    pub fee_overrides: String,
+    /// "auto" executes immediately; "semi-auto" queues order intents for approval
+    pub execution_mode: String,
+    /// Seconds an unapproved intent stays valid
+    pub intent_ttl_secs: i64,
+    /// Orders per minute by venue, e.g. [("polymarket", 60)]
+    pub order_rate_limits: Vec<(String, u32)>,
+    /// Cancels per minute by venue
+    pub cancel_rate_limits: Vec<(String, u32)>,
+    /// Orders per second on any one venue; 0 disables the burst cap
+    pub order_burst_limit: u32,
+    /// What to do when an order would cross our own resting order
+    pub self_trade_policy: SelfTradePolicy,
+    /// Currency the TUI and reports show amounts in
+    pub display_currency: Currency,
+    /// How prices are shown: probability, cents or percent
+    pub price_format: PriceFormat,
+    /// Decimals for prices; unset uses the format's default
+    pub price_decimals: Option<usize>,
+    /// Decimals for edges, returns and other percentages
+    pub percent_decimals: usize,
+    pub price_rounding: Rounding,
+    /// Language of the TUI's labels and messages: en or es
+    pub tui_locale: Locale,
+    /// What the TUI may do: viewer, trader or admin
+    pub tui_role: Role,
+    /// Asked for before the TUI's dangerous actions (unset = not asked)
+    pub tui_pin: Option<String>,
+    /// Clock for unlabelled resolution times, the daily rollover, reports
+    /// and displayed timestamps
+    pub timezone: Tz,
+    /// Where the web dashboard listens, e.g. 0.0.0.0:8080 (unset = off)
+    pub dashboard_addr: Option<String>,
+    /// Tokens accepted by the dashboard, each with its role (empty = no
+    /// token asked, view only)
+    pub dashboard_tokens: Vec<(String, Role)>,
+
+    // Detection universe
+    /// Kalshi series tickers to enumerate (e.g. KXBTCD, KXETHD)
+    pub kalshi_series: Vec<String>,
+    /// Seconds between universe refreshes
+    pub universe_refresh_secs: u64,
+    /// Seconds between leaderboard refreshes (0 = off)
+    pub leaderboard_refresh_secs: u64,
+    /// Minimum seconds between leaderboard history snapshots (0 = none kept)
+    pub leaderboard_snapshot_secs: u64,
+    /// Fraction refresh intervals are randomly stretched or shrunk by
+    pub refresh_jitter: f64,
+
+    // Paper trading
+    /// Named paper portfolio the TUI and reports use
+    pub paper_profile: String,
+    /// Starting cash of newly created or reset paper portfolios
+    pub paper_balance: f64,
+
+    // Capital allocation
+    /// Strategy weights, e.g. "arbitrage:0.5,copy_trade:0.3,directional:0.2"
+    pub capital_allocation: Vec<(String, f64)>,
+    /// Venue weights, e.g. "polymarket:0.6,kalshi:0.4" (empty = unconstrained)
+    pub venue_allocation: Vec<(String, f64)>,
+    pub rebalance_rule: RebalanceRule,
+    /// Hours between rebalances under the sharpe rule
+    pub rebalance_interval_hours: i64,
+    /// Policy per strategy for orders opposing another strategy's position,
+    /// e.g. ("copy_trade", "skip"); "*" sets the default (allow)
+    pub conflict_policies: Vec<(String, String)>,
+    /// Max loss on any one question, netted across strategies and venues,
+    /// if it resolves against the book (0 = unlimited)
+    pub max_question_loss: f64,
+    /// Largest net contracts held on one question, across venues, before
+    /// orders adding to it are refused (0 = no cap)
+    pub inventory_cap: f64,
+    /// Quote shift at the inventory cap, in price
+    pub max_inventory_skew: f64,
+    /// How the quote shift grows with inventory
+    pub skew_function: SkewFunction,
+    /// Trades each strategy may place per day, e.g. ("copy_trade", 50)
+    /// (unlisted = unlimited)
+    pub max_trades_per_day: Vec<(String, u32)>,
+    /// Orders placed per day across all strategies (unset = unlimited)
+    pub max_orders_per_day: Option<u32>,
+    /// Queued orders in flight at once on each venue
+    pub execution_workers: usize,
+    /// Drawdown from the equity high-water mark that cuts position sizes,
+    /// e.g. 0.10 (unset = never)
+    pub derisk_drawdown: Option<f64>,
+    /// Drawdown at or below which full sizes return (default: half the trigger)
+    pub derisk_recovery: Option<f64>,
+    /// Multiplier on max position sizes while de-risked
+    pub derisk_factor: f64,
+
+    /// Cron-like schedule per strategy, e.g. ("arbitrage", "* 9-20 * * *");
+    /// unlisted strategies run 24/7
+    pub strategy_schedules: Vec<(String, String)>,
+    /// Time zone schedules are evaluated in (default: `timezone`)
+    pub schedule_timezone: String,
+    /// Strategies whose orders are filled virtually (shadow mode) while the
+    /// rest of the bot trades live
+    pub shadow_strategies: Vec<String>,
+    /// Per-strategy DRY_RUN overrides, e.g. [("arbitrage", false)]
+    pub strategy_dry_run: Vec<(String, bool)>,
+
+    /// Seconds between mark-to-market passes over open positions
+    pub mark_interval_secs: u64,
+
+    // Alerts
+    pub telegram_bot_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    /// Chats allowed to send operator commands (empty = TELEGRAM_CHAT_ID)
+    pub telegram_command_chats: Vec<String>,
+    /// Role of each Telegram user ID; unlisted users may only look
+    pub telegram_command_users: Vec<(String, Role)>,
+    pub discord_webhook_url: Option<String>,
+    /// Receives opportunity and fill events as JSON
+    pub webhook_url: Option<String>,
+    /// Signs webhook requests with HMAC-SHA256 when set
+    pub webhook_secret: Option<String>,
+    /// SMTP server for email alerts (unset = no email)
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    /// "starttls", "tls" or "none"
+    pub smtp_tls: SmtpTls,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub email_from: String,
+    pub email_to: Vec<String>,
+    pub email_subject_template: String,
+    pub email_body_template: String,
+    /// Alert kinds that are emailed ("*" = all)
+    pub email_alert_kinds: Vec<String>,
+    /// Alert routing rules, e.g. "breaker=telegram+email;opportunity=discord"
+    pub notify_routes: String,
+    /// Repeats of an alert within this many seconds are held back
+    pub notify_dedup_secs: u64,
+    /// Messages per channel per minute (0 = unlimited)
+    pub notify_max_per_minute: usize,
+    /// Hour (in `timezone`) the daily report is sent; unset = never
+    pub daily_report_hour: Option<u32>,
+    /// JSON file listing watched markets and their alert rules
+    pub watchlist_path: String,
+    /// Seconds between watchlist evaluations
+    pub watchlist_poll_secs: u64,
+
+    // Raw payload logging
+    /// Write redacted request/response bodies under `raw_log_dir`
+    pub raw_log: bool,
+    pub raw_log_dir: String,
+    /// Rotate after this many megabytes
+    pub raw_log_max_mb: u64,
+    /// Rotated files to keep
+    pub raw_log_files: usize,
+
+    // HTTP connection pool
+    /// Idle connections kept open per host
+    pub http_pool_size: usize,
+    pub http_idle_timeout_secs: u64,
+    /// TCP keep-alive and HTTP/2 ping interval
+    pub http_keep_alive_secs: u64,
+
+    // Endpoint failover
+    /// Alternative Kalshi API base URLs, tried in order
+    pub kalshi_api_fallbacks: Vec<String>,
+    /// Alternative Polymarket CLOB base URLs
+    pub polymarket_clob_fallbacks: Vec<String>,
+    /// Backup Polygon RPCs
+    pub polygon_rpc_fallbacks: Vec<String>,
+    /// Consecutive connection errors before switching to the next endpoint
+    pub failover_after_errors: u32,
+
+    // Fixture settings
+    /// "off", "record" or "replay"
+    pub fixture_mode: String,
+    /// Directory holding recorded responses
+    pub fixture_dir: String,
 }
 
 impl Config {
@@ -35,40 +279,408 @@ impl Config {
         // Load .env file if present
         let _ = dotenv();
 
+        Self::from_map(&env::vars().collect())
+    }
+
+    /// Load configuration from `vars`, read like environment variables.
+    pub fn from_map(vars: &HashMap<String, String>) -> Result<Self> {
+        let var = |key: &str| vars.get(key).cloned().ok_or(env::VarError::NotPresent);
+
         Ok(Self {
             // Polymarket
-            polymarket_api_key: env::var("POLYMARKET_API_KEY")
+            polymarket_api_key: var("POLYMARKET_API_KEY")
+                .unwrap_or_default(),
+            polymarket_secret: var("POLYMARKET_SECRET")
                 .unwrap_or_default(),
-            polymarket_secret: env::var("POLYMARKET_SECRET")
+            polymarket_passphrase: var("POLYMARKET_PASSPHRASE")
                 .unwrap_or_default(),
-            polymarket_passphrase: env::var("POLYMARKET_PASSPHRASE")
+            polymarket_private_key: var("POLYMARKET_PRIVATE_KEY")
                 .unwrap_or_default(),
-            polymarket_private_key: env::var("POLYMARKET_PRIVATE_KEY")
+            polymarket_signature_type: var("POLYMARKET_SIGNATURE_TYPE")
+                .unwrap_or_else(|_| "EOA".to_string())
+                .parse()
+                .context("Invalid POLYMARKET_SIGNATURE_TYPE")?,
+            polymarket_funder: var("POLYMARKET_FUNDER")
                 .unwrap_or_default(),
-            polygon_rpc_url: env::var("POLYGON_RPC_URL")
+            polygon_rpc_url: var("POLYGON_RPC_URL")
                 .unwrap_or_else(|_| "https://polygon-rpc.com".to_string()),
+            gas_max_fee_gwei: var("GAS_MAX_FEE_GWEI")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .context("Invalid GAS_MAX_FEE_GWEI")?,
+            gas_max_priority_gwei: var("GAS_MAX_PRIORITY_GWEI")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .context("Invalid GAS_MAX_PRIORITY_GWEI")?,
+            tx_stuck_secs: var("TX_STUCK_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid TX_STUCK_SECS")?,
+            min_venue_float: var("MIN_VENUE_FLOAT")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid MIN_VENUE_FLOAT")?,
+            balance_poll_secs: var("BALANCE_POLL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid BALANCE_POLL_SECS")?,
+            resolution_poll_secs: var("RESOLUTION_POLL_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid RESOLUTION_POLL_SECS")?,
+            resolution_warning_minutes: var("RESOLUTION_WARNING_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid RESOLUTION_WARNING_MINUTES")?,
+            snooze_hours: var("SNOOZE_HOURS")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .context("Invalid SNOOZE_HOURS")?,
 
             // Kalshi
-            kalshi_email: env::var("KALSHI_EMAIL").ok(),
-            kalshi_password: env::var("KALSHI_PASSWORD").ok(),
-            kalshi_api_key: env::var("KALSHI_API_KEY").ok(),
-            kalshi_api_secret: env::var("KALSHI_API_SECRET").ok(),
+            kalshi_email: var("KALSHI_EMAIL").ok(),
+            kalshi_password: var("KALSHI_PASSWORD").ok(),
+            kalshi_api_key: var("KALSHI_API_KEY").ok(),
+            kalshi_api_secret: var("KALSHI_API_SECRET").ok(),
+            kalshi_fill_poll_secs: var("KALSHI_FILL_POLL_SECS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .context("Invalid KALSHI_FILL_POLL_SECS")?,
+            kalshi_follow_api_key: var("KALSHI_FOLLOW_API_KEY").ok().filter(|v| !v.is_empty()),
+            kalshi_follow_api_secret: var("KALSHI_FOLLOW_API_SECRET").ok().filter(|v| !v.is_empty()),
+            kalshi_follow_scale: var("KALSHI_FOLLOW_SCALE")
+                .unwrap_or_else(|_| "1.0".to_string())
+                .parse()
+                .context("Invalid KALSHI_FOLLOW_SCALE")?,
+            kalshi_follow_max_contracts: var("KALSHI_FOLLOW_MAX_CONTRACTS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .context("Invalid KALSHI_FOLLOW_MAX_CONTRACTS")?,
+
+            // Copy trading
+            copy_traders: parse_list(&var("COPY_TRADERS").unwrap_or_default()),
+            copy_poll_secs: var("COPY_POLL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid COPY_POLL_SECS")?,
 
             // Bot settings
-            min_profit_threshold: env::var("MIN_PROFIT_THRESHOLD")
+            min_profit_threshold: var("MIN_PROFIT_THRESHOLD")
                 .unwrap_or_else(|_| "0.02".to_string())
                 .parse()
                 .context("Invalid MIN_PROFIT_THRESHOLD")?,
-            max_position_size: env::var("MAX_POSITION_SIZE")
+            max_position_size: var("MAX_POSITION_SIZE")
                 .unwrap_or_else(|_| "100".to_string())
                 .parse()
                 .context("Invalid MAX_POSITION_SIZE")?,
-            dry_run: env::var("DRY_RUN")
+            dry_run: var("DRY_RUN")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .unwrap_or(true),
-            log_level: env::var("LOG_LEVEL")
+            log_level: var("LOG_LEVEL")
                 .unwrap_or_else(|_| "INFO".to_string()),
+            record_quotes: var("RECORD_QUOTES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            warmup_quotes: var("WARMUP_QUOTES")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid WARMUP_QUOTES")?,
+            stale_book_secs: var("STALE_BOOK_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("Invalid STALE_BOOK_SECS")?,
+            revalidate_edge_fraction: var("REVALIDATE_EDGE_FRACTION")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .context("Invalid REVALIDATE_EDGE_FRACTION")?,
+            market_cooldown_secs: var("MARKET_COOLDOWN_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid MARKET_COOLDOWN_SECS")?,
+            move_jump: var("MOVE_JUMP")
+                .unwrap_or_else(|_| "0.05".to_string())
+                .parse()
+                .context("Invalid MOVE_JUMP")?,
+            move_liquidity_pull: var("MOVE_LIQUIDITY_PULL")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .context("Invalid MOVE_LIQUIDITY_PULL")?,
+            lag_threshold: var("LAG_THRESHOLD")
+                .unwrap_or_else(|_| "0.03".to_string())
+                .parse()
+                .context("Invalid LAG_THRESHOLD")?,
+            fee_overrides: var("FEE_OVERRIDES").unwrap_or_default(),
+            execution_mode: var("EXECUTION_MODE")
+                .unwrap_or_else(|_| "auto".to_string()),
+            intent_ttl_secs: var("INTENT_TTL_SECS")
+                .unwrap_or_else(|_| "120".to_string())
+                .parse()
+                .context("Invalid INTENT_TTL_SECS")?,
+            order_rate_limits: parse_values(
+                &var("ORDER_RATE_LIMITS").unwrap_or_else(|_| "polymarket:60,kalshi:60".to_string()),
+            )
+            .context("Invalid ORDER_RATE_LIMITS")?,
+            cancel_rate_limits: parse_values(
+                &var("CANCEL_RATE_LIMITS").unwrap_or_else(|_| "polymarket:120,kalshi:120".to_string()),
+            )
+            .context("Invalid CANCEL_RATE_LIMITS")?,
+            order_burst_limit: var("ORDER_BURST_LIMIT")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid ORDER_BURST_LIMIT")?,
+            self_trade_policy: var("SELF_TRADE_POLICY")
+                .unwrap_or_else(|_| "cancel".to_string())
+                .parse()
+                .context("Invalid SELF_TRADE_POLICY")?,
+            display_currency: var("DISPLAY_CURRENCY")
+                .unwrap_or_else(|_| "USD".to_string())
+                .parse()
+                .context("Invalid DISPLAY_CURRENCY")?,
+            price_format: var("PRICE_FORMAT")
+                .unwrap_or_else(|_| "probability".to_string())
+                .parse()
+                .context("Invalid PRICE_FORMAT")?,
+            price_decimals: var("PRICE_DECIMALS")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse())
+                .transpose()
+                .context("Invalid PRICE_DECIMALS")?,
+            percent_decimals: var("PERCENT_DECIMALS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .context("Invalid PERCENT_DECIMALS")?,
+            price_rounding: var("PRICE_ROUNDING")
+                .unwrap_or_else(|_| "half_up".to_string())
+                .parse()
+                .context("Invalid PRICE_ROUNDING")?,
+            tui_locale: var("TUI_LOCALE")
+                .unwrap_or_else(|_| "en".to_string())
+                .parse()
+                .context("Invalid TUI_LOCALE")?,
+            tui_role: var("TUI_ROLE")
+                .unwrap_or_else(|_| "trader".to_string())
+                .parse()
+                .context("Invalid TUI_ROLE")?,
+            tui_pin: var("TUI_PIN").ok().filter(|v| !v.is_empty()),
+            timezone: timezone::parse(&var("TIMEZONE").unwrap_or_else(|_| timezone::DEFAULT_TIMEZONE.name().to_string()))
+                .context("Invalid TIMEZONE")?,
+            dashboard_addr: var("DASHBOARD_ADDR").ok().filter(|v| !v.is_empty()),
+            dashboard_tokens: permissions::parse_roles(&var("DASHBOARD_TOKENS").unwrap_or_default())
+                .context("Invalid DASHBOARD_TOKENS")?,
+
+            // Detection universe
+            kalshi_series: var("KALSHI_SERIES")
+                .map(|v| parse_list(&v))
+                .unwrap_or_default(),
+            universe_refresh_secs: var("UNIVERSE_REFRESH_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid UNIVERSE_REFRESH_SECS")?,
+            leaderboard_refresh_secs: var("LEADERBOARD_REFRESH_SECS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid LEADERBOARD_REFRESH_SECS")?,
+            leaderboard_snapshot_secs: var("LEADERBOARD_SNAPSHOT_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("Invalid LEADERBOARD_SNAPSHOT_SECS")?,
+            refresh_jitter: var("REFRESH_JITTER")
+                .unwrap_or_else(|_| "0.1".to_string())
+                .parse()
+                .context("Invalid REFRESH_JITTER")?,
+
+            // Paper trading
+            paper_profile: var("PAPER_PROFILE")
+                .unwrap_or_else(|_| "default".to_string()),
+            paper_balance: var("PAPER_BALANCE")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .context("Invalid PAPER_BALANCE")?,
+
+            // Capital allocation
+            capital_allocation: var("CAPITAL_ALLOCATION")
+                .map(|v| parse_weights(&v))
+                .unwrap_or_else(|_| Ok(Vec::new()))
+                .context("Invalid CAPITAL_ALLOCATION")?,
+            venue_allocation: var("VENUE_ALLOCATION")
+                .map(|v| parse_weights(&v))
+                .unwrap_or_else(|_| Ok(Vec::new()))
+                .context("Invalid VENUE_ALLOCATION")?,
+            rebalance_rule: var("REBALANCE_RULE")
+                .unwrap_or_else(|_| "fixed".to_string())
+                .parse()
+                .context("Invalid REBALANCE_RULE")?,
+            rebalance_interval_hours: var("REBALANCE_INTERVAL_HOURS")
+                .unwrap_or_else(|_| "24".to_string())
+                .parse()
+                .context("Invalid REBALANCE_INTERVAL_HOURS")?,
+            conflict_policies: var("CONFLICT_POLICY")
+                .map(|v| parse_pairs(&v))
+                .unwrap_or_default(),
+            max_question_loss: var("MAX_QUESTION_LOSS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid MAX_QUESTION_LOSS")?,
+            inventory_cap: var("INVENTORY_CAP")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("Invalid INVENTORY_CAP")?,
+            max_inventory_skew: var("MAX_INVENTORY_SKEW")
+                .unwrap_or_else(|_| "0.02".to_string())
+                .parse()
+                .context("Invalid MAX_INVENTORY_SKEW")?,
+            skew_function: var("SKEW_FUNCTION")
+                .unwrap_or_else(|_| "linear".to_string())
+                .parse()
+                .context("Invalid SKEW_FUNCTION")?,
+            max_trades_per_day: var("MAX_TRADES_PER_DAY")
+                .map(|v| parse_pairs(&v))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(strategy, cap)| Ok((strategy, cap.parse().context("Invalid MAX_TRADES_PER_DAY")?)))
+                .collect::<Result<_>>()?,
+            max_orders_per_day: var("MAX_ORDERS_PER_DAY")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse())
+                .transpose()
+                .context("Invalid MAX_ORDERS_PER_DAY")?,
+            execution_workers: var("EXECUTION_WORKERS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .context("Invalid EXECUTION_WORKERS")?,
+            derisk_drawdown: var("DERISK_DRAWDOWN")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse())
+                .transpose()
+                .context("Invalid DERISK_DRAWDOWN")?,
+            derisk_recovery: var("DERISK_RECOVERY")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse())
+                .transpose()
+                .context("Invalid DERISK_RECOVERY")?,
+            derisk_factor: var("DERISK_FACTOR")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .context("Invalid DERISK_FACTOR")?,
+
+            // Strategy schedules
+            strategy_schedules: var("STRATEGY_SCHEDULES")
+                .map(|v| parse_schedules(&v))
+                .unwrap_or_default(),
+            schedule_timezone: var("SCHEDULE_TIMEZONE")
+                .or_else(|_| var("TIMEZONE"))
+                .unwrap_or_else(|_| timezone::DEFAULT_TIMEZONE.name().to_string()),
+            shadow_strategies: var("SHADOW_STRATEGIES")
+                .map(|v| parse_list(&v))
+                .unwrap_or_default(),
+            strategy_dry_run: parse_values(&var("STRATEGY_DRY_RUN").unwrap_or_default())
+                .context("Invalid STRATEGY_DRY_RUN")?,
+
+            mark_interval_secs: var("MARK_INTERVAL_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid MARK_INTERVAL_SECS")?,
+
+            // Alerts
+            telegram_bot_token: var("TELEGRAM_BOT_TOKEN").ok(),
+            telegram_chat_id: var("TELEGRAM_CHAT_ID").ok(),
+            telegram_command_chats: var("TELEGRAM_COMMAND_CHATS").map(|v| parse_list(&v)).unwrap_or_default(),
+            telegram_command_users: permissions::parse_roles(&var("TELEGRAM_COMMAND_USERS").unwrap_or_default())
+                .context("Invalid TELEGRAM_COMMAND_USERS")?,
+            discord_webhook_url: var("DISCORD_WEBHOOK_URL").ok(),
+            webhook_url: var("WEBHOOK_URL").ok().filter(|v| !v.is_empty()),
+            webhook_secret: var("WEBHOOK_SECRET").ok().filter(|v| !v.is_empty()),
+            smtp_host: var("SMTP_HOST").ok().filter(|v| !v.is_empty()),
+            smtp_port: var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse()
+                .context("Invalid SMTP_PORT")?,
+            smtp_tls: var("SMTP_TLS")
+                .unwrap_or_else(|_| "starttls".to_string())
+                .parse()
+                .context("Invalid SMTP_TLS")?,
+            smtp_username: var("SMTP_USERNAME").ok(),
+            smtp_password: var("SMTP_PASSWORD").ok(),
+            email_from: var("EMAIL_FROM")
+                .unwrap_or_else(|_| "polybot@localhost".to_string()),
+            email_to: parse_list(&var("EMAIL_TO").unwrap_or_default()),
+            email_subject_template: var("EMAIL_SUBJECT_TEMPLATE")
+                .unwrap_or_else(|_| "[polybot] {icon} {title}".to_string()),
+            email_body_template: var("EMAIL_BODY_TEMPLATE")
+                .unwrap_or_else(|_| "{title}\n\n{message}\n\n{severity} {kind} alert at {timestamp}".to_string()),
+            email_alert_kinds: parse_list(&var("EMAIL_ALERT_KINDS").unwrap_or_else(|_| "report,breaker,login".to_string())),
+            notify_routes: var("NOTIFY_ROUTES").unwrap_or_default(),
+            notify_dedup_secs: var("NOTIFY_DEDUP_SECS")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse()
+                .context("Invalid NOTIFY_DEDUP_SECS")?,
+            notify_max_per_minute: var("NOTIFY_MAX_PER_MINUTE")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .context("Invalid NOTIFY_MAX_PER_MINUTE")?,
+            daily_report_hour: var("DAILY_REPORT_HOUR")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse())
+                .transpose()
+                .context("Invalid DAILY_REPORT_HOUR")?,
+            watchlist_path: var("WATCHLIST_PATH")
+                .unwrap_or_else(|_| "data/watchlist.json".to_string()),
+            watchlist_poll_secs: var("WATCHLIST_POLL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid WATCHLIST_POLL_SECS")?,
+
+            // Raw payload logging
+            raw_log: var("RAW_LOG")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            raw_log_dir: var("RAW_LOG_DIR")
+                .unwrap_or_else(|_| "data/raw".to_string()),
+            raw_log_max_mb: var("RAW_LOG_MAX_MB")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("Invalid RAW_LOG_MAX_MB")?,
+            raw_log_files: var("RAW_LOG_FILES")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid RAW_LOG_FILES")?,
+
+            // HTTP connection pool
+            http_pool_size: var("HTTP_POOL_SIZE")
+                .unwrap_or_else(|_| "16".to_string())
+                .parse()
+                .context("Invalid HTTP_POOL_SIZE")?,
+            http_idle_timeout_secs: var("HTTP_IDLE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .context("Invalid HTTP_IDLE_TIMEOUT_SECS")?,
+            http_keep_alive_secs: var("HTTP_KEEP_ALIVE_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid HTTP_KEEP_ALIVE_SECS")?,
+
+            // Endpoint failover
+            kalshi_api_fallbacks: parse_list(&var("KALSHI_API_FALLBACKS").unwrap_or_default()),
+            polymarket_clob_fallbacks: parse_list(&var("POLYMARKET_CLOB_FALLBACKS").unwrap_or_default()),
+            polygon_rpc_fallbacks: parse_list(&var("POLYGON_RPC_FALLBACKS").unwrap_or_default()),
+            failover_after_errors: var("FAILOVER_AFTER_ERRORS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid FAILOVER_AFTER_ERRORS")?,
+
+            // Fixtures
+            fixture_mode: var("FIXTURE_MODE")
+                .unwrap_or_else(|_| "off".to_string()),
+            fixture_dir: var("FIXTURE_DIR")
+                .unwrap_or_else(|_| "data/fixtures".to_string()),
         })
     }
 
@@ -79,9 +691,116 @@ impl Config {
             && !self.polymarket_private_key.is_empty()
     }
 
+    /// Short hash of the settings, stamped on records for provenance.
+    /// Credentials and alert destinations are left out so rotating them
+    /// doesn't look like a config change.
+    pub fn fingerprint(&self) -> String {
+        let mut settings = self.clone();
+        for secret in [
+            &mut settings.polymarket_api_key,
+            &mut settings.polymarket_secret,
+            &mut settings.polymarket_passphrase,
+            &mut settings.polymarket_private_key,
+            &mut settings.polygon_rpc_url,
+        ] {
+            secret.clear();
+        }
+        for secret in [
+            &mut settings.kalshi_email,
+            &mut settings.kalshi_password,
+            &mut settings.kalshi_api_key,
+            &mut settings.kalshi_api_secret,
+            &mut settings.kalshi_follow_api_key,
+            &mut settings.kalshi_follow_api_secret,
+            &mut settings.telegram_bot_token,
+            &mut settings.telegram_chat_id,
+            &mut settings.discord_webhook_url,
+            &mut settings.webhook_url,
+            &mut settings.webhook_secret,
+            &mut settings.smtp_username,
+            &mut settings.smtp_password,
+            &mut settings.tui_pin,
+        ] {
+            *secret = None;
+        }
+        settings.dashboard_tokens.clear();
+        // RPC URLs often embed an API key
+        settings.polygon_rpc_fallbacks.clear();
+        let digest = Sha256::digest(format!("{:?}", settings).as_bytes());
+        digest.iter().take(6).map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Whether order intents wait for manual approval.
+    pub fn is_semi_auto(&self) -> bool {
+        self.execution_mode.eq_ignore_ascii_case("semi-auto")
+    }
+
     /// Check if Kalshi credentials are configured.
     pub fn has_kalshi_credentials(&self) -> bool {
         (self.kalshi_email.is_some() && self.kalshi_password.is_some())
             || (self.kalshi_api_key.is_some() && self.kalshi_api_secret.is_some())
     }
 }
+
+/// Split a comma-separated env value into trimmed, non-empty items.
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Parse "name:weight" pairs from a comma-separated env value. Weights
+/// must be non-negative numbers.
+fn parse_weights(value: &str) -> Result<Vec<(String, f64)>> {
+    parse_list(value)
+        .into_iter()
+        .map(|item| {
+            let (name, weight) = item
+                .split_once(':')
+                .with_context(|| format!("Expected name:weight, got '{}'", item))?;
+            let weight: f64 = weight.trim().parse().with_context(|| format!("Invalid weight in '{}'", item))?;
+            anyhow::ensure!(weight.is_finite() && weight >= 0.0, "Weight must be non-negative in '{}'", item);
+            Ok((name.trim().to_string(), weight))
+        })
+        .collect()
+}
+
+/// Parse "name:value" pairs from a comma-separated env value, skipping
+/// malformed entries.
+fn parse_pairs(value: &str) -> Vec<(String, String)> {
+    parse_list(value)
+        .into_iter()
+        .filter_map(|item| {
+            let (name, value) = item.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Parse "name:value" pairs from a comma-separated env value, e.g.
+/// "polymarket:60" or "arbitrage:false".
+fn parse_values<T>(value: &str) -> Result<Vec<(String, T)>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    parse_pairs(value)
+        .into_iter()
+        .map(|(name, value)| Ok((name, value.parse()?)))
+        .collect()
+}
+
+/// Parse "name=expression" pairs separated by ';' (cron expressions contain
+/// commas), skipping malformed entries.
+fn parse_schedules(value: &str) -> Vec<(String, String)> {
+    value
+        .split(';')
+        .filter_map(|item| {
+            let (name, expr) = item.split_once('=')?;
+            let (name, expr) = (name.trim(), expr.trim());
+            (!name.is_empty() && !expr.is_empty()).then(|| (name.to_string(), expr.to_string()))
+        })
+        .collect()
+}
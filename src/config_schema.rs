@@ -0,0 +1,334 @@
+//! Registry of every setting the bot reads from the environment.
+//!
+//! One entry per variable with its section, default and a line of
+//! documentation. `polybot config` renders it as an annotated
+//! `.env.example` or as JSON, and startup checks the `.env` file and the
+//! environment against it so a misspelled variable fails loudly instead of
+//! silently falling back to its default.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use tracing::warn;
+
+/// One environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Setting {
+    pub section: &'static str,
+    pub key: &'static str,
+    /// Value used when unset; empty when unset means off or none
+    pub default: &'static str,
+    pub doc: &'static str,
+    /// Credential: never given a value in the example
+    pub secret: bool,
+}
+
+const fn setting(section: &'static str, key: &'static str, default: &'static str, doc: &'static str) -> Setting {
+    Setting { section, key, default, doc, secret: false }
+}
+
+const fn secret(section: &'static str, key: &'static str, doc: &'static str) -> Setting {
+    Setting { section, key, default: "", doc, secret: true }
+}
+
+const POLYMARKET: &str = "Polymarket";
+const KALSHI: &str = "Kalshi";
+const BOT: &str = "Bot";
+const DISPLAY: &str = "Display and TUI";
+const UNIVERSE: &str = "Detection universe";
+const ALLOCATION: &str = "Capital allocation and risk";
+const STRATEGIES: &str = "Strategies";
+const ALERTS: &str = "Alerts";
+const STORAGE: &str = "Logging and storage";
+const NETWORK: &str = "Network";
+
+/// Every setting, in `.env.example` order.
+pub const SETTINGS: &[Setting] = &[
+    secret(POLYMARKET, "POLYMARKET_API_KEY", "CLOB API key"),
+    secret(POLYMARKET, "POLYMARKET_SECRET", "CLOB API secret"),
+    secret(POLYMARKET, "POLYMARKET_PASSPHRASE", "CLOB API passphrase"),
+    secret(POLYMARKET, "POLYMARKET_PRIVATE_KEY", "Wallet key orders are signed with"),
+    setting(POLYMARKET, "POLYMARKET_SIGNATURE_TYPE", "EOA", "How orders are signed: EOA, POLY_PROXY (email/Magic) or POLY_GNOSIS_SAFE"),
+    secret(POLYMARKET, "POLYMARKET_FUNDER", "Proxy wallet holding the funds (required for proxy signature types)"),
+    setting(POLYMARKET, "POLYGON_RPC_URL", "https://polygon-rpc.com", "Polygon RPC for balances, approvals and redemptions"),
+    setting(POLYMARKET, "GAS_MAX_FEE_GWEI", "500", "Highest EIP-1559 max fee offered for on-chain transactions"),
+    setting(POLYMARKET, "GAS_MAX_PRIORITY_GWEI", "50", "Highest priority fee offered"),
+    setting(POLYMARKET, "TX_STUCK_SECS", "60", "Seconds without a receipt before a transaction is re-sent with higher fees"),
+    secret(KALSHI, "KALSHI_EMAIL", "Login email (or use an API key)"),
+    secret(KALSHI, "KALSHI_PASSWORD", "Login password"),
+    secret(KALSHI, "KALSHI_API_KEY", "API key ID"),
+    secret(KALSHI, "KALSHI_API_SECRET", "API private key"),
+    setting(KALSHI, "KALSHI_FILL_POLL_SECS", "2", "Seconds between polls of our Kalshi fills"),
+    secret(KALSHI, "KALSHI_FOLLOW_API_KEY", "Second Kalshi account whose fills are mirrored (unset = off)"),
+    secret(KALSHI, "KALSHI_FOLLOW_API_SECRET", "Private key of the followed account"),
+    setting(KALSHI, "KALSHI_FOLLOW_SCALE", "1.0", "Our contracts per contract the followed account trades"),
+    setting(KALSHI, "KALSHI_FOLLOW_MAX_CONTRACTS", "100", "Cap on contracts per mirrored fill"),
+    setting(BOT, "COPY_TRADERS", "", "Polymarket addresses whose trades are copied, comma-separated (empty = off)"),
+    setting(BOT, "COPY_POLL_SECS", "30", "Seconds between scans of the copied traders' activity"),
+    setting(BOT, "MIN_PROFIT_THRESHOLD", "0.02", "Net edge an arb needs to be acted on (0.02 = 2%)"),
+    setting(BOT, "MAX_POSITION_SIZE", "100", "USD per arb"),
+    setting(BOT, "DRY_RUN", "true", "Log orders instead of sending them"),
+    setting(BOT, "LOG_LEVEL", "INFO", "TRACE, DEBUG, INFO, WARN or ERROR"),
+    setting(BOT, "RECORD_QUOTES", "false", "Append every quote the detector checks to the backtest tape"),
+    setting(BOT, "WARMUP_QUOTES", "3", "Consecutive sane quotes a market needs before it can signal"),
+    setting(BOT, "STALE_BOOK_SECS", "600", "Seconds a Polymarket book may go unchanged before its quotes are rejected (0 = never)"),
+    setting(BOT, "REVALIDATE_EDGE_FRACTION", "0.5", "Share of the detected edge an arb must keep when re-checked before execution (0 = no re-check)"),
+    setting(BOT, "MARKET_COOLDOWN_SECS", "60", "Seconds a market sits out detection and execution after we trade it"),
+    setting(BOT, "MOVE_JUMP", "0.05", "Best bid or ask change between passes that counts as the market moving (0 = off)"),
+    setting(BOT, "LAG_THRESHOLD", "0.03", "Kalshi move over recent candles, and Polymarket gap behind it, that flags a lag (0 = off)"),
+    setting(BOT, "MOVE_LIQUIDITY_PULL", "0.5", "Share of the size near the top pulled between passes that counts as the market moving (0 = off)"),
+    setting(BOT, "FEE_OVERRIDES", "", "Fee rate overrides by venue and ticker or token ID prefix, e.g. kalshi:KXBTCD=0.035/0;polymarket=200/0"),
+    setting(BOT, "EXECUTION_MODE", "auto", "auto executes immediately; semi-auto queues order intents for approval"),
+    setting(BOT, "INTENT_TTL_SECS", "120", "Seconds an unapproved intent stays valid"),
+    setting(BOT, "ORDER_RATE_LIMITS", "polymarket:60,kalshi:60", "Orders per minute by venue"),
+    setting(BOT, "CANCEL_RATE_LIMITS", "polymarket:120,kalshi:120", "Cancels per minute by venue"),
+    setting(BOT, "ORDER_BURST_LIMIT", "5", "Orders per second on any one venue (0 = no burst cap)"),
+    setting(BOT, "SELF_TRADE_POLICY", "cancel", "What to do when an order would cross our own resting order: cancel, skip or off"),
+    setting(BOT, "MARK_INTERVAL_SECS", "60", "Seconds between mark-to-market passes over open positions"),
+    setting(BOT, "MIN_VENUE_FLOAT", "0", "Balance each venue needs for arbitrage; alert below it (0 = off)"),
+    setting(BOT, "BALANCE_POLL_SECS", "300", "How often balances are refreshed"),
+    setting(BOT, "RESOLUTION_POLL_SECS", "300", "How often matched pairs are checked for resolution"),
+    setting(BOT, "RESOLUTION_WARNING_MINUTES", "60", "Minutes before a position's market closes to warn about it (0 = off)"),
+    setting(DISPLAY, "DISPLAY_CURRENCY", "USD", "Currency the TUI and reports show amounts in"),
+    setting(DISPLAY, "PRICE_FORMAT", "probability", "How prices are shown: probability, cents or percent"),
+    setting(DISPLAY, "PRICE_DECIMALS", "", "Decimals for prices (unset = the format's default)"),
+    setting(DISPLAY, "PERCENT_DECIMALS", "2", "Decimals for edges, returns and other percentages"),
+    setting(DISPLAY, "PRICE_ROUNDING", "half_up", "half_up, half_even, floor or ceil"),
+    setting(DISPLAY, "TUI_LOCALE", "en", "Language of the TUI: en or es"),
+    setting(DISPLAY, "TUI_ROLE", "trader", "What the TUI may do: viewer, trader or admin"),
+    secret(DISPLAY, "TUI_PIN", "Asked for before the TUI's dangerous actions (unset = not asked)"),
+    setting(DISPLAY, "TIMEZONE", "America/New_York", "Clock for unlabelled resolution times, the daily rollover, reports and timestamps"),
+    setting(DISPLAY, "DASHBOARD_ADDR", "", "Where the web dashboard listens, e.g. 0.0.0.0:8080 (unset = off)"),
+    secret(DISPLAY, "DASHBOARD_TOKENS", "Dashboard tokens with their roles, e.g. s3cret:admin,l00k:viewer, sent as a Bearer token or cookie (unset = no token asked, view only)"),
+    setting(DISPLAY, "SNOOZE_HOURS", "4", "How long a snooze from the TUI holds back a market's alerts"),
+    setting(UNIVERSE, "KALSHI_SERIES", "", "Kalshi series tickers to enumerate, e.g. KXBTCD,KXETHD (unset = built-in pairs)"),
+    setting(UNIVERSE, "UNIVERSE_REFRESH_SECS", "300", "Seconds between universe refreshes"),
+    setting(UNIVERSE, "LEADERBOARD_REFRESH_SECS", "0", "Seconds between leaderboard refreshes (0 = off)"),
+    setting(UNIVERSE, "LEADERBOARD_SNAPSHOT_SECS", "86400", "Minimum seconds between leaderboard history snapshots (0 = none kept)"),
+    setting(UNIVERSE, "REFRESH_JITTER", "0.1", "Fraction refresh intervals are randomly stretched or shrunk by"),
+    setting(ALLOCATION, "PAPER_PROFILE", "default", "Named paper portfolio the TUI and reports use"),
+    setting(ALLOCATION, "PAPER_BALANCE", "1000", "Starting cash of newly created or reset paper portfolios"),
+    setting(ALLOCATION, "CAPITAL_ALLOCATION", "", "Strategy weights, e.g. arbitrage:0.5,copy_trade:0.3,directional:0.2"),
+    setting(ALLOCATION, "VENUE_ALLOCATION", "", "Venue weights, e.g. polymarket:0.6,kalshi:0.4 (unset = unconstrained)"),
+    setting(ALLOCATION, "REBALANCE_RULE", "fixed", "fixed or sharpe"),
+    setting(ALLOCATION, "REBALANCE_INTERVAL_HOURS", "24", "Hours between rebalances under the sharpe rule"),
+    setting(ALLOCATION, "CONFLICT_POLICY", "", "Policy per strategy for orders opposing another strategy's position, e.g. copy_trade:net"),
+    setting(ALLOCATION, "MAX_QUESTION_LOSS", "0", "Max loss on one question if it resolves against the netted book (0 = unlimited)"),
+    setting(ALLOCATION, "INVENTORY_CAP", "0", "Largest net contracts on one question before orders adding to it are refused (0 = no cap)"),
+    setting(ALLOCATION, "MAX_INVENTORY_SKEW", "0.02", "Quote shift at the inventory cap, in price"),
+    setting(ALLOCATION, "SKEW_FUNCTION", "linear", "How the quote shift grows with inventory: linear, quadratic or tanh"),
+    setting(ALLOCATION, "MAX_TRADES_PER_DAY", "", "Trades per strategy per day, e.g. arbitrage:20,copy_trade:50 (unlisted = unlimited)"),
+    setting(ALLOCATION, "MAX_ORDERS_PER_DAY", "", "Orders per day across all strategies (unset = unlimited)"),
+    setting(ALLOCATION, "EXECUTION_WORKERS", "2", "Queued orders in flight at once on each venue"),
+    setting(ALLOCATION, "DERISK_DRAWDOWN", "", "Drawdown from the equity high-water mark that cuts sizes, e.g. 0.10 (unset = never)"),
+    setting(ALLOCATION, "DERISK_RECOVERY", "", "Drawdown at or below which full sizes return (unset = half the trigger)"),
+    setting(ALLOCATION, "DERISK_FACTOR", "0.5", "Multiplier on max position sizes while de-risked"),
+    setting(STRATEGIES, "STRATEGY_SCHEDULES", "", "Cron-like schedule per strategy, e.g. arbitrage=* 9-20 * * *; unlisted run 24/7"),
+    setting(STRATEGIES, "SCHEDULE_TIMEZONE", "", "Time zone schedules are evaluated in (unset = TIMEZONE)"),
+    setting(STRATEGIES, "SHADOW_STRATEGIES", "", "Strategies filled virtually against the live books, e.g. arbitrage"),
+    setting(STRATEGIES, "STRATEGY_DRY_RUN", "", "Per-strategy DRY_RUN overrides, e.g. arbitrage:false,copy_trade:true"),
+    secret(ALERTS, "TELEGRAM_BOT_TOKEN", "Telegram bot for alerts and approvals"),
+    secret(ALERTS, "TELEGRAM_CHAT_ID", "Chat the bot posts to"),
+    setting(ALERTS, "TELEGRAM_COMMAND_CHATS", "", "Chat IDs allowed to send operator commands (unset = TELEGRAM_CHAT_ID)"),
+    setting(ALERTS, "TELEGRAM_COMMAND_USERS", "", "Telegram user IDs with their roles, e.g. 12345:admin; others may only look"),
+    secret(ALERTS, "DISCORD_WEBHOOK_URL", "Discord channel webhook for alerts"),
+    secret(ALERTS, "WEBHOOK_URL", "Receives opportunity and fill events as JSON"),
+    secret(ALERTS, "WEBHOOK_SECRET", "Signs webhook requests with HMAC-SHA256 when set"),
+    setting(ALERTS, "SMTP_HOST", "", "SMTP server for email alerts (unset = no email)"),
+    setting(ALERTS, "SMTP_PORT", "587", "SMTP port"),
+    setting(ALERTS, "SMTP_TLS", "starttls", "starttls, tls or none"),
+    secret(ALERTS, "SMTP_USERNAME", "SMTP login"),
+    secret(ALERTS, "SMTP_PASSWORD", "SMTP password"),
+    setting(ALERTS, "EMAIL_FROM", "polybot@localhost", "Sender of alert emails"),
+    setting(ALERTS, "EMAIL_TO", "", "Comma-separated recipients"),
+    setting(ALERTS, "EMAIL_SUBJECT_TEMPLATE", "[polybot] {icon} {title}", "Subject of alert emails"),
+    setting(ALERTS, "EMAIL_BODY_TEMPLATE", "{title}\n\n{message}\n\n{severity} {kind} alert at {timestamp}", "Body of alert emails"),
+    setting(ALERTS, "EMAIL_ALERT_KINDS", "report,breaker,login", "Alert kinds that are emailed (* = all)"),
+    setting(ALERTS, "NOTIFY_ROUTES", "", "Alert routing rules, e.g. breaker=telegram+email;opportunity=discord"),
+    setting(ALERTS, "NOTIFY_DEDUP_SECS", "300", "Repeats of an alert within this many seconds are held back"),
+    setting(ALERTS, "NOTIFY_MAX_PER_MINUTE", "20", "Messages per channel per minute (0 = unlimited)"),
+    setting(ALERTS, "DAILY_REPORT_HOUR", "", "Hour (in TIMEZONE) the daily report is sent (unset = never)"),
+    setting(ALERTS, "WATCHLIST_PATH", "data/watchlist.json", "JSON file listing watched markets and their alert rules"),
+    setting(ALERTS, "WATCHLIST_POLL_SECS", "30", "Seconds between watchlist evaluations"),
+    setting(STORAGE, "RAW_LOG", "false", "Write redacted request/response bodies under RAW_LOG_DIR"),
+    setting(STORAGE, "RAW_LOG_DIR", "data/raw", "Where raw payloads are written"),
+    setting(STORAGE, "RAW_LOG_MAX_MB", "10", "Rotate after this many megabytes"),
+    setting(STORAGE, "RAW_LOG_FILES", "5", "Rotated files to keep"),
+    secret(STORAGE, "DATA_ENCRYPTION_KEY", "Base64 32-byte key sealing state files at rest (unset = plaintext)"),
+    setting(STORAGE, "DATA_ENCRYPTION_KEY_FILE", "", "File holding the key instead"),
+    setting(STORAGE, "FIXTURE_MODE", "off", "off, record or replay API responses and websocket frames"),
+    setting(STORAGE, "FIXTURE_DIR", "data/fixtures", "Directory holding recorded responses and frames"),
+    setting(NETWORK, "HTTP_POOL_SIZE", "16", "Idle connections kept open per host"),
+    setting(NETWORK, "HTTP_IDLE_TIMEOUT_SECS", "90", "Seconds an idle connection is kept"),
+    setting(NETWORK, "HTTP_KEEP_ALIVE_SECS", "30", "TCP keep-alive and HTTP/2 ping interval"),
+    setting(NETWORK, "KALSHI_API_FALLBACKS", "", "Alternative Kalshi API base URLs, tried in order"),
+    setting(NETWORK, "POLYMARKET_CLOB_FALLBACKS", "", "Alternative Polymarket CLOB base URLs"),
+    secret(NETWORK, "POLYGON_RPC_FALLBACKS", "Backup Polygon RPCs (URLs often embed an API key)"),
+    setting(NETWORK, "FAILOVER_AFTER_ERRORS", "3", "Consecutive connection errors before switching to the next endpoint"),
+];
+
+/// The setting named `key`.
+pub fn find(key: &str) -> Option<&'static Setting> {
+    SETTINGS.iter().find(|s| s.key == key)
+}
+
+/// The schema as an annotated `.env.example`. Settings without a default
+/// are commented out, since an empty value is not the same as unset for
+/// all of them.
+pub fn env_example() -> String {
+    let mut out = String::from("# Generated by `polybot config schema`; every setting with its default.\n");
+    let mut section = "";
+    for s in SETTINGS {
+        if s.section != section {
+            section = s.section;
+            out.push_str(&format!("\n# --- {} ---\n", section));
+        }
+        out.push_str(&format!("# {}\n", s.doc));
+        if s.secret || s.default.is_empty() {
+            out.push_str(&format!("# {}=\n", s.key));
+        } else {
+            out.push_str(&format!("{}={}\n", s.key, quote(s.default)));
+        }
+    }
+    out
+}
+
+/// A value as dotenv reads it back: quoted when it has spaces, `#` or
+/// newlines.
+fn quote(value: &str) -> String {
+    if value.chars().any(|c| c.is_whitespace() || c == '#' || c == '"') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+    } else {
+        value.to_string()
+    }
+}
+
+/// A variable that is not a setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unknown {
+    pub key: String,
+    /// The setting it is probably a misspelling of
+    pub suggestion: Option<&'static str>,
+}
+
+impl fmt::Display for Unknown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.suggestion {
+            Some(setting) => write!(f, "{} (did you mean {}?)", self.key, setting),
+            None => write!(f, "{}", self.key),
+        }
+    }
+}
+
+/// Keys among `keys` that are not settings, with the setting each is
+/// probably a misspelling of.
+pub fn unknown<'a>(keys: impl IntoIterator<Item = &'a str>) -> Vec<Unknown> {
+    keys.into_iter()
+        .filter(|key| find(key).is_none())
+        .map(|key| Unknown { key: key.to_string(), suggestion: suggest(key) })
+        .collect()
+}
+
+/// The setting within a couple of typos of `key`, if any.
+fn suggest(key: &str) -> Option<&'static str> {
+    let upper = key.to_uppercase();
+    SETTINGS
+        .iter()
+        .map(|s| (edit_distance(&upper, s.key), s.key))
+        .filter(|(distance, _)| *distance <= 2 && distance * 5 <= upper.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, key)| key)
+}
+
+/// Levenshtein distance.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Check `env_file` and the environment before the settings are used.
+/// Likely misspellings anywhere are an error; other unknown keys in the
+/// file are only warned about, since other tools may share it.
+pub fn check_environment(env_file: &str) -> Result<()> {
+    let mut file_keys = BTreeSet::new();
+    if fs::metadata(env_file).is_ok() {
+        for item in dotenvy::from_path_iter(env_file).with_context(|| format!("Invalid {}", env_file))? {
+            let (key, _) = item.with_context(|| format!("Invalid {}", env_file))?;
+            file_keys.insert(key);
+        }
+    }
+    let env_keys: BTreeSet<String> = std::env::vars_os().filter_map(|(k, _)| k.into_string().ok()).collect();
+
+    let misspelled: Vec<Unknown> = unknown(file_keys.union(&env_keys).map(String::as_str))
+        .into_iter()
+        .filter(|u| u.suggestion.is_some())
+        .collect();
+    if !misspelled.is_empty() {
+        let list: Vec<String> = misspelled.iter().map(|u| u.to_string()).collect();
+        anyhow::bail!("Unknown settings: {}. Run `polybot config schema` for every setting", list.join(", "));
+    }
+    for u in unknown(file_keys.iter().map(String::as_str)) {
+        warn!("{} in {} is not a polybot setting and is ignored", u.key, env_file);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_covers_config_and_renders_its_defaults() {
+        // Every variable the loaders read is registered, once
+        let sources = [include_str!("config.rs"), include_str!("utils/encryption.rs")];
+        let mut read = BTreeSet::new();
+        for source in sources {
+            for part in source.split("var(\"").skip(1) {
+                read.insert(part.split('"').next().unwrap());
+            }
+        }
+        let registered: BTreeSet<&str> = SETTINGS.iter().map(|s| s.key).collect();
+        assert_eq!(registered.len(), SETTINGS.len());
+        assert_eq!(read, registered);
+
+        // The example reads back as the defaults, leaving credentials and
+        // settings without one unset
+        let example = env_example();
+        let parsed: Vec<(String, String)> =
+            dotenvy::from_read_iter(example.as_bytes()).collect::<Result<_, _>>().unwrap();
+        let with_defaults = SETTINGS.iter().filter(|s| !s.secret && !s.default.is_empty()).count();
+        assert_eq!(parsed.len(), with_defaults);
+        for (key, value) in parsed {
+            assert_eq!(value, find(&key).unwrap().default, "{}", key);
+        }
+        assert!(example.contains("# KALSHI_API_KEY=\n"));
+    }
+
+    #[test]
+    fn test_misspellings_are_suggested() {
+        let found = unknown(["KALSHI_APIKEY", "MIN_PROFIT_THRESHHOLD", "dry_run", "DRY_RUN", "HOME", "TZ"]);
+        let shown: Vec<String> = found.iter().map(|u| u.to_string()).collect();
+        assert_eq!(
+            shown,
+            [
+                "KALSHI_APIKEY (did you mean KALSHI_API_KEY?)",
+                "MIN_PROFIT_THRESHHOLD (did you mean MIN_PROFIT_THRESHOLD?)",
+                "dry_run (did you mean DRY_RUN?)",
+                "HOME",
+                "TZ",
+            ]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Operator controls.
+//!
+//! The actions an operator can take on a running bot from outside the TUI:
+//! check status, list positions, pause and resume trading, and close a paper
+//! position. Telegram commands and the dashboard's control endpoints both go
+//! through [`Controls`], so they behave the same. A pause stops new orders
+//! from going out and detections from being traded; it is kept in
+//! [`PAUSE_FILE`], so it holds across restarts until resumed.
+//!
+//! Looking is open to every caller; pausing, resuming and closing need the
+//! trader role (see [`crate::utils::permissions`]).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::display;
+use crate::money::Money;
+use crate::paper_trading::{PaperTradingEngine, Profile};
+use crate::utils::permissions::{Action, Permissions};
+use crate::utils::{state, timezone, CircuitBreaker};
+
+/// Whether trading is paused, shared by the bot and its control surfaces.
+pub const PAUSE_FILE: &str = "data/state/pause.json";
+
+/// Commands accepted, for help replies.
+pub const HELP: &str = "/status, /positions, /pause, /resume, /close <market>";
+
+/// Who paused trading and when.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PauseState {
+    pub paused: bool,
+    /// e.g. "telegram:12345" or "dashboard"
+    pub by: Option<String>,
+    pub at: Option<DateTime<Utc>>,
+}
+
+/// An operator command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Status,
+    Positions,
+    Pause,
+    Resume,
+    /// Close the paper position in a market
+    Close(String),
+    Help,
+}
+
+impl Command {
+    /// Parse chat text such as `/close BTC-100K` (a `@botname` suffix on the
+    /// command is ignored). Errors carry the reply to send back.
+    pub fn parse(text: &str) -> std::result::Result<Self, String> {
+        let text = text.trim();
+        let (command, argument) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let command = command.split('@').next().unwrap_or_default().to_ascii_lowercase();
+        let argument = argument.trim();
+        match command.as_str() {
+            "/status" => Ok(Command::Status),
+            "/positions" => Ok(Command::Positions),
+            "/pause" => Ok(Command::Pause),
+            "/resume" => Ok(Command::Resume),
+            "/close" if argument.is_empty() => Err("Usage: /close <market>".to_string()),
+            "/close" => Ok(Command::Close(argument.to_string())),
+            "/help" | "/start" => Ok(Command::Help),
+            _ => Err(format!("Unknown command. Try {}", HELP)),
+        }
+    }
+
+    /// What the command does to the bot, or `None` if it only looks.
+    pub fn action(&self) -> Option<Action> {
+        match self {
+            Command::Pause | Command::Resume | Command::Close(_) => Some(Action::Trade),
+            Command::Status | Command::Positions | Command::Help => None,
+        }
+    }
+}
+
+/// Operator actions on the running bot.
+pub struct Controls {
+    profile: Profile,
+    paper_balance: f64,
+    pause_file: String,
+    breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl Controls {
+    /// Controls over `profile`'s paper portfolio.
+    pub fn new(profile: Profile, paper_balance: f64) -> Self {
+        Self { profile, paper_balance, pause_file: PAUSE_FILE.to_string(), breaker: None }
+    }
+
+    /// The configured PAPER_PROFILE and PAPER_BALANCE.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Ok(Self::new(Profile::from_config(config)?, config.paper_balance))
+    }
+
+    pub fn with_pause_file(mut self, path: &str) -> Self {
+        self.pause_file = path.to_string();
+        self
+    }
+
+    /// Report the circuit breaker in status.
+    pub fn with_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.breaker = Some(breaker);
+        self
+    }
+
+    pub fn pause_state(&self) -> PauseState {
+        state::load(&self.pause_file)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause_state().paused
+    }
+
+    /// Run a command for `by`, returning the reply. Refused unless
+    /// `permissions` allow it.
+    pub fn execute(&self, command: &Command, by: &str, permissions: &Permissions) -> Result<String> {
+        if let Some(action) = command.action().filter(|a| !permissions.allows(*a)) {
+            anyhow::bail!("Not permitted: {} needs the {} role", by, action.required_role());
+        }
+        match command {
+            Command::Status => Ok(self.status()),
+            Command::Positions => Ok(self.positions()),
+            Command::Pause => self.set_paused(true, by),
+            Command::Resume => self.set_paused(false, by),
+            Command::Close(market) => self.close(market),
+            Command::Help => Ok(format!("Commands: {}", HELP)),
+        }
+    }
+
+    fn set_paused(&self, paused: bool, by: &str) -> Result<String> {
+        if self.is_paused() == paused {
+            return Ok(if paused { "Already paused" } else { "Not paused" }.to_string());
+        }
+        let pause = PauseState { paused, by: Some(by.to_string()), at: Some(Utc::now()) };
+        state::save(&self.pause_file, &pause).context("Failed to save pause state")?;
+        Ok(if paused {
+            "⏸️ Trading paused: no new orders until /resume".to_string()
+        } else {
+            "▶️ Trading resumed".to_string()
+        })
+    }
+
+    /// Trading state and paper portfolio totals.
+    pub fn status(&self) -> String {
+        let mut lines = Vec::new();
+        let pause = self.pause_state();
+        match (&self.breaker, pause.paused) {
+            (Some(breaker), _) if !breaker.is_allowed() => {
+                let reason = breaker.state().reason.unwrap_or_else(|| "unknown".to_string());
+                lines.push(format!("🛑 Circuit breaker tripped: {}", reason));
+            }
+            (_, true) => lines.push(format!(
+                "⏸️ Paused by {} at {}",
+                pause.by.as_deref().unwrap_or("unknown"),
+                pause.at.map_or_else(|| "?".to_string(), |at| timezone::format(at, "%H:%M %Z")),
+            )),
+            _ => lines.push("▶️ Trading".to_string()),
+        }
+        let summary = PaperTradingEngine::snapshot_of(&self.profile).summary();
+        lines.push(format!(
+            "💼 {}: {} ({} net P&L, {} this run)",
+            self.profile.name,
+            Money::usd(summary.total_value),
+            Money::usd(summary.net_pnl),
+            Money::usd(summary.session_pnl),
+        ));
+        lines.push(format!("📊 {} open positions, {} cash", summary.positions_count, Money::usd(summary.cash_balance)));
+        lines.join("\n")
+    }
+
+    /// Open paper positions, one per line.
+    pub fn positions(&self) -> String {
+        let engine = PaperTradingEngine::snapshot_of(&self.profile);
+        let mut positions: Vec<_> = engine.portfolio.positions.values().collect();
+        if positions.is_empty() {
+            return "No open positions".to_string();
+        }
+        positions.sort_by(|a, b| a.market.cmp(&b.market));
+        positions
+            .iter()
+            .map(|p| {
+                format!(
+                    "{} ({}): {:.1} @ {} → {} {}",
+                    p.market,
+                    p.platform,
+                    p.size,
+                    display::price(p.avg_price),
+                    display::price(p.current_price),
+                    Money::usd(p.unrealized_pnl),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Sell the paper position in `market` (matched case-insensitively) at
+    /// its last mark, under the portfolio's lock so a TUI trading the same
+    /// portfolio doesn't overwrite the sale.
+    pub fn close(&self, market: &str) -> Result<String> {
+        let mut engine = PaperTradingEngine::for_profile(&self.profile, self.paper_balance)?;
+        let (position, pnl) = engine.update(|engine| {
+            let Some(position) = engine
+                .portfolio
+                .positions
+                .values()
+                .find(|p| p.market.eq_ignore_ascii_case(market))
+                .cloned()
+            else {
+                anyhow::bail!("No open position in {}", market);
+            };
+            let pnl = engine.sell(&position.market, position.current_price)?;
+            Ok((position, pnl))
+        })?;
+        Ok(format!(
+            "✅ Closed {} at {}: {} realized",
+            position.market,
+            display::price(position.current_price),
+            Money::usd(pnl)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::permissions::Role;
+
+    #[test]
+    fn test_commands_parse_and_pause_persists() {
+        assert_eq!(Command::parse("/status"), Ok(Command::Status));
+        assert_eq!(Command::parse("/Positions@polybot_bot"), Ok(Command::Positions));
+        assert_eq!(Command::parse("/close  BTC-100K "), Ok(Command::Close("BTC-100K".to_string())));
+        assert!(Command::parse("/close").unwrap_err().contains("Usage"));
+        assert!(Command::parse("hello").unwrap_err().contains("/status"));
+
+        let path = std::env::temp_dir().join(format!("polybot-pause-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_string_lossy().to_string();
+        let controls = Controls::new(Profile::default(), 1000.0).with_pause_file(&path);
+        let trader = Permissions::new(Role::Trader);
+        assert!(!controls.is_paused());
+
+        // Viewers may look but not pause
+        let viewer = Permissions::new(Role::Viewer);
+        assert!(controls.execute(&Command::Pause, "telegram:7", &viewer).unwrap_err().to_string().contains("trader role"));
+        assert!(controls.execute(&Command::Status, "telegram:7", &viewer).is_ok());
+        assert!(!controls.is_paused());
+
+        assert!(controls.execute(&Command::Pause, "telegram:42", &trader).unwrap().contains("paused"));
+        assert_eq!(controls.execute(&Command::Pause, "dashboard", &trader).unwrap(), "Already paused");
+
+        // Another process reading the same file sees the pause
+        let other = Controls::new(Profile::default(), 1000.0).with_pause_file(&path);
+        let pause = other.pause_state();
+        assert!(pause.paused && pause.by.as_deref() == Some("telegram:42"));
+        other.execute(&Command::Resume, "dashboard", &trader).unwrap();
+        assert!(!controls.is_paused());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_close_is_not_overwritten_by_another_engine() {
+        let temp = |name: &str| {
+            std::env::temp_dir().join(format!("polybot-{}-{}.json", name, uuid::Uuid::new_v4())).to_string_lossy().to_string()
+        };
+        let profile = Profile { name: "close-test".to_string(), portfolio_file: temp("portfolio"), trades_file: temp("trades") };
+
+        // A TUI holding the portfolio in memory
+        let mut tui = PaperTradingEngine::for_profile(&profile, 1000.0).unwrap();
+        tui.update(|e| e.buy("BTC", "BTC", "1h", "polymarket", 10.0, 0.5, "manual", 0.5)).unwrap();
+
+        let controls = Controls::new(profile.clone(), 1000.0);
+        assert!(controls.close("btc").unwrap().contains("Closed BTC"));
+
+        // The TUI's next trade starts from the saved portfolio, without BTC
+        tui.update(|e| e.buy("ETH", "ETH", "1h", "polymarket", 10.0, 0.5, "manual", 0.5)).unwrap();
+        let saved = PaperTradingEngine::snapshot_of(&profile);
+        let mut markets: Vec<_> = saved.portfolio.positions.keys().cloned().collect();
+        markets.sort();
+        assert_eq!(markets, vec!["ETH".to_string()]);
+        assert_eq!(saved.trade_log.get_all().len(), 2);
+        for path in [&profile.portfolio_file, &profile.trades_file] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! Embedded web dashboard.
+//!
+//! With DASHBOARD_ADDR set the bot serves a single static page, so it can be
+//! checked from a phone browser without hosting anything. The page reads
+//! `/api/snapshot` (paper portfolio value, open positions and the latest
+//! opportunities as JSON) once, then follows `/events`, a server-sent event
+//! stream pushing a fresh snapshot every few seconds and each opportunity as
+//! it is detected. With DASHBOARD_TOKENS set every request but the page
+//! itself must carry one of them, as `Authorization: Bearer <token>` or in
+//! the `polybot_token` cookie the page sets after asking for it once (never
+//! in the URL, where it would end up in browser history and proxy logs).
+//!
+//! The same [`Controls`] as the Telegram commands are offered as POST
+//! endpoints: `/api/pause`, `/api/resume` and `/api/close?market=`. They
+//! change what the bot does, so they need a token whose role allows it;
+//! without tokens the dashboard is view only.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tracing::{debug, info};
+
+use crate::arbitrage::Opportunity;
+use crate::config::Config;
+use crate::control::{Command, Controls};
+use crate::paper_trading::PaperTradingEngine;
+use crate::utils::permissions::{Permissions, Role};
+use crate::utils::CircuitBreaker;
+
+/// The page itself, compiled into the binary.
+const INDEX_HTML: &str = include_str!("index.html");
+
+/// Opportunities kept for the feed.
+const FEED_LEN: usize = 50;
+
+/// How often the event stream pushes a fresh snapshot.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a client may take to send its request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Request headers read before giving up on a client.
+const MAX_HEADERS: usize = 64;
+
+/// Longest request or header line accepted.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+
+/// Pages following `/events` at once.
+const MAX_EVENT_CLIENTS: usize = 16;
+
+/// Cookie the page keeps the token in.
+const TOKEN_COOKIE: &str = "polybot_token";
+
+/// Portfolio totals shown at the top of the page.
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioView {
+    pub total_value: f64,
+    pub cash_balance: f64,
+    pub net_pnl: f64,
+    pub pnl_percent: f64,
+    pub session_pnl: f64,
+}
+
+/// One open paper position.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionView {
+    pub market: String,
+    pub platform: String,
+    pub size: f64,
+    pub avg_price: f64,
+    pub current_price: f64,
+    pub unrealized_pnl: f64,
+}
+
+/// Everything the page shows.
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub at: DateTime<Utc>,
+    /// Trading paused by an operator
+    pub paused: bool,
+    pub portfolio: PortfolioView,
+    pub positions: Vec<PositionView>,
+    /// Latest first
+    pub opportunities: Vec<Opportunity>,
+}
+
+/// What a request asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Route {
+    Index,
+    Snapshot,
+    Events,
+    /// A command from a caller with this role
+    Control(Command, Role),
+    BadRequest(String),
+    /// Controls from a role that may not use them
+    Forbidden,
+    Unauthorized,
+    NotFound,
+}
+
+/// Serves the dashboard and keeps its opportunity feed.
+pub struct Dashboard {
+    /// Accepted tokens and their roles
+    tokens: Vec<(String, Role)>,
+    controls: Option<Arc<Controls>>,
+    feed: Mutex<VecDeque<Opportunity>>,
+    sender: broadcast::Sender<Opportunity>,
+    /// Pages following `/events` right now
+    event_clients: AtomicUsize,
+}
+
+/// The request line and the token the client sent, if any.
+#[derive(Debug, Default)]
+struct Request {
+    line: String,
+    token: Option<String>,
+}
+
+impl Dashboard {
+    /// A dashboard asking for one of `tokens` on every request, or open
+    /// to viewers without any.
+    pub fn new(tokens: Vec<(String, Role)>) -> Self {
+        let (sender, _) = broadcast::channel(FEED_LEN);
+        Self { tokens, controls: None, feed: Mutex::new(VecDeque::new()), sender, event_clients: AtomicUsize::new(0) }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.dashboard_tokens.clone())
+    }
+
+    /// Offer pause, resume and close through `controls`.
+    pub fn with_controls(mut self, controls: Arc<Controls>) -> Self {
+        self.controls = Some(controls);
+        self
+    }
+
+    /// Add a detected opportunity to the feed and push it to open pages.
+    pub fn record_opportunity(&self, opportunity: &Opportunity) {
+        let mut feed = self.feed.lock().unwrap();
+        feed.push_front(opportunity.clone());
+        feed.truncate(FEED_LEN);
+        // No open pages is fine
+        let _ = self.sender.send(opportunity.clone());
+    }
+
+    /// The paper portfolio as last saved, with the opportunity feed.
+    pub fn snapshot(&self) -> Snapshot {
+        let engine = PaperTradingEngine::snapshot();
+        let summary = engine.summary();
+        let mut positions: Vec<PositionView> = engine
+            .portfolio
+            .positions
+            .values()
+            .map(|p| PositionView {
+                market: p.market.clone(),
+                platform: p.platform.clone(),
+                size: p.size,
+                avg_price: p.avg_price,
+                current_price: p.current_price,
+                unrealized_pnl: p.unrealized_pnl,
+            })
+            .collect();
+        positions.sort_by(|a, b| a.market.cmp(&b.market));
+        Snapshot {
+            at: Utc::now(),
+            paused: self.controls.as_ref().is_some_and(|c| c.is_paused()),
+            portfolio: PortfolioView {
+                total_value: summary.total_value,
+                cash_balance: summary.cash_balance,
+                net_pnl: summary.net_pnl,
+                pnl_percent: summary.pnl_percent,
+                session_pnl: summary.session_pnl,
+            },
+            positions,
+            opportunities: self.feed.lock().unwrap().iter().cloned().collect(),
+        }
+    }
+
+    /// Accept connections on `addr` until `breaker` trips or the listener
+    /// fails.
+    pub async fn serve(self: Arc<Self>, addr: &str, breaker: &CircuitBreaker) -> Result<()> {
+        let listener = TcpListener::bind(addr).await.with_context(|| format!("Failed to bind dashboard to {}", addr))?;
+        info!("📱 Dashboard on http://{}", addr);
+        loop {
+            let (stream, peer) = tokio::select! {
+                accepted = listener.accept() => accepted?,
+                _ = breaker.tripped() => return Ok(()),
+            };
+            let dashboard = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = dashboard.handle(stream).await {
+                    debug!("Dashboard connection from {} ended: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    async fn handle(&self, stream: TcpStream) -> Result<()> {
+        let mut reader = BufReader::new(stream);
+        let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader))
+            .await
+            .context("Request timed out")??;
+        let mut stream = reader.into_inner();
+
+        match self.route(&request.line, request.token.as_deref()) {
+            Route::Index => respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX_HTML).await,
+            Route::Snapshot => {
+                let body = serde_json::to_string(&self.snapshot())?;
+                respond(&mut stream, "200 OK", "application/json", &body).await
+            }
+            Route::Events => {
+                if self.event_clients.fetch_add(1, Ordering::SeqCst) >= MAX_EVENT_CLIENTS {
+                    self.event_clients.fetch_sub(1, Ordering::SeqCst);
+                    return respond(&mut stream, "503 Service Unavailable", "text/plain", "Too many open dashboards\n").await;
+                }
+                let result = self.stream_events(stream).await;
+                self.event_clients.fetch_sub(1, Ordering::SeqCst);
+                result
+            }
+            Route::Control(command, role) => {
+                let Some(controls) = &self.controls else {
+                    return respond(&mut stream, "404 Not Found", "text/plain", "Not found\n").await;
+                };
+                info!("📱 Dashboard command from a {}: {:?}", role, command);
+                match controls.execute(&command, &format!("dashboard:{}", role), &Permissions::new(role)) {
+                    Ok(reply) => respond(&mut stream, "200 OK", "text/plain; charset=utf-8", &reply).await,
+                    Err(e) => respond(&mut stream, "400 Bad Request", "text/plain; charset=utf-8", &e.to_string()).await,
+                }
+            }
+            Route::BadRequest(reason) => respond(&mut stream, "400 Bad Request", "text/plain", &reason).await,
+            Route::Forbidden => {
+                respond(&mut stream, "403 Forbidden", "text/plain", "This token's role may not use the controls\n").await
+            }
+            Route::Unauthorized => respond(&mut stream, "401 Unauthorized", "text/plain", "Missing or wrong token\n").await,
+            Route::NotFound => respond(&mut stream, "404 Not Found", "text/plain", "Not found\n").await,
+        }
+    }
+
+    /// Push a snapshot every few seconds and opportunities as they come,
+    /// until the page goes away.
+    async fn stream_events(&self, mut stream: TcpStream) -> Result<()> {
+        let mut receiver = self.sender.subscribe();
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n")
+            .await?;
+        let mut ticks = tokio::time::interval(SNAPSHOT_INTERVAL);
+        loop {
+            let event = tokio::select! {
+                _ = ticks.tick() => event("snapshot", &self.snapshot())?,
+                received = receiver.recv() => match received {
+                    Ok(opportunity) => event("opportunity", &opportunity)?,
+                    // The next snapshot carries the feed anyway
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return Ok(()),
+                },
+            };
+            stream.write_all(event.as_bytes()).await?;
+        }
+    }
+
+    /// Role of the caller holding `token`, `None` if it isn't accepted.
+    /// Every configured token is compared in full, so timing doesn't tell
+    /// how close a guess came.
+    fn role_of(&self, token: Option<&str>) -> Option<Role> {
+        if self.tokens.is_empty() {
+            return Some(Role::Viewer);
+        }
+        let given = Sha256::digest(token?.as_bytes());
+        let mut found = None;
+        for (accepted, role) in &self.tokens {
+            let expected = Sha256::digest(accepted.as_bytes());
+            let diff = given.iter().zip(expected.iter()).fold(0u8, |acc, (a, b)| acc | (a ^ b));
+            if diff == 0 {
+                found = Some(*role);
+            }
+        }
+        found
+    }
+
+    fn route(&self, request_line: &str, token: Option<&str>) -> Route {
+        let mut parts = request_line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Route::NotFound;
+        };
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let param = |name: &str| {
+            query.split('&').find_map(|pair| pair.strip_prefix(name)?.strip_prefix('=')).map(percent_decode)
+        };
+        // The page holds no data and asks for the token itself
+        if method == "GET" && matches!(path, "/" | "/index.html") {
+            return Route::Index;
+        }
+        let Some(role) = self.role_of(token) else {
+            return Route::Unauthorized;
+        };
+        let command = match (method, path) {
+            ("GET", "/api/snapshot") => return Route::Snapshot,
+            ("GET", "/events") => return Route::Events,
+            ("POST", "/api/pause") => Command::Pause,
+            ("POST", "/api/resume") => Command::Resume,
+            ("POST", "/api/close") => match param("market").filter(|m| !m.is_empty()) {
+                Some(market) => Command::Close(market),
+                None => return Route::BadRequest("Missing ?market=\n".to_string()),
+            },
+            _ => return Route::NotFound,
+        };
+        if command.action().is_some_and(|action| !Permissions::new(role).allows(action)) {
+            return Route::Forbidden;
+        }
+        Route::Control(command, role)
+    }
+}
+
+/// Decode `%XX` escapes and `+` in a query value.
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The request line and the token from the `Authorization` header or the
+/// token cookie; other headers are read and ignored.
+async fn read_request(reader: &mut BufReader<TcpStream>) -> Result<Request> {
+    let mut request = Request { line: read_line(reader).await?, token: None };
+    for _ in 0..MAX_HEADERS {
+        let header = read_line(reader).await?;
+        if header.trim().is_empty() {
+            return Ok(request);
+        }
+        if let Some(token) = header_token(&header) {
+            request.token = Some(token);
+        }
+    }
+    anyhow::bail!("Too many headers")
+}
+
+/// One line of at most [`MAX_LINE_BYTES`].
+async fn read_line(reader: &mut BufReader<TcpStream>) -> Result<String> {
+    let mut line = Vec::new();
+    let read = (&mut *reader).take(MAX_LINE_BYTES as u64).read_until(b'\n', &mut line).await?;
+    if read == MAX_LINE_BYTES && !line.ends_with(b"\n") {
+        anyhow::bail!("Line longer than {} bytes", MAX_LINE_BYTES);
+    }
+    Ok(String::from_utf8_lossy(&line).into_owned())
+}
+
+/// The token carried by an `Authorization: Bearer` or `Cookie` header.
+fn header_token(header: &str) -> Option<String> {
+    let (name, value) = header.split_once(':')?;
+    let value = value.trim();
+    match name.trim().to_ascii_lowercase().as_str() {
+        "authorization" => value.strip_prefix("Bearer ").map(|t| t.trim().to_string()),
+        "cookie" => value
+            .split(';')
+            .find_map(|c| c.trim().strip_prefix(TOKEN_COOKIE)?.strip_prefix('='))
+            .map(percent_decode),
+        _ => None,
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(stream.shutdown().await?)
+}
+
+/// One server-sent event.
+fn event(name: &str, data: &impl Serialize) -> Result<String> {
+    Ok(format!("event: {}\ndata: {}\n\n", name, serde_json::to_string(data)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fees::FeeSchedule;
+
+    #[test]
+    fn test_routes_require_the_token_and_the_feed_is_capped() {
+        let dashboard = Dashboard::new(vec![("s3cret".to_string(), Role::Trader), ("l00k".to_string(), Role::Viewer)]);
+        let trader = Some("s3cret");
+        // The page itself holds no data
+        assert_eq!(dashboard.route("GET / HTTP/1.1", None), Route::Index);
+        assert_eq!(dashboard.route("GET /events?x=1 HTTP/1.1", trader), Route::Events);
+        assert_eq!(dashboard.route("GET /api/snapshot HTTP/1.1", None), Route::Unauthorized);
+        assert_eq!(dashboard.route("GET /api/snapshot HTTP/1.1", Some("wrong")), Route::Unauthorized);
+        assert_eq!(dashboard.route("GET /api/snapshot HTTP/1.1", Some("s3cre")), Route::Unauthorized);
+        // Tokens in the URL are not accepted
+        assert_eq!(dashboard.route("GET /api/snapshot?token=s3cret HTTP/1.1", None), Route::Unauthorized);
+        assert_eq!(dashboard.route("GET /nope HTTP/1.1", trader), Route::NotFound);
+        assert_eq!(dashboard.route("POST / HTTP/1.1", trader), Route::NotFound);
+        assert_eq!(Dashboard::new(Vec::new()).route("GET /api/snapshot HTTP/1.1", None), Route::Snapshot);
+
+        // Controls are POSTs, refused to viewers and without tokens set
+        assert_eq!(dashboard.route("POST /api/pause HTTP/1.1", trader), Route::Control(Command::Pause, Role::Trader));
+        assert_eq!(
+            dashboard.route("POST /api/close?market=BTC%20100K+Dec HTTP/1.1", trader),
+            Route::Control(Command::Close("BTC 100K Dec".to_string()), Role::Trader)
+        );
+        assert_eq!(dashboard.route("GET /api/snapshot HTTP/1.1", Some("l00k")), Route::Snapshot);
+        assert_eq!(dashboard.route("POST /api/close?market=BTC HTTP/1.1", Some("l00k")), Route::Forbidden);
+        assert!(matches!(dashboard.route("POST /api/close HTTP/1.1", trader), Route::BadRequest(_)));
+        assert_eq!(dashboard.route("GET /api/pause HTTP/1.1", trader), Route::NotFound);
+        assert_eq!(Dashboard::new(Vec::new()).route("POST /api/resume HTTP/1.1", None), Route::Forbidden);
+
+        // The token comes from the Authorization header or the cookie
+        assert_eq!(header_token("Authorization: Bearer s3cret\r\n").as_deref(), Some("s3cret"));
+        assert_eq!(header_token("cookie: theme=dark; polybot_token=s3%2Bcret\r\n").as_deref(), Some("s3+cret"));
+        assert_eq!(header_token("Cookie: theme=dark\r\n"), None);
+        assert_eq!(header_token("Authorization: Basic czNjcmV0\r\n"), None);
+
+        let mut receiver = dashboard.sender.subscribe();
+        let fees = FeeSchedule::new();
+        for i in 0..FEED_LEN + 5 {
+            let market = format!("M{}", i);
+            let opp = Opportunity::priced(&market, ("polymarket", 0.40), ("kalshi", 0.50), &fees, Utc::now()).unwrap();
+            dashboard.record_opportunity(&opp);
+        }
+        let feed = dashboard.feed.lock().unwrap();
+        assert_eq!(feed.len(), FEED_LEN);
+        assert_eq!(feed[0].market, format!("M{}", FEED_LEN + 4));
+        assert_eq!(receiver.try_recv().unwrap().market, "M0");
+        let pushed = event("opportunity", &feed[0]).unwrap();
+        assert!(pushed.starts_with("event: opportunity\ndata: {") && pushed.ends_with("}\n\n"));
+    }
+
+    #[tokio::test]
+    async fn test_requests_read_the_token_and_cap_line_length() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let send = |bytes: Vec<u8>| async move {
+            let mut client = TcpStream::connect(addr).await.unwrap();
+            client.write_all(&bytes).await.unwrap();
+            client
+        };
+
+        let _client = send(b"GET /api/snapshot HTTP/1.1\r\nHost: x\r\nAuthorization: Bearer s3cret\r\n\r\n".to_vec()).await;
+        let (stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut BufReader::new(stream)).await.unwrap();
+        assert_eq!((request.line.trim(), request.token.as_deref()), ("GET /api/snapshot HTTP/1.1", Some("s3cret")));
+
+        let mut long = b"GET /".to_vec();
+        long.extend(std::iter::repeat_n(b'a', MAX_LINE_BYTES));
+        let _client = send(long).await;
+        let (stream, _) = listener.accept().await.unwrap();
+        assert!(read_request(&mut BufReader::new(stream)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serving_stops_when_the_breaker_trips() {
+        let breaker = Arc::new(CircuitBreaker::new());
+        let serving = tokio::spawn({
+            let breaker = breaker.clone();
+            async move { Arc::new(Dashboard::new(Vec::new())).serve("127.0.0.1:0", &breaker).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!serving.is_finished());
+
+        breaker.trip("test");
+        let served = tokio::time::timeout(Duration::from_secs(1), serving).await.unwrap().unwrap();
+        assert!(served.is_ok());
+    }
+}
@@ -0,0 +1,232 @@
+//! Price and percentage formatting.
+//!
+//! Prices are kept as probabilities (0.505) internally but can be shown as
+//! probabilities, cents (50.5¢) or percent (50.5%), with a configurable
+//! number of decimals and rounding mode. The TUI, logs, alerts and reports
+//! all format through here so the same price reads the same everywhere.
+//! Amounts of money go through [`crate::money::MoneyDisplay`] instead.
+
+use anyhow::Result;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::utils::timezone;
+
+static GLOBAL: OnceLock<PriceDisplay> = OnceLock::new();
+
+/// How prices are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceFormat {
+    /// 0.505
+    Probability,
+    /// 50.5¢
+    Cents,
+    /// 50.5%
+    Percent,
+}
+
+impl PriceFormat {
+    /// Decimals used when PRICE_DECIMALS is unset.
+    fn default_decimals(&self) -> usize {
+        match self {
+            PriceFormat::Probability => 3,
+            PriceFormat::Cents | PriceFormat::Percent => 1,
+        }
+    }
+}
+
+impl FromStr for PriceFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "probability" | "decimal" => PriceFormat::Probability,
+            "cents" => PriceFormat::Cents,
+            "percent" => PriceFormat::Percent,
+            other => anyhow::bail!("Unknown price format '{}' (expected probability, cents or percent)", other),
+        })
+    }
+}
+
+/// How values are rounded to the displayed decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Ties away from zero
+    HalfUp,
+    /// Ties to the even digit
+    HalfEven,
+    /// Towards negative infinity
+    Floor,
+    /// Towards positive infinity
+    Ceil,
+}
+
+impl FromStr for Rounding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "half_up" => Rounding::HalfUp,
+            "half_even" => Rounding::HalfEven,
+            "floor" => Rounding::Floor,
+            "ceil" => Rounding::Ceil,
+            other => anyhow::bail!("Unknown rounding '{}' (expected half_up, half_even, floor or ceil)", other),
+        })
+    }
+}
+
+impl Rounding {
+    /// Round `value` to `decimals` places.
+    pub fn round(&self, value: f64, decimals: usize) -> f64 {
+        let scale = 10f64.powi(decimals as i32);
+        // Snap float noise first so 0.505 * 100 counts as the tie it is
+        let scaled = (value * scale * 1e6).round() / 1e6;
+        let rounded = match self {
+            Rounding::HalfUp => scaled.round(),
+            Rounding::HalfEven => {
+                let floor = scaled.floor();
+                match scaled - floor {
+                    d if d < 0.5 => floor,
+                    d if d > 0.5 => floor + 1.0,
+                    _ if floor % 2.0 == 0.0 => floor,
+                    _ => floor + 1.0,
+                }
+            }
+            Rounding::Floor => scaled.floor(),
+            Rounding::Ceil => scaled.ceil(),
+        };
+        rounded / scale
+    }
+}
+
+/// Formats prices and percentages.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceDisplay {
+    pub format: PriceFormat,
+    /// Decimals for prices in the chosen format
+    pub price_decimals: usize,
+    /// Decimals for edges, returns and other percentages
+    pub percent_decimals: usize,
+    pub rounding: Rounding,
+}
+
+impl PriceDisplay {
+    /// Probabilities to 3 places, percentages to 2, ties away from zero.
+    pub fn new() -> Self {
+        Self { format: PriceFormat::Probability, price_decimals: 3, percent_decimals: 2, rounding: Rounding::HalfUp }
+    }
+
+    /// Show prices as `format` with its default decimals.
+    pub fn with_format(mut self, format: PriceFormat) -> Self {
+        self.format = format;
+        self.price_decimals = format.default_decimals();
+        self
+    }
+
+    pub fn with_price_decimals(mut self, decimals: usize) -> Self {
+        self.price_decimals = decimals;
+        self
+    }
+
+    pub fn with_percent_decimals(mut self, decimals: usize) -> Self {
+        self.percent_decimals = decimals;
+        self
+    }
+
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// PRICE_FORMAT, PRICE_DECIMALS, PERCENT_DECIMALS and PRICE_ROUNDING.
+    pub fn from_config(config: &Config) -> Self {
+        let mut display = Self::new().with_format(config.price_format).with_rounding(config.price_rounding);
+        if let Some(decimals) = config.price_decimals {
+            display = display.with_price_decimals(decimals);
+        }
+        display.with_percent_decimals(config.percent_decimals)
+    }
+
+    /// A price (or price difference such as a spread), e.g. "0.505",
+    /// "50.5¢" or "50.5%".
+    pub fn price(&self, price: f64) -> String {
+        let (value, unit) = match self.format {
+            PriceFormat::Probability => (price, ""),
+            PriceFormat::Cents => (price * 100.0, "¢"),
+            PriceFormat::Percent => (price * 100.0, "%"),
+        };
+        format!("{:.*}{}", self.price_decimals, self.rounding.round(value, self.price_decimals), unit)
+    }
+
+    /// A fraction as a percentage, e.g. 0.0234 -> "2.34%".
+    pub fn percent(&self, fraction: f64) -> String {
+        format!("{:.*}%", self.percent_decimals, self.rounding.round(fraction * 100.0, self.percent_decimals))
+    }
+
+    /// A percentage with its sign, e.g. "+2.34%".
+    pub fn signed_percent(&self, fraction: f64) -> String {
+        let rounded = self.rounding.round(fraction * 100.0, self.percent_decimals);
+        format!("{:+.*}%", self.percent_decimals, rounded)
+    }
+}
+
+impl Default for PriceDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Install the process-wide display settings from config, the clock
+/// timestamps are shown on included. Formatting before this uses the
+/// defaults.
+pub fn init(config: &Config) {
+    GLOBAL.set(PriceDisplay::from_config(config)).ok();
+    timezone::init(config);
+}
+
+pub fn global() -> &'static PriceDisplay {
+    GLOBAL.get_or_init(PriceDisplay::new)
+}
+
+/// A price in the configured format.
+pub fn price(price: f64) -> String {
+    global().price(price)
+}
+
+/// A fraction as a percentage in the configured precision.
+pub fn percent(fraction: f64) -> String {
+    global().percent(fraction)
+}
+
+/// A signed percentage in the configured precision.
+pub fn signed_percent(fraction: f64) -> String {
+    global().signed_percent(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_and_rounds_prices() {
+        let default = PriceDisplay::new();
+        assert_eq!(default.price(0.505), "0.505");
+        assert_eq!(default.percent(0.02345), "2.35%");
+        assert_eq!(default.signed_percent(-0.01), "-1.00%");
+
+        let cents = PriceDisplay::new().with_format(PriceFormat::Cents);
+        assert_eq!(cents.price(0.505), "50.5¢");
+        assert_eq!(cents.with_price_decimals(0).price(0.505), "51¢");
+        assert_eq!(cents.with_price_decimals(0).with_rounding(Rounding::HalfEven).price(0.505), "50¢");
+        assert_eq!(cents.with_price_decimals(0).with_rounding(Rounding::Floor).price(0.519), "51¢");
+        assert_eq!(PriceDisplay::new().with_format(PriceFormat::Percent).price(0.505), "50.5%");
+
+        assert_eq!(Rounding::HalfEven.round(0.125, 2), 0.12);
+        assert_eq!(Rounding::HalfUp.round(-0.125, 2), -0.13);
+        assert_eq!(Rounding::Ceil.round(0.1201, 2), 0.13);
+        assert_eq!("half-even".parse::<Rounding>().unwrap(), Rounding::HalfEven);
+        assert_eq!("CENTS".parse::<PriceFormat>().unwrap(), PriceFormat::Cents);
+        assert!("basis".parse::<PriceFormat>().is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! Venue fee schedules.
+//!
+//! All fee math lives here so the detector, executor and paper engine agree
+//! on what a trade really costs:
+//!
+//! - Kalshi charges `rate x contracts x P x (1 - P)` per order, rounded up
+//!   to the cent (taker 7%, maker 1.75% by default).
+//! - Polymarket charges `bps / 10_000 x min(P, 1 - P) x shares`; most
+//!   markets are fee-free, and neg-risk markets have their own rates.
+//!
+//! Rates can be overridden per venue or per market prefix. Prefixes match
+//! each leg's native ID (the Kalshi ticker or Polymarket token ID); matched
+//! pair names are resolved to their legs.
+
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::config::Config;
+
+/// Which side of the book an order takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// Maker/taker rates in the venue's own unit (Kalshi: fraction of
+/// `P x (1 - P)`, Polymarket: basis points of `min(P, 1 - P)`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rates {
+    pub maker: f64,
+    pub taker: f64,
+}
+
+impl Rates {
+    fn get(&self, liquidity: Liquidity) -> f64 {
+        match liquidity {
+            Liquidity::Maker => self.maker,
+            Liquidity::Taker => self.taker,
+        }
+    }
+}
+
+/// Rates for one venue, optionally limited to markets with a prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeOverride {
+    /// "kalshi", "polymarket" or "polymarket_neg_risk"
+    pub venue: String,
+    /// Prefix of the Kalshi ticker or Polymarket token ID; empty matches
+    /// every market on the venue
+    pub market_prefix: String,
+    pub rates: Rates,
+}
+
+/// Fee schedule for both venues.
+#[derive(Debug, Clone)]
+pub struct FeeSchedule {
+    kalshi: Rates,
+    polymarket: Rates,
+    polymarket_neg_risk: Rates,
+    /// Polymarket tokens that use neg-risk rates, shared between clones
+    neg_risk_markets: Arc<RwLock<HashSet<String>>>,
+    overrides: Vec<FeeOverride>,
+    /// Native ID of each matched pair's leg by (venue, pair name), shared
+    /// between clones so every caller sees the current pairs
+    legs: Arc<RwLock<HashMap<(String, String), String>>>,
+}
+
+impl FeeSchedule {
+    /// Published default rates.
+    pub fn new() -> Self {
+        Self {
+            kalshi: Rates { maker: 0.0175, taker: 0.07 },
+            polymarket: Rates { maker: 0.0, taker: 0.0 },
+            polymarket_neg_risk: Rates { maker: 0.0, taker: 0.0 },
+            neg_risk_markets: Arc::new(RwLock::new(HashSet::new())),
+            overrides: Vec::new(),
+            legs: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Defaults plus overrides from `FEE_OVERRIDES`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut schedule = Self::new();
+        for entry in config.fee_overrides.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            schedule.overrides.push(parse_override(entry)?);
+        }
+        Ok(schedule)
+    }
+
+    /// Override rates for a venue, or for its markets starting with
+    /// `market_prefix`. The longest matching prefix wins.
+    pub fn with_override(mut self, venue: &str, market_prefix: &str, rates: Rates) -> Self {
+        self.overrides.push(FeeOverride {
+            venue: venue.to_string(),
+            market_prefix: market_prefix.to_string(),
+            rates,
+        });
+        self
+    }
+
+    /// Charge neg-risk rates on a Polymarket token.
+    pub fn with_neg_risk_market(self, token_id: &str) -> Self {
+        self.set_neg_risk(token_id, true);
+        self
+    }
+
+    /// Record whether a Polymarket token trades on the neg-risk exchange.
+    pub fn set_neg_risk(&self, token_id: &str, neg_risk: bool) {
+        let mut markets = self.neg_risk_markets.write().unwrap();
+        if neg_risk {
+            markets.insert(token_id.to_string());
+        } else {
+            markets.remove(token_id);
+        }
+    }
+
+    /// Resolve matched pair names to the native IDs of their legs.
+    pub fn set_pairs<'a>(&self, pairs: impl IntoIterator<Item = &'a MatchedMarket>) {
+        let mut legs = self.legs.write().unwrap();
+        legs.clear();
+        for pair in pairs {
+            for leg in [&pair.polymarket, &pair.kalshi] {
+                legs.insert((leg.venue.as_str().to_string(), pair.name.clone()), leg.native.clone());
+            }
+        }
+    }
+
+    /// Native ID of the leg on `venue` of the matched pair named `pair`.
+    pub fn native_id(&self, venue: &str, pair: &str) -> Option<String> {
+        self.legs.read().unwrap().get(&(venue.to_string(), pair.to_string())).cloned()
+    }
+
+    /// Rates that apply to a market, given as its native ID or the name of a
+    /// matched pair.
+    pub fn rates(&self, venue: &str, market: &str) -> Rates {
+        let native = self.native_id(venue, market);
+        let market = native.as_deref().unwrap_or(market);
+        let venue_key = if venue == "polymarket" && self.neg_risk_markets.read().unwrap().contains(market) {
+            "polymarket_neg_risk"
+        } else {
+            venue
+        };
+        let overridden = self.overrides
+            .iter()
+            .filter(|o| o.venue == venue_key && market.starts_with(&o.market_prefix))
+            .max_by_key(|o| o.market_prefix.len());
+        if let Some(o) = overridden {
+            return o.rates;
+        }
+        match venue_key {
+            "kalshi" => self.kalshi,
+            "polymarket_neg_risk" => self.polymarket_neg_risk,
+            _ => self.polymarket,
+        }
+    }
+
+    /// Unrounded fee for one contract at `price`.
+    pub fn per_contract(&self, venue: &str, market: &str, liquidity: Liquidity, price: f64) -> f64 {
+        let rate = self.rates(venue, market).get(liquidity);
+        match venue {
+            "kalshi" => rate * price * (1.0 - price),
+            _ => rate / 10_000.0 * price.min(1.0 - price),
+        }
+    }
+
+    /// Fee for one order of `contracts` at `price`.
+    pub fn fee(&self, venue: &str, market: &str, liquidity: Liquidity, contracts: f64, price: f64) -> f64 {
+        self.order_fee(venue, market, liquidity, [(price, contracts)])
+    }
+
+    /// Fee for one order filled across several `(price, contracts)` levels.
+    pub fn order_fee(
+        &self,
+        venue: &str,
+        market: &str,
+        liquidity: Liquidity,
+        fills: impl IntoIterator<Item = (f64, f64)>,
+    ) -> f64 {
+        let fee: f64 = fills
+            .into_iter()
+            .map(|(price, contracts)| self.per_contract(venue, market, liquidity, price) * contracts)
+            .sum();
+        match venue {
+            // Kalshi rounds each order's fee up to the next cent
+            "kalshi" => (fee * 100.0 - 1e-9).ceil().max(0.0) / 100.0,
+            _ => fee,
+        }
+    }
+
+    /// Entry plus exit fees for a position opened at `entry` and closed at
+    /// `exit` (use `exit = 1.0` or `0.0` for holding to settlement, which is
+    /// fee-free on both venues).
+    pub fn round_trip(
+        &self,
+        venue: &str,
+        market: &str,
+        liquidity: Liquidity,
+        contracts: f64,
+        entry: f64,
+        exit: f64,
+    ) -> f64 {
+        self.fee(venue, market, liquidity, contracts, entry)
+            + self.fee(venue, market, liquidity, contracts, exit)
+    }
+
+    /// Taker fees on both legs of an arb held to settlement.
+    pub fn arb_fee(
+        &self,
+        market: &str,
+        (yes_venue, yes_price): (&str, f64),
+        (no_venue, no_price): (&str, f64),
+        contracts: f64,
+    ) -> f64 {
+        self.fee(yes_venue, market, Liquidity::Taker, contracts, yes_price)
+            + self.fee(no_venue, market, Liquidity::Taker, contracts, no_price)
+    }
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse `venue[:market_prefix]=taker[/maker]`, e.g. `kalshi:KXBTCD=0.035/0`.
+fn parse_override(entry: &str) -> Result<FeeOverride> {
+    let (target, rates) = entry
+        .split_once('=')
+        .with_context(|| format!("Invalid fee override '{}': expected venue=taker/maker", entry))?;
+    let (venue, market_prefix) = target.split_once(':').unwrap_or((target, ""));
+    let (taker, maker) = rates.split_once('/').unwrap_or((rates, rates));
+    let parse = |v: &str| v.trim().parse::<f64>()
+        .with_context(|| format!("Invalid fee rate '{}' in '{}'", v, entry));
+    Ok(FeeOverride {
+        venue: venue.trim().to_lowercase(),
+        market_prefix: market_prefix.trim().to_string(),
+        rates: Rates { taker: parse(taker)?, maker: parse(maker)? },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kalshi_fee_rounds_up_per_order() {
+        let fees = FeeSchedule::new();
+        // 0.07 x 100 x 0.5 x 0.5 = $1.75 exactly
+        assert!((fees.fee("kalshi", "KXBTCD", Liquidity::Taker, 100.0, 0.50) - 1.75).abs() < 1e-9);
+        // 0.07 x 1 x 0.4 x 0.6 = $0.0168 -> 2 cents
+        assert!((fees.fee("kalshi", "KXBTCD", Liquidity::Taker, 1.0, 0.40) - 0.02).abs() < 1e-9);
+        // Maker: 0.0175 x 10 x 0.4 x 0.6 = $0.042 -> 5 cents
+        assert!((fees.fee("kalshi", "KXBTCD", Liquidity::Maker, 10.0, 0.40) - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_overrides_and_round_trips() {
+        let fees = FeeSchedule::new()
+            .with_override("polymarket", "", Rates { maker: 0.0, taker: 200.0 })
+            .with_override("kalshi", "KXETH", Rates { maker: 0.0, taker: 0.035 })
+            .with_override("polymarket_neg_risk", "", Rates { maker: 0.0, taker: 100.0 })
+            .with_neg_risk_market("election");
+
+        // 200 bps of min(0.3, 0.7) on 100 shares = $0.60, each way at 0.3/0.7
+        let rt = fees.round_trip("polymarket", "BTC", Liquidity::Taker, 100.0, 0.30, 0.70);
+        assert!((rt - 1.20).abs() < 1e-9);
+        assert!((fees.fee("polymarket", "election", Liquidity::Taker, 100.0, 0.30) - 0.30).abs() < 1e-9);
+
+        // Prefix override halves Kalshi's rate for ETH only
+        assert_eq!(fees.rates("kalshi", "KXETHD-26").taker, 0.035);
+        assert_eq!(fees.rates("kalshi", "KXBTCD-26").taker, 0.07);
+
+        // Settlement is free, so holding an arb costs only the entry fees
+        let arb = fees.arb_fee("KXBTCD", ("polymarket", 0.45), ("kalshi", 0.50), 10.0);
+        let expected = fees.round_trip("polymarket", "KXBTCD", Liquidity::Taker, 10.0, 0.45, 1.0)
+            + fees.round_trip("kalshi", "KXBTCD", Liquidity::Taker, 10.0, 0.50, 0.0);
+        assert!((arb - expected).abs() < 1e-9);
+        assert!((arb - (0.09 + 0.18)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_override() {
+        let o = parse_override("kalshi:KXBTCD=0.035/0").unwrap();
+        assert_eq!((o.venue.as_str(), o.market_prefix.as_str()), ("kalshi", "KXBTCD"));
+        assert_eq!(o.rates, Rates { maker: 0.0, taker: 0.035 });
+        assert_eq!(parse_override("polymarket=50").unwrap().rates, Rates { maker: 50.0, taker: 50.0 });
+        assert!(parse_override("kalshi").is_err());
+        assert!(parse_override("kalshi=cheap").is_err());
+    }
+
+    #[test]
+    fn test_pair_names_resolve_to_native_ids() {
+        use crate::utils::market_id::MarketId;
+
+        let fees = FeeSchedule::new()
+            .with_override("kalshi", "KXBTCD", Rates { maker: 0.0, taker: 0.035 })
+            .with_override("polymarket_neg_risk", "", Rates { maker: 0.0, taker: 100.0 })
+            .with_neg_risk_market("12345");
+        let shared = fees.clone();
+        shared.set_pairs(&[MatchedMarket {
+            name: "BTC above 100k".to_string(),
+            polymarket: MarketId::polymarket("12345"),
+            kalshi: MarketId::kalshi("KXBTCD-26DEC31-B100000"),
+        }]);
+
+        // The override is keyed by ticker, not by the pair's name
+        assert_eq!(fees.rates("kalshi", "BTC above 100k").taker, 0.035);
+        assert_eq!(fees.rates("polymarket", "BTC above 100k").taker, 100.0);
+        assert_eq!(fees.rates("kalshi", "KXBTCD-26DEC31-B100000").taker, 0.035);
+        assert_eq!(fees.rates("kalshi", "ETH above 5k").taker, 0.07);
+
+        // Neg-risk flags found later reach every clone
+        shared.set_neg_risk("12345", false);
+        assert_eq!(fees.rates("polymarket", "BTC above 100k").taker, 0.0);
+    }
+}
@@ -1,19 +1,28 @@
 //! Kalshi REST API client.
 //!
-//! Handles all HTTP communication with Kalshi's trading API.
-//! Uses RSA-PSS signature-based authentication.
+//! Handles all HTTP communication with Kalshi's trading API. Portfolio
+//! requests are signed with the API key's RSA private key (see [`auth`](super::auth)).
 
 use anyhow::{Context, Result};
 use reqwest::Client;
-use serde::Deserialize;
 use tracing::{debug, info, warn};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
 
+use super::auth::KalshiSigner;
 use super::types::*;
 use crate::config::Config;
+use crate::utils::fixtures::{self, FixtureStore};
+use crate::utils::throttle::{self, OrderAction};
+use crate::utils::http_pool::PreparedRequest;
+use crate::utils::{endpoints, http_pool, raw_log, tolerant, OpenOrder, OpenOrders, SelfTradePolicy, VenueConstraints};
 
 /// Base URL for Kalshi API (production - new endpoint).
-const KALSHI_API_URL: &str = "https://api.elections.kalshi.com/trade-api/v2";
+pub const KALSHI_API_URL: &str = "https://api.elections.kalshi.com/trade-api/v2";
+
+/// Active API base URL; differs from [`KALSHI_API_URL`] after a failover.
+fn api_url() -> String {
+    endpoints::base(endpoints::KALSHI, KALSHI_API_URL)
+}
 
 /// Kalshi API client with RSA-PSS authentication.
 pub struct KalshiClient {
@@ -21,57 +30,92 @@ pub struct KalshiClient {
     http: Client,
     /// API Key ID
     api_key_id: Option<String>,
-    /// API Secret (for HMAC or simpler auth if available)
-    api_secret: Option<String>,
+    /// Signs portfolio requests; needs the key ID and its private key
+    signer: Option<Arc<KalshiSigner>>,
     /// Email for legacy login (deprecated)
     email: Option<String>,
     /// Password for legacy login (deprecated)
     password: Option<String>,
     /// Whether in dry-run mode
     dry_run: bool,
+    /// Record/replay store for responses
+    fixtures: Option<Arc<FixtureStore>>,
+    /// Resting orders, persisted across restarts
+    open_orders: Option<Arc<Mutex<OpenOrders>>>,
+    /// What to do when an order would cross our own resting order
+    self_trade: SelfTradePolicy,
 }
 
 impl KalshiClient {
     /// Create a new Kalshi client from configuration.
     pub fn new(config: &Config) -> Result<Self> {
-        let http = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to create HTTP client")?;
+        let http = http_pool::client();
 
         Ok(Self {
             http,
             api_key_id: config.kalshi_api_key.clone(),
-            api_secret: config.kalshi_api_secret.clone(),
+            signer: Self::signer(config.kalshi_api_key.as_deref(), config.kalshi_api_secret.as_deref())?,
             email: config.kalshi_email.clone(),
             password: config.kalshi_password.clone(),
             dry_run: config.dry_run,
+            fixtures: FixtureStore::from_config(config)?,
+            open_orders: None,
+            self_trade: config.self_trade_policy,
         })
     }
 
-    /// Get current timestamp in milliseconds.
-    fn current_timestamp_ms() -> u64 {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_millis() as u64
+    /// Signer for a key ID and private key, when both are set.
+    fn signer(api_key: Option<&str>, private_key: Option<&str>) -> Result<Option<Arc<KalshiSigner>>> {
+        match (api_key, private_key) {
+            (Some(id), Some(key)) if !key.is_empty() => Ok(Some(Arc::new(KalshiSigner::new(id, key)?))),
+            _ => Ok(None),
+        }
     }
 
-    /// Generate authentication headers for API requests.
-    /// 
-    /// Note: Full RSA-PSS signing requires the private key file.
-    /// For now, we'll use a simpler approach if API key/secret is available.
-    fn auth_headers(&self, _method: &str, _path: &str) -> Option<Vec<(&'static str, String)>> {
-        let api_key = self.api_key_id.as_ref()?;
-        let timestamp = Self::current_timestamp_ms().to_string();
-        
-        // Basic API key auth headers
-        Some(vec![
-            ("KALSHI-ACCESS-KEY", api_key.clone()),
-            ("KALSHI-ACCESS-TIMESTAMP", timestamp),
-            // Note: Full implementation needs RSA-PSS signature
-            // ("KALSHI-ACCESS-SIGNATURE", signature),
-        ])
+    /// Act as a different account, e.g. one whose fills are followed.
+    pub fn with_api_key(mut self, api_key: &str, api_secret: Option<&str>) -> Result<Self> {
+        self.api_key_id = Some(api_key.to_string());
+        self.signer = Self::signer(Some(api_key), api_secret)?;
+        self.email = None;
+        self.password = None;
+        Ok(self)
+    }
+
+    /// Track resting orders in a persistent store.
+    pub fn with_open_orders(mut self, store: Arc<Mutex<OpenOrders>>) -> Self {
+        self.open_orders = Some(store);
+        self
+    }
+
+    /// Log orders instead of sending them, or send them, whatever DRY_RUN
+    /// says: for a strategy with its own mode.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Use a specific fixture store for recording or replaying responses.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.fixtures = Some(store);
+        self
+    }
+
+    /// GET a URL through the fixture layer.
+    async fn get_text(&self, url: &str) -> Result<(u16, String)> {
+        fixtures::fetch_text(&self.http, self.fixtures.as_deref(), url).await
+    }
+
+    /// Signed auth headers for a portfolio request.
+    fn auth_headers(&self, method: &str, url: &str) -> Result<Vec<(String, String)>> {
+        let signer = self.signer.as_ref()
+            .context("Kalshi private key (KALSHI_API_SECRET) required to sign portfolio requests")?;
+        signer.headers(method, url, chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Signed GET of a portfolio URL through the fixture layer.
+    async fn get_signed(&self, url: &str) -> Result<(u16, String)> {
+        let headers = self.auth_headers("GET", url)?;
+        fixtures::fetch_text_with_headers(&self.http, self.fixtures.as_deref(), url, &headers).await
     }
 
     /// Authenticate/login is not needed with API key auth.
@@ -105,19 +149,13 @@ impl KalshiClient {
 
     /// Get exchange status (public endpoint, no auth required).
     pub async fn get_exchange_status(&self) -> Result<String> {
-        let url = format!("{}/exchange/status", KALSHI_API_URL);
+        let url = format!("{}/exchange/status", api_url());
         
-        let response = self.http
-            .get(&url)
-            .send()
+        let (status, text) = self.get_text(&url)
             .await
             .context("Failed to fetch exchange status")?;
-
-        let status = response.status();
-        let text = response.text().await
-            .context("Failed to read exchange status response")?;
         
-        if status.is_success() {
+        if (200..300).contains(&status) {
             Ok(text)
         } else {
             anyhow::bail!("Exchange status check failed: {}", text)
@@ -128,88 +166,247 @@ impl KalshiClient {
     pub async fn get_events(&self, limit: Option<i32>) -> Result<Vec<KalshiEvent>> {
         let url = format!(
             "{}/events?limit={}&status=open",
-            KALSHI_API_URL,
+            api_url(),
             limit.unwrap_or(100)
         );
         
         debug!("Fetching Kalshi events");
 
-        let response = self.http
-            .get(&url)
-            .send()
+        let (_, text) = self.get_text(&url)
+            .await
+            .context("Failed to fetch events")?;
+
+        let events_value: serde_json::Value = tolerant::parse_field("kalshi events", &text, "events")?;
+        let events: Vec<KalshiEvent> = tolerant::parse_list("kalshi event", events_value);
+
+        info!("Fetched {} Kalshi events", events.len());
+        Ok(events)
+    }
+
+    /// Get one page of events, optionally in one series and with their
+    /// markets nested. Returns the events and the cursor for the next page,
+    /// if any.
+    pub async fn get_events_page(
+        &self,
+        series_ticker: Option<&str>,
+        with_markets: bool,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<KalshiEvent>, Option<String>)> {
+        let mut url = format!("{}/events?status=open&limit=200", api_url());
+        if let Some(series) = series_ticker {
+            url.push_str(&format!("&series_ticker={}", series));
+        }
+        if with_markets {
+            url.push_str("&with_nested_markets=true");
+        }
+        if let Some(c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+
+        let (status, text) = self.get_text(&url)
             .await
             .context("Failed to fetch events")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi events request failed ({}): {}", status, text);
+        }
+
+        let mut page: serde_json::Value = tolerant::parse("kalshi events page", &text)?;
+        let events = tolerant::parse_list("kalshi event", page["events"].take());
+        let next = page["cursor"].as_str().filter(|c| !c.is_empty()).map(str::to_string);
+        Ok((events, next))
+    }
+
+    /// Get every open event in a series, following cursors.
+    pub async fn get_series_events(&self, series_ticker: &str, with_markets: bool) -> Result<Vec<KalshiEvent>> {
+        let mut events = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let (page, next) = self.get_events_page(Some(series_ticker), with_markets, cursor.as_deref()).await?;
+            events.extend(page);
+            match next {
+                Some(c) => cursor = Some(c),
+                None => break,
+            }
+        }
+
+        debug!("Fetched {} open events for series {}", events.len(), series_ticker);
+        Ok(events)
+    }
+
+    /// List series, optionally in one category (e.g. "Crypto").
+    pub async fn get_series_list(&self, category: Option<&str>) -> Result<Vec<KalshiSeries>> {
+        let mut url = format!("{}/series", api_url());
+        if let Some(category) = category {
+            url.push_str(&format!("?category={}", category));
+        }
 
-        #[derive(Deserialize)]
-        struct EventsResponse {
-            events: Vec<KalshiEvent>,
+        let (status, text) = self.get_text(&url)
+            .await
+            .context("Failed to fetch series")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi series request failed ({}): {}", status, text);
         }
 
-        let events_resp: EventsResponse = response
-            .json()
+        let series: serde_json::Value = tolerant::parse_field("kalshi series list", &text, "series")?;
+        Ok(tolerant::parse_list("kalshi series", series))
+    }
+
+    /// Get one series by ticker.
+    pub async fn get_series(&self, series_ticker: &str) -> Result<KalshiSeries> {
+        let url = format!("{}/series/{}", api_url(), series_ticker);
+
+        let (status, text) = self.get_text(&url)
+            .await
+            .context("Failed to fetch series")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi series request failed ({}): {}", status, text);
+        }
+
+        tolerant::parse_field("kalshi series", &text, "series")
+    }
+
+    /// Get an event with all of its markets (e.g. the strike ladder for one
+    /// expiry) in one call.
+    pub async fn get_event(&self, event_ticker: &str) -> Result<KalshiEvent> {
+        let url = format!("{}/events/{}?with_nested_markets=true", api_url(), event_ticker);
+
+        debug!("Fetching Kalshi event {}", event_ticker);
+
+        let (status, text) = self.get_text(&url)
             .await
-            .context("Failed to parse events response")?;
+            .context("Failed to fetch event")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi event request failed: {}", text);
+        }
+
+        let mut value: serde_json::Value = tolerant::parse("kalshi event", &text)?;
+        let mut event: KalshiEvent = tolerant::from_value("kalshi event", value["event"].take())?;
+        // Without nesting the markets come alongside the event
+        if event.markets.is_empty() {
+            event.markets = tolerant::parse_list("kalshi market", value["markets"].take());
+        }
+        if event.ticker.is_empty() {
+            event.ticker = event_ticker.to_string();
+        }
+        Ok(event)
+    }
+
+    /// Get every open market in a series (e.g. "KXBTCD"), following cursors.
+    pub async fn get_series_markets(&self, series_ticker: &str) -> Result<Vec<KalshiMarket>> {
+        let mut markets = Vec::new();
+        let mut cursor: Option<String> = None;
 
-        info!("Fetched {} Kalshi events", events_resp.events.len());
-        Ok(events_resp.events)
+        loop {
+            let mut url = format!(
+                "{}/markets?series_ticker={}&status=open&limit=200",
+                api_url(), series_ticker
+            );
+            if let Some(c) = &cursor {
+                url.push_str(&format!("&cursor={}", c));
+            }
+
+            let (_, text) = self.get_text(&url)
+                .await
+                .context("Failed to fetch series markets")?;
+            let mut page: serde_json::Value = tolerant::parse("kalshi markets page", &text)?;
+            markets.extend(tolerant::parse_list::<KalshiMarket>("kalshi market", page["markets"].take()));
+            match page["cursor"].as_str() {
+                Some(c) if !c.is_empty() => cursor = Some(c.to_string()),
+                _ => break,
+            }
+        }
+
+        debug!("Fetched {} open markets for series {}", markets.len(), series_ticker);
+        Ok(markets)
+    }
+
+    /// Get up to `limit` open markets across all series, following cursors.
+    pub async fn get_open_markets(&self, limit: usize) -> Result<Vec<KalshiMarket>> {
+        let mut markets = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        while markets.len() < limit {
+            let mut url = format!(
+                "{}/markets?status=open&limit={}",
+                api_url(),
+                (limit - markets.len()).min(1000)
+            );
+            if let Some(c) = &cursor {
+                url.push_str(&format!("&cursor={}", c));
+            }
+
+            let (_, text) = self.get_text(&url)
+                .await
+                .context("Failed to fetch open markets")?;
+            let mut page: serde_json::Value = tolerant::parse("kalshi markets page", &text)?;
+            let batch = tolerant::parse_list::<KalshiMarket>("kalshi market", page["markets"].take());
+            if batch.is_empty() {
+                break;
+            }
+            markets.extend(batch);
+            match page["cursor"].as_str() {
+                Some(c) if !c.is_empty() => cursor = Some(c.to_string()),
+                _ => break,
+            }
+        }
+
+        debug!("Fetched {} open Kalshi markets", markets.len());
+        Ok(markets)
     }
 
     /// Get a specific market by ticker.
     pub async fn get_market(&self, ticker: &str) -> Result<KalshiMarket> {
-        let url = format!("{}/markets/{}", KALSHI_API_URL, ticker);
+        let url = format!("{}/markets/{}", api_url(), ticker);
         
         debug!("Fetching Kalshi market {}", ticker);
 
-        let response = self.http
-            .get(&url)
-            .send()
+        let (status, text) = self.get_text(&url)
             .await
             .context("Failed to fetch market")?;
-
-        let status = response.status();
-        let text = response.text().await
-            .context("Failed to read market response body")?;
         
         debug!("Kalshi market response ({}): {}", status, &text[..text.len().min(500)]);
 
-        if !status.is_success() {
+        if !(200..300).contains(&status) {
             anyhow::bail!("Kalshi market request failed: {}", text);
         }
 
-        #[derive(Deserialize)]
-        struct MarketResponse {
-            market: KalshiMarket,
-        }
+        tolerant::parse_field("kalshi market", &text, "market")
+    }
 
-        let market_resp: MarketResponse = serde_json::from_str(&text)
-            .context("Failed to parse market response")?;
+    /// Candles of `ticker` between two Unix timestamps, `period_minutes`
+    /// long (1, 60 or 1440). The series is the ticker's prefix, e.g.
+    /// "KXBTCD" for "KXBTCD-26JAN05-T100000".
+    pub async fn get_candlesticks(&self, ticker: &str, start_ts: i64, end_ts: i64, period_minutes: u32) -> Result<Vec<KalshiCandlestick>> {
+        let series = ticker.split('-').next().unwrap_or(ticker);
+        let url = format!(
+            "{}/series/{}/markets/{}/candlesticks?start_ts={}&end_ts={}&period_interval={}",
+            api_url(), series, ticker, start_ts, end_ts, period_minutes
+        );
 
-        Ok(market_resp.market)
+        debug!("Fetching Kalshi candles for {}", ticker);
+
+        let (status, text) = self.get_text(&url)
+            .await
+            .context("Failed to fetch candlesticks")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi candlestick request failed ({}): {}", status, text);
+        }
+
+        let candles: serde_json::Value = tolerant::parse_field("kalshi candlesticks", &text, "candlesticks")?;
+        Ok(tolerant::parse_list("kalshi candlestick", candles))
     }
 
     /// Get order book for a market.
     pub async fn get_orderbook(&self, ticker: &str) -> Result<KalshiOrderBook> {
-        let url = format!("{}/markets/{}/orderbook", KALSHI_API_URL, ticker);
+        let url = format!("{}/markets/{}/orderbook", api_url(), ticker);
         
         debug!("Fetching Kalshi orderbook for {}", ticker);
 
-        let response = self.http
-            .get(&url)
-            .send()
+        let (_, text) = self.get_text(&url)
             .await
             .context("Failed to fetch orderbook")?;
 
-        #[derive(Deserialize)]
-        struct OrderBookResponse {
-            orderbook: KalshiOrderBook,
-        }
-
-        let book_resp: OrderBookResponse = response
-            .json()
-            .await
-            .context("Failed to parse orderbook response")?;
-
-        Ok(book_resp.orderbook)
+        tolerant::parse_field("kalshi orderbook", &text, "orderbook")
     }
 
     /// Get best prices for a market (converted to 0.0-1.0 scale).
@@ -223,18 +420,42 @@ impl KalshiClient {
         Ok((yes_bid, yes_ask))
     }
 
-    /// Place an order.
-    pub async fn place_order(&self, order: KalshiOrderRequest) -> Result<KalshiOrderResponse> {
-        // For now, orders require full RSA-PSS auth which we don't have yet
-        if !self.api_key_id.is_some() {
+    /// Validate an order and build the request [`place_order`](Self::place_order)
+    /// would send, without sending it.
+    pub fn prepare_order(&self, order: &KalshiOrderRequest) -> Result<PreparedRequest> {
+        let mut request = self.unsigned_order(order)?;
+        request.headers.extend(self.auth_headers("POST", &request.url)?);
+        Ok(request)
+    }
+
+    /// The order request before it is signed, all a dry run needs: it
+    /// works with just KALSHI_API_KEY.
+    fn unsigned_order(&self, order: &KalshiOrderRequest) -> Result<PreparedRequest> {
+        if self.api_key_id.is_none() {
             anyhow::bail!("API key required for placing orders");
         }
 
+        let constraints = VenueConstraints::kalshi();
+        match order.yes_price {
+            Some(cents) => constraints.check(cents as f64 / 100.0, order.count as f64)?,
+            None if order.count < 1 => anyhow::bail!("kalshi: count {} below minimum 1", order.count),
+            None => {}
+        }
+
+        let url = format!("{}/portfolio/orders", api_url());
+        let headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        Ok(PreparedRequest { method: "POST".to_string(), url, headers, body: serde_json::to_string(order)? })
+    }
+
+    /// Place an order.
+    pub async fn place_order(&self, order: KalshiOrderRequest) -> Result<KalshiOrderResponse> {
         if self.dry_run {
+            let request = self.unsigned_order(&order)?;
             info!(
                 "DRY RUN: Would place {} {} order for {} contracts on {}",
                 order.action, order.side, order.count, order.ticker
             );
+            debug!("DRY RUN: {} {} {}", request.method, request.url, request.body);
             return Ok(KalshiOrderResponse {
                 order_id: Some("DRY_RUN_ORDER".to_string()),
                 status: Some("filled".to_string()),
@@ -242,23 +463,38 @@ impl KalshiClient {
             });
         }
 
-        let url = format!("{}/portfolio/orders", KALSHI_API_URL);
+        let request = self.prepare_order(&order)?;
+        let side = format!("{} {}", order.action, order.side);
+        let price = order.yes_price.map(|c| c as f64 / 100.0);
+        self.prevent_self_trade(&order.ticker, &side, price).await?;
 
-        // TODO: Add proper RSA-PSS signature auth headers here
-        let response = self.http
-            .post(&url)
-            .json(&order)
-            .send()
+        throttle::acquire("kalshi", OrderAction::Place)?;
+
+        let response = http_pool::send(request.to_builder(&self.http))
             .await
             .context("Failed to place order")?;
+        let status = response.status().as_u16();
+        let text = response.text().await.context("Failed to read order response")?;
+        raw_log::record("POST", &request.url, Some(&request.body), status, &text);
 
-        let order_resp: KalshiOrderResponse = response
-            .json()
-            .await
+        let order_resp: KalshiOrderResponse = serde_json::from_str(&text)
             .context("Failed to parse order response")?;
 
         if order_resp.order_id.is_some() {
             info!("Kalshi order placed: {:?}", order_resp.order_id);
+            if let (Some(store), Some(id)) = (&self.open_orders, &order_resp.order_id) {
+                if order_resp.status.as_deref() == Some("resting") {
+                    store.lock().unwrap().add(OpenOrder {
+                        order_id: id.clone(),
+                        venue: "kalshi".to_string(),
+                        market: order.ticker.clone(),
+                        side,
+                        price: price.unwrap_or(0.0),
+                        size: order.count as f64,
+                        placed_at: chrono::Utc::now(),
+                    });
+                }
+            }
         } else {
             warn!("Kalshi order failed: {:?}", order_resp.error);
         }
@@ -266,55 +502,169 @@ impl KalshiClient {
         Ok(order_resp)
     }
 
+    /// Cancel a resting order.
+    pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
+        if self.api_key_id.is_none() {
+            anyhow::bail!("API key required for cancelling orders");
+        }
+
+        if self.dry_run {
+            info!("DRY RUN: Would cancel Kalshi order {}", order_id);
+            return Ok(true);
+        }
+
+        throttle::acquire("kalshi", OrderAction::Cancel)?;
+
+        let url = format!("{}/portfolio/orders/{}", api_url(), order_id);
+
+        let mut request = self.http.delete(&url);
+        for (name, value) in self.auth_headers("DELETE", &url)? {
+            request = request.header(name, value);
+        }
+        let response = http_pool::send(request)
+            .await
+            .context("Failed to cancel order")?;
+        let success = response.status().is_success();
+        let status = response.status().as_u16();
+        raw_log::record("DELETE", &url, None, status, &response.text().await.unwrap_or_default());
+
+        if success {
+            info!("Kalshi order {} cancelled", order_id);
+            if let Some(store) = &self.open_orders {
+                store.lock().unwrap().remove(order_id);
+            }
+        } else {
+            warn!("Failed to cancel Kalshi order {}", order_id);
+        }
+
+        Ok(success)
+    }
+
+    /// Check a new order against our resting orders on the market and, per
+    /// the self-trade policy, cancel the ones it would cross or refuse it.
+    async fn prevent_self_trade(&self, ticker: &str, side: &str, price: Option<f64>) -> Result<()> {
+        let crossing = match (&self.open_orders, self.self_trade) {
+            (Some(store), policy) if policy != SelfTradePolicy::Off => {
+                store.lock().unwrap().crossing("kalshi", ticker, side, price)
+            }
+            _ => return Ok(()),
+        };
+        for resting in crossing {
+            warn!(
+                "Self-trade: {} on {} would cross our {} @ {} ({})",
+                side, ticker, resting.side, resting.price, resting.order_id
+            );
+            if self.self_trade == SelfTradePolicy::Skip {
+                anyhow::bail!("Self-trade prevented: would cross our resting order {}", resting.order_id);
+            }
+            if !self.cancel_order(&resting.order_id).await? {
+                anyhow::bail!("Self-trade prevented: could not cancel our resting order {}", resting.order_id);
+            }
+        }
+        Ok(())
+    }
+
     /// Get current positions.
     pub async fn get_positions(&self) -> Result<Vec<KalshiPosition>> {
-        if !self.api_key_id.is_some() {
+        if self.api_key_id.is_none() {
             anyhow::bail!("API key required for fetching positions");
         }
 
-        let url = format!("{}/portfolio/positions", KALSHI_API_URL);
+        let url = format!("{}/portfolio/positions", api_url());
 
-        // TODO: Add proper RSA-PSS signature auth headers here
-        let response = self.http
-            .get(&url)
-            .send()
+        let (_, text) = self.get_signed(&url)
             .await
             .context("Failed to fetch positions")?;
 
-        #[derive(Deserialize)]
-        struct PositionsResponse {
-            positions: Vec<KalshiPosition>,
+        let positions: serde_json::Value = tolerant::parse_field("kalshi positions", &text, "positions")?;
+        Ok(tolerant::parse_list("kalshi position", positions))
+    }
+
+    /// Get one page of our fills at or after `min_ts` (unix seconds).
+    /// Returns the fills and the cursor for the next page, if any.
+    pub async fn get_fills(&self, min_ts: i64, cursor: Option<&str>) -> Result<(Vec<KalshiFill>, Option<String>)> {
+        if self.api_key_id.is_none() {
+            anyhow::bail!("API key required for fetching fills");
         }
 
-        let positions_resp: PositionsResponse = response
-            .json()
+        let mut url = format!("{}/portfolio/fills?min_ts={}&limit=100", api_url(), min_ts);
+        if let Some(c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+
+        let (status, text) = self.get_signed(&url)
             .await
-            .context("Failed to parse positions response")?;
+            .context("Failed to fetch fills")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi fills request failed ({}): {}", status, text);
+        }
 
-        Ok(positions_resp.positions)
+        let mut page: serde_json::Value = tolerant::parse("kalshi fills page", &text)?;
+        let fills = tolerant::parse_list("kalshi fill", page["fills"].take());
+        let next = page["cursor"].as_str().filter(|c| !c.is_empty()).map(str::to_string);
+        Ok((fills, next))
+    }
+
+    /// IDs of our orders still resting on the exchange.
+    pub async fn get_resting_order_ids(&self) -> Result<Vec<String>> {
+        if self.api_key_id.is_none() {
+            anyhow::bail!("API key required for fetching orders");
+        }
+
+        let mut ids = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut url = format!("{}/portfolio/orders?status=resting&limit=200", api_url());
+            if let Some(c) = &cursor {
+                url.push_str(&format!("&cursor={}", c));
+            }
+            let (status, text) = self.get_signed(&url)
+                .await
+                .context("Failed to fetch orders")?;
+            if !(200..300).contains(&status) {
+                anyhow::bail!("Kalshi orders request failed ({}): {}", status, text);
+            }
+
+            let page: serde_json::Value = tolerant::parse("kalshi orders page", &text)?;
+            ids.extend(
+                page["orders"].as_array().into_iter().flatten()
+                    .filter_map(|o| o["order_id"].as_str().map(str::to_string)),
+            );
+            cursor = page["cursor"].as_str().filter(|c| !c.is_empty()).map(str::to_string);
+            if cursor.is_none() {
+                return Ok(ids);
+            }
+        }
+    }
+
+    /// Forget restored open orders that are no longer resting on Kalshi.
+    /// Returns how many were dropped.
+    pub async fn reconcile_open_orders(&self) -> Result<usize> {
+        let Some(store) = &self.open_orders else { return Ok(0) };
+        let resting = self.get_resting_order_ids().await?;
+        let dropped = store.lock().unwrap().reconcile("kalshi", &resting);
+        for order in &dropped {
+            info!("Kalshi order {} on {} is no longer resting", order.order_id, order.market);
+        }
+        Ok(dropped.len())
     }
 
     /// Get account balance.
     pub async fn get_balance(&self) -> Result<KalshiBalance> {
-        if !self.api_key_id.is_some() {
+        if self.api_key_id.is_none() {
             anyhow::bail!("API key required for fetching balance");
         }
 
-        let url = format!("{}/portfolio/balance", KALSHI_API_URL);
+        let url = format!("{}/portfolio/balance", api_url());
 
-        // TODO: Add proper RSA-PSS signature auth headers here
-        let response = self.http
-            .get(&url)
-            .send()
+        let (status, text) = self.get_signed(&url)
             .await
             .context("Failed to fetch balance")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Kalshi balance request failed ({}): {}", status, text);
+        }
 
-        let balance: KalshiBalance = response
-            .json()
-            .await
-            .context("Failed to parse balance response")?;
-
-        Ok(balance)
+        tolerant::parse("kalshi balance", &text)
     }
 
     /// Check if authenticated (API key configured).
@@ -327,3 +677,37 @@ impl KalshiClient {
         self.api_key_id.is_some() || (self.email.is_some() && self.password.is_some())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_dry_run_orders_need_no_private_key() {
+        let client = KalshiClient {
+            http: http_pool::client(),
+            api_key_id: Some("key-id".to_string()),
+            signer: None,
+            email: None,
+            password: None,
+            dry_run: true,
+            fixtures: None,
+            open_orders: None,
+            self_trade: SelfTradePolicy::default(),
+        };
+        let order = || KalshiOrderRequest {
+            ticker: "KXBTC-1".to_string(),
+            side: "yes".to_string(),
+            action: "buy".to_string(),
+            count: 10,
+            yes_price: Some(45),
+            order_type: "limit".to_string(),
+        };
+        let placed = client.place_order(order()).await.unwrap();
+        assert_eq!(placed.order_id.as_deref(), Some("DRY_RUN_ORDER"));
+
+        // Sending it for real has to be signed
+        let err = client.with_dry_run(false).place_order(order()).await.unwrap_err();
+        assert!(err.to_string().contains("KALSHI_API_SECRET"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Kalshi fill polling.
+//!
+//! Polls `/portfolio/fills` with a timestamp watermark and page cursors,
+//! feeding our fills into the same [`FillRouter`] as the Polymarket user
+//! channel. The watermark is persisted so a restart neither misses fills
+//! nor re-applies old ones. Kalshi lists fills newest first, so every page
+//! since the watermark is read before any fill is applied, oldest first.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{debug, warn};
+
+use super::client::KalshiClient;
+use super::types::KalshiFill;
+use crate::utils::{state, Fill, FillRouter};
+
+/// Default watermark location.
+pub const KALSHI_FILLS_FILE: &str = "data/state/kalshi_fills.json";
+
+const VENUE: &str = "kalshi";
+
+/// Convert a Kalshi fill. The market is "<ticker> YES" / "<ticker> NO" so
+/// the two sides of a ticker are tracked as separate positions.
+pub fn to_fill(fill: &KalshiFill) -> Fill {
+    Fill {
+        venue: VENUE.to_string(),
+        order_id: fill.order_id.clone(),
+        trade_id: fill.trade_id.clone(),
+        market: format!("{} {}", fill.ticker, fill.side.to_uppercase()),
+        side: fill.action.to_uppercase(),
+        price: fill.price(),
+        size: fill.count as f64,
+        filled_at: DateTime::parse_from_rfc3339(&fill.created_time)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now()),
+    }
+}
+
+/// Every page of fills at or after `since`.
+pub(crate) async fn fetch_since(client: &KalshiClient, since: i64) -> Result<Vec<KalshiFill>> {
+    let mut fills = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let (page, next) = client.get_fills(since, cursor.as_deref()).await?;
+        let empty = page.is_empty();
+        fills.extend(page);
+        match next {
+            Some(c) if !empty => cursor = Some(c),
+            _ => break,
+        }
+    }
+    Ok(fills)
+}
+
+/// Fills oldest first, ties broken by trade ID, so a watermark advances
+/// through them in order.
+pub(crate) fn oldest_first(fills: &[KalshiFill]) -> Vec<(&KalshiFill, Fill)> {
+    let mut sorted: Vec<_> = fills.iter().map(|f| (f, to_fill(f))).collect();
+    sorted.sort_by(|(_, a), (_, b)| (a.filled_at, &a.trade_id).cmp(&(b.filled_at, &b.trade_id)));
+    sorted
+}
+
+/// Persisted poll position.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct Watermark {
+    /// Newest fill time seen (unix seconds)
+    pub since: i64,
+    /// Trade IDs at exactly `since`, already applied
+    trade_ids: HashSet<String>,
+}
+
+impl Watermark {
+    /// Start from now.
+    pub fn now() -> Self {
+        Self {
+            since: Utc::now().timestamp(),
+            trade_ids: HashSet::new(),
+        }
+    }
+
+    /// Resume from `path`, or from now if nothing is saved there.
+    pub fn load(path: &str) -> Self {
+        let saved: Watermark = state::load(path);
+        if saved.since > 0 { saved } else { Self::now() }
+    }
+
+    /// Advance past a fill. False if it is at or before the watermark and
+    /// was already seen.
+    pub fn admit(&mut self, fill: &Fill) -> bool {
+        let ts = fill.filled_at.timestamp();
+        if ts < self.since || (ts == self.since && self.trade_ids.contains(&fill.trade_id)) {
+            return false;
+        }
+        if ts > self.since {
+            self.since = ts;
+            self.trade_ids.clear();
+        }
+        self.trade_ids.insert(fill.trade_id.clone());
+        true
+    }
+}
+
+/// Polls Kalshi for new fills.
+pub struct FillPoller {
+    watermark: Watermark,
+    file_path: Option<String>,
+}
+
+impl FillPoller {
+    /// Start from now; earlier fills are assumed already reflected.
+    pub fn new() -> Self {
+        Self {
+            watermark: Watermark::now(),
+            file_path: None,
+        }
+    }
+
+    /// Resume from the watermark saved at `path`, or from now if none.
+    pub fn load(path: &str) -> Self {
+        Self {
+            watermark: Watermark::load(path),
+            file_path: Some(path.to_string()),
+        }
+    }
+
+    /// Apply a batch of fills in any order, oldest first, skipping any at
+    /// or before the watermark that were already applied. Returns how many
+    /// were new.
+    pub fn apply(&mut self, fills: &[KalshiFill], router: &FillRouter) -> usize {
+        let mut applied = 0;
+        for (_, fill) in oldest_first(fills) {
+            if !self.watermark.admit(&fill) {
+                continue;
+            }
+
+            let order_id = fill.order_id.clone();
+            if router.on_fill(fill) {
+                applied += 1;
+            }
+            // Kalshi has no order-closed event here; close once fully filled
+            if router.open_order(&order_id).is_some_and(|o| router.filled_size(&order_id) >= o.size) {
+                router.on_order_closed(VENUE, &order_id, "filled");
+            }
+        }
+        applied
+    }
+
+    /// Fetch every page of fills since the watermark, then apply them.
+    pub async fn poll(&mut self, client: &KalshiClient, router: &FillRouter) -> Result<usize> {
+        let fills = fetch_since(client, self.watermark.since).await?;
+        let applied = self.apply(&fills, router);
+        if applied > 0 {
+            debug!("Applied {} Kalshi fills", applied);
+            self.save();
+        }
+        Ok(applied)
+    }
+
+    fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.watermark) {
+                warn!("Failed to persist Kalshi fill watermark: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for FillPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{OpenOrder, OpenOrders, PositionTracker};
+    use std::sync::{Arc, Mutex};
+
+    fn fill(trade_id: &str, count: i32, time: &str) -> KalshiFill {
+        KalshiFill {
+            trade_id: trade_id.to_string(),
+            order_id: "ord-1".to_string(),
+            ticker: "KXBTCD-26JAN05-T100000".to_string(),
+            side: "no".to_string(),
+            action: "buy".to_string(),
+            count,
+            yes_price: 58,
+            no_price: 42,
+            is_taker: true,
+            created_time: time.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_applies_new_fills_once_and_closes_filled_orders() {
+        let mut orders = OpenOrders::new();
+        orders.add(OpenOrder {
+            order_id: "ord-1".to_string(),
+            venue: "kalshi".to_string(),
+            market: "KXBTCD-26JAN05-T100000".to_string(),
+            side: "buy no".to_string(),
+            price: 0.42,
+            size: 10.0,
+            placed_at: Utc::now(),
+        });
+        let orders = Arc::new(Mutex::new(orders));
+        let router = FillRouter::new(Arc::new(PositionTracker::new())).with_open_orders(orders.clone());
+
+        let mut poller = FillPoller::new();
+        poller.watermark.since = 0;
+        let first = [fill("t1", 4, "2026-01-05T15:00:00Z")];
+        assert_eq!(poller.apply(&first, &router), 1);
+        assert_eq!(orders.lock().unwrap().len(), 1, "partially filled order stays open");
+
+        // The next poll re-reads fills at the watermark second
+        let second = [fill("t1", 4, "2026-01-05T15:00:00Z"), fill("t2", 6, "2026-01-05T15:00:03Z")];
+        assert_eq!(poller.apply(&second, &router), 1);
+        assert!(orders.lock().unwrap().is_empty(), "fully filled order is closed");
+
+        let position = router.positions().get_position("kalshi", "KXBTCD-26JAN05-T100000 NO").unwrap();
+        assert_eq!(position.quantity, 10);
+        assert!((position.avg_price - 0.42).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_newest_first_pages_apply_every_fill() {
+        let router = FillRouter::new(Arc::new(PositionTracker::new()));
+        let mut poller = FillPoller::new();
+        poller.watermark.since = 0;
+
+        // As Kalshi lists them: newest first, across two pages read before applying
+        let page_one = [fill("t4", 1, "2026-01-05T15:00:09Z"), fill("t3", 2, "2026-01-05T15:00:05Z")];
+        let page_two = [fill("t2", 3, "2026-01-05T15:00:05Z"), fill("t1", 4, "2026-01-05T15:00:01Z")];
+        let fills: Vec<KalshiFill> = page_one.into_iter().chain(page_two).collect();
+        assert_eq!(poller.apply(&fills, &router), 4);
+        assert_eq!(poller.watermark.since, DateTime::parse_from_rfc3339("2026-01-05T15:00:09Z").unwrap().timestamp());
+
+        let position = router.positions().get_position("kalshi", "KXBTCD-26JAN05-T100000 NO").unwrap();
+        assert_eq!(position.quantity, 10);
+
+        // The next poll re-reads the newest second: nothing is applied twice
+        assert_eq!(poller.apply(&fills[..1], &router), 0);
+    }
+}
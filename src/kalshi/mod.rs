@@ -2,7 +2,10 @@
 //!
 //! Provides client for interacting with Kalshi's REST API.
 
+pub mod auth;
 pub mod client;
+pub mod fill_stream;
 pub mod types;
 
 pub use client::KalshiClient;
+pub use fill_stream::FillPoller;
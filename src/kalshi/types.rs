@@ -2,15 +2,62 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::money::Money;
+
+/// Source a series settles on.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettlementSource {
+    pub name: String,
+    pub url: String,
+}
+
+/// Kalshi series: a recurring question (e.g. "KXBTCD", Bitcoin daily
+/// close) whose events are its individual expiries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KalshiSeries {
+    /// Series ticker (e.g., "KXBTCD")
+    pub ticker: String,
+    pub title: String,
+    /// How often a new event opens ("daily", "hourly", "weekly", ...)
+    pub frequency: String,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub settlement_sources: Vec<SettlementSource>,
+    /// Contract terms document
+    pub contract_url: Option<String>,
+    /// Fee structure ("quadratic", "flat", ...)
+    pub fee_type: Option<String>,
+    /// Multiplier on the standard fee
+    pub fee_multiplier: Option<f64>,
+}
+
 /// Kalshi market event.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct KalshiEvent {
     /// Event ticker (e.g., "INXW-24JAN10-B5175")
+    #[serde(alias = "event_ticker")]
     pub ticker: String,
+    /// Series this event belongs to
+    #[serde(default)]
+    pub series_ticker: Option<String>,
     /// Event title
     pub title: String,
+    /// Subtitle, e.g. the expiry ("Jan 10, 2024")
+    #[serde(default)]
+    pub sub_title: Option<String>,
     /// Category
     pub category: Option<String>,
+    /// At most one market of the event can resolve YES
+    #[serde(default)]
+    pub mutually_exclusive: Option<bool>,
+    /// When the event's strikes are observed (ISO 8601)
+    #[serde(default)]
+    pub strike_date: Option<String>,
+    /// Observation window instead of a date, e.g. "week"
+    #[serde(default)]
+    pub strike_period: Option<String>,
     /// Markets under this event
     #[serde(default)]
     pub markets: Vec<KalshiMarket>,
@@ -99,6 +146,12 @@ pub struct KalshiMarket {
     /// Can close early
     #[serde(default)]
     pub can_close_early: Option<bool>,
+    /// How the strikes apply: "greater", "less", "between", ...
+    #[serde(default)]
+    pub strike_type: Option<String>,
+    /// Floor strike
+    #[serde(default)]
+    pub floor_strike: Option<f64>,
     /// Cap strike
     #[serde(default)]
     pub cap_strike: Option<f64>,
@@ -149,6 +202,15 @@ pub struct KalshiOrderRequest {
     pub order_type: String,
 }
 
+impl KalshiOrderRequest {
+    /// Limit price of the traded side (0.0-1.0), if any.
+    pub fn side_price(&self) -> Option<f64> {
+        let cents = self.yes_price?;
+        let cents = if self.side.eq_ignore_ascii_case("no") { 100 - cents } else { cents };
+        Some(cents as f64 / 100.0)
+    }
+}
+
 /// Kalshi order response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KalshiOrderResponse {
@@ -191,6 +253,68 @@ pub struct KalshiPosition {
     pub realized_pnl: Option<f64>,
 }
 
+/// One of our fills (`/portfolio/fills`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KalshiFill {
+    pub trade_id: String,
+    pub order_id: String,
+    pub ticker: String,
+    /// "yes" or "no"
+    pub side: String,
+    /// "buy" or "sell"
+    pub action: String,
+    pub count: i32,
+    /// Prices in cents
+    pub yes_price: i32,
+    pub no_price: i32,
+    pub is_taker: bool,
+    /// RFC 3339
+    pub created_time: String,
+}
+
+impl KalshiFill {
+    /// Price paid for the traded side (0.0-1.0).
+    pub fn price(&self) -> f64 {
+        let cents = if self.side.eq_ignore_ascii_case("no") { self.no_price } else { self.yes_price };
+        cents as f64 / 100.0
+    }
+}
+
+/// Open, high, low and close of one candle, in cents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KalshiOhlc {
+    pub open: Option<i32>,
+    pub high: Option<i32>,
+    pub low: Option<i32>,
+    pub close: Option<i32>,
+}
+
+/// One candle of a market's history (`/candlesticks`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KalshiCandlestick {
+    /// Unix seconds the period ends at
+    pub end_period_ts: i64,
+    pub yes_bid: KalshiOhlc,
+    pub yes_ask: KalshiOhlc,
+    /// Traded YES prices; empty in periods without trades
+    pub price: KalshiOhlc,
+    pub volume: i64,
+}
+
+impl KalshiCandlestick {
+    /// YES value at the close of the period (0.0-1.0): the mid of the
+    /// closing bid and ask, or the last trade when a side is missing.
+    pub fn close(&self) -> Option<f64> {
+        match (self.yes_bid.close, self.yes_ask.close) {
+            (Some(bid), Some(ask)) if bid > 0 && ask > 0 => Some((bid + ask) as f64 / 200.0),
+            _ => self.price.close.map(|c| c as f64 / 100.0),
+        }
+    }
+}
+
 /// Kalshi balance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KalshiBalance {
@@ -199,3 +323,37 @@ pub struct KalshiBalance {
     /// Pending balance
     pub pending: Option<i64>,
 }
+
+impl KalshiBalance {
+    /// Available balance as USD.
+    pub fn available(&self) -> Money {
+        Money::from_cents(self.balance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_and_event_taxonomy_parse() {
+        let series: KalshiSeries = serde_json::from_str(
+            r#"{"ticker":"KXBTCD","title":"Bitcoin daily","frequency":"daily","category":"Crypto",
+                "tags":["BTC"],"settlement_sources":[{"name":"CF Benchmarks","url":"https://cfbenchmarks.com"}],
+                "fee_type":"quadratic","fee_multiplier":1}"#,
+        )
+        .unwrap();
+        assert_eq!((series.frequency.as_str(), series.tags.len()), ("daily", 1));
+        assert_eq!(series.settlement_sources[0].name, "CF Benchmarks");
+
+        // The events endpoints key the ticker as event_ticker
+        let event: KalshiEvent = serde_json::from_str(
+            r#"{"event_ticker":"KXBTCD-26JAN05","series_ticker":"KXBTCD","title":"Bitcoin price",
+                "sub_title":"Jan 5","category":"Crypto","mutually_exclusive":false}"#,
+        )
+        .unwrap();
+        assert_eq!(event.ticker, "KXBTCD-26JAN05");
+        assert_eq!(event.series_ticker.as_deref(), Some("KXBTCD"));
+        assert!(event.markets.is_empty());
+    }
+}
@@ -1,10 +1,14 @@
-use anyhow::Result;
-
 pub fn add(left: usize, right: usize) -> usize {
     left + right
 }
 
 pub mod config;
+pub mod config_schema;
+pub mod app_state;
+pub mod chain;
+pub mod fees;
+pub mod money;
+pub mod display;
 pub mod polymarket;
 pub mod kalshi;
 pub mod arbitrage;
@@ -13,3 +17,11 @@ pub mod strategies;
 pub mod analysis;
 pub mod paper_trading;
 pub mod tui;
+pub mod notify;
+pub mod monitor;
+pub mod risk;
+pub mod approval;
+pub mod dashboard;
+pub mod backtest;
+pub mod rehearsal;
+pub mod control;
@@ -1,23 +1,49 @@
-mod config;
-mod polymarket;
-mod kalshi;
-mod arbitrage;
-mod utils;
-
 use anyhow::Result;
+use std::sync::{Arc, Mutex};
 use tracing::{info, warn, Level};
 use tracing_subscriber::FmtSubscriber;
 
-use config::Config;
-use polymarket::PolymarketClient;
-use kalshi::KalshiClient;
-use arbitrage::{ArbitrageDetector, MarketMatcher};
-use utils::CircuitBreaker;
+use polymarket_kalshi_arbitrage_bot::approval::{ExecutionQueue, IntentQueue, INTENTS_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::attribution::{Attribution, ATTRIBUTION_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::capture::{CaptureLog, CAPTURE_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::execution::{ExecutionLog, EXECUTIONS_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::heatmap::{Heatmap, HEATMAP_FILE};
+use polymarket_kalshi_arbitrage_bot::chain::{missing_allowances, TxManager};
+use polymarket_kalshi_arbitrage_bot::app_state::{AppState, ENV_FILE};
+use polymarket_kalshi_arbitrage_bot::config_schema;
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::fees::FeeSchedule;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::backtest::tape::{QuoteTape, QUOTE_TAPE_FILE};
+use polymarket_kalshi_arbitrage_bot::arbitrage::market_matcher::{MatchedMarket, MATCHED_MARKETS_FILE};
+use polymarket_kalshi_arbitrage_bot::arbitrage::{ArbitrageDetector, MarketMatcher, MoveDetector, SeriesUniverse};
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::BOOKS_FILE;
+use polymarket_kalshi_arbitrage_bot::paper_trading::session::SESSION_FILE;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{shadow, PaperTradingEngine, Portfolio, Profile, Session};
+use polymarket_kalshi_arbitrage_bot::control::Controls;
+use polymarket_kalshi_arbitrage_bot::dashboard::Dashboard;
+use polymarket_kalshi_arbitrage_bot::notify::{Alert, Notifier, Severity};
+use polymarket_kalshi_arbitrage_bot::arbitrage::cooldown::Cooldowns;
+use polymarket_kalshi_arbitrage_bot::risk::derisk::{Derisker, DERISK_FILE};
+use polymarket_kalshi_arbitrage_bot::risk::trade_limits::{TradeLimits, TRADE_COUNTS_FILE};
+use polymarket_kalshi_arbitrage_bot::risk::{ConflictGuard, InventorySkew, PreTradeRisk};
+use polymarket_kalshi_arbitrage_bot::risk::discrepancies::{DiscrepancyLedger, DISCREPANCIES_FILE};
+use polymarket_kalshi_arbitrage_bot::strategies::StrategyManager;
+use polymarket_kalshi_arbitrage_bot::utils::audit_log::AUDIT_FILE;
+use polymarket_kalshi_arbitrage_bot::utils::circuit_breaker::BREAKER_FILE;
+use polymarket_kalshi_arbitrage_bot::utils::open_orders::OPEN_ORDERS_FILE;
+use polymarket_kalshi_arbitrage_bot::utils::http_pool;
+use polymarket_kalshi_arbitrage_bot::utils::throttle;
+use polymarket_kalshi_arbitrage_bot::utils::{encryption, endpoints, raw_log, state, run_info, AuditLog, CircuitBreaker, FillRouter, OpenOrders, PositionTracker, Supervisor};
+
+mod tasks;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize logging
-    let _subscriber = FmtSubscriber::builder()
+    FmtSubscriber::builder()
         .with_max_level(Level::DEBUG)
         .with_target(false)
         .with_thread_ids(false)
@@ -29,70 +55,309 @@ async fn main() -> Result<()> {
     info!("🚀 Starting Polymarket-Kalshi Arbitrage Bot");
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-    // Load configuration
+    // Load configuration, refusing misspelled settings
+    config_schema::check_environment(ENV_FILE)?;
     let config = Config::from_env()?;
+    raw_log::init(&config);
+    display::init(&config);
+    http_pool::init(&config)?;
+    throttle::init(&config);
+    endpoints::init(&config);
+    info!("🪪 {}", run_info::init(&config).summary());
     
-    if config.dry_run {
+    if config.dry_run && config.strategy_dry_run.iter().all(|(_, dry_run)| *dry_run) {
         info!("⚠️  DRY RUN MODE - No real trades will be executed");
+    } else if config.dry_run {
+        info!("⚠️  DRY RUN MODE - Only strategies set live in STRATEGY_DRY_RUN trade");
     }
 
-    info!("📊 Min Profit Threshold: {:.2}%", config.min_profit_threshold * 100.0);
+    info!("📊 Min Profit Threshold: {}", display::percent(config.min_profit_threshold));
     info!("💰 Max Position Size: ${}", config.max_position_size);
+    if config.is_semi_auto() {
+        info!("✋ SEMI-AUTO MODE - Order intents wait for approval ({}s to decide)", config.intent_ttl_secs);
+    }
 
-    // Initialize Safety
-    let circuit_breaker = CircuitBreaker::new();
+    // Fee overrides, strategy schedules, notification routes and the paper
+    // profile are validated up front so a typo fails fast
+    let fees = FeeSchedule::from_config(&config)?;
+    let notifier = Arc::new(Notifier::new(&config)?);
+    let profile = Profile::from_config(&config)?;
+    if encryption::check()? {
+        info!("🔐 Encrypting data files at rest");
+    }
+    // Settings the running tasks read, updated when the config file is edited
+    let app = Arc::new(AppState::new(config.clone()));
+    let strategy_manager = Arc::new(StrategyManager::from_config(&config)?);
+    if let Some(schedule) = strategy_manager.schedule("arbitrage") {
+        info!("⏰ Arbitrage schedule: {} ({})", schedule.expr(), config.schedule_timezone);
+    }
+    for (name, dry_run) in strategy_manager.dry_run_overrides() {
+        info!("{} {}", name, if dry_run { "⚠️  dry-running: its orders are only logged" } else { "🚀 trading live" });
+    }
+    for name in strategy_manager.shadowed() {
+        info!("🕶️  {} in shadow mode: orders fill virtually into the {} paper profile", name, shadow::profile(name)?.name);
+    }
+
+    // Warm start: restore persisted state so a restart picks up where it left off
+    let circuit_breaker = Arc::new(CircuitBreaker::load(BREAKER_FILE));
+    let breaker_state = circuit_breaker.state();
+    if breaker_state.tripped {
+        warn!(
+            "🛑 Circuit breaker still tripped since {} ({}) - delete {} to resume",
+            breaker_state.tripped_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            breaker_state.reason.unwrap_or_default(),
+            BREAKER_FILE
+        );
+    }
+    let open_orders = Arc::new(Mutex::new(OpenOrders::load(OPEN_ORDERS_FILE)));
+    let heatmap = Arc::new(Mutex::new(Heatmap::load(HEATMAP_FILE)));
+    // Live and shadow executions side by side, for capture_report
+    let capture = Arc::new(Mutex::new(CaptureLog::load(CAPTURE_FILE)));
+    // Detections, executions and failures per pair, for the attribution report
+    let attribution = Arc::new(Mutex::new(Attribution::load(ATTRIBUTION_FILE)));
+    // Decision, submitted and fill prices of live orders, for the executions scorecard
+    let executions = Arc::new(Mutex::new(ExecutionLog::load(EXECUTIONS_FILE)));
+    let intents = Arc::new(Mutex::new(IntentQueue::load(INTENTS_FILE)));
+    // Markets we just traded sit out detection and execution for a while
+    let cooldowns = Arc::new(Cooldowns::new());
+    let discrepancies = Arc::new(DiscrepancyLedger::new(DISCREPANCIES_FILE));
+    for entry in discrepancies.open_entries() {
+        warn!("🚨 Discrepancy {} on {} awaits acknowledgement: {}", entry.id, entry.market, entry.summary);
+    }
+    let derisker = Derisker::from_config(&config)?.map(|d| Arc::new(Mutex::new(d.with_state_file(DERISK_FILE))));
+    if let Some(derisker) = &derisker {
+        let derisker = derisker.lock().unwrap();
+        if derisker.is_derisked() {
+            warn!(
+                "📉 De-risked since {}: max position size ${:.2} until the drawdown recovers",
+                derisker.state().derisked_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                derisker.max_size(config.max_position_size)
+            );
+        }
+    }
+    // Daily caps on trades per strategy and orders overall, checked with
+    // the other limits before each live order
+    let mut pretrade = PreTradeRisk::new(TradeLimits::from_config(&config).with_state_file(TRADE_COUNTS_FILE))
+        .with_conflicts(ConflictGuard::from_config(&config)?)
+        .with_question_limit(config.max_question_loss)
+        .with_executions(executions.clone());
+    if let Some(skew) = InventorySkew::from_config(&config) {
+        pretrade = pretrade.with_inventory(skew);
+    }
+    let pretrade = Arc::new(pretrade);
+    // Every order goes out through one prioritised queue
+    let execution = Arc::new(ExecutionQueue::new(config.execution_workers));
+    info!(
+        "♻️  Restored {} open paper positions, {} open orders, {} recorded opportunities",
+        Portfolio::load(&profile.portfolio_file).map(|p| p.position_count()).unwrap_or(0),
+        open_orders.lock().unwrap().len(),
+        heatmap.lock().unwrap().total_count()
+    );
+
+    // P&L since this start is tracked apart from the portfolio's all-time
+    // P&L, and each run is summarised into the audit log when it ends
+    let audit = Arc::new(AuditLog::new(AUDIT_FILE));
+    if let Some(previous) = Session::load(SESSION_FILE) {
+        // Left behind by a run that didn't shut down cleanly
+        audit.record_session(&previous.summary());
+    }
+    let session = {
+        let snapshot = PaperTradingEngine::snapshot_of(&profile);
+        let trades = snapshot.trade_log.get_all().len();
+        Session::start(&profile.name, &snapshot.portfolio, trades, chrono::Utc::now())
+    };
+    session.save(SESSION_FILE)?;
+    let session = Arc::new(Mutex::new(session));
+
+    // Fills from the venue user streams update positions, open orders and the audit log
+    let fills = Arc::new(
+        FillRouter::new(Arc::new(PositionTracker::new()))
+            .with_open_orders(open_orders.clone())
+            .with_audit(audit.clone())
+            .with_discrepancies(discrepancies.clone())
+            .with_executions(executions.clone())
+            .with_capture(capture.clone()),
+    );
 
     // Initialize Polymarket client
     info!("🔌 Connecting to Polymarket...");
-    let poly_client = PolymarketClient::new(&config)?;
+    let poly_client = PolymarketClient::new(&config)?.with_open_orders(open_orders.clone());
     
     if poly_client.can_trade() {
         info!("✅ Polymarket: Trading enabled");
+        // EOA accounts approve the exchanges themselves, with `polybot approve`; proxies come approved
+        if strategy_manager.any_live() && !config.polymarket_signature_type.is_proxy() {
+            if let Some(tx) = TxManager::from_config(&config)? {
+                match missing_allowances(&tx).await {
+                    Ok(missing) if !missing.is_empty() => warn!(
+                        "⚠️  Polymarket: Missing exchange approvals ({}); run `polybot approve --amount USDC` before trading live",
+                        missing.join(", ")
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!("⚠️  Polymarket: Could not verify exchange allowances: {}", e),
+                }
+            }
+        }
     } else {
         warn!("⚠️  Polymarket: Read-only mode (no credentials)");
     }
 
     // Initialize Kalshi client
     info!("🔌 Connecting to Kalshi...");
-    let mut kalshi_client = KalshiClient::new(&config)?;
+    let mut kalshi_client = KalshiClient::new(&config)?.with_open_orders(open_orders.clone());
     
     if kalshi_client.has_credentials() {
-        match kalshi_client.login().await {
-            Ok(true) => info!("✅ Kalshi: Logged in successfully"),
-            Ok(false) => warn!("⚠️  Kalshi: Login failed"),
-            Err(e) => warn!("⚠️  Kalshi: Login error: {}", e),
+        let failure = match kalshi_client.login().await {
+            Ok(true) => None,
+            Ok(false) => Some("Kalshi rejected the configured credentials".to_string()),
+            Err(e) => Some(format!("Kalshi login error: {}", e)),
+        };
+        match failure {
+            None => info!("✅ Kalshi: Logged in successfully"),
+            Some(reason) => notifier.notify(Alert::new(Severity::Critical, "login", "🔐 Kalshi login failed", &reason)).await,
         }
     } else {
         warn!("⚠️  Kalshi: No credentials configured");
     }
 
-    // Initialize Market Matcher
-    let matcher = MarketMatcher::new();
+    // Orders restored from the last run may have filled or been cancelled
+    // while the bot was down; keep only the ones the venues still have resting
+    if !open_orders.lock().unwrap().is_empty() {
+        if poly_client.can_trade() {
+            match poly_client.reconcile_open_orders().await {
+                Ok(dropped) if dropped > 0 => info!("🧹 Polymarket: Dropped {} orders no longer open", dropped),
+                Ok(_) => {}
+                Err(e) => warn!("⚠️  Polymarket: Could not reconcile open orders: {}", e),
+            }
+        }
+        if kalshi_client.has_credentials() {
+            match kalshi_client.reconcile_open_orders().await {
+                Ok(dropped) if dropped > 0 => info!("🧹 Kalshi: Dropped {} orders no longer resting", dropped),
+                Ok(_) => {}
+                Err(e) => warn!("⚠️  Kalshi: Could not reconcile open orders: {}", e),
+            }
+        }
+    }
+
+    // Initialize Market Matcher, with the series pairs the last universe
+    // refresh found until the next one runs
+    let mut matcher = MarketMatcher::new();
+    if !config.kalshi_series.is_empty() {
+        let universe = SeriesUniverse::new(config.kalshi_series.clone());
+        let saved: Vec<MatchedMarket> = state::load(MATCHED_MARKETS_FILE);
+        for pair in saved.into_iter().filter(|m| universe.owns(&m.kalshi.native)) {
+            matcher.add_match(pair);
+        }
+    }
     info!("📚 Initialized {} market pairs", matcher.get_all().len());
 
     // Initialize Arbitrage Detector
-    let detector = ArbitrageDetector::new(
+    // Sudden book changes between passes, for anything that reacts to moves
+    let moves = Arc::new(MoveDetector::new(config.move_jump, config.move_liquidity_pull));
+    let mut detector = ArbitrageDetector::new(
         poly_client,
         kalshi_client,
         matcher,
         config.min_profit_threshold,
-    );
+    )
+    .with_fees(fees.clone())
+    .with_books(BOOKS_FILE)
+    .with_warmup(config.warmup_quotes)
+    .with_stale_after(config.stale_book_secs)
+    .with_moves(moves.clone());
+    if config.record_quotes {
+        info!("📼 Recording quotes to {}", QUOTE_TAPE_FILE);
+        detector = detector.with_tape(QuoteTape::new(QUOTE_TAPE_FILE));
+    }
+    let detector = Arc::new(detector);
+
+    // Fetch every pair's metadata and book up front so the first passes
+    // don't crawl through cold caches one pair at a time
+    if !detector.matched_markets().is_empty() {
+        info!("🔥 Priming caches for {} market pairs...", detector.matched_markets().len());
+        let report = detector.prime().await;
+        for readiness in &report.subsystems {
+            if readiness.is_ready() {
+                info!("✅ {}", readiness.summary());
+            } else {
+                warn!("⚠️  {}", readiness.summary());
+            }
+        }
+        if !report.dropped.is_empty() {
+            warn!("🗑️  Dropped {} pairs closed on Kalshi: {}", report.dropped.len(), report.dropped.join(", "));
+        }
+    }
 
     info!("👀 Monitoring for arbitrage opportunities...");
     info!("");
 
-    // Simple monitoring loop
-    while circuit_breaker.is_allowed() {
-        if let Err(e) = detector.check_all_opportunities().await {
-            warn!("Error in detection pass: {}", e);
-            // If we hit too many sequential errors, trip the breaker
-            // circuit_breaker.trip("Too many API errors"); 
-        }
-        
-        // Wait before next pass
-        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+    // Pause, resume and the like, from Telegram and the dashboard
+    let controls = Arc::new(Controls::from_config(&config)?.with_breaker(circuit_breaker.clone()));
+    if controls.is_paused() {
+        warn!("⏸️  Trading is paused - resume with /resume on Telegram or from the dashboard");
     }
 
+    // Phone-friendly status page, fed with detections by the detector task
+    let dashboard = config
+        .dashboard_addr
+        .as_ref()
+        .map(|_| Arc::new(Dashboard::from_config(&config).with_controls(controls.clone())));
+    if config.dashboard_addr.is_some() && config.dashboard_tokens.is_empty() {
+        warn!("📱 DASHBOARD_TOKENS is not set - anyone who can reach the dashboard can read it");
+    }
+
+    // Every long-running subsystem runs supervised: restarted with backoff
+    // after a crash, and a crash loop trips the breaker
+    let shared = tasks::Shared {
+        config: config.clone(),
+        app,
+        breaker: circuit_breaker.clone(),
+        notifier: notifier.clone(),
+        manager: strategy_manager,
+        fees,
+        profile: profile.clone(),
+        session: session.clone(),
+        detector,
+        moves,
+        fills,
+        open_orders,
+        heatmap,
+        capture,
+        attribution,
+        executions,
+        intents,
+        cooldowns,
+        discrepancies,
+        derisker,
+        pretrade,
+        execution,
+        controls,
+        dashboard,
+    };
+    let mut supervisor = Supervisor::new(circuit_breaker.clone());
+    tasks::spawn_all(&mut supervisor, &shared)?;
+    supervisor.join().await;
+
+    let summary = {
+        let snapshot = PaperTradingEngine::snapshot_of(&profile);
+        let mut session = session.lock().unwrap();
+        session.update(&snapshot.portfolio, snapshot.trade_log.get_all().len(), chrono::Utc::now());
+        session.summary()
+    };
+    audit.record_session(&summary);
+    let _ = std::fs::remove_file(SESSION_FILE);
+    info!(
+        "📒 Session P&L ${:+.2} over {} trades (all-time ${:+.2})",
+        summary.pnl, summary.trades, summary.all_time_pnl
+    );
+
+    let state = circuit_breaker.state();
+    notifier.notify(Alert::new(
+        Severity::Critical,
+        "breaker",
+        "🛑 Circuit breaker tripped",
+        &format!("{}. The bot has shut down.", state.reason.as_deref().unwrap_or("No reason recorded")),
+    )).await;
     info!("🛑 Bot halted by circuit breaker. Shutting down...");
     Ok(())
 }
@@ -0,0 +1,289 @@
+//! Currency-tagged amounts and the display layer.
+//!
+//! Kalshi reports balances in cents USD and Polymarket settles in USDC;
+//! both are tagged here so they are never mixed up. Internally the bot
+//! keeps dollars (USDC is treated 1:1); [`MoneyDisplay`] converts to the
+//! user's `DISPLAY_CURRENCY` with a cached FX rate when rendering.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::Config;
+use crate::utils::state;
+
+/// Cached FX rates, refreshed by the bot and read by the TUI and reports.
+pub const FX_RATES_FILE: &str = "data/state/fx_rates.json";
+/// USD-based rates, no API key needed.
+const FX_URL: &str = "https://open.er-api.com/v6/latest/USD";
+/// Rates older than this are refreshed.
+const FX_MAX_AGE_HOURS: i64 = 12;
+
+/// Currencies the bot holds or can display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Currency {
+    Usd,
+    Usdc,
+    Eur,
+    Gbp,
+    Jpy,
+    Cad,
+    Aud,
+    Chf,
+}
+
+impl Currency {
+    /// ISO code ("USDC" for the stablecoin).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Usdc => "USDC",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Jpy => "JPY",
+            Currency::Cad => "CAD",
+            Currency::Aud => "AUD",
+            Currency::Chf => "CHF",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd | Currency::Usdc => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+            Currency::Jpy => "¥",
+            Currency::Cad => "C$",
+            Currency::Aud => "A$",
+            Currency::Chf => "CHF ",
+        }
+    }
+
+    fn decimals(&self) -> usize {
+        match self {
+            Currency::Jpy => 0,
+            _ => 2,
+        }
+    }
+
+    /// USD and USDC convert 1:1 without a rate.
+    pub fn is_dollar(&self) -> bool {
+        matches!(self, Currency::Usd | Currency::Usdc)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_uppercase().as_str() {
+            "USD" => Currency::Usd,
+            "USDC" => Currency::Usdc,
+            "EUR" => Currency::Eur,
+            "GBP" => Currency::Gbp,
+            "JPY" => Currency::Jpy,
+            "CAD" => Currency::Cad,
+            "AUD" => Currency::Aud,
+            "CHF" => Currency::Chf,
+            other => anyhow::bail!("Unsupported currency '{}'", other),
+        })
+    }
+}
+
+/// An amount tagged with its currency.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Money {
+    pub amount: f64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: f64, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub fn usd(amount: f64) -> Self {
+        Self::new(amount, Currency::Usd)
+    }
+
+    pub fn usdc(amount: f64) -> Self {
+        Self::new(amount, Currency::Usdc)
+    }
+
+    /// Kalshi amounts, which are integer cents USD.
+    pub fn from_cents(cents: i64) -> Self {
+        Self::usd(cents as f64 / 100.0)
+    }
+
+    /// Sum of two amounts, if they are in the same currency (or both dollars).
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        if self.currency == other.currency || (self.currency.is_dollar() && other.currency.is_dollar()) {
+            Some(Money::new(self.amount + other.amount, self.currency))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.amount < 0.0 { "-" } else { "" };
+        write!(f, "{}{}{:.*}", sign, self.currency.symbol(), self.currency.decimals(), self.amount.abs())
+    }
+}
+
+/// FX rates as units of each currency per USD.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FxRates {
+    pub fetched_at: Option<DateTime<Utc>>,
+    pub rates: HashMap<String, f64>,
+}
+
+#[derive(Deserialize)]
+struct FxResponse {
+    rates: HashMap<String, f64>,
+}
+
+impl FxRates {
+    /// Cached rates, or none if never fetched.
+    pub fn load(path: &str) -> Self {
+        state::load(path)
+    }
+
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        self.fetched_at.is_none_or(|t| now - t > Duration::hours(FX_MAX_AGE_HOURS))
+    }
+
+    /// Units of `currency` per USD.
+    pub fn rate(&self, currency: Currency) -> Option<f64> {
+        if currency.is_dollar() {
+            return Some(1.0);
+        }
+        self.rates.get(currency.code()).copied()
+    }
+
+    /// Convert an amount, or `None` without a rate for either currency.
+    pub fn convert(&self, money: Money, to: Currency) -> Option<Money> {
+        let usd = money.amount / self.rate(money.currency)?;
+        Some(Money::new(usd * self.rate(to)?, to))
+    }
+
+    /// Fetch current rates.
+    pub async fn fetch(client: &reqwest::Client) -> Result<Self> {
+        let response: FxResponse = client
+            .get(FX_URL)
+            .send()
+            .await
+            .context("Failed to fetch FX rates")?
+            .json()
+            .await
+            .context("Failed to parse FX rates")?;
+        Ok(Self { fetched_at: Some(Utc::now()), rates: response.rates })
+    }
+
+    /// Refresh the cache at `path` if it is stale.
+    pub async fn refresh(client: &reqwest::Client, path: &str) -> Result<Self> {
+        let cached = Self::load(path);
+        if !cached.is_stale(Utc::now()) {
+            return Ok(cached);
+        }
+        let rates = Self::fetch(client).await?;
+        state::save(path, &rates)?;
+        Ok(rates)
+    }
+}
+
+/// Renders dollar amounts in the user's display currency.
+#[derive(Debug, Clone)]
+pub struct MoneyDisplay {
+    pub currency: Currency,
+    rates: FxRates,
+}
+
+impl MoneyDisplay {
+    /// Plain dollars.
+    pub fn new() -> Self {
+        Self { currency: Currency::Usd, rates: FxRates::default() }
+    }
+
+    pub fn with_currency(mut self, currency: Currency, rates: FxRates) -> Self {
+        self.currency = currency;
+        self.rates = rates;
+        self
+    }
+
+    /// `DISPLAY_CURRENCY` with the cached rates.
+    pub fn from_config(config: &Config) -> Self {
+        Self::new().with_currency(config.display_currency, FxRates::load(FX_RATES_FILE))
+    }
+
+    /// Pick up rates the bot refreshed since this display was built.
+    pub fn reload(&mut self) {
+        if !self.currency.is_dollar() {
+            self.rates = FxRates::load(FX_RATES_FILE);
+        }
+    }
+
+    /// A dollar amount in the display currency, falling back to dollars
+    /// until a rate is cached.
+    pub fn convert(&self, usd: f64) -> Money {
+        self.rates
+            .convert(Money::usd(usd), self.currency)
+            .unwrap_or_else(|| Money::usd(usd))
+    }
+
+    /// e.g. "€12.34"
+    pub fn fmt(&self, usd: f64) -> String {
+        self.convert(usd).to_string()
+    }
+
+    /// e.g. "+€12.34"
+    pub fn fmt_signed(&self, usd: f64) -> String {
+        let sign = if usd >= 0.0 { "+" } else { "" };
+        format!("{}{}", sign, self.convert(usd))
+    }
+
+    /// Thousands, e.g. "€12K"
+    pub fn fmt_thousands(&self, usd: f64) -> String {
+        let money = self.convert(usd);
+        format!("{}{:.0}K", money.currency.symbol(), money.amount / 1000.0)
+    }
+}
+
+impl Default for MoneyDisplay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_and_formats_in_display_currency() {
+        let rates = FxRates {
+            fetched_at: Some(Utc::now()),
+            rates: HashMap::from([("EUR".to_string(), 0.9), ("JPY".to_string(), 150.0)]),
+        };
+        assert_eq!(Money::from_cents(-1234).to_string(), "-$12.34");
+        assert!(Money::usdc(1.0).checked_add(Money::from_cents(50)).is_some());
+        assert!(Money::new(1.0, Currency::Eur).checked_add(Money::usd(1.0)).is_none());
+
+        let eur = MoneyDisplay::new().with_currency(Currency::Eur, rates.clone());
+        assert_eq!(eur.fmt(100.0), "€90.00");
+        assert_eq!(eur.fmt_signed(-10.0), "-€9.00");
+        let jpy = MoneyDisplay::new().with_currency(Currency::Jpy, rates.clone());
+        assert_eq!(jpy.fmt(10.0), "¥1500");
+        assert!((rates.convert(Money::new(90.0, Currency::Eur), Currency::Usdc).unwrap().amount - 100.0).abs() < 1e-9);
+
+        // No cached rate: stay in dollars rather than show a wrong number
+        let gbp = MoneyDisplay::new().with_currency(Currency::Gbp, rates);
+        assert_eq!(gbp.fmt(5.0), "$5.00");
+        assert_eq!("usdc".parse::<Currency>().unwrap(), Currency::Usdc);
+        assert!("XYZ".parse::<Currency>().is_err());
+    }
+}
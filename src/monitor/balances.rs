@@ -0,0 +1,161 @@
+//! Balances overview across Polygon and both venues.
+//!
+//! Aggregates USDC in the signing wallet, Polymarket collateral and the
+//! Kalshi balance, and alerts when a venue's balance drops below the float
+//! arbitrage needs on each side.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::warn;
+
+use crate::chain::{usdc_balance, TxManager};
+use crate::kalshi::KalshiClient;
+use crate::money::Money;
+use crate::notify::notifier::{Alert, Severity};
+use crate::polymarket::PolymarketClient;
+use crate::utils::state;
+
+/// Latest balances, shared with the TUI.
+pub const BALANCES_FILE: &str = "data/balances.json";
+
+/// One snapshot of every balance; `None` where it could not be fetched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Balances {
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// USDC in the signing wallet on Polygon
+    pub polygon_usdc: Option<Money>,
+    /// Collateral available on the Polymarket CLOB
+    pub polymarket: Option<Money>,
+    pub kalshi: Option<Money>,
+    /// EOA accounts trade straight from the wallet, so its USDC is the
+    /// Polymarket collateral and must not be counted twice
+    #[serde(default)]
+    pub wallet_is_collateral: bool,
+}
+
+impl Balances {
+    /// Fetch every balance the configured credentials allow.
+    pub async fn fetch(
+        poly: &PolymarketClient,
+        kalshi: &KalshiClient,
+        chain: Option<&TxManager>,
+        wallet_is_collateral: bool,
+    ) -> Self {
+        fn logged(source: &str, result: Result<Money>) -> Option<Money> {
+            result.map_err(|e| warn!("{} balance unavailable: {}", source, e)).ok()
+        }
+
+        let polygon_usdc = match chain {
+            Some(tx) => logged("Polygon wallet", usdc_balance(tx, tx.address()).await),
+            None => None,
+        };
+        let polymarket = if poly.can_trade() {
+            logged("Polymarket", poly.get_collateral_balance().await)
+        } else {
+            None
+        };
+        let kalshi = if kalshi.is_authenticated() {
+            logged("Kalshi", kalshi.get_balance().await.map(|b| b.available()))
+        } else {
+            None
+        };
+
+        Self {
+            fetched_at: Some(Utc::now()),
+            polygon_usdc,
+            polymarket,
+            kalshi,
+            wallet_is_collateral,
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        state::load(path)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        state::save(path, self)
+    }
+
+    /// Dollars across all sources.
+    pub fn total(&self) -> f64 {
+        let wallet = if self.wallet_is_collateral { None } else { self.polygon_usdc };
+        [wallet, self.polymarket, self.kalshi].iter().flatten().map(|m| m.amount).sum()
+    }
+
+    /// Venues whose balance is below `min_float` dollars.
+    pub fn below_float(&self, min_float: f64) -> Vec<(&'static str, Money)> {
+        [("polymarket", self.polymarket), ("kalshi", self.kalshi)]
+            .into_iter()
+            .filter_map(|(venue, balance)| balance.filter(|b| b.amount < min_float).map(|b| (venue, b)))
+            .collect()
+    }
+}
+
+/// Raises one alert when a venue drops below its float, and re-arms once
+/// it is topped up.
+pub struct FloatMonitor {
+    min_float: f64,
+    low: HashSet<&'static str>,
+}
+
+impl FloatMonitor {
+    pub fn new(min_float: f64) -> Self {
+        Self { min_float, low: HashSet::new() }
+    }
+
+    /// Alerts for venues that just dropped below the float.
+    pub fn check(&mut self, balances: &Balances) -> Vec<Alert> {
+        if self.min_float <= 0.0 {
+            return Vec::new();
+        }
+        let low = balances.below_float(self.min_float);
+        let mut alerts = Vec::new();
+        for (venue, balance) in &low {
+            if self.low.insert(venue) {
+                alerts.push(Alert::new(
+                    Severity::Warning,
+                    "balance",
+                    &format!("💸 {} balance low", venue),
+                    &format!("{} available, ${:.2} float needed for arbitrage", balance, self.min_float),
+                ));
+            }
+        }
+        self.low.retain(|venue| low.iter().any(|(v, _)| v == venue));
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_and_float_alerts() {
+        let mut balances = Balances {
+            fetched_at: Some(Utc::now()),
+            polygon_usdc: Some(Money::usdc(50.0)),
+            polymarket: Some(Money::usdc(40.0)),
+            kalshi: Some(Money::from_cents(20_000)),
+            wallet_is_collateral: false,
+        };
+        assert!((balances.total() - 290.0).abs() < 1e-9);
+        balances.wallet_is_collateral = true;
+        assert!((balances.total() - 240.0).abs() < 1e-9);
+
+        let mut monitor = FloatMonitor::new(100.0);
+        let alerts = monitor.check(&balances);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].title.contains("polymarket"));
+        // Still low: no repeat alert
+        assert!(monitor.check(&balances).is_empty());
+
+        // Topped up, then low again: alerts again
+        balances.polymarket = Some(Money::usdc(500.0));
+        assert!(monitor.check(&balances).is_empty());
+        balances.polymarket = Some(Money::usdc(10.0));
+        assert_eq!(monitor.check(&balances).len(), 1);
+    }
+}
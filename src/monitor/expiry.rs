@@ -0,0 +1,178 @@
+//! Countdown to resolution for open positions.
+//!
+//! Each open position's market close time is looked up once (the Kalshi
+//! close time, or the Polymarket end date via Gamma) and a warning alert is
+//! raised when it comes within RESOLUTION_WARNING_MINUTES, with the size at
+//! risk and what to do about it. Alerts reach the TUI's Alerts pane and the
+//! routed channels like any other; each position warns once.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
+
+use crate::display;
+use crate::kalshi::KalshiClient;
+use crate::notify::notifier::{Alert, Severity};
+use crate::paper_trading::{Portfolio, Position};
+use crate::polymarket::PolymarketClient;
+use crate::utils::clock::{system_clock, SharedClock};
+use crate::utils::{timezone, MarketId, Venue};
+
+/// Close times of position markets and which positions were warned about.
+#[derive(Debug)]
+pub struct ExpiryMonitor {
+    /// None when the venue publishes no close time
+    close_times: HashMap<MarketId, Option<DateTime<Utc>>>,
+    /// "profile/market" keys already warned
+    warned: HashSet<String>,
+    clock: SharedClock,
+}
+
+impl Default for ExpiryMonitor {
+    fn default() -> Self {
+        Self { close_times: HashMap::new(), warned: HashSet::new(), clock: system_clock() }
+    }
+}
+
+impl ExpiryMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Look up close times of `portfolio`'s markets not seen before. A
+    /// failed lookup is retried on the next call.
+    pub async fn refresh(&mut self, poly: &PolymarketClient, kalshi: &KalshiClient, portfolio: &Portfolio) {
+        for (_, market) in portfolio.mark_targets() {
+            if self.close_times.contains_key(&market) {
+                continue;
+            }
+            let close = match market.venue {
+                Venue::Kalshi => kalshi.get_market(&market.native).await
+                    .map(|m| m.close_time.and_then(|t| timezone::parse_time(&t, timezone::current()))),
+                Venue::Polymarket => poly.get_gamma_market_by_token(&market.native).await
+                    .map(|m| m.and_then(|m| m.end_date).and_then(|t| timezone::parse_time(&t, timezone::current()))),
+            };
+            match close {
+                Ok(close) => {
+                    self.close_times.insert(market, close);
+                }
+                Err(e) => debug!("No close time for {}: {}", market, e),
+            }
+        }
+    }
+
+    /// Record a close time directly (e.g. from a matched pair).
+    pub fn set_close_time(&mut self, market: MarketId, close: Option<DateTime<Utc>>) {
+        self.close_times.insert(market, close);
+    }
+
+    /// Warnings for `profile`'s positions closing within `warn_before` that
+    /// have not been warned about yet.
+    pub fn check(&mut self, profile: &str, portfolio: &Portfolio, warn_before: Duration) -> Vec<Alert> {
+        let now = self.clock.now();
+        let prefix = format!("{}/", profile);
+        self.warned.retain(|key| {
+            key.strip_prefix(&prefix).is_none_or(|market| portfolio.positions.contains_key(market))
+        });
+        if warn_before <= Duration::zero() {
+            return Vec::new();
+        }
+
+        let mut alerts = Vec::new();
+        for (key, market) in portfolio.mark_targets() {
+            let Some(Some(close)) = self.close_times.get(&market) else {
+                continue;
+            };
+            let left = *close - now;
+            if left > warn_before || left < Duration::zero() {
+                continue;
+            }
+            let Some(position) = portfolio.positions.get(&key).filter(|p| p.size > 0.0) else {
+                continue;
+            };
+            if self.warned.insert(format!("{}{}", prefix, key)) {
+                alerts.push(warning(profile, position, *close, left));
+            }
+        }
+        alerts
+    }
+}
+
+fn warning(profile: &str, position: &Position, close: DateTime<Utc>, left: Duration) -> Alert {
+    Alert::new(
+        Severity::Warning,
+        "expiry",
+        &format!("⏳ {} resolves in {} min", position.market, left.num_minutes().max(1)),
+        &format!(
+            "{} ({}): {:.2} shares @ {} avg, marked {} - ${:.2} at risk, closes {}. {}",
+            position.market,
+            profile,
+            position.size,
+            display::price(position.avg_price),
+            display::price(position.current_price),
+            position.current_value(),
+            timezone::format(close, "%H:%M %Z"),
+            suggestion(position)
+        ),
+    )
+}
+
+/// What to do with a position about to settle, from where it is marked.
+fn suggestion(position: &Position) -> String {
+    let price = position.current_price;
+    if price >= 0.9 {
+        format!("Likely to settle in our favour: hold to collect ${:.2}, or sell now to lock in ${:.2}", position.size, position.current_value())
+    } else if price <= 0.1 {
+        format!("Likely to settle against us: sell now to salvage ${:.2} before it goes to zero", position.current_value())
+    } else {
+        format!("Outcome still open at {}: close or cut size unless you mean to hold through settlement", display::price(price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_warns_once_inside_the_window() {
+        let mut portfolio = Portfolio::new(1000.0);
+        portfolio.open_position("KX-1", "BTC", "kalshi", 40.0, 0.40).unwrap();
+        portfolio.positions.get_mut("KX-1").unwrap().update_pnl(0.95);
+        let now = Utc::now();
+        let clock = Arc::new(MockClock::new(now));
+        let mut monitor = ExpiryMonitor::new().with_clock(clock.clone());
+        monitor.set_close_time(MarketId::kalshi("KX-1"), Some(now + Duration::minutes(90)));
+        let window = Duration::minutes(60);
+
+        assert!(monitor.check("default", &portfolio, window).is_empty());
+        clock.set(now + Duration::minutes(45));
+        let alerts = monitor.check("default", &portfolio, window);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, "expiry");
+        assert!(alerts[0].message.contains("hold to collect $100.00"), "{}", alerts[0].message);
+        assert!(alerts[0].message.contains("$95.00 at risk"), "{}", alerts[0].message);
+        clock.set(now + Duration::minutes(50));
+        assert!(monitor.check("default", &portfolio, window).is_empty());
+        // Another profile holding the same market warns separately
+        assert_eq!(monitor.check("other", &portfolio, window).len(), 1);
+
+        // Closing and reopening the position arms the warning again
+        let held = portfolio.positions.remove("KX-1").unwrap();
+        clock.set(now + Duration::minutes(55));
+        assert!(monitor.check("default", &portfolio, window).is_empty());
+        portfolio.positions.insert("KX-1".to_string(), held);
+        clock.set(now + Duration::minutes(56));
+        assert_eq!(monitor.check("default", &portfolio, window).len(), 1);
+        // Past the close it is the resolution monitor's business
+        clock.set(now + Duration::minutes(91));
+        assert!(monitor.check("default", &portfolio, window).is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+//! Latency to the venues.
+//!
+//! Times round trips to the Polymarket and Kalshi REST APIs and websocket
+//! connects from this machine, to help pick where to host the bot. HTTP
+//! round trips reuse a warm connection; the cold first request (DNS, TCP
+//! and TLS) is reported separately. Any response counts, whatever its
+//! status: it is the network being measured, not the endpoint. With a
+//! fixture store in record mode every probe is saved, one frame per probe,
+//! and in replay mode a run is rebuilt from them without the network.
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tokio_tungstenite::{connect_async, tungstenite};
+use tracing::warn;
+
+use crate::kalshi::client::KALSHI_API_URL;
+use crate::polymarket::client::CLOB_API_URL;
+use crate::polymarket::discovery::GAMMA_API_URL;
+use crate::utils::fixtures::{FixtureMode, FixtureStore};
+
+/// Polymarket market data channel.
+const POLYMARKET_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+/// Kalshi websocket; it rejects unauthenticated upgrades, which still
+/// times the connect.
+const KALSHI_WS_URL: &str = "wss://api.elections.kalshi.com/trade-api/ws/v2";
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProbeKind {
+    /// GET round trip
+    Http,
+    /// Websocket connect and upgrade
+    WebSocket,
+}
+
+/// An endpoint to time.
+#[derive(Debug, Clone, Serialize)]
+pub struct Endpoint {
+    pub name: String,
+    pub venue: String,
+    pub kind: ProbeKind,
+    pub url: String,
+}
+
+impl Endpoint {
+    pub fn new(name: &str, venue: &str, kind: ProbeKind, url: &str) -> Self {
+        Self { name: name.to_string(), venue: venue.to_string(), kind, url: url.to_string() }
+    }
+}
+
+/// The endpoints the bot depends on.
+pub fn default_endpoints() -> Vec<Endpoint> {
+    vec![
+        Endpoint::new("CLOB", "polymarket", ProbeKind::Http, &format!("{}/time", CLOB_API_URL)),
+        Endpoint::new("Gamma", "polymarket", ProbeKind::Http, &format!("{}/markets?limit=1", GAMMA_API_URL)),
+        Endpoint::new("Market WS", "polymarket", ProbeKind::WebSocket, POLYMARKET_WS_URL),
+        Endpoint::new("REST", "kalshi", ProbeKind::Http, &format!("{}/exchange/status", KALSHI_API_URL)),
+        Endpoint::new("WS", "kalshi", ProbeKind::WebSocket, KALSHI_WS_URL),
+    ]
+}
+
+/// Latency distribution of one endpoint, in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub errors: usize,
+    pub min: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+    /// Mean absolute change between consecutive samples
+    pub jitter: f64,
+}
+
+impl LatencyStats {
+    /// Summarize samples in the order they were taken.
+    pub fn from_samples(samples: &[f64], errors: usize) -> Self {
+        if samples.is_empty() {
+            return Self { errors, ..Default::default() };
+        }
+        let jitter = if samples.len() > 1 {
+            samples.windows(2).map(|w| (w[1] - w[0]).abs()).sum::<f64>() / (samples.len() - 1) as f64
+        } else {
+            0.0
+        };
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        // Nearest rank
+        let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+        Self {
+            samples: samples.len(),
+            errors,
+            min: sorted[0],
+            p50: rank(0.50),
+            p90: rank(0.90),
+            p99: rank(0.99),
+            max: sorted[sorted.len() - 1],
+            jitter,
+        }
+    }
+}
+
+/// Results for one endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointReport {
+    pub endpoint: Endpoint,
+    /// First HTTP request including DNS, TCP and TLS setup
+    pub cold_ms: Option<f64>,
+    pub stats: LatencyStats,
+    /// Last error seen, if any
+    pub last_error: Option<String>,
+}
+
+/// One timed probe, as recorded for replay.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Probe {
+    /// The first HTTP request, reported apart from the rounds
+    #[serde(default)]
+    cold: bool,
+    #[serde(flatten)]
+    result: ProbeResult,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ProbeResult {
+    Ms(f64),
+    Error(String),
+}
+
+impl Probe {
+    fn new(cold: bool, result: Result<f64>) -> Self {
+        let result = match result {
+            Ok(ms) => ProbeResult::Ms(ms),
+            Err(e) => ProbeResult::Error(e.to_string()),
+        };
+        Self { cold, result }
+    }
+}
+
+/// Fixture stream an endpoint's probes are recorded under.
+fn stream_name(endpoint: &Endpoint) -> String {
+    format!("latency {} {}", endpoint.venue, endpoint.name)
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Time one websocket connect. A refused upgrade still completed the
+/// handshake round trips, so it counts.
+async fn connect_ms(url: &str) -> Result<f64> {
+    let start = Instant::now();
+    match tokio::time::timeout(TIMEOUT, connect_async(url)).await {
+        Err(_) => anyhow::bail!("timed out after {}s", TIMEOUT.as_secs()),
+        Ok(Ok((mut socket, _))) => {
+            let ms = elapsed_ms(start);
+            let _ = socket.close(None).await;
+            Ok(ms)
+        }
+        Ok(Err(tungstenite::Error::Http(_))) => Ok(elapsed_ms(start)),
+        Ok(Err(e)) => Err(e.into()),
+    }
+}
+
+async fn get_ms(http: &Client, url: &str) -> Result<f64> {
+    let start = Instant::now();
+    let response = http.get(url).send().await?;
+    let ms = elapsed_ms(start);
+    // Drain the body so the connection is reused
+    let _ = response.bytes().await;
+    Ok(ms)
+}
+
+/// Time `rounds` probes of `endpoint`, pausing `pause` between them. A
+/// store in replay mode serves the recorded probes instead.
+pub async fn bench(endpoint: &Endpoint, rounds: usize, pause: Duration, fixtures: Option<&FixtureStore>) -> EndpointReport {
+    let stream = stream_name(endpoint);
+    if let Some(store) = fixtures.filter(|f| f.mode() == FixtureMode::Replay) {
+        let probes = store.load_frames(&stream).and_then(|frames| {
+            frames.iter().map(|f| Ok(serde_json::from_str::<Probe>(f)?)).collect::<Result<Vec<_>>>()
+        });
+        return match probes {
+            Ok(probes) => report(endpoint, &probes),
+            Err(e) => EndpointReport {
+                endpoint: endpoint.clone(),
+                cold_ms: None,
+                stats: LatencyStats::default(),
+                last_error: Some(e.to_string()),
+            },
+        };
+    }
+
+    let mut probes = Vec::with_capacity(rounds + 1);
+    let http = Client::builder().timeout(TIMEOUT).build().unwrap_or_default();
+    if endpoint.kind == ProbeKind::Http {
+        probes.push(Probe::new(true, get_ms(&http, &endpoint.url).await));
+    }
+    for round in 0..rounds {
+        if round > 0 {
+            tokio::time::sleep(pause).await;
+        }
+        let result = match endpoint.kind {
+            ProbeKind::Http => get_ms(&http, &endpoint.url).await,
+            ProbeKind::WebSocket => connect_ms(&endpoint.url).await,
+        };
+        probes.push(Probe::new(false, result));
+    }
+    if let Some(store) = fixtures {
+        for probe in &probes {
+            if let Err(e) = serde_json::to_string(probe).map_err(anyhow::Error::from).and_then(|f| store.record_frame(&stream, &f)) {
+                warn!("Failed to record {} probe: {}", stream, e);
+            }
+        }
+    }
+    report(endpoint, &probes)
+}
+
+/// Summarize probes in the order they were taken.
+fn report(endpoint: &Endpoint, probes: &[Probe]) -> EndpointReport {
+    let mut samples = Vec::with_capacity(probes.len());
+    let mut errors = 0;
+    let mut last_error = None;
+    let mut cold_ms = None;
+    for probe in probes {
+        match (&probe.result, probe.cold) {
+            (ProbeResult::Ms(ms), true) => cold_ms = Some(*ms),
+            (ProbeResult::Ms(ms), false) => samples.push(*ms),
+            (ProbeResult::Error(e), cold) => {
+                // A failed cold request isn't a failed round
+                if !cold {
+                    errors += 1;
+                }
+                last_error = Some(e.clone());
+            }
+        }
+    }
+    EndpointReport {
+        endpoint: endpoint.clone(),
+        cold_ms,
+        stats: LatencyStats::from_samples(&samples, errors),
+        last_error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_and_jitter() {
+        let samples: Vec<f64> = (1..=100).map(f64::from).rev().collect();
+        let stats = LatencyStats::from_samples(&samples, 2);
+        assert_eq!((stats.min, stats.p50, stats.p90, stats.p99, stats.max), (1.0, 50.0, 90.0, 99.0, 100.0));
+        assert_eq!(stats.jitter, 1.0);
+        assert_eq!((stats.samples, stats.errors), (100, 2));
+
+        let spiky = LatencyStats::from_samples(&[10.0, 30.0, 10.0], 0);
+        assert_eq!((spiky.p50, spiky.jitter), (10.0, 20.0));
+        assert_eq!(LatencyStats::from_samples(&[], 3), LatencyStats { errors: 3, ..Default::default() });
+    }
+
+    #[tokio::test]
+    async fn test_recorded_probes_replay_the_same_report() {
+        let dir = std::env::temp_dir().join(format!("polybot-latency-{}", uuid::Uuid::new_v4()));
+        let endpoint = Endpoint::new("REST", "kalshi", ProbeKind::Http, "https://example.invalid/status");
+        let probes = vec![
+            Probe::new(true, Ok(120.0)),
+            Probe::new(false, Ok(40.0)),
+            Probe::new(false, Err(anyhow::anyhow!("timed out after 10s"))),
+            Probe::new(false, Ok(60.0)),
+        ];
+        let recorder = FixtureStore::new(&dir, FixtureMode::Record);
+        for probe in &probes {
+            recorder.record_frame(&stream_name(&endpoint), &serde_json::to_string(probe).unwrap()).unwrap();
+        }
+
+        let replay = FixtureStore::new(&dir, FixtureMode::Replay);
+        let replayed = bench(&endpoint, 3, Duration::ZERO, Some(&replay)).await;
+        let expected = report(&endpoint, &probes);
+        assert_eq!((replayed.cold_ms, &replayed.stats), (Some(120.0), &expected.stats));
+        assert_eq!((replayed.stats.samples, replayed.stats.errors, replayed.stats.p50), (2, 1, 40.0));
+        assert_eq!(replayed.last_error.as_deref(), Some("timed out after 10s"));
+
+        // Nothing recorded for an endpoint is reported, not probed
+        let other = Endpoint::new("WS", "kalshi", ProbeKind::WebSocket, "wss://example.invalid");
+        let missing = bench(&other, 3, Duration::ZERO, Some(&replay)).await;
+        assert!(missing.last_error.unwrap().contains("No frames recorded"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,159 @@
+//! Mark-to-market module.
+//!
+//! Prices every open position at its venue mid (falling back to the last
+//! trade) so unrealized P&L stays current without manual `update_prices`
+//! calls. Positions linked to the NO side are marked at the NO quotes, or
+//! at the complement of YES where the venue has none.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use tracing::{debug, warn};
+
+use crate::kalshi::types::KalshiMarket;
+use crate::kalshi::KalshiClient;
+use crate::paper_trading::Portfolio;
+use crate::polymarket::PolymarketClient;
+use crate::utils::{state, MarketId, Outcome, PositionTracker, Venue};
+
+/// Latest marks by paper position key, shared with the TUI.
+pub const MARKS_FILE: &str = "data/marks.json";
+
+/// Midpoint of a quote, or whichever side exists.
+pub fn mid_price(bid: Option<f64>, ask: Option<f64>) -> Option<f64> {
+    match (bid, ask) {
+        (Some(b), Some(a)) => Some((b + a) / 2.0),
+        (Some(p), None) | (None, Some(p)) => Some(p),
+        _ => None,
+    }
+}
+
+/// Mark of one side of a Kalshi market, in dollars.
+pub fn kalshi_mark(market: &KalshiMarket, outcome: Outcome) -> Option<f64> {
+    let dollars = |cents: Option<i32>| cents.map(|c| c as f64 / 100.0);
+    let last = dollars(market.last_price);
+    match outcome {
+        Outcome::Yes => mid_price(dollars(market.yes_bid), dollars(market.yes_ask)).or(last),
+        Outcome::No => mid_price(dollars(market.no_bid), dollars(market.no_ask)).or(last.map(|p| 1.0 - p)),
+    }
+}
+
+/// Fetches marks from both venues.
+pub struct MarkToMarket {
+    poly: PolymarketClient,
+    kalshi: KalshiClient,
+}
+
+impl MarkToMarket {
+    /// Create a marker over the given clients.
+    pub fn new(poly: PolymarketClient, kalshi: KalshiClient) -> Self {
+        Self { poly, kalshi }
+    }
+
+    /// Current mark for a venue market, of its NO side when the ID names
+    /// that outcome.
+    pub async fn fetch_mark(&self, market: &MarketId) -> Result<Option<f64>> {
+        let outcome = market.outcome.unwrap_or(Outcome::Yes);
+        match market.venue {
+            Venue::Polymarket => {
+                // Pairs link the YES token, so NO is its complement
+                let (bid, ask) = self.poly.get_best_prices(&market.native).await?;
+                Ok(mid_price(bid, ask).map(|p| if outcome == Outcome::No { 1.0 - p } else { p }))
+            }
+            Venue::Kalshi => Ok(kalshi_mark(&self.kalshi.get_market(&market.native).await?, outcome)),
+        }
+    }
+
+    /// Re-mark every open paper position, returning the marks applied.
+    pub async fn mark_portfolio(&self, portfolio: &mut Portfolio) -> HashMap<String, f64> {
+        let mut prices = HashMap::new();
+        for (key, market_id) in portfolio.mark_targets() {
+            match self.fetch_mark(&market_id).await {
+                Ok(Some(price)) => {
+                    prices.insert(key, price);
+                }
+                Ok(None) => debug!("No mark available for {}", market_id),
+                Err(e) => warn!("Failed to mark {}: {}", market_id, e),
+            }
+        }
+        if !prices.is_empty() {
+            portfolio.update_prices(&prices);
+        }
+        prices
+    }
+
+    /// Re-mark every open tracked position. Returns the number marked.
+    pub async fn mark_tracker(&self, tracker: &PositionTracker) -> usize {
+        let mut marked = 0;
+        for (platform, position) in tracker.open_positions() {
+            let Ok(market) = MarketId::on(&platform, &position.ticker) else {
+                continue;
+            };
+            match self.fetch_mark(&market).await {
+                Ok(Some(price)) => {
+                    tracker.mark(&platform, &position.ticker, price);
+                    marked += 1;
+                }
+                Ok(None) => debug!("No mark available for {}", position.ticker),
+                Err(e) => warn!("Failed to mark {}: {}", position.ticker, e),
+            }
+        }
+        marked
+    }
+}
+
+/// Persist marks for other processes.
+pub fn save_marks(path: &str, marks: &HashMap<String, f64>) -> Result<()> {
+    state::save(path, marks)
+}
+
+/// Load marks written by `save_marks` (empty if missing or unreadable).
+pub fn load_marks(path: &str) -> HashMap<String, f64> {
+    state::load(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marks_flow_into_unrealized_pnl() {
+        assert_eq!(mid_price(Some(0.40), Some(0.50)), Some(0.45));
+        assert_eq!(mid_price(None, Some(0.50)), Some(0.50));
+
+        let mut portfolio = Portfolio::new(1000.0);
+        portfolio.open_position("BTC 5PM", "BTC", "polymarket", 100.0, 0.50).unwrap();
+        portfolio.link_market("BTC 5PM", &MarketId::polymarket("12345"));
+        assert_eq!(
+            portfolio.mark_targets(),
+            vec![("BTC 5PM".to_string(), MarketId::polymarket("12345"))]
+        );
+        // Linked before IDs were venue-qualified
+        portfolio.positions.get_mut("BTC 5PM").unwrap().market_id = Some("12345".to_string());
+        assert_eq!(portfolio.mark_targets()[0].1, MarketId::polymarket("12345"));
+
+        portfolio.update_prices(&HashMap::from([("BTC 5PM".to_string(), 0.60)]));
+        assert!((portfolio.unrealized_pnl() - 20.0).abs() < 1e-9);
+
+        let tracker = PositionTracker::new();
+        tracker.update_position("kalshi", "KXBTCD-T1", 10, 0.40);
+        tracker.mark("kalshi", "KXBTCD-T1", 0.55);
+        assert!((tracker.unrealized_pnl() - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_no_side_marks_at_no_quotes() {
+        let market = KalshiMarket {
+            yes_bid: Some(40),
+            yes_ask: Some(44),
+            no_bid: Some(56),
+            no_ask: Some(60),
+            last_price: Some(42),
+            ..Default::default()
+        };
+        assert!((kalshi_mark(&market, Outcome::Yes).unwrap() - 0.42).abs() < 1e-9);
+        assert!((kalshi_mark(&market, Outcome::No).unwrap() - 0.58).abs() < 1e-9);
+        // Without NO quotes, the complement of the last YES trade
+        let quiet = KalshiMarket { last_price: Some(30), ..Default::default() };
+        assert!((kalshi_mark(&quiet, Outcome::No).unwrap() - 0.70).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,22 @@
+//! Market monitoring module.
+//!
+//! Watches markets outside the arbitrage pairs, raises alerts, keeps
+//! open positions marked to market and warns before they resolve, tracks
+//! account balances, measures latency to the venues, checks how matched
+//! pairs resolve and reports the bot's own process health.
+
+pub mod balances;
+pub mod expiry;
+pub mod latency;
+pub mod marker;
+pub mod resolution;
+pub mod system;
+pub mod watchlist;
+
+pub use balances::{Balances, FloatMonitor};
+pub use expiry::ExpiryMonitor;
+pub use latency::{Endpoint, EndpointReport, LatencyStats};
+pub use marker::MarkToMarket;
+pub use resolution::{Outcome, Resolution, ResolutionMonitor};
+pub use system::{StreamState, SystemStatus};
+pub use watchlist::{AlertRule, WatchItem, Watchlist};
@@ -0,0 +1,350 @@
+//! Resolution monitoring.
+//!
+//! Polls each matched pair until both venues have settled it, then records
+//! how each side resolved. The pairs only approximate the same question
+//! (different sources, strikes or cut-off times), so when the venues
+//! disagree the hedge did not hold: a critical alert goes out and a
+//! discrepancy is opened, pausing the family until acknowledged.
+//! Resolutions are appended to [`RESOLUTIONS_FILE`] for analysis, and
+//! settle the pair's records in the capture log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::analysis::capture::CaptureLog;
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::kalshi::types::KalshiMarket;
+use crate::kalshi::KalshiClient;
+use crate::notify::notifier::{Alert, Severity};
+use crate::polymarket::discovery::GammaMarket;
+use crate::polymarket::PolymarketClient;
+use crate::risk::discrepancies::DiscrepancyLedger;
+use crate::utils::state;
+
+/// Resolution history.
+pub const RESOLUTIONS_FILE: &str = "data/resolutions.jsonl";
+/// Kalshi statuses after which `result` is final.
+const KALSHI_SETTLED: [&str; 3] = ["settled", "finalized", "determined"];
+
+/// How a market's YES side resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Yes,
+    No,
+    /// Voided or split 50/50
+    Void,
+}
+
+impl Outcome {
+    /// What one YES share paid out.
+    pub fn yes_payout(&self) -> f64 {
+        match self {
+            Outcome::Yes => 1.0,
+            Outcome::No => 0.0,
+            Outcome::Void => 0.5,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Yes => write!(f, "YES"),
+            Outcome::No => write!(f, "NO"),
+            Outcome::Void => write!(f, "VOID"),
+        }
+    }
+}
+
+/// Outcome of a Kalshi market, once settled.
+pub fn kalshi_outcome(market: &KalshiMarket) -> Option<Outcome> {
+    if !KALSHI_SETTLED.contains(&market.status.to_lowercase().as_str()) {
+        return None;
+    }
+    match market.result.as_deref()?.to_lowercase().as_str() {
+        "yes" => Some(Outcome::Yes),
+        "no" => Some(Outcome::No),
+        "void" => Some(Outcome::Void),
+        _ => None,
+    }
+}
+
+/// Outcome of `token_id` in a Gamma market, once closed with final prices.
+pub fn polymarket_outcome(market: &GammaMarket, token_id: &str) -> Option<Outcome> {
+    if !market.closed {
+        return None;
+    }
+    let price = market.to_market().tokens.into_iter().find(|t| t.token_id == token_id)?.price?;
+    match price {
+        p if p >= 0.99 => Some(Outcome::Yes),
+        p if p <= 0.01 => Some(Outcome::No),
+        p if (p - 0.5).abs() <= 0.01 => Some(Outcome::Void),
+        // Closed but not settled yet
+        _ => None,
+    }
+}
+
+/// How both venues resolved a matched pair.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resolution {
+    pub name: String,
+    pub polymarket_id: String,
+    pub kalshi_ticker: String,
+    pub polymarket: Outcome,
+    pub kalshi: Outcome,
+    /// When each venue was first seen resolved
+    pub polymarket_seen_at: DateTime<Utc>,
+    pub kalshi_seen_at: DateTime<Utc>,
+}
+
+impl Resolution {
+    /// The venues disagreed, so a hedged pair did not offset.
+    pub fn mismatched(&self) -> bool {
+        self.polymarket != self.kalshi
+    }
+
+    /// Hours between the first and second venue resolving.
+    pub fn lag_hours(&self) -> f64 {
+        (self.kalshi_seen_at - self.polymarket_seen_at).num_seconds().abs() as f64 / 3600.0
+    }
+
+    fn alert(&self, discrepancy: Option<&str>) -> Alert {
+        let mut message = format!(
+            "Polymarket resolved {} but Kalshi resolved {} ({}). Arbitrage legs on this pair did not offset: \
+             check the payout against the hedge.",
+            self.polymarket, self.kalshi, self.kalshi_ticker
+        );
+        if let Some(id) = discrepancy {
+            message.push_str(&format!(" Trading on the family is paused until discrepancy {} is acknowledged.", id));
+        }
+        Alert::new(Severity::Critical, "resolution", &format!("⚖️ {} resolved differently", self.name), &message)
+    }
+}
+
+/// A pair waiting for one or both venues to resolve.
+#[derive(Debug, Clone)]
+struct Pending {
+    matched: MatchedMarket,
+    polymarket: Option<(Outcome, DateTime<Utc>)>,
+    kalshi: Option<(Outcome, DateTime<Utc>)>,
+}
+
+/// Watches matched pairs until both venues resolve them.
+pub struct ResolutionMonitor {
+    pending: HashMap<(String, String), Pending>,
+    /// Pairs already in the history
+    recorded: HashSet<(String, String)>,
+    history_path: Option<String>,
+    ledger: Option<Arc<DiscrepancyLedger>>,
+    capture: Option<Arc<Mutex<CaptureLog>>>,
+}
+
+impl Default for ResolutionMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResolutionMonitor {
+    pub fn new() -> Self {
+        Self { pending: HashMap::new(), recorded: HashSet::new(), history_path: None, ledger: None, capture: None }
+    }
+
+    /// Append resolutions to `path`, skipping pairs it already holds.
+    pub fn with_history(mut self, path: &str) -> Self {
+        self.recorded = load_recent_resolutions(path, usize::MAX)
+            .into_iter()
+            .map(|r| (r.polymarket_id, r.kalshi_ticker))
+            .collect();
+        self.history_path = Some(path.to_string());
+        self
+    }
+
+    /// Open a discrepancy in `ledger` when the venues disagree.
+    pub fn with_ledger(mut self, ledger: Arc<DiscrepancyLedger>) -> Self {
+        self.ledger = Some(ledger);
+        self
+    }
+
+    /// Settle the pair's records in a capture log once it resolves.
+    pub fn with_capture(mut self, capture: Arc<Mutex<CaptureLog>>) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Start watching `matched`. Pairs stay watched after they drop out of
+    /// the matcher, until both venues resolve.
+    pub fn watch(&mut self, matched: &MatchedMarket) {
+        let key = (matched.polymarket.native.clone(), matched.kalshi.native.clone());
+        if !self.recorded.contains(&key) {
+            self.pending
+                .entry(key)
+                .or_insert_with(|| Pending { matched: matched.clone(), polymarket: None, kalshi: None });
+        }
+    }
+
+    /// Pairs still waiting on a venue.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Note each venue's outcome for a pair; once both are in it is
+    /// recorded, alerting if they differ.
+    pub fn observe(
+        &mut self,
+        polymarket_id: &str,
+        kalshi_ticker: &str,
+        polymarket: Option<Outcome>,
+        kalshi: Option<Outcome>,
+    ) -> Option<Alert> {
+        let key = (polymarket_id.to_string(), kalshi_ticker.to_string());
+        let pending = self.pending.get_mut(&key)?;
+        let now = Utc::now();
+        if pending.polymarket.is_none() {
+            pending.polymarket = polymarket.map(|o| (o, now));
+        }
+        if pending.kalshi.is_none() {
+            pending.kalshi = kalshi.map(|o| (o, now));
+        }
+        let (Some((poly, poly_at)), Some((kalshi, kalshi_at))) = (pending.polymarket, pending.kalshi) else {
+            return None;
+        };
+
+        let pending = self.pending.remove(&key)?;
+        self.recorded.insert(key);
+        let resolution = Resolution {
+            name: pending.matched.name,
+            polymarket_id: pending.matched.polymarket.native,
+            kalshi_ticker: pending.matched.kalshi.native,
+            polymarket: poly,
+            kalshi,
+            polymarket_seen_at: poly_at,
+            kalshi_seen_at: kalshi_at,
+        };
+        self.record(&resolution);
+        if let Some(capture) = &self.capture {
+            capture.lock().unwrap().record_resolution(&resolution);
+        }
+        if resolution.mismatched() {
+            warn!(
+                "⚖️ {} resolved {} on Polymarket but {} on Kalshi",
+                resolution.name, resolution.polymarket, resolution.kalshi
+            );
+            let discrepancy = self.ledger.as_ref().and_then(|ledger| ledger.open_settlement(&resolution));
+            Some(resolution.alert(discrepancy.as_ref().map(|d| d.id.as_str())))
+        } else {
+            info!("⚖️ {} resolved {} on both venues", resolution.name, resolution.polymarket);
+            None
+        }
+    }
+
+    /// Check every watched pair against both venues.
+    pub async fn check(&mut self, poly: &PolymarketClient, kalshi: &KalshiClient) -> Vec<Alert> {
+        let watched: Vec<Pending> = self.pending.values().cloned().collect();
+        let mut alerts = Vec::new();
+        for pending in watched {
+            let m = &pending.matched;
+            let poly_outcome = match pending.polymarket {
+                Some((outcome, _)) => Some(outcome),
+                None => match poly.get_gamma_market_by_token(&m.polymarket.native).await {
+                    Ok(market) => market.and_then(|market| polymarket_outcome(&market, &m.polymarket.native)),
+                    Err(e) => {
+                        debug!("Resolution check failed for {} on Polymarket: {}", m.name, e);
+                        None
+                    }
+                },
+            };
+            let kalshi_outcome = match pending.kalshi {
+                Some((outcome, _)) => Some(outcome),
+                None => match kalshi.get_market(&m.kalshi.native).await {
+                    Ok(market) => kalshi_outcome(&market),
+                    Err(e) => {
+                        debug!("Resolution check failed for {} on Kalshi: {}", m.kalshi.native, e);
+                        None
+                    }
+                },
+            };
+            alerts.extend(self.observe(&m.polymarket.native, &m.kalshi.native, poly_outcome, kalshi_outcome));
+        }
+        alerts
+    }
+
+    fn record(&self, resolution: &Resolution) {
+        let Some(path) = &self.history_path else {
+            return;
+        };
+        let result = serde_json::to_string(resolution)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| state::append_line(path, &line));
+        if let Err(e) = result {
+            warn!("Failed to record resolution: {}", e);
+        }
+    }
+}
+
+/// The last `n` resolutions, newest first.
+pub fn load_recent_resolutions(path: &str, n: usize) -> Vec<Resolution> {
+    state::read_lines(path)
+        .iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(name: &str, token: &str, ticker: &str) -> MatchedMarket {
+        MatchedMarket::new(name, token, ticker)
+    }
+
+    #[test]
+    fn test_records_pairs_and_alerts_on_mismatch() {
+        let gamma = GammaMarket {
+            closed: true,
+            clob_token_ids: Some("[\"111\", \"222\"]".to_string()),
+            outcome_prices: Some("[\"0\", \"1\"]".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(polymarket_outcome(&gamma, "111"), Some(Outcome::No));
+        assert_eq!(polymarket_outcome(&gamma, "222"), Some(Outcome::Yes));
+        let kalshi: KalshiMarket =
+            serde_json::from_str(r#"{"ticker": "KX-1", "status": "finalized", "result": "yes"}"#).unwrap();
+        assert_eq!(kalshi_outcome(&kalshi), Some(Outcome::Yes));
+        let open: KalshiMarket = serde_json::from_str(r#"{"ticker": "KX-1", "status": "active", "result": ""}"#).unwrap();
+        assert_eq!(kalshi_outcome(&open), None);
+
+        let path = std::env::temp_dir()
+            .join(format!("polybot-resolutions-{}.jsonl", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let mut monitor = ResolutionMonitor::new().with_history(&path);
+        monitor.watch(&pair("BTC 100k", "111", "KX-1"));
+        monitor.watch(&pair("ETH 5k", "333", "KX-2"));
+
+        // Waits for both venues, remembering the first to resolve
+        assert!(monitor.observe("111", "KX-1", Some(Outcome::No), None).is_none());
+        let alert = monitor.observe("111", "KX-1", None, Some(Outcome::Yes)).unwrap();
+        assert_eq!((alert.severity, alert.kind.as_str()), (Severity::Critical, "resolution"));
+        assert!(monitor.observe("333", "KX-2", Some(Outcome::Yes), Some(Outcome::Yes)).is_none());
+        assert_eq!(monitor.pending(), 0);
+
+        let recent = load_recent_resolutions(&path, 10);
+        assert_eq!(recent.len(), 2);
+        assert!(!recent[0].mismatched());
+        assert!(recent[1].mismatched());
+
+        // Recorded pairs are not watched again after a restart
+        let mut restarted = ResolutionMonitor::new().with_history(&path);
+        restarted.watch(&pair("BTC 100k", "111", "KX-1"));
+        assert_eq!(restarted.pending(), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+}
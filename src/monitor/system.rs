@@ -0,0 +1,189 @@
+//! Process health for the TUI's System tab.
+//!
+//! Every few seconds the bot writes [`SYSTEM_FILE`]: uptime, resident
+//! memory, tokio task counts, the state of each websocket, venues that are
+//! down, how deep the internal queues are and, per supervised subsystem,
+//! whether it runs, how often it restarted and its last error. An operator can tell a degraded
+//! bot at a glance instead of reading logs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+use crate::arbitrage::VenueOutage;
+use crate::utils::state;
+use crate::utils::supervisor::TaskStats;
+
+/// Latest process health, written by the running bot.
+pub const SYSTEM_FILE: &str = "data/state/system.json";
+
+static STREAMS: OnceLock<Mutex<BTreeMap<String, StreamStatus>>> = OnceLock::new();
+
+/// Connection state of a websocket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamState {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+impl fmt::Display for StreamState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StreamState::Connecting => "connecting",
+            StreamState::Connected => "connected",
+            StreamState::Disconnected => "disconnected",
+        })
+    }
+}
+
+/// A websocket's state and when it last changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamStatus {
+    pub state: StreamState,
+    pub since: DateTime<Utc>,
+    /// Why it last dropped
+    pub last_error: Option<String>,
+}
+
+/// Record that websocket `name` moved to `state`.
+pub fn set_stream(name: &str, state: StreamState, error: Option<String>) {
+    let mut streams = STREAMS.get_or_init(Default::default).lock().unwrap();
+    let last_error = error.or_else(|| streams.get(name).and_then(|s| s.last_error.clone()));
+    streams.insert(name.to_string(), StreamStatus { state, since: Utc::now(), last_error });
+}
+
+/// States of every websocket reported so far.
+pub fn streams() -> BTreeMap<String, StreamStatus> {
+    STREAMS.get_or_init(Default::default).lock().unwrap().clone()
+}
+
+/// One supervised subsystem.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subsystem {
+    pub name: String,
+    pub running: bool,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+/// Snapshot of the bot's health.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SystemStatus {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    /// Resident memory in bytes, where the platform reports it
+    pub memory_bytes: Option<u64>,
+    /// Tokio tasks alive and runtime worker threads
+    pub tasks: usize,
+    pub workers: usize,
+    pub streams: BTreeMap<String, StreamStatus>,
+    /// Venues the detector cannot reach; cross-venue detection is
+    /// suspended while any is listed
+    #[serde(default)]
+    pub venues_down: Vec<VenueOutage>,
+    /// Items waiting per internal queue
+    pub queues: BTreeMap<String, usize>,
+    pub subsystems: Vec<Subsystem>,
+}
+
+impl SystemStatus {
+    /// Capture the current process state. `queues` are the depths the
+    /// caller can see; tokio counts come from the current runtime.
+    pub fn capture(
+        started_at: DateTime<Utc>,
+        tasks: &HashMap<String, TaskStats>,
+        queues: BTreeMap<String, usize>,
+    ) -> Self {
+        let metrics = tokio::runtime::Handle::try_current().ok().map(|h| h.metrics());
+        let mut subsystems: Vec<Subsystem> = tasks
+            .iter()
+            .map(|(name, stats)| Subsystem {
+                name: name.clone(),
+                running: stats.running,
+                restarts: stats.restarts,
+                last_error: stats.last_error.clone(),
+            })
+            .collect();
+        subsystems.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            updated_at: Some(Utc::now()),
+            started_at: Some(started_at),
+            memory_bytes: resident_memory(),
+            tasks: metrics.as_ref().map_or(0, |m| m.num_alive_tasks()),
+            workers: metrics.as_ref().map_or(0, |m| m.num_workers()),
+            streams: streams(),
+            venues_down: Vec::new(),
+            queues,
+            subsystems,
+        }
+    }
+
+    /// Add the venues that are down.
+    pub fn with_venue_outages(mut self, outages: Vec<VenueOutage>) -> Self {
+        self.venues_down = outages;
+        self
+    }
+
+    pub fn load(path: &str) -> Self {
+        state::load(path)
+    }
+
+    /// Seconds since the bot started, as of the snapshot.
+    pub fn uptime_secs(&self) -> i64 {
+        match (self.started_at, self.updated_at) {
+            (Some(start), Some(at)) => (at - start).num_seconds().max(0),
+            _ => 0,
+        }
+    }
+
+    /// Subsystems that are down or have crashed before.
+    pub fn degraded(&self) -> Vec<&Subsystem> {
+        self.subsystems.iter().filter(|s| !s.running || s.restarts > 0).collect()
+    }
+}
+
+/// Resident set size from /proc; None off Linux.
+fn resident_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_rss(&status)
+}
+
+fn parse_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_reports_subsystems_and_streams() {
+        assert_eq!(parse_rss("Name:\tbot\nVmRSS:\t   20480 kB\n"), Some(20 * 1024 * 1024));
+        assert_eq!(parse_rss("Name:\tbot\n"), None);
+
+        set_stream("test_stream", StreamState::Disconnected, Some("closed".to_string()));
+        set_stream("test_stream", StreamState::Connected, None);
+
+        let started = Utc::now() - chrono::Duration::seconds(90);
+        let tasks = HashMap::from([
+            ("detector".to_string(), TaskStats { restarts: 0, last_error: None, running: true }),
+            ("marker".to_string(), TaskStats { restarts: 2, last_error: Some("timeout".to_string()), running: true }),
+        ]);
+        let status = SystemStatus::capture(started, &tasks, BTreeMap::from([("intents".to_string(), 3)]));
+
+        assert!(status.uptime_secs() >= 90);
+        assert_eq!(status.queues["intents"], 3);
+        let stream = &status.streams["test_stream"];
+        assert_eq!(stream.state, StreamState::Connected);
+        // The last drop reason survives the reconnect
+        assert_eq!(stream.last_error.as_deref(), Some("closed"));
+        let degraded: Vec<&str> = status.degraded().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(degraded, vec!["marker"]);
+    }
+}
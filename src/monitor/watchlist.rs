@@ -0,0 +1,220 @@
+//! Watchlist module.
+//!
+//! Single markets the user wants monitored, independent of matched pairs,
+//! each with alert rules evaluated on every monitoring pass.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use super::marker::mid_price;
+use crate::display;
+use crate::kalshi::KalshiClient;
+use crate::notify::{Alert, Severity};
+use crate::polymarket::PolymarketClient;
+
+/// Alert rule attached to a watched market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertRule {
+    /// Mid price crosses above `value`.
+    PriceAbove { value: f64 },
+    /// Mid price crosses below `value`.
+    PriceBelow { value: f64 },
+    /// Bid/ask spread wider than `value` (probability units).
+    SpreadAbove { value: f64 },
+    /// Volume traded since the previous pass exceeds `multiplier` times its
+    /// running average.
+    VolumeSpike { multiplier: f64 },
+}
+
+/// A market on the watchlist.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchItem {
+    /// Display name
+    pub name: String,
+    /// "polymarket" or "kalshi"
+    pub venue: String,
+    /// Polymarket token ID or Kalshi ticker
+    pub market_id: String,
+    pub rules: Vec<AlertRule>,
+}
+
+/// Quote snapshot used to evaluate rules.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WatchQuote {
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// Cumulative traded volume, when the venue reports it
+    pub volume: Option<f64>,
+}
+
+impl WatchQuote {
+    fn mid(&self) -> Option<f64> {
+        mid_price(self.bid, self.ask)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct WatchState {
+    last_mid: Option<f64>,
+    last_volume: Option<f64>,
+    avg_volume_delta: Option<f64>,
+    spread_alerted: bool,
+}
+
+/// Smoothing factor for the volume-delta running average.
+const VOLUME_EMA_ALPHA: f64 = 0.2;
+
+/// Watchlist with per-item rule state.
+pub struct Watchlist {
+    items: Vec<WatchItem>,
+    state: HashMap<String, WatchState>,
+}
+
+impl Watchlist {
+    /// Create a watchlist from items.
+    pub fn new(items: Vec<WatchItem>) -> Self {
+        Self {
+            items,
+            state: HashMap::new(),
+        }
+    }
+
+    /// Load a watchlist JSON file; a missing file is an empty watchlist.
+    pub fn load(path: &str) -> Result<Self> {
+        if !Path::new(path).exists() {
+            return Ok(Self::new(Vec::new()));
+        }
+        let content = fs::read_to_string(path).context("Failed to read watchlist")?;
+        let items: Vec<WatchItem> = serde_json::from_str(&content).context("Invalid watchlist file")?;
+        Ok(Self::new(items))
+    }
+
+    /// Watched items.
+    pub fn items(&self) -> &[WatchItem] {
+        &self.items
+    }
+
+    /// Fetch a quote for an item from its venue.
+    pub async fn fetch_quote(item: &WatchItem, poly: &PolymarketClient, kalshi: &KalshiClient) -> Result<WatchQuote> {
+        match item.venue.as_str() {
+            "polymarket" => {
+                let (bid, ask) = poly.get_best_prices(&item.market_id).await?;
+                Ok(WatchQuote { bid, ask, volume: None })
+            }
+            "kalshi" => {
+                let market = kalshi.get_market(&item.market_id).await?;
+                Ok(WatchQuote {
+                    bid: market.yes_bid.map(|p| p as f64 / 100.0),
+                    ask: market.yes_ask.map(|p| p as f64 / 100.0),
+                    volume: market.volume.map(|v| v as f64),
+                })
+            }
+            other => anyhow::bail!("Unknown watchlist venue: {}", other),
+        }
+    }
+
+    /// Evaluate an item's rules against a fresh quote.
+    pub fn evaluate(&mut self, index: usize, quote: WatchQuote) -> Vec<Alert> {
+        let Some(item) = self.items.get(index) else {
+            return Vec::new();
+        };
+        let state = self.state.entry(item.market_id.clone()).or_default();
+        let mut alerts = Vec::new();
+        let mid = quote.mid();
+
+        let volume_delta = match (quote.volume, state.last_volume) {
+            (Some(now), Some(prev)) if now >= prev => Some(now - prev),
+            _ => None,
+        };
+
+        for rule in &item.rules {
+            match *rule {
+                AlertRule::PriceAbove { value } => {
+                    if let (Some(prev), Some(now)) = (state.last_mid, mid) {
+                        if prev <= value && now > value {
+                            alerts.push(alert(item, &format!("price crossed above {} (now {})", display::price(value), display::price(now))));
+                        }
+                    }
+                }
+                AlertRule::PriceBelow { value } => {
+                    if let (Some(prev), Some(now)) = (state.last_mid, mid) {
+                        if prev >= value && now < value {
+                            alerts.push(alert(item, &format!("price crossed below {} (now {})", display::price(value), display::price(now))));
+                        }
+                    }
+                }
+                AlertRule::SpreadAbove { value } => {
+                    if let (Some(bid), Some(ask)) = (quote.bid, quote.ask) {
+                        let wide = ask - bid > value;
+                        // Only alert on the transition into a wide spread
+                        if wide && !state.spread_alerted {
+                            alerts.push(alert(item, &format!("spread {} exceeds {}", display::price(ask - bid), display::price(value))));
+                        }
+                        state.spread_alerted = wide;
+                    }
+                }
+                AlertRule::VolumeSpike { multiplier } => {
+                    if let (Some(delta), Some(avg)) = (volume_delta, state.avg_volume_delta) {
+                        if avg > 0.0 && delta > avg * multiplier {
+                            alerts.push(alert(item, &format!("volume spike: {:.0} vs avg {:.0}", delta, avg)));
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(delta) = volume_delta {
+            state.avg_volume_delta = Some(match state.avg_volume_delta {
+                Some(avg) => avg + VOLUME_EMA_ALPHA * (delta - avg),
+                None => delta,
+            });
+        }
+        if quote.volume.is_some() {
+            state.last_volume = quote.volume;
+        }
+        if mid.is_some() {
+            state.last_mid = mid;
+        }
+
+        alerts
+    }
+}
+
+fn alert(item: &WatchItem, message: &str) -> Alert {
+    Alert::new(Severity::Info, "watchlist", &format!("👁 {}", item.name), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(bid: f64, ask: f64, volume: f64) -> WatchQuote {
+        WatchQuote { bid: Some(bid), ask: Some(ask), volume: Some(volume) }
+    }
+
+    #[test]
+    fn test_rules_fire_on_transitions() {
+        let mut watchlist = Watchlist::new(vec![WatchItem {
+            name: "BTC 95k".to_string(),
+            venue: "kalshi".to_string(),
+            market_id: "KXBTCD-26JAN0517-T94999.99".to_string(),
+            rules: vec![
+                AlertRule::PriceAbove { value: 0.60 },
+                AlertRule::SpreadAbove { value: 0.05 },
+                AlertRule::VolumeSpike { multiplier: 3.0 },
+            ],
+        }]);
+
+        assert!(watchlist.evaluate(0, quote(0.55, 0.57, 100.0)).is_empty());
+        assert!(watchlist.evaluate(0, quote(0.55, 0.57, 110.0)).is_empty());
+        // Price crosses 0.60 and volume jumps 10 -> 100
+        assert_eq!(watchlist.evaluate(0, quote(0.61, 0.63, 210.0)).len(), 2);
+        // Spread widens: alert once, not on every pass
+        assert_eq!(watchlist.evaluate(0, quote(0.55, 0.65, 220.0)).len(), 1);
+        assert!(watchlist.evaluate(0, quote(0.55, 0.65, 230.0)).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Notification delivery channels.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+
+use super::email::EmailSettings;
+use super::notifier::Alert;
+use crate::config::Config;
+use crate::utils::http_pool;
+
+/// Alerts give up sooner than trading requests.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A destination for alerts.
+#[derive(Debug, Clone)]
+pub enum Channel {
+    /// Telegram bot message to a chat.
+    Telegram { bot_token: String, chat_id: String },
+    /// Discord incoming webhook.
+    Discord { webhook_url: String },
+    /// SMTP email, for the alert kinds it lists.
+    Email(Box<EmailSettings>),
+}
+
+impl Channel {
+    /// Build every channel that has credentials configured.
+    pub fn from_config(config: &Config) -> Vec<Channel> {
+        let mut channels = Vec::new();
+        if let (Some(bot_token), Some(chat_id)) = (&config.telegram_bot_token, &config.telegram_chat_id) {
+            channels.push(Channel::Telegram {
+                bot_token: bot_token.clone(),
+                chat_id: chat_id.clone(),
+            });
+        }
+        if let Some(webhook_url) = &config.discord_webhook_url {
+            channels.push(Channel::Discord {
+                webhook_url: webhook_url.clone(),
+            });
+        }
+        if let Some(email) = EmailSettings::from_config(config) {
+            channels.push(Channel::Email(Box::new(email)));
+        }
+        channels
+    }
+
+    /// Short channel name (used in logs and routing rules).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Channel::Telegram { .. } => "telegram",
+            Channel::Discord { .. } => "discord",
+            Channel::Email(_) => "email",
+        }
+    }
+
+    /// Whether the channel wants this alert.
+    pub fn accepts(&self, alert: &Alert) -> bool {
+        match self {
+            Channel::Email(email) => email.accepts(alert),
+            _ => true,
+        }
+    }
+
+    /// Deliver an alert.
+    pub async fn send(&self, http: &Client, alert: &Alert) -> Result<()> {
+        let text = format!("{} {}\n{}", alert.severity.icon(), alert.title, alert.message);
+
+        let response = match self {
+            Channel::Telegram { bot_token, chat_id } => {
+                let url = format!("https://api.telegram.org/bot{}/sendMessage", bot_token);
+                let request = http.post(&url).json(&json!({ "chat_id": chat_id, "text": text }));
+                http_pool::send(request.timeout(TIMEOUT)).await
+            }
+            Channel::Discord { webhook_url } => {
+                http_pool::send(http.post(webhook_url).json(&json!({ "content": text })).timeout(TIMEOUT)).await
+            }
+            Channel::Email(email) => return email.send(alert).await,
+        }
+        .with_context(|| format!("Failed to reach {}", self.name()))?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("{} rejected alert: {}", self.name(), response.status());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+//! SMTP email delivery.
+//!
+//! Meant for low-frequency events: unless `NOTIFY_ROUTES` says otherwise,
+//! only alerts whose kind is listed in `EMAIL_ALERT_KINDS` (daily report,
+//! breaker trips, login failures by default) are mailed.
+//!
+//! Subjects and bodies are templates with `{icon}`, `{severity}`, `{kind}`,
+//! `{title}`, `{message}` and `{timestamp}` placeholders.
+
+use anyhow::{Context, Result};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::str::FromStr;
+
+use super::notifier::Alert;
+use crate::config::Config;
+use crate::utils::timezone;
+
+/// How the SMTP connection is secured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// TLS from the first byte (usually port 465)
+    Implicit,
+    /// Unencrypted, for local relays only
+    None,
+}
+
+impl FromStr for SmtpTls {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "starttls" => Ok(SmtpTls::StartTls),
+            "tls" | "implicit" => Ok(SmtpTls::Implicit),
+            "none" => Ok(SmtpTls::None),
+            other => anyhow::bail!("Unknown SMTP TLS mode '{}' (expected starttls, tls or none)", other),
+        }
+    }
+}
+
+/// SMTP server, addresses and message templates.
+#[derive(Debug, Clone)]
+pub struct EmailSettings {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    pub subject_template: String,
+    pub body_template: String,
+    /// Alert kinds that are mailed
+    pub kinds: Vec<String>,
+}
+
+impl EmailSettings {
+    /// Settings from SMTP_* / EMAIL_* config, or `None` without a host and
+    /// recipients.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let host = config.smtp_host.clone()?;
+        if config.email_to.is_empty() {
+            return None;
+        }
+        Some(Self {
+            host,
+            port: config.smtp_port,
+            tls: config.smtp_tls,
+            username: config.smtp_username.clone(),
+            password: config.smtp_password.clone(),
+            from: config.email_from.clone(),
+            to: config.email_to.clone(),
+            subject_template: config.email_subject_template.clone(),
+            body_template: config.email_body_template.clone(),
+            kinds: config.email_alert_kinds.clone(),
+        })
+    }
+
+    /// Whether this alert should be mailed.
+    pub fn accepts(&self, alert: &Alert) -> bool {
+        self.kinds.iter().any(|k| k == "*" || k.eq_ignore_ascii_case(&alert.kind))
+    }
+
+    /// Fill a template's placeholders from an alert.
+    pub fn render(template: &str, alert: &Alert) -> String {
+        template
+            .replace("\\n", "\n")
+            .replace("{icon}", alert.severity.icon())
+            .replace("{severity}", &format!("{:?}", alert.severity))
+            .replace("{kind}", &alert.kind)
+            .replace("{title}", &alert.title)
+            .replace("{message}", &alert.message)
+            .replace("{timestamp}", &timezone::format(alert.timestamp, "%Y-%m-%d %H:%M:%S %Z"))
+    }
+
+    fn transport(&self) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let builder = match self.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)?,
+            SmtpTls::Implicit => AsyncSmtpTransport::<Tokio1Executor>::relay(&self.host)?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host),
+        };
+        let builder = match (&self.username, &self.password) {
+            (Some(user), Some(pass)) => builder.credentials(Credentials::new(user.clone(), pass.clone())),
+            _ => builder,
+        };
+        Ok(builder.port(self.port).build())
+    }
+
+    /// Mail an alert to every recipient.
+    pub async fn send(&self, alert: &Alert) -> Result<()> {
+        let mut message = Message::builder()
+            .from(self.from.parse().with_context(|| format!("Invalid EMAIL_FROM '{}'", self.from))?)
+            .subject(Self::render(&self.subject_template, alert));
+        for to in &self.to {
+            message = message.to(to.parse().with_context(|| format!("Invalid EMAIL_TO address '{}'", to))?);
+        }
+        let message = message
+            .header(ContentType::TEXT_PLAIN)
+            .body(Self::render(&self.body_template, alert))
+            .context("Failed to build email")?;
+        self.transport()?.send(message).await.context("SMTP delivery failed")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::Severity;
+
+    #[test]
+    fn test_templates_and_kind_filter() {
+        let settings = EmailSettings {
+            host: "smtp.example.com".to_string(),
+            port: 587,
+            tls: "STARTTLS".parse().unwrap(),
+            username: None,
+            password: None,
+            from: "bot@example.com".to_string(),
+            to: vec!["me@example.com".to_string()],
+            subject_template: "[polybot] {severity}: {title}".to_string(),
+            body_template: "{message}\\n-- {kind}".to_string(),
+            kinds: vec!["breaker".to_string(), "login".to_string()],
+        };
+        assert_eq!(settings.tls, SmtpTls::StartTls);
+        assert!("ssl".parse::<SmtpTls>().is_err());
+
+        let alert = Alert::new(Severity::Critical, "breaker", "Circuit breaker tripped", "Task crashed 5 times");
+        assert!(settings.accepts(&alert));
+        assert!(!settings.accepts(&Alert::new(Severity::Info, "watchlist", "BTC", "moved")));
+        assert_eq!(EmailSettings::render(&settings.subject_template, &alert), "[polybot] Critical: Circuit breaker tripped");
+        assert_eq!(EmailSettings::render(&settings.body_template, &alert), "Task crashed 5 times\n-- breaker");
+    }
+}
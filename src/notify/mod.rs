@@ -0,0 +1,20 @@
+//! Notification module.
+//!
+//! Delivers alerts to the log, a local alert history file (read by the TUI
+//! Alerts pane) and optional Telegram/Discord/email channels, routed and
+//! throttled by configurable rules, and publishes
+//! opportunity and fill events to an outbound webhook. Deliveries that fail
+//! are queued on disk and retried.
+
+pub mod channels;
+pub mod email;
+pub mod notifier;
+pub mod outbox;
+pub mod report;
+pub mod router;
+pub mod webhook;
+
+pub use channels::Channel;
+pub use notifier::{Alert, Notifier, Severity};
+pub use outbox::{Outbox, OUTBOX_FILE};
+pub use webhook::{Webhook, WebhookEvent};
@@ -0,0 +1,227 @@
+//! Alert notifier.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use tracing::{error, info, warn};
+
+use super::channels::Channel;
+use super::outbox::{Outbox, Pending, OUTBOX_FILE};
+use super::router::{Admission, Router};
+use crate::config::Config;
+use crate::utils::http_pool;
+use crate::utils::run_info::{self, RunStamp};
+use crate::utils::state;
+
+/// Default alert history file shared with the TUI.
+pub const ALERTS_FILE: &str = "data/alerts.jsonl";
+
+/// Alert severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ️",
+            Severity::Warning => "⚠️",
+            Severity::Critical => "🚨",
+        }
+    }
+}
+
+/// A single alert.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub timestamp: DateTime<Utc>,
+    pub severity: Severity,
+    /// Event type, e.g. "watchlist", "breaker"
+    pub kind: String,
+    pub title: String,
+    pub message: String,
+    /// Process that raised the alert
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunStamp>,
+}
+
+impl Alert {
+    /// Create an alert stamped with the current time.
+    pub fn new(severity: Severity, kind: &str, title: &str, message: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            severity,
+            kind: kind.to_string(),
+            title: title.to_string(),
+            message: message.to_string(),
+            run: Some(run_info::current().clone()),
+        }
+    }
+}
+
+/// Fans alerts out to the log, the history file and configured channels.
+pub struct Notifier {
+    http: Client,
+    channels: Vec<Channel>,
+    history_path: Option<String>,
+    router: RwLock<Router>,
+    /// Where failed channel deliveries wait for a retry
+    outbox: Option<Outbox>,
+}
+
+impl Notifier {
+    /// Create a notifier with channels and routing rules from configuration.
+    pub fn new(config: &Config) -> Result<Self> {
+        let channels = Channel::from_config(config);
+        if !channels.is_empty() {
+            let names: Vec<_> = channels.iter().map(|c| c.name()).collect();
+            info!("🔔 Notification channels: {}", names.join(", "));
+        }
+        let router = Router::from_config(config)?;
+        if router.has_routes() {
+            info!("🔀 Notification routes: {}", config.notify_routes);
+        }
+        Ok(Self::with_channels(channels, Some(ALERTS_FILE.to_string()))
+            .with_router(router)
+            .with_outbox(Outbox::new(OUTBOX_FILE)))
+    }
+
+    /// Queue failed channel deliveries in `outbox` instead of dropping them.
+    pub fn with_outbox(mut self, outbox: Outbox) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Route and throttle deliveries with explicit rules.
+    pub fn with_router(mut self, router: Router) -> Self {
+        self.router = RwLock::new(router);
+        self
+    }
+
+    /// Replace the routing rules of a running notifier. Throttling starts
+    /// over under the new rules.
+    pub fn set_router(&self, router: Router) {
+        *self.router.write().unwrap() = router;
+    }
+
+    /// Create a notifier with explicit channels and history file.
+    pub fn with_channels(channels: Vec<Channel>, history_path: Option<String>) -> Self {
+        Self {
+            http: http_pool::client(),
+            channels,
+            history_path,
+            router: RwLock::new(Router::default()),
+            outbox: None,
+        }
+    }
+
+    /// Whether a routing rule sends alerts of `kind` anywhere. Lets callers
+    /// skip building opt-in alerts nobody receives.
+    pub fn is_routed(&self, kind: &str) -> bool {
+        self.router.read().unwrap().names_kind(kind)
+    }
+
+    /// Deliver an alert everywhere. Delivery failures are logged, never fatal.
+    pub async fn notify(&self, alert: Alert) {
+        match alert.severity {
+            Severity::Info => info!("{} {}: {}", alert.severity.icon(), alert.title, alert.message),
+            Severity::Warning => warn!("{} {}: {}", alert.severity.icon(), alert.title, alert.message),
+            Severity::Critical => error!("{} {}: {}", alert.severity.icon(), alert.title, alert.message),
+        }
+
+        self.append_history(&alert);
+
+        let now = Utc::now();
+        for channel in &self.channels {
+            let admission = {
+                let router = self.router.read().unwrap();
+                if !router.routes_to(&alert, channel.name(), channel.accepts(&alert)) {
+                    continue;
+                }
+                router.admit(channel.name(), &alert, now)
+            };
+            let suppressed = match admission {
+                Admission::Send { suppressed } => suppressed,
+                Admission::Suppress => continue,
+            };
+            let mut delivered = alert.clone();
+            if suppressed > 0 {
+                delivered.message.push_str(&format!("\n(+{} similar alerts suppressed)", suppressed));
+            }
+            if let Err(e) = channel.send(&self.http, &delivered).await {
+                self.queue(channel.name(), delivered, &e.to_string());
+            }
+        }
+    }
+
+    fn queue(&self, channel: &str, alert: Alert, error: &str) {
+        let Some(outbox) = &self.outbox else {
+            warn!("Alert delivery via {} failed: {}", channel, error);
+            return;
+        };
+        warn!("Alert delivery via {} failed, queued for retry: {}", channel, error);
+        let pending = Pending::Alert { channel: channel.to_string(), alert };
+        if let Err(e) = outbox.push(pending, error, Utc::now()) {
+            error!("Failed to queue alert for {}: {}", channel, e);
+        }
+    }
+
+    /// Retry queued alerts that are due. Alerts for channels no longer
+    /// configured are discarded.
+    pub async fn retry_pending(&self) {
+        let Some(outbox) = &self.outbox else {
+            return;
+        };
+        let now = Utc::now();
+        for entry in outbox.due(now) {
+            let Pending::Alert { channel: name, alert } = &entry.item else {
+                continue;
+            };
+            let result = match self.channels.iter().find(|c| c.name() == name) {
+                Some(channel) => match channel.send(&self.http, alert).await {
+                    Ok(()) => {
+                        info!("📬 Delivered queued alert \"{}\" via {} after {} retries", alert.title, name, entry.attempts + 1);
+                        outbox.delivered(&entry.id)
+                    }
+                    Err(e) => outbox.failed(&entry.id, &e.to_string(), now),
+                },
+                None => {
+                    warn!("Discarding queued alert \"{}\": channel {} is no longer configured", alert.title, name);
+                    outbox.delivered(&entry.id)
+                }
+            };
+            if let Err(e) = result {
+                error!("Failed to update notification outbox: {}", e);
+            }
+        }
+    }
+
+    /// Deliveries waiting in the outbox.
+    pub fn pending(&self) -> usize {
+        self.outbox.as_ref().map_or(0, Outbox::len)
+    }
+
+    fn append_history(&self, alert: &Alert) {
+        let Some(path) = &self.history_path else {
+            return;
+        };
+        if let Ok(line) = serde_json::to_string(alert) {
+            let _ = state::append_line(path, &line);
+        }
+    }
+}
+
+/// Load the most recent `n` alerts from a history file (newest first).
+pub fn load_recent_alerts(path: &str, n: usize) -> Vec<Alert> {
+    state::read_lines(path)
+        .iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(n)
+        .collect()
+}
@@ -0,0 +1,207 @@
+//! Durable queue of undelivered notifications.
+//!
+//! An alert a channel refused, or a webhook event that exhausted its
+//! immediate retries, is written to [`OUTBOX_FILE`] instead of being
+//! dropped. A background task retries due entries with exponential backoff,
+//! so a breaker trip raised during a network blip still reaches Telegram
+//! once the network is back, even across a restart. Entries older than
+//! [`MAX_AGE`] are given up on with an error in the log.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tracing::error;
+use uuid::Uuid;
+
+use super::notifier::Alert;
+use super::webhook::WebhookEvent;
+use crate::utils::state;
+
+/// Notifications waiting for redelivery.
+pub const OUTBOX_FILE: &str = "data/state/outbox.json";
+
+/// Delay before the first redelivery attempt.
+const RETRY_BASE: Duration = Duration::seconds(30);
+
+/// Cap on the redelivery backoff.
+const MAX_BACKOFF: Duration = Duration::minutes(30);
+
+/// How long an entry is retried before it is given up on.
+const MAX_AGE: Duration = Duration::hours(24);
+
+/// Serializes read-modify-write of the outbox file across handles.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// What is waiting to be delivered.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Pending {
+    /// An alert for one notification channel
+    Alert { channel: String, alert: Alert },
+    /// An event for the outbound webhook
+    Webhook { event: WebhookEvent },
+}
+
+impl Pending {
+    /// Short description for the log.
+    pub fn describe(&self) -> String {
+        match self {
+            Pending::Alert { channel, alert } => format!("{} alert \"{}\"", channel, alert.title),
+            Pending::Webhook { event } => format!("{} webhook {}", event.event, event.id),
+        }
+    }
+}
+
+/// One queued notification.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Queued {
+    pub id: String,
+    pub item: Pending,
+    pub queued_at: DateTime<Utc>,
+    /// Failed redelivery attempts so far
+    pub attempts: u32,
+    pub next_attempt: DateTime<Utc>,
+    pub last_error: String,
+}
+
+/// Handle on the outbox file.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    path: String,
+}
+
+impl Outbox {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string() }
+    }
+
+    /// Queue `item` after a failed delivery.
+    pub fn push(&self, item: Pending, error: &str, now: DateTime<Utc>) -> Result<()> {
+        self.update(|entries| {
+            entries.push(Queued {
+                id: Uuid::new_v4().to_string(),
+                item,
+                queued_at: now,
+                attempts: 0,
+                next_attempt: now + RETRY_BASE,
+                last_error: error.to_string(),
+            })
+        })
+    }
+
+    /// Entries due for redelivery at `now`, oldest first. Entries past
+    /// [`MAX_AGE`] are removed and logged.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<Queued> {
+        let _guard = LOCK.lock().unwrap();
+        let mut entries: Vec<Queued> = state::load(&self.path);
+        let queued = entries.len();
+        entries.retain(|entry| {
+            let expired = now - entry.queued_at > MAX_AGE;
+            if expired {
+                error!(
+                    "Giving up on {} after {} attempts over {}h: {}",
+                    entry.item.describe(),
+                    entry.attempts,
+                    MAX_AGE.num_hours(),
+                    entry.last_error
+                );
+            }
+            !expired
+        });
+        if entries.len() < queued {
+            if let Err(e) = state::save(&self.path, &entries) {
+                error!("Failed to update notification outbox: {}", e);
+            }
+        }
+        entries.into_iter().filter(|e| e.next_attempt <= now).collect()
+    }
+
+    /// Remove a delivered entry.
+    pub fn delivered(&self, id: &str) -> Result<()> {
+        self.update(|entries| entries.retain(|e| e.id != id))
+    }
+
+    /// Record another failed attempt and push the next one back.
+    pub fn failed(&self, id: &str, error: &str, now: DateTime<Utc>) -> Result<()> {
+        self.update(|entries| {
+            if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+                entry.attempts += 1;
+                entry.next_attempt = now + backoff(entry.attempts);
+                entry.last_error = error.to_string();
+            }
+        })
+    }
+
+    /// Entries waiting.
+    pub fn len(&self) -> usize {
+        let _guard = LOCK.lock().unwrap();
+        state::load::<Vec<Queued>>(&self.path).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Vec<Queued>)) -> Result<()> {
+        let _guard = LOCK.lock().unwrap();
+        let mut entries: Vec<Queued> = state::load(&self.path);
+        f(&mut entries);
+        state::save(&self.path, &entries)
+    }
+}
+
+/// Delay after `attempts` failed redeliveries: doubling from
+/// [`RETRY_BASE`], capped at [`MAX_BACKOFF`].
+fn backoff(attempts: u32) -> Duration {
+    let factor = 2i32.saturating_pow(attempts.min(16));
+    (RETRY_BASE * factor).min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notify::Severity;
+
+    #[test]
+    fn test_entries_survive_and_back_off_until_delivered() {
+        let dir = std::env::temp_dir().join(format!("outbox-{}", Uuid::new_v4()));
+        let path = dir.join("outbox.json").to_string_lossy().to_string();
+        let outbox = Outbox::new(&path);
+        let now = Utc::now();
+
+        let alert = Alert::new(Severity::Critical, "breaker", "Circuit breaker tripped", "5 errors");
+        outbox.push(Pending::Alert { channel: "telegram".to_string(), alert }, "timeout", now).unwrap();
+        assert!(outbox.due(now).is_empty());
+
+        // A fresh handle (as after a restart) sees the entry once it is due
+        let reopened = Outbox::new(&path);
+        let due = reopened.due(now + Duration::seconds(30));
+        assert_eq!(due.len(), 1);
+        assert!(matches!(&due[0].item, Pending::Alert { channel, .. } if channel == "telegram"));
+
+        // Each failure doubles the wait
+        reopened.failed(&due[0].id, "503", now + Duration::seconds(30)).unwrap();
+        assert!(reopened.due(now + Duration::seconds(89)).is_empty());
+        let due = reopened.due(now + Duration::seconds(90));
+        assert_eq!(due[0].attempts, 1);
+        assert_eq!(due[0].last_error, "503");
+        assert_eq!(backoff(10), MAX_BACKOFF);
+
+        reopened.delivered(&due[0].id).unwrap();
+        assert!(reopened.is_empty());
+
+        // Entries too old to matter are given up on
+        let event = WebhookEvent {
+            id: "evt-1".to_string(),
+            event: "fill".to_string(),
+            at: now,
+            data: serde_json::Value::Null,
+            run: crate::utils::run_info::current().clone(),
+        };
+        outbox.push(Pending::Webhook { event }, "refused", now).unwrap();
+        assert!(outbox.due(now + MAX_AGE + Duration::seconds(1)).is_empty());
+        assert!(outbox.is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,112 @@
+//! Daily summary report.
+//!
+//! One alert per day summarizing the paper portfolio, trading since the
+//! same time the day before, venue balances and the circuit breaker, sent
+//! at a fixed hour on the configured clock.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::notifier::{Alert, Severity};
+use crate::display;
+use crate::monitor::Balances;
+use crate::paper_trading::{Portfolio, PaperTrade, TradeStatus};
+use crate::utils::circuit_breaker::BreakerState;
+use crate::utils::{run_info, timezone};
+
+/// Next time after `now` that the local clock in `tz` reads `hour`:00.
+pub fn next_report_at(now: DateTime<Utc>, hour: u32, tz: Tz) -> DateTime<Utc> {
+    let local = now.with_timezone(&tz).date_naive();
+    (0..3)
+        .filter_map(|days| (local + Duration::days(days)).and_hms_opt(hour, 0, 0))
+        // Skips hours that do not exist on DST change days
+        .filter_map(|naive| tz.from_local_datetime(&naive).earliest())
+        .map(|at| at.with_timezone(&Utc))
+        .find(|at| *at > now)
+        .unwrap_or(now + Duration::days(1))
+}
+
+/// Build the report alert for the local day ending at `now` in `tz`.
+pub fn daily_report(
+    portfolio: &Portfolio,
+    trades: &[PaperTrade],
+    balances: &Balances,
+    breaker: &BreakerState,
+    now: DateTime<Utc>,
+    tz: Tz,
+) -> Alert {
+    let since = timezone::day_before(now, tz);
+    let recent: Vec<&PaperTrade> = trades.iter().filter(|t| t.timestamp >= since).collect();
+    let closed: Vec<&&PaperTrade> = recent.iter().filter(|t| t.status == TradeStatus::Closed).collect();
+    let realized: f64 = closed.iter().filter_map(|t| t.pnl).sum();
+    let wins = closed.iter().filter(|t| t.is_profitable()).count();
+
+    let mut lines = vec![
+        format!(
+            "Paper equity ${:.2} ({}), {} open positions",
+            portfolio.total_value(),
+            display::signed_percent(portfolio.pnl_percent() / 100.0),
+            portfolio.position_count()
+        ),
+        format!(
+            "Since {}: {} trades, {} closed ({} won), realized {:+.2}",
+            since.with_timezone(&tz).format("%a %H:%M %Z"),
+            recent.len(),
+            closed.len(),
+            wins,
+            realized
+        ),
+    ];
+    if balances.fetched_at.is_some() {
+        let show = |b: Option<crate::money::Money>| b.map(|m| format!("{:.2}", m.amount)).unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "Balances: Polymarket {}, Kalshi {}, wallet {} (total ${:.2})",
+            show(balances.polymarket),
+            show(balances.kalshi),
+            show(balances.polygon_usdc),
+            balances.total()
+        ));
+    }
+    lines.push(match (&breaker.tripped, &breaker.reason) {
+        (true, Some(reason)) => format!("🛑 Circuit breaker TRIPPED: {}", reason),
+        (true, None) => "🛑 Circuit breaker TRIPPED".to_string(),
+        _ => "Circuit breaker OK".to_string(),
+    });
+    lines.push(run_info::current().summary());
+
+    Alert::new(
+        Severity::Info,
+        "report",
+        &format!("📰 Daily report {}", timezone::local_date(now, tz)),
+        &lines.join("\n"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::Side;
+
+    #[test]
+    fn test_schedule_and_summary() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        // 14:00 UTC is 09:00 in New York (EST)
+        let now: DateTime<Utc> = "2024-01-10T14:00:00Z".parse().unwrap();
+        assert_eq!(next_report_at(now, 18, tz), "2024-01-10T23:00:00Z".parse::<DateTime<Utc>>().unwrap());
+        assert_eq!(next_report_at(now, 9, tz), "2024-01-11T14:00:00Z".parse::<DateTime<Utc>>().unwrap());
+
+        let mut win = PaperTrade::new("BTC", "BTC", "Hourly", "kalshi", Side::Buy, 10.0, 0.40, "manual", 0.5);
+        win.timestamp = now - Duration::hours(2);
+        win.close(0.60);
+        let mut old = win.clone();
+        old.timestamp = now - Duration::days(3);
+
+        let breaker = BreakerState { tripped: true, reason: Some("too many errors".to_string()), tripped_at: None };
+        let alert = daily_report(&Portfolio::new(1000.0), &[win, old], &Balances::default(), &breaker, now, tz);
+        assert_eq!(alert.kind, "report");
+        assert!(alert.title.ends_with("2024-01-10"));
+        assert!(alert.message.contains("Since Tue 09:00 EST: 1 trades, 1 closed (1 won)"));
+        assert!(alert.message.contains("TRIPPED: too many errors"));
+        assert!(!alert.message.contains("Balances"));
+    }
+}
@@ -0,0 +1,223 @@
+//! Notification routing rules.
+//!
+//! `NOTIFY_ROUTES` maps alert kinds and severities to channels, e.g.
+//! `breaker=telegram+email; opportunity=discord; *@critical=telegram; *=discord`.
+//! Each rule is `<kind>[@<min severity>]=<channels>`, where kind `*` matches
+//! any alert and channels are joined with `+` (`all` and `none` are also
+//! accepted). The first matching rule wins; alerts no rule matches reach no
+//! channel. Without rules every alert goes to every channel that accepts
+//! it. Opportunities, market moves and lag signals are too frequent for
+//! that: they are only delivered by a rule that names them.
+//!
+//! Deliveries are also throttled per channel: an alert repeating the same
+//! kind and title within the dedup window is held back and counted, and
+//! each channel sends at most a fixed number of messages per minute.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::notifier::{Alert, Severity};
+use crate::config::Config;
+
+/// Alert kinds that are only delivered when a rule routes them.
+const OPT_IN_KINDS: [&str; 3] = ["opportunity", "market_move", "lag"];
+
+/// One routing rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    /// Alert kind, or "*" for any
+    pub kind: String,
+    pub min_severity: Severity,
+    /// Channel names, or "all"
+    pub channels: Vec<String>,
+}
+
+impl Route {
+    fn matches(&self, alert: &Alert) -> bool {
+        let kind = if self.kind == "*" {
+            !OPT_IN_KINDS.contains(&alert.kind.as_str())
+        } else {
+            self.kind.eq_ignore_ascii_case(&alert.kind)
+        };
+        kind && alert.severity >= self.min_severity
+    }
+}
+
+fn parse_severity(value: &str) -> Result<Severity> {
+    match value.to_ascii_lowercase().as_str() {
+        "info" => Ok(Severity::Info),
+        "warning" | "warn" => Ok(Severity::Warning),
+        "critical" => Ok(Severity::Critical),
+        other => anyhow::bail!("Unknown severity '{}'", other),
+    }
+}
+
+/// Parse `NOTIFY_ROUTES`.
+pub fn parse_routes(spec: &str) -> Result<Vec<Route>> {
+    spec.split(';')
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(|rule| {
+            let (matcher, channels) = rule.split_once('=').with_context(|| format!("Route '{}' needs '='", rule))?;
+            let (kind, severity) = match matcher.trim().split_once('@') {
+                Some((kind, severity)) => (kind, parse_severity(severity.trim())?),
+                None => (matcher, Severity::Info),
+            };
+            let channels = channels
+                .split('+')
+                .map(|c| c.trim().to_ascii_lowercase())
+                .filter(|c| !c.is_empty() && c != "none")
+                .collect();
+            Ok(Route { kind: kind.trim().to_string(), min_severity: severity, channels })
+        })
+        .collect()
+}
+
+/// Whether to deliver, and how many repeats were held back before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Send { suppressed: u32 },
+    Suppress,
+}
+
+#[derive(Debug, Default)]
+struct Throttle {
+    /// Last delivery and repeats held back since, per channel/kind/title
+    recent: HashMap<(String, String, String), (DateTime<Utc>, u32)>,
+    /// Delivery times in the last minute, per channel
+    sent: HashMap<String, Vec<DateTime<Utc>>>,
+}
+
+/// Routes alerts to channels and throttles deliveries.
+#[derive(Debug)]
+pub struct Router {
+    routes: Vec<Route>,
+    dedup_window: Duration,
+    /// 0 = unlimited
+    max_per_minute: usize,
+    throttle: Mutex<Throttle>,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Self::new(Vec::new(), Duration::zero(), 0)
+    }
+}
+
+impl Router {
+    pub fn new(routes: Vec<Route>, dedup_window: Duration, max_per_minute: usize) -> Self {
+        Self {
+            routes,
+            dedup_window,
+            max_per_minute,
+            throttle: Mutex::new(Throttle::default()),
+        }
+    }
+
+    /// Router from NOTIFY_ROUTES / NOTIFY_DEDUP_SECS / NOTIFY_MAX_PER_MINUTE.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let routes = parse_routes(&config.notify_routes).context("Invalid NOTIFY_ROUTES")?;
+        Ok(Self::new(routes, Duration::seconds(config.notify_dedup_secs as i64), config.notify_max_per_minute))
+    }
+
+    pub fn has_routes(&self) -> bool {
+        !self.routes.is_empty()
+    }
+
+    /// Whether a rule names `kind` explicitly.
+    pub fn names_kind(&self, kind: &str) -> bool {
+        self.routes.iter().any(|r| r.kind.eq_ignore_ascii_case(kind) && !r.channels.is_empty())
+    }
+
+    /// Whether `channel` should receive `alert`; `accepts` is the channel's
+    /// own filter, used when no rules are configured.
+    pub fn routes_to(&self, alert: &Alert, channel: &str, accepts: bool) -> bool {
+        if self.routes.is_empty() {
+            return accepts && !OPT_IN_KINDS.contains(&alert.kind.as_str());
+        }
+        self.routes
+            .iter()
+            .find(|r| r.matches(alert))
+            .is_some_and(|r| r.channels.iter().any(|c| c == "all" || c == channel))
+    }
+
+    /// Record an intended delivery, holding back repeats and bursts.
+    pub fn admit(&self, channel: &str, alert: &Alert, now: DateTime<Utc>) -> Admission {
+        let mut throttle = self.throttle.lock().unwrap();
+        let key = (channel.to_string(), alert.kind.clone(), alert.title.clone());
+
+        let mut suppressed = 0;
+        if let Some((last, held)) = throttle.recent.get_mut(&key) {
+            if now - *last < self.dedup_window {
+                *held += 1;
+                return Admission::Suppress;
+            }
+            suppressed = *held;
+        }
+
+        if self.max_per_minute > 0 {
+            let sent = throttle.sent.entry(channel.to_string()).or_default();
+            sent.retain(|t| now - *t < Duration::minutes(1));
+            if sent.len() >= self.max_per_minute {
+                // Counted against the next delivery without blocking it
+                let outside_window = now - self.dedup_window - Duration::seconds(1);
+                throttle.recent.entry(key).or_insert((outside_window, 0)).1 += 1;
+                return Admission::Suppress;
+            }
+            sent.push(now);
+        }
+        throttle.recent.insert(key, (now, 0));
+        Admission::Send { suppressed }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routing_and_throttling() {
+        let routes = parse_routes("breaker=telegram+email; opportunity=discord; *@critical=telegram; *=none").unwrap();
+        assert_eq!(routes[2].min_severity, Severity::Critical);
+        assert!(parse_routes("breaker").is_err());
+        assert!(parse_routes("*@loud=telegram").is_err());
+
+        let router = Router::new(routes, Duration::minutes(5), 2);
+        let breaker = Alert::new(Severity::Critical, "breaker", "Tripped", "");
+        let opportunity = Alert::new(Severity::Info, "opportunity", "BTC 3%", "");
+        let critical = Alert::new(Severity::Critical, "balance", "Low", "");
+        let info = Alert::new(Severity::Info, "watchlist", "BTC", "");
+        assert!(router.routes_to(&breaker, "email", false) && router.routes_to(&breaker, "telegram", true));
+        assert!(!router.routes_to(&breaker, "discord", true));
+        assert!(router.routes_to(&opportunity, "discord", true));
+        assert!(router.routes_to(&critical, "telegram", true) && !router.routes_to(&critical, "discord", true));
+        assert!(!router.routes_to(&info, "telegram", true));
+        assert!(router.names_kind("opportunity") && !router.names_kind("balance"));
+        // Catch-all rules never match opportunities
+        let catch_all = Router::new(parse_routes("*=all").unwrap(), Duration::zero(), 0);
+        assert!(!catch_all.routes_to(&opportunity, "discord", true));
+
+        // Without rules, channels decide, and opportunities are opt-in
+        let open = Router::default();
+        assert!(open.routes_to(&info, "discord", true) && !open.routes_to(&info, "email", false));
+        assert!(!open.routes_to(&opportunity, "discord", true));
+
+        // A flapping alert is sent once per window, then reports the repeats
+        let now = Utc::now();
+        assert_eq!(router.admit("telegram", &breaker, now), Admission::Send { suppressed: 0 });
+        for i in 1..=3 {
+            assert_eq!(router.admit("telegram", &breaker, now + Duration::seconds(i)), Admission::Suppress);
+        }
+        assert_eq!(router.admit("email", &breaker, now), Admission::Send { suppressed: 0 });
+        let later = now + Duration::minutes(6);
+        assert_eq!(router.admit("telegram", &breaker, later), Admission::Send { suppressed: 3 });
+
+        // At most two per minute per channel
+        assert_eq!(router.admit("telegram", &critical, later), Admission::Send { suppressed: 0 });
+        assert_eq!(router.admit("telegram", &info, later), Admission::Suppress);
+        let next_minute = later + Duration::minutes(1);
+        assert_eq!(router.admit("telegram", &info, next_minute), Admission::Send { suppressed: 1 });
+    }
+}
@@ -0,0 +1,218 @@
+//! Outbound webhooks for external consumers.
+//!
+//! Opportunity, fill, market move and lag events are POSTed as JSON to a configured URL.
+//! With a secret set, each request carries an HMAC-SHA256 signature over
+//! `"{timestamp}.{body}"` in `X-Polybot-Signature` (as `sha256=<hex>`) and
+//! the timestamp in `X-Polybot-Timestamp`, so receivers can verify the
+//! sender and reject replays.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::outbox::{Outbox, Pending, OUTBOX_FILE};
+use crate::arbitrage::{LagSignal, MarketMove, Opportunity};
+use crate::config::Config;
+use crate::utils::http_pool;
+use crate::utils::run_info::{self, RunStamp};
+use crate::utils::Fill;
+
+/// Immediate delivery attempts per event before it is queued.
+const MAX_ATTEMPTS: u32 = 3;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// One webhook payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Unique per event, for receivers to de-duplicate retries
+    pub id: String,
+    /// "opportunity", "fill", "market_move" or "lag"
+    pub event: String,
+    pub at: DateTime<Utc>,
+    pub data: serde_json::Value,
+    /// Bot process that sent the event
+    pub run: RunStamp,
+}
+
+impl WebhookEvent {
+    fn new(event: &str, data: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            event: event.to_string(),
+            at: Utc::now(),
+            data,
+            run: run_info::current().clone(),
+        }
+    }
+
+    /// A detected arbitrage opportunity, with its edge before and after fees.
+    pub fn opportunity(opportunity: &Opportunity) -> Self {
+        let mut data = serde_json::to_value(opportunity).unwrap_or_default();
+        data["edge"] = opportunity.edge().into();
+        data["net_edge"] = opportunity.net_edge().into();
+        Self::new("opportunity", data)
+    }
+
+    /// One of our orders filling.
+    pub fn fill(fill: &Fill) -> Self {
+        Self::new("fill", serde_json::to_value(fill).unwrap_or_default())
+    }
+
+    /// Polymarket trailing a Kalshi move.
+    pub fn lag(signal: &LagSignal) -> Self {
+        Self::new("lag", serde_json::to_value(signal).unwrap_or_default())
+    }
+
+    /// A sudden change in a watched book.
+    pub fn market_move(market_move: &MarketMove) -> Self {
+        Self::new("market_move", serde_json::to_value(market_move).unwrap_or_default())
+    }
+}
+
+/// Hex HMAC-SHA256 of `"{timestamp}.{body}"`.
+pub fn signature(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Webhook endpoint.
+pub struct Webhook {
+    url: String,
+    secret: Option<String>,
+    http: Client,
+    /// Where events that could not be delivered wait for a retry
+    outbox: Option<Outbox>,
+}
+
+impl Webhook {
+    pub fn new(url: &str, secret: Option<String>) -> Self {
+        Self {
+            url: url.to_string(),
+            secret,
+            http: http_pool::client(),
+            outbox: None,
+        }
+    }
+
+    /// Queue events that exhaust their attempts in `outbox` instead of
+    /// dropping them.
+    pub fn with_outbox(mut self, outbox: Outbox) -> Self {
+        self.outbox = Some(outbox);
+        self
+    }
+
+    /// Webhook from WEBHOOK_URL / WEBHOOK_SECRET, or `None` without a URL.
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        let url = config.webhook_url.as_ref()?;
+        Some(Arc::new(Self::new(url, config.webhook_secret.clone()).with_outbox(Outbox::new(OUTBOX_FILE))))
+    }
+
+    async fn post(&self, event: &WebhookEvent) -> Result<()> {
+        let body = serde_json::to_string(event)?;
+        let timestamp = Utc::now().timestamp();
+        let mut request = self.http
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header("X-Polybot-Event", &event.event)
+            .header("X-Polybot-Timestamp", timestamp.to_string());
+        if let Some(secret) = &self.secret {
+            request = request.header("X-Polybot-Signature", format!("sha256={}", signature(secret, timestamp, &body)));
+        }
+        let request = request.body(body).timeout(Duration::from_secs(10));
+        let response = http_pool::send(request).await.context("Failed to reach webhook")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Webhook rejected event: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Deliver an event, retrying with backoff, then queueing it in the
+    /// outbox. Failures are logged, never fatal.
+    pub async fn deliver(&self, event: &WebhookEvent) {
+        for attempt in 1..=MAX_ATTEMPTS {
+            match self.post(event).await {
+                Ok(()) => return,
+                Err(e) if attempt == MAX_ATTEMPTS => self.queue(event, attempt, &e.to_string()),
+                Err(_) => tokio::time::sleep(Duration::from_secs(1 << attempt)).await,
+            }
+        }
+    }
+
+    fn queue(&self, event: &WebhookEvent, attempts: u32, error: &str) {
+        let Some(outbox) = &self.outbox else {
+            warn!("Dropping {} webhook {} after {} attempts: {}", event.event, event.id, attempts, error);
+            return;
+        };
+        warn!("Queued {} webhook {} for retry after {} attempts: {}", event.event, event.id, attempts, error);
+        if let Err(e) = outbox.push(Pending::Webhook { event: event.clone() }, error, Utc::now()) {
+            error!("Failed to queue {} webhook {}: {}", event.event, event.id, e);
+        }
+    }
+
+    /// Retry queued events that are due, once each. They keep their id so
+    /// receivers can de-duplicate.
+    pub async fn retry_pending(&self) {
+        let Some(outbox) = &self.outbox else {
+            return;
+        };
+        let now = Utc::now();
+        for entry in outbox.due(now) {
+            let Pending::Webhook { event } = &entry.item else {
+                continue;
+            };
+            let result = match self.post(event).await {
+                Ok(()) => {
+                    info!("📬 Delivered queued {} webhook {} after {} retries", event.event, event.id, entry.attempts + 1);
+                    outbox.delivered(&entry.id)
+                }
+                Err(e) => outbox.failed(&entry.id, &e.to_string(), now),
+            };
+            if let Err(e) = result {
+                error!("Failed to update notification outbox: {}", e);
+            }
+        }
+    }
+
+    /// Deliver in the background so callers never wait on the receiver.
+    pub fn emit(self: &Arc<Self>, event: WebhookEvent) {
+        let webhook = self.clone();
+        tokio::spawn(async move { webhook.deliver(&event).await });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_and_payload() {
+        // Reference value from `printf '1700000000.{}' | openssl dgst -sha256 -hmac secret`
+        assert_eq!(
+            signature("secret", 1_700_000_000, "{}"),
+            "b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+
+        let opportunity = Opportunity {
+            market: "BTC".to_string(),
+            yes_venue: "polymarket",
+            yes_price: 0.45,
+            no_venue: "kalshi",
+            no_price: 0.50,
+            fee: 0.02,
+            detected_at: Utc::now(),
+        };
+        let event = WebhookEvent::opportunity(&opportunity);
+        assert_eq!(event.event, "opportunity");
+        assert_eq!(event.data["yes_venue"], "polymarket");
+        assert!((event.data["net_edge"].as_f64().unwrap() - 0.03).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,216 @@
+//! Order book fills for paper trades.
+//!
+//! A simulated market order walks the book level by level instead of filling
+//! everything at the top, so a large order pays up through the depth while a
+//! small one fills at the touch. The bot saves the Polymarket books it reads
+//! during detection to `data/books.json` for the TUI's paper trades.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::fees::{FeeSchedule, Liquidity};
+use crate::kalshi::types::KalshiOrderBook;
+use crate::polymarket::types::{OrderBook, PriceLevel};
+use crate::utils::state;
+
+/// Latest YES book per matched pair, written by the bot.
+pub const BOOKS_FILE: &str = "data/books.json";
+/// Books older than this are not traded against.
+const BOOK_MAX_AGE_SECS: i64 = 60;
+
+/// Resting size at one price (0.0-1.0 scale, contracts).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// Both sides of a book, best level first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Depth {
+    pub bids: Vec<Level>,
+    pub asks: Vec<Level>,
+}
+
+impl Depth {
+    /// Sort levels best first and drop empty ones.
+    pub fn new(mut bids: Vec<Level>, mut asks: Vec<Level>) -> Self {
+        bids.retain(|l| l.size > 0.0);
+        asks.retain(|l| l.size > 0.0);
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+        Self { bids, asks }
+    }
+
+    pub fn from_polymarket(book: &OrderBook) -> Self {
+        let levels = |side: &[PriceLevel]| {
+            side.iter()
+                .filter_map(|l| Some(Level { price: l.price.parse().ok()?, size: l.size.parse().ok()? }))
+                .collect()
+        };
+        Self::new(levels(&book.bids), levels(&book.asks))
+    }
+
+    /// One side ("yes" or "no") of a Kalshi book.
+    pub fn from_kalshi(book: &KalshiOrderBook, side: &str) -> Self {
+        let levels = |side: &[(i32, i32)]| {
+            side.iter().map(|&(cents, count)| Level { price: cents as f64 / 100.0, size: count as f64 }).collect()
+        };
+        if side.eq_ignore_ascii_case("no") {
+            Self::new(levels(&book.no_bids), levels(&book.no_asks))
+        } else {
+            Self::new(levels(&book.yes_bids), levels(&book.yes_asks))
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    /// Market buy spending up to `usd`.
+    pub fn buy(&self, usd: f64) -> BookFill {
+        let mut fill = BookFill::default();
+        let mut left = usd;
+        for level in &self.asks {
+            if left <= 1e-9 {
+                break;
+            }
+            let size = level.size.min(left / level.price);
+            fill.levels.push(Level { price: level.price, size });
+            left -= size * level.price;
+        }
+        fill
+    }
+
+    /// Market buy of up to `contracts`.
+    pub fn buy_contracts(&self, contracts: f64) -> BookFill {
+        take(&self.asks, contracts)
+    }
+
+    /// Market sell of up to `contracts`.
+    pub fn sell(&self, contracts: f64) -> BookFill {
+        take(&self.bids, contracts)
+    }
+}
+
+/// Take up to `contracts` from `levels`, best first.
+fn take(levels: &[Level], contracts: f64) -> BookFill {
+    let mut fill = BookFill::default();
+    let mut left = contracts;
+    for level in levels {
+        if left <= 1e-9 {
+            break;
+        }
+        let size = level.size.min(left);
+        fill.levels.push(Level { price: level.price, size });
+        left -= size;
+    }
+    fill
+}
+
+/// What a market order took from each level it reached.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookFill {
+    pub levels: Vec<Level>,
+}
+
+impl BookFill {
+    pub fn contracts(&self) -> f64 {
+        self.levels.iter().map(|l| l.size).sum()
+    }
+
+    /// USD paid (or received).
+    pub fn notional(&self) -> f64 {
+        self.levels.iter().map(|l| l.size * l.price).sum()
+    }
+
+    /// Volume-weighted fill price.
+    pub fn vwap(&self) -> f64 {
+        let contracts = self.contracts();
+        if contracts > 0.0 {
+            self.notional() / contracts
+        } else {
+            0.0
+        }
+    }
+
+    /// Taker fee on the whole order.
+    pub fn fee(&self, fees: &FeeSchedule, venue: &str, market: &str) -> f64 {
+        fees.order_fee(venue, market, Liquidity::Taker, self.levels.iter().map(|l| (l.price, l.size)))
+    }
+}
+
+/// A book as the bot last read it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookSnapshot {
+    pub at: DateTime<Utc>,
+    pub venue: String,
+    pub depth: Depth,
+}
+
+impl BookSnapshot {
+    pub fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        (now - self.at).num_seconds() <= BOOK_MAX_AGE_SECS
+    }
+}
+
+/// Books by matched pair name.
+pub fn load_books(path: &str) -> HashMap<String, BookSnapshot> {
+    state::load(path)
+}
+
+/// Fresh book for `market`, if the bot has one.
+pub fn load_book(path: &str, market: &str, now: DateTime<Utc>) -> Option<BookSnapshot> {
+    load_books(path).remove(market).filter(|b| b.is_fresh(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::PaperTradingEngine;
+
+    #[test]
+    fn test_large_orders_walk_the_book() {
+        let depth = Depth::new(
+            vec![Level { price: 0.48, size: 100.0 }, Level { price: 0.50, size: 50.0 }],
+            vec![Level { price: 0.55, size: 20.0 }, Level { price: 0.52, size: 50.0 }, Level { price: 0.60, size: 0.0 }],
+        );
+        assert_eq!((depth.best_bid(), depth.best_ask()), (Some(0.50), Some(0.52)));
+
+        // $13 fits in the top level
+        let small = depth.buy(13.0);
+        assert_eq!(small.levels.len(), 1);
+        assert!((small.vwap() - 0.52).abs() < 1e-9);
+        // $37 takes the 50 @ 0.52 and 20 @ 0.55; the rest goes unfilled
+        let large = depth.buy(100.0);
+        assert!((large.contracts() - 70.0).abs() < 1e-9);
+        assert!((large.notional() - 37.0).abs() < 1e-9);
+        assert!(large.vwap() > small.vwap());
+        assert!((depth.sell(80.0).vwap() - (50.0 * 0.50 + 30.0 * 0.48) / 80.0).abs() < 1e-9);
+
+        // Kalshi rounds the whole order's fee up once: 0.8736 + 0.3465 -> 1.23
+        let fees = FeeSchedule::new();
+        let fee = large.fee(&fees, "kalshi", "KX");
+        assert_eq!(fee, fees.order_fee("kalshi", "KX", Liquidity::Taker, [(0.52, 50.0), (0.55, 20.0)]));
+        assert!((fee - 1.23).abs() < 1e-9);
+
+        let mut engine = PaperTradingEngine::in_memory("test", 1000.0);
+        let (id, fill) = engine.buy_from_book("BTC", "BTC", "Hourly", "kalshi", 30.0, &depth, "manual", 0.5).unwrap();
+        let trade = engine.trade_log.get_all().iter().find(|t| t.id == id).unwrap();
+        assert!((trade.entry_price - fill.vwap()).abs() < 1e-9);
+        assert_eq!(trade.fee, Some(fill.fee(&fees, "kalshi", "BTC")));
+        assert!((engine.portfolio.positions["BTC"].size - fill.contracts()).abs() < 1e-9);
+
+        // Selling more than the bids hold closes only what they absorb
+        let thin = Depth::new(vec![Level { price: 0.50, size: 20.0 }], Vec::new());
+        let (_, sold) = engine.sell_into_book("BTC", &thin).unwrap();
+        assert_eq!(sold.contracts(), 20.0);
+        assert!(engine.portfolio.positions.contains_key("BTC"));
+        assert!(engine.buy_from_book("ETH", "ETH", "Hourly", "kalshi", 10.0, &thin, "manual", 0.5).is_err());
+    }
+}
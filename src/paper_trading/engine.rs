@@ -1,53 +1,234 @@
 //! Paper trading engine - coordinates trading simulation.
 
-use super::{Portfolio, TradeLog, PaperTrade, Side};
+use super::book_fill::{BookFill, Depth};
+use super::session::{Session, SESSION_FILE};
+use super::{Portfolio, Profile, TradeLog, PaperTrade, Side, TradeStatus};
+use crate::arbitrage::Opportunity;
+use crate::fees::{FeeSchedule, Liquidity};
+use crate::risk::{CapitalAllocator, ConflictGuard, Exposure, Holding, Netting};
+use crate::utils::venue_constraints::{self, PairedOrder, Sizing, VenueConstraints};
+use crate::utils::{state, MarketId, Outcome};
 use anyhow::Result;
+use std::collections::HashMap;
+use tracing::warn;
 
-/// Default data directory
-const DATA_DIR: &str = "data";
-const DEFAULT_BALANCE: f64 = 1000.0;
+/// Persisted paper portfolio of the default profile
+pub const PORTFOLIO_FILE: &str = "data/portfolio.json";
+/// Starting balance of a new portfolio when PAPER_BALANCE is not read
+pub const DEFAULT_BALANCE: f64 = 1000.0;
 
 /// Paper trading engine that coordinates the simulation.
 pub struct PaperTradingEngine {
     pub portfolio: Portfolio,
     pub trade_log: TradeLog,
+    /// Per-strategy capital limits (unconstrained when None)
+    pub allocator: Option<CapitalAllocator>,
+    /// Policies for orders opposing other strategies (allowed when None)
+    pub conflicts: Option<ConflictGuard>,
+    /// Max netted loss on any one question (unlimited when None)
+    pub question_limit: Option<f64>,
+    /// Fees charged on simulated fills
+    pub fees: FeeSchedule,
+    /// Named portfolio this engine trades
+    pub profile: Profile,
+    /// This run, for P&L since start
+    pub session: Session,
 }
 
 impl PaperTradingEngine {
     /// Create a new paper trading engine.
-    pub fn new() -> Self {
-        // Ensure data directory exists
-        let _ = std::fs::create_dir_all(DATA_DIR);
+    pub fn new() -> Result<Self> {
+        Self::for_profile(&Profile::default(), DEFAULT_BALANCE)
+    }
 
-        let portfolio = Portfolio::load_or_create(
-            &format!("{}/portfolio.json", DATA_DIR),
-            DEFAULT_BALANCE,
-        );
-        let trade_log = TradeLog::new(&format!("{}/paper_trades.json", DATA_DIR));
+    /// Create with custom initial balance.
+    pub fn with_balance(initial_balance: f64) -> Result<Self> {
+        Self::for_profile(&Profile::default(), initial_balance)
+    }
 
-        Self {
+    /// Trade a named profile, creating it with `initial_balance` if new.
+    /// Fails when its files are encrypted and no data key is configured.
+    pub fn for_profile(profile: &Profile, initial_balance: f64) -> Result<Self> {
+        // Ensure the profile's directory exists
+        if let Some(parent) = std::path::Path::new(&profile.portfolio_file).parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+
+        let portfolio = Portfolio::load_or_create(&profile.portfolio_file, initial_balance)?;
+        let trade_log = TradeLog::new(&profile.trades_file)?;
+        let session = Session::start(&profile.name, &portfolio, trade_log.get_all().len(), chrono::Utc::now());
+
+        Ok(Self {
             portfolio,
             trade_log,
-        }
+            allocator: None,
+            conflicts: None,
+            question_limit: None,
+            fees: FeeSchedule::new(),
+            profile: profile.clone(),
+            session,
+        })
     }
 
-    /// Create with custom initial balance.
-    pub fn with_balance(initial_balance: f64) -> Self {
-        let _ = std::fs::create_dir_all(DATA_DIR);
-
-        let portfolio = Portfolio::load_or_create(
-            &format!("{}/portfolio.json", DATA_DIR),
-            initial_balance,
-        );
-        let trade_log = TradeLog::new(&format!("{}/paper_trades.json", DATA_DIR));
+    /// Read-only view of the persisted portfolio and trade log, for
+    /// observers. Nothing done to it is written back.
+    pub fn snapshot() -> Self {
+        Self::snapshot_of(&Profile::default())
+    }
 
+    /// Read-only view of a named profile. Its session is the running
+    /// bot's when the bot trades this profile.
+    pub fn snapshot_of(profile: &Profile) -> Self {
+        let portfolio = Portfolio::load(&profile.portfolio_file).unwrap_or_else(|| Portfolio::new(DEFAULT_BALANCE));
+        let trade_log = TradeLog::snapshot(&profile.trades_file);
+        let session = Session::load(SESSION_FILE)
+            .filter(|s| s.profile == profile.name)
+            .unwrap_or_else(|| Session::start(&profile.name, &portfolio, trade_log.get_all().len(), chrono::Utc::now()));
         Self {
             portfolio,
             trade_log,
+            allocator: None,
+            conflicts: None,
+            question_limit: None,
+            fees: FeeSchedule::new(),
+            profile: profile.clone(),
+            session,
+        }
+    }
+
+    /// Fresh portfolio that is never persisted, e.g. for replays. `name`
+    /// labels it in place of a profile.
+    pub fn in_memory(name: &str, initial_balance: f64) -> Self {
+        let portfolio = Portfolio::new(initial_balance);
+        let session = Session::start(name, &portfolio, 0, chrono::Utc::now());
+        Self {
+            portfolio,
+            trade_log: TradeLog::in_memory(),
+            allocator: None,
+            conflicts: None,
+            question_limit: None,
+            fees: FeeSchedule::new(),
+            profile: Profile { name: name.to_string(), ..Profile::default() },
+            session,
+        }
+    }
+
+    /// Run `f` on the latest saved portfolio and trade log, under the
+    /// portfolio file's lock, so the bot's controls, the TUI and the CLI
+    /// don't overwrite each other's trades. In-memory engines just run `f`.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let _lock = self.portfolio.file_path().and_then(|path| {
+            state::lock(path)
+                .map_err(|e| warn!("Failed to lock portfolio: {}", e))
+                .ok()
+        });
+        self.portfolio.reload();
+        self.trade_log.reload();
+        f(self)
+    }
+
+    /// Enforce per-strategy buying power with an allocator.
+    pub fn with_allocator(mut self, allocator: CapitalAllocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Enforce cross-strategy conflict policies.
+    pub fn with_conflicts(mut self, guard: ConflictGuard) -> Self {
+        self.conflicts = Some(guard);
+        self
+    }
+
+    /// Size allowed through the conflict policy for `strategy`.
+    fn resolve_conflicts(&self, strategy: &str, market: &str, side: Side, size: f64) -> Result<f64> {
+        match &self.conflicts {
+            Some(guard) => guard.resolve(strategy, market, side, size, self.trade_log.get_all()),
+            None => Ok(size),
+        }
+    }
+
+    /// Cap the loss on any one question, netted across the portfolio's
+    /// strategies and venues. Zero or less leaves it unlimited.
+    pub fn with_question_limit(mut self, max_loss: f64) -> Self {
+        self.question_limit = (max_loss > 0.0).then_some(max_loss);
+        self
+    }
+
+    /// Bail if `holdings` would push their question's worst-case loss over
+    /// the limit.
+    fn check_question_loss(&self, holdings: &[Holding]) -> Result<()> {
+        let Some(limit) = self.question_limit else {
+            return Ok(());
+        };
+        let mut netting = Netting::new();
+        netting.add_paper(&self.portfolio, self.trade_log.get_all());
+        netting.check_loss(holdings, limit)
+    }
+
+    /// Charge fees from a specific schedule.
+    pub fn with_fees(mut self, fees: FeeSchedule) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Bail unless the available balance covers the cost plus fees.
+    fn check_cash(&self, cost: f64, fee: f64) -> Result<()> {
+        self.check_funds(self.available_balance(), cost, fee)
+    }
+
+    fn check_funds(&self, available: f64, cost: f64, fee: f64) -> Result<()> {
+        if cost + fee > available + 1e-9 {
+            anyhow::bail!(
+                "Insufficient balance: ${:.2} available, ${:.2} needed (${:.2} fees)",
+                available, cost + fee, fee
+            );
+        }
+        Ok(())
+    }
+
+    /// Collateral a working order locks: what is left of it plus the fee
+    /// on that, held back from the balance until it fills or is cancelled,
+    /// as Kalshi does for resting orders.
+    fn order_collateral(&self, trade: &PaperTrade) -> f64 {
+        let remaining = trade.remaining_size();
+        if trade.status != TradeStatus::Open || remaining <= 1e-9 || trade.entry_price <= 0.0 {
+            return 0.0;
+        }
+        let contracts = remaining / trade.entry_price;
+        remaining + self.fees.fee(&trade.platform, &trade.market, Liquidity::Taker, contracts, trade.entry_price)
+    }
+
+    /// Collateral locked per venue, by open positions and working orders.
+    pub fn locked_by_venue(&self) -> HashMap<String, f64> {
+        let mut locked = self.portfolio.locked_by_venue();
+        for trade in self.trade_log.get_all() {
+            let collateral = self.order_collateral(trade);
+            if collateral > 0.0 {
+                *locked.entry(trade.platform.clone()).or_default() += collateral;
+            }
         }
+        locked
+    }
+
+    /// Collateral locked by open positions and working orders.
+    pub fn locked_collateral(&self) -> f64 {
+        self.locked_by_venue().values().sum()
+    }
+
+    /// Cash free to back new orders: what working orders lock is held back.
+    pub fn available_balance(&self) -> f64 {
+        let held: f64 = self.trade_log.get_all().iter().map(|t| self.order_collateral(t)).sum();
+        self.portfolio.cash_balance - held
+    }
+
+    /// Cash plus the entry cost of open positions. Unrealized gains are
+    /// left out since they cannot back new orders.
+    fn account_balance(&self) -> f64 {
+        self.portfolio.cash_balance + self.portfolio.cost_basis()
     }
 
     /// Execute a paper trade (buy).
+    #[allow(clippy::too_many_arguments)]
     pub fn buy(
         &mut self,
         market: &str,
@@ -59,9 +240,22 @@ impl PaperTradingEngine {
         strategy: &str,
         confidence: f64,
     ) -> Result<String> {
+        // Positions other strategies hold on the other side
+        let size_usd = self.resolve_conflicts(strategy, market, Side::Buy, size_usd)?;
+        self.check_question_loss(&[holding(market, platform, strategy, size_usd / price, size_usd)])?;
+
+        // Strategy/venue buying power
+        if let Some(allocator) = &self.allocator {
+            allocator.check(strategy, platform, size_usd, &self.exposure())?;
+        }
+
+        let fee = self.fees.fee(platform, market, Liquidity::Taker, size_usd / price, price);
+        self.check_cash(size_usd, fee)?;
+
         // Open position in portfolio
         self.portfolio.open_position(market, coin, platform, size_usd, price)
             .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_position_fee(market, fee);
 
         // Log the trade
         let trade = PaperTrade::new(
@@ -74,33 +268,324 @@ impl PaperTradingEngine {
             price,
             strategy,
             confidence,
-        );
+        )
+        .with_fee(fee);
         let trade_id = trade.id.clone();
         self.trade_log.add_trade(trade);
 
         Ok(trade_id)
     }
 
+    /// Execute a paper trade sized in shares/contracts; the USD amount is
+    /// derived from the price.
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_contracts(
+        &mut self,
+        market: &str,
+        coin: &str,
+        timeframe: &str,
+        platform: &str,
+        contracts: f64,
+        price: f64,
+        strategy: &str,
+        confidence: f64,
+    ) -> Result<String> {
+        let contracts = self.resolve_conflicts(strategy, market, Side::Buy, contracts * price)? / price;
+        let size_usd = contracts * price;
+        self.check_question_loss(&[holding(market, platform, strategy, contracts, size_usd)])?;
+        if let Some(allocator) = &self.allocator {
+            allocator.check(strategy, platform, size_usd, &self.exposure())?;
+        }
+
+        let fee = self.fees.fee(platform, market, Liquidity::Taker, contracts, price);
+        self.check_cash(size_usd, fee)?;
+
+        self.portfolio.open_contracts(market, coin, platform, contracts, price)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_position_fee(market, fee);
+
+        let trade = PaperTrade::new(
+            market, coin, timeframe, platform, Side::Buy, size_usd, price, strategy, confidence,
+        )
+        .with_fee(fee);
+        let trade_id = trade.id.clone();
+        self.trade_log.add_trade(trade);
+
+        Ok(trade_id)
+    }
+
+    /// Market buy of up to `size_usd` filled by walking the asks of `book`,
+    /// at the volume-weighted price with the fee of every level taken.
+    /// Whatever the book can't absorb goes unfilled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn buy_from_book(
+        &mut self,
+        market: &str,
+        coin: &str,
+        timeframe: &str,
+        platform: &str,
+        size_usd: f64,
+        book: &Depth,
+        strategy: &str,
+        confidence: f64,
+    ) -> Result<(String, BookFill)> {
+        let size_usd = self.resolve_conflicts(strategy, market, Side::Buy, size_usd)?;
+        let fill = book.buy(size_usd);
+        if fill.contracts() <= 0.0 {
+            anyhow::bail!("No asks on the {} book to fill against", market);
+        }
+        let (contracts, cost, price) = (fill.contracts(), fill.notional(), fill.vwap());
+        self.check_question_loss(&[holding(market, platform, strategy, contracts, cost)])?;
+        if let Some(allocator) = &self.allocator {
+            allocator.check(strategy, platform, cost, &self.exposure())?;
+        }
+
+        let fee = fill.fee(&self.fees, platform, market);
+        self.check_cash(cost, fee)?;
+
+        self.portfolio.open_contracts(market, coin, platform, contracts, price)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_position_fee(market, fee);
+
+        // The order is for the full size; what the book couldn't absorb is cancelled
+        let mut trade = PaperTrade::new(
+            market, coin, timeframe, platform, Side::Buy, size_usd.max(cost), price, strategy, confidence,
+        )
+        .unfilled()
+        .with_fee(fee);
+        trade.add_fill(price, cost);
+        trade.cancel_remaining();
+        let trade_id = trade.id.clone();
+        self.trade_log.add_trade(trade);
+
+        Ok((trade_id, fill))
+    }
+
+    /// Place a buy order for `size_usd` at `price` that fills later, in one
+    /// or more [`fill_order`](Self::fill_order) calls. Nothing is held until
+    /// it fills, but the order locks its cost and fee from the available
+    /// balance while it works.
+    #[allow(clippy::too_many_arguments)]
+    pub fn place_order(
+        &mut self,
+        market: &str,
+        coin: &str,
+        timeframe: &str,
+        platform: &str,
+        size_usd: f64,
+        price: f64,
+        strategy: &str,
+        confidence: f64,
+    ) -> Result<String> {
+        if price <= 0.0 || size_usd <= 0.0 {
+            anyhow::bail!("Invalid order: ${:.2} at {}", size_usd, price);
+        }
+        let size_usd = self.resolve_conflicts(strategy, market, Side::Buy, size_usd)?;
+        self.check_question_loss(&[holding(market, platform, strategy, size_usd / price, size_usd)])?;
+        if let Some(allocator) = &self.allocator {
+            allocator.check(strategy, platform, size_usd, &self.exposure())?;
+        }
+        let fee = self.fees.fee(platform, market, Liquidity::Taker, size_usd / price, price);
+        self.check_cash(size_usd, fee)?;
+
+        let trade = PaperTrade::new(
+            market, coin, timeframe, platform, Side::Buy, size_usd, price, strategy, confidence,
+        )
+        .unfilled();
+        let trade_id = trade.id.clone();
+        self.trade_log.add_trade(trade);
+        Ok(trade_id)
+    }
+
+    /// Fill up to `contracts` of a working order at `price`, opening them
+    /// in the portfolio. Returns the contracts filled, which stop at the
+    /// order's remaining size.
+    pub fn fill_order(&mut self, trade_id: &str, contracts: f64, price: f64) -> Result<f64> {
+        let trade = self.trade_log.get(trade_id)
+            .ok_or_else(|| anyhow::anyhow!("No trade found with ID {}", trade_id))?;
+        if trade.status != TradeStatus::Open {
+            anyhow::bail!("Trade {} is no longer open", trade_id);
+        }
+        if price <= 0.0 {
+            anyhow::bail!("Invalid fill price: {}", price);
+        }
+        let contracts = contracts.min(trade.remaining_size() / price);
+        if contracts <= 0.0 {
+            anyhow::bail!("Nothing left to fill on trade {}", trade_id);
+        }
+        let (market, coin, platform) = (trade.market.clone(), trade.coin.clone(), trade.platform.clone());
+
+        // The order's own collateral pays for its fills
+        let fee = self.fees.fee(&platform, &market, Liquidity::Taker, contracts, price);
+        self.check_funds(self.available_balance() + self.order_collateral(trade), contracts * price, fee)?;
+        self.portfolio.open_contracts(&market, &coin, &platform, contracts, price)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_position_fee(&market, fee);
+        self.trade_log.fill_trade(trade_id, price, contracts * price, fee);
+        Ok(contracts)
+    }
+
+    /// Cancel what hasn't filled of a working order, returning the USD
+    /// amount cancelled. An order with no fills is cancelled outright.
+    pub fn cancel_order(&mut self, trade_id: &str) -> Result<f64> {
+        self.trade_log.cancel_remaining(trade_id)
+            .ok_or_else(|| anyhow::anyhow!("No trade found with ID {}", trade_id))
+    }
+
+    /// Paper-trade both legs of an arb with identical contract counts.
+    ///
+    /// Legs are held as "<market> YES" and "<market> NO". Nothing is opened
+    /// unless both legs fit the venues, the cash balance and buying power.
+    pub fn buy_arb(
+        &mut self,
+        opportunity: &Opportunity,
+        coin: &str,
+        timeframe: &str,
+        sizing: Sizing,
+        strategy: &str,
+    ) -> Result<PairedOrder> {
+        let order = venue_constraints::fit_pair(
+            &VenueConstraints::for_venue(opportunity.yes_venue)?,
+            &VenueConstraints::for_venue(opportunity.no_venue)?,
+            opportunity.yes_price,
+            opportunity.no_price,
+            sizing.contracts(opportunity.cost()),
+        )?;
+
+        let fees = self.fees.arb_fee(
+            &opportunity.market,
+            (opportunity.yes_venue, order.price_a),
+            (opportunity.no_venue, order.price_b),
+            order.size,
+        );
+        self.check_cash(order.cost(), fees)?;
+        // Legs are paired, so a leg can't be netted down on its own
+        for (outcome, usd) in [("YES", order.usd_a()), ("NO", order.usd_b())] {
+            let market = format!("{} {}", opportunity.market, outcome);
+            if self.resolve_conflicts(strategy, &market, Side::Buy, usd)? < usd - 1e-9 {
+                anyhow::bail!("{} leg of {} is netted by an opposing position", outcome, opportunity.market);
+            }
+        }
+        self.check_question_loss(&[
+            holding(&format!("{} YES", opportunity.market), opportunity.yes_venue, strategy, order.size, order.usd_a()),
+            holding(&format!("{} NO", opportunity.market), opportunity.no_venue, strategy, order.size, order.usd_b()),
+        ])?;
+        if let Some(allocator) = &self.allocator {
+            let mut exposure = self.exposure();
+            allocator.check(strategy, opportunity.yes_venue, order.usd_a(), &exposure)?;
+            exposure.add(strategy, opportunity.yes_venue, order.usd_a());
+            allocator.check(strategy, opportunity.no_venue, order.usd_b(), &exposure)?;
+        }
+
+        let legs = [
+            ("YES", Outcome::Yes, opportunity.yes_venue, order.price_a),
+            ("NO", Outcome::No, opportunity.no_venue, order.price_b),
+        ];
+        for (label, outcome, venue, price) in legs {
+            let market = format!("{} {}", opportunity.market, label);
+            self.portfolio.open_contracts(&market, coin, venue, order.size, price)
+                .map_err(|e| anyhow::anyhow!(e))?;
+            // Marked on the pair's leg, at the side held
+            if let Some(native) = self.fees.native_id(venue, &opportunity.market) {
+                self.portfolio.link_market(&market, &MarketId::on(venue, &native)?.with_outcome(outcome));
+            }
+            let fee = self.fees.fee(venue, &opportunity.market, Liquidity::Taker, order.size, price);
+            self.portfolio.record_position_fee(&market, fee);
+            self.trade_log.add_trade(
+                PaperTrade::new(&market, coin, timeframe, venue, Side::Buy, order.size * price, price, strategy, 1.0)
+                    .with_fee(fee),
+            );
+        }
+
+        Ok(order)
+    }
+
+    /// Capital deployed per strategy (open trades) and per venue (locked collateral).
+    fn exposure(&self) -> Exposure {
+        Exposure::from_trades(self.trade_log.get_all())
+            .with_locked_collateral(self.locked_by_venue())
+    }
+
     /// Close a position (sell).
     pub fn sell(&mut self, market: &str, exit_price: f64) -> Result<f64> {
+        let fee = self.portfolio.positions.get(market)
+            .map(|p| self.fees.fee(&p.platform, market, Liquidity::Taker, p.size, exit_price))
+            .unwrap_or(0.0);
+        self.close(market, exit_price, fee)
+    }
+
+    /// Market sell of a position into the bids of `book`, at the
+    /// volume-weighted price. If the bids can't absorb it all, only what
+    /// they take is closed.
+    pub fn sell_into_book(&mut self, market: &str, book: &Depth) -> Result<(f64, BookFill)> {
+        let position = self.portfolio.positions.get(market)
+            .ok_or_else(|| anyhow::anyhow!("No position found for {}", market))?;
+        let fill = book.sell(position.size);
+        if fill.contracts() <= 0.0 {
+            anyhow::bail!("No bids on the {} book to sell into", market);
+        }
+        let fee = fill.fee(&self.fees, &position.platform, market);
+        let pnl = if fill.contracts() >= position.size - 1e-9 {
+            self.close(market, fill.vwap(), fee)?
+        } else {
+            let pnl = self.portfolio.reduce_position(market, fill.contracts(), fill.vwap())
+                .map_err(|e| anyhow::anyhow!(e))?;
+            self.portfolio.record_fee(fee);
+            pnl
+        };
+        Ok((pnl, fill))
+    }
+
+    /// Close all of a position at `exit_price`, paying `fee`.
+    fn close(&mut self, market: &str, exit_price: f64, fee: f64) -> Result<f64> {
         // Close position in portfolio
         let pnl = self.portfolio.close_position(market, exit_price)
             .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_fee(fee);
 
         // Find and close the trade in log
         // Clone the ID first to avoid borrow issues
         let trade_id: Option<String> = self.trade_log.get_open()
             .iter()
-            .find(|t| t.market == market)
+            .find(|t| t.market == market && t.filled_size() > 0.0)
             .map(|t| t.id.clone());
         
         if let Some(id) = trade_id {
+            // Whatever is still working is pulled before the position closes
+            self.trade_log.cancel_remaining(&id);
             self.trade_log.close_trade(&id, exit_price);
         }
 
         Ok(pnl)
     }
 
+    /// Close `fraction` (0-1] of a position; a full fraction closes it
+    /// outright.
+    pub fn sell_fraction(&mut self, market: &str, fraction: f64, exit_price: f64) -> Result<f64> {
+        if fraction >= 1.0 {
+            return self.sell(market, exit_price);
+        }
+        let position = self.portfolio.positions.get(market)
+            .ok_or_else(|| anyhow::anyhow!("No position found for {}", market))?;
+        let shares = position.size * fraction;
+        let fee = self.fees.fee(&position.platform, market, Liquidity::Taker, shares, exit_price);
+
+        let pnl = self.portfolio.reduce_position(market, shares, exit_price)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        self.portfolio.record_fee(fee);
+        Ok(pnl)
+    }
+
+    /// Close every position marked at or below its stop, returning
+    /// (market, P&L) for each.
+    pub fn check_stops(&mut self) -> Vec<(String, f64)> {
+        self.portfolio
+            .triggered_stops()
+            .into_iter()
+            .filter_map(|(market, mark)| self.sell(&market, mark).ok().map(|pnl| (market, pnl)))
+            .collect()
+    }
+
     /// Get current portfolio summary.
     pub fn summary(&self) -> PortfolioSummary {
         let (win_rate, wins, total) = self.trade_log.win_rate();
@@ -108,10 +593,18 @@ impl PaperTradingEngine {
         PortfolioSummary {
             total_value: self.portfolio.total_value(),
             cash_balance: self.portfolio.cash_balance,
+            locked_collateral: self.locked_collateral(),
             positions_count: self.portfolio.position_count(),
             realized_pnl: self.portfolio.realized_pnl,
             unrealized_pnl: self.portfolio.unrealized_pnl(),
             total_pnl: self.portfolio.total_pnl(),
+            fees_paid: self.portfolio.fees_paid,
+            net_pnl: self.portfolio.total_pnl() - self.portfolio.fees_paid,
+            avg_fee_per_trade: match self.trade_log.get_all().len() {
+                0 => 0.0,
+                trades => self.portfolio.fees_paid / trades as f64,
+            },
+            session_pnl: self.session.pnl_of(&self.portfolio),
             pnl_percent: self.portfolio.pnl_percent(),
             win_rate,
             wins,
@@ -121,11 +614,42 @@ impl PaperTradingEngine {
         }
     }
 
+    /// Re-size allocations to the account balance and apply the rebalancing
+    /// rule. Unrealized gains are excluded since they cannot back new orders.
+    pub fn rebalance(&mut self) -> bool {
+        let bankroll = self.account_balance();
+        let Some(allocator) = &mut self.allocator else {
+            return false;
+        };
+        allocator.set_bankroll(bankroll);
+        allocator.rebalance(self.trade_log.get_all(), chrono::Utc::now())
+    }
+
+    /// Re-size allocations to the account balance and pick up the weights
+    /// the bot last rebalanced to.
+    pub fn refresh_allocation(&mut self) {
+        let bankroll = self.account_balance();
+        if let Some(allocator) = &mut self.allocator {
+            allocator.set_bankroll(bankroll);
+            allocator.reload();
+        }
+    }
+
     /// Reset the engine (clear all trades and positions).
     pub fn reset(&mut self) {
         self.portfolio.reset();
+        self.session = Session::start(&self.profile.name, &self.portfolio, self.trade_log.get_all().len(), chrono::Utc::now());
         // Note: Trade log is not cleared, for historical reference
     }
+
+    /// Start over from `balance` with an empty trade log. The old log is
+    /// archived next to the current one; returns the archive path.
+    pub fn reset_to(&mut self, balance: f64) -> Result<Option<String>> {
+        let archived = self.trade_log.archive()?;
+        self.portfolio.reset_to(balance);
+        self.session = Session::start(&self.profile.name, &self.portfolio, 0, chrono::Utc::now());
+        Ok(archived)
+    }
 }
 
 /// Summary of portfolio performance.
@@ -133,10 +657,19 @@ impl PaperTradingEngine {
 pub struct PortfolioSummary {
     pub total_value: f64,
     pub cash_balance: f64,
+    pub locked_collateral: f64,
     pub positions_count: usize,
     pub realized_pnl: f64,
     pub unrealized_pnl: f64,
+    /// Realized + unrealized, before fees
     pub total_pnl: f64,
+    /// Fees paid since the portfolio started
+    pub fees_paid: f64,
+    /// Total P&L after fees
+    pub net_pnl: f64,
+    pub avg_fee_per_trade: f64,
+    /// P&L since the session started
+    pub session_pnl: f64,
     pub pnl_percent: f64,
     pub win_rate: f64,
     pub wins: usize,
@@ -145,8 +678,80 @@ pub struct PortfolioSummary {
     pub worst_trade_pnl: Option<f64>,
 }
 
-impl Default for PaperTradingEngine {
-    fn default() -> Self {
-        Self::new()
+impl PortfolioSummary {
+    /// Share of the gross P&L fees took, while there is a gross profit.
+    pub fn fee_share(&self) -> Option<f64> {
+        (self.total_pnl > 0.0).then(|| self.fees_paid / self.total_pnl)
+    }
+}
+
+/// A pending buy, for netting checks.
+fn holding(market: &str, venue: &str, strategy: &str, shares: f64, cost: f64) -> Holding {
+    Holding {
+        market: market.to_string(),
+        venue: venue.to_string(),
+        strategy: strategy.to_string(),
+        shares,
+        cost,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_working_orders_lock_collateral_until_filled_or_cancelled() {
+        let mut engine = PaperTradingEngine::in_memory("test", 100.0);
+        let fee = engine.fees.fee("kalshi", "KXA", Liquidity::Taker, 120.0, 0.50);
+        let id = engine.place_order("KXA", "BTC", "Hourly", "kalshi", 60.0, 0.50, "manual", 1.0).unwrap();
+
+        // Nothing is spent yet, but the order's cost and fee are held back
+        assert_eq!(engine.portfolio.cash_balance, 100.0);
+        assert!((engine.available_balance() - (40.0 - fee)).abs() < 1e-9);
+        assert!((engine.locked_by_venue()["kalshi"] - (60.0 + fee)).abs() < 1e-9);
+        assert!((engine.summary().locked_collateral - (60.0 + fee)).abs() < 1e-9);
+        let err = engine.place_order("KXB", "BTC", "Hourly", "kalshi", 60.0, 0.50, "manual", 1.0).unwrap_err();
+        assert!(err.to_string().contains("Insufficient balance"));
+
+        // The order's own collateral pays for its fill
+        assert_eq!(engine.fill_order(&id, 120.0, 0.50).unwrap(), 120.0);
+        assert!((engine.available_balance() - engine.portfolio.cash_balance).abs() < 1e-9);
+        assert!((engine.locked_collateral() - 60.0).abs() < 1e-9);
+
+        // Cancelling releases what an order held
+        let id = engine.place_order("KXB", "BTC", "Hourly", "kalshi", 20.0, 0.50, "manual", 1.0).unwrap();
+        assert!(engine.available_balance() < engine.portfolio.cash_balance - 20.0);
+        engine.cancel_order(&id).unwrap();
+        assert!((engine.available_balance() - engine.portfolio.cash_balance).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summary_nets_fees_out_of_pnl() {
+        let mut engine = PaperTradingEngine::in_memory("test", 1000.0);
+        let fee = |engine: &PaperTradingEngine, market: &str, shares: f64, price: f64| {
+            engine.fees.fee("kalshi", market, Liquidity::Taker, shares, price)
+        };
+        let entry_fees = fee(&engine, "KXA", 100.0, 0.50) + fee(&engine, "KXB", 75.0, 0.40);
+        let exit_fee = fee(&engine, "KXA", 100.0, 0.70);
+        engine.buy("KXA", "BTC", "Hourly", "kalshi", 50.0, 0.50, "manual", 1.0).unwrap();
+        engine.buy("KXB", "BTC", "Hourly", "kalshi", 30.0, 0.40, "manual", 1.0).unwrap();
+        engine.sell("KXA", 0.70).unwrap();
+
+        // Entry and exit fees both count, spread over the two trades
+        let summary = engine.summary();
+        assert!(entry_fees > 0.0 && exit_fee > 0.0);
+        assert!((summary.fees_paid - (entry_fees + exit_fee)).abs() < 1e-9);
+        assert!((summary.net_pnl - (summary.total_pnl - summary.fees_paid)).abs() < 1e-9);
+        assert!((summary.avg_fee_per_trade - summary.fees_paid / 2.0).abs() < 1e-9);
+        assert!((summary.fee_share().unwrap() - summary.fees_paid / summary.total_pnl).abs() < 1e-9);
+
+        // Fees are no share of a loss, and nothing is averaged over no trades
+        engine.sell("KXB", 0.05).unwrap();
+        assert!(engine.summary().total_pnl < 0.0);
+        assert_eq!(engine.summary().fee_share(), None);
+        let summary = PaperTradingEngine::in_memory("empty", 1000.0).summary();
+        assert_eq!((summary.fees_paid, summary.avg_fee_per_trade, summary.fee_share()), (0.0, 0.0, None));
     }
 }
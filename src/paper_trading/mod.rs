@@ -1,9 +1,17 @@
 //! Paper trading module for simulating trades without real money.
 
+pub mod book_fill;
 pub mod engine;
 pub mod portfolio;
+pub mod profiles;
+pub mod session;
+pub mod shadow;
 pub mod trade_log;
 
+pub use book_fill::{BookFill, Depth};
 pub use engine::PaperTradingEngine;
-pub use portfolio::{Portfolio, Position};
-pub use trade_log::{PaperTrade, TradeLog, TradeStatus, Side};
+pub use portfolio::{Lot, Portfolio, Position};
+pub use profiles::Profile;
+pub use session::{Session, SessionSummary};
+pub use shadow::ShadowBook;
+pub use trade_log::{PaperTrade, TradeFill, TradeLog, TradeStatus, Side};
@@ -1,9 +1,24 @@
 //! Portfolio management for paper trading.
 
+use anyhow::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
+use tracing::warn;
+
+use crate::utils::{state, MarketId};
+
+/// Marks kept per position for the detail view sparkline.
+const MARK_HISTORY_LEN: usize = 60;
+
+/// Shares bought in one fill.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lot {
+    pub opened_at: DateTime<Utc>,
+    pub shares: f64,
+    pub price: f64,
+}
 
 /// A position in a market
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +30,26 @@ pub struct Position {
     pub avg_price: f64,     // Average entry price
     pub current_price: f64, // Current market price
     pub unrealized_pnl: f64,
+    /// Venue market used for marking (`venue:id[:outcome]`, see
+    /// [`MarketId`]), when the position is keyed by a display name. Older
+    /// files hold the bare token ID or ticker
+    #[serde(default)]
+    pub market_id: Option<String>,
+    /// Entry fills still held, oldest first
+    #[serde(default)]
+    pub lots: Vec<Lot>,
+    /// Entry fees paid on this position
+    #[serde(default)]
+    pub fees_paid: f64,
+    /// Recent marks, oldest first
+    #[serde(default)]
+    pub mark_history: Vec<f64>,
+    /// Close the position once marked at or below this price
+    #[serde(default)]
+    pub stop_price: Option<f64>,
+    /// Trader this position copies, if any
+    #[serde(default)]
+    pub source: Option<String>,
 }
 
 impl Position {
@@ -24,6 +59,27 @@ impl Position {
         self.unrealized_pnl = self.size * (current_price - self.avg_price);
     }
 
+    /// Record a mark in the history used by the detail view.
+    fn push_mark(&mut self, price: f64) {
+        self.mark_history.push(price);
+        if self.mark_history.len() > MARK_HISTORY_LEN {
+            self.mark_history.remove(0);
+        }
+    }
+
+    /// Drop `shares` from the oldest lots first.
+    fn consume_lots(&mut self, mut shares: f64) {
+        while shares > 0.0 {
+            let Some(lot) = self.lots.first_mut() else { break };
+            if lot.shares > shares {
+                lot.shares -= shares;
+                break;
+            }
+            shares -= lot.shares;
+            self.lots.remove(0);
+        }
+    }
+
     /// Current value of the position.
     pub fn current_value(&self) -> f64 {
         self.size * self.current_price
@@ -44,8 +100,11 @@ pub struct Portfolio {
     pub cash_balance: f64,
     /// Open positions (keyed by market ID)
     pub positions: HashMap<String, Position>,
-    /// Total realized P&L
+    /// Total realized P&L (before fees)
     pub realized_pnl: f64,
+    /// Cumulative fees paid
+    #[serde(default)]
+    pub fees_paid: f64,
     /// File path for persistence
     #[serde(skip)]
     file_path: Option<String>,
@@ -59,24 +118,47 @@ impl Portfolio {
             cash_balance: initial_balance,
             positions: HashMap::new(),
             realized_pnl: 0.0,
+            fees_paid: 0.0,
             file_path: None,
         }
     }
 
-    /// Load portfolio from file or create new.
-    pub fn load_or_create(file_path: &str, initial_balance: f64) -> Self {
+    /// Load portfolio from file or create new. Fails when the file is
+    /// encrypted and no data key is configured.
+    pub fn load_or_create(file_path: &str, initial_balance: f64) -> Result<Self> {
         if Path::new(file_path).exists() {
-            let content = fs::read_to_string(file_path).unwrap_or_default();
-            if let Ok(mut portfolio) = serde_json::from_str::<Portfolio>(&content) {
+            if let Some(mut portfolio) = state::read::<Portfolio>(file_path) {
                 portfolio.file_path = Some(file_path.to_string());
-                return portfolio;
+                return Ok(portfolio);
             }
+            // Keep the unreadable file for inspection instead of overwriting it
+            state::quarantine(file_path)?;
         }
 
         let mut portfolio = Self::new(initial_balance);
         portfolio.file_path = Some(file_path.to_string());
         portfolio.save();
-        portfolio
+        Ok(portfolio)
+    }
+
+    /// Load a read-only snapshot from file (changes are not persisted).
+    pub fn load(file_path: &str) -> Option<Self> {
+        state::read(file_path)
+    }
+
+    /// File the portfolio is saved to, if persisted.
+    pub fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    /// Re-read a persisted portfolio from its file, picking up changes
+    /// saved by another process. Kept as is if the file can't be read.
+    pub fn reload(&mut self) {
+        let Some(path) = self.file_path.clone() else { return };
+        if let Some(mut portfolio) = state::read::<Portfolio>(&path) {
+            portfolio.file_path = Some(path);
+            *self = portfolio;
+        }
     }
 
     /// Open a new position or add to existing.
@@ -96,18 +178,49 @@ impl Portfolio {
             ));
         }
 
+        // Calculate shares (size in shares = USD / price)
+        self.add_shares(market, coin, platform, size_usd / price, size_usd, price);
+        Ok(())
+    }
+
+    /// Open a new position or add to existing by share/contract count; the
+    /// USD cost follows from the price.
+    pub fn open_contracts(
+        &mut self,
+        market: &str,
+        coin: &str,
+        platform: &str,
+        contracts: f64,
+        price: f64,
+    ) -> Result<(), String> {
+        if contracts <= 0.0 {
+            return Err(format!("Invalid contract count: {}", contracts));
+        }
+        let cost = contracts * price;
+        if cost > self.cash_balance {
+            return Err(format!(
+                "Insufficient balance: ${:.2} available, ${:.2} needed",
+                self.cash_balance, cost
+            ));
+        }
+
+        self.add_shares(market, coin, platform, contracts, cost, price);
+        Ok(())
+    }
+
+    fn add_shares(&mut self, market: &str, coin: &str, platform: &str, shares: f64, size_usd: f64, price: f64) {
         // Deduct from cash
         self.cash_balance -= size_usd;
 
-        // Calculate shares (size in shares = USD / price)
-        let shares = size_usd / price;
-
         if let Some(pos) = self.positions.get_mut(market) {
             // Add to existing position
             let total_shares = pos.size + shares;
             let total_value = (pos.size * pos.avg_price) + size_usd;
             pos.avg_price = total_value / total_shares;
             pos.size = total_shares;
+            pos.lots.push(Lot { opened_at: Utc::now(), shares, price });
+            // Re-mark so unrealized P&L reflects the enlarged position
+            pos.update_pnl(price);
         } else {
             // Create new position
             self.positions.insert(
@@ -120,12 +233,17 @@ impl Portfolio {
                     avg_price: price,
                     current_price: price,
                     unrealized_pnl: 0.0,
+                    market_id: None,
+                    lots: vec![Lot { opened_at: Utc::now(), shares, price }],
+                    fees_paid: 0.0,
+                    mark_history: vec![price],
+                    stop_price: None,
+                    source: None,
                 },
             );
         }
 
         self.save();
-        Ok(())
     }
 
     /// Close a position (or part of it).
@@ -145,11 +263,146 @@ impl Portfolio {
         Ok(pnl)
     }
 
+    /// Close part of a position, selling `shares` at `exit_price`.
+    ///
+    /// Closing the full size (or more) removes the position.
+    pub fn reduce_position(&mut self, market: &str, shares: f64, exit_price: f64) -> Result<f64, String> {
+        let position = self.positions.get_mut(market)
+            .ok_or_else(|| format!("No position found for {}", market))?;
+
+        if shares <= 0.0 {
+            return Err(format!("Invalid close size: {}", shares));
+        }
+        if shares >= position.size {
+            return self.close_position(market, exit_price);
+        }
+
+        let pnl = shares * (exit_price - position.avg_price);
+        position.size -= shares;
+        position.consume_lots(shares);
+        self.cash_balance += shares * exit_price;
+        self.realized_pnl += pnl;
+
+        self.save();
+        Ok(pnl)
+    }
+
+    /// Settle a position at resolution: winning shares pay $1, losing shares $0.
+    pub fn settle_position(&mut self, market: &str, won: bool) -> Result<f64, String> {
+        let payout = if won { 1.0 } else { 0.0 };
+        self.close_position(market, payout)
+    }
+
+    /// Deduct a trading fee from cash.
+    pub fn record_fee(&mut self, fee: f64) {
+        self.cash_balance -= fee;
+        self.fees_paid += fee;
+        self.save();
+    }
+
+    /// Deduct an entry fee from cash and attribute it to a position.
+    pub fn record_position_fee(&mut self, market: &str, fee: f64) {
+        if let Some(pos) = self.positions.get_mut(market) {
+            pos.fees_paid += fee;
+        }
+        self.record_fee(fee);
+    }
+
+    /// Set or clear a position's stop price.
+    pub fn set_stop(&mut self, market: &str, stop_price: Option<f64>) -> Result<(), String> {
+        let position = self.positions.get_mut(market)
+            .ok_or_else(|| format!("No position found for {}", market))?;
+        position.stop_price = stop_price;
+        self.save();
+        Ok(())
+    }
+
+    /// Record the trader a position copies.
+    pub fn link_source(&mut self, market: &str, trader: &str) {
+        if let Some(pos) = self.positions.get_mut(market) {
+            pos.source = Some(trader.to_string());
+            self.save();
+        }
+    }
+
+    /// (position key, mark) for every position marked at or below its stop.
+    pub fn triggered_stops(&self) -> Vec<(String, f64)> {
+        self.positions
+            .iter()
+            .filter(|(_, p)| p.stop_price.is_some_and(|stop| p.current_price <= stop))
+            .map(|(key, p)| (key.clone(), p.current_price))
+            .collect()
+    }
+
+    /// Capital currently tied up in positions at entry prices.
+    pub fn cost_basis(&self) -> f64 {
+        self.positions.values()
+            .map(|p| p.initial_value())
+            .sum()
+    }
+
+    /// Collateral locked by open positions, per platform: the max loss of
+    /// a long binary contract is its entry price, however far it is marked
+    /// up, so gains are not released until the position closes.
+    pub fn locked_by_venue(&self) -> HashMap<String, f64> {
+        let mut locked = HashMap::new();
+        for position in self.positions.values() {
+            *locked.entry(position.platform.clone()).or_default() += position.initial_value();
+        }
+        locked
+    }
+
+    /// Difference between what the books hold and what they should hold.
+    ///
+    /// `cash + cost basis + fees` must always equal
+    /// `initial balance + realized P&L`; anything else is accounting drift.
+    pub fn accounting_drift(&self) -> f64 {
+        (self.cash_balance + self.cost_basis() + self.fees_paid)
+            - (self.initial_balance + self.realized_pnl)
+    }
+
+    /// Verify accounting invariants, returning a description of the first violation.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let drift = self.accounting_drift();
+        if drift.abs() > 1e-6 {
+            return Err(format!("Accounting drift of {:.9}", drift));
+        }
+        if let Some(p) = self.positions.values().find(|p| p.size < 0.0) {
+            return Err(format!("Negative position size in {}", p.market));
+        }
+        Ok(())
+    }
+
+    /// Associate a position with the venue market (and outcome held) used
+    /// to price it.
+    pub fn link_market(&mut self, market: &str, market_id: &MarketId) {
+        if let Some(pos) = self.positions.get_mut(market) {
+            pos.market_id = Some(market_id.to_string());
+            self.save();
+        }
+    }
+
+    /// (position key, venue market) for every open position on a known
+    /// venue. Positions without a linked ID are assumed to be keyed by it.
+    pub fn mark_targets(&self) -> Vec<(String, MarketId)> {
+        self.positions
+            .iter()
+            .filter_map(|(key, p)| {
+                let market = match &p.market_id {
+                    Some(id) => id.parse().or_else(|_| MarketId::on(&p.platform, id)),
+                    None => MarketId::on(&p.platform, key),
+                };
+                Some((key.clone(), market.ok()?))
+            })
+            .collect()
+    }
+
     /// Update all positions with current prices.
     pub fn update_prices(&mut self, prices: &HashMap<String, f64>) {
         for (market, position) in &mut self.positions {
             if let Some(&price) = prices.get(market) {
                 position.update_pnl(price);
+                position.push_mark(price);
             }
         }
         self.save();
@@ -191,8 +444,8 @@ impl Portfolio {
     /// Save portfolio to file.
     fn save(&self) {
         if let Some(ref path) = self.file_path {
-            if let Ok(content) = serde_json::to_string_pretty(&self) {
-                let _ = fs::write(path, content);
+            if let Err(e) = state::save_with_backup(path, self) {
+                warn!("Failed to save portfolio: {}", e);
             }
         }
     }
@@ -202,6 +455,151 @@ impl Portfolio {
         self.cash_balance = self.initial_balance;
         self.positions.clear();
         self.realized_pnl = 0.0;
+        self.fees_paid = 0.0;
         self.save();
     }
+
+    /// Reset portfolio to a new starting `balance`.
+    pub fn reset_to(&mut self, balance: f64) {
+        self.initial_balance = balance;
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[derive(Debug, Clone)]
+    enum Op {
+        Buy { market: usize, size_usd: f64, price: f64, fee: f64 },
+        PartialClose { market: usize, fraction: f64, price: f64 },
+        Close { market: usize, price: f64 },
+        Settle { market: usize, won: bool },
+        Mark { market: usize, price: f64 },
+    }
+
+    fn price() -> impl Strategy<Value = f64> {
+        (1u32..100).prop_map(|c| c as f64 / 100.0)
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0usize..3, 1.0f64..300.0, price(), 0.0f64..2.0)
+                .prop_map(|(market, size_usd, price, fee)| Op::Buy { market, size_usd, price, fee }),
+            (0usize..3, 0.01f64..1.0, price())
+                .prop_map(|(market, fraction, price)| Op::PartialClose { market, fraction, price }),
+            (0usize..3, price()).prop_map(|(market, price)| Op::Close { market, price }),
+            (0usize..3, any::<bool>()).prop_map(|(market, won)| Op::Settle { market, won }),
+            (0usize..3, price()).prop_map(|(market, price)| Op::Mark { market, price }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_accounting_balances(ops in prop::collection::vec(op(), 1..60)) {
+            let mut portfolio = Portfolio::new(1000.0);
+            let markets = ["BTC-A", "BTC-B", "ETH-A"];
+
+            for op in ops {
+                match op {
+                    Op::Buy { market, size_usd, price, fee } => {
+                        if portfolio.open_position(markets[market], "BTC", "polymarket", size_usd, price).is_ok() {
+                            portfolio.record_fee(fee);
+                        }
+                    }
+                    Op::PartialClose { market, fraction, price } => {
+                        let shares = portfolio.positions.get(markets[market]).map(|p| p.size * fraction);
+                        if let Some(shares) = shares {
+                            portfolio.reduce_position(markets[market], shares, price).unwrap();
+                        }
+                    }
+                    Op::Close { market, price } => {
+                        let _ = portfolio.close_position(markets[market], price);
+                    }
+                    Op::Settle { market, won } => {
+                        let _ = portfolio.settle_position(markets[market], won);
+                    }
+                    Op::Mark { market, price } => {
+                        let prices = HashMap::from([(markets[market].to_string(), price)]);
+                        portfolio.update_prices(&prices);
+                    }
+                }
+                prop_assert!(portfolio.check_invariants().is_ok(), "{:?}", portfolio.check_invariants());
+            }
+        }
+
+        #[test]
+        fn prop_total_pnl_matches_value_change(ops in prop::collection::vec(op(), 1..60)) {
+            let mut portfolio = Portfolio::new(1000.0);
+            let markets = ["BTC-A", "BTC-B", "ETH-A"];
+
+            for op in ops {
+                match op {
+                    Op::Buy { market, size_usd, price, .. } => {
+                        let _ = portfolio.open_position(markets[market], "BTC", "kalshi", size_usd, price);
+                    }
+                    Op::Close { market, price } | Op::Mark { market, price } => {
+                        let prices = HashMap::from([(markets[market].to_string(), price)]);
+                        portfolio.update_prices(&prices);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Without fees, value gained over the starting balance is exactly total P&L.
+            let expected = portfolio.initial_balance + portfolio.total_pnl();
+            prop_assert!((portfolio.total_value() - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_open_contracts_derives_cost_from_price() {
+        let mut portfolio = Portfolio::new(100.0);
+        portfolio.open_contracts("BTC YES", "BTC", "polymarket", 40.0, 0.45).unwrap();
+        portfolio.open_contracts("BTC NO", "BTC", "kalshi", 40.0, 0.52).unwrap();
+
+        assert_eq!(portfolio.positions["BTC YES"].size, portfolio.positions["BTC NO"].size);
+        assert!((portfolio.cash_balance - (100.0 - 40.0 * 0.97)).abs() < 1e-9);
+        assert!(portfolio.open_contracts("BTC NO", "BTC", "kalshi", 200.0, 0.52).is_err());
+        assert!(portfolio.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_gains_stay_locked_until_close() {
+        let mut portfolio = Portfolio::new(100.0);
+        portfolio.open_contracts("KXBTC", "BTC", "kalshi", 100.0, 0.40).unwrap();
+        portfolio.update_prices(&HashMap::from([("KXBTC".to_string(), 0.90)]));
+
+        // Marked up to $90, but only the $40 max loss is locked and $60 free
+        assert!((portfolio.total_value() - 150.0).abs() < 1e-9);
+        assert!((portfolio.locked_by_venue()["kalshi"] - 40.0).abs() < 1e-9);
+        assert!((portfolio.cash_balance + portfolio.cost_basis() - 100.0).abs() < 1e-9);
+        assert!(portfolio.open_contracts("KXETH", "ETH", "kalshi", 100.0, 0.70).is_err());
+    }
+
+    #[test]
+    fn test_lots_fees_and_stops_track_per_position() {
+        let mut portfolio = Portfolio::new(100.0);
+        portfolio.open_contracts("KXBTC", "BTC", "kalshi", 10.0, 0.40).unwrap();
+        portfolio.record_position_fee("KXBTC", 0.17);
+        portfolio.open_contracts("KXBTC", "BTC", "kalshi", 20.0, 0.50).unwrap();
+        portfolio.record_position_fee("KXBTC", 0.35);
+
+        // Partial closes consume the oldest lot first
+        portfolio.reduce_position("KXBTC", 15.0, 0.55).unwrap();
+        let pos = &portfolio.positions["KXBTC"];
+        assert_eq!(pos.lots.len(), 1);
+        assert!((pos.lots[0].shares - 15.0).abs() < 1e-9);
+        assert!((pos.fees_paid - 0.52).abs() < 1e-9);
+        assert!(portfolio.check_invariants().is_ok());
+
+        portfolio.set_stop("KXBTC", Some(0.45)).unwrap();
+        portfolio.update_prices(&HashMap::from([("KXBTC".to_string(), 0.46)]));
+        assert!(portfolio.triggered_stops().is_empty());
+        portfolio.update_prices(&HashMap::from([("KXBTC".to_string(), 0.44)]));
+        assert_eq!(portfolio.triggered_stops(), vec![("KXBTC".to_string(), 0.44)]);
+        assert_eq!(portfolio.positions["KXBTC"].mark_history, vec![0.40, 0.46, 0.44]);
+    }
 }
@@ -0,0 +1,103 @@
+//! Named paper portfolios.
+//!
+//! Each profile keeps its own portfolio and trade log so experiments such as
+//! "arb-only" and "copy-aggressive" don't share one balance. The "default"
+//! profile is the original `data/portfolio.json`, so existing state keeps
+//! working; named profiles live under `data/portfolios/`.
+
+use anyhow::{Context, Result};
+use std::fs;
+
+use super::engine::PORTFOLIO_FILE;
+use crate::config::Config;
+
+/// Directory holding named profiles
+pub const PROFILES_DIR: &str = "data/portfolios";
+/// Profile backed by the legacy portfolio files
+pub const DEFAULT_PROFILE: &str = "default";
+/// Trade log of the default profile
+pub const TRADES_FILE: &str = "data/paper_trades.json";
+
+/// Where one paper portfolio is persisted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub portfolio_file: String,
+    pub trades_file: String,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_PROFILE.to_string(),
+            portfolio_file: PORTFOLIO_FILE.to_string(),
+            trades_file: TRADES_FILE.to_string(),
+        }
+    }
+}
+
+impl Profile {
+    /// Profile called `name`. Names are lowercase letters, digits, '-' and
+    /// '_' so they map safely onto file names.
+    pub fn named(name: &str) -> Result<Self> {
+        let name = name.trim().to_lowercase();
+        if name == DEFAULT_PROFILE {
+            return Ok(Self::default());
+        }
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            anyhow::bail!("Invalid profile name '{}': use letters, digits, '-' or '_'", name);
+        }
+        Ok(Self {
+            portfolio_file: format!("{}/{}.json", PROFILES_DIR, name),
+            trades_file: format!("{}/{}.trades.json", PROFILES_DIR, name),
+            name,
+        })
+    }
+
+    /// Profile selected by `PAPER_PROFILE`.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        Self::named(&config.paper_profile).context("Invalid PAPER_PROFILE")
+    }
+
+    /// Whether the profile has been created.
+    pub fn exists(&self) -> bool {
+        std::path::Path::new(&self.portfolio_file).exists()
+    }
+
+    /// The default profile followed by every named profile on disk, by name.
+    pub fn list() -> Vec<Self> {
+        let mut names: Vec<String> = fs::read_dir(PROFILES_DIR)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let file = entry.file_name().into_string().ok()?;
+                let name = file.strip_suffix(".json")?;
+                (!name.ends_with(".trades")).then(|| name.to_string())
+            })
+            .collect();
+        names.sort();
+        std::iter::once(Self::default())
+            .chain(names.iter().filter_map(|n| Self::named(n).ok()).filter(|p| p.name != DEFAULT_PROFILE))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_paths_and_names() {
+        assert_eq!(Profile::named("Default").unwrap(), Profile::default());
+        assert_eq!(Profile::default().portfolio_file, "data/portfolio.json");
+
+        let arb = Profile::named("arb-only").unwrap();
+        assert_eq!(arb.portfolio_file, "data/portfolios/arb-only.json");
+        assert_eq!(arb.trades_file, "data/portfolios/arb-only.trades.json");
+
+        assert!(Profile::named("").is_err());
+        assert!(Profile::named("../etc").is_err());
+        assert!(Profile::named("copy aggressive").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Trading sessions.
+//!
+//! A session is one run of the bot or TUI. It remembers the portfolio's
+//! all-time P&L when the run started, so P&L since start can be shown next
+//! to the ever-growing all-time figure, and is summarised into the audit
+//! log when the run ends. The bot keeps its session in
+//! `data/state/session.json` so an observing TUI shows the bot's run.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::Portfolio;
+use crate::utils::{run_info, state};
+
+/// Session of the running bot.
+pub const SESSION_FILE: &str = "data/state/session.json";
+
+/// One run against a paper portfolio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub run_id: String,
+    pub profile: String,
+    pub started_at: DateTime<Utc>,
+    /// All-time P&L when the session started
+    pub starting_pnl: f64,
+    /// Trades in the log when the session started
+    pub starting_trades: usize,
+    /// When the readings below were taken
+    pub updated_at: DateTime<Utc>,
+    pub all_time_pnl: f64,
+    pub trades: usize,
+}
+
+/// What a finished session did, as written to the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub run_id: String,
+    pub profile: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    /// P&L made during the session
+    pub pnl: f64,
+    /// All-time P&L at the end
+    pub all_time_pnl: f64,
+    /// Trades opened during the session
+    pub trades: usize,
+}
+
+impl Session {
+    /// Start a session for this process on `portfolio`, which has `trades`
+    /// trades logged so far.
+    pub fn start(profile: &str, portfolio: &Portfolio, trades: usize, now: DateTime<Utc>) -> Self {
+        let pnl = portfolio.total_pnl();
+        Self {
+            run_id: run_info::current().run_id.clone(),
+            profile: profile.to_string(),
+            started_at: now,
+            starting_pnl: pnl,
+            starting_trades: trades,
+            updated_at: now,
+            all_time_pnl: pnl,
+            trades,
+        }
+    }
+
+    /// Take a new reading of the portfolio.
+    pub fn update(&mut self, portfolio: &Portfolio, trades: usize, now: DateTime<Utc>) {
+        self.updated_at = now;
+        self.all_time_pnl = portfolio.total_pnl();
+        self.trades = trades;
+    }
+
+    /// P&L since start as of the last reading.
+    pub fn pnl(&self) -> f64 {
+        self.all_time_pnl - self.starting_pnl
+    }
+
+    /// P&L since start with `portfolio` as it is now.
+    pub fn pnl_of(&self, portfolio: &Portfolio) -> f64 {
+        portfolio.total_pnl() - self.starting_pnl
+    }
+
+    /// Summary as of the last reading.
+    pub fn summary(&self) -> SessionSummary {
+        SessionSummary {
+            run_id: self.run_id.clone(),
+            profile: self.profile.clone(),
+            started_at: self.started_at,
+            ended_at: self.updated_at,
+            pnl: self.pnl(),
+            all_time_pnl: self.all_time_pnl,
+            trades: self.trades.saturating_sub(self.starting_trades),
+        }
+    }
+
+    pub fn load(path: &str) -> Option<Self> {
+        state::read(path)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        state::save(path, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_pnl_is_relative_to_start() {
+        let mut portfolio = Portfolio::new(1000.0);
+        portfolio.realized_pnl = 50.0;
+        let start = Utc::now();
+        let mut session = Session::start("default", &portfolio, 4, start);
+        assert_eq!(session.pnl(), 0.0);
+
+        portfolio.realized_pnl = 35.0;
+        assert_eq!(session.pnl_of(&portfolio), -15.0);
+        session.update(&portfolio, 7, start + chrono::Duration::hours(2));
+        let summary = session.summary();
+        assert_eq!(summary.pnl, -15.0);
+        assert_eq!(summary.all_time_pnl, 35.0);
+        assert_eq!(summary.trades, 3);
+        assert_eq!(summary.ended_at - summary.started_at, chrono::Duration::hours(2));
+
+        let path = std::env::temp_dir().join(format!("session-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        session.save(path).unwrap();
+        assert_eq!(Session::load(path), Some(session));
+        let _ = std::fs::remove_file(path);
+    }
+}
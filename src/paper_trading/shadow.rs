@@ -0,0 +1,258 @@
+//! Shadow mode.
+//!
+//! A strategy in shadow mode runs on live data and produces its signals as
+//! usual, but its orders are filled virtually against the live books into a
+//! paper portfolio of its own ("shadow-<strategy>") while the rest of the bot
+//! trades live. Shadow arb fills also go to the capture log under the
+//! [`SHADOW_MODE`] mode, next to the live ones, so `polybot capture-report` shows how
+//! the strategy would have done before it is promoted.
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+use super::book_fill::Depth;
+use super::engine::PaperTradingEngine;
+use super::profiles::Profile;
+use crate::analysis::heatmap::market_family;
+use crate::analysis::CaptureLog;
+use crate::arbitrage::Opportunity;
+use crate::fees::FeeSchedule;
+use crate::strategies::{CopyTrade, FollowTrade};
+use crate::utils::venue_constraints::{PairedOrder, Sizing};
+use crate::utils::{MarketId, Outcome};
+
+/// Capture log mode of virtual fills.
+pub const SHADOW_MODE: &str = "shadow";
+/// Timeframe recorded on shadow trades.
+const SHADOW_TIMEFRAME: &str = "Shadow";
+
+/// Paper profile holding a strategy's shadow fills.
+pub fn profile(strategy: &str) -> Result<Profile> {
+    Profile::named(&format!("shadow-{}", strategy))
+}
+
+/// Virtual fills of one strategy.
+pub struct ShadowBook {
+    strategy: String,
+    engine: PaperTradingEngine,
+    capture: Option<Arc<Mutex<CaptureLog>>>,
+}
+
+impl ShadowBook {
+    /// Fill `strategy`'s orders into `engine`.
+    pub fn new(strategy: &str, engine: PaperTradingEngine) -> Self {
+        Self { strategy: strategy.to_string(), engine, capture: None }
+    }
+
+    /// Fill into the strategy's shadow profile, charging `fees`. A new
+    /// profile starts with `balance`.
+    pub fn for_strategy(strategy: &str, fees: FeeSchedule, balance: f64) -> Result<Self> {
+        let engine = PaperTradingEngine::for_profile(&profile(strategy)?, balance)?.with_fees(fees);
+        Ok(Self::new(strategy, engine))
+    }
+
+    /// Record arb fills in a capture log.
+    pub fn with_capture(mut self, capture: Arc<Mutex<CaptureLog>>) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    pub fn strategy(&self) -> &str {
+        &self.strategy
+    }
+
+    pub fn engine(&self) -> &PaperTradingEngine {
+        &self.engine
+    }
+
+    /// Fill both legs of an arb. A Polymarket YES leg takes its contracts
+    /// from `yes_book` and pays the limit a live order would need to reach
+    /// that deep; other legs fill at the detected price.
+    pub fn fill_arb(&mut self, opportunity: &Opportunity, sizing: Sizing, yes_book: Option<&Depth>) -> Result<PairedOrder> {
+        let mut fill = opportunity.clone();
+        let mut sizing = sizing;
+        if let Some(book) = yes_book.filter(|_| opportunity.yes_venue == "polymarket") {
+            let contracts = sizing.contracts(opportunity.cost()).floor();
+            let taken = book.buy_contracts(contracts);
+            if taken.contracts() < contracts - 1e-9 {
+                anyhow::bail!("Only {:.0} of {} contracts on the {} book", taken.contracts(), contracts, opportunity.market);
+            }
+            fill.yes_price = taken.levels.last().map_or(fill.yes_price, |l| l.price);
+            sizing = Sizing::Contracts(contracts);
+        }
+        let family = market_family(&opportunity.market);
+        let order = self.engine.buy_arb(&fill, &family, SHADOW_TIMEFRAME, sizing, &self.strategy)?;
+        if let Some(capture) = &self.capture {
+            let mut log = capture.lock().unwrap();
+            let id = log.record_detection(opportunity, SHADOW_MODE, order.size);
+            log.record_execution(&id, order.price_a, order.price_b);
+        }
+        Ok(order)
+    }
+
+    /// Fill a followed Kalshi trade, walking `book` (that side's Kalshi
+    /// book) when there is one and at the followed price otherwise. Returns
+    /// the realized P&L of a sell.
+    pub fn fill_follow(&mut self, trade: &FollowTrade, book: Option<&Depth>) -> Result<Option<f64>> {
+        let market = format!("{} {}", trade.ticker, trade.side.to_uppercase());
+        let family = market_family(&trade.ticker);
+        let contracts = trade.count as f64;
+        if trade.action.eq_ignore_ascii_case("sell") {
+            let Some(held) = self.engine.portfolio.positions.get(&market).map(|p| p.size) else {
+                anyhow::bail!("No shadow position in {} to sell", market);
+            };
+            let pnl = match book {
+                // Only the bids the followed count reaches
+                Some(book) => self.engine.sell_into_book(&market, &Depth::new(book.sell(contracts).levels, Vec::new()))?.0,
+                None => self.engine.sell_fraction(&market, (contracts / held).min(1.0), trade.price)?,
+            };
+            return Ok(Some(pnl));
+        }
+        match book {
+            Some(book) => {
+                let usd = book.buy_contracts(contracts).notional();
+                self.engine.buy_from_book(&market, &family, SHADOW_TIMEFRAME, "kalshi", usd, book, &self.strategy, 1.0)?;
+            }
+            None => {
+                self.engine.buy_contracts(
+                    &market, &family, SHADOW_TIMEFRAME, "kalshi", contracts, trade.price, &self.strategy, 1.0,
+                )?;
+            }
+        }
+        let outcome = if trade.side.eq_ignore_ascii_case("no") { Outcome::No } else { Outcome::Yes };
+        self.engine.portfolio.link_market(&market, &MarketId::kalshi(&trade.ticker).with_outcome(outcome));
+        Ok(None)
+    }
+
+    /// Fill a copied Polymarket trade, walking `book` (the token's book)
+    /// when there is one and at the copied price otherwise. The position
+    /// remembers the trader it copies. Returns the realized P&L of a sell.
+    pub fn fill_copy(&mut self, trade: &CopyTrade, book: Option<&Depth>) -> Result<Option<f64>> {
+        let market = trade.label();
+        let contracts = trade.our_size / trade.price;
+        if trade.side.eq_ignore_ascii_case("sell") {
+            let Some(held) = self.engine.portfolio.positions.get(&market).map(|p| p.size) else {
+                anyhow::bail!("No shadow position in {} to sell", market);
+            };
+            let pnl = match book {
+                Some(book) => self.engine.sell_into_book(&market, &Depth::new(book.sell(contracts).levels, Vec::new()))?.0,
+                None => self.engine.sell_fraction(&market, (contracts / held).min(1.0), trade.price)?,
+            };
+            return Ok(Some(pnl));
+        }
+        let family = market_family(&trade.title);
+        match book {
+            Some(book) => {
+                self.engine.buy_from_book(&market, &family, SHADOW_TIMEFRAME, "polymarket", trade.our_size, book, &self.strategy, 1.0)?;
+            }
+            None => {
+                self.engine.buy(&market, &family, SHADOW_TIMEFRAME, "polymarket", trade.our_size, trade.price, &self.strategy, 1.0)?;
+            }
+        }
+        self.engine.portfolio.link_market(&market, &MarketId::polymarket(&trade.asset));
+        self.engine.portfolio.link_source(&market, &trade.trader_address);
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::market_matcher::MatchedMarket;
+    use crate::paper_trading::book_fill::Level;
+    use chrono::Utc;
+
+    #[test]
+    fn test_shadow_fills_walk_live_books() {
+        let capture = Arc::new(Mutex::new(CaptureLog::new()));
+        let fees = FeeSchedule::new();
+        fees.set_pairs([&MatchedMarket::new("BTC 5PM", "111", "KXBTC-1")]);
+        let engine = PaperTradingEngine::in_memory("shadow-arbitrage", 1000.0).with_fees(fees);
+        let mut shadow = ShadowBook::new("arbitrage", engine).with_capture(capture.clone());
+        let opportunity = Opportunity {
+            market: "BTC 5PM".to_string(),
+            yes_venue: "polymarket",
+            yes_price: 0.40,
+            no_venue: "kalshi",
+            no_price: 0.55,
+            fee: 0.0,
+            detected_at: Utc::now(),
+        };
+        let book = Depth::new(Vec::new(), vec![Level { price: 0.40, size: 5.0 }, Level { price: 0.42, size: 20.0 }]);
+
+        // 10 contracts reach the second level, so the YES leg pays its limit
+        let order = shadow.fill_arb(&opportunity, Sizing::Contracts(10.0), Some(&book)).unwrap();
+        assert_eq!((order.size, order.price_a, order.price_b), (10.0, 0.42, 0.55));
+        assert!(shadow.engine().portfolio.positions.contains_key("BTC 5PM YES"));
+        // Each leg marks on its side of the pair
+        let mut targets = shadow.engine().portfolio.mark_targets();
+        targets.sort();
+        assert_eq!(
+            targets,
+            vec![
+                ("BTC 5PM NO".to_string(), MarketId::kalshi("KXBTC-1").with_outcome(Outcome::No)),
+                ("BTC 5PM YES".to_string(), MarketId::polymarket("111").with_outcome(Outcome::Yes)),
+            ]
+        );
+        let log = capture.lock().unwrap();
+        let record = &log.records()[0];
+        assert_eq!(record.mode, SHADOW_MODE);
+        assert_eq!((record.detected_yes, record.executed_yes), (0.40, Some(0.42)));
+        drop(log);
+        assert!(shadow.fill_arb(&opportunity, Sizing::Contracts(30.0), Some(&book)).is_err());
+
+        let mut follow = ShadowBook::new("kalshi_follow", PaperTradingEngine::in_memory("shadow-kalshi_follow", 1000.0));
+        let trade = FollowTrade {
+            trade_id: "t1".to_string(),
+            ticker: "KXBTCD-26JAN0517-T94249.99".to_string(),
+            side: "yes".to_string(),
+            action: "buy".to_string(),
+            price: 0.50,
+            leader_count: 10,
+            count: 10,
+        };
+        let asks = Depth::new(Vec::new(), vec![Level { price: 0.50, size: 4.0 }, Level { price: 0.52, size: 10.0 }]);
+        assert_eq!(follow.fill_follow(&trade, Some(&asks)).unwrap(), None);
+        let market = "KXBTCD-26JAN0517-T94249.99 YES";
+        assert!((follow.engine().portfolio.positions[market].size - 10.0).abs() < 1e-9);
+        assert_eq!(
+            follow.engine().portfolio.mark_targets()[0].1,
+            MarketId::kalshi(&trade.ticker).with_outcome(Outcome::Yes)
+        );
+
+        // A partial sell takes only the followed count from the bids
+        let bids = Depth::new(vec![Level { price: 0.55, size: 2.0 }, Level { price: 0.54, size: 10.0 }], Vec::new());
+        let sell = FollowTrade { action: "sell".to_string(), count: 4, ..trade.clone() };
+        assert!(follow.fill_follow(&sell, Some(&bids)).unwrap().is_some());
+        assert!((follow.engine().portfolio.positions[market].size - 6.0).abs() < 1e-9);
+        let other = FollowTrade { ticker: "KXETH".to_string(), ..sell };
+        assert!(follow.fill_follow(&other, None).is_err());
+    }
+
+    #[test]
+    fn test_shadow_copies_link_their_trader() {
+        let mut copy = ShadowBook::new("copy_trade", PaperTradingEngine::in_memory("shadow-copy_trade", 1000.0));
+        let trade = CopyTrade {
+            trader_address: "0xabc".to_string(),
+            condition_id: "0xcond".to_string(),
+            asset: "222".to_string(),
+            side: "BUY".to_string(),
+            original_size: 100.0,
+            our_size: 10.0,
+            price: 0.50,
+            title: "Will BTC hit 100k?".to_string(),
+            event_slug: "btc-100k".to_string(),
+            outcome: "Yes".to_string(),
+        };
+        assert_eq!(copy.fill_copy(&trade, None).unwrap(), None);
+        let position = &copy.engine().portfolio.positions["Will BTC hit 100k? [Yes]"];
+        assert_eq!(position.source.as_deref(), Some("0xabc"));
+        assert!((position.size - 20.0).abs() < 1e-9);
+        assert_eq!(copy.engine().portfolio.mark_targets()[0].1, MarketId::polymarket("222"));
+
+        // Selling $5 at 0.50 closes half
+        let sell = CopyTrade { side: "SELL".to_string(), our_size: 5.0, ..trade };
+        assert!(copy.fill_copy(&sell, None).unwrap().is_some());
+        assert!((copy.engine().portfolio.positions["Will BTC hit 100k? [Yes]"].size - 10.0).abs() < 1e-9);
+    }
+}
@@ -1,11 +1,16 @@
 //! Trade log for recording paper trades.
 
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use tracing::warn;
 use uuid::Uuid;
 
+use crate::utils::run_info::{self, RunStamp};
+use crate::utils::state;
+
 /// Trade direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Side {
@@ -30,6 +35,22 @@ pub enum TradeStatus {
     Cancelled,
 }
 
+/// One execution of a paper trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeFill {
+    pub timestamp: DateTime<Utc>,
+    pub price: f64,
+    /// USD amount filled
+    pub size: f64,
+}
+
+impl TradeFill {
+    /// Shares/contracts filled.
+    pub fn contracts(&self) -> f64 {
+        if self.price > 0.0 { self.size / self.price } else { 0.0 }
+    }
+}
+
 /// A paper trade record
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaperTrade {
@@ -40,18 +61,32 @@ pub struct PaperTrade {
     pub timeframe: String,
     pub platform: String,  // "polymarket" or "kalshi"
     pub side: Side,
-    pub size: f64,         // USD amount
-    pub entry_price: f64,  // 0.0 - 1.0
+    pub size: f64,         // USD amount ordered
+    pub entry_price: f64,  // 0.0 - 1.0, average fill price
     pub exit_price: Option<f64>,
     pub pnl: Option<f64>,
     pub status: TradeStatus,
     pub strategy: String,  // "arbitrage", "copy_trade", "manual"
     pub confidence: f64,   // 0.0 - 1.0
     pub notes: Option<String>,
+    /// Simulated entry fee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<f64>,
+    /// Process that opened the trade
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunStamp>,
+    /// Executions so far, when filled piecemeal; None when filled in full
+    /// at the entry price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fills: Option<Vec<TradeFill>>,
+    /// USD of the order cancelled before it filled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancelled_size: Option<f64>,
 }
 
 impl PaperTrade {
     /// Create a new paper trade.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         market: &str,
         coin: &str,
@@ -79,7 +114,76 @@ impl PaperTrade {
             strategy: strategy.to_string(),
             confidence,
             notes: None,
+            fee: None,
+            run: Some(run_info::current().clone()),
+            fills: None,
+            cancelled_size: None,
+        }
+    }
+
+    /// Track executions one by one instead of assuming the whole size
+    /// filled at the entry price.
+    pub fn unfilled(mut self) -> Self {
+        self.fills = Some(Vec::new());
+        self
+    }
+
+    /// USD amount filled so far.
+    pub fn filled_size(&self) -> f64 {
+        match &self.fills {
+            Some(fills) => fills.iter().map(|f| f.size).sum(),
+            None => self.size,
+        }
+    }
+
+    /// Shares/contracts filled so far.
+    pub fn filled_contracts(&self) -> f64 {
+        match &self.fills {
+            Some(fills) => fills.iter().map(|f| f.contracts()).sum(),
+            None if self.entry_price > 0.0 => self.size / self.entry_price,
+            None => 0.0,
+        }
+    }
+
+    /// USD amount still working: neither filled nor cancelled.
+    pub fn remaining_size(&self) -> f64 {
+        (self.size - self.filled_size() - self.cancelled_size.unwrap_or(0.0)).max(0.0)
+    }
+
+    /// Nothing of the order is left working.
+    pub fn is_filled(&self) -> bool {
+        self.remaining_size() <= 1e-9
+    }
+
+    /// Record an execution of up to the remaining size, moving the entry
+    /// price to the average fill price. Returns the USD amount applied.
+    pub fn add_fill(&mut self, price: f64, size_usd: f64) -> f64 {
+        let size = size_usd.min(self.remaining_size());
+        if size <= 0.0 || price <= 0.0 {
+            return 0.0;
+        }
+        self.fills.get_or_insert_with(Vec::new).push(TradeFill { timestamp: Utc::now(), price, size });
+        self.entry_price = self.filled_size() / self.filled_contracts();
+        size
+    }
+
+    /// Cancel whatever has not filled. A trade with no fills at all is
+    /// cancelled outright. Returns the USD amount cancelled.
+    pub fn cancel_remaining(&mut self) -> f64 {
+        let remaining = self.remaining_size();
+        if remaining > 0.0 {
+            self.cancelled_size = Some(self.cancelled_size.unwrap_or(0.0) + remaining);
+        }
+        if self.filled_size() <= 0.0 {
+            self.status = TradeStatus::Cancelled;
         }
+        remaining
+    }
+
+    /// Record the simulated entry fee.
+    pub fn with_fee(mut self, fee: f64) -> Self {
+        self.fee = Some(fee);
+        self
     }
 
     /// Close the trade with an exit price.
@@ -87,12 +191,13 @@ impl PaperTrade {
         self.exit_price = Some(exit_price);
         self.status = TradeStatus::Closed;
         
-        // Calculate P&L
-        // For a YES position (buy): profit = size * (exit - entry)
-        // For a NO position (sell): profit = size * (entry - exit)
+        // Calculate P&L on what actually filled
+        // For a YES position (buy): profit = filled * (exit - entry)
+        // For a NO position (sell): profit = filled * (entry - exit)
+        let filled = self.filled_size();
         let pnl = match self.side {
-            Side::Buy => self.size * (exit_price - self.entry_price),
-            Side::Sell => self.size * (self.entry_price - exit_price),
+            Side::Buy => filled * (exit_price - self.entry_price),
+            Side::Sell => filled * (self.entry_price - exit_price),
         };
         self.pnl = Some(pnl);
     }
@@ -106,22 +211,52 @@ impl PaperTrade {
 /// Trade log that persists trades to disk.
 pub struct TradeLog {
     trades: Vec<PaperTrade>,
-    file_path: String,
+    /// None for a read-only snapshot
+    file_path: Option<String>,
 }
 
 impl TradeLog {
-    /// Create or load a trade log from file.
-    pub fn new(file_path: &str) -> Self {
-        let trades = if Path::new(file_path).exists() {
-            let content = fs::read_to_string(file_path).unwrap_or_default();
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            Vec::new()
+    /// Create or load a trade log from file. Fails when the file is
+    /// encrypted and no data key is configured.
+    pub fn new(file_path: &str) -> Result<Self> {
+        let trades = match state::read(file_path) {
+            Some(trades) => trades,
+            None => {
+                if Path::new(file_path).exists() {
+                    // Keep the unreadable log for inspection instead of overwriting it
+                    state::quarantine(file_path)?;
+                }
+                Vec::new()
+            }
         };
 
-        Self {
+        Ok(Self {
             trades,
-            file_path: file_path.to_string(),
+            file_path: Some(file_path.to_string()),
+        })
+    }
+
+    /// Load a read-only snapshot from file (changes are not persisted).
+    pub fn snapshot(file_path: &str) -> Self {
+        Self {
+            trades: state::read(file_path).unwrap_or_default(),
+            file_path: None,
+        }
+    }
+
+    /// Re-read a persisted log from its file, picking up trades saved by
+    /// another process. Kept as is if the file can't be read.
+    pub fn reload(&mut self) {
+        if let Some(trades) = self.file_path.as_deref().and_then(state::read) {
+            self.trades = trades;
+        }
+    }
+
+    /// Empty log kept only in memory.
+    pub fn in_memory() -> Self {
+        Self {
+            trades: Vec::new(),
+            file_path: None,
         }
     }
 
@@ -131,6 +266,20 @@ impl TradeLog {
         self.save();
     }
 
+    /// Move the log file aside to `<file>.archived-<timestamp>` and start
+    /// an empty log. Returns the archive path, None when there was nothing
+    /// on disk to archive.
+    pub fn archive(&mut self) -> Result<Option<String>> {
+        self.trades.clear();
+        let Some(path) = self.file_path.as_deref().filter(|p| Path::new(p).exists()) else {
+            return Ok(None);
+        };
+        let target = format!("{}.archived-{}", path, Utc::now().format("%Y%m%dT%H%M%S"));
+        fs::rename(path, &target).with_context(|| format!("Failed to archive {}", path))?;
+        self.save();
+        Ok(Some(target))
+    }
+
     /// Get all trades.
     pub fn get_all(&self) -> &[PaperTrade] {
         &self.trades
@@ -151,6 +300,32 @@ impl TradeLog {
         self.trades.iter().rev().take(n).collect()
     }
 
+    /// Get a trade by ID.
+    pub fn get(&self, id: &str) -> Option<&PaperTrade> {
+        self.trades.iter().find(|t| t.id == id)
+    }
+
+    /// Apply an execution and its fee to a trade by ID, returning the USD
+    /// amount applied (None for unknown trades).
+    pub fn fill_trade(&mut self, id: &str, price: f64, size_usd: f64, fee: f64) -> Option<f64> {
+        let trade = self.trades.iter_mut().find(|t| t.id == id)?;
+        let applied = trade.add_fill(price, size_usd);
+        if applied > 0.0 {
+            trade.fee = Some(trade.fee.unwrap_or(0.0) + fee);
+        }
+        self.save();
+        Some(applied)
+    }
+
+    /// Cancel the unfilled remainder of a trade by ID, returning the USD
+    /// amount cancelled (None for unknown trades).
+    pub fn cancel_remaining(&mut self, id: &str) -> Option<f64> {
+        let trade = self.trades.iter_mut().find(|t| t.id == id)?;
+        let cancelled = trade.cancel_remaining();
+        self.save();
+        Some(cancelled)
+    }
+
     /// Close a trade by ID.
     pub fn close_trade(&mut self, id: &str, exit_price: f64) -> bool {
         if let Some(trade) = self.trades.iter_mut().find(|t| t.id == id) {
@@ -196,8 +371,67 @@ impl TradeLog {
 
     /// Save trades to file.
     fn save(&self) {
-        if let Ok(content) = serde_json::to_string_pretty(&self.trades) {
-            let _ = fs::write(&self.file_path, content);
+        let Some(ref path) = self.file_path else {
+            return;
+        };
+        if let Err(e) = state::save_with_backup(path, &self.trades) {
+            warn!("Failed to save trade log: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_fills_and_cancel() {
+        let mut trade = PaperTrade::new("BTC YES", "BTC", "Hourly", "kalshi", Side::Buy, 100.0, 0.50, "manual", 1.0).unfilled();
+        assert_eq!(trade.filled_size(), 0.0);
+
+        assert_eq!(trade.add_fill(0.40, 20.0), 20.0);
+        assert_eq!(trade.add_fill(0.50, 30.0), 30.0);
+        // 50 + 60 contracts for $50
+        assert!((trade.filled_contracts() - 110.0).abs() < 1e-9);
+        assert!((trade.entry_price - 50.0 / 110.0).abs() < 1e-9);
+        assert!((trade.remaining_size() - 50.0).abs() < 1e-9);
+
+        assert!((trade.cancel_remaining() - 50.0).abs() < 1e-9);
+        assert!(trade.is_filled());
+        assert_eq!(trade.status, TradeStatus::Open);
+        assert_eq!(trade.add_fill(0.50, 10.0), 0.0, "nothing left to fill");
+
+        // P&L is on the $50 that filled, not the $100 ordered
+        trade.close(0.60);
+        assert!((trade.pnl.unwrap() - 50.0 * (0.60 - 50.0 / 110.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cancel_unfilled_and_legacy_trades() {
+        let mut unfilled = PaperTrade::new("m", "BTC", "Hourly", "kalshi", Side::Buy, 10.0, 0.50, "manual", 1.0).unfilled();
+        assert_eq!(unfilled.cancel_remaining(), 10.0);
+        assert_eq!(unfilled.status, TradeStatus::Cancelled);
+
+        // Trades without fills are filled in full at entry
+        let legacy = PaperTrade::new("m", "BTC", "Hourly", "kalshi", Side::Buy, 10.0, 0.50, "manual", 1.0);
+        assert_eq!((legacy.filled_size(), legacy.filled_contracts()), (10.0, 20.0));
+        assert!(legacy.is_filled());
+    }
+
+    #[test]
+    fn test_archive_starts_an_empty_log() {
+        let path = std::env::temp_dir().join(format!("trades-{}.json", Uuid::new_v4())).to_string_lossy().to_string();
+        let mut log = TradeLog::new(&path).unwrap();
+        assert_eq!(log.archive().unwrap(), None, "nothing on disk yet");
+
+        log.add_trade(PaperTrade::new("m", "BTC", "Hourly", "kalshi", Side::Buy, 10.0, 0.50, "manual", 1.0));
+        let archived = log.archive().unwrap().unwrap();
+        assert!(archived.starts_with(&format!("{}.archived-", path)));
+        assert!(log.get_all().is_empty());
+        assert!(TradeLog::new(&path).unwrap().get_all().is_empty());
+        assert_eq!(TradeLog::snapshot(&archived).get_all().len(), 1);
+        for file in [state::backup_path(&path), path, archived] {
+            let _ = fs::remove_file(file);
         }
     }
 }
@@ -4,14 +4,64 @@
 
 use anyhow::{Context, Result};
 use reqwest::Client;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
+use super::discovery::{self, GammaEvent, GammaMarket, GAMMA_API_URL};
+use super::order_builder::{self, OrderBuilder};
 use super::signer::PolymarketSigner;
 use super::types::*;
 use crate::config::Config;
+use crate::money::Money;
+use crate::utils::fixtures::{self, FixtureStore};
+use crate::utils::throttle::{self, OrderAction};
+use crate::utils::http_pool::PreparedRequest;
+use crate::utils::{endpoints, http_pool, raw_log, tolerant, Cache, MarketId, OpenOrder, OpenOrders, SelfTradePolicy, VenueConstraints};
 
 /// Base URL for Polymarket CLOB API.
-const CLOB_API_URL: &str = "https://clob.polymarket.com";
+pub const CLOB_API_URL: &str = "https://clob.polymarket.com";
+
+/// How long a market's tick size and minimum size are trusted. Tick sizes
+/// shrink as prices near 0 or 1, so they are re-read now and then.
+const CONSTRAINTS_TTL: Duration = Duration::from_secs(300);
+
+/// Tick and minimum sizes per token, from the CLOB. Shared by every client,
+/// so a market read once (e.g. while priming at startup) is warm for all.
+static CONSTRAINTS: OnceLock<Cache<VenueConstraints>> = OnceLock::new();
+
+fn constraints_cache() -> &'static Cache<VenueConstraints> {
+    CONSTRAINTS.get_or_init(|| Cache::new(CONSTRAINTS_TTL))
+}
+
+/// How long a token's neg-risk flag is trusted; it is fixed per market.
+const NEG_RISK_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Whether each token trades on the neg-risk exchange, shared like the
+/// constraints.
+static NEG_RISK: OnceLock<Cache<bool>> = OnceLock::new();
+
+fn neg_risk_cache() -> &'static Cache<bool> {
+    NEG_RISK.get_or_init(|| Cache::new(NEG_RISK_TTL))
+}
+
+/// How long a token's fee rate is trusted; markets can change their fees.
+const FEE_RATE_TTL: Duration = Duration::from_secs(300);
+
+/// Fee rate in basis points per token, shared like the constraints.
+static FEE_RATES: OnceLock<Cache<u32>> = OnceLock::new();
+
+fn fee_rate_cache() -> &'static Cache<u32> {
+    FEE_RATES.get_or_init(|| Cache::new(FEE_RATE_TTL))
+}
+
+/// `next_cursor` of the CLOB's last page of results.
+const END_CURSOR: &str = "LTE=";
+
+/// Active CLOB base URL; differs from [`CLOB_API_URL`] after a failover.
+fn clob_url() -> String {
+    endpoints::base(endpoints::POLYMARKET, CLOB_API_URL)
+}
 
 /// Polymarket API client.
 pub struct PolymarketClient {
@@ -21,15 +71,18 @@ pub struct PolymarketClient {
     signer: Option<PolymarketSigner>,
     /// Whether in dry-run mode
     dry_run: bool,
+    /// Record/replay store for responses
+    fixtures: Option<Arc<FixtureStore>>,
+    /// Resting orders, persisted across restarts
+    open_orders: Option<Arc<Mutex<OpenOrders>>>,
+    /// What to do when an order would cross our own resting order
+    self_trade: SelfTradePolicy,
 }
 
 impl PolymarketClient {
     /// Create a new Polymarket client from configuration.
     pub fn new(config: &Config) -> Result<Self> {
-        let http = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to create HTTP client")?;
+        let http = http_pool::client();
 
         let signer = if config.has_polymarket_credentials() {
             Some(PolymarketSigner::new(
@@ -37,7 +90,7 @@ impl PolymarketClient {
                 &config.polymarket_api_key,
                 &config.polymarket_secret,
                 &config.polymarket_passphrase,
-            )?)
+            )?.with_signature_type(config.polymarket_signature_type, &config.polymarket_funder)?)
         } else {
             warn!("Polymarket credentials not configured - read-only mode");
             None
@@ -47,53 +100,183 @@ impl PolymarketClient {
             http,
             signer,
             dry_run: config.dry_run,
+            fixtures: FixtureStore::from_config(config)?,
+            open_orders: None,
+            self_trade: config.self_trade_policy,
         })
     }
 
-    /// Get all active markets.
+    /// Track resting orders in a persistent store.
+    pub fn with_open_orders(mut self, store: Arc<Mutex<OpenOrders>>) -> Self {
+        self.open_orders = Some(store);
+        self
+    }
+
+    /// Log orders instead of sending them, or send them, whatever DRY_RUN
+    /// says: for a strategy with its own mode.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Use a specific fixture store for recording or replaying responses.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.fixtures = Some(store);
+        self
+    }
+
+    /// Get all active markets, following the CLOB's cursor across pages.
     pub async fn get_markets(&self) -> Result<Vec<Market>> {
-        let url = format!("{}/markets", CLOB_API_URL);
-        
-        debug!("Fetching markets from {}", url);
-        
-        let response = self.http
-            .get(&url)
-            .send()
+        let mut markets = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut url = format!("{}/markets", clob_url());
+            if let Some(c) = &cursor {
+                url.push_str(&format!("?next_cursor={}", c));
+            }
+            debug!("Fetching markets from {}", url);
+
+            let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
+                .await
+                .context("Failed to fetch markets")?;
+            if !(200..300).contains(&status) {
+                anyhow::bail!("Markets request failed ({}): {}", status, text);
+            }
+
+            // The CLOB returns a paginated wrapper; older deployments returned a bare list
+            let mut value: serde_json::Value = tolerant::parse("polymarket markets", &text)?;
+            let next = value.get("next_cursor").and_then(|c| c.as_str()).map(str::to_string);
+            let list = match value.get_mut("data") {
+                Some(data) => data.take(),
+                None => value,
+            };
+            markets.extend(tolerant::parse_list::<Market>("polymarket market", list));
+
+            // "LTE=" (base64 "-1") marks the last page
+            match next {
+                Some(c) if !c.is_empty() && c != END_CURSOR && cursor.as_deref() != Some(c.as_str()) => cursor = Some(c),
+                _ => break,
+            }
+        }
+
+        info!("Fetched {} markets", markets.len());
+        Ok(markets)
+    }
+
+    /// Get a Gamma event by slug, or `None` if it does not exist (yet).
+    pub async fn get_event_by_slug(&self, slug: &str) -> Result<Option<GammaEvent>> {
+        let url = format!("{}/events?slug={}", GAMMA_API_URL, slug);
+
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
             .await
-            .context("Failed to fetch markets")?;
+            .context("Failed to fetch Gamma event")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Gamma event request failed ({}): {}", status, text);
+        }
+
+        let events: Vec<GammaEvent> = tolerant::parse_items("gamma event", &text)?;
+        Ok(events.into_iter().next())
+    }
+
+    /// Get an event with all of its outcome markets, or `None` if it does
+    /// not exist (yet).
+    pub async fn get_outcome_event(&self, slug: &str) -> Result<Option<OutcomeEvent>> {
+        Ok(self.get_event_by_slug(slug).await?.map(|event| event.to_outcome_event()))
+    }
+
+    /// Get the Gamma market trading `token_id`, open or closed.
+    pub async fn get_gamma_market_by_token(&self, token_id: &str) -> Result<Option<GammaMarket>> {
+        let url = format!("{}/markets?clob_token_ids={}", GAMMA_API_URL, token_id);
 
-        let markets: Vec<Market> = response
-            .json()
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
             .await
-            .context("Failed to parse markets response")?;
+            .context("Failed to fetch Gamma market")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Gamma market request failed ({}): {}", status, text);
+        }
 
-        info!("Fetched {} markets", markets.len());
+        let markets: Vec<GammaMarket> = tolerant::parse_items("gamma market", &text)?;
+        Ok(markets.into_iter().next())
+    }
+
+    /// Get the `limit` most traded active Gamma markets.
+    pub async fn get_active_gamma_markets(&self, limit: usize) -> Result<Vec<GammaMarket>> {
+        let url = format!(
+            "{}/markets?active=true&closed=false&order=volumeNum&ascending=false&limit={}",
+            GAMMA_API_URL, limit
+        );
+
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
+            .await
+            .context("Failed to fetch Gamma markets")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Gamma markets request failed ({}): {}", status, text);
+        }
+
+        tolerant::parse_items("gamma market", &text)
+    }
+
+    /// Discover the current recurring crypto markets for each coin
+    /// (slug names such as "bitcoin", "ethereum").
+    pub async fn discover_crypto_markets(
+        &self,
+        coins: &[&str],
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<Market>> {
+        let markets: Vec<Market> = self
+            .discover_crypto_events(coins, now)
+            .await?
+            .into_iter()
+            .flat_map(|event| event.markets)
+            .collect();
+        info!("Discovered {} Polymarket crypto markets", markets.len());
         Ok(markets)
     }
 
+    /// Discover the current recurring crypto events for each coin, with
+    /// every outcome market of each.
+    pub async fn discover_crypto_events(
+        &self,
+        coins: &[&str],
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<OutcomeEvent>> {
+        let mut events = Vec::new();
+
+        for coin in coins {
+            for slug in discovery::candidate_slugs(coin, now) {
+                match self.get_outcome_event(&slug).await {
+                    Ok(Some(event)) => {
+                        debug!(
+                            "Discovered {} ({} markets{})",
+                            event.slug,
+                            event.markets.len(),
+                            if event.neg_risk { ", negRisk" } else { "" }
+                        );
+                        events.push(event);
+                    }
+                    Ok(None) => debug!("No Polymarket event for slug {}", slug),
+                    Err(e) => warn!("Discovery failed for {}: {}", slug, e),
+                }
+            }
+        }
+        Ok(events)
+    }
+
     /// Get order book for a specific token.
     pub async fn get_orderbook(&self, token_id: &str) -> Result<OrderBook> {
-        let url = format!("{}/book?token_id={}", CLOB_API_URL, token_id);
+        let url = format!("{}/book?token_id={}", clob_url(), token_id);
         
         debug!("Fetching orderbook for token {}", token_id);
         
-        let response = self.http
-            .get(&url)
-            .send()
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
             .await
             .context("Failed to fetch orderbook")?;
-
-        let status = response.status();
-        let text = response.text().await
-            .context("Failed to read orderbook response")?;
         
         debug!("Orderbook response ({}): {}", status, &text[..text.len().min(500)]);
 
         // Parse the response - Polymarket CLOB returns a specific format
-        let book: OrderBook = serde_json::from_str(&text)
-            .context("Failed to parse orderbook response")?;
-
-        Ok(book)
+        tolerant::parse("polymarket orderbook", &text)
     }
 
     /// Get the best bid and ask prices for a token.
@@ -102,11 +285,117 @@ impl PolymarketClient {
         Ok((book.best_bid(), book.best_ask()))
     }
 
+    /// Tick size and minimum order size of a token's market, read from its
+    /// CLOB book and cached. Falls back to the defaults when the book cannot
+    /// be fetched.
+    pub async fn get_constraints(&self, token_id: &str) -> VenueConstraints {
+        if let Some(constraints) = constraints_cache().get(&MarketId::polymarket(token_id)) {
+            return constraints;
+        }
+        match self.get_orderbook(token_id).await {
+            Ok(book) => self.cache_constraints(token_id, &book),
+            Err(e) => {
+                warn!("Using default order constraints for token {}: {}", token_id, e);
+                VenueConstraints::polymarket()
+            }
+        }
+    }
+
+    /// Cache the tick size and minimum order size of a book already fetched.
+    pub fn cache_constraints(&self, token_id: &str, book: &OrderBook) -> VenueConstraints {
+        let constraints = order_builder::constraints(book);
+        constraints_cache().set(&MarketId::polymarket(token_id), constraints);
+        constraints
+    }
+
+    /// Whether `token_id` trades on the neg-risk exchange, whose contract
+    /// its orders must be signed for.
+    pub async fn is_neg_risk(&self, token_id: &str) -> Result<bool> {
+        let id = MarketId::polymarket(token_id);
+        if let Some(neg_risk) = neg_risk_cache().get(&id) {
+            return Ok(neg_risk);
+        }
+        let url = format!("{}/neg-risk?token_id={}", clob_url(), token_id);
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
+            .await
+            .context("Failed to fetch neg-risk flag")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Polymarket neg-risk request failed ({}): {}", status, text);
+        }
+        // Read strictly: a defaulted flag would sign for the wrong exchange
+        let neg_risk = serde_json::from_str::<NegRisk>(&text)
+            .context("Failed to parse neg-risk flag")?
+            .neg_risk;
+        neg_risk_cache().set(&id, neg_risk);
+        Ok(neg_risk)
+    }
+
+    /// Fee rate of `token_id` in basis points. The CLOB rejects an order
+    /// signed with a lower rate than the market charges.
+    pub async fn fee_rate_bps(&self, token_id: &str) -> Result<u32> {
+        let id = MarketId::polymarket(token_id);
+        if let Some(fee_rate) = fee_rate_cache().get(&id) {
+            return Ok(fee_rate);
+        }
+        let url = format!("{}/fee-rate?token_id={}", clob_url(), token_id);
+        let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), &url)
+            .await
+            .context("Failed to fetch fee rate")?;
+        if !(200..300).contains(&status) {
+            anyhow::bail!("Polymarket fee rate request failed ({}): {}", status, text);
+        }
+        // Read strictly: a defaulted rate would sign an order the CLOB refuses
+        let fee_rate = serde_json::from_str::<FeeRate>(&text)
+            .context("Failed to parse fee rate")?
+            .base_fee;
+        fee_rate_cache().set(&id, fee_rate);
+        Ok(fee_rate)
+    }
+
+    /// Build and sign an order exactly as [`place_order`](Self::place_order)
+    /// would, returning the rounded order and the request that would be
+    /// sent, without sending it or touching our resting orders.
+    pub async fn prepare_order(&self, order: Order) -> Result<(Order, PreparedRequest)> {
+        let signer = self.signer.as_ref()
+            .context("Cannot sign orders without credentials")?;
+        let order = OrderBuilder::new(self.get_constraints(&order.token_id).await).build(order)?;
+        let neg_risk = self.is_neg_risk(&order.token_id).await?;
+        let fee_rate_bps = self.fee_rate_bps(&order.token_id).await?;
+        let request = Self::sign_request(signer, &order, neg_risk, fee_rate_bps)?;
+        Ok((order, request))
+    }
+
+    /// Signed `POST /order` for an already built order.
+    fn sign_request(signer: &PolymarketSigner, order: &Order, neg_risk: bool, fee_rate_bps: u32) -> Result<PreparedRequest> {
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        // Salts stay within JavaScript's safe integer range
+        let signed = signer.sign_order(order, rand::random::<u32>() as u64, neg_risk, fee_rate_bps)?;
+        let body = serde_json::to_string(&PostOrder {
+            order: signed,
+            owner: signer.api_key().to_string(),
+            order_type: order.order_type,
+        })?;
+        let signature = signer.create_hmac_signature(&timestamp, "POST", "/order", &body)?;
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        headers.extend(signer.get_auth_headers(&timestamp, &signature));
+        Ok(PreparedRequest {
+            method: "POST".to_string(),
+            url: format!("{}/order", clob_url()),
+            headers,
+            body,
+        })
+    }
+
     /// Place an order on the CLOB.
     pub async fn place_order(&self, order: Order) -> Result<OrderResponse> {
         let signer = self.signer.as_ref()
             .context("Cannot place orders without credentials")?;
 
+        // Snap to the market's tick/lot grid and enforce its minimum size
+        let order = OrderBuilder::new(self.get_constraints(&order.token_id).await).build(order)?;
+        let side = format!("{:?}", order.side).to_uppercase();
+        self.prevent_self_trade(&order.token_id, &side, order.price).await?;
+
         if self.dry_run {
             info!(
                 "DRY RUN: Would place {:?} order for {} shares at {} on token {}",
@@ -120,40 +409,37 @@ impl PolymarketClient {
             });
         }
 
-        let url = format!("{}/order", CLOB_API_URL);
-        let timestamp = chrono::Utc::now().timestamp().to_string();
-        let body = serde_json::to_string(&order)?;
-        
-        let signature = signer.create_hmac_signature(
-            &timestamp,
-            "POST",
-            "/order",
-            &body,
-        )?;
-
-        let auth_headers = signer.get_auth_headers(&timestamp, &signature);
-        
-        let mut request = self.http
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .body(body);
-
-        for (key, value) in auth_headers {
-            request = request.header(&key, &value);
-        }
+        throttle::acquire("polymarket", OrderAction::Place)?;
 
-        let response = request
-            .send()
+        let neg_risk = self.is_neg_risk(&order.token_id).await?;
+        let fee_rate_bps = self.fee_rate_bps(&order.token_id).await?;
+        let request = Self::sign_request(signer, &order, neg_risk, fee_rate_bps)?;
+        let response = http_pool::send(request.to_builder(&self.http))
             .await
             .context("Failed to place order")?;
+        let status = response.status().as_u16();
+        let text = response.text().await.context("Failed to read order response")?;
+        raw_log::record("POST", &request.url, Some(&request.body), status, &text);
 
-        let order_response: OrderResponse = response
-            .json()
-            .await
+        let order_response: OrderResponse = serde_json::from_str(&text)
             .context("Failed to parse order response")?;
 
         if order_response.success {
             info!("Order placed successfully: {:?}", order_response.order_id);
+            // Fill-or-kill orders never rest on the book
+            if let (Some(store), Some(id)) = (&self.open_orders, &order_response.order_id) {
+                if order.order_type != OrderType::Fok {
+                    store.lock().unwrap().add(OpenOrder {
+                        order_id: id.clone(),
+                        venue: "polymarket".to_string(),
+                        market: order.token_id.clone(),
+                        side,
+                        price: order.price,
+                        size: order.size,
+                        placed_at: chrono::Utc::now(),
+                    });
+                }
+            }
         } else {
             warn!("Order failed: {:?}", order_response.error);
         }
@@ -161,6 +447,111 @@ impl PolymarketClient {
         Ok(order_response)
     }
 
+    /// Check a new order against our resting orders on the token and, per
+    /// the self-trade policy, cancel the ones it would cross or refuse it.
+    async fn prevent_self_trade(&self, token_id: &str, side: &str, price: f64) -> Result<()> {
+        let crossing = match (&self.open_orders, self.self_trade) {
+            (Some(store), policy) if policy != SelfTradePolicy::Off => {
+                store.lock().unwrap().crossing("polymarket", token_id, side, Some(price))
+            }
+            _ => return Ok(()),
+        };
+        for resting in crossing {
+            warn!(
+                "Self-trade: {} @ {} would cross our {} @ {} ({})",
+                side, price, resting.side, resting.price, resting.order_id
+            );
+            if self.self_trade == SelfTradePolicy::Skip {
+                anyhow::bail!("Self-trade prevented: would cross our resting order {}", resting.order_id);
+            }
+            if !self.cancel_order(&resting.order_id).await? {
+                anyhow::bail!("Self-trade prevented: could not cancel our resting order {}", resting.order_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// USDC collateral available to trade on the CLOB.
+    pub async fn get_collateral_balance(&self) -> Result<Money> {
+        let signer = self.signer.as_ref()
+            .context("Cannot fetch balances without credentials")?;
+
+        let path = "/balance-allowance";
+        let url = format!(
+            "{}{}?asset_type=COLLATERAL&signature_type={}",
+            clob_url(), path, signer.signature_type().id()
+        );
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = signer.create_hmac_signature(&timestamp, "GET", path, "")?;
+
+        let mut request = self.http.get(&url);
+        for (key, value) in signer.get_auth_headers(&timestamp, &signature) {
+            request = request.header(&key, &value);
+        }
+        let response = http_pool::send(request).await.context("Failed to fetch collateral balance")?;
+        let status = response.status().as_u16();
+        let text = response.text().await.context("Failed to read balance response")?;
+        raw_log::record("GET", &url, None, status, &text);
+
+        let balance: BalanceAllowance = tolerant::parse("polymarket balance", &text)?;
+        Ok(Money::usdc(balance.balance / 1_000_000.0))
+    }
+
+    /// IDs of our orders still open on the CLOB.
+    pub async fn get_open_order_ids(&self) -> Result<Vec<String>> {
+        let signer = self.signer.as_ref()
+            .context("Cannot fetch orders without credentials")?;
+
+        let path = "/data/orders";
+        let mut ids = Vec::new();
+        let mut cursor = String::new();
+        loop {
+            let url = if cursor.is_empty() {
+                format!("{}{}", clob_url(), path)
+            } else {
+                format!("{}{}?next_cursor={}", clob_url(), path, cursor)
+            };
+            let timestamp = chrono::Utc::now().timestamp().to_string();
+            let signature = signer.create_hmac_signature(&timestamp, "GET", path, "")?;
+
+            let mut request = self.http.get(&url);
+            for (key, value) in signer.get_auth_headers(&timestamp, &signature) {
+                request = request.header(&key, &value);
+            }
+            let response = http_pool::send(request).await.context("Failed to fetch open orders")?;
+            let status = response.status().as_u16();
+            let text = response.text().await.context("Failed to read orders response")?;
+            raw_log::record("GET", &url, None, status, &text);
+            if !(200..300).contains(&status) {
+                anyhow::bail!("Polymarket orders request failed ({}): {}", status, text);
+            }
+
+            let page: serde_json::Value = tolerant::parse("polymarket orders page", &text)?;
+            let orders = if page.is_array() { &page } else { &page["data"] };
+            ids.extend(
+                orders.as_array().into_iter().flatten()
+                    .filter_map(|o| o["id"].as_str().map(str::to_string)),
+            );
+            // The CLOB marks the last page with the cursor "LTE="
+            match page["next_cursor"].as_str() {
+                Some(next) if !next.is_empty() && next != "LTE=" && next != cursor => cursor = next.to_string(),
+                _ => return Ok(ids),
+            }
+        }
+    }
+
+    /// Forget restored open orders that are no longer open on the CLOB.
+    /// Returns how many were dropped.
+    pub async fn reconcile_open_orders(&self) -> Result<usize> {
+        let Some(store) = &self.open_orders else { return Ok(0) };
+        let open = self.get_open_order_ids().await?;
+        let dropped = store.lock().unwrap().reconcile("polymarket", &open);
+        for order in &dropped {
+            info!("Polymarket order {} on {} is no longer open", order.order_id, order.market);
+        }
+        Ok(dropped.len())
+    }
+
     /// Cancel an open order.
     pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
         let signer = self.signer.as_ref()
@@ -171,7 +562,9 @@ impl PolymarketClient {
             return Ok(true);
         }
 
-        let url = format!("{}/order/{}", CLOB_API_URL, order_id);
+        throttle::acquire("polymarket", OrderAction::Cancel)?;
+
+        let url = format!("{}/order/{}", clob_url(), order_id);
         let timestamp = chrono::Utc::now().timestamp().to_string();
         
         let signature = signer.create_hmac_signature(
@@ -189,15 +582,19 @@ impl PolymarketClient {
             request = request.header(&key, &value);
         }
 
-        let response = request
-            .send()
+        let response = http_pool::send(request)
             .await
             .context("Failed to cancel order")?;
 
         let success = response.status().is_success();
+        let status = response.status().as_u16();
+        raw_log::record("DELETE", &url, None, status, &response.text().await.unwrap_or_default());
         
         if success {
             info!("Order {} cancelled successfully", order_id);
+            if let Some(store) = &self.open_orders {
+                store.lock().unwrap().remove(order_id);
+            }
         } else {
             warn!("Failed to cancel order {}", order_id);
         }
@@ -209,4 +606,9 @@ impl PolymarketClient {
     pub fn can_trade(&self) -> bool {
         self.signer.is_some()
     }
+
+    /// Address our positions are held under, when credentials are configured.
+    pub fn maker_address(&self) -> Option<String> {
+        self.signer.as_ref().map(|s| format!("{:?}", s.maker()))
+    }
 }
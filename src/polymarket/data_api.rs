@@ -0,0 +1,287 @@
+//! Polymarket data-api client.
+//!
+//! One client and one set of types for the public data-api endpoints
+//! (leaderboard, positions, activity), shared by the copy trader and the
+//! trader analyzer. Requests are paced to a minimum interval, retried after
+//! a pause when the API answers 429, and paged with `offset` when more rows
+//! are wanted than one request returns.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::utils::fixtures::{self, FixtureMode, FixtureStore};
+use crate::utils::{http_pool, tolerant};
+
+pub const DATA_API_URL: &str = "https://data-api.polymarket.com";
+
+/// Latest leaderboard fetched by the bot's refresh task.
+pub const LEADERBOARD_FILE: &str = "data/leaderboard.json";
+
+/// Default gap between consecutive requests.
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Pause before retrying a rate-limited request.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Retries of a rate-limited request before giving up.
+const RATE_LIMIT_RETRIES: usize = 2;
+
+/// Largest page each endpoint serves.
+const LEADERBOARD_PAGE: usize = 50;
+const ACTIVITY_PAGE: usize = 500;
+const POSITIONS_PAGE: usize = 500;
+
+/// A trade or other activity from a trader's feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeActivity {
+    #[serde(default)]
+    pub proxy_wallet: String,
+    #[serde(default)]
+    pub timestamp: i64,
+    #[serde(default)]
+    pub condition_id: String,
+    #[serde(default, rename = "type")]
+    pub activity_type: String,
+    #[serde(default)]
+    pub size: f64,
+    #[serde(default)]
+    pub usdc_size: f64,
+    #[serde(default)]
+    pub transaction_hash: String,
+    #[serde(default)]
+    pub price: f64,
+    #[serde(default)]
+    pub asset: String,
+    #[serde(default)]
+    pub side: String,  // "BUY" or "SELL"
+    #[serde(default)]
+    pub outcome_index: i32,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub event_slug: String,
+    #[serde(default)]
+    pub outcome: String,
+}
+
+/// A position held by a trader
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraderPosition {
+    #[serde(default)]
+    pub proxy_wallet: String,
+    #[serde(default)]
+    pub asset: String,
+    #[serde(default)]
+    pub condition_id: String,
+    #[serde(default)]
+    pub size: f64,
+    #[serde(default)]
+    pub avg_price: f64,
+    #[serde(default)]
+    pub initial_value: f64,
+    #[serde(default)]
+    pub current_value: f64,
+    #[serde(default)]
+    pub cash_pnl: f64,
+    #[serde(default)]
+    pub percent_pnl: f64,
+    #[serde(default)]
+    pub cur_price: f64,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub event_slug: String,
+    #[serde(default)]
+    pub outcome: String,
+    #[serde(default)]
+    pub outcome_index: i32,
+}
+
+/// Leaderboard entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub proxy_address: String,
+    #[serde(default)]
+    pub profit_and_loss: f64,
+    #[serde(default)]
+    pub volume: f64,
+    #[serde(default)]
+    pub positions_value: f64,
+    #[serde(default)]
+    pub num_markets_won: i32,
+}
+
+/// Paced, paginating client for the data-api.
+pub struct DataApiClient {
+    http: Client,
+    base_url: String,
+    /// Record/replay store for responses
+    fixtures: Option<Arc<FixtureStore>>,
+    min_interval: Duration,
+    /// When the last request went out
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl DataApiClient {
+    /// Create a client for the public data-api.
+    pub fn new() -> Self {
+        Self {
+            http: http_pool::client(),
+            base_url: DATA_API_URL.to_string(),
+            fixtures: None,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Use a fixture store for recording or replaying responses.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.fixtures = Some(store);
+        self
+    }
+
+    /// Leave at least `interval` between requests.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Top traders by profit over `time_period` ("day", "week", "month", "all").
+    pub async fn leaderboard(&self, time_period: &str, limit: usize) -> Result<Vec<LeaderboardEntry>> {
+        let path = format!("v1/leaderboard?timePeriod={}&orderBy=PNL", time_period);
+        let entries = self.fetch_pages("leaderboard entry", &path, "&category=overall", limit, LEADERBOARD_PAGE).await?;
+        debug!("Fetched {} leaderboard entries", entries.len());
+        Ok(entries)
+    }
+
+    /// Up to `limit` positions held by `user`.
+    pub async fn positions(&self, user: &str, limit: usize) -> Result<Vec<TraderPosition>> {
+        let path = format!("positions?user={}", user);
+        self.fetch_pages("trader position", &path, "", limit, POSITIONS_PAGE).await
+    }
+
+    /// Up to `limit` recent activities of `user`, newest first, optionally
+    /// only of one type (e.g. "TRADE").
+    pub async fn activity(&self, user: &str, kind: Option<&str>, limit: usize) -> Result<Vec<TradeActivity>> {
+        let path = match kind {
+            Some(kind) => format!("activity?user={}&type={}", user, kind),
+            None => format!("activity?user={}", user),
+        };
+        self.fetch_pages("trade activity", &path, "", limit, ACTIVITY_PAGE).await
+    }
+
+    /// Fetch up to `limit` rows of `path`, `page` at a time, stopping at the
+    /// first short page. A failed later page keeps the rows already fetched.
+    async fn fetch_pages<T: serde::de::DeserializeOwned + Serialize>(
+        &self,
+        what: &str,
+        path: &str,
+        suffix: &str,
+        limit: usize,
+        page: usize,
+    ) -> Result<Vec<T>> {
+        let mut rows = Vec::new();
+        while rows.len() < limit {
+            let wanted = (limit - rows.len()).min(page);
+            // The first page carries no offset so its URL matches single-page requests
+            let offset = if rows.is_empty() { String::new() } else { format!("&offset={}", rows.len()) };
+            let url = format!("{}/{}&limit={}{}{}", self.base_url, path, wanted, offset, suffix);
+
+            let batch: Vec<T> = match self.get(&url).await {
+                Ok(text) => tolerant::parse_items(what, &text)?,
+                Err(e) if !rows.is_empty() => {
+                    warn!("Stopped paging {} after {} rows: {}", what, rows.len(), e);
+                    break;
+                }
+                Err(e) => return Err(e),
+            };
+            let short = batch.len() < wanted;
+            rows.extend(batch);
+            if short {
+                break;
+            }
+        }
+        Ok(rows)
+    }
+
+    /// GET `url`, paced and retried on 429; bails on any other non-2xx.
+    async fn get(&self, url: &str) -> Result<String> {
+        for attempt in 0..=RATE_LIMIT_RETRIES {
+            self.pace().await;
+            let (status, text) = fixtures::fetch_text(&self.http, self.fixtures.as_deref(), url).await
+                .context("Failed to reach the data-api")?;
+            if status == 429 && attempt < RATE_LIMIT_RETRIES {
+                warn!("Data-api rate limited, retrying in {:?}", RATE_LIMIT_BACKOFF);
+                tokio::time::sleep(RATE_LIMIT_BACKOFF).await;
+                continue;
+            }
+            if !(200..300).contains(&status) {
+                anyhow::bail!("Data-api request failed: {} - {}", status, text);
+            }
+            return Ok(text);
+        }
+        unreachable!("the last attempt always returns")
+    }
+
+    /// Wait until `min_interval` has passed since the previous request.
+    /// Replayed fixtures never touch the network and are not paced.
+    async fn pace(&self) {
+        if self.fixtures.as_ref().is_some_and(|s| s.mode() == FixtureMode::Replay) {
+            return;
+        }
+        let mut last = self.last_request.lock().await;
+        if let Some(at) = *last {
+            let wait = self.min_interval.saturating_sub(at.elapsed());
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+impl Default for DataApiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pages_until_a_short_page() {
+        let dir = std::env::temp_dir().join(format!("polybot-data-api-{}", uuid::Uuid::new_v4()));
+        let store = Arc::new(FixtureStore::new(&dir, FixtureMode::Replay));
+        let rows = |n: usize| serde_json::to_string(&vec![serde_json::json!({"asset": "a", "size": 1.0}); n]).unwrap();
+        let base = format!("{}/positions?user=0xabc", DATA_API_URL);
+        store.save("GET", &format!("{}&limit=500", base), 200, &rows(500)).unwrap();
+        store.save("GET", &format!("{}&limit=500&offset=500", base), 200, &rows(120)).unwrap();
+
+        let client = DataApiClient::new().with_fixtures(store.clone());
+        assert_eq!(client.positions("0xabc", 2000).await.unwrap().len(), 620);
+        assert_eq!(client.positions("0xabc", 500).await.unwrap().len(), 500);
+
+        // A failed later page keeps what was fetched; a failed first page is an error
+        store.save("GET", &format!("{}&limit=500&offset=500", base), 500, "oops").unwrap();
+        assert_eq!(client.positions("0xabc", 2000).await.unwrap().len(), 500);
+        assert!(client.positions("0xdef", 10).await.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
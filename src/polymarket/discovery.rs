@@ -0,0 +1,262 @@
+//! Polymarket market discovery via the Gamma API.
+//!
+//! Recurring crypto markets follow predictable slugs, e.g.
+//! `bitcoin-up-or-down-january-5-5pm-et` (hourly) and
+//! `bitcoin-above-on-january-5` (daily strike ladder). Generating the slugs
+//! for the current and upcoming windows lets the matcher pick up fresh token
+//! IDs without manual edits.
+//!
+//! An event may also group one YES/NO market per outcome (an election's
+//! candidates); negRisk events make those outcomes mutually exclusive.
+
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use chrono_tz::America::New_York;
+use serde::{Deserialize, Serialize};
+
+use super::types::{Market, OutcomeEvent, Token};
+use crate::analysis::rewards::RewardTerms;
+
+/// Multi-outcome events found by the last universe refresh, for the TUI.
+pub const OUTCOME_EVENTS_FILE: &str = "data/state/outcome_events.json";
+
+/// Base URL for the Gamma markets API.
+pub const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
+
+/// Market as returned by Gamma (token IDs and outcomes are JSON-encoded strings).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GammaMarket {
+    pub condition_id: String,
+    pub question: String,
+    pub slug: String,
+    pub end_date: Option<String>,
+    pub active: bool,
+    pub closed: bool,
+    /// e.g. "[\"Up\", \"Down\"]"
+    pub outcomes: Option<String>,
+    /// e.g. "[\"1234...\", \"5678...\"]"
+    pub clob_token_ids: Option<String>,
+    /// e.g. "[\"0.505\", \"0.495\"]"
+    pub outcome_prices: Option<String>,
+    /// Lifetime volume in USD
+    pub volume_num: Option<f64>,
+    /// Resting liquidity in USD
+    pub liquidity_num: Option<f64>,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub neg_risk: bool,
+    /// Outcome name within a multi-outcome event, e.g. "Bitcoin"
+    pub group_item_title: Option<String>,
+    /// Shares a resting order needs to earn liquidity rewards
+    pub rewards_min_size: Option<f64>,
+    /// Farthest from the midpoint a rewarded order may rest, in cents
+    pub rewards_max_spread: Option<f64>,
+    pub clob_rewards: Vec<GammaReward>,
+}
+
+/// A liquidity reward program on a Gamma market.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GammaReward {
+    /// USD paid out per day
+    pub rewards_daily_rate: f64,
+}
+
+/// Event grouping one or more Gamma markets.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GammaEvent {
+    pub slug: String,
+    pub title: String,
+    pub neg_risk: bool,
+    pub markets: Vec<GammaMarket>,
+}
+
+impl GammaEvent {
+    /// Convert into an [`OutcomeEvent`], tagging each market with the event.
+    pub fn to_outcome_event(&self) -> OutcomeEvent {
+        let markets = self
+            .markets
+            .iter()
+            .map(|m| Market {
+                event_slug: Some(self.slug.clone()),
+                neg_risk: m.neg_risk || self.neg_risk,
+                ..m.to_market()
+            })
+            .collect();
+        OutcomeEvent { slug: self.slug.clone(), title: self.title.clone(), neg_risk: self.neg_risk, markets }
+    }
+}
+
+fn decode_list(value: &Option<String>) -> Vec<String> {
+    value
+        .as_deref()
+        .and_then(|v| serde_json::from_str(v).ok())
+        .unwrap_or_default()
+}
+
+impl GammaMarket {
+    /// YES (first outcome) token ID.
+    pub fn yes_token_id(&self) -> Option<String> {
+        decode_list(&self.clob_token_ids).into_iter().next()
+    }
+
+    /// NO (second outcome) token ID.
+    pub fn no_token_id(&self) -> Option<String> {
+        decode_list(&self.clob_token_ids).into_iter().nth(1)
+    }
+
+    /// Liquidity reward terms, if the market pays any.
+    pub fn reward_terms(&self) -> Option<RewardTerms> {
+        let daily_rate: f64 = self.clob_rewards.iter().map(|r| r.rewards_daily_rate).sum();
+        let max_spread = self.rewards_max_spread.filter(|s| *s > 0.0)? / 100.0;
+        (daily_rate > 0.0).then(|| RewardTerms { min_size: self.rewards_min_size.unwrap_or(0.0), max_spread, daily_rate })
+    }
+
+    /// Convert into the CLOB `Market` shape used by the rest of the bot.
+    pub fn to_market(&self) -> Market {
+        let outcomes = decode_list(&self.outcomes);
+        let token_ids = decode_list(&self.clob_token_ids);
+        let prices = decode_list(&self.outcome_prices);
+
+        let tokens = token_ids
+            .into_iter()
+            .enumerate()
+            .map(|(i, token_id)| Token {
+                token_id,
+                outcome: outcomes.get(i).cloned().unwrap_or_default(),
+                price: prices.get(i).and_then(|p| p.parse().ok()),
+            })
+            .collect();
+
+        Market {
+            condition_id: self.condition_id.clone(),
+            question: self.question.clone(),
+            description: None,
+            end_date_iso: self.end_date.clone(),
+            active: self.active,
+            closed: self.closed,
+            tokens,
+            neg_risk: self.neg_risk,
+            event_slug: None,
+            group_item_title: self.group_item_title.clone().filter(|t| !t.is_empty()),
+        }
+    }
+}
+
+/// Slug of the hourly "Up or Down" event covering the hour starting at `start`.
+///
+/// `coin` is the lowercase slug name, e.g. "bitcoin".
+pub fn up_or_down_slug(coin: &str, start: DateTime<Utc>) -> String {
+    let et = start.with_timezone(&New_York);
+    let (is_pm, hour12) = et.hour12();
+    format!(
+        "{}-up-or-down-{}-{}-{}{}-et",
+        coin,
+        month_name(et.month()),
+        et.day(),
+        hour12,
+        if is_pm { "pm" } else { "am" }
+    )
+}
+
+/// Slug of the daily "above $X" strike ladder expiring on `date`'s ET day.
+pub fn above_slug(coin: &str, date: DateTime<Utc>) -> String {
+    let et = date.with_timezone(&New_York);
+    format!("{}-above-on-{}-{}", coin, month_name(et.month()), et.day())
+}
+
+/// Slugs worth querying around `now`: the current and next hourly windows,
+/// plus today's and tomorrow's strike ladders.
+pub fn candidate_slugs(coin: &str, now: DateTime<Utc>) -> Vec<String> {
+    let hour_start = now
+        .with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(now);
+
+    vec![
+        up_or_down_slug(coin, hour_start),
+        up_or_down_slug(coin, hour_start + Duration::hours(1)),
+        above_slug(coin, now),
+        above_slug(coin, now + Duration::days(1)),
+    ]
+}
+
+fn month_name(month: u32) -> &'static str {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june",
+        "july", "august", "september", "october", "november", "december",
+    ];
+    MONTHS[(month as usize).saturating_sub(1) % 12]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_slugs_use_eastern_time() {
+        // 22:00 UTC on Jan 5 is 5PM EST
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 22, 0, 0).unwrap();
+        assert_eq!(up_or_down_slug("bitcoin", start), "bitcoin-up-or-down-january-5-5pm-et");
+        // 03:30 UTC on Jan 6 is still Jan 5 in New York
+        let late = Utc.with_ymd_and_hms(2026, 1, 6, 3, 30, 0).unwrap();
+        assert_eq!(above_slug("ethereum", late), "ethereum-above-on-january-5");
+    }
+
+    #[test]
+    fn test_gamma_market_conversion() {
+        let gamma = GammaMarket {
+            question: "Bitcoin Up or Down - January 5, 5PM ET".to_string(),
+            active: true,
+            outcomes: Some("[\"Up\", \"Down\"]".to_string()),
+            clob_token_ids: Some("[\"111\", \"222\"]".to_string()),
+            outcome_prices: Some("[\"0.52\", \"0.48\"]".to_string()),
+            ..Default::default()
+        };
+        let market = gamma.to_market();
+        assert_eq!(market.tokens.len(), 2);
+        assert_eq!(market.tokens[0].token_id, "111");
+        assert_eq!(market.tokens[0].outcome, "Up");
+        assert_eq!(market.tokens[1].price, Some(0.48));
+        assert!(market.is_binary());
+        assert_eq!(market.yes_token().unwrap().token_id, "111");
+        assert_eq!(market.outcome_label(), gamma.question);
+        assert!(gamma.reward_terms().is_none());
+
+        let rewarded: GammaMarket = serde_json::from_str(
+            r#"{"rewardsMinSize": 50, "rewardsMaxSpread": 3.5, "clobRewards": [{"rewardsDailyRate": 25}]}"#,
+        )
+        .unwrap();
+        let terms = rewarded.reward_terms().unwrap();
+        assert_eq!((terms.min_size, terms.daily_rate), (50.0, 25.0));
+        assert!((terms.max_spread - 0.035).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_multi_outcome_event() {
+        let candidate = |name: &str, token: &str, yes: &str| GammaMarket {
+            question: format!("Will {} be the largest crypto on December 31?", name),
+            active: true,
+            outcomes: Some("[\"Yes\", \"No\"]".to_string()),
+            clob_token_ids: Some(format!("[\"{}\", \"{}-no\"]", token, token)),
+            outcome_prices: Some(format!("[\"{}\", \"0\"]", yes)),
+            group_item_title: Some(name.to_string()),
+            ..Default::default()
+        };
+        let event = GammaEvent {
+            slug: "largest-crypto-on-december-31".to_string(),
+            title: "Largest crypto on December 31?".to_string(),
+            neg_risk: true,
+            markets: vec![candidate("Ethereum", "2", "0.08"), candidate("Bitcoin", "1", "0.9"), candidate("Solana", "3", "0.03")],
+        };
+        let event = event.to_outcome_event();
+        assert!(event.is_multi_outcome());
+        assert!(event.markets.iter().all(|m| m.neg_risk && m.event_slug.as_deref() == Some("largest-crypto-on-december-31")));
+        assert_eq!(event.outcomes()[0], ("Bitcoin", Some(0.9)));
+        assert_eq!(event.markets[2].outcome_label(), "Solana");
+        assert!((event.yes_total() - 1.01).abs() < 1e-9);
+    }
+}
@@ -1,9 +1,17 @@
 //! Polymarket integration module.
 //!
-//! Provides client for interacting with Polymarket's CLOB API and smart contracts.
+//! Provides client for interacting with Polymarket's CLOB API and smart contracts,
+//! and a shared client for the public data-api.
 
 pub mod client;
 pub mod types;
 pub mod signer;
+pub mod order_builder;
+pub mod discovery;
+pub mod user_stream;
+pub mod data_api;
 
 pub use client::PolymarketClient;
+pub use data_api::DataApiClient;
+pub use order_builder::OrderBuilder;
+pub use user_stream::UserStream;
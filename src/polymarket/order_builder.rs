@@ -0,0 +1,112 @@
+//! Polymarket order builder.
+//!
+//! Each CLOB market has its own tick size (1¢, or 0.1¢ once a price nears 0
+//! or 1) and minimum order size, both published with its book. The builder
+//! rounds an order onto that market's grid, rejects what the exchange would
+//! refuse, and computes the exact maker/taker amounts the EIP-712 payload
+//! carries, so nothing invalid is ever signed.
+
+use anyhow::{Context, Result};
+
+use super::types::{Order, OrderBook, Side};
+use crate::utils::VenueConstraints;
+
+/// 6-decimal base units of USDC and conditional tokens.
+const BASE_UNITS: f64 = 1_000_000.0;
+
+/// Constraints for a market from its CLOB book, falling back to the
+/// defaults for anything the book does not publish.
+pub fn constraints(book: &OrderBook) -> VenueConstraints {
+    let mut constraints = VenueConstraints::polymarket();
+    if book.tick_size > 0.0 {
+        constraints = constraints.with_tick_size(book.tick_size);
+    }
+    if book.min_order_size > 0.0 {
+        constraints = constraints.with_min_size(book.min_order_size);
+    }
+    constraints
+}
+
+/// Rounds and validates orders against one market's constraints.
+#[derive(Debug, Clone, Copy)]
+pub struct OrderBuilder {
+    constraints: VenueConstraints,
+}
+
+impl OrderBuilder {
+    pub fn new(constraints: VenueConstraints) -> Self {
+        Self { constraints }
+    }
+
+    /// The order snapped to the tick and lot grid, or why the exchange
+    /// would reject it.
+    pub fn build(&self, mut order: Order) -> Result<Order> {
+        if order.token_id.is_empty() || !order.token_id.bytes().all(|b| b.is_ascii_digit()) {
+            anyhow::bail!("polymarket: invalid token ID {:?}", order.token_id);
+        }
+        if !order.price.is_finite() || !order.size.is_finite() {
+            anyhow::bail!("polymarket: non-finite price {} or size {}", order.price, order.size);
+        }
+        (order.price, order.size) = self.constraints.normalize(order.price, order.size, order.side)?;
+        let (maker, taker) = amounts(&order)?;
+        if maker == 0 || taker == 0 {
+            anyhow::bail!("polymarket: order of {} at {} rounds to nothing", order.size, order.price);
+        }
+        Ok(order)
+    }
+}
+
+/// Maker and taker amounts in base units: buyers give USDC for shares,
+/// sellers the reverse. Exact for any order on a tick and lot grid.
+pub fn amounts(order: &Order) -> Result<(u64, u64)> {
+    let shares = (order.size * BASE_UNITS).round() as u128;
+    let price = (order.price * BASE_UNITS).round() as u128;
+    let usdc = shares * price / BASE_UNITS as u128;
+    let shares = u64::try_from(shares).context("Order size out of range")?;
+    let usdc = u64::try_from(usdc).context("Order amount out of range")?;
+    Ok(match order.side {
+        Side::Buy => (usdc, shares),
+        Side::Sell => (shares, usdc),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::types::OrderType;
+
+    fn order(side: Side, price: f64, size: f64) -> Order {
+        Order { token_id: "12345".to_string(), side, price, size, order_type: OrderType::Gtc }
+    }
+
+    #[test]
+    fn test_builds_orders_on_the_market_grid() {
+        let book: OrderBook = serde_json::from_value(serde_json::json!({
+            "market": "0xabc", "asset_id": "12345", "timestamp": "0", "hash": "h",
+            "bids": [], "asks": [], "tick_size": "0.001", "min_order_size": "15"
+        }))
+        .unwrap();
+        let builder = OrderBuilder::new(constraints(&book));
+
+        // Buys round down, sells up, onto the 0.1¢ grid
+        let buy = builder.build(order(Side::Buy, 0.95749, 12.345 + 5.0)).unwrap();
+        assert_eq!((buy.price, buy.size), (0.957, 17.34));
+        assert_eq!(amounts(&buy).unwrap(), (16_594_380, 17_340_000));
+        let sell = builder.build(order(Side::Sell, 0.95701, 20.0)).unwrap();
+        assert_eq!(sell.price, 0.958);
+        assert_eq!(amounts(&sell).unwrap(), (20_000_000, 19_160_000));
+
+        // The market's minimum, not the default 5 shares, applies
+        assert!(builder.build(order(Side::Buy, 0.5, 10.0)).is_err());
+        assert!(builder.build(order(Side::Buy, 1.5, 20.0)).is_err());
+        assert!(builder.build(order(Side::Buy, f64::NAN, 20.0)).is_err());
+        let mut bad = order(Side::Buy, 0.5, 20.0);
+        bad.token_id = "0xabc".to_string();
+        assert!(builder.build(bad).is_err());
+
+        // Books without the fields keep the defaults
+        let mut plain = book.clone();
+        (plain.tick_size, plain.min_order_size) = (0.0, 0.0);
+        assert_eq!(constraints(&plain), VenueConstraints::polymarket());
+    }
+}
@@ -1,15 +1,76 @@
 //! Ethereum transaction signer for Polymarket.
 //!
 //! Handles signing of orders and transactions for the CLOB API.
+//!
+//! Orders are EIP-712 signed for the exchange that settles them: the CTF
+//! Exchange, or the Neg Risk CTF Exchange for markets of a negRisk event.
+//! Accounts created with an email/Magic login or through the browser keep
+//! funds in a proxy wallet: the order's `maker` is then the proxy (the
+//! "funder") while the key that signs is the owner.
 
 use anyhow::{Context, Result};
+use ethers::abi::{encode, Token};
 use ethers::signers::{LocalWallet, Signer};
-use ethers::types::{Address, Signature};
+use ethers::types::{Address, Signature, H256, U256};
+use ethers::utils::keccak256;
 use sha2::Sha256;
 use hmac::{Hmac, Mac};
+use std::str::FromStr;
+
+use super::order_builder;
+use super::types::{Order, Side, SignedOrder};
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Polygon mainnet.
+const CHAIN_ID: u64 = 137;
+/// Polymarket CTF Exchange, the EIP-712 verifying contract.
+const EXCHANGE_ADDRESS: &str = "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E";
+/// Neg Risk CTF Exchange, the verifying contract for neg-risk markets.
+const NEG_RISK_EXCHANGE_ADDRESS: &str = "0xC5d563A36AE78145C45a50134d48A1215220f80a";
+const DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const ORDER_TYPE: &str = "Order(uint256 salt,address maker,address signer,address taker,uint256 tokenId,uint256 makerAmount,uint256 takerAmount,uint256 expiration,uint256 nonce,uint256 feeRateBps,uint8 side,uint8 signatureType)";
+
+/// How the exchange verifies an order's signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureType {
+    /// Funds and key in the same externally owned account
+    Eoa,
+    /// Polymarket proxy wallet (email/Magic accounts)
+    PolyProxy,
+    /// Gnosis Safe proxy (browser wallet accounts)
+    PolyGnosisSafe,
+}
+
+impl SignatureType {
+    /// Value in the signed order.
+    pub fn id(&self) -> u8 {
+        match self {
+            SignatureType::Eoa => 0,
+            SignatureType::PolyProxy => 1,
+            SignatureType::PolyGnosisSafe => 2,
+        }
+    }
+
+    /// Whether funds live in a separate proxy wallet.
+    pub fn is_proxy(&self) -> bool {
+        *self != SignatureType::Eoa
+    }
+}
+
+impl FromStr for SignatureType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_uppercase().as_str() {
+            "EOA" | "0" => SignatureType::Eoa,
+            "POLY_PROXY" | "1" => SignatureType::PolyProxy,
+            "POLY_GNOSIS_SAFE" | "2" => SignatureType::PolyGnosisSafe,
+            other => anyhow::bail!("Unknown signature type '{}': expected EOA, POLY_PROXY or POLY_GNOSIS_SAFE", other),
+        })
+    }
+}
+
 /// Polymarket order signer.
 pub struct PolymarketSigner {
     /// Ethereum wallet for signing
@@ -20,6 +81,10 @@ pub struct PolymarketSigner {
     api_secret: String,
     /// API passphrase
     passphrase: String,
+    /// How orders are signed
+    signature_type: SignatureType,
+    /// Proxy wallet holding the funds, for proxy signature types
+    funder: Option<Address>,
 }
 
 impl PolymarketSigner {
@@ -39,9 +104,38 @@ impl PolymarketSigner {
             api_key: api_key.to_string(),
             api_secret: api_secret.to_string(),
             passphrase: passphrase.to_string(),
+            signature_type: SignatureType::Eoa,
+            funder: None,
         })
     }
 
+    /// Sign for a proxy wallet account. Proxy signature types need the
+    /// funder (proxy) address; EOA accounts ignore it.
+    pub fn with_signature_type(mut self, signature_type: SignatureType, funder: &str) -> Result<Self> {
+        self.funder = match (signature_type.is_proxy(), funder.trim()) {
+            (true, "") => anyhow::bail!("{:?} accounts require POLYMARKET_FUNDER (the proxy wallet address)", signature_type),
+            (true, funder) => Some(funder.parse().context("Invalid POLYMARKET_FUNDER address")?),
+            (false, _) => None,
+        };
+        self.signature_type = signature_type;
+        Ok(self)
+    }
+
+    /// How this account's orders are signed.
+    pub fn signature_type(&self) -> SignatureType {
+        self.signature_type
+    }
+
+    /// API key that owns placed orders.
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Address holding the funds: the proxy wallet, or the signer itself.
+    pub fn maker(&self) -> Address {
+        self.funder.unwrap_or_else(|| self.address())
+    }
+
     /// Get the wallet address.
     pub fn address(&self) -> Address {
         self.wallet.address()
@@ -91,26 +185,76 @@ impl PolymarketSigner {
         ]
     }
 
-    /// Sign an order for the CLOB.
-    pub async fn sign_order(
-        &self,
-        token_id: &str,
-        price: f64,
-        size: f64,
-        side: &str,
-        nonce: u64,
-    ) -> Result<String> {
-        // Create the order hash according to Polymarket's EIP-712 spec
-        let order_data = format!(
-            "{}:{}:{}:{}:{}",
-            token_id, price, size, side, nonce
-        );
-        
-        let signature = self.sign_message(order_data.as_bytes()).await?;
-        Ok(format!("0x{}", signature))
+    /// Build and EIP-712 sign an order for the CLOB. `neg_risk` picks the
+    /// exchange the signature is for; `fee_rate_bps` must be at least the
+    /// market's fee rate or the CLOB refuses the order.
+    pub fn sign_order(&self, order: &Order, salt: u64, neg_risk: bool, fee_rate_bps: u32) -> Result<SignedOrder> {
+        let (maker_amount, taker_amount) = order_builder::amounts(order)?;
+        let mut signed = SignedOrder {
+            salt,
+            maker: format!("{:?}", self.maker()),
+            signer: format!("{:?}", self.address()),
+            taker: format!("{:?}", Address::zero()),
+            token_id: order.token_id.clone(),
+            maker_amount: maker_amount.to_string(),
+            taker_amount: taker_amount.to_string(),
+            expiration: "0".to_string(),
+            nonce: "0".to_string(),
+            fee_rate_bps: fee_rate_bps.to_string(),
+            side: order.side,
+            signature_type: self.signature_type.id(),
+            signature: String::new(),
+        };
+        let signature = self.wallet
+            .sign_hash(order_digest(&signed, neg_risk)?)
+            .context("Failed to sign order")?;
+        signed.signature = format!("0x{}", signature);
+        Ok(signed)
     }
 }
 
+/// EIP-712 digest the exchange verifies an order's signature against.
+pub fn order_digest(order: &SignedOrder, neg_risk: bool) -> Result<H256> {
+    let uint = |field: &str, value: &str| U256::from_dec_str(value)
+        .with_context(|| format!("Invalid {} {}", field, value));
+    let address = |field: &str, value: &str| value.parse::<Address>()
+        .with_context(|| format!("Invalid {} {}", field, value));
+    let side: u8 = match order.side {
+        Side::Buy => 0,
+        Side::Sell => 1,
+    };
+
+    let struct_hash = keccak256(encode(&[
+        Token::FixedBytes(keccak256(ORDER_TYPE).to_vec()),
+        Token::Uint(order.salt.into()),
+        Token::Address(address("maker", &order.maker)?),
+        Token::Address(address("signer", &order.signer)?),
+        Token::Address(address("taker", &order.taker)?),
+        Token::Uint(uint("token ID", &order.token_id)?),
+        Token::Uint(uint("maker amount", &order.maker_amount)?),
+        Token::Uint(uint("taker amount", &order.taker_amount)?),
+        Token::Uint(uint("expiration", &order.expiration)?),
+        Token::Uint(uint("nonce", &order.nonce)?),
+        Token::Uint(uint("fee rate", &order.fee_rate_bps)?),
+        Token::Uint(side.into()),
+        Token::Uint(order.signature_type.into()),
+    ]));
+    Ok(H256::from(keccak256([&[0x19, 0x01][..], &domain_separator(neg_risk), &struct_hash].concat())))
+}
+
+/// EIP-712 domain of the CTF Exchange, or of the Neg Risk CTF Exchange.
+fn domain_separator(neg_risk: bool) -> [u8; 32] {
+    let exchange = if neg_risk { NEG_RISK_EXCHANGE_ADDRESS } else { EXCHANGE_ADDRESS };
+    let exchange: Address = exchange.parse().expect("valid exchange address");
+    keccak256(encode(&[
+        Token::FixedBytes(keccak256(DOMAIN_TYPE).to_vec()),
+        Token::FixedBytes(keccak256("Polymarket CTF Exchange").to_vec()),
+        Token::FixedBytes(keccak256("1").to_vec()),
+        Token::Uint(CHAIN_ID.into()),
+        Token::Address(exchange),
+    ]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +266,45 @@ mod tests {
         let signer = PolymarketSigner::new(test_key, "key", "secret", "pass");
         assert!(signer.is_ok());
     }
+
+    #[test]
+    fn test_proxy_orders_name_funder_as_maker() {
+        let test_key = "0x0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+        let funder = "0x1111111111111111111111111111111111111111";
+        let signer = || PolymarketSigner::new(test_key, "key", "secret", "pass").unwrap();
+        assert!(signer().with_signature_type(SignatureType::PolyProxy, "").is_err());
+        let signer = signer().with_signature_type(SignatureType::PolyProxy, funder).unwrap();
+
+        let order = Order {
+            token_id: "12345".to_string(),
+            side: Side::Buy,
+            price: 0.45,
+            size: 10.0,
+            order_type: crate::polymarket::types::OrderType::Gtc,
+        };
+        let signed = signer.sign_order(&order, 7, false, 0).unwrap();
+        assert_eq!(signed.maker, funder);
+        assert_eq!(signed.signer, format!("{:?}", signer.address()));
+        assert_eq!((signed.maker_amount.as_str(), signed.taker_amount.as_str()), ("4500000", "10000000"));
+        assert_eq!(signed.signature_type, 1);
+        assert_eq!("poly_gnosis_safe".parse::<SignatureType>().unwrap(), SignatureType::PolyGnosisSafe);
+
+        // The signature recovers to the owner key, not the proxy
+        let sig: Signature = signed.signature.trim_start_matches("0x").parse().unwrap();
+        assert_eq!(sig.recover(order_digest(&signed, false).unwrap()).unwrap(), signer.address());
+
+        // Neg-risk orders are signed for the Neg Risk exchange
+        let neg_risk = signer.sign_order(&order, 7, true, 0).unwrap();
+        let sig: Signature = neg_risk.signature.trim_start_matches("0x").parse().unwrap();
+        assert_ne!(order_digest(&neg_risk, true).unwrap(), order_digest(&neg_risk, false).unwrap());
+        assert_eq!(sig.recover(order_digest(&neg_risk, true).unwrap()).unwrap(), signer.address());
+
+        // The market's fee rate is carried and signed over
+        let fee_bearing = signer.sign_order(&order, 7, false, 100).unwrap();
+        assert_eq!(signed.fee_rate_bps, "0");
+        assert_eq!(fee_bearing.fee_rate_bps, "100");
+        let sig: Signature = fee_bearing.signature.trim_start_matches("0x").parse().unwrap();
+        assert_ne!(order_digest(&fee_bearing, false).unwrap(), order_digest(&signed, false).unwrap());
+        assert_eq!(sig.recover(order_digest(&fee_bearing, false).unwrap()).unwrap(), signer.address());
+    }
 }
@@ -17,8 +17,76 @@ pub struct Market {
     pub active: bool,
     /// Whether the market is closed
     pub closed: bool,
-    /// Market tokens (YES/NO)
+    /// Market tokens (YES/NO, or one per outcome)
     pub tokens: Vec<Token>,
+    /// Part of a negative-risk event: its outcomes are mutually exclusive
+    /// and one NO share converts into a YES share of every other outcome
+    #[serde(default)]
+    pub neg_risk: bool,
+    /// Slug of the event grouping this market, if any
+    #[serde(default)]
+    pub event_slug: Option<String>,
+    /// The outcome this market stands for within its event (e.g. a
+    /// candidate's name)
+    #[serde(default)]
+    pub group_item_title: Option<String>,
+}
+
+impl Market {
+    /// Whether the market has exactly two outcomes.
+    pub fn is_binary(&self) -> bool {
+        self.tokens.len() == 2
+    }
+
+    /// Token for the YES/"Up" side, or the first outcome.
+    pub fn yes_token(&self) -> Option<&Token> {
+        self.tokens
+            .iter()
+            .find(|t| matches!(t.outcome.to_lowercase().as_str(), "yes" | "up"))
+            .or_else(|| self.tokens.first())
+    }
+
+    /// Name of the outcome within its event, falling back to the question.
+    pub fn outcome_label(&self) -> &str {
+        self.group_item_title.as_deref().filter(|t| !t.is_empty()).unwrap_or(&self.question)
+    }
+}
+
+/// An event grouping several markets, e.g. one YES/NO market per candidate
+/// of an election.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutcomeEvent {
+    pub slug: String,
+    pub title: String,
+    /// Outcomes are mutually exclusive (negRisk)
+    pub neg_risk: bool,
+    /// One market per outcome
+    pub markets: Vec<Market>,
+}
+
+impl OutcomeEvent {
+    /// More than one outcome to choose from.
+    pub fn is_multi_outcome(&self) -> bool {
+        self.markets.len() > 1
+    }
+
+    /// YES price of each open outcome, highest first.
+    pub fn outcomes(&self) -> Vec<(&str, Option<f64>)> {
+        let mut outcomes: Vec<_> = self
+            .markets
+            .iter()
+            .filter(|m| !m.closed)
+            .map(|m| (m.outcome_label(), m.yes_token().and_then(|t| t.price)))
+            .collect();
+        outcomes.sort_by(|a, b| b.1.unwrap_or(0.0).total_cmp(&a.1.unwrap_or(0.0)));
+        outcomes
+    }
+
+    /// Sum of the open outcomes' YES prices. Exactly one outcome of a
+    /// negRisk event pays out, so this sits near 1.0.
+    pub fn yes_total(&self) -> f64 {
+        self.outcomes().iter().filter_map(|(_, p)| *p).sum()
+    }
 }
 
 /// Represents a token (YES or NO outcome) in a market.
@@ -56,6 +124,12 @@ pub struct OrderBook {
     pub bids: Vec<PriceLevel>,
     /// Ask orders (price, size)
     pub asks: Vec<PriceLevel>,
+    /// Minimum price increment; 0 when not sent
+    #[serde(default, deserialize_with = "crate::utils::tolerant::f64_from_any")]
+    pub tick_size: f64,
+    /// Minimum order size in shares; 0 when not sent
+    #[serde(default, deserialize_with = "crate::utils::tolerant::f64_from_any")]
+    pub min_order_size: f64,
 }
 
 impl OrderBook {
@@ -95,6 +169,58 @@ pub struct Order {
     pub order_type: OrderType,
 }
 
+/// EIP-712 signed order in the CLOB's wire format. Amounts are in
+/// 6-decimal base units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedOrder {
+    pub salt: u64,
+    /// Address holding the funds (the proxy wallet for proxy accounts)
+    pub maker: String,
+    /// Address whose key signed the order
+    pub signer: String,
+    pub taker: String,
+    pub token_id: String,
+    pub maker_amount: String,
+    pub taker_amount: String,
+    pub expiration: String,
+    pub nonce: String,
+    pub fee_rate_bps: String,
+    pub side: Side,
+    pub signature_type: u8,
+    pub signature: String,
+}
+
+/// Body of `POST /order`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostOrder {
+    pub order: SignedOrder,
+    /// API key of the order's owner
+    pub owner: String,
+    pub order_type: OrderType,
+}
+
+/// Response from `GET /balance-allowance`; amounts in 6-decimal base units.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceAllowance {
+    #[serde(deserialize_with = "crate::utils::tolerant::f64_from_any")]
+    pub balance: f64,
+}
+
+/// Response from `GET /fee-rate`: the market's fee in basis points, which
+/// a signed order must carry at least.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeRate {
+    pub base_fee: u32,
+}
+
+/// Response from `GET /neg-risk`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NegRisk {
+    pub neg_risk: bool,
+}
+
 /// Order type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -120,6 +246,19 @@ pub struct OrderResponse {
     pub executions: Option<Vec<Execution>>,
 }
 
+impl OrderResponse {
+    /// Shares matched when the order was placed.
+    pub fn filled_size(&self) -> f64 {
+        self.executions.iter().flatten().map(|e| e.size).sum()
+    }
+
+    /// Size-weighted price of the matches made when the order was placed.
+    pub fn avg_fill_price(&self) -> Option<f64> {
+        let size = self.filled_size();
+        (size > 0.0).then(|| self.executions.iter().flatten().map(|e| e.price * e.size).sum::<f64>() / size)
+    }
+}
+
 /// Trade execution details.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Execution {
@@ -0,0 +1,369 @@
+//! Polymarket authenticated user websocket.
+//!
+//! The `user` channel pushes updates for our own orders and trades, so
+//! fills are confirmed as they happen instead of by polling the CLOB. With
+//! FIXTURE_MODE=record every frame is written to the fixture store, and
+//! with replay the recorded frames are routed instead of connecting.
+
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::monitor::system::{self, StreamState};
+use crate::utils::fixtures::{FixtureMode, FixtureStore};
+use crate::utils::{tolerant, Fill, FillRouter};
+
+/// User channel endpoint.
+const USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
+/// The server drops connections that stay silent longer than this.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+const VENUE: &str = "polymarket";
+/// Name the connection state is reported under.
+pub const STREAM: &str = "polymarket_user";
+
+/// Our order as reported on the user channel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrderEvent {
+    pub id: String,
+    #[serde(default)]
+    pub asset_id: String,
+    #[serde(default)]
+    pub side: String,
+    /// PLACEMENT, UPDATE or CANCELLATION
+    #[serde(rename = "type", default)]
+    pub update_type: String,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub original_size: f64,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub size_matched: f64,
+}
+
+/// A maker order matched by a trade.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MakerOrder {
+    pub order_id: String,
+    #[serde(default)]
+    pub owner: String,
+    #[serde(default)]
+    pub asset_id: String,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub matched_amount: f64,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub price: f64,
+    pub side: Option<String>,
+}
+
+/// A trade involving one of our orders.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TradeEvent {
+    pub id: String,
+    #[serde(default)]
+    pub asset_id: String,
+    #[serde(default)]
+    pub side: String,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub price: f64,
+    #[serde(deserialize_with = "tolerant::f64_from_any")]
+    pub size: f64,
+    /// MATCHED, MINED, CONFIRMED, RETRYING or FAILED
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub taker_order_id: String,
+    /// TAKER or MAKER: which side of the trade we were on
+    #[serde(default)]
+    pub trader_side: String,
+    #[serde(default)]
+    pub maker_orders: Vec<MakerOrder>,
+    /// Unix seconds
+    #[serde(default, deserialize_with = "tolerant::f64_from_any")]
+    pub timestamp: f64,
+}
+
+/// A user channel message.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event_type", rename_all = "lowercase")]
+pub enum UserEvent {
+    Order(OrderEvent),
+    Trade(TradeEvent),
+}
+
+/// Parse a frame, which may hold one event or an array of them. Unknown
+/// event types are skipped.
+pub fn parse_events(text: &str) -> Vec<UserEvent> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return Vec::new();
+    };
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value(item).ok())
+        .collect()
+}
+
+/// Our fills in a trade. `api_key` identifies our maker orders.
+pub fn trade_fills(trade: &TradeEvent, api_key: &str) -> Vec<Fill> {
+    // Only the first notification counts; MINED/CONFIRMED repeat the match
+    if !trade.status.eq_ignore_ascii_case("matched") {
+        return Vec::new();
+    }
+    let filled_at = Utc
+        .timestamp_opt(trade.timestamp as i64, 0)
+        .single()
+        .filter(|_| trade.timestamp > 0.0)
+        .unwrap_or_else(Utc::now);
+
+    if trade.trader_side.eq_ignore_ascii_case("maker") {
+        // Makers take the other side of the taker
+        let maker_side = if trade.side.eq_ignore_ascii_case("buy") { "SELL" } else { "BUY" };
+        trade.maker_orders
+            .iter()
+            .filter(|m| m.owner == api_key)
+            .map(|m| Fill {
+                venue: VENUE.to_string(),
+                order_id: m.order_id.clone(),
+                trade_id: trade.id.clone(),
+                market: if m.asset_id.is_empty() { trade.asset_id.clone() } else { m.asset_id.clone() },
+                side: m.side.clone().unwrap_or_else(|| maker_side.to_string()).to_uppercase(),
+                price: m.price,
+                size: m.matched_amount,
+                filled_at,
+            })
+            .collect()
+    } else {
+        vec![Fill {
+            venue: VENUE.to_string(),
+            order_id: trade.taker_order_id.clone(),
+            trade_id: trade.id.clone(),
+            market: trade.asset_id.clone(),
+            side: trade.side.to_uppercase(),
+            price: trade.price,
+            size: trade.size,
+            filled_at,
+        }]
+    }
+}
+
+/// Streams our order updates and fills into a [`FillRouter`].
+pub struct UserStream {
+    api_key: String,
+    secret: String,
+    passphrase: String,
+    fixtures: Option<Arc<FixtureStore>>,
+}
+
+impl UserStream {
+    /// Build from config; the user channel needs CLOB API credentials.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        if !config.has_polymarket_credentials() {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            api_key: config.polymarket_api_key.clone(),
+            secret: config.polymarket_secret.clone(),
+            passphrase: config.polymarket_passphrase.clone(),
+            fixtures: FixtureStore::from_config(config)?,
+        }))
+    }
+
+    /// Record frames to, or replay them from, a fixture store.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.fixtures = Some(store);
+        self
+    }
+
+    /// Whether frames are replayed rather than streamed.
+    pub fn is_replay(&self) -> bool {
+        self.fixtures.as_ref().is_some_and(|f| f.mode() == FixtureMode::Replay)
+    }
+
+    /// Route every event of a frame.
+    fn handle_frame(&self, text: &str, router: &FillRouter) {
+        if let Some(store) = &self.fixtures {
+            if let Err(e) = store.record_frame(STREAM, text) {
+                warn!("Failed to record user channel frame: {}", e);
+            }
+        }
+        for event in parse_events(text) {
+            self.apply(event, router);
+        }
+    }
+
+    /// Route the frames recorded in `store`, in arrival order. Returns the
+    /// number of frames.
+    pub fn replay(&self, store: &FixtureStore, router: &FillRouter) -> Result<usize> {
+        let frames = store.load_frames(STREAM)?;
+        for frame in &frames {
+            self.handle_frame(frame, router);
+        }
+        Ok(frames.len())
+    }
+
+    /// Route one event.
+    pub fn apply(&self, event: UserEvent, router: &FillRouter) {
+        match event {
+            UserEvent::Trade(trade) => {
+                for fill in trade_fills(&trade, &self.api_key) {
+                    router.on_fill(fill);
+                }
+            }
+            UserEvent::Order(order) => {
+                let fully_filled = order.original_size > 0.0 && order.size_matched >= order.original_size;
+                if order.update_type.eq_ignore_ascii_case("cancellation") {
+                    router.on_order_closed(VENUE, &order.id, "cancelled");
+                } else if fully_filled {
+                    router.on_order_closed(VENUE, &order.id, "filled");
+                } else {
+                    debug!(
+                        "Order {} {}: {}/{} matched",
+                        order.id, order.update_type, order.size_matched, order.original_size
+                    );
+                }
+            }
+        }
+    }
+
+    /// Connect, subscribe and route events until the connection drops. In
+    /// replay mode the recorded frames are routed and it returns.
+    pub async fn run(&self, router: &FillRouter) -> Result<()> {
+        if let Some(store) = self.fixtures.as_ref().filter(|_| self.is_replay()) {
+            let frames = self.replay(store, router)?;
+            info!("📼 Replayed {} Polymarket user channel frames", frames);
+            return Ok(());
+        }
+        system::set_stream(STREAM, StreamState::Connecting, None);
+        let (socket, _) = connect_async(USER_WS_URL)
+            .await
+            .context("Failed to connect to Polymarket user channel")?;
+        let (mut write, mut read) = socket.split();
+
+        let subscribe = json!({
+            "type": "user",
+            "markets": [],
+            "auth": {
+                "apiKey": self.api_key,
+                "secret": self.secret,
+                "passphrase": self.passphrase,
+            },
+        });
+        write.send(Message::Text(subscribe.to_string())).await?;
+        info!("📡 Subscribed to Polymarket user channel");
+        system::set_stream(STREAM, StreamState::Connected, None);
+
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ping.tick() => {
+                    write.send(Message::Text("PING".to_string())).await?;
+                }
+                message = read.next() => {
+                    let message = match message {
+                        Some(m) => m.context("Polymarket user channel error")?,
+                        None => anyhow::bail!("Polymarket user channel closed"),
+                    };
+                    match message {
+                        Message::Text(text) if text != "PONG" => self.handle_frame(&text, router),
+                        Message::Ping(data) => write.send(Message::Pong(data)).await?,
+                        Message::Close(frame) => {
+                            warn!("Polymarket user channel closed by server: {:?}", frame);
+                            anyhow::bail!("Polymarket user channel closed");
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::PositionTracker;
+
+    fn stream(fixtures: Arc<FixtureStore>) -> UserStream {
+        UserStream {
+            api_key: "key".to_string(),
+            secret: String::new(),
+            passphrase: String::new(),
+            fixtures: None,
+        }
+        .with_fixtures(fixtures)
+    }
+
+    #[test]
+    fn test_parses_taker_and_maker_fills() {
+        let frame = r#"[
+            {"event_type":"trade","id":"t1","asset_id":"tok","side":"BUY","price":"0.45","size":"10",
+             "status":"MATCHED","taker_order_id":"ours","trader_side":"TAKER","timestamp":"1700000000","maker_orders":[]},
+            {"event_type":"trade","id":"t2","asset_id":"tok","side":"BUY","price":"0.46","size":"8",
+             "status":"MATCHED","taker_order_id":"theirs","trader_side":"MAKER","timestamp":"1700000001",
+             "maker_orders":[
+                {"order_id":"mine","owner":"key","asset_id":"tok","matched_amount":"5","price":"0.46"},
+                {"order_id":"other","owner":"someone","asset_id":"tok","matched_amount":"3","price":"0.46"}]},
+            {"event_type":"trade","id":"t1","asset_id":"tok","side":"BUY","price":"0.45","size":"10",
+             "status":"CONFIRMED","taker_order_id":"ours","trader_side":"TAKER","maker_orders":[]},
+            {"event_type":"order","id":"ours","asset_id":"tok","side":"BUY","type":"UPDATE",
+             "original_size":"10","size_matched":"10"},
+            {"event_type":"last_trade_price","asset_id":"tok"}
+        ]"#;
+        let events = parse_events(frame);
+        assert_eq!(events.len(), 4);
+
+        let fills: Vec<Fill> = events
+            .iter()
+            .filter_map(|e| match e {
+                UserEvent::Trade(t) => Some(trade_fills(t, "key")),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        assert_eq!(fills.len(), 2, "confirmation of t1 is not a new fill");
+        assert_eq!((fills[0].order_id.as_str(), fills[0].side.as_str(), fills[0].size), ("ours", "BUY", 10.0));
+        assert_eq!(fills[0].filled_at.timestamp(), 1_700_000_000);
+        assert_eq!((fills[1].order_id.as_str(), fills[1].side.as_str(), fills[1].size), ("mine", "SELL", 5.0));
+
+        match &events[3] {
+            UserEvent::Order(o) => assert_eq!(o.size_matched, o.original_size),
+            other => panic!("expected order event, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recorded_session_replays_into_the_router() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ws");
+        let replay = stream(Arc::new(FixtureStore::new(dir, FixtureMode::Replay)));
+        assert!(replay.is_replay());
+        let router = FillRouter::new(Arc::new(PositionTracker::new()));
+        replay.run(&router).await.unwrap();
+
+        // Our taker order filled in two trades, the confirmation isn't a
+        // third, and our resting bid was hit for 3 more
+        assert_eq!(router.filled_size("ours"), 10.0);
+        assert_eq!(router.filled_size("resting"), 3.0);
+        assert_eq!(router.positions().get_position("polymarket", "tok").unwrap().quantity, 13);
+
+        // Frames routed while recording replay the same way
+        let recorded = std::env::temp_dir().join(format!("polybot-ws-{}", uuid::Uuid::new_v4()));
+        let recorder = stream(Arc::new(FixtureStore::new(&recorded, FixtureMode::Record)));
+        let live = FillRouter::new(Arc::new(PositionTracker::new()));
+        for frame in FixtureStore::new(dir, FixtureMode::Replay).load_frames(STREAM).unwrap() {
+            recorder.handle_frame(&frame, &live);
+        }
+        let store = FixtureStore::new(&recorded, FixtureMode::Replay);
+        let again = FillRouter::new(Arc::new(PositionTracker::new()));
+        assert_eq!(recorder.replay(&store, &again).unwrap(), 6);
+        assert_eq!(again.filled_size("ours"), live.filled_size("ours"));
+        let _ = std::fs::remove_dir_all(recorded);
+    }
+}
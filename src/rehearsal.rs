@@ -0,0 +1,333 @@
+//! End-to-end rehearsal of live trading.
+//!
+//! `polybot rehearse` runs the live pipeline once: credentials, market discovery, a
+//! detection pass, the risk checks an arb passes before execution, order
+//! building and signing. It stops right before submission and prints the
+//! signed payloads that would have gone out, so a configuration can be
+//! validated before DRY_RUN is turned off. Nothing is sent, cancelled or
+//! recorded.
+
+use anyhow::Result;
+use std::fmt;
+
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::arbitrage::mutes::{MuteList, MUTES_FILE};
+use crate::arbitrage::{ArbitrageDetector, MarketMatcher, Opportunity, SeriesUniverse};
+use crate::config::Config;
+use crate::display;
+use crate::fees::FeeSchedule;
+use crate::kalshi::types::KalshiOrderRequest;
+use crate::kalshi::KalshiClient;
+use crate::polymarket::types::{Order, OrderType, Side};
+use crate::polymarket::PolymarketClient;
+use crate::risk::derisk::{Derisker, DERISK_FILE};
+use crate::risk::discrepancies::{DiscrepancyLedger, DISCREPANCIES_FILE};
+use crate::strategies::kalshi_follow;
+use crate::utils::circuit_breaker::BREAKER_FILE;
+use crate::utils::http_pool::PreparedRequest;
+use crate::utils::venue_constraints::{self, PairedOrder};
+use crate::utils::{CircuitBreaker, Sizing, VenueConstraints};
+
+/// How a rehearsal step went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// Would not stop live trading, but worth a look
+    Warning,
+    /// Live trading would stop here
+    Failed,
+}
+
+impl Outcome {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Outcome::Passed => "✅",
+            Outcome::Warning => "⚠️ ",
+            Outcome::Failed => "❌",
+        }
+    }
+}
+
+/// One checked stage of the pipeline.
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub stage: &'static str,
+    pub outcome: Outcome,
+    pub detail: String,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:<10} {}", self.outcome.icon(), self.stage, self.detail)
+    }
+}
+
+/// One leg of an arb as the order its venue takes.
+#[derive(Debug, Clone)]
+pub enum LegOrder {
+    Polymarket(Order),
+    Kalshi(KalshiOrderRequest),
+}
+
+/// The two orders of `opportunity` on `matched` at the fitted `order`:
+/// buy YES on its YES venue, NO on its NO venue. A Polymarket NO leg needs
+/// the market's NO token.
+pub fn leg_orders(
+    opportunity: &Opportunity,
+    matched: &MatchedMarket,
+    no_token: Option<&str>,
+    order: &PairedOrder,
+) -> Result<[LegOrder; 2]> {
+    let leg = |venue: &str, outcome: &str, price: f64| -> Result<LegOrder> {
+        match venue {
+            "polymarket" => {
+                let token_id = match outcome {
+                    "yes" => matched.polymarket.native.clone(),
+                    _ => no_token
+                        .ok_or_else(|| anyhow::anyhow!("No NO token known for {}", matched.polymarket))?
+                        .to_string(),
+                };
+                // Arb legs take liquidity and must never rest
+                Ok(LegOrder::Polymarket(Order { token_id, side: Side::Buy, price, size: order.size, order_type: OrderType::Fok }))
+            }
+            "kalshi" => Ok(LegOrder::Kalshi(kalshi_follow::limit_order(
+                &matched.kalshi.native,
+                outcome,
+                "buy",
+                price,
+                order.size.round() as i32,
+            ))),
+            other => anyhow::bail!("Unknown venue '{}'", other),
+        }
+    };
+    Ok([
+        leg(opportunity.yes_venue, "yes", order.price_a)?,
+        leg(opportunity.no_venue, "no", order.price_b)?,
+    ])
+}
+
+/// Result of one rehearsal.
+#[derive(Debug, Default)]
+pub struct Rehearsal {
+    pub steps: Vec<Step>,
+    /// Signed requests that would have been submitted, by venue
+    pub payloads: Vec<(&'static str, PreparedRequest)>,
+}
+
+impl Rehearsal {
+    /// Whether live trading would have reached submission.
+    pub fn passed(&self) -> bool {
+        !self.steps.is_empty() && self.steps.iter().all(|s| s.outcome != Outcome::Failed)
+    }
+
+    fn step(&mut self, stage: &'static str, outcome: Outcome, detail: impl Into<String>) {
+        self.steps.push(Step { stage, outcome, detail: detail.into() });
+    }
+
+    /// Run every stage in order, stopping at the first one live trading
+    /// would stop at.
+    pub async fn run(config: &Config) -> Result<Self> {
+        let mut rehearsal = Self::default();
+        let fees = FeeSchedule::from_config(config)?;
+
+        // Credentials: signing on Polymarket, an authenticated read on both
+        let poly = PolymarketClient::new(config)?;
+        let mut kalshi = KalshiClient::new(config)?;
+        if !poly.can_trade() {
+            rehearsal.step("auth", Outcome::Failed, "Polymarket credentials missing: orders cannot be signed");
+            return Ok(rehearsal);
+        }
+        match poly.get_collateral_balance().await {
+            Ok(balance) => rehearsal.step("auth", Outcome::Passed, format!("Polymarket API key accepted, {} collateral", balance)),
+            Err(e) => rehearsal.step("auth", Outcome::Failed, format!("Polymarket rejected the API credentials: {}", e)),
+        }
+        match kalshi.login().await {
+            Ok(true) => rehearsal.step("auth", Outcome::Passed, "Kalshi API key accepted"),
+            Ok(false) => rehearsal.step("auth", Outcome::Failed, "Kalshi rejected the configured credentials"),
+            Err(e) => rehearsal.step("auth", Outcome::Failed, format!("Kalshi login error: {}", e)),
+        }
+        if !rehearsal.passed() {
+            return Ok(rehearsal);
+        }
+
+        // Discovery: the KALSHI_SERIES universe, or the built-in pairs
+        let universe = SeriesUniverse::new(config.kalshi_series.clone());
+        let mut matcher = MarketMatcher::new();
+        if !universe.is_empty() {
+            match universe.discover(&kalshi, &poly).await {
+                Ok((pairs, _)) => {
+                    universe.apply(&mut matcher, pairs);
+                }
+                Err(e) => {
+                    rehearsal.step("discovery", Outcome::Failed, format!("Discovering {} failed: {}", config.kalshi_series.join(", "), e));
+                    return Ok(rehearsal);
+                }
+            }
+        }
+        let pairs = matcher.get_all().len();
+        if pairs == 0 {
+            rehearsal.step("discovery", Outcome::Failed, "No matched pairs to trade");
+            return Ok(rehearsal);
+        }
+        rehearsal.step("discovery", Outcome::Passed, format!("{} matched pairs", pairs));
+
+        // Detection: one pass, keeping every priced combination so there is
+        // always something to rehearse with
+        let detector = ArbitrageDetector::new(PolymarketClient::new(config)?, KalshiClient::new(config)?, matcher, f64::NEG_INFINITY)
+            .with_fees(fees.clone())
+            .with_stale_after(config.stale_book_secs);
+        let found = match detector.check_all_opportunities().await {
+            Ok(found) => found,
+            Err(e) => {
+                rehearsal.step("detection", Outcome::Failed, format!("Detection pass failed: {}", e));
+                return Ok(rehearsal);
+            }
+        };
+        for outage in detector.venue_outages() {
+            rehearsal.step("detection", Outcome::Warning, format!("{} unreachable: cross-venue detection would be suspended", outage.venue));
+        }
+        let Some(best) = found.into_iter().max_by(|a, b| a.net_edge().total_cmp(&b.net_edge())) else {
+            rehearsal.step("detection", Outcome::Failed, "No pair has asks on both legs");
+            return Ok(rehearsal);
+        };
+        let summary = format!(
+            "{}: YES {} @ {} + NO {} @ {}, {} net",
+            best.market,
+            best.yes_venue,
+            display::price(best.yes_price),
+            best.no_venue,
+            display::price(best.no_price),
+            display::percent(best.net_edge())
+        );
+        let tradeable = best.net_edge() > config.min_profit_threshold;
+        if tradeable {
+            rehearsal.step("detection", Outcome::Passed, summary);
+        } else {
+            rehearsal.step(
+                "detection",
+                Outcome::Warning,
+                format!("{} (below MIN_PROFIT_THRESHOLD {}, rehearsing with it anyway)", summary, display::percent(config.min_profit_threshold)),
+            );
+        }
+        let Some(matched) = detector.matched_markets().into_iter().find(|m| m.name == best.market) else {
+            rehearsal.step("detection", Outcome::Failed, format!("{} is no longer matched", best.market));
+            return Ok(rehearsal);
+        };
+
+        // Risk: what stands between an approved arb and its orders
+        if !CircuitBreaker::load(BREAKER_FILE).is_allowed() {
+            rehearsal.step("risk", Outcome::Failed, "Circuit breaker is tripped: reset it before trading");
+        }
+        if DiscrepancyLedger::new(DISCREPANCIES_FILE).blocked_families(std::slice::from_ref(&matched)).contains(&matched.family()) {
+            rehearsal.step("risk", Outcome::Failed, format!("{} has an unacknowledged discrepancy", matched.name));
+        }
+        if MuteList::new(MUTES_FILE).muted().contains(&matched.name) {
+            rehearsal.step("risk", Outcome::Failed, format!("{} is muted", matched.name));
+        }
+        let mut max_size = config.max_position_size;
+        if let Some(derisker) = Derisker::from_config(config)? {
+            let derisker = derisker.with_state_file(DERISK_FILE);
+            max_size = derisker.max_size(max_size);
+            if derisker.is_derisked() {
+                rehearsal.step("risk", Outcome::Warning, format!("De-risked: max position size cut to ${:.2}", max_size));
+            }
+        }
+        if tradeable && config.revalidate_edge_fraction > 0.0 {
+            match detector.revalidate(&best, config.revalidate_edge_fraction).await {
+                Ok(Some(_)) => {}
+                Ok(None) => rehearsal.step("risk", Outcome::Warning, "Edge evaporated on the pre-execution re-check"),
+                Err(e) => rehearsal.step("risk", Outcome::Failed, format!("Pre-execution re-check failed: {}", e)),
+            }
+        }
+        let paired = venue_constraints::fit_pair(
+            &VenueConstraints::for_venue(best.yes_venue)?,
+            &VenueConstraints::for_venue(best.no_venue)?,
+            best.yes_price,
+            best.no_price,
+            Sizing::Usd(max_size).contracts(best.cost()),
+        );
+        let paired = match paired {
+            Ok(paired) => paired,
+            Err(e) => {
+                rehearsal.step("risk", Outcome::Failed, format!("No size fits both venues within ${:.2}: {}", max_size, e));
+                return Ok(rehearsal);
+            }
+        };
+        if !rehearsal.passed() {
+            return Ok(rehearsal);
+        }
+        rehearsal.step(
+            "risk",
+            Outcome::Passed,
+            format!("{} x YES + NO for ${:.2} of ${:.2} allowed", paired.size, paired.cost(), max_size),
+        );
+
+        // Orders and signing, stopping short of submission
+        let no_token = if best.no_venue == "polymarket" {
+            poly.get_gamma_market_by_token(&matched.polymarket.native).await.ok().flatten().and_then(|m| m.no_token_id())
+        } else {
+            None
+        };
+        let legs = match leg_orders(&best, &matched, no_token.as_deref(), &paired) {
+            Ok(legs) => legs,
+            Err(e) => {
+                rehearsal.step("orders", Outcome::Failed, e.to_string());
+                return Ok(rehearsal);
+            }
+        };
+        for leg in legs {
+            match leg {
+                LegOrder::Polymarket(order) => match poly.prepare_order(order).await {
+                    Ok((order, request)) => {
+                        rehearsal.step(
+                            "signing",
+                            Outcome::Passed,
+                            format!("Polymarket {:?} {} @ {} on {} signed", order.side, order.size, order.price, order.token_id),
+                        );
+                        rehearsal.payloads.push(("polymarket", request));
+                    }
+                    Err(e) => rehearsal.step("signing", Outcome::Failed, format!("Polymarket order refused: {}", e)),
+                },
+                LegOrder::Kalshi(order) => match kalshi.prepare_order(&order) {
+                    Ok(request) => {
+                        rehearsal.step(
+                            "signing",
+                            Outcome::Warning,
+                            format!("Kalshi buy {} x {} on {} built, but Kalshi requests are not RSA-PSS signed yet", order.count, order.side, order.ticker),
+                        );
+                        rehearsal.payloads.push(("kalshi", request));
+                    }
+                    Err(e) => rehearsal.step("signing", Outcome::Failed, format!("Kalshi order refused: {}", e)),
+                },
+            }
+        }
+        Ok(rehearsal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_legs_buy_yes_and_no_on_their_venues() {
+        let matched = MatchedMarket::new("BTC", "111", "KXBTC-1");
+        let fees = FeeSchedule::new();
+        let paired = PairedOrder { price_a: 0.45, price_b: 0.50, size: 12.0 };
+
+        let opp = Opportunity::priced("BTC", ("polymarket", 0.45), ("kalshi", 0.50), &fees, Utc::now()).unwrap();
+        let [yes, no] = leg_orders(&opp, &matched, None, &paired).unwrap();
+        let LegOrder::Polymarket(yes) = yes else { panic!("expected a Polymarket leg") };
+        assert_eq!((yes.token_id.as_str(), yes.side, yes.size, yes.order_type), ("111", Side::Buy, 12.0, OrderType::Fok));
+        let LegOrder::Kalshi(no) = no else { panic!("expected a Kalshi leg") };
+        // Kalshi takes the YES price of a NO order
+        assert_eq!((no.side.as_str(), no.count, no.yes_price), ("no", 12, Some(50)));
+
+        // A Polymarket NO leg needs the NO token
+        let opp = Opportunity::priced("BTC", ("kalshi", 0.45), ("polymarket", 0.50), &fees, Utc::now()).unwrap();
+        assert!(leg_orders(&opp, &matched, None, &paired).is_err());
+        let [_, no] = leg_orders(&opp, &matched, Some("222"), &paired).unwrap();
+        assert!(matches!(no, LegOrder::Polymarket(order) if order.token_id == "222"));
+    }
+}
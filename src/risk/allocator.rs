@@ -0,0 +1,397 @@
+//! Capital allocator.
+//!
+//! Splits the bankroll across strategies and venues, exposes the remaining
+//! buying power for each, and optionally shifts weight toward the strategy
+//! with the best recent Sharpe ratio. The bot rebalances on its schedule and
+//! keeps the weights in `data/state/allocation.json`, where the TUI and
+//! reports read them.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::paper_trading::{PaperTrade, TradeStatus};
+use crate::utils::state;
+
+/// Rebalanced weights and when they were last rebalanced.
+pub const ALLOCATION_FILE: &str = "data/state/allocation.json";
+
+/// Minimum closed trades in the lookback before a strategy's Sharpe counts.
+const MIN_TRADES_FOR_SHARPE: usize = 5;
+
+/// How allocations change over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebalanceRule {
+    /// Keep the configured weights.
+    Fixed,
+    /// Move `step` of weight from the worst to the best strategy by Sharpe
+    /// over `lookback_days`, never taking a strategy below `min_weight`.
+    SharpeTilt { lookback_days: i64, step: f64, min_weight: f64 },
+}
+
+impl RebalanceRule {
+    /// Parse "fixed" or "sharpe".
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "fixed" => Ok(RebalanceRule::Fixed),
+            "sharpe" => Ok(RebalanceRule::SharpeTilt { lookback_days: 7, step: 0.05, min_weight: 0.05 }),
+            other => anyhow::bail!("Unknown rebalance rule '{}': expected fixed or sharpe", other),
+        }
+    }
+}
+
+impl FromStr for RebalanceRule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Persisted strategy weights.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllocationState {
+    pub weights: HashMap<String, f64>,
+    pub rebalanced_at: Option<DateTime<Utc>>,
+}
+
+/// Capital currently deployed, per strategy and per venue.
+#[derive(Debug, Clone, Default)]
+pub struct Exposure {
+    pub by_strategy: HashMap<String, f64>,
+    pub by_venue: HashMap<String, f64>,
+}
+
+impl Exposure {
+    /// Sum the open trades' sizes, filled or still working.
+    pub fn from_trades<'a>(trades: impl IntoIterator<Item = &'a PaperTrade>) -> Self {
+        let mut exposure = Self::default();
+        for trade in trades.into_iter().filter(|t| t.status == TradeStatus::Open) {
+            let size = trade.filled_size() + trade.remaining_size();
+            *exposure.by_strategy.entry(trade.strategy.clone()).or_default() += size;
+            *exposure.by_venue.entry(trade.platform.clone()).or_default() += size;
+        }
+        exposure
+    }
+
+    /// Use the collateral locked per venue instead of entry sizes, so venue
+    /// limits track what positions and working orders actually tie up.
+    pub fn with_locked_collateral(mut self, by_venue: HashMap<String, f64>) -> Self {
+        self.by_venue = by_venue;
+        self
+    }
+
+    /// Include a pending trade.
+    pub fn add(&mut self, strategy: &str, venue: &str, size_usd: f64) {
+        *self.by_strategy.entry(strategy.to_string()).or_default() += size_usd;
+        *self.by_venue.entry(venue.to_string()).or_default() += size_usd;
+    }
+}
+
+/// Splits the bankroll across strategies and venues.
+#[derive(Debug, Clone)]
+pub struct CapitalAllocator {
+    bankroll: f64,
+    strategy_weights: HashMap<String, f64>,
+    /// Empty means venues are unconstrained
+    venue_weights: HashMap<String, f64>,
+    rule: RebalanceRule,
+    /// Least time between rebalances
+    interval: Duration,
+    rebalanced_at: Option<DateTime<Utc>>,
+    path: Option<String>,
+}
+
+impl CapitalAllocator {
+    /// Create an allocator; weights are normalized to sum to 1.
+    pub fn new(bankroll: f64, strategy_weights: Vec<(String, f64)>) -> Self {
+        Self {
+            bankroll,
+            strategy_weights: normalize(strategy_weights),
+            venue_weights: HashMap::new(),
+            rule: RebalanceRule::Fixed,
+            interval: Duration::zero(),
+            rebalanced_at: None,
+            path: None,
+        }
+    }
+
+    /// Build from CAPITAL_ALLOCATION / VENUE_ALLOCATION / REBALANCE_RULE /
+    /// REBALANCE_INTERVAL_HOURS.
+    /// Returns None when no strategy allocation is configured.
+    pub fn from_config(config: &Config, bankroll: f64) -> Option<Self> {
+        if config.capital_allocation.is_empty() {
+            return None;
+        }
+        Some(
+            Self::new(bankroll, config.capital_allocation.clone())
+                .with_venue_weights(config.venue_allocation.clone())
+                .with_rule(config.rebalance_rule)
+                .with_interval(Duration::hours(config.rebalance_interval_hours)),
+        )
+    }
+
+    /// Constrain capital per venue as well.
+    pub fn with_venue_weights(mut self, weights: Vec<(String, f64)>) -> Self {
+        self.venue_weights = normalize(weights);
+        self
+    }
+
+    /// Set the rebalancing rule.
+    pub fn with_rule(mut self, rule: RebalanceRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Rebalance at most once per `interval`.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Restore rebalanced weights from and persist them to `path`.
+    pub fn with_state_file(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self.reload();
+        self
+    }
+
+    pub fn rule(&self) -> RebalanceRule {
+        self.rule
+    }
+
+    /// Pick up weights rebalanced by another process. Saved weights for a
+    /// different set of strategies than configured are ignored.
+    pub fn reload(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let saved: AllocationState = state::load(path);
+        let mut saved_strategies: Vec<_> = saved.weights.keys().collect();
+        let mut strategies: Vec<_> = self.strategy_weights.keys().collect();
+        saved_strategies.sort();
+        strategies.sort();
+        if saved_strategies == strategies {
+            self.strategy_weights = saved.weights;
+            self.rebalanced_at = saved.rebalanced_at;
+        }
+    }
+
+    /// Update the bankroll (e.g. after P&L changes total equity).
+    pub fn set_bankroll(&mut self, bankroll: f64) {
+        self.bankroll = bankroll;
+    }
+
+    /// Current weight of a strategy (0 if unallocated).
+    pub fn weight(&self, strategy: &str) -> f64 {
+        self.strategy_weights.get(strategy).copied().unwrap_or(0.0)
+    }
+
+    /// Capital allocated to a strategy.
+    pub fn strategy_budget(&self, strategy: &str) -> f64 {
+        self.bankroll * self.weight(strategy)
+    }
+
+    /// Capital allocated to a venue (the whole bankroll if venues are unconstrained).
+    pub fn venue_budget(&self, venue: &str) -> f64 {
+        if self.venue_weights.is_empty() {
+            return self.bankroll;
+        }
+        self.bankroll * self.venue_weights.get(venue).copied().unwrap_or(0.0)
+    }
+
+    /// Remaining buying power for a strategy given current exposure.
+    pub fn buying_power(&self, strategy: &str, exposure: &Exposure) -> f64 {
+        let deployed = exposure.by_strategy.get(strategy).copied().unwrap_or(0.0);
+        (self.strategy_budget(strategy) - deployed).max(0.0)
+    }
+
+    /// Reject an order that would exceed the strategy's or venue's allocation.
+    pub fn check(&self, strategy: &str, venue: &str, size_usd: f64, exposure: &Exposure) -> Result<()> {
+        let available = self.buying_power(strategy, exposure);
+        if size_usd > available + 1e-9 {
+            anyhow::bail!(
+                "{} buying power exceeded: ${:.2} requested, ${:.2} available",
+                strategy, size_usd, available
+            );
+        }
+
+        let venue_deployed = exposure.by_venue.get(venue).copied().unwrap_or(0.0);
+        let venue_available = (self.venue_budget(venue) - venue_deployed).max(0.0);
+        if size_usd > venue_available + 1e-9 {
+            anyhow::bail!(
+                "{} allocation exceeded: ${:.2} requested, ${:.2} available",
+                venue, size_usd, venue_available
+            );
+        }
+        Ok(())
+    }
+
+    /// Apply the rebalancing rule using closed trades, if a rebalance is
+    /// due. Returns true when weights changed.
+    pub fn rebalance(&mut self, trades: &[PaperTrade], now: DateTime<Utc>) -> bool {
+        let RebalanceRule::SharpeTilt { lookback_days, step, min_weight } = self.rule else {
+            return false;
+        };
+        if self.rebalanced_at.is_some_and(|at| now - at < self.interval) {
+            return false;
+        }
+        let changed = self.tilt(trades, now, lookback_days, step, min_weight);
+        self.rebalanced_at = Some(now);
+        if let Some(path) = &self.path {
+            let saved = AllocationState { weights: self.strategy_weights.clone(), rebalanced_at: self.rebalanced_at };
+            if let Err(e) = state::save(path, &saved) {
+                warn!("Failed to save allocation {}: {}", path, e);
+            }
+        }
+        changed
+    }
+
+    /// Move `step` of weight from the worst to the best strategy by Sharpe.
+    fn tilt(&mut self, trades: &[PaperTrade], now: DateTime<Utc>, lookback_days: i64, step: f64, min_weight: f64) -> bool {
+
+        let since = now - Duration::days(lookback_days);
+        let mut returns: HashMap<&str, Vec<f64>> = HashMap::new();
+        for trade in trades {
+            if trade.status != TradeStatus::Closed || trade.timestamp < since || trade.filled_size() <= 0.0 {
+                continue;
+            }
+            if let Some(pnl) = trade.pnl {
+                returns.entry(trade.strategy.as_str()).or_default().push(pnl / trade.filled_size());
+            }
+        }
+
+        let mut ranked: Vec<(String, f64)> = self.strategy_weights.keys()
+            .filter_map(|s| {
+                let r = returns.get(s.as_str())?;
+                (r.len() >= MIN_TRADES_FOR_SHARPE).then(|| sharpe(r)).flatten().map(|v| (s.clone(), v))
+            })
+            .collect();
+        if ranked.len() < 2 {
+            return false;
+        }
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (worst, _) = &ranked[0];
+        let (best, _) = &ranked[ranked.len() - 1];
+
+        let shift = step.min(self.weight(worst) - min_weight);
+        if shift <= 0.0 {
+            return false;
+        }
+        *self.strategy_weights.get_mut(worst).expect("ranked from weights") -= shift;
+        *self.strategy_weights.get_mut(best).expect("ranked from weights") += shift;
+        info!("⚖️ Rebalanced {:.0}% of capital from {} to {}", shift * 100.0, worst, best);
+        true
+    }
+}
+
+/// Per-trade Sharpe ratio (mean / standard deviation of returns).
+pub fn sharpe(returns: &[f64]) -> Option<f64> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let var = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let sd = var.sqrt();
+    (sd > 0.0).then(|| mean / sd)
+}
+
+fn normalize(weights: Vec<(String, f64)>) -> HashMap<String, f64> {
+    let total: f64 = weights.iter().map(|(_, w)| w.max(0.0)).sum();
+    if total <= 0.0 {
+        return HashMap::new();
+    }
+    weights.into_iter().map(|(k, w)| (k, w.max(0.0) / total)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::Side;
+
+    fn closed(strategy: &str, size: f64, exit: f64) -> PaperTrade {
+        let mut trade = PaperTrade::new("m", "BTC", "Hourly", "polymarket", Side::Buy, size, 0.5, strategy, 0.5);
+        trade.close(exit);
+        trade
+    }
+
+    #[test]
+    fn test_check_enforces_strategy_and_venue_budgets() {
+        let allocator = CapitalAllocator::new(1000.0, vec![
+            ("arbitrage".to_string(), 3.0),
+            ("copy_trade".to_string(), 1.0),
+        ])
+        .with_venue_weights(vec![("polymarket".to_string(), 0.5), ("kalshi".to_string(), 0.5)]);
+
+        let open = PaperTrade::new("m", "BTC", "Hourly", "polymarket", Side::Buy, 200.0, 0.5, "copy_trade", 0.5);
+        let exposure = Exposure::from_trades([&open]);
+
+        assert_eq!(allocator.strategy_budget("arbitrage"), 750.0);
+        assert_eq!(allocator.buying_power("copy_trade", &exposure), 50.0);
+        assert!(allocator.check("copy_trade", "kalshi", 60.0, &exposure).is_err());
+        assert!(allocator.check("arbitrage", "polymarket", 350.0, &exposure).is_err());
+        assert!(allocator.check("arbitrage", "kalshi", 350.0, &exposure).is_ok());
+        assert!(allocator.check("directional", "kalshi", 1.0, &exposure).is_err());
+    }
+
+    #[test]
+    fn test_sharpe_tilt_moves_weight_to_best_strategy() {
+        let mut allocator = CapitalAllocator::new(1000.0, vec![
+            ("arbitrage".to_string(), 0.5),
+            ("copy_trade".to_string(), 0.5),
+        ])
+        .with_rule(RebalanceRule::parse("sharpe").unwrap())
+        .with_interval(Duration::days(1));
+
+        let mut trades = Vec::new();
+        for i in 0..6 {
+            trades.push(closed("arbitrage", 100.0, 0.52 + 0.01 * (i % 2) as f64));
+            trades.push(closed("copy_trade", 100.0, if i % 2 == 0 { 0.7 } else { 0.35 }));
+        }
+
+        assert!(allocator.rebalance(&trades, Utc::now()));
+        assert!((allocator.weight("arbitrage") - 0.55).abs() < 1e-9);
+        assert!((allocator.weight("copy_trade") - 0.45).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalances_on_schedule_and_persists_weights() {
+        let path = std::env::temp_dir().join(format!("allocation-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let weights = || vec![("arbitrage".to_string(), 0.5), ("copy_trade".to_string(), 0.5)];
+        let allocator = || {
+            CapitalAllocator::new(1000.0, weights())
+                .with_rule("sharpe".parse().unwrap())
+                .with_interval(Duration::days(1))
+                .with_state_file(path)
+        };
+        let mut trades = Vec::new();
+        for i in 0..6 {
+            trades.push(closed("arbitrage", 100.0, 0.52 + 0.01 * (i % 2) as f64));
+            trades.push(closed("copy_trade", 100.0, if i % 2 == 0 { 0.7 } else { 0.35 }));
+        }
+        let now = Utc::now();
+        let mut bot = allocator();
+        assert!(bot.rebalance(&trades, now));
+        // Not due again until a day has passed, restarts included
+        assert!(!bot.rebalance(&trades, now + Duration::hours(1)));
+        let mut restarted = allocator();
+        assert!((restarted.weight("arbitrage") - 0.55).abs() < 1e-9);
+        assert!(!restarted.rebalance(&trades, now + Duration::hours(2)));
+        assert!(restarted.rebalance(&trades, now + Duration::days(1)));
+
+        // Another process picks the weights up; a changed set of strategies
+        // starts from the configured weights
+        assert!((CapitalAllocator::new(1000.0, weights()).with_state_file(path).weight("arbitrage") - 0.60).abs() < 1e-9);
+        let other = CapitalAllocator::new(1000.0, vec![("arbitrage".to_string(), 1.0)]).with_state_file(path);
+        assert_eq!(other.weight("arbitrage"), 1.0);
+        let _ = std::fs::remove_file(path);
+
+        assert!(RebalanceRule::parse("sharp").is_err());
+    }
+}
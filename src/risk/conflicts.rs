@@ -0,0 +1,285 @@
+//! Cross-strategy conflicts.
+//!
+//! A copy trade can take the other side of a market an arbitrage leg (or
+//! any other strategy) already holds. Each strategy has a policy for that:
+//! skip the order, net it against the opposing exposure, or allow it.
+//! Every conflict is appended to a history file the TUI shows.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+use super::netting::{split_outcome, Netting};
+use crate::config::Config;
+use crate::paper_trading::{PaperTrade, Side, TradeStatus};
+use crate::utils::state;
+
+/// Conflict history shared with the TUI.
+pub const CONFLICTS_FILE: &str = "data/conflicts.jsonl";
+
+/// What to do with an order that opposes another strategy's position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Reject the order.
+    Skip,
+    /// Only trade what exceeds the opposing exposure.
+    Net,
+    /// Trade it anyway.
+    Allow,
+}
+
+impl ConflictPolicy {
+    /// Parse "skip", "net" or "allow".
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "skip" => Ok(ConflictPolicy::Skip),
+            "net" => Ok(ConflictPolicy::Net),
+            "allow" => Ok(ConflictPolicy::Allow),
+            other => anyhow::bail!("Unknown conflict policy '{}' (expected skip, net or allow)", other),
+        }
+    }
+}
+
+/// An order that opposed another strategy's open position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conflict {
+    pub timestamp: DateTime<Utc>,
+    pub strategy: String,
+    pub market: String,
+    pub side: Side,
+    /// Strategies holding the other side
+    pub opposing: Vec<String>,
+    /// USD held on the other side
+    pub opposing_usd: f64,
+    pub requested_usd: f64,
+    /// USD actually allowed through (0 when skipped)
+    pub allowed_usd: f64,
+    pub policy: ConflictPolicy,
+}
+
+/// Applies per-strategy conflict policies to new orders.
+#[derive(Debug, Clone)]
+pub struct ConflictGuard {
+    policies: HashMap<String, ConflictPolicy>,
+    /// Policy for strategies not listed
+    default_policy: ConflictPolicy,
+    history_path: Option<String>,
+}
+
+impl Default for ConflictGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConflictGuard {
+    /// Allow everything until policies are set.
+    pub fn new() -> Self {
+        Self {
+            policies: HashMap::new(),
+            default_policy: ConflictPolicy::Allow,
+            history_path: None,
+        }
+    }
+
+    /// Build from CONFLICT_POLICY, logging to [`CONFLICTS_FILE`]. A "*"
+    /// entry sets the policy for unlisted strategies.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut guard = Self::new().with_history(CONFLICTS_FILE);
+        for (strategy, policy) in &config.conflict_policies {
+            let policy = ConflictPolicy::parse(policy).context("Invalid CONFLICT_POLICY")?;
+            guard = guard.with_policy(strategy, policy);
+        }
+        Ok(guard)
+    }
+
+    /// Set a strategy's policy ("*" for the default).
+    pub fn with_policy(mut self, strategy: &str, policy: ConflictPolicy) -> Self {
+        if strategy == "*" {
+            self.default_policy = policy;
+        } else {
+            self.policies.insert(strategy.to_string(), policy);
+        }
+        self
+    }
+
+    /// Append conflicts to `path`.
+    pub fn with_history(mut self, path: &str) -> Self {
+        self.history_path = Some(path.to_string());
+        self
+    }
+
+    pub fn policy(&self, strategy: &str) -> ConflictPolicy {
+        self.policies.get(strategy).copied().unwrap_or(self.default_policy)
+    }
+
+    /// USD `strategy` may trade of a `size_usd` order on `market`, given
+    /// the open trades. Errors when the policy rejects the order outright.
+    pub fn resolve(
+        &self,
+        strategy: &str,
+        market: &str,
+        side: Side,
+        size_usd: f64,
+        trades: &[PaperTrade],
+    ) -> Result<f64> {
+        let (base, long) = direction(market, side);
+        let mut opposing: Vec<String> = Vec::new();
+        let mut opposing_usd = 0.0;
+        for trade in trades {
+            if trade.status != TradeStatus::Open || trade.strategy == strategy {
+                continue;
+            }
+            let (other_base, other_long) = direction(&trade.market, trade.side);
+            if other_base == base && other_long != long {
+                opposing_usd += trade.filled_size() + trade.remaining_size();
+                if !opposing.contains(&trade.strategy) {
+                    opposing.push(trade.strategy.clone());
+                }
+            }
+        }
+        self.apply(strategy, market, side, size_usd, opposing, opposing_usd)
+    }
+
+    /// As [`ConflictGuard::resolve`] for a live order at `price` a share,
+    /// given the live book netted per question: strategies whose net delta
+    /// on the order's question runs the other way oppose it, valued at
+    /// `price` a share.
+    pub fn resolve_netted(
+        &self,
+        strategy: &str,
+        market: &str,
+        side: Side,
+        size_usd: f64,
+        price: f64,
+        netting: &Netting,
+    ) -> Result<f64> {
+        let (question, yes) = netting.question_of(market);
+        let long = yes == (side == Side::Buy);
+        let mut opposing: Vec<String> = Vec::new();
+        let mut opposing_usd = 0.0;
+        for (other, delta) in netting.question(&question).map(|q| &q.strategies).into_iter().flatten() {
+            if other != strategy && delta.abs() > 1e-9 && (*delta > 0.0) != long {
+                opposing.push(other.clone());
+                opposing_usd += delta.abs() * price;
+            }
+        }
+        self.apply(strategy, market, side, size_usd, opposing, opposing_usd)
+    }
+
+    /// Apply `strategy`'s policy to an order opposed by `opposing`.
+    fn apply(
+        &self,
+        strategy: &str,
+        market: &str,
+        side: Side,
+        size_usd: f64,
+        opposing: Vec<String>,
+        opposing_usd: f64,
+    ) -> Result<f64> {
+        if opposing.is_empty() {
+            return Ok(size_usd);
+        }
+
+        let policy = self.policy(strategy);
+        let allowed_usd = match policy {
+            ConflictPolicy::Skip => 0.0,
+            ConflictPolicy::Net => (size_usd - opposing_usd).max(0.0),
+            ConflictPolicy::Allow => size_usd,
+        };
+        let conflict = Conflict {
+            timestamp: Utc::now(),
+            strategy: strategy.to_string(),
+            market: market.to_string(),
+            side,
+            opposing: opposing.clone(),
+            opposing_usd,
+            requested_usd: size_usd,
+            allowed_usd,
+            policy,
+        };
+        info!(
+            "⚔️ {} {} {} (${:.2}) opposes {} (${:.2}): {:?} -> ${:.2}",
+            strategy, side, market, size_usd, opposing.join(", "), opposing_usd, policy, allowed_usd
+        );
+        self.record(&conflict);
+
+        if allowed_usd <= 0.0 {
+            anyhow::bail!(
+                "{} order on {} conflicts with {} (policy: {:?})",
+                strategy, market, opposing.join(", "), policy
+            );
+        }
+        Ok(allowed_usd)
+    }
+
+    fn record(&self, conflict: &Conflict) {
+        let Some(path) = &self.history_path else {
+            return;
+        };
+        let result = serde_json::to_string(conflict)
+            .map_err(anyhow::Error::from)
+            .and_then(|line| state::append_line(path, &line));
+        if let Err(e) = result {
+            warn!("Failed to record strategy conflict: {}", e);
+        }
+    }
+}
+
+/// Base market and whether the order is long its YES outcome.
+fn direction(market: &str, side: Side) -> (&str, bool) {
+    let (base, yes) = split_outcome(market);
+    (base, yes == (side == Side::Buy))
+}
+
+/// The last `n` conflicts, newest first.
+pub fn load_recent_conflicts(path: &str, n: usize) -> Vec<Conflict> {
+    state::read_lines(path)
+        .iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(market: &str, strategy: &str, size: f64) -> PaperTrade {
+        PaperTrade::new(market, "BTC", "Hourly", "polymarket", Side::Buy, size, 0.5, strategy, 1.0)
+    }
+
+    #[test]
+    fn test_policies_against_arb_legs() {
+        let trades = vec![open("BTC 100k YES", "arbitrage", 40.0), open("BTC 100k NO", "arbitrage", 30.0)];
+        let path = std::env::temp_dir()
+            .join(format!("polybot-conflicts-{}.jsonl", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        let guard = ConflictGuard::new()
+            .with_policy("copy_trade", ConflictPolicy::Net)
+            .with_policy("manual", ConflictPolicy::Skip)
+            .with_history(&path);
+
+        // Buying YES opposes the NO leg; selling YES opposes the YES leg
+        assert_eq!(guard.resolve("copy_trade", "BTC 100k", Side::Buy, 50.0, &trades).unwrap(), 20.0);
+        assert!(guard.resolve("copy_trade", "BTC 100k YES", Side::Sell, 40.0, &trades).is_err());
+        assert!(guard.resolve("manual", "BTC 100k NO", Side::Buy, 10.0, &trades).is_err());
+        // Unlisted strategies, same-strategy positions and other markets pass
+        assert_eq!(guard.resolve("directional", "BTC 100k", Side::Buy, 10.0, &trades).unwrap(), 10.0);
+        assert_eq!(guard.resolve("arbitrage", "BTC 100k", Side::Buy, 10.0, &trades).unwrap(), 10.0);
+        assert_eq!(guard.resolve("manual", "ETH 5k", Side::Buy, 10.0, &trades).unwrap(), 10.0);
+
+        let recent = load_recent_conflicts(&path, 10);
+        assert_eq!(recent.len(), 4);
+        assert_eq!(recent[0].strategy, "directional");
+        assert_eq!((recent[3].allowed_usd, recent[3].opposing_usd), (20.0, 30.0));
+        assert_eq!(ConflictPolicy::parse("NET").unwrap(), ConflictPolicy::Net);
+        assert!(ConflictPolicy::parse("hedge").is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
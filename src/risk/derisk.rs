@@ -0,0 +1,255 @@
+//! Drawdown-based de-risking.
+//!
+//! Tracks equity against its high-water mark. Once equity falls a set
+//! fraction below the mark, max position sizes are scaled down (halved by
+//! default) until the drawdown recovers to a smaller threshold. The mark
+//! and the current mode survive restarts in `data/state/derisk.json`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::Config;
+use crate::display;
+use crate::notify::notifier::{Alert, Severity};
+use crate::utils::clock::{system_clock, SharedClock};
+use crate::utils::state;
+
+/// Persisted de-risking state.
+pub const DERISK_FILE: &str = "data/state/derisk.json";
+
+/// When to cut sizes and when to restore them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeriskRule {
+    /// Drawdown from the high-water mark that cuts sizes, e.g. 0.10
+    pub trigger: f64,
+    /// Drawdown at or below which full sizes return
+    pub recovery: f64,
+    /// Multiplier on max position sizes while de-risked
+    pub factor: f64,
+}
+
+impl DeriskRule {
+    /// Cut to `factor` at a `trigger` drawdown, restoring at half of it.
+    pub fn new(trigger: f64, factor: f64) -> Self {
+        Self { trigger, recovery: trigger / 2.0, factor }
+    }
+
+    pub fn with_recovery(mut self, recovery: f64) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        anyhow::ensure!(self.trigger > 0.0 && self.trigger < 1.0, "DERISK_DRAWDOWN must be between 0 and 1");
+        anyhow::ensure!(
+            self.recovery >= 0.0 && self.recovery < self.trigger,
+            "DERISK_RECOVERY must be below DERISK_DRAWDOWN"
+        );
+        anyhow::ensure!(self.factor > 0.0 && self.factor <= 1.0, "DERISK_FACTOR must be above 0 and at most 1");
+        Ok(())
+    }
+}
+
+/// High-water mark and current mode.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DeriskState {
+    pub high_water_mark: f64,
+    pub high_water_at: Option<DateTime<Utc>>,
+    /// Latest equity observed
+    pub equity: f64,
+    /// When sizes were cut; None at full size
+    pub derisked_at: Option<DateTime<Utc>>,
+}
+
+impl DeriskState {
+    /// Fraction below the high-water mark.
+    pub fn drawdown(&self) -> f64 {
+        if self.high_water_mark <= 0.0 {
+            return 0.0;
+        }
+        ((self.high_water_mark - self.equity) / self.high_water_mark).max(0.0)
+    }
+}
+
+/// A change of mode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeriskEvent {
+    Derisked { equity: f64, high_water_mark: f64, drawdown: f64, factor: f64 },
+    Restored { equity: f64, high_water_mark: f64, drawdown: f64 },
+}
+
+impl DeriskEvent {
+    pub fn alert(&self) -> Alert {
+        match self {
+            DeriskEvent::Derisked { equity, high_water_mark, drawdown, factor } => Alert::new(
+                Severity::Warning,
+                "derisk",
+                "📉 Position sizes cut",
+                &format!(
+                    "Equity ${:.2} is {} below its high of ${:.2}; max position sizes scaled to {} until it recovers",
+                    equity,
+                    display::percent(*drawdown),
+                    high_water_mark,
+                    display::percent(*factor)
+                ),
+            ),
+            DeriskEvent::Restored { equity, high_water_mark, drawdown } => Alert::new(
+                Severity::Info,
+                "derisk",
+                "📈 Position sizes restored",
+                &format!(
+                    "Equity ${:.2} is back within {} of its high of ${:.2}; full position sizes restored",
+                    equity,
+                    display::percent(*drawdown),
+                    high_water_mark
+                ),
+            ),
+        }
+    }
+}
+
+/// Scales max position sizes by drawdown.
+#[derive(Debug, Clone)]
+pub struct Derisker {
+    rule: DeriskRule,
+    state: DeriskState,
+    path: Option<String>,
+    clock: SharedClock,
+}
+
+impl Derisker {
+    pub fn new(rule: DeriskRule) -> Self {
+        Self { rule, state: DeriskState::default(), path: None, clock: system_clock() }
+    }
+
+    /// DERISK_DRAWDOWN, DERISK_RECOVERY and DERISK_FACTOR; None when
+    /// DERISK_DRAWDOWN is unset.
+    pub fn from_config(config: &Config) -> Result<Option<Self>> {
+        let Some(trigger) = config.derisk_drawdown else {
+            return Ok(None);
+        };
+        let mut rule = DeriskRule::new(trigger, config.derisk_factor);
+        if let Some(recovery) = config.derisk_recovery {
+            rule = rule.with_recovery(recovery);
+        }
+        rule.validate()?;
+        Ok(Some(Self::new(rule)))
+    }
+
+    /// Restore from and persist to `path`.
+    pub fn with_state_file(mut self, path: &str) -> Self {
+        self.state = state::load(path);
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn rule(&self) -> &DeriskRule {
+        &self.rule
+    }
+
+    pub fn state(&self) -> &DeriskState {
+        &self.state
+    }
+
+    pub fn is_derisked(&self) -> bool {
+        self.state.derisked_at.is_some()
+    }
+
+    /// Multiplier on max position sizes right now.
+    pub fn scale(&self) -> f64 {
+        if self.is_derisked() {
+            self.rule.factor
+        } else {
+            1.0
+        }
+    }
+
+    /// `max` scaled for the current mode.
+    pub fn max_size(&self, max: f64) -> f64 {
+        max * self.scale()
+    }
+
+    /// Record the latest equity, returning a change of mode if any.
+    pub fn observe(&mut self, equity: f64) -> Option<DeriskEvent> {
+        let now = self.clock.now();
+        self.state.equity = equity;
+        if equity > self.state.high_water_mark {
+            self.state.high_water_mark = equity;
+            self.state.high_water_at = Some(now);
+        }
+        let drawdown = self.state.drawdown();
+        let event = match self.state.derisked_at {
+            None if drawdown >= self.rule.trigger => {
+                self.state.derisked_at = Some(now);
+                Some(DeriskEvent::Derisked {
+                    equity,
+                    high_water_mark: self.state.high_water_mark,
+                    drawdown,
+                    factor: self.rule.factor,
+                })
+            }
+            Some(_) if drawdown <= self.rule.recovery => {
+                self.state.derisked_at = None;
+                Some(DeriskEvent::Restored { equity, high_water_mark: self.state.high_water_mark, drawdown })
+            }
+            _ => None,
+        };
+        if let Some(path) = &self.path {
+            if let Err(e) = state::save(path, &self.state) {
+                warn!("Failed to save de-risking state {}: {}", path, e);
+            }
+        }
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_cuts_at_drawdown_and_restores_on_recovery() {
+        let path = std::env::temp_dir().join(format!("derisk-{}.json", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let now = Utc::now();
+        let clock = Arc::new(MockClock::new(now));
+        let mut derisker = Derisker::new(DeriskRule::new(0.10, 0.5)).with_state_file(path).with_clock(clock.clone());
+
+        assert_eq!(derisker.observe(1000.0), None);
+        assert_eq!(derisker.observe(1100.0), None);
+        assert_eq!(derisker.max_size(100.0), 100.0);
+
+        // 980 is 10.9% below the 1100 high
+        clock.advance(std::time::Duration::from_secs(60));
+        assert!(matches!(derisker.observe(980.0), Some(DeriskEvent::Derisked { .. })));
+        assert_eq!(derisker.state().high_water_at, Some(now));
+        assert_eq!(derisker.state().derisked_at, Some(now + chrono::Duration::seconds(60)));
+        assert_eq!(derisker.max_size(100.0), 50.0);
+        // Still 9% down: stays cut, without another event
+        assert_eq!(derisker.observe(1000.0), None);
+        assert!(derisker.is_derisked());
+
+        // A restart picks up the high and the mode
+        let mut restarted = Derisker::new(DeriskRule::new(0.10, 0.5)).with_state_file(path);
+        assert_eq!(restarted.state().high_water_mark, 1100.0);
+        assert_eq!(restarted.scale(), 0.5);
+        let restored = restarted.observe(1050.0);
+        assert!(matches!(restored, Some(DeriskEvent::Restored { .. })));
+        assert_eq!(restored.unwrap().alert().kind, "derisk");
+        assert_eq!(restarted.scale(), 1.0);
+
+        assert!(DeriskRule::new(0.10, 0.5).with_recovery(0.2).validate().is_err());
+        assert!(DeriskRule::new(0.10, 0.0).validate().is_err());
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,342 @@
+//! Discrepancy ledger.
+//!
+//! When the venues settle a matched pair differently, or a fill doesn't
+//! match the order we have on record, an entry is opened here with the
+//! evidence at hand: recent quotes, our orders, the audited fills and the
+//! resolution. Trading on the affected market family (the Kalshi series,
+//! see `market_matcher::family`) stays off until someone acknowledges the
+//! entry (see `polybot discrepancies`).
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::arbitrage::market_matcher::{family, MatchedMarket};
+use crate::backtest::tape::{QuoteTape, QuoteTick, QUOTE_TAPE_FILE};
+use crate::monitor::resolution::Resolution;
+use crate::utils::audit_log::{AuditEvent, AuditLog, AUDIT_FILE};
+use crate::utils::fills::Fill;
+use crate::utils::open_orders::OpenOrder;
+use crate::utils::state;
+
+/// Discrepancy ledger shared with the bot, TUI and CLI.
+pub const DISCREPANCIES_FILE: &str = "data/state/discrepancies.json";
+/// Quotes kept as evidence, most recent first.
+const EVIDENCE_QUOTES: usize = 20;
+/// Audit events kept as evidence, most recent first.
+const EVIDENCE_EVENTS: usize = 50;
+
+/// What disagreed with our records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscrepancyKind {
+    /// The venues resolved a matched pair differently
+    Settlement,
+    /// A fill didn't match the order we placed
+    Fill,
+}
+
+/// Everything on record about the affected markets when the entry opened.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Evidence {
+    pub quotes: Vec<QuoteTick>,
+    pub orders: Vec<OpenOrder>,
+    /// Audited order and fill events
+    pub events: Vec<AuditEvent>,
+    pub resolution: Option<Resolution>,
+}
+
+/// Who signed off on a discrepancy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Acknowledgement {
+    pub by: String,
+    pub at: DateTime<Utc>,
+    pub note: Option<String>,
+}
+
+/// One ledger entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Discrepancy {
+    pub id: String,
+    pub opened_at: DateTime<Utc>,
+    pub kind: DiscrepancyKind,
+    /// Matched pair name, or the instrument for fills
+    pub market: String,
+    /// Family blocked until acknowledged; fills block the family of any
+    /// pair trading one of the instruments
+    pub family: Option<String>,
+    /// Token IDs and tickers involved
+    pub instruments: Vec<String>,
+    pub summary: String,
+    pub evidence: Evidence,
+    pub acknowledged: Option<Acknowledgement>,
+}
+
+impl Discrepancy {
+    pub fn is_open(&self) -> bool {
+        self.acknowledged.is_none()
+    }
+
+    /// Whether this entry concerns `matched`.
+    pub fn covers(&self, matched: &MatchedMarket) -> bool {
+        self.family.as_deref() == Some(matched.family().as_str())
+            || self.instruments.iter().any(|i| {
+                i == &matched.polymarket.native
+                    || i == &matched.kalshi.native
+                    // Kalshi fills name the side, e.g. "KXBTCD-... NO"
+                    || i.strip_prefix(matched.kalshi.native.as_str()).is_some_and(|rest| rest.starts_with(' '))
+            })
+    }
+}
+
+/// Persistent list of discrepancies.
+#[derive(Debug)]
+pub struct DiscrepancyLedger {
+    path: String,
+    quotes_path: String,
+    audit_path: String,
+}
+
+impl DiscrepancyLedger {
+    /// Ledger at `path`, gathering evidence from the default quote tape and
+    /// audit log.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            quotes_path: QUOTE_TAPE_FILE.to_string(),
+            audit_path: AUDIT_FILE.to_string(),
+        }
+    }
+
+    /// Gather evidence from another quote tape and audit log.
+    pub fn with_evidence_from(mut self, quotes_path: &str, audit_path: &str) -> Self {
+        self.quotes_path = quotes_path.to_string();
+        self.audit_path = audit_path.to_string();
+        self
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> Vec<Discrepancy> {
+        state::load(&self.path)
+    }
+
+    /// Entries still awaiting acknowledgement.
+    pub fn open_entries(&self) -> Vec<Discrepancy> {
+        self.entries().into_iter().filter(Discrepancy::is_open).collect()
+    }
+
+    /// Open an entry for pairs the venues resolved differently.
+    pub fn open_settlement(&self, resolution: &Resolution) -> Option<Discrepancy> {
+        let instruments = vec![resolution.polymarket_id.clone(), resolution.kalshi_ticker.clone()];
+        let mut evidence = self.gather(Some(&resolution.name), &instruments);
+        evidence.resolution = Some(resolution.clone());
+        self.open(Discrepancy {
+            id: Uuid::new_v4().to_string()[..8].to_string(),
+            opened_at: Utc::now(),
+            kind: DiscrepancyKind::Settlement,
+            market: resolution.name.clone(),
+            family: Some(family(&resolution.kalshi_ticker)),
+            instruments,
+            summary: format!(
+                "Polymarket resolved {} but Kalshi resolved {}",
+                resolution.polymarket, resolution.kalshi
+            ),
+            evidence,
+            acknowledged: None,
+        })
+    }
+
+    /// Open an entry for a fill that doesn't match `order`.
+    pub fn open_fill(&self, fill: &Fill, order: &OpenOrder, reason: &str) -> Option<Discrepancy> {
+        let mut instruments = vec![order.market.clone()];
+        if fill.market != order.market {
+            instruments.push(fill.market.clone());
+        }
+        let mut evidence = self.gather(None, &instruments);
+        evidence.orders.push(order.clone());
+        self.open(Discrepancy {
+            id: Uuid::new_v4().to_string()[..8].to_string(),
+            opened_at: Utc::now(),
+            kind: DiscrepancyKind::Fill,
+            market: fill.market.clone(),
+            family: None,
+            instruments,
+            summary: format!("{} fill {} on order {}: {}", fill.venue, fill.trade_id, fill.order_id, reason),
+            evidence,
+            acknowledged: None,
+        })
+    }
+
+    /// Sign off on entry `id` (or a unique prefix of it).
+    pub fn acknowledge(&self, id: &str, by: &str, note: Option<&str>) -> Result<Discrepancy> {
+        // Held across the read and the save so the bot opening an entry
+        // meanwhile isn't lost
+        let _lock = state::lock(&self.path)?;
+        let mut entries = self.entries();
+        let mut matching = entries.iter_mut().filter(|d| d.id.starts_with(id));
+        let entry = match (matching.next(), matching.next()) {
+            (Some(entry), None) => entry,
+            (None, _) => anyhow::bail!("No discrepancy {}", id),
+            (Some(_), Some(_)) => anyhow::bail!("Discrepancy ID '{}' is ambiguous", id),
+        };
+        if let Some(ack) = &entry.acknowledged {
+            anyhow::bail!("Discrepancy {} was already acknowledged by {}", entry.id, ack.by);
+        }
+        entry.acknowledged = Some(Acknowledgement { by: by.to_string(), at: Utc::now(), note: note.map(str::to_string) });
+        let entry = entry.clone();
+        state::save(&self.path, &entries)?;
+        info!("✅ Discrepancy {} on {} acknowledged by {}", entry.id, entry.market, by);
+        Ok(entry)
+    }
+
+    /// Families with an open entry, given the pairs currently matched.
+    pub fn blocked_families(&self, matches: &[MatchedMarket]) -> BTreeSet<String> {
+        let open = self.open_entries();
+        let mut families: BTreeSet<String> = open.iter().filter_map(|d| d.family.clone()).collect();
+        for matched in matches {
+            if open.iter().any(|d| d.covers(matched)) {
+                families.insert(matched.family());
+            }
+        }
+        families
+    }
+
+    /// Append `entry` unless an open one of the same kind already covers
+    /// its instruments.
+    fn open(&self, entry: Discrepancy) -> Option<Discrepancy> {
+        let _lock = state::lock(&self.path).map_err(|e| warn!("Failed to lock discrepancy ledger: {}", e)).ok();
+        let mut entries = self.entries();
+        if entries.iter().any(|d| d.is_open() && d.kind == entry.kind && d.instruments == entry.instruments) {
+            return None;
+        }
+        error!(
+            "🚨 Discrepancy {} on {}: {} - trading on {} stays off until acknowledged",
+            entry.id,
+            entry.market,
+            entry.summary,
+            entry.family.as_deref().unwrap_or("pairs trading it")
+        );
+        entries.push(entry.clone());
+        if let Err(e) = state::save(&self.path, &entries) {
+            warn!("Failed to save discrepancy ledger: {}", e);
+        }
+        Some(entry)
+    }
+
+    fn gather(&self, name: Option<&str>, instruments: &[String]) -> Evidence {
+        let quotes = match name {
+            Some(name) => {
+                let mut ticks: Vec<QuoteTick> =
+                    QuoteTape::new(&self.quotes_path).load().into_iter().filter(|t| t.market == name).collect();
+                ticks.reverse();
+                ticks.truncate(EVIDENCE_QUOTES);
+                ticks
+            }
+            None => Vec::new(),
+        };
+        let events = AuditLog::new(&self.audit_path)
+            .recent(usize::MAX)
+            .into_iter()
+            .filter(|e| instruments.iter().any(|i| e.market.starts_with(i.as_str())))
+            .take(EVIDENCE_EVENTS)
+            .collect();
+        Evidence { quotes, orders: Vec::new(), events, resolution: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::resolution::Outcome;
+
+    fn temp(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("polybot-{}-{}", name, Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    fn pair(name: &str, token: &str, ticker: &str) -> MatchedMarket {
+        MatchedMarket::new(name, token, ticker)
+    }
+
+    #[test]
+    fn test_blocks_family_until_acknowledged() {
+        let (path, audit_path) = (temp("discrepancies.json"), temp("audit.jsonl"));
+        let audit = AuditLog::new(&audit_path);
+        let event = |market: &str| AuditEvent {
+            at: Utc::now(),
+            venue: "kalshi".to_string(),
+            kind: "fill".to_string(),
+            order_id: "ord-1".to_string(),
+            market: market.to_string(),
+            side: "BUY".to_string(),
+            price: 0.42,
+            size: 10.0,
+            detail: None,
+            run: None,
+        };
+        audit.record(&event("KXBTC-1 NO"));
+        audit.record(&event("KXETH-2 NO"));
+        let ledger = DiscrepancyLedger::new(&path).with_evidence_from(&temp("quotes.jsonl"), &audit_path);
+
+        let now = Utc::now();
+        let resolution = Resolution {
+            name: "BTC 100k".to_string(),
+            polymarket_id: "111".to_string(),
+            kalshi_ticker: "KXBTC-1".to_string(),
+            polymarket: Outcome::No,
+            kalshi: Outcome::Yes,
+            polymarket_seen_at: now,
+            kalshi_seen_at: now,
+        };
+        let entry = ledger.open_settlement(&resolution).unwrap();
+        assert_eq!(entry.evidence.events.len(), 1);
+        assert_eq!(entry.evidence.resolution.as_ref(), Some(&resolution));
+        // The same mismatch isn't opened twice
+        assert!(ledger.open_settlement(&resolution).is_none());
+
+        // Families are Kalshi series: a pair worded alike on another series
+        // keeps trading
+        let matches =
+            vec![pair("BTC 100k", "111", "KXBTC-1"), pair("BTC Up/Down 5PM ET", "222", "KXBTCD-3"), pair("ETH 5k", "333", "KXETH-2")];
+        assert_eq!(entry.family.as_deref(), Some("KXBTC"));
+        assert_eq!(ledger.blocked_families(&matches), BTreeSet::from(["KXBTC".to_string()]));
+
+        // A fill entry blocks the family of the pair trading the instrument
+        let order = OpenOrder {
+            order_id: "ord-1".to_string(),
+            venue: "kalshi".to_string(),
+            market: "KXETH-2".to_string(),
+            side: "buy no".to_string(),
+            price: 0.42,
+            size: 5.0,
+            placed_at: now,
+        };
+        let fill = Fill {
+            venue: "kalshi".to_string(),
+            order_id: "ord-1".to_string(),
+            trade_id: "t1".to_string(),
+            market: "KXETH-2 NO".to_string(),
+            side: "BUY".to_string(),
+            price: 0.42,
+            size: 10.0,
+            filled_at: now,
+        };
+        let fill_entry = ledger.open_fill(&fill, &order, "filled 10 of 5").unwrap();
+        assert_eq!(fill_entry.instruments, vec!["KXETH-2".to_string(), "KXETH-2 NO".to_string()]);
+        assert_eq!(ledger.blocked_families(&matches).len(), 2);
+
+        assert!(ledger.acknowledge("zzz", "ops", None).is_err());
+        ledger.acknowledge(&entry.id[..4], "ops", Some("Kalshi used a different price source")).unwrap();
+        assert!(ledger.acknowledge(&entry.id, "ops", None).is_err());
+        assert_eq!(ledger.blocked_families(&matches), BTreeSet::from(["KXETH".to_string()]));
+        assert_eq!(ledger.open_entries().len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&audit_path);
+    }
+}
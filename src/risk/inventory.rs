@@ -0,0 +1,166 @@
+//! Inventory skew for two-sided quoting.
+//!
+//! A maker that keeps getting filled on one side builds up inventory. The
+//! skew shifts both quotes against it: long inventory moves the bid and ask
+//! down so the ask fills more often and the bid less, short inventory moves
+//! them up. The shift grows with inventory as a fraction of the cap, shaped
+//! by a skew function, and quotes that would add to inventory past the cap
+//! are pulled.
+//!
+//! Inventory is our net delta on a question across live fills (YES shares
+//! less NO shares, both venues). INVENTORY_CAP is enforced on every live
+//! order by the pre-trade check, and the TUI Strategies tab shows each
+//! question's inventory and the shift quotes on it would get. The bot has
+//! no quoting loop yet to apply the shift itself.
+
+use anyhow::Result;
+use std::str::FromStr;
+
+use crate::config::Config;
+
+/// Shape of the shift as inventory grows towards the cap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkewFunction {
+    /// Proportional to inventory
+    Linear,
+    /// Gentle near flat, steep near the cap
+    Quadratic,
+    /// Steep near flat, flattening towards the cap
+    Tanh,
+}
+
+impl SkewFunction {
+    /// Parse a SKEW_FUNCTION value.
+    pub fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "quadratic" => Ok(Self::Quadratic),
+            "tanh" => Ok(Self::Tanh),
+            other => anyhow::bail!("Unknown skew function '{}': expected linear, quadratic or tanh", other),
+        }
+    }
+
+    /// Shift for inventory at `ratio` of the cap, both in -1..=1.
+    fn apply(self, ratio: f64) -> f64 {
+        let ratio = ratio.clamp(-1.0, 1.0);
+        match self {
+            Self::Linear => ratio,
+            Self::Quadratic => ratio * ratio.abs(),
+            Self::Tanh => (2.0 * ratio).tanh() / 2.0_f64.tanh(),
+        }
+    }
+}
+
+impl FromStr for SkewFunction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Quotes after skewing; a side is None when it would breach the cap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkewedQuote {
+    pub bid: Option<f64>,
+    pub ask: Option<f64>,
+    /// Price shift applied to both sides, negative when long
+    pub shift: f64,
+}
+
+/// Inventory-aware quote adjustment with a hard inventory cap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InventorySkew {
+    /// Largest absolute inventory, in shares/contracts
+    pub cap: f64,
+    /// Shift at the cap, in price
+    pub max_skew: f64,
+    pub function: SkewFunction,
+}
+
+impl InventorySkew {
+    pub fn new(cap: f64, max_skew: f64, function: SkewFunction) -> Self {
+        Self { cap, max_skew, function }
+    }
+
+    /// INVENTORY_CAP, MAX_INVENTORY_SKEW and SKEW_FUNCTION; None when no
+    /// cap is set.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        (config.inventory_cap > 0.0)
+            .then(|| Self::new(config.inventory_cap, config.max_inventory_skew, config.skew_function))
+    }
+
+    /// Inventory as a fraction of the cap.
+    pub fn ratio(&self, inventory: f64) -> f64 {
+        if self.cap > 0.0 { inventory / self.cap } else { 0.0 }
+    }
+
+    /// Price shift for `inventory`, negative when long.
+    pub fn shift(&self, inventory: f64) -> f64 {
+        -self.max_skew * self.function.apply(self.ratio(inventory))
+    }
+
+    /// Bail if trading `delta` (positive buys) would take inventory past
+    /// the cap. Trades that reduce inventory always pass.
+    pub fn check(&self, inventory: f64, delta: f64) -> Result<()> {
+        let after = inventory + delta;
+        if after.abs() > self.cap + 1e-9 && after.abs() > inventory.abs() {
+            anyhow::bail!(
+                "Inventory cap exceeded: {:.2} after the trade, cap {:.2}",
+                after, self.cap
+            );
+        }
+        Ok(())
+    }
+
+    /// Skew `bid`/`ask` for `inventory` quoting `size` a side. Prices stay
+    /// inside the 0-1 contract range and a side that would breach the cap
+    /// is dropped.
+    pub fn quote(&self, bid: f64, ask: f64, inventory: f64, size: f64) -> SkewedQuote {
+        let shift = self.shift(inventory);
+        let clamp = |p: f64| p.clamp(0.01, 0.99);
+        SkewedQuote {
+            bid: self.check(inventory, size).is_ok().then(|| clamp(bid + shift)),
+            ask: self.check(inventory, -size).is_ok().then(|| clamp(ask + shift)),
+            shift,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_inventory_shifts_quotes_down() {
+        let skew = InventorySkew::new(100.0, 0.04, SkewFunction::Linear);
+        let flat = skew.quote(0.48, 0.52, 0.0, 10.0);
+        assert_eq!((flat.bid, flat.ask), (Some(0.48), Some(0.52)));
+
+        let long = skew.quote(0.48, 0.52, 50.0, 10.0);
+        assert!((long.shift + 0.02).abs() < 1e-9);
+        assert!((long.bid.unwrap() - 0.46).abs() < 1e-9);
+        let short = skew.quote(0.48, 0.52, -50.0, 10.0);
+        assert!((short.ask.unwrap() - 0.54).abs() < 1e-9);
+
+        // Quadratic skews less at half the cap, tanh more
+        let quadratic = InventorySkew { function: SkewFunction::Quadratic, ..skew };
+        let tanh = InventorySkew { function: SkewFunction::Tanh, ..skew };
+        assert!(quadratic.shift(50.0).abs() < long.shift.abs());
+        assert!(tanh.shift(50.0).abs() > long.shift.abs());
+        assert!((tanh.shift(100.0) + 0.04).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cap_pulls_the_side_that_adds_inventory() {
+        let skew = InventorySkew::new(100.0, 0.04, SkewFunction::Linear);
+        let at_cap = skew.quote(0.48, 0.52, 95.0, 10.0);
+        assert_eq!(at_cap.bid, None);
+        assert!(at_cap.ask.is_some());
+
+        assert!(skew.check(95.0, 10.0).is_err());
+        assert!(skew.check(120.0, -10.0).is_ok(), "reducing an over-cap inventory");
+        assert!(skew.check(-95.0, -10.0).is_err());
+        assert!(SkewFunction::parse("TANH").is_ok() && SkewFunction::parse("cubic").is_err());
+    }
+}
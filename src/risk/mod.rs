@@ -0,0 +1,29 @@
+//! Risk management module.
+//!
+//! Pre-trade checks that limit how much capital each strategy may deploy
+//! and how strategies may trade against each other, net exposure per
+//! question across strategies and venues, simulation of where the current strategy mix could end up,
+//! the ledger of settlement and fill discrepancies that pauses trading until acknowledged,
+//! drawdown-based cuts to position sizes, inventory skew and caps for two-sided quoting,
+//! daily caps on trades per strategy and orders overall, and the pre-trade check every live
+//! order passes through.
+
+pub mod allocator;
+pub mod conflicts;
+pub mod derisk;
+pub mod discrepancies;
+pub mod inventory;
+pub mod monte_carlo;
+pub mod netting;
+pub mod pretrade;
+pub mod trade_limits;
+
+pub use allocator::{CapitalAllocator, Exposure, RebalanceRule};
+pub use conflicts::{Conflict, ConflictGuard, ConflictPolicy};
+pub use derisk::{DeriskEvent, DeriskRule, Derisker};
+pub use discrepancies::{Discrepancy, DiscrepancyKind, DiscrepancyLedger};
+pub use inventory::{InventorySkew, SkewFunction, SkewedQuote};
+pub use monte_carlo::{MonteCarlo, RiskReport};
+pub use netting::{Holding, Netting, QuestionExposure};
+pub use pretrade::PreTradeRisk;
+pub use trade_limits::{DailyCounts, TradeLimits};
@@ -0,0 +1,233 @@
+//! Monte Carlo simulation of strategy returns.
+//!
+//! Resamples each strategy's closed-trade P&L at its historical trade rate
+//! to build a distribution of outcomes over the horizon. With a capital
+//! allocator, each strategy's P&L is scaled from its historical share of
+//! traded notional to its current weight, so the simulation reflects the
+//! strategy mix being run now rather than the one that was run.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::allocator::CapitalAllocator;
+use crate::paper_trading::{PaperTrade, TradeStatus};
+
+/// Days simulated by default.
+pub const RISK_HORIZON_DAYS: u32 = 30;
+const DEFAULT_PATHS: usize = 5_000;
+/// Losing this fraction of starting equity counts as ruin.
+const DEFAULT_RUIN_FRACTION: f64 = 0.5;
+
+/// Closed-trade history of one strategy.
+#[derive(Debug, Clone, Default)]
+struct StrategyHistory {
+    pnls: Vec<f64>,
+    notional: f64,
+}
+
+/// Distribution of simulated outcomes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RiskReport {
+    pub paths: usize,
+    pub days: u32,
+    pub starting_equity: f64,
+    /// Historical trades per day across all simulated strategies
+    pub trades_per_day: f64,
+    pub expected_pnl: f64,
+    pub median_pnl: f64,
+    pub p5_pnl: f64,
+    pub p95_pnl: f64,
+    /// 95% value at risk over the horizon (a loss, as a positive number)
+    pub var_95: f64,
+    /// Average loss in the worst 5% of paths
+    pub cvar_95: f64,
+    /// Fraction of paths that fell to the ruin level
+    pub prob_ruin: f64,
+    pub ruin_level: f64,
+    /// Mean of each path's largest peak-to-trough drawdown
+    pub expected_drawdown: f64,
+    pub p95_drawdown: f64,
+    /// Scale applied to each strategy's historical P&L
+    pub strategy_scale: BTreeMap<String, f64>,
+}
+
+/// Monte Carlo settings.
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    paths: usize,
+    days: u32,
+    ruin_fraction: f64,
+    seed: Option<u64>,
+}
+
+impl Default for MonteCarlo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let index = ((sorted.len() - 1) as f64 * p).round() as usize;
+    sorted[index]
+}
+
+impl MonteCarlo {
+    pub fn new() -> Self {
+        Self {
+            paths: DEFAULT_PATHS,
+            days: RISK_HORIZON_DAYS,
+            ruin_fraction: DEFAULT_RUIN_FRACTION,
+            seed: None,
+        }
+    }
+
+    pub fn with_paths(mut self, paths: usize) -> Self {
+        self.paths = paths.max(1);
+        self
+    }
+
+    pub fn with_days(mut self, days: u32) -> Self {
+        self.days = days.max(1);
+        self
+    }
+
+    /// Fixed seed for reproducible runs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Simulate from `trades`, starting at `equity`. `None` without any
+    /// closed trades to resample.
+    pub fn simulate(&self, trades: &[PaperTrade], allocator: Option<&CapitalAllocator>, equity: f64) -> Option<RiskReport> {
+        let closed: Vec<&PaperTrade> = trades
+            .iter()
+            .filter(|t| t.status == TradeStatus::Closed && t.pnl.is_some())
+            .collect();
+        let first = closed.iter().map(|t| t.timestamp).min()?;
+        let last = closed.iter().map(|t| t.timestamp).max()?;
+        // At least a day, so a burst of trades is not extrapolated
+        let span_days = ((last - first).num_seconds() as f64 / 86_400.0).max(1.0);
+
+        let mut histories: BTreeMap<String, StrategyHistory> = BTreeMap::new();
+        for trade in &closed {
+            let history = histories.entry(trade.strategy.clone()).or_default();
+            history.pnls.push(trade.pnl.unwrap_or(0.0));
+            history.notional += trade.filled_size();
+        }
+        let total_notional: f64 = histories.values().map(|h| h.notional).sum();
+        let strategy_scale: BTreeMap<String, f64> = histories
+            .iter()
+            .map(|(name, history)| {
+                let scale = match allocator {
+                    Some(a) if total_notional > 0.0 && history.notional > 0.0 => {
+                        a.weight(name) / (history.notional / total_notional)
+                    }
+                    Some(_) => 0.0,
+                    None => 1.0,
+                };
+                (name.clone(), scale)
+            })
+            .collect();
+        let simulated: Vec<(&StrategyHistory, f64, f64)> = histories
+            .iter()
+            .filter(|(name, _)| strategy_scale[*name] > 0.0)
+            .map(|(name, h)| (h, h.pnls.len() as f64 / span_days, strategy_scale[name]))
+            .collect();
+
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let ruin_level = equity * (1.0 - self.ruin_fraction);
+        let mut outcomes = Vec::with_capacity(self.paths);
+        let mut drawdowns = Vec::with_capacity(self.paths);
+        let mut ruined = 0;
+
+        for _ in 0..self.paths {
+            let (mut value, mut peak, mut drawdown, mut hit_ruin) = (equity, equity, 0.0_f64, false);
+            for _ in 0..self.days {
+                for (history, rate, scale) in &simulated {
+                    // Whole trades per day, plus one more with the leftover probability
+                    let count = rate.floor() as usize + rng.gen_bool(rate.fract()) as usize;
+                    for _ in 0..count {
+                        value += history.pnls[rng.gen_range(0..history.pnls.len())] * scale;
+                        peak = peak.max(value);
+                        drawdown = drawdown.max(peak - value);
+                        hit_ruin |= value <= ruin_level;
+                    }
+                }
+            }
+            outcomes.push(value - equity);
+            drawdowns.push(drawdown);
+            ruined += hit_ruin as usize;
+        }
+
+        outcomes.sort_by(|a, b| a.total_cmp(b));
+        drawdowns.sort_by(|a, b| a.total_cmp(b));
+        let n = self.paths as f64;
+        let p5_pnl = percentile(&outcomes, 0.05);
+        let tail: Vec<f64> = outcomes.iter().copied().filter(|p| *p <= p5_pnl).collect();
+
+        Some(RiskReport {
+            paths: self.paths,
+            days: self.days,
+            starting_equity: equity,
+            trades_per_day: simulated.iter().map(|(_, rate, _)| rate).sum(),
+            expected_pnl: outcomes.iter().sum::<f64>() / n,
+            median_pnl: percentile(&outcomes, 0.5),
+            p5_pnl,
+            p95_pnl: percentile(&outcomes, 0.95),
+            var_95: (-p5_pnl).max(0.0),
+            cvar_95: (-tail.iter().sum::<f64>() / tail.len() as f64).max(0.0),
+            prob_ruin: ruined as f64 / n,
+            ruin_level,
+            expected_drawdown: drawdowns.iter().sum::<f64>() / n,
+            p95_drawdown: percentile(&drawdowns, 0.95),
+            strategy_scale,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::Side;
+    use chrono::{Duration, Utc};
+
+    fn closed(strategy: &str, days_ago: i64, exit: f64) -> PaperTrade {
+        let mut trade = PaperTrade::new("BTC", "BTC", "Hourly", "kalshi", Side::Buy, 100.0, 0.50, strategy, 0.5);
+        trade.timestamp = Utc::now() - Duration::days(days_ago);
+        trade.close(exit);
+        trade
+    }
+
+    #[test]
+    fn test_distribution_and_strategy_mix() {
+        // One trade a day for ten days: mostly small wins, a few big losses
+        let mut trades: Vec<PaperTrade> = (0..10)
+            .map(|d| closed("arbitrage", d, if d % 4 == 0 { 0.10 } else { 0.55 }))
+            .collect();
+        trades.push(closed("copy_trade", 3, 0.60));
+        assert!(MonteCarlo::new().simulate(&[], None, 1000.0).is_none());
+
+        let mc = MonteCarlo::new().with_paths(2_000).with_seed(7);
+        let report = mc.simulate(&trades, None, 1000.0).unwrap();
+        assert_eq!(report.days, RISK_HORIZON_DAYS);
+        assert!(report.p5_pnl <= report.median_pnl && report.median_pnl <= report.p95_pnl);
+        assert!(report.var_95 > 0.0 && report.cvar_95 >= report.var_95);
+        assert!(report.expected_drawdown > 0.0);
+        assert!((0.0..=1.0).contains(&report.prob_ruin));
+        // Same seed, same distribution
+        assert_eq!(mc.simulate(&trades, None, 1000.0).unwrap().p5_pnl, report.p5_pnl);
+
+        // Allocating everything to copy trading drops arbitrage entirely
+        let allocator = CapitalAllocator::new(1000.0, vec![("copy_trade".to_string(), 1.0)]);
+        let mixed = mc.simulate(&trades, Some(&allocator), 1000.0).unwrap();
+        assert_eq!(mixed.strategy_scale["arbitrage"], 0.0);
+        assert!((mixed.strategy_scale["copy_trade"] - 11.0).abs() < 1e-9);
+        assert!(mixed.p5_pnl >= 0.0);
+    }
+}
@@ -0,0 +1,276 @@
+//! Position netting across strategies and venues.
+//!
+//! Paper positions (arb legs, copies, manual trades) and live fills are
+//! grouped by the underlying question they pay out on, whichever venue
+//! they sit on, to show the net delta to each outcome: what the whole book
+//! makes or loses if the question resolves YES or NO.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::analysis::execution::ExecutionRecord;
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::arbitrage::MarketMatcher;
+use crate::paper_trading::{PaperTrade, Portfolio, TradeStatus};
+use crate::utils::audit_log::AuditEvent;
+
+/// Split a position name into its question and whether it is the YES
+/// outcome. Outcomes are named by a " YES"/" NO" suffix (arb legs, Kalshi
+/// fills); names without one are taken as YES.
+pub fn split_outcome(market: &str) -> (&str, bool) {
+    if let Some(base) = strip_suffix_ignore_case(market, " NO") {
+        (base, false)
+    } else if let Some(base) = strip_suffix_ignore_case(market, " YES") {
+        (base, true)
+    } else {
+        (market, true)
+    }
+}
+
+fn strip_suffix_ignore_case<'a>(value: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = value.len().checked_sub(suffix.len())?;
+    (value.is_char_boundary(split) && value[split..].eq_ignore_ascii_case(suffix)).then(|| &value[..split])
+}
+
+/// Shares of one outcome held by one strategy on one venue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    /// Position or venue market name (a " YES"/" NO" suffix picks the outcome)
+    pub market: String,
+    pub venue: String,
+    pub strategy: String,
+    /// Shares held; negative when sold short
+    pub shares: f64,
+    /// USD paid (negative for proceeds)
+    pub cost: f64,
+}
+
+/// Net exposure to one question.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QuestionExposure {
+    pub question: String,
+    /// Shares paying $1 if the question resolves YES
+    pub yes_shares: f64,
+    /// Shares paying $1 if it resolves NO
+    pub no_shares: f64,
+    /// Net USD paid for all of them
+    pub cost: f64,
+    pub venues: BTreeSet<String>,
+    /// Net delta per strategy
+    pub strategies: BTreeMap<String, f64>,
+}
+
+impl QuestionExposure {
+    /// YES shares less NO shares: the payout swing between the outcomes.
+    pub fn net_delta(&self) -> f64 {
+        self.yes_shares - self.no_shares
+    }
+
+    pub fn pnl_if_yes(&self) -> f64 {
+        self.yes_shares - self.cost
+    }
+
+    pub fn pnl_if_no(&self) -> f64 {
+        self.no_shares - self.cost
+    }
+
+    /// Loss if the question resolves against the book (0 when both
+    /// outcomes pay, e.g. a completed arb).
+    pub fn worst_loss(&self) -> f64 {
+        (-self.pnl_if_yes().min(self.pnl_if_no())).max(0.0)
+    }
+
+    fn add(&mut self, holding: &Holding, yes: bool) {
+        if yes {
+            self.yes_shares += holding.shares;
+        } else {
+            self.no_shares += holding.shares;
+        }
+        self.cost += holding.cost;
+        self.venues.insert(holding.venue.clone());
+        let delta = if yes { holding.shares } else { -holding.shares };
+        *self.strategies.entry(holding.strategy.clone()).or_default() += delta;
+    }
+}
+
+/// Exposure per question across every book added.
+#[derive(Debug, Clone, Default)]
+pub struct Netting {
+    questions: BTreeMap<String, QuestionExposure>,
+    /// Venue market IDs to the question they belong to
+    names: HashMap<String, String>,
+}
+
+impl Netting {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Name venue IDs (Polymarket YES tokens, Kalshi tickers) after the
+    /// matched pair, so both venues net into one question.
+    pub fn with_matches(self, matcher: &MarketMatcher) -> Self {
+        self.with_pairs(matcher.get_all())
+    }
+
+    /// As [`Netting::with_matches`], for pairs held outside a matcher.
+    pub fn with_pairs<'a>(mut self, pairs: impl IntoIterator<Item = &'a MatchedMarket>) -> Self {
+        for matched in pairs {
+            self.names.insert(matched.polymarket.native.clone(), matched.name.clone());
+            self.names.insert(matched.kalshi.native.clone(), matched.name.clone());
+        }
+        self
+    }
+
+    /// Question a position name belongs to, and whether it is YES.
+    pub fn question_of(&self, market: &str) -> (String, bool) {
+        let (base, yes) = split_outcome(market);
+        let question = self.names.get(base).map_or(base, String::as_str);
+        (question.to_string(), yes)
+    }
+
+    pub fn add(&mut self, holding: Holding) {
+        let (question, yes) = self.question_of(&holding.market);
+        self.questions
+            .entry(question.clone())
+            .or_insert_with(|| QuestionExposure { question, ..Default::default() })
+            .add(&holding, yes);
+    }
+
+    /// Add a paper portfolio's positions. Each is attributed to the
+    /// strategy of its latest open trade; copied positions to "copy_trade".
+    pub fn add_paper(&mut self, portfolio: &Portfolio, trades: &[PaperTrade]) {
+        for position in portfolio.positions.values() {
+            let strategy = match &position.source {
+                Some(_) => "copy_trade".to_string(),
+                None => trades
+                    .iter()
+                    .rev()
+                    .find(|t| t.status == TradeStatus::Open && t.market == position.market)
+                    .map_or_else(|| "manual".to_string(), |t| t.strategy.clone()),
+            };
+            self.add(Holding {
+                market: position.market.clone(),
+                venue: position.platform.clone(),
+                strategy,
+                shares: position.size,
+                cost: position.size * position.avg_price,
+            });
+        }
+    }
+
+    /// Add live fills from the audit log, attributed to "live".
+    pub fn add_fills(&mut self, events: &[AuditEvent]) {
+        for event in events.iter().filter(|e| e.kind == "fill") {
+            let shares = if event.side.eq_ignore_ascii_case("sell") { -event.size } else { event.size };
+            self.add(Holding {
+                market: event.market.clone(),
+                venue: event.venue.clone(),
+                strategy: "live".to_string(),
+                shares,
+                cost: shares * event.price,
+            });
+        }
+    }
+
+    /// Add our live orders' fills, attributed to the strategy that placed
+    /// each order.
+    pub fn add_executions(&mut self, records: &[ExecutionRecord]) {
+        for record in records {
+            let Some(price) = record.avg_fill_price() else { continue };
+            let filled = record.filled_size();
+            let shares = if record.side.eq_ignore_ascii_case("sell") { -filled } else { filled };
+            let market = match &record.outcome {
+                Some(outcome) => format!("{} {}", record.market, outcome.to_uppercase()),
+                None => record.market.clone(),
+            };
+            self.add(Holding {
+                market,
+                venue: record.venue.clone(),
+                strategy: record.source.clone(),
+                shares,
+                cost: shares * price,
+            });
+        }
+    }
+
+    pub fn question(&self, question: &str) -> Option<&QuestionExposure> {
+        self.questions.get(question)
+    }
+
+    /// Questions with a position, by name.
+    pub fn questions(&self) -> impl Iterator<Item = &QuestionExposure> {
+        self.questions.values().filter(|q| q.yes_shares.abs() > 1e-9 || q.no_shares.abs() > 1e-9)
+    }
+
+    /// Worst-case loss on `holding`'s question if it were added.
+    pub fn worst_loss_with(&self, holding: &Holding) -> f64 {
+        let (question, yes) = self.question_of(&holding.market);
+        let mut exposure = self.questions.get(&question).cloned().unwrap_or_default();
+        exposure.add(holding, yes);
+        exposure.worst_loss()
+    }
+
+    /// Bail if adding `holdings`, all on one question, would push its
+    /// worst-case loss over `limit`.
+    pub fn check_loss(&self, holdings: &[Holding], limit: f64) -> Result<()> {
+        let Some(first) = holdings.first() else {
+            return Ok(());
+        };
+        let mut netting = self.clone();
+        for holding in &holdings[1..] {
+            netting.add(holding.clone());
+        }
+        let loss = netting.worst_loss_with(first);
+        if loss > limit + 1e-9 {
+            anyhow::bail!(
+                "{} exposure limit exceeded: ${:.2} at risk, limit ${:.2}",
+                self.question_of(&first.market).0, loss, limit
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(market: &str, venue: &str, strategy: &str, shares: f64, price: f64) -> Holding {
+        Holding {
+            market: market.to_string(),
+            venue: venue.to_string(),
+            strategy: strategy.to_string(),
+            shares,
+            cost: shares * price,
+        }
+    }
+
+    #[test]
+    fn test_nets_across_venues_and_strategies() {
+        let mut matcher = MarketMatcher::empty();
+        matcher.add_match(MatchedMarket::new("BTC 100k", "0xtoken", "KXBTC-100K"));
+        let mut netting = Netting::new().with_matches(&matcher);
+
+        // A completed arb: YES on Polymarket, NO on Kalshi for 95¢ a pair
+        netting.add(holding("BTC 100k YES", "polymarket", "arbitrage", 100.0, 0.45));
+        netting.add(holding("KXBTC-100K NO", "kalshi", "arbitrage", 100.0, 0.50));
+        let q = netting.question("BTC 100k").unwrap();
+        assert_eq!(q.net_delta(), 0.0);
+        assert_eq!(q.worst_loss(), 0.0);
+
+        // A copied YES buy on the Polymarket token tilts the book
+        netting.add(holding("0xtoken", "polymarket", "copy_trade", 40.0, 0.5));
+        let q = netting.question("BTC 100k").unwrap();
+        assert_eq!(q.net_delta(), 40.0);
+        assert!((q.pnl_if_yes() - 25.0).abs() < 1e-9);
+        assert!((q.pnl_if_no() - (-15.0)).abs() < 1e-9);
+        assert_eq!(q.strategies["arbitrage"], 0.0);
+        assert_eq!(q.venues.len(), 2);
+
+        // Selling it back flattens the delta
+        let sell = holding("0xtoken", "polymarket", "live", -40.0, 0.5);
+        assert!((netting.worst_loss_with(&sell) - 0.0).abs() < 1e-9);
+        assert_eq!(netting.questions().count(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+//! Pre-trade risk check for live orders.
+//!
+//! Every order the execution queue sends goes through [`PreTradeRisk::check`]
+//! first, so the limits hold however many tasks feed the queue: the
+//! cross-strategy conflict policies, the per-question loss limit and the
+//! inventory cap, all against our live fills netted per question, then the
+//! daily caps. An
+//! order that passes, possibly netted down to a smaller size, has its place
+//! under the daily caps reserved; one that doesn't is refused with the
+//! reason.
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+
+use super::conflicts::ConflictGuard;
+use super::inventory::InventorySkew;
+use super::netting::{Holding, Netting};
+use super::trade_limits::TradeLimits;
+use crate::analysis::execution::ExecutionLog;
+use crate::approval::IntentKind;
+use crate::arbitrage::market_matcher::MatchedMarket;
+use crate::paper_trading::Side;
+
+/// The checks a live order must pass before it goes out.
+pub struct PreTradeRisk {
+    limits: Mutex<TradeLimits>,
+    conflicts: Option<ConflictGuard>,
+    /// Max netted loss on any one question (unlimited when None)
+    question_limit: Option<f64>,
+    /// Cap on net contracts per question
+    inventory: Option<InventorySkew>,
+    /// Our live orders and fills, the book the checks net against
+    executions: Option<Arc<Mutex<ExecutionLog>>>,
+}
+
+impl PreTradeRisk {
+    pub fn new(limits: TradeLimits) -> Self {
+        Self { limits: Mutex::new(limits), conflicts: None, question_limit: None, inventory: None, executions: None }
+    }
+
+    /// Enforce cross-strategy conflict policies.
+    pub fn with_conflicts(mut self, guard: ConflictGuard) -> Self {
+        self.conflicts = Some(guard);
+        self
+    }
+
+    /// Cap the loss on any one question. Zero or less leaves it unlimited.
+    pub fn with_question_limit(mut self, max_loss: f64) -> Self {
+        self.question_limit = (max_loss > 0.0).then_some(max_loss);
+        self
+    }
+
+    /// Refuse orders that take a question's inventory past the skew's cap.
+    pub fn with_inventory(mut self, skew: InventorySkew) -> Self {
+        self.inventory = Some(skew);
+        self
+    }
+
+    /// Net against the fills of the orders in `log`.
+    pub fn with_executions(mut self, log: Arc<Mutex<ExecutionLog>>) -> Self {
+        self.executions = Some(log);
+        self
+    }
+
+    /// Check an order about to be sent, with venue IDs named after the
+    /// matched `pairs`, and reserve it under the daily caps.
+    /// Returns the order to send, netted down if its conflict policy says
+    /// so, or errors with the reason it may not go out.
+    pub fn check(&self, kind: &IntentKind, pairs: &[MatchedMarket]) -> Result<IntentKind> {
+        let mut netting = Netting::new().with_pairs(pairs);
+        if let Some(log) = &self.executions {
+            netting.add_executions(log.lock().unwrap().records());
+        }
+        let kind = self.resolve_conflicts(kind, &netting)?;
+        let adding = holdings(&kind);
+        if let Some(limit) = self.question_limit {
+            netting.check_loss(&adding, limit)?;
+        }
+        if let Some(skew) = &self.inventory {
+            // An arb's legs offset each other, so it adds no inventory
+            let (question, _) = netting.question_of(&adding[0].market);
+            let delta: f64 = adding
+                .iter()
+                .map(|h| if netting.question_of(&h.market).1 { h.shares } else { -h.shares })
+                .sum();
+            let inventory = netting.question(&question).map_or(0.0, |q| q.net_delta());
+            skew.check(inventory, delta)
+                .map_err(|e| anyhow::anyhow!("{} on {}", e, question))?;
+        }
+        self.limits.lock().unwrap().try_reserve(kind.strategy(), orders(&kind))?;
+        Ok(kind)
+    }
+
+    /// The order after its strategy's conflict policy.
+    fn resolve_conflicts(&self, kind: &IntentKind, netting: &Netting) -> Result<IntentKind> {
+        let Some(guard) = &self.conflicts else {
+            return Ok(kind.clone());
+        };
+        let strategy = kind.strategy();
+        let mut kind = kind.clone();
+        match &mut kind {
+            IntentKind::Arbitrage { market, yes_price, no_price, contracts, .. } => {
+                // Legs are paired, so a leg can't be netted down on its own
+                for (outcome, price) in [("YES", *yes_price), ("NO", *no_price)] {
+                    let usd = *contracts * price;
+                    let leg = format!("{} {}", market, outcome);
+                    if guard.resolve_netted(strategy, &leg, Side::Buy, usd, price, netting)? < usd - 1e-9 {
+                        anyhow::bail!("{} leg of {} is netted by an opposing position", outcome, market);
+                    }
+                }
+            }
+            IntentKind::CopyTrade { asset, side, price, size_usd, .. } => {
+                *size_usd = guard.resolve_netted(strategy, asset, side_of(side), *size_usd, *price, netting)?;
+            }
+            IntentKind::KalshiFollow { ticker, side, action, price, count, .. } => {
+                let usd = *count as f64 * *price;
+                let market = format!("{} {}", ticker, side.to_uppercase());
+                let allowed = guard.resolve_netted(strategy, &market, side_of(action), usd, *price, netting)?;
+                *count = (*count as f64 * allowed / usd).floor() as i32;
+                if *count < 1 {
+                    anyhow::bail!("{} order on {} netted down to nothing", strategy, ticker);
+                }
+            }
+        }
+        Ok(kind)
+    }
+}
+
+/// Orders an intent places: both legs of an arb count.
+fn orders(kind: &IntentKind) -> u32 {
+    match kind {
+        IntentKind::Arbitrage { .. } => 2,
+        _ => 1,
+    }
+}
+
+fn side_of(side: &str) -> Side {
+    if side.eq_ignore_ascii_case("sell") { Side::Sell } else { Side::Buy }
+}
+
+/// What an intent adds to the book, for the question loss limit.
+fn holdings(kind: &IntentKind) -> Vec<Holding> {
+    let strategy = kind.strategy().to_string();
+    let holding = |market: String, venue: &str, shares: f64, price: f64| Holding {
+        market,
+        venue: venue.to_string(),
+        strategy: strategy.clone(),
+        shares,
+        cost: shares * price,
+    };
+    match kind {
+        IntentKind::Arbitrage { market, yes_venue, yes_price, no_venue, no_price, contracts } => vec![
+            holding(format!("{} YES", market), yes_venue, *contracts, *yes_price),
+            holding(format!("{} NO", market), no_venue, *contracts, *no_price),
+        ],
+        IntentKind::CopyTrade { asset, side, price, size_usd, .. } => {
+            let shares = size_usd / price;
+            let shares = if side_of(side) == Side::Sell { -shares } else { shares };
+            vec![holding(asset.clone(), "polymarket", shares, *price)]
+        }
+        IntentKind::KalshiFollow { ticker, side, action, price, count, .. } => {
+            let shares = if side_of(action) == Side::Sell { -*count as f64 } else { *count as f64 };
+            vec![holding(format!("{} {}", ticker, side.to_uppercase()), "kalshi", shares, *price)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::{ConflictPolicy, SkewFunction};
+    use crate::utils::fills::Fill;
+
+    fn arb(contracts: f64) -> IntentKind {
+        IntentKind::Arbitrage {
+            market: "BTC".to_string(),
+            yes_venue: "polymarket".to_string(),
+            yes_price: 0.45,
+            no_venue: "kalshi".to_string(),
+            no_price: 0.50,
+            contracts,
+        }
+    }
+
+    fn follow(side: &str, count: i32) -> IntentKind {
+        IntentKind::KalshiFollow {
+            trade_id: "t".to_string(),
+            ticker: "KXBTC-1".to_string(),
+            side: side.to_string(),
+            action: "buy".to_string(),
+            price: 0.40,
+            count,
+        }
+    }
+
+    /// A live log holding 10 filled copy-trade YES contracts on KXBTC-1.
+    fn live_book() -> Arc<Mutex<ExecutionLog>> {
+        let mut log = ExecutionLog::new();
+        log.record_submission("o1", "kalshi", "KXBTC-1", "KXBTC-1", "copy_trade", "buy", (0.60, 0.60), 10.0, Some("yes"));
+        log.record_fill(&Fill {
+            venue: "kalshi".to_string(),
+            order_id: "o1".to_string(),
+            trade_id: "f1".to_string(),
+            market: "KXBTC-1".to_string(),
+            side: "BUY".to_string(),
+            price: 0.60,
+            size: 10.0,
+            filled_at: chrono::Utc::now(),
+        });
+        Arc::new(Mutex::new(log))
+    }
+
+    #[test]
+    fn test_reserves_both_arb_legs_under_the_order_cap() {
+        let risk = PreTradeRisk::new(TradeLimits::new(chrono_tz::UTC).with_order_cap(3));
+        assert!(risk.check(&arb(10.0), &[]).is_ok());
+        assert!(risk.check(&arb(10.0), &[]).unwrap_err().to_string().contains("orders allowed today"));
+    }
+
+    #[test]
+    fn test_conflicts_and_question_loss_apply_to_live_orders() {
+        let limits = || TradeLimits::new(chrono_tz::UTC).with_order_cap(1);
+        let skip = ConflictGuard::new().with_policy("*", ConflictPolicy::Skip);
+        let net = ConflictGuard::new().with_policy("*", ConflictPolicy::Net);
+
+        // Buying NO against copy_trade's YES is skipped, and a refused order
+        // takes no place under the caps
+        let risk = PreTradeRisk::new(limits()).with_conflicts(skip).with_executions(live_book());
+        assert!(risk.check(&follow("no", 20), &[]).unwrap_err().to_string().contains("conflicts with copy_trade"));
+        assert!(risk.check(&follow("yes", 20), &[]).is_ok());
+
+        // Netting trades only what exceeds the $4 held the other way
+        let risk = PreTradeRisk::new(limits()).with_conflicts(net).with_executions(live_book());
+        match risk.check(&follow("no", 20), &[]).unwrap() {
+            IntentKind::KalshiFollow { count, .. } => assert_eq!(count, 10),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        // $6 is already at risk if the question resolves NO; $4 more of YES
+        // takes it over the limit, while NO offsets it
+        let risk = PreTradeRisk::new(limits()).with_question_limit(8.0).with_executions(live_book());
+        assert!(risk.check(&follow("yes", 10), &[]).unwrap_err().to_string().contains("exposure limit"));
+        assert!(risk.check(&follow("no", 5), &[]).is_ok());
+
+        // 10 YES held against a cap of 15: NO brings it down, more YES breaches it
+        let risk = PreTradeRisk::new(limits())
+            .with_inventory(InventorySkew::new(15.0, 0.02, SkewFunction::Linear))
+            .with_executions(live_book());
+        assert!(risk.check(&follow("yes", 6), &[]).unwrap_err().to_string().contains("Inventory cap"));
+        assert!(risk.check(&follow("no", 20), &[]).is_ok());
+    }
+}
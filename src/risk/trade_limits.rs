@@ -0,0 +1,216 @@
+//! Daily trade caps.
+//!
+//! Caps how many trades each strategy may place per day and how many
+//! orders go out per day across all strategies: a runaway strategy or a
+//! feed glitch that looks like endless opportunities stops at the cap
+//! instead of trading all day. Counts roll over at midnight in the
+//! configured TIMEZONE and survive restarts in `data/state/trade_counts.json`,
+//! which the TUI reads for the Strategies tab.
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::utils::clock::{system_clock, SharedClock};
+use crate::utils::{state, timezone};
+
+/// Persisted counts of the current day.
+pub const TRADE_COUNTS_FILE: &str = "data/state/trade_counts.json";
+
+/// Trades and orders placed on one day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DailyCounts {
+    /// Local date the counts are for
+    pub day: Option<NaiveDate>,
+    /// Trades by strategy
+    pub trades: BTreeMap<String, u32>,
+    /// Orders across all strategies
+    pub orders: u32,
+}
+
+impl DailyCounts {
+    pub fn trades(&self, strategy: &str) -> u32 {
+        self.trades.get(strategy).copied().unwrap_or(0)
+    }
+}
+
+/// Enforces the daily caps.
+#[derive(Debug, Clone)]
+pub struct TradeLimits {
+    per_strategy: BTreeMap<String, u32>,
+    max_orders: Option<u32>,
+    tz: Tz,
+    counts: DailyCounts,
+    path: Option<String>,
+    clock: SharedClock,
+}
+
+impl TradeLimits {
+    /// Uncapped until limits are added, counting days in `tz`.
+    pub fn new(tz: Tz) -> Self {
+        Self {
+            per_strategy: BTreeMap::new(),
+            max_orders: None,
+            tz,
+            counts: DailyCounts::default(),
+            path: None,
+            clock: system_clock(),
+        }
+    }
+
+    /// MAX_TRADES_PER_DAY and MAX_ORDERS_PER_DAY, days in TIMEZONE.
+    pub fn from_config(config: &Config) -> Self {
+        let mut limits = Self::new(config.timezone);
+        for (strategy, cap) in &config.max_trades_per_day {
+            limits = limits.with_strategy_cap(strategy, *cap);
+        }
+        limits.max_orders = config.max_orders_per_day;
+        limits
+    }
+
+    pub fn with_strategy_cap(mut self, strategy: &str, cap: u32) -> Self {
+        self.per_strategy.insert(strategy.to_string(), cap);
+        self
+    }
+
+    pub fn with_order_cap(mut self, cap: u32) -> Self {
+        self.max_orders = Some(cap);
+        self
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Restore from and persist to `path`.
+    pub fn with_state_file(mut self, path: &str) -> Self {
+        self.counts = state::load(path);
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Re-read the counts from `path`, e.g. in the TUI while the bot
+    /// writes them. Nothing is written back unless a state file is set.
+    pub fn reload(&mut self, path: &str) {
+        self.counts = state::load(path);
+    }
+
+    /// Daily trade cap of `strategy`.
+    pub fn strategy_cap(&self, strategy: &str) -> Option<u32> {
+        self.per_strategy.get(strategy).copied()
+    }
+
+    pub fn order_cap(&self) -> Option<u32> {
+        self.max_orders
+    }
+
+    /// Counts of today: empty once the day has rolled over.
+    pub fn counts(&self) -> DailyCounts {
+        let today = timezone::local_date(self.clock.now(), self.tz);
+        if self.counts.day == Some(today) {
+            self.counts.clone()
+        } else {
+            DailyCounts { day: Some(today), ..DailyCounts::default() }
+        }
+    }
+
+    /// Why `strategy` may not place a trade of `orders` orders now, if it
+    /// may not.
+    pub fn blocked(&self, strategy: &str, orders: u32) -> Option<String> {
+        let counts = self.counts();
+        if let Some(cap) = self.strategy_cap(strategy).filter(|cap| counts.trades(strategy) >= *cap) {
+            return Some(format!("{} reached its cap of {} trades today", strategy, cap));
+        }
+        match self.max_orders {
+            Some(cap) if counts.orders + orders > cap => {
+                Some(format!("{} of the {} orders allowed today already placed", counts.orders, cap))
+            }
+            _ => None,
+        }
+    }
+
+    /// Check and count a trade of `orders` orders by `strategy` in one
+    /// step, so trades checked side by side cannot overshoot a cap. Errors
+    /// with the reason when a cap is reached.
+    pub fn try_reserve(&mut self, strategy: &str, orders: u32) -> Result<()> {
+        if let Some(reason) = self.blocked(strategy, orders) {
+            anyhow::bail!(reason);
+        }
+        self.record(strategy, orders);
+        Ok(())
+    }
+
+    /// Count a trade of `orders` orders by `strategy`.
+    pub fn record(&mut self, strategy: &str, orders: u32) {
+        self.counts = self.counts();
+        *self.counts.trades.entry(strategy.to_string()).or_insert(0) += 1;
+        self.counts.orders += orders;
+        if let Some(path) = &self.path {
+            if let Err(e) = state::save(path, &self.counts) {
+                warn!("Failed to save trade counts: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_caps_apply_per_day_in_the_configured_zone() {
+        let ny = chrono_tz::America::New_York;
+        // 11pm in New York
+        let night = Utc.with_ymd_and_hms(2026, 3, 10, 3, 0, 0).unwrap();
+        let clock = Arc::new(MockClock::new(night));
+        let mut limits = TradeLimits::new(ny)
+            .with_strategy_cap("copy_trade", 2)
+            .with_order_cap(5)
+            .with_clock(clock.clone());
+
+        limits.record("copy_trade", 1);
+        assert_eq!(limits.blocked("copy_trade", 1), None);
+        limits.record("copy_trade", 1);
+        assert!(limits.blocked("copy_trade", 1).unwrap().contains("cap of 2 trades"));
+
+        // Uncapped strategies still count toward the order cap
+        assert_eq!(limits.blocked("arbitrage", 2), None);
+        limits.record("arbitrage", 2);
+        assert!(limits.blocked("arbitrage", 2).is_some());
+        assert_eq!(limits.blocked("arbitrage", 1), None);
+        assert_eq!(limits.counts().orders, 4);
+
+        // Midnight in New York, 4am UTC, starts a new day
+        clock.set(Utc.with_ymd_and_hms(2026, 3, 10, 4, 30, 0).unwrap());
+        let counts = limits.counts();
+        assert_eq!((counts.orders, counts.trades("copy_trade")), (0, 0));
+        assert_eq!(limits.blocked("copy_trade", 1), None);
+        limits.record("copy_trade", 1);
+        assert_eq!(limits.counts().trades("copy_trade"), 1);
+    }
+
+    #[test]
+    fn test_reserving_counts_only_what_fits() {
+        let noon = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let mut limits = TradeLimits::new(chrono_tz::UTC)
+            .with_order_cap(3)
+            .with_clock(Arc::new(MockClock::new(noon)));
+
+        assert!(limits.try_reserve("arbitrage", 2).is_ok());
+        let refused = limits.try_reserve("arbitrage", 2).unwrap_err();
+        assert!(refused.to_string().contains("2 of the 3 orders"));
+        // A refused reservation isn't counted
+        assert_eq!(limits.counts().orders, 2);
+        assert!(limits.try_reserve("copy_trade", 1).is_ok());
+        assert_eq!(limits.counts().orders, 3);
+    }
+}
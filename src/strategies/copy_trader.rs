@@ -1,12 +1,35 @@
 //! Copy trading strategy module.
 //!
 //! Monitors top traders on Polymarket and mirrors their positions.
+//! Copying is driven by the activity feed; periodic snapshots of each
+//! trader's full positions are diffed to catch adds and exits the feed
+//! missed (e.g. under rate limiting) and generate corrective trades.
 
-use anyhow::{Context, Result};
-use reqwest::Client;
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::display;
+use crate::polymarket::data_api::DataApiClient;
+use crate::utils::clock::{system_clock, SharedClock};
+use crate::utils::fixtures::FixtureStore;
+use crate::utils::{state, VenueConstraints};
+
+pub use crate::polymarket::data_api::{TradeActivity, TraderPosition};
+
+/// Default location of processed copy-trade hashes.
+pub const PROCESSED_TRADES_FILE: &str = "data/state/copy_trades.json";
+
+/// Default location of the last position snapshot per trader.
+pub const POSITION_SNAPSHOTS_FILE: &str = "data/state/copy_positions.json";
+
+/// Activities older than this are not copied.
+const MAX_ACTIVITY_AGE_HOURS: f64 = 1.0;
+
+/// Most positions fetched per trader snapshot.
+const MAX_SNAPSHOT_POSITIONS: usize = 2000;
 
 /// Configuration for copy trading
 #[derive(Debug, Clone)]
@@ -17,82 +40,84 @@ pub struct CopyTraderConfig {
     pub max_position_size: f64,
     /// Minimum trade size to copy (filter dust trades)
     pub min_trade_size: f64,
+    /// Minimum USD size of our copy after scaling
+    pub min_copy_notional: f64,
+    /// EMA weight of the newest size ratio (1.0 = no smoothing)
+    pub ratio_smoothing: f64,
     /// Our wallet/proxy address
     pub our_address: String,
     /// Whether to actually execute trades
     pub dry_run: bool,
 }
 
-/// A trade activity from a trader
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TradeActivity {
-    #[serde(default)]
-    pub proxy_wallet: String,
-    #[serde(default)]
-    pub timestamp: i64,
-    #[serde(default)]
+/// One holding in a position snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionMark {
     pub condition_id: String,
-    #[serde(default, rename = "type")]
-    pub activity_type: String,
-    #[serde(default)]
+    /// Shares held
     pub size: f64,
-    #[serde(default)]
-    pub usdc_size: f64,
-    #[serde(default)]
-    pub transaction_hash: String,
-    #[serde(default)]
     pub price: f64,
-    #[serde(default)]
-    pub asset: String,
-    #[serde(default)]
-    pub side: String,  // "BUY" or "SELL"
-    #[serde(default)]
-    pub outcome_index: i32,
-    #[serde(default)]
     pub title: String,
-    #[serde(default)]
-    pub slug: String,
-    #[serde(default)]
     pub event_slug: String,
+    /// Outcome held, e.g. "Yes" or a candidate of a multi-outcome market
     #[serde(default)]
     pub outcome: String,
 }
 
-/// A position held by a trader
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TraderPosition {
-    #[serde(default)]
-    pub proxy_wallet: String,
-    #[serde(default)]
+/// A trader's holdings by asset at a point in time.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PositionSnapshot {
+    /// Unix ms the snapshot was taken
+    pub taken_at: i64,
+    pub positions: BTreeMap<String, PositionMark>,
+}
+
+impl PositionSnapshot {
+    pub fn new(taken_at: i64, positions: &[TraderPosition]) -> Self {
+        let positions = positions
+            .iter()
+            .filter(|p| p.size > 0.0)
+            .map(|p| {
+                let mark = PositionMark {
+                    condition_id: p.condition_id.clone(),
+                    size: p.size,
+                    price: p.cur_price,
+                    title: p.title.clone(),
+                    event_slug: p.event_slug.clone(),
+                    outcome: p.outcome.clone(),
+                };
+                (p.asset.clone(), mark)
+            })
+            .collect();
+        Self { taken_at, positions }
+    }
+}
+
+/// Change in one holding between two snapshots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionChange {
     pub asset: String,
-    #[serde(default)]
-    pub condition_id: String,
-    #[serde(default)]
-    pub size: f64,
-    #[serde(default)]
-    pub avg_price: f64,
-    #[serde(default)]
-    pub initial_value: f64,
-    #[serde(default)]
-    pub current_value: f64,
-    #[serde(default)]
-    pub cash_pnl: f64,
-    #[serde(default)]
-    pub percent_pnl: f64,
-    #[serde(default)]
-    pub cur_price: f64,
-    #[serde(default)]
-    pub title: String,
-    #[serde(default)]
-    pub slug: String,
-    #[serde(default)]
-    pub event_slug: String,
-    #[serde(default)]
-    pub outcome: String,
-    #[serde(default)]
-    pub outcome_index: i32,
+    /// Shares added (positive) or removed (negative)
+    pub shares: f64,
+    /// Latest known mark; an exit keeps the previous one
+    pub mark: PositionMark,
+}
+
+/// Holdings added, increased, reduced or exited between two snapshots.
+pub fn diff_positions(previous: &PositionSnapshot, current: &PositionSnapshot) -> Vec<PositionChange> {
+    let mut changes = Vec::new();
+    for (asset, mark) in &current.positions {
+        let before = previous.positions.get(asset).map_or(0.0, |m| m.size);
+        if (mark.size - before).abs() > f64::EPSILON {
+            changes.push(PositionChange { asset: asset.clone(), shares: mark.size - before, mark: mark.clone() });
+        }
+    }
+    for (asset, mark) in &previous.positions {
+        if !current.positions.contains_key(asset) {
+            changes.push(PositionChange { asset: asset.clone(), shares: -mark.size, mark: mark.clone() });
+        }
+    }
+    changes
 }
 
 /// Represents a trade we want to copy
@@ -107,75 +132,141 @@ pub struct CopyTrade {
     pub price: f64,
     pub title: String,
     pub event_slug: String,
+    /// Outcome traded, e.g. "No" on one candidate of an election event
+    pub outcome: String,
+}
+
+impl CopyTrade {
+    /// Market title with the outcome traded.
+    pub fn label(&self) -> String {
+        if self.outcome.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} [{}]", self.title, self.outcome)
+        }
+    }
+}
+
+/// Copies skipped because their scaled size was too small.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SkippedCopies {
+    /// Below `min_copy_notional`
+    pub below_min_notional: u64,
+    /// Below the venue's minimum order once rounded to lots
+    pub below_min_order: u64,
+}
+
+impl SkippedCopies {
+    pub fn total(&self) -> u64 {
+        self.below_min_notional + self.below_min_order
+    }
 }
 
 /// Copy trader that monitors and copies trades
 pub struct CopyTrader {
-    http: Client,
+    data_api: DataApiClient,
     config: CopyTraderConfig,
     /// Track trades we've already processed to avoid duplicates
     processed_trades: HashSet<String>,
+    /// Time source for staleness checks
+    clock: SharedClock,
+    /// File processed trade hashes are persisted to
+    state_file: Option<String>,
+    /// Last position snapshot per trader
+    snapshots: BTreeMap<String, PositionSnapshot>,
+    /// File snapshots are persisted to
+    snapshot_file: Option<String>,
+    /// Signed shares seen in the activity feed since the last snapshot,
+    /// by (trader, asset)
+    feed_shares: HashMap<(String, String), f64>,
+    /// Smoothed size ratio per trader
+    ratios: HashMap<String, f64>,
+    /// Order rules copies are rounded to
+    constraints: VenueConstraints,
+    skipped: SkippedCopies,
 }
 
 impl CopyTrader {
     /// Create a new copy trader.
     pub fn new(config: CopyTraderConfig) -> Self {
         Self {
-            http: Client::builder()
-                .timeout(std::time::Duration::from_secs(30))
-                .build()
-                .expect("Failed to create HTTP client"),
+            data_api: DataApiClient::new(),
             config,
             processed_trades: HashSet::new(),
+            clock: system_clock(),
+            state_file: None,
+            snapshots: BTreeMap::new(),
+            snapshot_file: None,
+            feed_shares: HashMap::new(),
+            ratios: HashMap::new(),
+            constraints: VenueConstraints::polymarket(),
+            skipped: SkippedCopies::default(),
         }
     }
 
-    /// Fetch recent trades for a trader.
-    pub async fn get_trader_activity(&self, address: &str, limit: usize) -> Result<Vec<TradeActivity>> {
-        let url = format!(
-            "https://data-api.polymarket.com/activity?user={}&type=TRADE&limit={}",
-            address, limit
-        );
+    /// Persist processed trade hashes to `path`, restoring any saved there,
+    /// so a restart does not copy the same trades again.
+    pub fn with_state_file(mut self, path: &str) -> Self {
+        self.processed_trades = state::load(path);
+        self.state_file = Some(path.to_string());
+        self
+    }
 
-        debug!("Fetching activity for {}", address);
+    /// Persist position snapshots to `path`, restoring any saved there, so
+    /// changes made while the bot was down are reconciled too.
+    pub fn with_snapshot_file(mut self, path: &str) -> Self {
+        self.snapshots = state::load(path);
+        self.snapshot_file = Some(path.to_string());
+        self
+    }
 
-        let response = self.http.get(&url).send().await
-            .context("Failed to fetch trader activity")?;
+    /// Number of trades already processed.
+    pub fn processed_count(&self) -> usize {
+        self.processed_trades.len()
+    }
 
-        if !response.status().is_success() {
-            warn!("Failed to fetch activity for {}: {}", address, response.status());
-            return Ok(vec![]);
-        }
+    /// Copies skipped for size since start.
+    pub fn skipped(&self) -> SkippedCopies {
+        self.skipped
+    }
+
+    /// Use a specific clock (e.g. a mock clock in tests or replays).
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Whether an activity (timestamp in ms) is too old to copy.
+    pub fn is_stale(&self, activity_timestamp_ms: i64) -> bool {
+        let now = self.clock.now().timestamp_millis();
+        let age_hours = (now - activity_timestamp_ms) as f64 / (1000.0 * 60.0 * 60.0);
+        age_hours > MAX_ACTIVITY_AGE_HOURS
+    }
 
-        let activities: Vec<TradeActivity> = response.json().await
+    /// Use a fixture store for recording or replaying responses.
+    pub fn with_fixtures(mut self, store: Arc<FixtureStore>) -> Self {
+        self.data_api = self.data_api.with_fixtures(store);
+        self
+    }
+
+    /// Fetch recent trades for a trader.
+    pub async fn get_trader_activity(&self, address: &str, limit: usize) -> Result<Vec<TradeActivity>> {
+        debug!("Fetching activity for {}", address);
+        Ok(self.data_api.activity(address, Some("TRADE"), limit).await
             .unwrap_or_else(|e| {
-                warn!("Failed to parse activity: {}", e);
+                warn!("Failed to fetch activity for {}: {}", address, e);
                 vec![]
-            });
-
-        Ok(activities)
+            }))
     }
 
     /// Fetch positions for a trader.
     pub async fn get_trader_positions(&self, address: &str) -> Result<Vec<TraderPosition>> {
-        let url = format!(
-            "https://data-api.polymarket.com/positions?user={}",
-            address
-        );
-
         debug!("Fetching positions for {}", address);
-
-        let response = self.http.get(&url).send().await
-            .context("Failed to fetch trader positions")?;
-
-        if !response.status().is_success() {
-            return Ok(vec![]);
-        }
-
-        let positions: Vec<TraderPosition> = response.json().await
-            .unwrap_or_else(|_| vec![]);
-
-        Ok(positions)
+        Ok(self.data_api.positions(address, MAX_SNAPSHOT_POSITIONS).await
+            .unwrap_or_else(|e| {
+                warn!("Failed to fetch positions for {}: {}", address, e);
+                vec![]
+            }))
     }
 
     /// Calculate trader's total portfolio value.
@@ -193,6 +284,7 @@ impl CopyTrader {
     /// Scan for new trades from target traders.
     pub async fn scan_for_new_trades(&mut self) -> Result<Vec<CopyTrade>> {
         let mut trades_to_copy = Vec::new();
+        let skipped_before = self.skipped.total();
         let our_value = self.get_our_portfolio_value().await.unwrap_or(1000.0);
 
         for trader_address in &self.config.target_traders.clone() {
@@ -204,66 +296,217 @@ impl CopyTrader {
                 .unwrap_or(100000.0);  // Default to 100k if unknown
 
             // Calculate size ratio
-            let size_ratio = our_value / trader_value;
+            let size_ratio = self.smoothed_ratio(trader_address, our_value / trader_value);
             debug!("Size ratio for {}: {} (our: ${}, trader: ${})", 
                    trader_address, size_ratio, our_value, trader_value);
 
-            for activity in activities {
-                // Skip if already processed
-                if self.processed_trades.contains(&activity.transaction_hash) {
-                    continue;
-                }
-
-                // Skip if too small
-                if activity.usdc_size < self.config.min_trade_size {
-                    continue;
-                }
-
-                // Skip if too old (more than 1 hour)
-                let now = chrono::Utc::now().timestamp_millis();
-                let age_hours = (now - activity.timestamp) as f64 / (1000.0 * 60.0 * 60.0);
-                if age_hours > 1.0 {
-                    continue;
-                }
-
-                // Calculate our position size
-                let mut our_size = activity.usdc_size * size_ratio;
-                
-                // Apply max position limit
-                if our_size > self.config.max_position_size {
-                    our_size = self.config.max_position_size;
-                }
-
-                // Mark as processed
-                self.processed_trades.insert(activity.transaction_hash.clone());
-
-                info!(
-                    "📋 New trade to copy from {}: {} {} @ ${:.4} (${:.2} -> ${:.2})",
-                    &trader_address[..8],
-                    activity.side,
-                    activity.outcome,
-                    activity.price,
-                    activity.usdc_size,
-                    our_size
-                );
-
-                trades_to_copy.push(CopyTrade {
-                    trader_address: trader_address.clone(),
-                    condition_id: activity.condition_id,
-                    asset: activity.asset,
-                    side: activity.side,
-                    original_size: activity.usdc_size,
-                    our_size,
-                    price: activity.price,
-                    title: activity.title,
-                    event_slug: activity.event_slug,
-                });
-            }
+            trades_to_copy.extend(self.admit(trader_address, activities, size_ratio));
+        }
+
+        let skipped = self.skipped.total() - skipped_before;
+        if skipped > 0 {
+            info!(
+                "📋 Skipped {} copies too small to place (since start: {} below min notional, {} below min order)",
+                skipped, self.skipped.below_min_notional, self.skipped.below_min_order
+            );
         }
 
         Ok(trades_to_copy)
     }
 
+    /// Copy trades for a trader's feed. Each transaction is handled once:
+    /// it is marked processed and counted against the next snapshot diff
+    /// before the size and age filters, so a skipped one is not counted
+    /// again on every scan.
+    fn admit(&mut self, trader_address: &str, activities: Vec<TradeActivity>, size_ratio: f64) -> Vec<CopyTrade> {
+        let mut trades_to_copy = Vec::new();
+        let mut seen_new = false;
+        for activity in activities {
+            // Skip if already processed
+            if !self.processed_trades.insert(activity.transaction_hash.clone()) {
+                continue;
+            }
+            seen_new = true;
+            self.record_feed_shares(trader_address, &activity);
+
+            // Skip if too small
+            if activity.usdc_size < self.config.min_trade_size {
+                continue;
+            }
+
+            // Skip if too old (more than 1 hour)
+            if self.is_stale(activity.timestamp) {
+                continue;
+            }
+
+            // Calculate our position size
+            let Some(our_size) = self.copy_size(activity.usdc_size * size_ratio, activity.price) else {
+                continue;
+            };
+
+            info!(
+                "📋 New trade to copy from {}: {} {} @ {} (${:.2} -> ${:.2})",
+                &trader_address[..8],
+                activity.side,
+                activity.outcome,
+                display::price(activity.price),
+                activity.usdc_size,
+                our_size
+            );
+
+            trades_to_copy.push(CopyTrade {
+                trader_address: trader_address.to_string(),
+                condition_id: activity.condition_id,
+                asset: activity.asset,
+                side: activity.side,
+                original_size: activity.usdc_size,
+                our_size,
+                price: activity.price,
+                title: activity.title,
+                event_slug: activity.event_slug,
+                outcome: activity.outcome,
+            });
+        }
+        if seen_new {
+            self.save_processed();
+        }
+        trades_to_copy
+    }
+
+    /// Count a newly seen activity against the next snapshot diff, whether
+    /// or not it was copied, so reconciliation only acts on what the feed
+    /// never showed.
+    fn record_feed_shares(&mut self, trader: &str, activity: &TradeActivity) {
+        let taken_at = self.snapshots.get(trader).map_or(i64::MAX, |s| s.taken_at);
+        if activity.timestamp <= taken_at {
+            return;
+        }
+        let shares = if activity.side.eq_ignore_ascii_case("SELL") { -activity.size } else { activity.size };
+        *self.feed_shares.entry((trader.to_string(), activity.asset.clone())).or_default() += shares;
+    }
+
+    /// Snapshot `trader`'s positions and return corrective trades for
+    /// changes since the previous snapshot that the activity feed missed.
+    /// The first snapshot of a trader is only a baseline.
+    pub fn reconcile(&mut self, trader: &str, positions: &[TraderPosition], size_ratio: f64) -> Vec<CopyTrade> {
+        let current = PositionSnapshot::new(self.clock.now().timestamp_millis(), positions);
+        let previous = self.snapshots.insert(trader.to_string(), current.clone());
+        let feed_shares: HashMap<String, f64> = self
+            .feed_shares
+            .iter()
+            .filter(|((t, _), _)| t == trader)
+            .map(|((_, asset), shares)| (asset.clone(), *shares))
+            .collect();
+        self.feed_shares.retain(|(t, _), _| t != trader);
+        let Some(previous) = previous else {
+            self.save_snapshots();
+            return Vec::new();
+        };
+
+        let mut corrections = Vec::new();
+        for change in diff_positions(&previous, &current) {
+            let missed = change.shares - feed_shares.get(&change.asset).copied().unwrap_or(0.0);
+            let usdc_size = missed.abs() * change.mark.price;
+            if usdc_size < self.config.min_trade_size {
+                continue;
+            }
+            let side = if missed > 0.0 { "BUY" } else { "SELL" };
+            let Some(our_size) = self.copy_size(usdc_size * size_ratio, change.mark.price) else {
+                continue;
+            };
+            info!(
+                "🔁 Feed missed {} {} {:.2} shares of {} (${:.2} -> ${:.2})",
+                &trader[..trader.len().min(8)],
+                side,
+                missed.abs(),
+                change.mark.title,
+                usdc_size,
+                our_size
+            );
+            corrections.push(CopyTrade {
+                trader_address: trader.to_string(),
+                condition_id: change.mark.condition_id,
+                asset: change.asset,
+                side: side.to_string(),
+                original_size: usdc_size,
+                our_size,
+                price: change.mark.price,
+                title: change.mark.title,
+                event_slug: change.mark.event_slug,
+                outcome: change.mark.outcome,
+            });
+        }
+        self.save_snapshots();
+        corrections
+    }
+
+    /// Snapshot every target trader and return corrective trades for the
+    /// changes the activity feed missed. Run it less often than the feed
+    /// scan; an empty response is skipped rather than treated as an exit.
+    pub async fn reconcile_positions(&mut self) -> Result<Vec<CopyTrade>> {
+        let our_value = self.get_our_portfolio_value().await.unwrap_or(1000.0);
+        let mut corrections = Vec::new();
+        for trader_address in &self.config.target_traders.clone() {
+            let positions = self.get_trader_positions(trader_address).await?;
+            // A failed fetch also comes back empty; don't mistake it for a full exit
+            if positions.is_empty() && self.snapshots.get(trader_address).is_some_and(|s| !s.positions.is_empty()) {
+                debug!("No positions returned for {}, skipping reconciliation", trader_address);
+                continue;
+            }
+            let trader_value: f64 = positions.iter().map(|p| p.current_value).sum();
+            let trader_value = if trader_value > 0.0 { trader_value } else { 100000.0 };
+            let size_ratio = self.smoothed_ratio(trader_address, our_value / trader_value);
+            corrections.extend(self.reconcile(trader_address, &positions, size_ratio));
+        }
+        Ok(corrections)
+    }
+
+    /// Blend the latest portfolio ratio into `trader`'s running EMA so one
+    /// odd portfolio reading doesn't swing copy sizes.
+    fn smoothed_ratio(&mut self, trader: &str, ratio: f64) -> f64 {
+        let alpha = self.config.ratio_smoothing.clamp(0.0, 1.0);
+        let smoothed = match self.ratios.get(trader) {
+            Some(avg) => avg + alpha * (ratio - avg),
+            None => ratio,
+        };
+        self.ratios.insert(trader.to_string(), smoothed);
+        smoothed
+    }
+
+    /// Our USD size for a scaled copy: capped, rounded down to whole lots
+    /// at `price`, or None (and counted) when too small to place.
+    fn copy_size(&mut self, usd: f64, price: f64) -> Option<f64> {
+        let usd = usd.min(self.config.max_position_size);
+        if usd < self.config.min_copy_notional {
+            self.skipped.below_min_notional += 1;
+            debug!("Skipping copy: ${:.2} below minimum ${:.2}", usd, self.config.min_copy_notional);
+            return None;
+        }
+        let shares = if price > 0.0 { self.constraints.round_size(usd / price) } else { 0.0 };
+        if shares < self.constraints.min_size {
+            self.skipped.below_min_order += 1;
+            debug!("Skipping copy: {:.2} shares below minimum order {}", shares, self.constraints.min_size);
+            return None;
+        }
+        Some(shares * price)
+    }
+
+    fn save_snapshots(&self) {
+        if let Some(ref path) = self.snapshot_file {
+            if let Err(e) = state::save(path, &self.snapshots) {
+                warn!("Failed to persist copy trader position snapshots: {}", e);
+            }
+        }
+    }
+
+    fn save_processed(&self) {
+        if let Some(ref path) = self.state_file {
+            if let Err(e) = state::save(path, &self.processed_trades) {
+                warn!("Failed to persist processed copy trades: {}", e);
+            }
+        }
+    }
+
     /// Get summary of traders being monitored.
     pub async fn get_trader_summaries(&self) -> Vec<(String, f64, usize)> {
         let mut summaries = Vec::new();
@@ -288,6 +531,8 @@ impl Default for CopyTraderConfig {
             ],
             max_position_size: 50.0,  // Start small
             min_trade_size: 5.0,      // Filter dust trades
+            min_copy_notional: 1.0,
+            ratio_smoothing: 0.2,
             our_address: String::new(),
             dry_run: true,
         }
@@ -298,17 +543,125 @@ impl Default for CopyTraderConfig {
 mod tests {
     use super::*;
 
+    use crate::utils::fixtures::FixtureMode;
+
+    fn replay_store() -> Arc<FixtureStore> {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/data_api");
+        Arc::new(FixtureStore::new(dir, FixtureMode::Replay))
+    }
+
     #[tokio::test]
     async fn test_get_trader_activity() {
         let config = CopyTraderConfig {
             target_traders: vec!["0x16b29c50f2439faf627209b2ac0c7bbddaa8a881".to_string()],
             ..Default::default()
         };
-        let trader = CopyTrader::new(config);
+        let trader = CopyTrader::new(config).with_fixtures(replay_store());
         let result = trader.get_trader_activity(
             "0x16b29c50f2439faf627209b2ac0c7bbddaa8a881",
             5
         ).await;
         assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 2);
+    }
+
+    fn position(asset: &str, size: f64, price: f64) -> TraderPosition {
+        serde_json::from_value(serde_json::json!({
+            "asset": asset, "conditionId": format!("cond-{}", asset), "size": size, "curPrice": price, "title": asset,
+        }))
+        .unwrap()
+    }
+
+    fn activity(asset: &str, side: &str, size: f64, timestamp: i64) -> TradeActivity {
+        serde_json::from_value(serde_json::json!({
+            "asset": asset, "side": side, "size": size, "timestamp": timestamp,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reconcile_copies_only_what_the_feed_missed() {
+        use crate::utils::clock::MockClock;
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.timestamp_millis_opt(1767639600000).unwrap();
+        let clock = Arc::new(MockClock::new(start));
+        let config = CopyTraderConfig { max_position_size: 30.0, ..Default::default() };
+        let mut trader = CopyTrader::new(config).with_clock(clock.clone());
+        let addr = "0x16b29c50f2439faf627209b2ac0c7bbddaa8a881";
+
+        // First snapshot is a baseline
+        let base = [position("a", 100.0, 0.5), position("b", 50.0, 0.4), position("c", 10.0, 0.9)];
+        assert!(trader.reconcile(addr, &base, 1.0).is_empty());
+
+        // The feed saw the add to "a" but missed the exit from "b" and the new "d"
+        trader.record_feed_shares(addr, &activity("a", "BUY", 40.0, 1767639600000 + 1));
+        clock.advance(std::time::Duration::from_secs(300));
+        let now = [position("a", 140.0, 0.5), position("c", 12.0, 0.9), position("d", 200.0, 0.25)];
+        let trades = trader.reconcile(addr, &now, 1.0);
+
+        // "c" grew by only $1.80, below the minimum trade size
+        let summary: Vec<_> = trades.iter().map(|t| (t.asset.as_str(), t.side.as_str(), t.our_size)).collect();
+        assert_eq!(summary, vec![("d", "BUY", 30.0), ("b", "SELL", 20.0)]);
+        assert_eq!(trades[1].condition_id, "cond-b");
+
+        // Coverage is reset once the snapshot catches up
+        assert!(trader.reconcile(addr, &now, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_feed_activity_counts_once_per_transaction() {
+        let mut trader = CopyTrader::new(CopyTraderConfig::default());
+        let addr = "0x16b29c50f2439faf627209b2ac0c7bbddaa8a881";
+        // A baseline snapshot, so later activity counts against the next diff
+        trader.snapshots.insert(addr.to_string(), PositionSnapshot::new(0, &[]));
+        let dust = || -> TradeActivity {
+            serde_json::from_value(serde_json::json!({
+                "asset": "a", "side": "BUY", "size": 4.0, "usdcSize": 2.0, "price": 0.5,
+                "timestamp": 1, "transactionHash": "0xdust",
+            }))
+            .unwrap()
+        };
+
+        // Below the minimum trade size: not copied, but counted, and only once
+        assert!(trader.admit(addr, vec![dust()], 1.0).is_empty());
+        assert!(trader.admit(addr, vec![dust(), dust()], 1.0).is_empty());
+        assert_eq!(trader.feed_shares.get(&(addr.to_string(), "a".to_string())), Some(&4.0));
+        assert_eq!(trader.processed_count(), 1);
+    }
+
+    #[test]
+    fn test_copy_sizing_smooths_and_skips_dust() {
+        let config = CopyTraderConfig { min_copy_notional: 2.0, ratio_smoothing: 0.5, ..Default::default() };
+        let mut trader = CopyTrader::new(config);
+        let addr = "0xdb27bf2ac5d428a9c63dbc914611036855a6c56e";
+
+        assert_eq!(trader.smoothed_ratio(addr, 0.01), 0.01);
+        assert!((trader.smoothed_ratio(addr, 0.03) - 0.02).abs() < 1e-12);
+
+        // $10.01 at 0.37 -> 27.05 shares
+        assert!((trader.copy_size(10.01, 0.37).unwrap() - 27.05 * 0.37).abs() < 1e-9);
+        // Capped at max_position_size
+        assert!((trader.copy_size(500.0, 0.5).unwrap() - 50.0).abs() < 1e-9);
+        // A whale's $90 trade scales to $1.80
+        assert_eq!(trader.copy_size(1.8, 0.5), None);
+        // $2.10 at 0.60 is 3.5 shares, under the 5 share minimum
+        assert_eq!(trader.copy_size(2.1, 0.6), None);
+        assert_eq!(trader.skipped(), SkippedCopies { below_min_notional: 1, below_min_order: 1 });
+        assert_eq!(trader.skipped().total(), 2);
+    }
+
+    #[test]
+    fn test_staleness_uses_injected_clock() {
+        use crate::utils::clock::MockClock;
+        use chrono::TimeZone;
+
+        let start = chrono::Utc.timestamp_millis_opt(1767639600000).unwrap();
+        let clock = Arc::new(MockClock::new(start));
+        let trader = CopyTrader::new(CopyTraderConfig::default()).with_clock(clock.clone());
+
+        assert!(!trader.is_stale(1767639600000));
+        clock.advance(std::time::Duration::from_secs(61 * 60));
+        assert!(trader.is_stale(1767639600000));
     }
 }
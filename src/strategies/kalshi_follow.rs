@@ -0,0 +1,187 @@
+//! Kalshi follow strategy.
+//!
+//! Mirrors the fills of a second Kalshi account (e.g. a discretionary one)
+//! into ours, using its `/portfolio/fills` as the signal, so the manual and
+//! algorithmic books hold the same markets. The followed account's
+//! watermark is persisted so a restart neither misses nor repeats fills.
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+
+use crate::config::Config;
+use crate::kalshi::fill_stream::{self, Watermark};
+use crate::kalshi::types::{KalshiFill, KalshiOrderRequest};
+use crate::kalshi::KalshiClient;
+use crate::utils::state;
+
+/// Default watermark location for the followed account.
+pub const KALSHI_FOLLOW_FILE: &str = "data/state/kalshi_follow.json";
+
+/// Name the strategy is scheduled and allocated under.
+pub const STRATEGY: &str = "kalshi_follow";
+
+/// A followed fill to repeat on our account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowTrade {
+    pub trade_id: String,
+    pub ticker: String,
+    /// "yes" or "no"
+    pub side: String,
+    /// "buy" or "sell"
+    pub action: String,
+    /// Price of the traded side (0.0-1.0)
+    pub price: f64,
+    /// Contracts the followed account traded
+    pub leader_count: i32,
+    /// Contracts we trade
+    pub count: i32,
+}
+
+impl FollowTrade {
+    /// Limit order at the followed account's price.
+    pub fn order(&self) -> KalshiOrderRequest {
+        limit_order(&self.ticker, &self.side, &self.action, self.price, self.count)
+    }
+}
+
+/// Limit order for `count` contracts of `side` at `price` (0.0-1.0) of
+/// that side. Kalshi takes the limit in YES cents.
+pub fn limit_order(ticker: &str, side: &str, action: &str, price: f64, count: i32) -> KalshiOrderRequest {
+    let cents = (price * 100.0).round() as i32;
+    let yes_cents = if side.eq_ignore_ascii_case("no") { 100 - cents } else { cents };
+    KalshiOrderRequest {
+        ticker: ticker.to_string(),
+        side: side.to_string(),
+        action: action.to_string(),
+        count,
+        yes_price: Some(yes_cents),
+        order_type: "limit".to_string(),
+    }
+}
+
+/// Turns a followed account's fills into our trades.
+pub struct KalshiFollower {
+    scale: f64,
+    max_contracts: i32,
+    watermark: Watermark,
+    file_path: Option<String>,
+}
+
+impl KalshiFollower {
+    /// Follow from now, trading `scale` contracts per followed contract,
+    /// at most `max_contracts` per fill.
+    pub fn new(scale: f64, max_contracts: i32) -> Self {
+        Self {
+            scale,
+            max_contracts,
+            watermark: Watermark::now(),
+            file_path: None,
+        }
+    }
+
+    /// Resume from the watermark saved at `path`.
+    pub fn with_state_file(mut self, path: &str) -> Self {
+        self.watermark = Watermark::load(path);
+        self.file_path = Some(path.to_string());
+        self
+    }
+
+    /// Client for the followed account, if KALSHI_FOLLOW_API_KEY is set.
+    pub fn leader_client(config: &Config) -> Result<Option<KalshiClient>> {
+        let Some(key) = &config.kalshi_follow_api_key else {
+            return Ok(None);
+        };
+        Ok(Some(KalshiClient::new(config)?.with_api_key(key, config.kalshi_follow_api_secret.as_deref())?))
+    }
+
+    /// New fills to mirror, oldest first, scaled and capped. Fills that
+    /// round to zero contracts are skipped.
+    pub fn mirror(&mut self, fills: &[KalshiFill]) -> Vec<FollowTrade> {
+        let mut trades = Vec::new();
+        for (fill, converted) in fill_stream::oldest_first(fills) {
+            if !self.watermark.admit(&converted) {
+                continue;
+            }
+            let count = ((fill.count as f64 * self.scale).round() as i32).min(self.max_contracts);
+            if count < 1 {
+                debug!("Skipping followed fill {}: scales to no contracts", fill.trade_id);
+                continue;
+            }
+            trades.push(FollowTrade {
+                trade_id: fill.trade_id.clone(),
+                ticker: fill.ticker.clone(),
+                side: fill.side.to_lowercase(),
+                action: fill.action.to_lowercase(),
+                price: fill.price(),
+                leader_count: fill.count,
+                count,
+            });
+        }
+        trades
+    }
+
+    /// Fetch every page of the followed account's fills since the
+    /// watermark, then mirror them.
+    pub async fn poll(&mut self, leader: &KalshiClient) -> Result<Vec<FollowTrade>> {
+        let fills = fill_stream::fetch_since(leader, self.watermark.since).await?;
+        let trades = self.mirror(&fills);
+        if !trades.is_empty() {
+            info!("👥 {} new fills on the followed Kalshi account", trades.len());
+            self.save();
+        }
+        Ok(trades)
+    }
+
+    fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.watermark) {
+                warn!("Failed to persist Kalshi follow watermark: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(trade_id: &str, side: &str, count: i32, time: &str) -> KalshiFill {
+        KalshiFill {
+            trade_id: trade_id.to_string(),
+            order_id: "leader-ord".to_string(),
+            ticker: "KXBTCD-26JAN05-T100000".to_string(),
+            side: side.to_string(),
+            action: "buy".to_string(),
+            count,
+            yes_price: 58,
+            no_price: 42,
+            is_taker: false,
+            created_time: time.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mirrors_scaled_fills_once() {
+        let mut follower = KalshiFollower::new(0.5, 8);
+        follower.watermark.since = 0;
+        // Newest first, as Kalshi lists them
+        let fills = [
+            fill("t3", "yes", 1, "2026-01-05T15:00:02Z"),
+            fill("t2", "yes", 40, "2026-01-05T15:00:01Z"),
+            fill("t1", "no", 10, "2026-01-05T15:00:00Z"),
+        ];
+
+        let trades = follower.mirror(&fills);
+        // 10 -> 5, 40 -> 20 capped at 8, 1 -> 0.5 rounds to 1
+        assert_eq!(trades.iter().map(|t| t.count).collect::<Vec<_>>(), vec![5, 8, 1]);
+        assert!((trades[0].price - 0.42).abs() < 1e-9);
+
+        // A NO limit is sent in YES cents
+        let order = trades[0].order();
+        assert_eq!((order.side.as_str(), order.yes_price, order.count), ("no", Some(58), 5));
+        assert_eq!(order.side_price(), Some(0.42));
+
+        // Re-polled fills are not mirrored again
+        assert!(follower.mirror(&fills).is_empty());
+    }
+}
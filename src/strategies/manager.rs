@@ -0,0 +1,209 @@
+//! Strategy manager.
+//!
+//! Tracks which strategies are enabled and enforces their schedules, so a
+//! strategy can be switched off by hand or be outside its trading window.
+//! A strategy in shadow mode keeps producing signals, but its orders are
+//! filled virtually instead of being sent to the venues. Each strategy can
+//! also override the global DRY_RUN flag, e.g. arbitrage live while copy
+//! trading stays on paper.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+use super::schedule::Schedule;
+use crate::config::Config;
+use crate::utils::timezone;
+
+/// Whether a strategy may trade right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrategyState {
+    Active,
+    /// Switched off by the user
+    Disabled,
+    /// Enabled, but outside its schedule
+    ScheduledOff,
+}
+
+impl StrategyState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StrategyState::Active => "ON",
+            StrategyState::Disabled => "OFF",
+            StrategyState::ScheduledOff => "SCHEDULED OFF",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    enabled: bool,
+    /// None runs 24/7
+    schedule: Option<Schedule>,
+    /// Orders are filled virtually
+    shadow: bool,
+    /// Overrides the default dry-run mode
+    dry_run: Option<bool>,
+}
+
+/// Enabled flags and schedules per strategy, keyed by strategy name
+/// (as recorded on trades, e.g. "arbitrage").
+#[derive(Debug, Clone)]
+pub struct StrategyManager {
+    strategies: BTreeMap<String, Entry>,
+    timezone: Tz,
+    /// Dry-run mode of strategies without an override
+    dry_run: bool,
+}
+
+impl StrategyManager {
+    /// Create a manager evaluating schedules in `timezone`. Strategies
+    /// dry-run until told otherwise.
+    pub fn new(timezone: Tz) -> Self {
+        Self {
+            strategies: BTreeMap::new(),
+            timezone,
+            dry_run: true,
+        }
+    }
+
+    /// Build from STRATEGY_SCHEDULES / SCHEDULE_TIMEZONE, SHADOW_STRATEGIES
+    /// and DRY_RUN / STRATEGY_DRY_RUN.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let timezone = timezone::parse(&config.schedule_timezone).context("Invalid SCHEDULE_TIMEZONE")?;
+        let mut manager = Self::new(timezone);
+        for (name, expr) in &config.strategy_schedules {
+            let schedule = Schedule::parse(expr)
+                .with_context(|| format!("Invalid schedule for {}", name))?;
+            manager = manager.with_schedule(name, schedule);
+        }
+        for name in &config.shadow_strategies {
+            manager = manager.with_shadow(name);
+        }
+        manager = manager.with_default_dry_run(config.dry_run);
+        for (name, dry_run) in &config.strategy_dry_run {
+            manager = manager.with_dry_run(name, *dry_run);
+        }
+        Ok(manager)
+    }
+
+    /// Restrict a strategy to a schedule.
+    pub fn with_schedule(mut self, name: &str, schedule: Schedule) -> Self {
+        self.entry(name).schedule = Some(schedule);
+        self
+    }
+
+    /// Run a strategy in shadow mode.
+    pub fn with_shadow(mut self, name: &str) -> Self {
+        self.entry(name).shadow = true;
+        self
+    }
+
+    /// Dry-run or trade live strategies without an override.
+    pub fn with_default_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Dry-run or trade live one strategy, whatever the default.
+    pub fn with_dry_run(mut self, name: &str, dry_run: bool) -> Self {
+        self.entry(name).dry_run = Some(dry_run);
+        self
+    }
+
+    fn entry(&mut self, name: &str) -> &mut Entry {
+        self.strategies
+            .entry(name.to_string())
+            .or_insert(Entry { enabled: true, schedule: None, shadow: false, dry_run: None })
+    }
+
+    /// Enable or disable a strategy.
+    pub fn set_enabled(&mut self, name: &str, enabled: bool) {
+        self.entry(name).enabled = enabled;
+    }
+
+    /// The strategy's schedule, if it has one.
+    pub fn schedule(&self, name: &str) -> Option<&Schedule> {
+        self.strategies.get(name)?.schedule.as_ref()
+    }
+
+    /// Whether a strategy's orders are filled virtually.
+    pub fn is_shadow(&self, name: &str) -> bool {
+        self.strategies.get(name).is_some_and(|e| e.shadow)
+    }
+
+    /// Strategies in shadow mode, by name.
+    pub fn shadowed(&self) -> Vec<&str> {
+        self.strategies.iter().filter(|(_, e)| e.shadow).map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Whether a strategy's orders are only logged, not sent.
+    pub fn is_dry_run(&self, name: &str) -> bool {
+        self.strategies.get(name).and_then(|e| e.dry_run).unwrap_or(self.dry_run)
+    }
+
+    /// Whether any strategy trades live.
+    pub fn any_live(&self) -> bool {
+        !self.dry_run || self.strategies.values().any(|e| e.dry_run == Some(false))
+    }
+
+    /// Strategies whose dry-run mode differs from the default, by name.
+    pub fn dry_run_overrides(&self) -> Vec<(&str, bool)> {
+        self.strategies
+            .iter()
+            .filter_map(|(name, e)| e.dry_run.filter(|d| *d != self.dry_run).map(|d| (name.as_str(), d)))
+            .collect()
+    }
+
+    /// State of a strategy at `now`. Unknown strategies are active.
+    pub fn state(&self, name: &str, now: DateTime<Utc>) -> StrategyState {
+        let Some(entry) = self.strategies.get(name) else {
+            return StrategyState::Active;
+        };
+        if !entry.enabled {
+            return StrategyState::Disabled;
+        }
+        match &entry.schedule {
+            Some(schedule) if !schedule.is_on(&now.with_timezone(&self.timezone)) => {
+                StrategyState::ScheduledOff
+            }
+            _ => StrategyState::Active,
+        }
+    }
+
+    /// Whether a strategy may trade at `now`.
+    pub fn is_active(&self, name: &str, now: DateTime<Utc>) -> bool {
+        self.state(name, now) == StrategyState::Active
+    }
+}
+
+impl Default for StrategyManager {
+    fn default() -> Self {
+        Self::new(timezone::DEFAULT_TIMEZONE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dry_run_per_strategy() {
+        let manager = StrategyManager::default();
+        assert!(manager.is_dry_run("arbitrage") && !manager.any_live());
+
+        let manager = StrategyManager::default()
+            .with_dry_run("arbitrage", false)
+            .with_dry_run("copy_trade", true);
+        assert!(!manager.is_dry_run("arbitrage"));
+        assert!(manager.is_dry_run("copy_trade") && manager.is_dry_run("kalshi_follow"));
+        assert!(manager.any_live());
+        assert_eq!(manager.dry_run_overrides(), [("arbitrage", false)]);
+
+        // Live by default, with copy trading kept on paper
+        let manager = StrategyManager::default().with_default_dry_run(false).with_dry_run("copy_trade", true);
+        assert!(!manager.is_dry_run("kalshi_follow") && manager.is_dry_run("copy_trade"));
+        assert_eq!(manager.dry_run_overrides(), [("copy_trade", true)]);
+    }
+}
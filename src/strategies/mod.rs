@@ -1,5 +1,11 @@
 //! Trading strategies module.
 
 pub mod copy_trader;
+pub mod kalshi_follow;
+pub mod manager;
+pub mod schedule;
 
-pub use copy_trader::{CopyTrader, CopyTraderConfig, CopyTrade};
+pub use copy_trader::{CopyTrader, CopyTraderConfig, CopyTrade, PositionSnapshot, SkippedCopies, POSITION_SNAPSHOTS_FILE};
+pub use kalshi_follow::{FollowTrade, KalshiFollower};
+pub use manager::{StrategyManager, StrategyState};
+pub use schedule::Schedule;
@@ -0,0 +1,146 @@
+//! Cron-like strategy schedules.
+//!
+//! Standard five-field expressions (`minute hour day-of-month month
+//! day-of-week`) evaluated in a configured time zone. Fields accept `*`,
+//! values, ranges, lists and steps (`9-20`, `0,30`, `*/15`), and day names
+//! (`mon-fri`). As in cron, when both day fields are restricted a time
+//! matches if either one does.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Allowed values of one field as a bitset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+    bits: u64,
+    /// Written as `*` (matters for the day-of-month/day-of-week rule)
+    any: bool,
+}
+
+impl Field {
+    fn parse(spec: &str, min: u32, max: u32, names: &[&str]) -> Result<Self> {
+        let mut bits = 0u64;
+        for item in spec.split(',') {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, step.parse::<u32>().context("Invalid step")?),
+                None => (item, 1),
+            };
+            if step == 0 {
+                anyhow::bail!("Step must be positive in '{}'", item);
+            }
+            let (start, end) = if range == "*" {
+                (min, max)
+            } else if let Some((a, b)) = range.split_once('-') {
+                (value(a, names)?, value(b, names)?)
+            } else {
+                let v = value(range, names)?;
+                // "5/15" means from 5 to the end in steps of 15
+                (v, if item.contains('/') { max } else { v })
+            };
+            if start < min || end > max || start > end {
+                anyhow::bail!("'{}' is outside {}-{}", item, min, max);
+            }
+            for v in (start..=end).step_by(step as usize) {
+                bits |= 1 << v;
+            }
+        }
+        Ok(Self { bits, any: spec == "*" })
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        self.bits & (1 << value) != 0
+    }
+}
+
+fn value(token: &str, names: &[&str]) -> Result<u32> {
+    let lower = token.to_lowercase();
+    if let Some(i) = names.iter().position(|n| *n == lower) {
+        return Ok(i as u32);
+    }
+    token.parse().with_context(|| format!("Invalid schedule value '{}'", token))
+}
+
+/// A parsed schedule expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    expr: String,
+    minute: Field,
+    hour: Field,
+    day_of_month: Field,
+    month: Field,
+    day_of_week: Field,
+}
+
+impl Schedule {
+    /// Parse a five-field cron expression.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            anyhow::bail!("Schedule '{}' must have 5 fields (minute hour day month weekday)", expr);
+        };
+
+        let mut day_of_week = Field::parse(dow, 0, 7, &DAY_NAMES)
+            .with_context(|| format!("Invalid weekday field in '{}'", expr))?;
+        // 7 is Sunday too
+        if day_of_week.contains(7) {
+            day_of_week.bits |= 1;
+        }
+
+        Ok(Self {
+            expr: expr.to_string(),
+            minute: Field::parse(minute, 0, 59, &[]).with_context(|| format!("Invalid minute field in '{}'", expr))?,
+            hour: Field::parse(hour, 0, 23, &[]).with_context(|| format!("Invalid hour field in '{}'", expr))?,
+            day_of_month: Field::parse(dom, 1, 31, &[]).with_context(|| format!("Invalid day field in '{}'", expr))?,
+            month: Field::parse(month, 1, 12, &[]).with_context(|| format!("Invalid month field in '{}'", expr))?,
+            day_of_week,
+        })
+    }
+
+    /// The expression as written.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Whether the schedule is on at `time` (in `time`'s own time zone).
+    pub fn is_on<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> bool {
+        let dom = self.day_of_month.contains(time.day());
+        let dow = self.day_of_week.contains(time.weekday().num_days_from_sunday());
+        let day = match (self.day_of_month.any, self.day_of_week.any) {
+            (false, false) => dom || dow,
+            _ => dom && dow,
+        };
+        day && self.minute.contains(time.minute())
+            && self.hour.contains(time.hour())
+            && self.month.contains(time.month())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use chrono_tz::America::New_York;
+
+    #[test]
+    fn test_business_hours_in_eastern_time() {
+        let schedule = Schedule::parse("* 9-20 * * mon-fri").unwrap();
+        // Monday 2026-01-05 14:30 UTC = 09:30 ET
+        let open = Utc.with_ymd_and_hms(2026, 1, 5, 14, 30, 0).unwrap().with_timezone(&New_York);
+        assert!(schedule.is_on(&open));
+        // 02:00 UTC Tuesday = 21:00 ET Monday
+        let late = Utc.with_ymd_and_hms(2026, 1, 6, 2, 0, 0).unwrap().with_timezone(&New_York);
+        assert!(!schedule.is_on(&late));
+        // Saturday
+        let weekend = Utc.with_ymd_and_hms(2026, 1, 10, 15, 0, 0).unwrap().with_timezone(&New_York);
+        assert!(!schedule.is_on(&weekend));
+
+        let quarter = Schedule::parse("*/15 * * * *").unwrap();
+        assert!(quarter.is_on(&Utc.with_ymd_and_hms(2026, 1, 5, 3, 45, 0).unwrap()));
+        assert!(!quarter.is_on(&Utc.with_ymd_and_hms(2026, 1, 5, 3, 46, 0).unwrap()));
+
+        assert!(Schedule::parse("* 9-25 * * *").is_err());
+        assert!(Schedule::parse("* 9-20 * *").is_err());
+    }
+}
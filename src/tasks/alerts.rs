@@ -0,0 +1,119 @@
+//! Alerts and reports sent out on their own schedule: market moves, the
+//! daily report, throttled orders and redelivery of what failed to send.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+use polymarket_kalshi_arbitrage_bot::monitor::balances::BALANCES_FILE;
+use polymarket_kalshi_arbitrage_bot::monitor::Balances;
+use polymarket_kalshi_arbitrage_bot::notify::report::{self, next_report_at};
+use polymarket_kalshi_arbitrage_bot::notify::{Alert, Severity, Webhook, WebhookEvent};
+use polymarket_kalshi_arbitrage_bot::paper_trading::PaperTradingEngine;
+use polymarket_kalshi_arbitrage_bot::utils::throttle;
+
+use super::Shared;
+
+/// How often queued notifications are checked for redelivery.
+const OUTBOX_INTERVAL: Duration = Duration::from_secs(15);
+/// How often refused orders are reported.
+const THROTTLE_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Log market moves and pass them on to the webhook and routed channels.
+pub async fn market_moves(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, notifier, moves, .. } = shared;
+    let webhook = Webhook::from_config(&config);
+    let notifier = notifier.is_routed("market_move").then_some(notifier);
+    let mut receiver = moves.subscribe();
+
+    // The detector outlives this task, so only the breaker ends it
+    loop {
+        let received = tokio::select! {
+            received = receiver.recv() => received,
+            _ = breaker.tripped() => break,
+        };
+        match received {
+            Ok(market_move) => {
+                info!("🌊 Market moving: {}", market_move.summary());
+                if let Some(webhook) = &webhook {
+                    webhook.emit(WebhookEvent::market_move(&market_move));
+                }
+                if let Some(notifier) = &notifier {
+                    notifier.notify(Alert::new(
+                        Severity::Info,
+                        "market_move",
+                        &format!("🌊 {} moving", market_move.market),
+                        &market_move.summary(),
+                    )).await;
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => warn!("Missed {} market moves", skipped),
+            Err(RecvError::Closed) => break,
+        }
+    }
+    Ok(())
+}
+
+/// Daily summary at `hour`, local time.
+pub async fn daily_report(shared: Shared, hour: u32) -> Result<()> {
+    let Shared { config, breaker, notifier, profile, .. } = shared;
+    let tz = config.timezone;
+
+    while breaker.is_allowed() {
+        let now = chrono::Utc::now();
+        let wait = (next_report_at(now, hour, tz) - now).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = breaker.tripped() => break,
+        }
+        let engine = PaperTradingEngine::snapshot_of(&profile);
+        let report = report::daily_report(
+            &engine.portfolio,
+            engine.trade_log.get_all(),
+            &Balances::load(BALANCES_FILE),
+            &breaker.state(),
+            chrono::Utc::now(),
+            tz,
+        );
+        notifier.notify(report).await;
+    }
+    Ok(())
+}
+
+/// Report orders and cancels refused by the rate caps.
+pub async fn throttle(shared: Shared) -> Result<()> {
+    let Shared { breaker, notifier, .. } = shared;
+
+    while breaker.is_allowed() {
+        tokio::time::sleep(THROTTLE_REPORT_INTERVAL).await;
+        let refused = throttle::global().take_unreported();
+        if refused.is_empty() {
+            continue;
+        }
+        let totals: HashMap<String, u64> = throttle::global().refused().into_iter().collect();
+        let lines: Vec<String> = refused
+            .iter()
+            .map(|(what, n)| format!("{} {}(s) refused ({} since start)", n, what, totals.get(what).unwrap_or(n)))
+            .collect();
+        notifier.notify(Alert::new(Severity::Warning, "throttle", "🚦 Orders throttled", &lines.join("\n"))).await;
+    }
+    Ok(())
+}
+
+/// Redeliver alerts and webhook events that failed to send, including ones
+/// queued before a restart.
+pub async fn outbox(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, notifier, .. } = shared;
+    let webhook = Webhook::from_config(&config);
+
+    while breaker.is_allowed() {
+        notifier.retry_pending().await;
+        if let Some(webhook) = &webhook {
+            webhook.retry_pending().await;
+        }
+        tokio::time::sleep(OUTBOX_INTERVAL).await;
+    }
+    Ok(())
+}
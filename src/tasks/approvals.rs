@@ -0,0 +1,106 @@
+//! Approvals in semi-auto mode: intents expire or go to the execution
+//! queue, and Telegram carries them to the operator along with commands.
+
+use anyhow::Result;
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use polymarket_kalshi_arbitrage_bot::approval::{IntentStatus, TelegramApprover};
+use polymarket_kalshi_arbitrage_bot::control::Command;
+use polymarket_kalshi_arbitrage_bot::utils::permissions::{Action, Permissions};
+
+use super::Shared;
+
+/// Expire order intents, queueing approved ones.
+pub async fn intents(shared: Shared) -> Result<()> {
+    let Shared { breaker, intents, execution, .. } = shared;
+
+    while breaker.is_allowed() {
+        let expired = intents.lock().unwrap().expire(chrono::Utc::now());
+        for intent in expired {
+            info!("⌛ Intent {} expired: {}", intent.id, intent.kind.summary());
+        }
+
+        let approved = intents.lock().unwrap().take_approved();
+        for intent in approved {
+            let (_, superseded) = execution.submit(intent.kind, Some(intent.id.clone()), intent.created_at);
+            let mut queue = intents.lock().unwrap();
+            for old in superseded.iter().filter_map(|o| o.intent_id.as_deref()) {
+                queue.finish(old, IntentStatus::Superseded, Some(format!("superseded by intent {}", intent.id)));
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    Ok(())
+}
+
+/// Telegram: intents sent for approval, button presses and operator commands.
+pub async fn telegram(shared: Shared) -> Result<()> {
+    // Only semi-auto mode has intents to approve
+    let intents = shared.approvals();
+    let Shared { config, breaker, manager, controls, .. } = shared;
+
+    let Some(mut telegram) = TelegramApprover::from_config(&config) else {
+        return Ok(());
+    };
+    let mut announced = HashSet::new();
+
+    while breaker.is_allowed() {
+        if let Some(intents) = &intents {
+            let pending: Vec<_> = intents.lock().unwrap().pending().into_iter().cloned().collect();
+            announced.retain(|id| pending.iter().any(|i| &i.id == id));
+            for intent in &pending {
+                if announced.contains(&intent.id) {
+                    continue;
+                }
+                match telegram.send_intent(intent).await {
+                    Ok(()) => {
+                        announced.insert(intent.id.clone());
+                    }
+                    Err(e) => warn!("Failed to send intent {} to telegram: {}", intent.id, e),
+                }
+            }
+        }
+
+        match telegram.poll().await {
+            Ok(polled) => {
+                if let Some(intents) = &intents {
+                    let mut queue = intents.lock().unwrap();
+                    for (d, role) in polled.decisions {
+                        // Approving an intent of a live strategy places real orders
+                        let live = queue.all().iter()
+                            .find(|i| i.id == d.intent_id)
+                            .is_some_and(|i| !manager.is_dry_run(i.kind.strategy()));
+                        let action = if d.approve && live { Action::GoLive } else { Action::Trade };
+                        if !Permissions::new(role).allows(action) {
+                            warn!("Ignoring telegram decision on {}: a {} may not approve live intents", d.intent_id, role);
+                            continue;
+                        }
+                        if let Err(e) = queue.decide(&d.intent_id, d.approve, "telegram", chrono::Utc::now()) {
+                            warn!("{}", e);
+                        }
+                    }
+                }
+                for message in polled.messages {
+                    let by = format!("telegram:{}", message.user_id.map_or_else(|| format!("chat {}", message.chat_id), |id| id.to_string()));
+                    info!("💬 Telegram command from {}: {}", by, message.text);
+                    let reply = match Command::parse(&message.text) {
+                        Ok(command) => controls
+                            .execute(&command, &by, &Permissions::new(message.role))
+                            .unwrap_or_else(|e| format!("❌ {}", e)),
+                        Err(reply) => reply,
+                    };
+                    if let Err(e) = telegram.reply(message.chat_id, &reply).await {
+                        warn!("Failed to answer telegram command: {}", e);
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to poll telegram: {}", e),
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    Ok(())
+}
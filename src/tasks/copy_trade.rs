@@ -0,0 +1,95 @@
+//! Copy trading: the trades of followed Polymarket traders, scanned and
+//! copied at our size.
+
+use anyhow::Result;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use polymarket_kalshi_arbitrage_bot::approval::IntentKind;
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::Depth;
+use polymarket_kalshi_arbitrage_bot::paper_trading::ShadowBook;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::strategies::copy_trader::{CopyTrader, CopyTraderConfig, POSITION_SNAPSHOTS_FILE, PROCESSED_TRADES_FILE};
+
+use super::{Shared, MAX_CONSECUTIVE_ERRORS};
+
+/// Copy trader feed scans between reconciliations of their positions.
+const COPY_RECONCILE_EVERY: u32 = 10;
+
+/// Copy the trades of followed Polymarket traders.
+pub async fn run(shared: Shared) -> Result<()> {
+    // In semi-auto mode copied trades become intents awaiting approval
+    let intents = shared.approvals();
+    let Shared { config, app, breaker, manager, fees, execution, .. } = shared;
+
+    let poly = PolymarketClient::new(&config)?;
+    let our_address = match poly.maker_address() {
+        Some(address) => address,
+        None => config.polymarket_funder.clone(),
+    };
+    let mut shadow = if manager.is_shadow("copy_trade") {
+        Some(ShadowBook::for_strategy("copy_trade", fees, config.paper_balance)?)
+    } else {
+        None
+    };
+    let mut copier = CopyTrader::new(CopyTraderConfig {
+        target_traders: config.copy_traders.clone(),
+        max_position_size: app.get(|c| c.max_position_size),
+        our_address,
+        dry_run: manager.is_dry_run("copy_trade"),
+        ..CopyTraderConfig::default()
+    })
+    .with_state_file(PROCESSED_TRADES_FILE)
+    .with_snapshot_file(POSITION_SNAPSHOTS_FILE);
+    let mut consecutive_errors = 0;
+    let mut scans = 0u32;
+    while breaker.is_allowed() {
+        let mut result = copier.scan_for_new_trades().await;
+        scans += 1;
+        if scans.is_multiple_of(COPY_RECONCILE_EVERY) {
+            if let Ok(trades) = result.as_mut() {
+                match copier.reconcile_positions().await {
+                    Ok(corrections) => trades.extend(corrections),
+                    Err(e) => warn!("Copy trade reconciliation failed: {}", e),
+                }
+            }
+        }
+        match result {
+            Ok(trades) => {
+                consecutive_errors = 0;
+                // Trades outside the schedule are skipped, not queued
+                if !trades.is_empty() && !manager.is_active("copy_trade", chrono::Utc::now()) {
+                    info!("⏸️  Copy trading scheduled off, skipping {} trades", trades.len());
+                } else if let Some(shadow) = shadow.as_mut() {
+                    for trade in &trades {
+                        // Fill against the live book of the token traded
+                        let book = poly.get_orderbook(&trade.asset).await.ok().map(|b| Depth::from_polymarket(&b));
+                        match shadow.fill_copy(trade, book.as_ref()) {
+                            Ok(_) => info!("🕶️  Shadow copy: {} ${:.2} of {}", trade.side, trade.our_size, trade.label()),
+                            Err(e) => debug!("Shadow copy of {} not filled: {}", trade.label(), e),
+                        }
+                    }
+                } else if let Some(intents) = &intents {
+                    let intent_ttl = chrono::Duration::seconds(app.get(|c| c.intent_ttl_secs));
+                    let mut queue = intents.lock().unwrap();
+                    for trade in &trades {
+                        queue.submit(IntentKind::copy_trade(trade), intent_ttl, chrono::Utc::now());
+                    }
+                } else {
+                    for trade in &trades {
+                        execution.submit(IntentKind::copy_trade(trade), None, chrono::Utc::now());
+                    }
+                }
+            }
+            Err(e) => {
+                consecutive_errors += 1;
+                if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                    anyhow::bail!("{} consecutive copy trade scan failures: {}", consecutive_errors, e);
+                }
+                warn!("Copy trade scan failed: {}", e);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.copy_poll_secs))).await;
+    }
+    Ok(())
+}
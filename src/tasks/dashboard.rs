@@ -0,0 +1,13 @@
+//! The phone-friendly status page.
+
+use anyhow::Result;
+use std::sync::Arc;
+
+use polymarket_kalshi_arbitrage_bot::dashboard::Dashboard;
+
+use super::Shared;
+
+/// Serve `dashboard` on `addr` until the breaker trips.
+pub async fn run(shared: Shared, dashboard: Arc<Dashboard>, addr: String) -> Result<()> {
+    dashboard.serve(&addr, &shared.breaker).await
+}
@@ -0,0 +1,228 @@
+//! Arbitrage detection: a pass over every matched pair, its opportunities
+//! recorded, alerted on and, per the strategy's mode, filled in shadow or
+//! queued as intents.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use polymarket_kalshi_arbitrage_bot::approval::IntentKind;
+use polymarket_kalshi_arbitrage_bot::arbitrage::mutes::{MuteList, MUTES_FILE};
+use polymarket_kalshi_arbitrage_bot::arbitrage::quality::QUALITY_FILE;
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::notify::{Alert, Severity, Webhook, WebhookEvent};
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::{load_book, BOOKS_FILE};
+use polymarket_kalshi_arbitrage_bot::paper_trading::ShadowBook;
+use polymarket_kalshi_arbitrage_bot::utils::{state, Sizing};
+
+use super::{Shared, MAX_CONSECUTIVE_ERRORS};
+
+/// How soon the detector runs again after a pass in which markets moved.
+const MOVING_PASS_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Detect opportunities every few seconds, sooner while markets are moving.
+pub async fn run(shared: Shared) -> Result<()> {
+    // In semi-auto mode detections become intents awaiting approval
+    let intents = shared.approvals();
+    let Shared {
+        config, app, breaker, notifier, manager, fees, detector, moves, heatmap, capture, attribution, cooldowns,
+        discrepancies, derisker, controls, dashboard, ..
+    } = shared;
+    let webhook = Webhook::from_config(&config);
+    let alerts = notifier.clone();
+    let notifier = notifier.is_routed("opportunity").then_some(notifier);
+    let mutes = MuteList::new(MUTES_FILE);
+    let paper_balance = config.paper_balance;
+    let mut moving = moves.subscribe();
+
+    // In shadow mode detections fill virtually instead of queueing intents
+    let mut shadow = if manager.is_shadow("arbitrage") {
+        Some(ShadowBook::for_strategy("arbitrage", fees, paper_balance)?.with_capture(capture))
+    } else {
+        None
+    };
+    let mut consecutive_errors = 0;
+    let mut scheduled_off = false;
+    let mut paused = false;
+    let mut blocked = BTreeSet::new();
+    let mut muted = BTreeSet::new();
+    let mut venues_down = BTreeSet::new();
+    while breaker.is_allowed() {
+        // Sit out passes while arbitrage is outside its schedule
+        let active = manager.is_active("arbitrage", chrono::Utc::now());
+        if active == scheduled_off {
+            scheduled_off = !active;
+            if scheduled_off {
+                info!("⏸️  Arbitrage scheduled off");
+            } else {
+                info!("▶️  Arbitrage scheduled on");
+            }
+        }
+        if scheduled_off {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            continue;
+        }
+        // And while an operator has trading paused
+        if controls.is_paused() != paused {
+            paused = !paused;
+            if paused {
+                info!("⏸️  Arbitrage paused by operator");
+            } else {
+                info!("▶️  Arbitrage resumed by operator");
+            }
+        }
+        if paused {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            continue;
+        }
+
+        let result = detector.check_all_opportunities().await;
+        state::save(QUALITY_FILE, &detector.quality_metrics())?;
+        // With a venue down the other is still watched on its own
+        let outages = detector.venue_outages();
+        let now_down: BTreeSet<String> = outages.iter().map(|o| o.venue.clone()).collect();
+        if now_down != venues_down {
+            for outage in outages.iter().filter(|o| !venues_down.contains(&o.venue)) {
+                alerts.notify(Alert::new(
+                    Severity::Warning,
+                    "venue",
+                    &format!("📴 {} unreachable", outage.venue),
+                    &format!(
+                        "Cross-venue detection suspended; still monitoring the other venue on its own. Last error: {}",
+                        outage.last_error.as_deref().unwrap_or("unknown")
+                    ),
+                )).await;
+            }
+            for venue in venues_down.difference(&now_down) {
+                alerts.notify(Alert::new(
+                    Severity::Info,
+                    "venue",
+                    &format!("📶 {} reachable again", venue),
+                    "Cross-venue detection resumed",
+                )).await;
+            }
+            venues_down = now_down;
+        }
+        match result {
+            Ok(mut found) => {
+                consecutive_errors = 0;
+                // Families with an unacknowledged discrepancy sit out
+                let matches = detector.matched_markets();
+                let now_blocked = discrepancies.blocked_families(&matches);
+                if now_blocked != blocked {
+                    blocked = now_blocked;
+                    if !blocked.is_empty() {
+                        let families: Vec<&str> = blocked.iter().map(String::as_str).collect();
+                        warn!("🚨 Trading paused on {} until discrepancies are acknowledged", families.join(", "));
+                    }
+                }
+                found.retain(|opp| !matches.iter().any(|m| m.name == opp.market && blocked.contains(&m.family())));
+                // Muted pairs stay matched but are never traded
+                let now_muted = mutes.muted();
+                if now_muted != muted {
+                    muted = now_muted;
+                    if !muted.is_empty() {
+                        let markets: Vec<&str> = muted.iter().map(String::as_str).collect();
+                        info!("🔇 Muted: {}", markets.join(", "));
+                    }
+                }
+                found.retain(|opp| !muted.contains(&opp.market));
+                // Recently traded markets wait for their books to settle
+                let cooldown = chrono::Duration::seconds(app.get(|c| c.market_cooldown_secs) as i64);
+                found.retain(|opp| match cooldowns.remaining(&opp.market, cooldown) {
+                    Some(left) => {
+                        debug!("🧊 {} cooling down for {}s more", opp.market, left.num_seconds());
+                        false
+                    }
+                    None => true,
+                });
+                if !found.is_empty() {
+                    let mut heatmap = heatmap.lock().unwrap();
+                    for opp in &found {
+                        heatmap.record(&opp.market, opp.detected_at, opp.edge());
+                    }
+                    heatmap.save();
+                    let mut attribution = attribution.lock().unwrap();
+                    for opp in &found {
+                        attribution.record_detection(opp);
+                    }
+                    attribution.save();
+                }
+                if let Some(webhook) = &webhook {
+                    for opp in &found {
+                        webhook.emit(WebhookEvent::opportunity(opp));
+                    }
+                }
+                if let Some(dashboard) = &dashboard {
+                    for opp in &found {
+                        dashboard.record_opportunity(opp);
+                    }
+                }
+                if let Some(notifier) = &notifier {
+                    let silenced = mutes.silenced(chrono::Utc::now());
+                    for opp in found.iter().filter(|opp| !silenced.contains(&opp.market)) {
+                        notifier.notify(Alert::new(
+                            Severity::Info,
+                            "opportunity",
+                            &format!("🔥 Arbitrage on {}", opp.market),
+                            &format!(
+                                "Buy YES on {} @ {} + NO on {} @ {}: {} net of fees",
+                                opp.yes_venue,
+                                display::price(opp.yes_price),
+                                opp.no_venue,
+                                display::price(opp.no_price),
+                                display::percent(opp.net_edge())
+                            ),
+                        )).await;
+                    }
+                }
+                // Sizes shrink while the derisker has them cut
+                let max_position_size = app.get(|c| c.max_position_size);
+                let max = derisker
+                    .as_ref()
+                    .map_or(max_position_size, |d| d.lock().unwrap().max_size(max_position_size));
+                let sizing = Sizing::Usd(max);
+                if let Some(shadow) = shadow.as_mut() {
+                    let now = chrono::Utc::now();
+                    for opp in &found {
+                        let book = load_book(BOOKS_FILE, &opp.market, now);
+                        match shadow.fill_arb(opp, sizing, book.as_ref().map(|b| &b.depth)) {
+                            Ok(order) => {
+                                cooldowns.executed(&opp.market);
+                                info!(
+                                    "🕶️  Shadow arb on {}: {} x YES @ {} + NO @ {}",
+                                    opp.market,
+                                    order.size,
+                                    display::price(order.price_a),
+                                    display::price(order.price_b)
+                                )
+                            }
+                            Err(e) => debug!("Shadow arb on {} not filled: {}", opp.market, e),
+                        }
+                    }
+                } else if let Some(intents) = &intents {
+                    let intent_ttl = chrono::Duration::seconds(app.get(|c| c.intent_ttl_secs));
+                    let mut queue = intents.lock().unwrap();
+                    for opp in &found {
+                        let contracts = sizing.contracts(opp.cost()).floor();
+                        queue.submit(IntentKind::arbitrage(opp, contracts), intent_ttl, chrono::Utc::now());
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("Error in detection pass: {}", e);
+                consecutive_errors += 1;
+                if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                    anyhow::bail!("{} consecutive detection failures: {}", consecutive_errors, e);
+                }
+            }
+        }
+
+        // Wait before next pass, less long while markets are moving
+        let moved = !moving.is_empty();
+        moving = moving.resubscribe();
+        tokio::time::sleep(if moved { MOVING_PASS_INTERVAL } else { Duration::from_secs(10) }).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Order execution: queued orders are checked once more (pause,
+//! discrepancies, cooldown, a fresh edge and the pre-trade limits) and
+//! placed, highest priority first and a few at a time per venue.
+
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use polymarket_kalshi_arbitrage_bot::analysis::attribution::PairExecution;
+use polymarket_kalshi_arbitrage_bot::approval::{IntentKind, IntentStatus, QueuedOrder};
+use polymarket_kalshi_arbitrage_bot::arbitrage::{Opportunity, TradeExecutor};
+use polymarket_kalshi_arbitrage_bot::display;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::polymarket::types::{Order, OrderType, Side};
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::strategies::kalshi_follow;
+use polymarket_kalshi_arbitrage_bot::utils::{Sizing, VenueConstraints};
+
+use super::Shared;
+
+/// Place queued orders until the breaker trips.
+pub async fn run(shared: Shared) -> Result<()> {
+    // What happened to each approved intent is written back to it
+    let intents = shared.approvals();
+    let Shared {
+        config, app, breaker, manager, fees, detector, fills, open_orders, capture, attribution, executions, cooldowns,
+        discrepancies, pretrade, execution, controls, ..
+    } = shared;
+
+    // Each strategy's orders go out in its own dry-run mode
+    let arb_dry_run = manager.is_dry_run("arbitrage");
+    let copy_dry_run = manager.is_dry_run("copy_trade");
+    let follow_dry_run = manager.is_dry_run(kalshi_follow::STRATEGY);
+    let executor = Arc::new(
+        TradeExecutor::new(
+            PolymarketClient::new(&config)?.with_dry_run(arb_dry_run),
+            KalshiClient::new(&config)?.with_dry_run(arb_dry_run),
+            arb_dry_run,
+        )
+        .with_fills(fills)
+        .with_fees(fees.clone())
+        .with_capture(capture),
+    );
+    let poly = Arc::new(PolymarketClient::new(&config)?.with_dry_run(copy_dry_run));
+    let kalshi = Arc::new(KalshiClient::new(&config)?.with_open_orders(open_orders.clone()).with_dry_run(follow_dry_run));
+    let revalidate_fraction = config.revalidate_edge_fraction;
+
+    let handler = move |order: QueuedOrder| {
+        let (executor, poly, kalshi) = (executor.clone(), poly.clone(), kalshi.clone());
+        let (detector, discrepancies, cooldowns) = (detector.clone(), discrepancies.clone(), cooldowns.clone());
+        let (attribution, executions, pretrade) = (attribution.clone(), executions.clone(), pretrade.clone());
+        let (fees, app, controls) = (fees.clone(), app.clone(), controls.clone());
+        let intents = intents.clone();
+        async move {
+            let label = order.intent_id.clone().unwrap_or_else(|| format!("#{}", order.id));
+            let intent_id = order.intent_id.clone();
+            let finish = |status: IntentStatus, outcome: Option<String>| {
+                if let (Some(intents), Some(id)) = (&intents, &intent_id) {
+                    intents.lock().unwrap().finish(id, status, outcome);
+                }
+            };
+            let skip = |reason: String| finish(IntentStatus::Skipped, Some(reason));
+            if controls.is_paused() {
+                warn!("⏸️  Not executing {}: trading is paused", label);
+                skip("trading is paused".to_string());
+                return;
+            }
+            if let IntentKind::Arbitrage { market, .. } = &order.kind {
+                let matches = detector.matched_markets();
+                let blocked = discrepancies.blocked_families(&matches);
+                if matches.iter().any(|m| &m.name == market && blocked.contains(&m.family())) {
+                    warn!("🚨 Not executing {}: {} has an unacknowledged discrepancy", label, market);
+                    skip(format!("{} has an unacknowledged discrepancy", market));
+                    return;
+                }
+                let cooldown = chrono::Duration::seconds(app.get(|c| c.market_cooldown_secs) as i64);
+                if let Some(left) = cooldowns.remaining(market, cooldown) {
+                    warn!("🧊 Not executing {}: {} was just traded ({}s of cooldown left)", label, market, left.num_seconds());
+                    skip(format!("{} is cooling down", market));
+                    return;
+                }
+            }
+            // The edge may have gone while the order waited for approval or a slot
+            if let IntentKind::Arbitrage { market, yes_venue, yes_price, no_venue, no_price, .. } = &order.kind {
+                if revalidate_fraction > 0.0 {
+                    let detected = Opportunity::priced(
+                        market, (yes_venue, *yes_price), (no_venue, *no_price), &fees, order.created_at,
+                    );
+                    let fresh = match detected {
+                        Ok(detected) => detector.revalidate(&detected, revalidate_fraction).await,
+                        Err(e) => Err(e),
+                    };
+                    match fresh {
+                        Ok(Some(_)) => {}
+                        Ok(None) => {
+                            warn!("💨 Not executing {}: the edge on {} evaporated", label, market);
+                            skip("the edge evaporated".to_string());
+                            return;
+                        }
+                        Err(e) => {
+                            warn!("Not executing {}: could not re-check {}: {}", label, market, e);
+                            skip(format!("could not re-check the edge: {}", e));
+                            return;
+                        }
+                    }
+                }
+            }
+            let kind = match pretrade.check(&order.kind, &detector.matched_markets()) {
+                Ok(kind) => kind,
+                Err(e) => {
+                    warn!("🛑 Not executing {}: {}", label, e);
+                    skip(e.to_string());
+                    return;
+                }
+            };
+            info!("🚀 Executing {}: {}", label, kind.summary());
+            let result = match kind {
+                IntentKind::Arbitrage { market, yes_venue, yes_price, no_venue, no_price, contracts } => {
+                    let result = executor
+                        .execute_arb(&market, &yes_venue, &no_venue, yes_price, no_price, Sizing::Contracts(contracts))
+                        .await;
+                    let mut attribution = attribution.lock().unwrap();
+                    match &result {
+                        Ok(paired) => {
+                            let now = chrono::Utc::now();
+                            cooldowns.executed(&market);
+                            match Opportunity::priced(&market, (&yes_venue, yes_price), (&no_venue, no_price), &fees, order.created_at) {
+                                Ok(detected) => {
+                                    executor.record_capture(&detected, paired);
+                                }
+                                Err(e) => warn!("Failed to capture {}: {}", label, e),
+                            }
+                            let fee = fees.arb_fee(&market, (&yes_venue, paired.price_a), (&no_venue, paired.price_b), paired.size);
+                            attribution.record_execution(&market, PairExecution::new(executor.mode(), (&yes_venue, &no_venue), paired, fee, now));
+                        }
+                        Err(e) => attribution.record_failure(&market, &e.to_string()),
+                    }
+                    result.map(|_| ())
+                }
+                IntentKind::CopyTrade { asset, title, side, price, size_usd, .. } => {
+                    let side = if side.eq_ignore_ascii_case("sell") { Side::Sell } else { Side::Buy };
+                    // Snapped to the grid up front so the submitted price is the one recorded
+                    match VenueConstraints::polymarket().normalize(price, size_usd / price, side) {
+                        Ok((limit, size)) => {
+                            let order = Order { token_id: asset, side, price: limit, size, order_type: OrderType::Gtc };
+                            poly.place_order(order.clone()).await.map(|response| {
+                                if let Some(avg) = response.avg_fill_price() {
+                                    info!(
+                                        "📋 Copy order matched {:.2} of {:.2} shares at {} on placement",
+                                        response.filled_size(), order.size, display::price(avg)
+                                    );
+                                }
+                                if let Some(id) = response.order_id.filter(|_| response.success && !copy_dry_run) {
+                                    executions.lock().unwrap().record_polymarket(&id, &order, &title, "copy_trade", price);
+                                }
+                            })
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                IntentKind::KalshiFollow { ticker, side, action, price, count, .. } => {
+                    let order = kalshi_follow::limit_order(&ticker, &side, &action, price, count);
+                    kalshi.place_order(order.clone()).await.map(|response| {
+                        if let Some(id) = response.order_id.filter(|_| !follow_dry_run) {
+                            executions.lock().unwrap().record_kalshi(&id, &order, kalshi_follow::STRATEGY, price);
+                        }
+                    })
+                }
+            };
+            match result {
+                Ok(()) => finish(IntentStatus::Executed, None),
+                Err(e) => {
+                    warn!("Order {} failed: {}", label, e);
+                    finish(IntentStatus::Failed, Some(e.to_string()));
+                }
+            }
+        }
+    };
+    execution.run(handler, move || breaker.is_allowed()).await;
+    Ok(())
+}
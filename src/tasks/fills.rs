@@ -0,0 +1,79 @@
+//! Our own fills: the Polymarket user channel and the Kalshi fill poll feed
+//! them to the fill router, which the outbound webhook follows.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use polymarket_kalshi_arbitrage_bot::kalshi::fill_stream::KALSHI_FILLS_FILE;
+use polymarket_kalshi_arbitrage_bot::kalshi::{FillPoller, KalshiClient};
+use polymarket_kalshi_arbitrage_bot::monitor::system::{self, StreamState};
+use polymarket_kalshi_arbitrage_bot::notify::{Webhook, WebhookEvent};
+use polymarket_kalshi_arbitrage_bot::polymarket::{user_stream, UserStream};
+
+use super::{Shared, MAX_CONSECUTIVE_ERRORS};
+
+/// Our Polymarket order updates and fills, pushed in real time. Dropped
+/// connections are routine, so they reconnect here rather than counting as
+/// crashes against the breaker.
+pub async fn poly_user_stream(shared: Shared, stream: Arc<UserStream>) -> Result<()> {
+    let Shared { breaker, fills, .. } = shared;
+
+    while breaker.is_allowed() {
+        if let Err(e) = stream.run(&fills).await {
+            warn!("Polymarket user stream dropped: {} - reconnecting", e);
+            system::set_stream(user_stream::STREAM, StreamState::Disconnected, Some(e.to_string()));
+        }
+        // A replay has nothing to reconnect to
+        if stream.is_replay() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+    Ok(())
+}
+
+/// Publish our fills to the outbound webhook.
+pub async fn webhook_fills(shared: Shared, webhook: Arc<Webhook>) -> Result<()> {
+    let Shared { breaker, fills, .. } = shared;
+    let mut receiver = fills.subscribe();
+
+    // The router outlives this task, so only the breaker ends it
+    loop {
+        let received = tokio::select! {
+            received = receiver.recv() => received,
+            _ = breaker.tripped() => break,
+        };
+        match received {
+            Ok(fill) => webhook.emit(WebhookEvent::fill(&fill)),
+            Err(RecvError::Lagged(skipped)) => warn!("Webhook missed {} fills", skipped),
+            Err(RecvError::Closed) => break,
+        }
+    }
+    Ok(())
+}
+
+/// Our Kalshi fills, polled tightly from a persisted watermark.
+pub async fn kalshi_fills(shared: Shared) -> Result<()> {
+    let Shared { config, app, breaker, fills, .. } = shared;
+
+    let kalshi = KalshiClient::new(&config)?;
+    let mut poller = FillPoller::load(KALSHI_FILLS_FILE);
+    let mut consecutive_errors = 0;
+    while breaker.is_allowed() {
+        match poller.poll(&kalshi, &fills).await {
+            Ok(_) => consecutive_errors = 0,
+            Err(e) => {
+                consecutive_errors += 1;
+                if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                    anyhow::bail!("{} consecutive Kalshi fill poll failures: {}", consecutive_errors, e);
+                }
+                warn!("Kalshi fill poll failed: {}", e);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.kalshi_fill_poll_secs))).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+//! Housekeeping: config reloads, RPC health, the FX cache and the process
+//! health and pool metrics the TUI's System tab reads.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use polymarket_kalshi_arbitrage_bot::app_state::{ConfigWatcher, ENV_FILE};
+use polymarket_kalshi_arbitrage_bot::chain::RpcPool;
+use polymarket_kalshi_arbitrage_bot::money::{FxRates, FX_RATES_FILE};
+use polymarket_kalshi_arbitrage_bot::monitor::system::{SystemStatus, SYSTEM_FILE};
+use polymarket_kalshi_arbitrage_bot::notify::router::Router;
+use polymarket_kalshi_arbitrage_bot::utils::http_pool::{self, HTTP_POOL_FILE};
+use polymarket_kalshi_arbitrage_bot::utils::state;
+use polymarket_kalshi_arbitrage_bot::utils::supervisor::TaskStats;
+
+use super::Shared;
+
+/// How often the config file is checked for edits.
+const CONFIG_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+/// How often the Polygon RPCs are health checked.
+const RPC_HEALTH_INTERVAL: Duration = Duration::from_secs(30);
+/// How often process health is written out.
+const SYSTEM_INTERVAL: Duration = Duration::from_secs(5);
+/// How often HTTP pool metrics are written out.
+const HTTP_POOL_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the FX cache is checked for staleness.
+const FX_CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Apply edits of the config file that are safe to take while running.
+pub async fn config_reload(shared: Shared) -> Result<()> {
+    let Shared { app, breaker, notifier, detector, .. } = shared;
+
+    let mut watcher = ConfigWatcher::new(ENV_FILE);
+    while breaker.is_allowed() {
+        match watcher.poll(&app) {
+            Ok(Some(reload)) => {
+                for (change, reason) in &reload.refused {
+                    warn!("🚫 {} changed in {} but was not applied: {}; restart to apply", change.key, ENV_FILE, reason);
+                }
+                for change in &reload.applied {
+                    info!("🔧 Config reloaded: {}", change);
+                }
+                if !reload.applied.is_empty() {
+                    let config = app.config();
+                    detector.set_min_profit(config.min_profit_threshold);
+                    if reload.applied.iter().any(|c| c.key.starts_with("NOTIFY_")) {
+                        notifier.set_router(Router::from_config(&config)?);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("⚠️  Config edit rejected, keeping the running settings: {:#}", e),
+        }
+        tokio::time::sleep(CONFIG_RELOAD_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Score the Polygon RPCs so chain calls prefer the healthiest.
+pub async fn rpc_health(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, .. } = shared;
+
+    let pool = RpcPool::from_config(&config)?;
+    while breaker.is_allowed() {
+        pool.check_health().await;
+        tokio::time::sleep(RPC_HEALTH_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Publish process health, with the supervisor's `tasks`, for the TUI's
+/// System tab.
+pub async fn system(
+    shared: Shared,
+    tasks: Arc<Mutex<HashMap<String, TaskStats>>>,
+    started_at: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let Shared { breaker, notifier, detector, fills, open_orders, intents, .. } = shared;
+
+    while breaker.is_allowed() {
+        let queues = BTreeMap::from([
+            ("intents".to_string(), intents.lock().unwrap().pending().len()),
+            ("fills".to_string(), fills.backlog()),
+            ("open_orders".to_string(), open_orders.lock().unwrap().len()),
+            ("outbox".to_string(), notifier.pending()),
+        ]);
+        let tasks = tasks.lock().unwrap().clone();
+        let status = SystemStatus::capture(started_at, &tasks, queues).with_venue_outages(detector.venue_outages());
+        state::save(SYSTEM_FILE, &status)?;
+        tokio::time::sleep(SYSTEM_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Publish connection pool metrics.
+pub async fn http_pool(shared: Shared) -> Result<()> {
+    let Shared { breaker, .. } = shared;
+
+    while breaker.is_allowed() {
+        tokio::time::sleep(HTTP_POOL_INTERVAL).await;
+        let metrics = http_pool::metrics();
+        debug!("🔌 HTTP pool: {} requests, {} beyond the idle pool", metrics.requests(), metrics.over_pool());
+        state::save(HTTP_POOL_FILE, &metrics)?;
+    }
+    Ok(())
+}
+
+/// Keep the FX cache used by the TUI and reports fresh.
+pub async fn fx_rates(shared: Shared) -> Result<()> {
+    let Shared { breaker, .. } = shared;
+
+    let client = http_pool::client();
+    while breaker.is_allowed() {
+        if let Err(e) = FxRates::refresh(&client, FX_RATES_FILE).await {
+            warn!("FX rate refresh failed: {}", e);
+        }
+        tokio::time::sleep(FX_CHECK_INTERVAL).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! Kalshi follow: a second Kalshi account's fills mirrored into ours.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use polymarket_kalshi_arbitrage_bot::approval::IntentKind;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::Depth;
+use polymarket_kalshi_arbitrage_bot::paper_trading::ShadowBook;
+use polymarket_kalshi_arbitrage_bot::strategies::kalshi_follow::{self, KALSHI_FOLLOW_FILE};
+use polymarket_kalshi_arbitrage_bot::strategies::KalshiFollower;
+
+use super::{Shared, MAX_CONSECUTIVE_ERRORS};
+
+/// Mirror the fills of the `leader` account into ours.
+pub async fn run(shared: Shared, leader: Arc<KalshiClient>) -> Result<()> {
+    // In semi-auto mode followed fills become intents awaiting approval
+    let intents = shared.approvals();
+    let Shared { config, app, breaker, manager, fees, open_orders, execution, .. } = shared;
+
+    let dry_run = manager.is_dry_run(kalshi_follow::STRATEGY);
+    let kalshi = KalshiClient::new(&config)?.with_open_orders(open_orders).with_dry_run(dry_run);
+    let mut shadow = if manager.is_shadow(kalshi_follow::STRATEGY) {
+        Some(ShadowBook::for_strategy(kalshi_follow::STRATEGY, fees, config.paper_balance)?)
+    } else {
+        None
+    };
+    let mut follower = KalshiFollower::new(config.kalshi_follow_scale, config.kalshi_follow_max_contracts)
+        .with_state_file(KALSHI_FOLLOW_FILE);
+    let mut consecutive_errors = 0;
+    while breaker.is_allowed() {
+        match follower.poll(&leader).await {
+            Ok(trades) => {
+                consecutive_errors = 0;
+                // Fills outside the schedule are skipped, not queued
+                if !trades.is_empty() && !manager.is_active(kalshi_follow::STRATEGY, chrono::Utc::now()) {
+                    info!("⏸️  Kalshi follow scheduled off, skipping {} fills", trades.len());
+                } else if let Some(shadow) = shadow.as_mut() {
+                    for trade in &trades {
+                        // Fill against the live book of the side traded
+                        let book = kalshi.get_orderbook(&trade.ticker).await.ok().map(|b| Depth::from_kalshi(&b, &trade.side));
+                        match shadow.fill_follow(trade, book.as_ref()) {
+                            Ok(_) => info!(
+                                "🕶️  Shadow follow: {} {} x {} {}",
+                                trade.action, trade.count, trade.ticker, trade.side
+                            ),
+                            Err(e) => debug!("Shadow follow of {} not filled: {}", trade.trade_id, e),
+                        }
+                    }
+                } else if let Some(intents) = &intents {
+                    let intent_ttl = chrono::Duration::seconds(app.get(|c| c.intent_ttl_secs));
+                    let mut queue = intents.lock().unwrap();
+                    for trade in &trades {
+                        queue.submit(IntentKind::kalshi_follow(trade), intent_ttl, chrono::Utc::now());
+                    }
+                } else {
+                    for trade in &trades {
+                        execution.submit(IntentKind::kalshi_follow(trade), None, chrono::Utc::now());
+                    }
+                }
+            }
+            Err(e) => {
+                consecutive_errors += 1;
+                if consecutive_errors >= MAX_CONSECUTIVE_ERRORS {
+                    anyhow::bail!("{} consecutive Kalshi follow poll failures: {}", consecutive_errors, e);
+                }
+                warn!("Kalshi follow poll failed: {}", e);
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.kalshi_fill_poll_secs))).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+//! Market data kept fresh on the side: the Kalshi series universe and the
+//! trader leaderboard, the strike ladders of traded events, and Polymarket
+//! lagging Kalshi moves.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use polymarket_kalshi_arbitrage_bot::analysis::ladder::{self, LadderSnapshot, StrikeLadder, LADDERS_FILE};
+use polymarket_kalshi_arbitrage_bot::analysis::leaderboard_history::{LeaderboardHistory, LEADERBOARD_HISTORY_FILE};
+use polymarket_kalshi_arbitrage_bot::arbitrage::lag::{self, LAG_WINDOW_MINUTES};
+use polymarket_kalshi_arbitrage_bot::arbitrage::market_matcher::MATCHED_MARKETS_FILE;
+use polymarket_kalshi_arbitrage_bot::arbitrage::{LagWatch, SeriesUniverse};
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::monitor::marker::mid_price;
+use polymarket_kalshi_arbitrage_bot::notify::{Alert, Severity, Webhook, WebhookEvent};
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::{load_books, BOOKS_FILE};
+use polymarket_kalshi_arbitrage_bot::polymarket::data_api::{DataApiClient, LEADERBOARD_FILE};
+use polymarket_kalshi_arbitrage_bot::polymarket::discovery::OUTCOME_EVENTS_FILE;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::utils::{state, Scheduler};
+
+use super::Shared;
+
+/// How often the strike ladders of traded Kalshi events are read.
+const LADDER_INTERVAL: Duration = Duration::from_secs(60);
+/// How often matched Kalshi tickers are checked for Polymarket lagging.
+const LAG_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+/// Traders kept from each leaderboard refresh.
+const LEADERBOARD_SIZE: usize = 50;
+
+/// Periodic refreshes on their own jittered intervals, backing off on
+/// failure: the universe from configured Kalshi series and the data-api
+/// leaderboard.
+pub async fn refresh(shared: Shared) -> Result<()> {
+    let Shared { config, app, breaker, detector, .. } = shared;
+    let refresh_universe = !config.kalshi_series.is_empty();
+    let refresh_leaderboard = config.leaderboard_refresh_secs > 0;
+
+    let universe = SeriesUniverse::new(config.kalshi_series.clone());
+    let kalshi = KalshiClient::new(&config)?;
+    let poly = PolymarketClient::new(&config)?;
+    let data_api = DataApiClient::new();
+    let history = LeaderboardHistory::new(LEADERBOARD_HISTORY_FILE);
+    let now = std::time::Instant::now();
+    let mut scheduler = Scheduler::new(config.refresh_jitter);
+    if refresh_universe {
+        scheduler = scheduler.with_job("universe", Duration::from_secs(config.universe_refresh_secs), now);
+    }
+    if refresh_leaderboard {
+        scheduler = scheduler.with_job("leaderboard", Duration::from_secs(config.leaderboard_refresh_secs), now);
+    }
+    while breaker.is_allowed() {
+        // Intervals are re-read each pass, so config reloads apply
+        scheduler.set_interval("universe", Duration::from_secs(app.get(|c| c.universe_refresh_secs)));
+        scheduler.set_interval("leaderboard", Duration::from_secs(app.get(|c| c.leaderboard_refresh_secs).max(1)));
+        for job in scheduler.due(std::time::Instant::now()) {
+            let result = match job.as_str() {
+                "universe" => universe.discover(&kalshi, &poly).await
+                    .map(|(pairs, events)| {
+                        detector.update_matcher(|m| universe.apply(m, pairs));
+                        events
+                    })
+                    .and_then(|events| {
+                        state::save(MATCHED_MARKETS_FILE, &detector.matched_markets())?;
+                        let events: Vec<_> = events.into_iter().filter(|e| e.is_multi_outcome()).collect();
+                        state::save(OUTCOME_EVENTS_FILE, &events)
+                    }),
+                "leaderboard" => data_api.leaderboard("monthly", LEADERBOARD_SIZE).await
+                    .and_then(|entries| {
+                        state::save(LEADERBOARD_FILE, &entries)?;
+                        let gap = app.get(|c| c.leaderboard_snapshot_secs);
+                        if gap > 0 && history.record_if_due("monthly", &entries, gap, chrono::Utc::now())? {
+                            debug!("Recorded leaderboard snapshot of {} traders", entries.len());
+                        }
+                        Ok(())
+                    }),
+                _ => Ok(()),
+            };
+            let delay = scheduler.finish(&job, &result, std::time::Instant::now());
+            if let Err(e) = result {
+                let (failures, _) = scheduler.failures(&job);
+                warn!("Refreshing {} failed ({} in a row), retrying in {:?}: {}", job, failures, delay, e);
+            }
+        }
+        let wake = scheduler.next_due().unwrap_or_else(|| std::time::Instant::now() + Duration::from_secs(60));
+        tokio::time::sleep_until(wake.into()).await;
+    }
+    Ok(())
+}
+
+/// Read the strike ladder of every Kalshi event being traded and price the
+/// Polymarket strikes on it for the TUI.
+pub async fn ladders(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, detector, .. } = shared;
+
+    let kalshi = KalshiClient::new(&config)?;
+    while breaker.is_allowed() {
+        let matches = detector.matched_markets();
+        let events: BTreeSet<&str> = matches.iter().map(|m| ladder::event_ticker(&m.kalshi.native)).collect();
+        let books = load_books(BOOKS_FILE);
+        let poly_mid = |name: &str| books.get(name).and_then(|b| mid_price(b.depth.best_bid(), b.depth.best_ask()));
+        let mut snapshots = Vec::new();
+        for event in events {
+            match StrikeLadder::fetch(&kalshi, event).await {
+                Ok(ladder) if !ladder.rungs.is_empty() => {
+                    snapshots.push(LadderSnapshot::new(ladder, &matches, poly_mid, chrono::Utc::now()));
+                }
+                Ok(_) => debug!("Kalshi event {} has no strike ladder", event),
+                Err(e) => warn!("Failed to fetch ladder for {}: {}", event, e),
+            }
+        }
+        state::save(LADDERS_FILE, &snapshots)?;
+        tokio::time::sleep(LADDER_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Price each matched pair from its recent Kalshi candles and flag
+/// Polymarket lagging a Kalshi move.
+pub async fn lag_watch(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, notifier, detector, .. } = shared;
+    let webhook = Webhook::from_config(&config);
+    let notifier = notifier.is_routed("lag").then_some(notifier);
+
+    let kalshi = KalshiClient::new(&config)?;
+    let mut watch = LagWatch::new(config.lag_threshold);
+    while breaker.is_allowed() {
+        let books = load_books(BOOKS_FILE);
+        let end = chrono::Utc::now().timestamp();
+        // One extra minute so a full window survives a candle still forming
+        let start = end - (LAG_WINDOW_MINUTES + 1) * 60;
+        for matched in detector.matched_markets() {
+            let Some(poly_mid) = books
+                .get(&matched.name)
+                .and_then(|b| mid_price(b.depth.best_bid(), b.depth.best_ask()))
+            else {
+                continue;
+            };
+            let ticker = &matched.kalshi.native;
+            let candles = match kalshi.get_candlesticks(ticker, start, end, 1).await {
+                Ok(candles) => candles,
+                Err(e) => {
+                    warn!("Failed to fetch candles for {}: {}", ticker, e);
+                    continue;
+                }
+            };
+            let Some(fair) = lag::fair_value(&candles) else {
+                continue;
+            };
+            let Some(signal) = watch.check(&matched.name, ticker, &fair, poly_mid) else {
+                continue;
+            };
+            info!("⏱️ Polymarket lagging: {}", signal.summary());
+            if let Some(webhook) = &webhook {
+                webhook.emit(WebhookEvent::lag(&signal));
+            }
+            if let Some(notifier) = &notifier {
+                notifier.notify(Alert::new(
+                    Severity::Info,
+                    "lag",
+                    &format!("⏱️ {} lagging Kalshi", signal.market),
+                    &signal.summary(),
+                )).await;
+            }
+        }
+        tokio::time::sleep(LAG_CHECK_INTERVAL).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,208 @@
+//! The bot's long-running tasks.
+//!
+//! Each task is an `async fn` taking the [`Shared`] state and runs until the
+//! circuit breaker trips. [`spawn_all`] starts the ones the config asks for
+//! under the [`Supervisor`], which restarts a crashed task with a fresh clone
+//! of the state.
+
+use anyhow::Result;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tracing::info;
+
+use polymarket_kalshi_arbitrage_bot::analysis::attribution::Attribution;
+use polymarket_kalshi_arbitrage_bot::analysis::capture::CaptureLog;
+use polymarket_kalshi_arbitrage_bot::analysis::execution::ExecutionLog;
+use polymarket_kalshi_arbitrage_bot::analysis::heatmap::Heatmap;
+use polymarket_kalshi_arbitrage_bot::app_state::AppState;
+use polymarket_kalshi_arbitrage_bot::approval::{ExecutionQueue, IntentQueue};
+use polymarket_kalshi_arbitrage_bot::arbitrage::cooldown::Cooldowns;
+use polymarket_kalshi_arbitrage_bot::arbitrage::{ArbitrageDetector, MoveDetector};
+use polymarket_kalshi_arbitrage_bot::config::Config;
+use polymarket_kalshi_arbitrage_bot::control::Controls;
+use polymarket_kalshi_arbitrage_bot::dashboard::Dashboard;
+use polymarket_kalshi_arbitrage_bot::fees::FeeSchedule;
+use polymarket_kalshi_arbitrage_bot::monitor::Watchlist;
+use polymarket_kalshi_arbitrage_bot::notify::{Notifier, Webhook};
+use polymarket_kalshi_arbitrage_bot::paper_trading::{Profile, Session};
+use polymarket_kalshi_arbitrage_bot::polymarket::UserStream;
+use polymarket_kalshi_arbitrage_bot::risk::derisk::Derisker;
+use polymarket_kalshi_arbitrage_bot::risk::discrepancies::DiscrepancyLedger;
+use polymarket_kalshi_arbitrage_bot::risk::{CapitalAllocator, PreTradeRisk, RebalanceRule};
+use polymarket_kalshi_arbitrage_bot::strategies::{KalshiFollower, StrategyManager};
+use polymarket_kalshi_arbitrage_bot::utils::{CircuitBreaker, FillRouter, OpenOrders, RestartPolicy, Supervisor};
+
+mod alerts;
+mod approvals;
+mod copy_trade;
+mod dashboard;
+mod detector;
+mod execution;
+mod fills;
+mod housekeeping;
+mod kalshi_follow;
+mod markets;
+mod monitors;
+mod portfolio;
+
+/// Consecutive failed passes before a polling task is restarted.
+const MAX_CONSECUTIVE_ERRORS: u32 = 5;
+
+/// What the tasks share, built once in `main`.
+#[derive(Clone)]
+pub struct Shared {
+    /// Settings at startup
+    pub config: Config,
+    /// Settings the running tasks read, updated when the config file is edited
+    pub app: Arc<AppState>,
+    pub breaker: Arc<CircuitBreaker>,
+    pub notifier: Arc<Notifier>,
+    pub manager: Arc<StrategyManager>,
+    pub fees: FeeSchedule,
+    /// Paper profile the bot trades and tracks the session in
+    pub profile: Profile,
+    pub session: Arc<Mutex<Session>>,
+    pub detector: Arc<ArbitrageDetector>,
+    /// Sudden book changes between detection passes
+    pub moves: Arc<MoveDetector>,
+    pub fills: Arc<FillRouter>,
+    pub open_orders: Arc<Mutex<OpenOrders>>,
+    pub heatmap: Arc<Mutex<Heatmap>>,
+    pub capture: Arc<Mutex<CaptureLog>>,
+    pub attribution: Arc<Mutex<Attribution>>,
+    pub executions: Arc<Mutex<ExecutionLog>>,
+    pub intents: Arc<Mutex<IntentQueue>>,
+    pub cooldowns: Arc<Cooldowns>,
+    pub discrepancies: Arc<DiscrepancyLedger>,
+    pub derisker: Option<Arc<Mutex<Derisker>>>,
+    pub pretrade: Arc<PreTradeRisk>,
+    /// Every order goes out through this queue
+    pub execution: Arc<ExecutionQueue>,
+    pub controls: Arc<Controls>,
+    pub dashboard: Option<Arc<Dashboard>>,
+}
+
+impl Shared {
+    /// The intent queue, in semi-auto mode where orders wait for approval.
+    fn approvals(&self) -> Option<Arc<Mutex<IntentQueue>>> {
+        self.config.is_semi_auto().then(|| self.intents.clone())
+    }
+}
+
+/// Start every task the config enables.
+pub fn spawn_all(supervisor: &mut Supervisor, shared: &Shared) -> Result<()> {
+    let config = &shared.config;
+    supervise(supervisor, "detector", shared, detector::run);
+
+    if let (Some(dashboard), Some(addr)) = (shared.dashboard.clone(), config.dashboard_addr.clone()) {
+        supervise(supervisor, "dashboard", shared, move |shared| dashboard::run(shared, dashboard.clone(), addr.clone()));
+    }
+
+    supervise(supervisor, "market_moves", shared, alerts::market_moves);
+
+    if let Some(stream) = UserStream::from_config(config)? {
+        let stream = Arc::new(stream);
+        supervise(supervisor, "poly_user_stream", shared, move |shared| fills::poly_user_stream(shared, stream.clone()));
+    }
+
+    if let Some(webhook) = Webhook::from_config(config) {
+        info!("🪝 Publishing opportunity and fill events to webhook");
+        supervise(supervisor, "webhook_fills", shared, move |shared| fills::webhook_fills(shared, webhook.clone()));
+    }
+
+    if config.kalshi_api_key.is_some() {
+        supervise(supervisor, "kalshi_fills", shared, fills::kalshi_fills);
+    }
+
+    if let Some(leader) = KalshiFollower::leader_client(config)? {
+        info!(
+            "👥 Following a second Kalshi account at x{} (max {} contracts per fill)",
+            config.kalshi_follow_scale, config.kalshi_follow_max_contracts
+        );
+        let leader = Arc::new(leader);
+        supervise(supervisor, "kalshi_follow", shared, move |shared| kalshi_follow::run(shared, leader.clone()));
+    }
+
+    if !config.copy_traders.is_empty() {
+        info!("📋 Copying {} Polymarket traders", config.copy_traders.len());
+        supervise(supervisor, "copy_trade", shared, copy_trade::run);
+    }
+
+    if config.is_semi_auto() {
+        if config.telegram_bot_token.is_none() || config.telegram_chat_id.is_none() {
+            info!("✋ Telegram not configured - approve intents from the TUI Approvals tab");
+        }
+        supervise(supervisor, "intents", shared, approvals::intents);
+    }
+
+    if config.telegram_bot_token.is_some() && config.telegram_chat_id.is_some() {
+        supervise(supervisor, "telegram", shared, approvals::telegram);
+    }
+
+    if config.is_semi_auto() || config.kalshi_follow_api_key.is_some() || !config.copy_traders.is_empty() {
+        supervise(supervisor, "execution", shared, execution::run);
+    }
+
+    if !config.kalshi_series.is_empty() {
+        info!("🌐 Tracking Kalshi series: {}", config.kalshi_series.join(", "));
+    }
+    if !config.kalshi_series.is_empty() || config.leaderboard_refresh_secs > 0 {
+        supervise(supervisor, "refresh", shared, markets::refresh);
+    }
+
+    supervise(supervisor, "marker", shared, portfolio::marker);
+
+    let allocator = CapitalAllocator::from_config(config, config.paper_balance).filter(|a| a.rule() != RebalanceRule::Fixed);
+    if let Some(allocator) = allocator {
+        supervise(supervisor, "rebalance", shared, move |shared| portfolio::rebalance(shared, allocator.clone()));
+    }
+
+    supervise(supervisor, "balances", shared, monitors::balances);
+    supervise(supervisor, "resolutions", shared, monitors::resolutions);
+    supervise(supervisor, "ladders", shared, markets::ladders);
+
+    if config.lag_threshold > 0.0 {
+        supervise(supervisor, "lag_watch", shared, markets::lag_watch);
+    }
+
+    supervise(supervisor, "rewards", shared, monitors::rewards);
+    supervise(supervisor, "config_reload", shared, housekeeping::config_reload);
+
+    if !config.polygon_rpc_fallbacks.is_empty() {
+        supervise(supervisor, "rpc_health", shared, housekeeping::rpc_health);
+    }
+
+    let tasks = supervisor.stats_handle();
+    let started_at = chrono::Utc::now();
+    supervise(supervisor, "system", shared, move |shared| housekeeping::system(shared, tasks.clone(), started_at));
+    supervise(supervisor, "outbox", shared, alerts::outbox);
+    supervise(supervisor, "http_pool", shared, housekeeping::http_pool);
+    supervise(supervisor, "throttle", shared, alerts::throttle);
+
+    if !config.display_currency.is_dollar() {
+        supervise(supervisor, "fx_rates", shared, housekeeping::fx_rates);
+    }
+
+    let watchlist = Watchlist::load(&config.watchlist_path)?;
+    if !watchlist.items().is_empty() {
+        info!("👁 Watching {} markets from {}", watchlist.items().len(), config.watchlist_path);
+        supervise(supervisor, "watchlist", shared, monitors::watchlist);
+    }
+
+    if let Some(hour) = config.daily_report_hour {
+        info!("📰 Daily report at {:02}:00 {}", hour, config.timezone);
+        supervise(supervisor, "daily_report", shared, move |shared| alerts::daily_report(shared, hour));
+    }
+    Ok(())
+}
+
+/// Run `task` under `name` with the default restart policy, handing it a
+/// fresh clone of the shared state on every start.
+fn supervise<F, Fut>(supervisor: &mut Supervisor, name: &str, shared: &Shared, task: F)
+where
+    F: Fn(Shared) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let shared = shared.clone();
+    supervisor.spawn(name, RestartPolicy::default(), move || task(shared.clone()));
+}
@@ -0,0 +1,138 @@
+//! Monitors that alert on what they find: balances and venue float, how
+//! matched pairs resolve, liquidity rewards on our resting orders and the
+//! watchlist rules.
+
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+use tracing::warn;
+
+use polymarket_kalshi_arbitrage_bot::analysis::rewards::{RewardTerms, RewardsTracker, REWARDS_FILE};
+use polymarket_kalshi_arbitrage_bot::chain::TxManager;
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::monitor::balances::BALANCES_FILE;
+use polymarket_kalshi_arbitrage_bot::monitor::resolution::RESOLUTIONS_FILE;
+use polymarket_kalshi_arbitrage_bot::monitor::{Balances, FloatMonitor, ResolutionMonitor, Watchlist};
+use polymarket_kalshi_arbitrage_bot::paper_trading::book_fill::Depth;
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::utils::open_orders::OpenOrder;
+
+use super::Shared;
+
+/// How often our resting Polymarket orders are scored for liquidity rewards.
+const REWARDS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Track balances on Polygon and both venues, alerting on a low float.
+pub async fn balances(shared: Shared) -> Result<()> {
+    let Shared { config, app, breaker, notifier, .. } = shared;
+
+    let poly = PolymarketClient::new(&config)?;
+    let kalshi = KalshiClient::new(&config)?;
+    let chain = TxManager::from_config(&config)?;
+    let mut monitor = FloatMonitor::new(config.min_venue_float);
+    let wallet_is_collateral = !config.polymarket_signature_type.is_proxy();
+    while breaker.is_allowed() {
+        let balances = Balances::fetch(&poly, &kalshi, chain.as_ref(), wallet_is_collateral).await;
+        balances.save(BALANCES_FILE)?;
+        for alert in monitor.check(&balances) {
+            notifier.notify(alert).await;
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.balance_poll_secs))).await;
+    }
+    Ok(())
+}
+
+/// Check how matched pairs resolve, alerting when the venues disagree.
+pub async fn resolutions(shared: Shared) -> Result<()> {
+    let Shared { config, app, breaker, notifier, detector, capture, discrepancies, .. } = shared;
+
+    let poly = PolymarketClient::new(&config)?;
+    let kalshi = KalshiClient::new(&config)?;
+    let mut monitor = ResolutionMonitor::new()
+        .with_history(RESOLUTIONS_FILE)
+        .with_ledger(discrepancies.clone())
+        .with_capture(capture.clone());
+    while breaker.is_allowed() {
+        for matched in detector.matched_markets() {
+            monitor.watch(&matched);
+        }
+        for alert in monitor.check(&poly, &kalshi).await {
+            notifier.notify(alert).await;
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.resolution_poll_secs))).await;
+    }
+    Ok(())
+}
+
+/// Score our resting Polymarket orders against the liquidity reward
+/// programs, accruing uptime and estimated rewards per market.
+pub async fn rewards(shared: Shared) -> Result<()> {
+    let Shared { config, breaker, open_orders, .. } = shared;
+
+    let poly = PolymarketClient::new(&config)?;
+    let mut tracker = RewardsTracker::load(REWARDS_FILE);
+    // Question and reward terms per token, looked up once a run
+    let mut terms: HashMap<String, (String, Option<RewardTerms>)> = HashMap::new();
+    while breaker.is_allowed() {
+        let orders: Vec<OpenOrder> = open_orders
+            .lock()
+            .unwrap()
+            .all()
+            .into_iter()
+            .filter(|o| o.venue == "polymarket")
+            .cloned()
+            .collect();
+        let mut tokens: BTreeSet<String> = orders.iter().map(|o| o.market.clone()).collect();
+        tokens.extend(tracker.tokens());
+        let now = chrono::Utc::now();
+        for token in tokens {
+            let ours: Vec<&OpenOrder> = orders.iter().filter(|o| o.market == token).collect();
+            if !ours.is_empty() && !terms.contains_key(&token) {
+                match poly.get_gamma_market_by_token(&token).await {
+                    Ok(market) => {
+                        let label = market.as_ref().map_or_else(|| token.clone(), |m| m.question.clone());
+                        terms.insert(token.clone(), (label, market.and_then(|m| m.reward_terms())));
+                    }
+                    Err(e) => warn!("Failed to look up reward terms for {}: {}", token, e),
+                }
+            }
+            let (label, market_terms) = terms.get(&token).cloned().unwrap_or_else(|| {
+                (tracker.get(&token).map_or_else(|| token.clone(), |m| m.label.clone()), None)
+            });
+            let book = match (&market_terms, ours.is_empty()) {
+                (Some(_), false) => poly.get_orderbook(&token).await.ok().map(|b| Depth::from_polymarket(&b)),
+                _ => None,
+            };
+            tracker.observe(&token, &label, market_terms, book.as_ref(), &ours, now);
+        }
+        tracker.save();
+        tokio::time::sleep(REWARDS_INTERVAL).await;
+    }
+    Ok(())
+}
+
+/// Evaluate the watchlist alert rules.
+pub async fn watchlist(shared: Shared) -> Result<()> {
+    let Shared { config, app, breaker, notifier, .. } = shared;
+
+    let mut watchlist = Watchlist::load(&config.watchlist_path)?;
+    let kalshi = KalshiClient::new(&config)?;
+    let poly = PolymarketClient::new(&config)?;
+    while breaker.is_allowed() {
+        for i in 0..watchlist.items().len() {
+            let item = &watchlist.items()[i];
+            let quote = match Watchlist::fetch_quote(item, &poly, &kalshi).await {
+                Ok(q) => q,
+                Err(e) => {
+                    warn!("Watchlist quote for {} failed: {}", item.name, e);
+                    continue;
+                }
+            };
+            for alert in watchlist.evaluate(i, quote) {
+                notifier.notify(alert).await;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.watchlist_poll_secs))).await;
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! The paper portfolios: marked to market, watched for stops, expiries and
+//! drawdown, and their capital rebalanced between strategies.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use polymarket_kalshi_arbitrage_bot::kalshi::KalshiClient;
+use polymarket_kalshi_arbitrage_bot::monitor::marker::{save_marks, MARKS_FILE};
+use polymarket_kalshi_arbitrage_bot::monitor::{ExpiryMonitor, MarkToMarket};
+use polymarket_kalshi_arbitrage_bot::notify::{Alert, Severity};
+use polymarket_kalshi_arbitrage_bot::paper_trading::session::SESSION_FILE;
+use polymarket_kalshi_arbitrage_bot::paper_trading::{PaperTradingEngine, Portfolio, Profile, TradeLog};
+use polymarket_kalshi_arbitrage_bot::polymarket::PolymarketClient;
+use polymarket_kalshi_arbitrage_bot::risk::allocator::ALLOCATION_FILE;
+use polymarket_kalshi_arbitrage_bot::risk::CapitalAllocator;
+
+use super::Shared;
+
+/// How often a capital rebalance is checked for being due.
+const REBALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Mark open paper positions to market for the TUI, and track the bot's
+/// portfolio for the session and drawdown de-risking.
+pub async fn marker(shared: Shared) -> Result<()> {
+    let session_profile = shared.profile.name.clone();
+    let Shared { config, app, breaker, notifier, fees, session, derisker, .. } = shared;
+
+    let marker = MarkToMarket::new(PolymarketClient::new(&config)?, KalshiClient::new(&config)?);
+    let (poly, kalshi) = (PolymarketClient::new(&config)?, KalshiClient::new(&config)?);
+    let mut expiry = ExpiryMonitor::new();
+    while breaker.is_allowed() {
+        let warn_before = chrono::Duration::minutes(app.get(|c| c.resolution_warning_minutes) as i64);
+        // Marks are per market, so every profile shares one file
+        let mut marks = HashMap::new();
+        for profile in Profile::list() {
+            if let Some(mut portfolio) = Portfolio::load(&profile.portfolio_file) {
+                let marked = marker.mark_portfolio(&mut portfolio).await;
+                if !marked.is_empty() {
+                    debug!("Marked {} {} positions, unrealized P&L ${:.2}", marked.len(), profile.name, portfolio.unrealized_pnl());
+                }
+                // Stops fire here so they hold with no TUI open
+                if !portfolio.triggered_stops().is_empty() {
+                    let mut engine = PaperTradingEngine::for_profile(&profile, config.paper_balance)?.with_fees(fees.clone());
+                    for (market, pnl) in engine.update(|engine| engine.check_stops()) {
+                        warn!("🛑 Stop hit on {} {}, P&L ${:+.2}", profile.name, market, pnl);
+                        let title = format!("🛑 Stop hit on {}", market);
+                        let message = format!("Closed in {}, P&L ${:+.2}", profile.name, pnl);
+                        notifier.notify(Alert::new(Severity::Warning, "stop", &title, &message)).await;
+                    }
+                    portfolio.reload();
+                }
+                if profile.name == session_profile {
+                    let trades = TradeLog::snapshot(&profile.trades_file).get_all().len();
+                    let mut session = session.lock().unwrap();
+                    session.update(&portfolio, trades, chrono::Utc::now());
+                    session.save(SESSION_FILE)?;
+                }
+                let event = derisker
+                    .as_ref()
+                    .filter(|_| profile.name == session_profile)
+                    .and_then(|d| d.lock().unwrap().observe(portfolio.total_value()));
+                if let Some(event) = event {
+                    warn!("{:?}", event);
+                    notifier.notify(event.alert()).await;
+                }
+                marks.extend(marked);
+                // Warn before positions settle
+                expiry.refresh(&poly, &kalshi, &portfolio).await;
+                for alert in expiry.check(&profile.name, &portfolio, warn_before) {
+                    notifier.notify(alert).await;
+                }
+            }
+        }
+        if !marks.is_empty() {
+            save_marks(MARKS_FILE, &marks)?;
+        }
+        tokio::time::sleep(Duration::from_secs(app.get(|c| c.mark_interval_secs))).await;
+    }
+    Ok(())
+}
+
+/// Rebalance capital between strategies on schedule; the TUI and reports
+/// read the weights back from the state file.
+pub async fn rebalance(shared: Shared, allocator: CapitalAllocator) -> Result<()> {
+    let Shared { config, breaker, profile, .. } = shared;
+
+    let mut engine = PaperTradingEngine::for_profile(&profile, config.paper_balance)?
+        .with_allocator(allocator.with_state_file(ALLOCATION_FILE));
+    while breaker.is_allowed() {
+        engine.update(|engine| engine.rebalance());
+        tokio::time::sleep(REBALANCE_CHECK_INTERVAL).await;
+    }
+    Ok(())
+}
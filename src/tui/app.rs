@@ -1,6 +1,61 @@
 //! TUI Application state and logic.
 
-use crate::paper_trading::{PaperTradingEngine, PaperTrade};
+use crate::analysis::execution::{ExecutionLog, EXECUTIONS_FILE};
+use crate::analysis::heatmap::{Heatmap, HEATMAP_FILE};
+use crate::analysis::ladder::{load_ladders, LadderSnapshot, LADDERS_FILE};
+use crate::approval::{IntentQueue, INTENTS_FILE};
+use crate::arbitrage::market_matcher::MarketMatcher;
+use crate::arbitrage::mutes::{MarketMute, MuteList, MUTES_FILE};
+use crate::arbitrage::BinaryQuote;
+use crate::backtest::replay::{progress_bar, Replay};
+use crate::backtest::{QuoteTick, SimEvent};
+use crate::config::Config;
+use crate::fees::FeeSchedule;
+use crate::money::MoneyDisplay;
+use crate::display;
+use super::i18n;
+use crate::monitor::balances::{Balances, BALANCES_FILE};
+use crate::monitor::marker::{load_marks, mid_price, MARKS_FILE};
+use crate::monitor::system::{SystemStatus, SYSTEM_FILE};
+use crate::notify::notifier::{load_recent_alerts, ALERTS_FILE};
+use crate::risk::conflicts::{load_recent_conflicts, Conflict, CONFLICTS_FILE};
+use crate::notify::Alert;
+use crate::paper_trading::book_fill::{load_book, BOOKS_FILE};
+use crate::paper_trading::engine::DEFAULT_BALANCE;
+use crate::paper_trading::{PaperTradingEngine, PaperTrade, Profile};
+use crate::polymarket::discovery::OUTCOME_EVENTS_FILE;
+use crate::polymarket::types::OutcomeEvent;
+use std::time::{Duration, Instant};
+use crate::risk::trade_limits::TRADE_COUNTS_FILE;
+use crate::risk::allocator::ALLOCATION_FILE;
+use crate::risk::{CapitalAllocator, ConflictGuard, InventorySkew, MonteCarlo, Netting, RiskReport, TradeLimits};
+use crate::strategies::{StrategyManager, StrategyState};
+use crate::utils::annotations::{Annotations, ANNOTATIONS_FILE};
+use crate::utils::circuit_breaker::{CircuitBreaker, BREAKER_FILE};
+use crate::utils::endpoints::{load_status as load_endpoint_status, EndpointStatus, ENDPOINTS_FILE};
+use crate::utils::permissions::{Action, Permissions};
+use crate::utils::{run_info, state, timezone, Sizing};
+
+/// Number of alerts kept for the Alerts pane.
+const ALERTS_SHOWN: usize = 20;
+/// Number of strategy conflicts kept for the Strategies tab.
+const CONFLICTS_SHOWN: usize = 10;
+/// How often open positions pick up marks (and balances) from the bot.
+const MARK_REFRESH: Duration = Duration::from_secs(60);
+/// How often the approval queue picks up new intents from the bot.
+const INTENT_REFRESH: Duration = Duration::from_secs(2);
+/// How often an observer reloads the bot's state.
+const OBSERVER_REFRESH: Duration = Duration::from_secs(5);
+/// Keys an observer may press: tabs, navigation and views. Anything else
+/// is refused, so a new key stays out of read-only mode until added here
+const OBSERVER_KEYS: &[char] = &['q', '1', '2', '3', '4', '5', '6', '7', ' ', 'r', 'j', 'k', 'l', 'f', 'o'];
+/// Rows shown on the Trades tab.
+const TRADES_SHOWN: usize = 20;
+/// How far below the mark a new stop is placed, and how far +/- move it.
+const STOP_OFFSET: f64 = 0.05;
+const STOP_STEP: f64 = 0.01;
+/// Snooze length when SNOOZE_HOURS isn't configured.
+const DEFAULT_SNOOZE_HOURS: u64 = 4;
 
 
 /// Active tab in the TUI
@@ -10,6 +65,9 @@ pub enum Tab {
     Markets,
     Trades,
     Strategies,
+    Heatmap,
+    Approvals,
+    System,
 }
 
 impl Tab {
@@ -18,29 +76,58 @@ impl Tab {
             Tab::Dashboard => Tab::Markets,
             Tab::Markets => Tab::Trades,
             Tab::Trades => Tab::Strategies,
-            Tab::Strategies => Tab::Dashboard,
+            Tab::Strategies => Tab::Heatmap,
+            Tab::Heatmap => Tab::Approvals,
+            Tab::Approvals => Tab::System,
+            Tab::System => Tab::Dashboard,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            Tab::Dashboard => Tab::Strategies,
+            Tab::Dashboard => Tab::System,
             Tab::Markets => Tab::Dashboard,
             Tab::Trades => Tab::Markets,
             Tab::Strategies => Tab::Trades,
+            Tab::Heatmap => Tab::Strategies,
+            Tab::Approvals => Tab::Heatmap,
+            Tab::System => Tab::Approvals,
         }
     }
 
     pub fn title(&self) -> &'static str {
+        let s = i18n::strings();
         match self {
-            Tab::Dashboard => "Dashboard",
-            Tab::Markets => "Markets",
-            Tab::Trades => "Trades",
-            Tab::Strategies => "Strategies",
+            Tab::Dashboard => s.tab_dashboard,
+            Tab::Markets => s.tab_markets,
+            Tab::Trades => s.tab_trades,
+            Tab::Strategies => s.tab_strategies,
+            Tab::Heatmap => s.tab_heatmap,
+            Tab::Approvals => s.tab_approvals,
+            Tab::System => s.tab_system,
         }
     }
 }
 
+/// What a text prompt edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Tag,
+    Note,
+    /// PIN confirming a dangerous action
+    Pin(Action),
+    /// Starting balance confirming a portfolio reset
+    Balance,
+}
+
+/// Text being typed into a prompt for a trade.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    pub kind: InputKind,
+    pub trade_id: String,
+    pub buffer: String,
+}
+
 /// Live market data
 #[derive(Debug, Clone)]
 pub struct MarketData {
@@ -58,9 +145,20 @@ pub struct MarketData {
 #[derive(Debug, Clone)]
 pub struct StrategyStatus {
     pub name: String,
+    /// Name recorded on trades and used for schedules, e.g. "arbitrage"
+    pub key: String,
     pub enabled: bool,
+    /// Enabled, but outside its configured schedule
+    pub scheduled_off: bool,
+    /// Orders fill virtually (shadow mode)
+    pub shadow: bool,
+    /// Orders are only logged (DRY_RUN or its STRATEGY_DRY_RUN override)
+    pub dry_run: bool,
     pub trades_today: usize,
     pub pnl_today: f64,
+    /// Trades the bot placed today, against its MAX_TRADES_PER_DAY cap
+    pub bot_trades_today: u32,
+    pub trade_cap: Option<u32>,
 }
 
 /// Top trader info
@@ -86,19 +184,175 @@ pub struct App {
     pub strategies: Vec<StrategyStatus>,
     /// Top traders
     pub top_traders: Vec<TopTrader>,
+    /// Recent alerts (newest first)
+    pub alerts: Vec<Alert>,
+    /// Recent cross-strategy conflicts (newest first)
+    pub conflicts: Vec<Conflict>,
+    /// Opportunity heatmap recorded by the bot
+    pub heatmap: Heatmap,
+    /// Order intents awaiting manual approval (semi-auto mode)
+    pub intents: IntentQueue,
+    /// Markets muted or snoozed right now
+    pub mutes: Vec<MarketMute>,
+    mute_list: MuteList,
+    /// Kalshi strike ladders recorded by the bot
+    pub ladders: Vec<LadderSnapshot>,
+    /// Ladder shown on the Markets tab
+    pub ladder_index: usize,
+    /// Polymarket events with several outcomes, from the last universe
+    /// refresh
+    pub outcome_events: Vec<OutcomeEvent>,
+    /// How long Z snoozes a market
+    snooze_hours: u64,
+    /// Starting balance of new and reset paper portfolios
+    paper_balance: f64,
+    /// The bot's daily trade caps and today's counts
+    pub trade_limits: TradeLimits,
+    /// The bot's inventory cap and skew, when one is set
+    pub inventory_skew: Option<InventorySkew>,
+    /// Net contracts held per question across our live fills
+    pub inventory: Vec<(String, f64)>,
+    /// Enabled flags and schedules
+    strategy_manager: StrategyManager,
     /// Selected index in current list
     pub selected_index: usize,
     /// Status message
     pub status_message: Option<String>,
     /// Is refreshing data
     pub is_refreshing: bool,
+    /// When marks were last applied
+    last_marked: Option<Instant>,
+    /// Observer mode: render the bot's state without trading or settings
+    pub read_only: bool,
+    /// What this TUI's role may do, and the PIN for dangerous actions
+    pub permissions: Permissions,
+    /// When an observer last reloaded state
+    last_reloaded: Option<Instant>,
+    /// When the intent queue was last reloaded
+    last_intents: Option<Instant>,
+    /// Position open in the detail view (Dashboard)
+    pub position_detail: Option<String>,
+    /// Renders amounts in the display currency
+    pub money: MoneyDisplay,
+    /// Venue and wallet balances recorded by the bot
+    pub balances: Balances,
+    /// Endpoints the bot is using, for the System panel
+    pub endpoints: Vec<EndpointStatus>,
+    /// Process health written by the bot, for the System tab
+    pub system: SystemStatus,
+    /// Tags and notes on trades
+    pub annotations: Annotations,
+    /// Trades tab shows only trades with this tag
+    pub trade_filter: Option<String>,
+    /// Open text prompt, capturing all typed keys
+    pub input: Option<TextInput>,
+    /// Simulated 30-day outcomes of the current strategy mix
+    pub risk: Option<RiskReport>,
+    /// Recorded session being played back instead of live state
+    pub replay: Option<Replay>,
+    pub replay_paused: bool,
+    /// When the replay last advanced
+    last_replayed: Option<Instant>,
 }
 
 impl App {
-    /// Create a new app instance.
-    pub fn new() -> Self {
-        let engine = PaperTradingEngine::new();
-        
+    /// Create a new app instance. Fails when the paper portfolio can't be
+    /// opened, e.g. encrypted without the data key.
+    pub fn new() -> anyhow::Result<Self> {
+        let config = Config::from_env().ok();
+        if let Some(config) = &config {
+            i18n::init(config.tui_locale);
+        }
+        let profile = config.as_ref().and_then(|c| Profile::from_config(c).ok()).unwrap_or_default();
+        let paper_balance = config.as_ref().map_or(DEFAULT_BALANCE, |c| c.paper_balance);
+        let mut engine = PaperTradingEngine::for_profile(&profile, paper_balance)?;
+        let allocator = config
+            .as_ref()
+            .and_then(|c| CapitalAllocator::from_config(c, engine.portfolio.total_value()))
+            .map(|allocator| allocator.with_state_file(ALLOCATION_FILE));
+        let strategy_manager = config
+            .as_ref()
+            .and_then(|c| StrategyManager::from_config(c).ok())
+            .unwrap_or_default();
+        if let Some(allocator) = allocator {
+            engine = engine.with_allocator(allocator);
+        }
+        if let Some(fees) = config.as_ref().and_then(|c| FeeSchedule::from_config(c).ok()) {
+            fees.set_pairs(MarketMatcher::known().get_all());
+            engine = engine.with_fees(fees);
+        }
+        if let Some(guard) = config.as_ref().and_then(|c| ConflictGuard::from_config(c).ok()) {
+            engine = engine.with_conflicts(guard);
+        }
+        if let Some(config) = &config {
+            engine = engine.with_question_limit(config.max_question_loss);
+        }
+        let mut app = Self::with_engine(engine, strategy_manager, false);
+        if let Some(config) = &config {
+            app.money = MoneyDisplay::from_config(config);
+            app.snooze_hours = config.snooze_hours;
+            app.paper_balance = paper_balance;
+            app.trade_limits = TradeLimits::from_config(config);
+            app.inventory_skew = InventorySkew::from_config(config);
+            app.permissions = Permissions::from_config(config);
+            display::init(config);
+            // Paper trades made here are stamped with this session
+            run_info::init(config);
+        }
+        Ok(app)
+    }
+
+    /// Create a read-only observer of a running bot. State is loaded from
+    /// the bot's files and reloaded periodically; nothing is ever written.
+    pub fn observer() -> Self {
+        let config = Config::from_env().ok();
+        if let Some(config) = &config {
+            i18n::init(config.tui_locale);
+        }
+        let strategy_manager = config
+            .as_ref()
+            .and_then(|c| StrategyManager::from_config(c).ok())
+            .unwrap_or_default();
+        let profile = config.as_ref().and_then(|c| Profile::from_config(c).ok()).unwrap_or_default();
+        let mut app = Self::with_engine(PaperTradingEngine::snapshot_of(&profile), strategy_manager, true);
+        if let Some(config) = &config {
+            app.trade_limits = TradeLimits::from_config(config);
+            app.inventory_skew = InventorySkew::from_config(config);
+            app.money = MoneyDisplay::from_config(config);
+            display::init(config);
+        }
+        app.last_reloaded = Some(Instant::now());
+        app.status_message = Some(i18n::strings().msg_observer.to_string());
+        app
+    }
+
+    /// Watch a recorded session: quotes from the tape fill the Markets tab
+    /// and simulated arb fills are paper traded in memory. Nothing is
+    /// read from or written to the bot's state.
+    pub fn replay(replay: Replay) -> Self {
+        let config = Config::from_env().ok();
+        if let Some(config) = &config {
+            i18n::init(config.tui_locale);
+        }
+        let engine = PaperTradingEngine::in_memory("replay", config.as_ref().map_or(DEFAULT_BALANCE, |c| c.paper_balance)).with_fees(replay.fees().clone());
+        let mut app = Self::with_engine(engine, StrategyManager::default(), true);
+        if let Some(config) = &config {
+            app.money = MoneyDisplay::from_config(config);
+            display::init(config);
+        }
+        app.markets.clear();
+        app.alerts.clear();
+        app.conflicts.clear();
+        app.balances = Balances::default();
+        app.endpoints.clear();
+        app.system = SystemStatus::default();
+        app.status_message = Some(i18n::fill(i18n::strings().msg_replaying, &[&replay.speed()]));
+        app.replay = Some(replay);
+        app
+    }
+
+    fn with_engine(engine: PaperTradingEngine, strategy_manager: StrategyManager, read_only: bool) -> Self {
+        let s = i18n::strings();
         // Initialize with default data
         let markets = vec![
             MarketData {
@@ -135,22 +389,40 @@ impl App {
 
         let strategies = vec![
             StrategyStatus {
-                name: "Arbitrage".to_string(),
+                name: s.strategy_arbitrage.to_string(),
+                key: "arbitrage".to_string(),
                 enabled: true,
+                scheduled_off: false,
+                shadow: false,
+                dry_run: true,
                 trades_today: 0,
                 pnl_today: 0.0,
+                bot_trades_today: 0,
+                trade_cap: None,
             },
             StrategyStatus {
-                name: "Copy Trading".to_string(),
+                name: s.strategy_copy_trading.to_string(),
+                key: "copy_trade".to_string(),
                 enabled: true,
+                scheduled_off: false,
+                shadow: false,
+                dry_run: true,
                 trades_today: 0,
                 pnl_today: 0.0,
+                bot_trades_today: 0,
+                trade_cap: None,
             },
             StrategyStatus {
-                name: "Manual".to_string(),
+                name: s.strategy_manual.to_string(),
+                key: "manual".to_string(),
                 enabled: true,
+                scheduled_off: false,
+                shadow: false,
+                dry_run: true,
                 trades_today: 0,
                 pnl_today: 0.0,
+                bot_trades_today: 0,
+                trade_cap: None,
             },
         ];
 
@@ -181,49 +453,371 @@ impl App {
             },
         ];
 
-        Self {
+        let mut app = Self {
             active_tab: Tab::Dashboard,
             should_quit: false,
             engine,
             markets,
             strategies,
             top_traders,
+            alerts: load_recent_alerts(ALERTS_FILE, ALERTS_SHOWN),
+            conflicts: load_recent_conflicts(CONFLICTS_FILE, CONFLICTS_SHOWN),
+            heatmap: Heatmap::load(HEATMAP_FILE),
+            intents: IntentQueue::load(INTENTS_FILE),
+            mutes: Vec::new(),
+            mute_list: MuteList::new(MUTES_FILE),
+            ladders: Vec::new(),
+            outcome_events: Vec::new(),
+            ladder_index: 0,
+            snooze_hours: DEFAULT_SNOOZE_HOURS,
+            paper_balance: DEFAULT_BALANCE,
+            trade_limits: TradeLimits::new(timezone::current()),
+            inventory_skew: None,
+            inventory: Vec::new(),
+            strategy_manager,
             selected_index: 0,
-            status_message: Some("Ready - Press 'h' for help".to_string()),
+            status_message: Some(s.msg_ready.to_string()),
             is_refreshing: false,
-        }
+            last_marked: None,
+            read_only,
+            permissions: Permissions::default(),
+            last_reloaded: None,
+            last_intents: None,
+            position_detail: None,
+            money: MoneyDisplay::new(),
+            balances: Balances::load(BALANCES_FILE),
+            endpoints: load_endpoint_status(ENDPOINTS_FILE),
+            system: SystemStatus::load(SYSTEM_FILE),
+            annotations: Annotations::load(ANNOTATIONS_FILE),
+            trade_filter: None,
+            input: None,
+            risk: None,
+            replay: None,
+            replay_paused: false,
+            last_replayed: None,
+        };
+        app.refresh_schedules();
+        app.refresh_risk();
+        app
     }
 
     /// Handle key input.
     pub fn on_key(&mut self, key: char) {
+        if let Some(input) = &mut self.input {
+            input.buffer.push(key);
+            return;
+        }
+        if self.active_tab == Tab::Dashboard && self.position_detail.is_some() && self.on_detail_key(key) {
+            return;
+        }
+        if self.read_only && !OBSERVER_KEYS.contains(&key.to_ascii_lowercase()) {
+            self.status_message = Some(i18n::strings().msg_read_only.to_string());
+            return;
+        }
         match key {
             'q' | 'Q' => self.should_quit = true,
-            '1' => self.active_tab = Tab::Dashboard,
-            '2' => self.active_tab = Tab::Markets,
-            '3' => self.active_tab = Tab::Trades,
-            '4' => self.active_tab = Tab::Strategies,
+            '1' => self.switch_tab(Tab::Dashboard),
+            '2' => self.switch_tab(Tab::Markets),
+            '3' => self.switch_tab(Tab::Trades),
+            '4' => self.switch_tab(Tab::Strategies),
+            '5' => self.switch_tab(Tab::Heatmap),
+            '6' => self.switch_tab(Tab::Approvals),
+            '7' => self.switch_tab(Tab::System),
+            ' ' if self.replay.is_some() => {
+                self.replay_paused = !self.replay_paused;
+                let s = i18n::strings();
+                self.status_message = Some(if self.replay_paused { s.msg_replay_paused } else { s.msg_replay_resumed }.to_string());
+            }
+            'r' | 'R' | 'o' | 'O' if self.replay.is_some() => {
+                self.status_message = Some(i18n::strings().msg_replay_only.to_string());
+            }
+            'r' | 'R' if self.read_only => {
+                self.reload_snapshot();
+                self.status_message = Some(i18n::strings().msg_reloaded.to_string());
+            }
+            'b' | 'B' | 's' | 'S' | 't' | 'T' | 'a' | 'A' | 'x' | 'X' | 'g' | 'G' | 'n' | 'N' | 'm' | 'M' | 'z' | 'Z'
+                if !self.permissions.allows(Action::Trade) =>
+            {
+                self.deny(Action::Trade);
+            }
+            // Approving an intent of a live strategy places real orders
+            'a' | 'A' if self.active_tab == Tab::Approvals && self.selected_intent_is_live() => {
+                self.guarded(Action::GoLive);
+            }
+            'c' | 'C' => self.guarded(Action::ResetBreaker),
+            'w' | 'W' if self.active_tab != Tab::System => {
+                self.status_message = Some(i18n::strings().msg_system_tab.to_string());
+            }
+            'w' | 'W' => self.guarded(Action::ResetPortfolio),
             'r' | 'R' => {
                 self.is_refreshing = true;
-                self.status_message = Some("Refreshing market data...".to_string());
+                self.alerts = load_recent_alerts(ALERTS_FILE, ALERTS_SHOWN);
+                self.conflicts = load_recent_conflicts(CONFLICTS_FILE, CONFLICTS_SHOWN);
+                self.heatmap = Heatmap::load(HEATMAP_FILE);
+                self.money.reload();
+                self.engine.update(|engine| engine.refresh_allocation());
+                self.refresh_risk();
+                self.apply_marks();
+                self.status_message = Some(i18n::strings().msg_refreshing.to_string());
             }
             'j' | 'J' => self.next_item(),
             'k' | 'K' => self.prev_item(),
             'b' | 'B' => self.execute_paper_buy(),
             's' | 'S' => self.execute_paper_sell(),
+            'm' | 'M' => self.toggle_mute(false),
+            'z' | 'Z' => self.toggle_mute(true),
+            'l' | 'L' => self.next_ladder(),
             't' | 'T' => self.toggle_strategy(),
+            'a' | 'A' => self.decide_intent(true),
+            'x' | 'X' => self.decide_intent(false),
+            'g' | 'G' => self.open_input(InputKind::Tag),
+            'n' | 'N' => self.open_input(InputKind::Note),
+            'f' | 'F' => self.cycle_trade_filter(),
+            'o' | 'O' => self.cycle_profile(),
             _ => {}
         }
     }
 
+    fn switch_tab(&mut self, tab: Tab) {
+        self.active_tab = tab;
+        self.position_detail = None;
+    }
+
+    /// Quick actions in the position detail view. Returns false for keys
+    /// the view does not handle.
+    fn on_detail_key(&mut self, key: char) -> bool {
+        let fraction = match key {
+            '1' => 0.25,
+            '2' => 0.5,
+            '3' => 1.0,
+            'p' | 'P' | '+' | '-' => 0.0,
+            _ => return false,
+        };
+        if self.read_only {
+            self.status_message = Some(i18n::strings().msg_read_only.to_string());
+        } else if !self.permissions.allows(Action::Trade) {
+            self.deny(Action::Trade);
+        } else if fraction > 0.0 {
+            self.close_detail_position(fraction);
+        } else {
+            self.adjust_stop(key);
+        }
+        true
+    }
+
+    fn close_detail_position(&mut self, fraction: f64) {
+        let Some(market) = self.position_detail.clone() else { return };
+        let Some(mark) = self.engine.portfolio.positions.get(&market).map(|p| p.current_price) else { return };
+        let s = i18n::strings();
+        self.status_message = Some(match self.engine.update(|engine| engine.sell_fraction(&market, fraction, mark)) {
+            Ok(pnl) => i18n::fill(
+                s.msg_closed,
+                &[&format!("{:.0}", fraction * 100.0), &market, &display::price(mark), &self.money.fmt_signed(pnl)],
+            ),
+            Err(e) => i18n::fill(s.msg_close_failed, &[&e]),
+        });
+        if !self.engine.portfolio.positions.contains_key(&market) {
+            self.position_detail = None;
+        }
+    }
+
+    /// 'p' sets a stop below the mark (or clears it); '+'/'-' move it.
+    fn adjust_stop(&mut self, key: char) {
+        let Some(market) = self.position_detail.clone() else { return };
+        let Some(pos) = self.engine.portfolio.positions.get(&market) else { return };
+        let s = i18n::strings();
+        let stop = match (key, pos.stop_price) {
+            ('p' | 'P', Some(_)) => None,
+            ('p' | 'P', None) => Some((pos.current_price - STOP_OFFSET).max(STOP_STEP)),
+            ('+', Some(stop)) => Some((stop + STOP_STEP).min(1.0 - STOP_STEP)),
+            ('-', Some(stop)) => Some((stop - STOP_STEP).max(STOP_STEP)),
+            _ => {
+                self.status_message = Some(s.msg_stop_first.to_string());
+                return;
+            }
+        };
+        self.status_message = Some(match self.engine.update(|engine| engine.portfolio.set_stop(&market, stop)) {
+            Ok(()) => match stop {
+                Some(stop) => i18n::fill(s.msg_stop_set, &[&market, &format!("{:.2}", stop)]),
+                None => i18n::fill(s.msg_stop_cleared, &[&market]),
+            },
+            Err(e) => e,
+        });
+    }
+
+    /// Periodic housekeeping, called from the event loop.
+    pub fn on_tick(&mut self) {
+        if self.replay.is_some() {
+            self.step_replay();
+            return;
+        }
+        self.refresh_schedules();
+        if self.read_only && self.last_reloaded.is_none_or(|t| t.elapsed() >= OBSERVER_REFRESH) {
+            self.reload_snapshot();
+        }
+        if self.last_marked.is_none_or(|t| t.elapsed() >= MARK_REFRESH) {
+            self.apply_marks();
+        }
+        if self.last_intents.is_none_or(|t| t.elapsed() >= INTENT_REFRESH) {
+            self.last_intents = Some(Instant::now());
+            self.intents.reload();
+            self.annotations.reload();
+        }
+    }
+
+    /// Replay: play the tape forward by the wall time since the last tick.
+    fn step_replay(&mut self) {
+        let wall = self.last_replayed.map_or(Duration::ZERO, |t| t.elapsed());
+        self.last_replayed = Some(Instant::now());
+        let Some(replay) = self.replay.as_mut().filter(|r| !r.is_done()) else {
+            return;
+        };
+        if self.replay_paused {
+            return;
+        }
+        let steps = replay.advance(wall);
+        let traded = !steps.is_empty();
+        for step in steps {
+            self.show_replayed_quote(&step.tick);
+            if let Some(SimEvent::Filled { fill, size, .. }) = step.event {
+                let coin = fill.market.split_whitespace().next().unwrap_or("").to_string();
+                if let Err(e) = self.engine.buy_arb(&fill, &coin, "Replay", Sizing::Contracts(size), "arbitrage") {
+                    self.status_message = Some(i18n::fill(i18n::strings().msg_replay_fill_failed, &[&e]));
+                }
+            }
+        }
+        if traded {
+            self.refresh_risk();
+        }
+
+        let Some(replay) = &self.replay else { return };
+        let result = replay.result();
+        let at = replay.now().map(|t| timezone::format(t, "%Y-%m-%d %H:%M:%S")).unwrap_or_default();
+        let icon = if replay.is_done() { "⏹" } else { "⏪" };
+        self.status_message = Some(i18n::fill(
+            i18n::strings().msg_replay_progress,
+            &[
+                &icon,
+                &replay.speed(),
+                &at,
+                &progress_bar(replay.progress(), 20),
+                &result.filled,
+                &result.missed,
+                &self.money.fmt_signed(result.pnl),
+            ],
+        ));
+    }
+
+    /// Show a replayed quote on the Markets tab.
+    fn show_replayed_quote(&mut self, tick: &QuoteTick) {
+        let yes_mid = |q: &BinaryQuote| mid_price(q.yes_bid, q.yes_ask).or_else(|| mid_price(q.no_bid, q.no_ask).map(|p| 1.0 - p));
+        let (poly, kalshi) = (yes_mid(&tick.poly), yes_mid(&tick.kalshi));
+        let index = match self.markets.iter().position(|m| m.name == tick.market) {
+            Some(i) => i,
+            None => {
+                self.markets.push(MarketData {
+                    name: tick.market.clone(),
+                    coin: tick.market.split_whitespace().next().unwrap_or("").to_string(),
+                    timeframe: "Replay".to_string(),
+                    poly_price: None,
+                    kalshi_price: None,
+                    spread: None,
+                    liquidity: 0.0,
+                    time_to_resolve: "-".to_string(),
+                });
+                self.markets.len() - 1
+            }
+        };
+        let market = &mut self.markets[index];
+        market.poly_price = poly;
+        market.kalshi_price = kalshi;
+        market.spread = poly.zip(kalshi).map(|(p, k)| (p - k).abs());
+    }
+
+    /// Observer: pick up the bot's latest persisted state.
+    fn reload_snapshot(&mut self) {
+        self.last_reloaded = Some(Instant::now());
+        let traded = self.engine.trade_log.get_all().len();
+        self.engine = PaperTradingEngine::snapshot_of(&self.engine.profile);
+        self.alerts = load_recent_alerts(ALERTS_FILE, ALERTS_SHOWN);
+        self.conflicts = load_recent_conflicts(CONFLICTS_FILE, CONFLICTS_SHOWN);
+        self.heatmap = Heatmap::load(HEATMAP_FILE);
+        self.money.reload();
+        self.annotations.reload();
+        // The simulation only changes with new trades
+        if self.engine.trade_log.get_all().len() != traded || self.risk.is_none() {
+            self.refresh_risk();
+        }
+        self.apply_marks();
+    }
+
+    /// Re-run the Monte Carlo simulation over the trade history.
+    fn refresh_risk(&mut self) {
+        self.risk = MonteCarlo::new().simulate(
+            self.engine.trade_log.get_all(),
+            self.engine.allocator.as_ref(),
+            self.engine.portfolio.total_value(),
+        );
+    }
+
+    /// Net our live fills per question, for the inventory panel.
+    fn refresh_inventory(&mut self) {
+        if self.inventory_skew.is_none() {
+            return;
+        }
+        let mut netting = Netting::new().with_matches(&MarketMatcher::known());
+        netting.add_executions(ExecutionLog::load(EXECUTIONS_FILE).records());
+        self.inventory = netting.questions().map(|q| (q.question.clone(), q.net_delta())).collect();
+        self.inventory.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+    }
+
+    /// Re-mark open positions with the latest prices from the bot.
+    fn apply_marks(&mut self) {
+        self.last_marked = Some(Instant::now());
+        self.mutes = self.mute_list.active(chrono::Utc::now());
+        self.ladders = load_ladders(LADDERS_FILE);
+        self.outcome_events = state::load(OUTCOME_EVENTS_FILE);
+        self.balances = Balances::load(BALANCES_FILE);
+        self.endpoints = load_endpoint_status(ENDPOINTS_FILE);
+        self.system = SystemStatus::load(SYSTEM_FILE);
+        self.refresh_inventory();
+        // Stops are the bot's: its marker closes them and raises "stop" alerts
+        let marks = load_marks(MARKS_FILE);
+        self.engine.update(|engine| {
+            if !marks.is_empty() && !engine.portfolio.positions.is_empty() {
+                engine.portfolio.update_prices(&marks);
+            }
+        });
+        if let Some(market) = &self.position_detail {
+            if !self.engine.portfolio.positions.contains_key(market) {
+                self.position_detail = None;
+            }
+        }
+    }
+
     /// Handle special keys.
     pub fn on_special_key(&mut self, key: crossterm::event::KeyCode) {
         use crossterm::event::KeyCode;
+        if self.input.is_some() {
+            match key {
+                KeyCode::Enter => self.submit_input(),
+                KeyCode::Esc => self.input = None,
+                KeyCode::Backspace => {
+                    if let Some(input) = &mut self.input {
+                        input.buffer.pop();
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
         match key {
-            KeyCode::Tab => self.active_tab = self.active_tab.next(),
-            KeyCode::BackTab => self.active_tab = self.active_tab.prev(),
+            KeyCode::Tab => self.switch_tab(self.active_tab.next()),
+            KeyCode::BackTab => self.switch_tab(self.active_tab.prev()),
             KeyCode::Down => self.next_item(),
             KeyCode::Up => self.prev_item(),
             KeyCode::Enter => self.select_item(),
+            KeyCode::Esc if self.position_detail.is_some() => self.position_detail = None,
             KeyCode::Esc => self.status_message = None,
             _ => {}
         }
@@ -231,9 +825,15 @@ impl App {
 
     fn next_item(&mut self) {
         let max = match self.active_tab {
+            Tab::Dashboard if self.position_detail.is_none() => {
+                self.engine.portfolio.positions.len().saturating_sub(1)
+            }
             Tab::Markets => self.markets.len().saturating_sub(1),
-            Tab::Trades => self.engine.trade_log.get_all().len().saturating_sub(1),
+            Tab::Trades => self.visible_trades().len().saturating_sub(1),
             Tab::Strategies => self.strategies.len().saturating_sub(1),
+            // Index 0 is all families combined
+            Tab::Heatmap => self.heatmap.families().len(),
+            Tab::Approvals => self.intents.pending().len().saturating_sub(1),
             _ => 0,
         };
         if self.selected_index < max {
@@ -249,9 +849,14 @@ impl App {
 
     fn select_item(&mut self) {
         match self.active_tab {
+            Tab::Dashboard => {
+                if let Some((market, _)) = self.open_positions().get(self.selected_index) {
+                    self.position_detail = Some(market.to_string());
+                }
+            }
             Tab::Markets => {
                 if let Some(market) = self.markets.get(self.selected_index) {
-                    self.status_message = Some(format!("Selected: {}", market.name));
+                    self.status_message = Some(i18n::fill(i18n::strings().msg_selected, &[&market.name]));
                 }
             }
             Tab::Strategies => {
@@ -262,27 +867,333 @@ impl App {
     }
 
     fn toggle_strategy(&mut self) {
+        if self.read_only {
+            self.status_message = Some(i18n::strings().msg_read_only.to_string());
+            return;
+        }
+        if !self.permissions.allows(Action::Trade) {
+            self.deny(Action::Trade);
+            return;
+        }
         if let Some(strategy) = self.strategies.get_mut(self.selected_index) {
             strategy.enabled = !strategy.enabled;
-            self.status_message = Some(format!(
-                "{} strategy {}",
-                strategy.name,
-                if strategy.enabled { "enabled" } else { "disabled" }
-            ));
+            self.strategy_manager.set_enabled(&strategy.key, strategy.enabled);
+            let s = i18n::strings();
+            let message = if strategy.enabled { s.msg_strategy_enabled } else { s.msg_strategy_disabled };
+            self.status_message = Some(i18n::fill(message, &[&strategy.name]));
+        }
+        self.refresh_schedules();
+    }
+
+    /// Mute (or snooze) the selected market, or lift an existing mute (or
+    /// snooze).
+    fn toggle_mute(&mut self, snooze: bool) {
+        let s = i18n::strings();
+        if self.active_tab != Tab::Markets {
+            self.status_message = Some(s.msg_mute_tab.to_string());
+            return;
+        }
+        let Some(market) = self.markets.get(self.selected_index).map(|m| m.name.clone()) else { return };
+        let now = chrono::Utc::now();
+        let current = self.mute(&market);
+        let result = match (snooze, current) {
+            (false, Some(m)) if m.muted => self.mute_list.unmute(&market, "tui", now).map(|_| s.msg_unmuted),
+            (false, _) => self.mute_list.mute(&market, "tui", None, now).map(|_| s.msg_muted),
+            (true, Some(m)) if m.is_snoozed(now) => self.mute_list.unsnooze(&market, "tui", now).map(|_| s.msg_unsnoozed),
+            (true, _) => self.mute_list.snooze(&market, self.snooze_hours, "tui", None, now).map(|_| s.msg_snoozed),
+        };
+        self.status_message = Some(match result {
+            Ok(message) => i18n::fill(message, &[&market, &self.snooze_hours]),
+            Err(e) => i18n::fill(s.msg_mute_failed, &[&e]),
+        });
+        self.mutes = self.mute_list.active(now);
+    }
+
+    /// Show the next event's strike ladder.
+    fn next_ladder(&mut self) {
+        let s = i18n::strings();
+        if self.ladders.is_empty() {
+            self.status_message = Some(s.msg_no_ladders.to_string());
+            return;
+        }
+        self.ladder_index = (self.ladder_index + 1) % self.ladders.len();
+        if let Some(ladder) = self.ladder() {
+            self.status_message = Some(i18n::fill(s.msg_ladder, &[&ladder.ladder.event_ticker]));
+        }
+    }
+
+    /// Strike ladder shown on the Markets tab.
+    pub fn ladder(&self) -> Option<&LadderSnapshot> {
+        self.ladders.get(self.ladder_index % self.ladders.len().max(1))
+    }
+
+    /// Mute or snooze in force on `market`.
+    pub fn mute(&self, market: &str) -> Option<&MarketMute> {
+        self.mutes.iter().find(|m| m.market == market)
+    }
+
+    /// Approve or reject the selected pending intent.
+    fn decide_intent(&mut self, approve: bool) {
+        let s = i18n::strings();
+        if self.active_tab != Tab::Approvals {
+            self.status_message = Some(s.msg_approvals_tab.to_string());
+            return;
+        }
+        let Some(id) = self.intents.pending().get(self.selected_index).map(|i| i.id.clone()) else {
+            self.status_message = Some(s.msg_no_intents.to_string());
+            return;
+        };
+        self.status_message = Some(match self.intents.decide(&id, approve, "tui", chrono::Utc::now()) {
+            Ok(()) if approve => i18n::fill(s.msg_approved, &[&id]),
+            Ok(()) => i18n::fill(s.msg_rejected, &[&id]),
+            Err(e) => e,
+        });
+        self.selected_index = self.selected_index.min(self.intents.pending().len().saturating_sub(1));
+    }
+
+    /// Whether the selected pending intent belongs to a strategy that trades live.
+    fn selected_intent_is_live(&self) -> bool {
+        self.intents
+            .pending()
+            .get(self.selected_index)
+            .is_some_and(|intent| !self.strategy_manager.is_dry_run(intent.kind.strategy()))
+    }
+
+    /// Say that this TUI's role may not take `action`.
+    fn deny(&mut self, action: Action) {
+        let s = i18n::strings();
+        let label = match action {
+            Action::Trade => s.action_trade,
+            Action::GoLive => s.action_go_live,
+            Action::ResetPortfolio => s.action_reset_portfolio,
+            Action::ResetBreaker => s.action_reset_breaker,
+        };
+        self.status_message =
+            Some(i18n::fill(s.msg_not_permitted, &[&label, &action.required_role(), &self.permissions.role()]));
+    }
+
+    /// Take `action` if the role allows it, asking for the PIN first when
+    /// it is dangerous.
+    fn guarded(&mut self, action: Action) {
+        if !self.permissions.allows(action) {
+            self.deny(action);
+        } else if self.permissions.needs_pin(action) {
+            self.input = Some(TextInput { kind: InputKind::Pin(action), trade_id: String::new(), buffer: String::new() });
+        } else {
+            self.perform(action);
+        }
+    }
+
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Trade => {}
+            Action::GoLive => self.decide_intent(true),
+            Action::ResetPortfolio => {
+                let buffer = format!("{:.2}", self.engine.portfolio.initial_balance);
+                self.input = Some(TextInput { kind: InputKind::Balance, trade_id: String::new(), buffer });
+            }
+            Action::ResetBreaker => self.clear_breaker(),
+        }
+    }
+
+    /// Start the paper portfolio over from `balance`, archiving its trade
+    /// history.
+    fn reset_portfolio(&mut self, balance: f64) {
+        if let Err(e) = self.engine.update(|engine| engine.reset_to(balance)) {
+            self.status_message = Some(i18n::fill(i18n::strings().msg_reset_failed, &[&e]));
+            return;
+        }
+        self.position_detail = None;
+        self.selected_index = 0;
+        self.refresh_risk();
+        let balance = self.money.fmt(self.engine.portfolio.total_value());
+        self.status_message = Some(i18n::fill(i18n::strings().msg_portfolio_reset, &[&self.engine.profile.name, &balance]));
+    }
+
+    /// Clear a tripped circuit breaker, so the bot trades again once
+    /// restarted.
+    fn clear_breaker(&mut self) {
+        let s = i18n::strings();
+        let breaker = CircuitBreaker::load(BREAKER_FILE);
+        self.status_message = Some(if breaker.is_allowed() {
+            s.msg_breaker_not_tripped.to_string()
+        } else {
+            breaker.reset();
+            s.msg_breaker_cleared.to_string()
+        });
+    }
+
+    /// Prompt for a tag or note on the selected trade.
+    fn open_input(&mut self, kind: InputKind) {
+        let s = i18n::strings();
+        if self.active_tab != Tab::Trades {
+            self.status_message = Some(s.msg_trades_tab.to_string());
+            return;
+        }
+        let Some(trade) = self.visible_trades().get(self.selected_index).map(|t| t.id.clone()) else {
+            self.status_message = Some(s.msg_no_trade.to_string());
+            return;
+        };
+        let buffer = match kind {
+            InputKind::Note => self.annotations.get(&trade).and_then(|a| a.notes.clone()).unwrap_or_default(),
+            InputKind::Tag | InputKind::Pin(_) | InputKind::Balance => String::new(),
+        };
+        self.input = Some(TextInput { kind, trade_id: trade, buffer });
+    }
+
+    fn submit_input(&mut self) {
+        let Some(input) = self.input.take() else { return };
+        let text = input.buffer.trim();
+        let s = i18n::strings();
+        if let InputKind::Pin(action) = input.kind {
+            if self.permissions.check_pin(text) {
+                self.perform(action);
+            } else {
+                self.status_message = Some(s.msg_wrong_pin.to_string());
+            }
+            return;
+        }
+        if input.kind == InputKind::Balance {
+            match text.parse::<f64>() {
+                Ok(balance) if balance.is_finite() && balance > 0.0 => self.reset_portfolio(balance),
+                _ => self.status_message = Some(s.msg_invalid_balance.to_string()),
+            }
+            return;
+        }
+        self.status_message = Some(match input.kind {
+            InputKind::Tag if text.is_empty() => return,
+            InputKind::Tag => match text.strip_prefix('-') {
+                Some(tag) => {
+                    self.annotations.untag(&input.trade_id, tag);
+                    i18n::fill(s.msg_tag_removed, &[&tag.trim()])
+                }
+                None => i18n::fill(s.msg_tagged, &[&self.annotations.tag(&input.trade_id, text)]),
+            },
+            InputKind::Note => {
+                self.annotations.set_notes(&input.trade_id, text);
+                if text.is_empty() { s.msg_note_cleared } else { s.msg_note_saved }.to_string()
+            }
+            InputKind::Pin(_) | InputKind::Balance => return,
+        });
+    }
+
+    /// Switch to another paper portfolio, creating it if new. Observers
+    /// only view it.
+    pub fn switch_profile(&mut self, profile: Profile) -> anyhow::Result<()> {
+        if self.read_only {
+            self.engine = PaperTradingEngine::snapshot_of(&profile);
+        } else {
+            let mut engine = PaperTradingEngine::for_profile(&profile, self.paper_balance)?.with_fees(self.engine.fees.clone());
+            if let Some(allocator) = self.engine.allocator.take() {
+                engine = engine.with_allocator(allocator);
+            }
+            if let Some(guard) = self.engine.conflicts.take() {
+                engine = engine.with_conflicts(guard);
+            }
+            engine.question_limit = self.engine.question_limit;
+            self.engine = engine;
+            self.engine.update(|engine| engine.refresh_allocation());
+        }
+        self.selected_index = 0;
+        self.position_detail = None;
+        self.refresh_risk();
+        self.apply_marks();
+        self.status_message = Some(i18n::fill(i18n::strings().msg_portfolio, &[&profile.name]));
+        Ok(())
+    }
+
+    fn cycle_profile(&mut self) {
+        let profiles = Profile::list();
+        if profiles.len() < 2 {
+            self.status_message = Some(i18n::strings().msg_no_portfolios.to_string());
+            return;
+        }
+        let current = profiles.iter().position(|p| *p == self.engine.profile).unwrap_or(0);
+        if let Err(e) = self.switch_profile(profiles[(current + 1) % profiles.len()].clone()) {
+            self.status_message = Some(format!("⚠️ {:#}", e));
+        }
+    }
+
+    /// Step the Trades tab filter through every tag in use, then back to all.
+    fn cycle_trade_filter(&mut self) {
+        let tags: Vec<String> = self.annotations.tags().into_iter().collect();
+        self.trade_filter = match &self.trade_filter {
+            None => tags.first().cloned(),
+            Some(current) => tags.iter().skip_while(|t| *t != current).nth(1).cloned(),
+        };
+        self.selected_index = 0;
+        let s = i18n::strings();
+        self.status_message = Some(match &self.trade_filter {
+            Some(tag) => i18n::fill(s.msg_filter_tag, &[tag]),
+            None if tags.is_empty() => s.msg_no_tags.to_string(),
+            None => s.msg_filter_all.to_string(),
+        });
+    }
+
+    /// Re-evaluate which enabled strategies are outside their schedule, and
+    /// their figures since the day rolled over on the configured clock.
+    fn refresh_schedules(&mut self) {
+        let now = chrono::Utc::now();
+        let today = timezone::day_start(now, timezone::current());
+        if self.replay.is_none() {
+            self.trade_limits.reload(TRADE_COUNTS_FILE);
+        }
+        let counts = self.trade_limits.counts();
+        for strategy in &mut self.strategies {
+            strategy.scheduled_off =
+                self.strategy_manager.state(&strategy.key, now) == StrategyState::ScheduledOff;
+            strategy.shadow = self.strategy_manager.is_shadow(&strategy.key);
+            strategy.dry_run = self.strategy_manager.is_dry_run(&strategy.key);
+            let trades = self.engine.trade_log.get_all().iter().filter(|t| t.strategy == strategy.key && t.timestamp >= today);
+            let (count, pnl) = trades.fold((0, 0.0), |(n, pnl), t| (n + 1, pnl + t.pnl.unwrap_or(0.0)));
+            strategy.trades_today = count;
+            strategy.pnl_today = pnl;
+            strategy.bot_trades_today = counts.trades(&strategy.key);
+            strategy.trade_cap = self.trade_limits.strategy_cap(&strategy.key);
         }
     }
 
     fn execute_paper_buy(&mut self) {
+        let s = i18n::strings();
         if self.active_tab != Tab::Markets {
-            self.status_message = Some("Switch to Markets tab to buy".to_string());
+            self.status_message = Some(s.msg_buy_tab.to_string());
             return;
         }
 
         if let Some(market) = self.markets.get(self.selected_index).cloned() {
             let price = market.poly_price.unwrap_or(0.5);
             let size = 10.0; // $10 default size
-            
-            match self.engine.buy(
+
+            // Walk the bot's latest book for this market when there is one
+            if let Some(book) = self.live_book(&market.name) {
+                let bought = self.engine.update(|engine| engine.buy_from_book(
+                    &market.name,
+                    &market.coin,
+                    &market.timeframe,
+                    "polymarket",
+                    size,
+                    &book,
+                    "manual",
+                    0.5,
+                ));
+                self.status_message = Some(match bought {
+                    Ok((_, fill)) => i18n::fill(
+                        s.msg_bought_book,
+                        &[
+                            &self.money.fmt(fill.notional()),
+                            &market.name,
+                            &display::price(fill.vwap()),
+                            &fill.levels.len(),
+                            &self.money.fmt(fill.fee(&self.engine.fees, "polymarket", &market.name)),
+                        ],
+                    ),
+                    Err(e) => i18n::fill(s.msg_buy_failed, &[&e]),
+                });
+                self.conflicts = load_recent_conflicts(CONFLICTS_FILE, CONFLICTS_SHOWN);
+                return;
+            }
+
+            let bought = self.engine.update(|engine| engine.buy(
                 &market.name,
                 &market.coin,
                 &market.timeframe,
@@ -291,57 +1202,132 @@ impl App {
                 price,
                 "manual",
                 0.5,
-            ) {
-                Ok(_) => {
-                    self.status_message = Some(format!(
-                        "✅ Bought ${:.0} of {} @ {:.2}",
-                        size, market.name, price
+            ));
+            match bought {
+                Ok(trade_id) => {
+                    // A conflict policy may have netted the size down
+                    let size = self.engine.trade_log.get_all().iter()
+                        .find(|t| t.id == trade_id)
+                        .map_or(size, |t| t.filled_size());
+                    self.status_message = Some(i18n::fill(
+                        s.msg_bought,
+                        &[&self.money.fmt(size), &market.name, &format!("{:.2}", price)],
                     ));
                 }
                 Err(e) => {
-                    self.status_message = Some(format!("❌ Buy failed: {}", e));
+                    self.status_message = Some(i18n::fill(s.msg_buy_failed, &[&e]));
                 }
             }
+            self.conflicts = load_recent_conflicts(CONFLICTS_FILE, CONFLICTS_SHOWN);
         }
     }
 
     fn execute_paper_sell(&mut self) {
+        let s = i18n::strings();
         if self.active_tab != Tab::Markets {
-            self.status_message = Some("Switch to Markets tab to sell".to_string());
+            self.status_message = Some(s.msg_sell_tab.to_string());
             return;
         }
 
         if let Some(market) = self.markets.get(self.selected_index).cloned() {
             let price = market.poly_price.unwrap_or(0.5);
-            
-            match self.engine.sell(&market.name, price) {
+
+            if let Some(book) = self.live_book(&market.name) {
+                self.status_message = Some(match self.engine.update(|engine| engine.sell_into_book(&market.name, &book)) {
+                    Ok((pnl, fill)) => {
+                        let emoji = if pnl >= 0.0 { "✅" } else { "❌" };
+                        i18n::fill(
+                            s.msg_sold_book,
+                            &[&emoji, &market.name, &display::price(fill.vwap()), &fill.levels.len(), &self.money.fmt(pnl)],
+                        )
+                    }
+                    Err(e) => i18n::fill(s.msg_sell_failed, &[&e]),
+                });
+                return;
+            }
+
+            match self.engine.update(|engine| engine.sell(&market.name, price)) {
                 Ok(pnl) => {
                     let emoji = if pnl >= 0.0 { "✅" } else { "❌" };
-                    self.status_message = Some(format!(
-                        "{} Sold {} for ${:.2} P&L",
-                        emoji, market.name, pnl
-                    ));
+                    self.status_message = Some(i18n::fill(s.msg_sold, &[&emoji, &market.name, &self.money.fmt(pnl)]));
                 }
                 Err(e) => {
-                    self.status_message = Some(format!("❌ Sell failed: {}", e));
+                    self.status_message = Some(i18n::fill(s.msg_sell_failed, &[&e]));
                 }
             }
         }
     }
 
+    /// The bot's order book for `market` if it is fresh. Replays only have
+    /// top-of-book quotes.
+    fn live_book(&self, market: &str) -> Option<crate::paper_trading::Depth> {
+        if self.replay.is_some() {
+            return None;
+        }
+        load_book(BOOKS_FILE, market, chrono::Utc::now()).map(|b| b.depth)
+    }
+
     /// Get recent trades for display.
     pub fn recent_trades(&self) -> Vec<&PaperTrade> {
         self.engine.trade_log.get_recent(10)
     }
 
-    /// Get open positions.
+    /// Trades tab rows, newest first, limited to the tag filter.
+    pub fn visible_trades(&self) -> Vec<&PaperTrade> {
+        self.engine
+            .trade_log
+            .get_all()
+            .iter()
+            .rev()
+            .filter(|t| self.trade_filter.as_ref().is_none_or(|tag| self.annotations.has_tag(&t.id, tag)))
+            .take(TRADES_SHOWN)
+            .collect()
+    }
+
+    /// Get open positions, sorted by market so selection is stable.
     pub fn open_positions(&self) -> Vec<(&String, &crate::paper_trading::Position)> {
-        self.engine.portfolio.positions.iter().collect()
+        let mut positions: Vec<_> = self.engine.portfolio.positions.iter().collect();
+        positions.sort_by(|a, b| a.0.cmp(b.0));
+        positions
+    }
+
+    /// Position shown in the detail view, with its trades (newest first).
+    pub fn detail(&self) -> Option<(&crate::paper_trading::Position, Vec<&PaperTrade>)> {
+        let market = self.position_detail.as_ref()?;
+        let position = self.engine.portfolio.positions.get(market)?;
+        let trades = self.engine.trade_log.get_all().iter().rev().filter(|t| &t.market == market).collect();
+        Some((position, trades))
     }
 }
 
-impl Default for App {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(read_only: bool) -> App {
+        App::with_engine(PaperTradingEngine::in_memory("test", 1000.0), StrategyManager::default(), read_only)
+    }
+
+    #[test]
+    fn test_observers_may_only_use_allowed_keys() {
+        let refused = i18n::strings().msg_read_only.to_string();
+        for key in ('!'..='~').filter(|k| !OBSERVER_KEYS.contains(&k.to_ascii_lowercase())) {
+            let mut observer = app(true);
+            observer.on_key(key);
+            assert_eq!(observer.status_message.as_deref(), Some(refused.as_str()), "key {:?}", key);
+            assert!(observer.engine.trade_log.get_all().is_empty() && observer.input.is_none());
+        }
+
+        // Tabs and quitting still work
+        let mut observer = app(true);
+        observer.on_key('3');
+        assert_eq!(observer.active_tab, Tab::Trades);
+        observer.on_key('q');
+        assert!(observer.should_quit);
+
+        // A trader is not refused as an observer
+        let mut trader = app(false);
+        trader.on_key('b');
+        assert_ne!(trader.status_message.as_deref(), Some(refused.as_str()));
     }
 }
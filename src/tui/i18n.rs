@@ -0,0 +1,900 @@
+//! TUI strings.
+//!
+//! Every label and status message the TUI shows comes from a [`Strings`]
+//! table, one per locale, picked with TUI_LOCALE. Messages that carry
+//! values use numbered placeholders ({0}, {1}, ...) filled by [`fill`], so
+//! a translation can order them however reads naturally. Errors passed up
+//! from the engine are shown as they come.
+
+use anyhow::Result;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+static GLOBAL: OnceLock<Locale> = OnceLock::new();
+
+/// Language of the TUI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+}
+
+impl Locale {
+    pub fn strings(&self) -> &'static Strings {
+        match self {
+            Locale::En => &EN,
+            Locale::Es => &ES,
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    /// "es", or a system locale such as "es_MX.UTF-8".
+    fn from_str(s: &str) -> Result<Self> {
+        let language = s.trim().split(['_', '-', '.']).next().unwrap_or_default().to_lowercase();
+        Ok(match language.as_str() {
+            "en" => Locale::En,
+            "es" => Locale::Es,
+            _ => anyhow::bail!("Unknown locale '{}' (expected en or es)", s),
+        })
+    }
+}
+
+/// Labels and messages for one locale. Fields documented with their
+/// placeholders are templates for [`fill`].
+#[derive(Debug)]
+pub struct Strings {
+    // Header
+    pub balance: &'static str,
+    pub pnl: &'static str,
+    pub all_time_pnl: &'static str,
+    /// P&L since this run started
+    pub session: &'static str,
+    pub portfolio: &'static str,
+    pub venues: &'static str,
+    pub replay_badge: &'static str,
+    pub replay_paused_badge: &'static str,
+    pub observer_badge: &'static str,
+
+    // Tabs
+    pub navigation_title: &'static str,
+    pub tab_dashboard: &'static str,
+    pub tab_markets: &'static str,
+    pub tab_trades: &'static str,
+    pub tab_strategies: &'static str,
+    pub tab_heatmap: &'static str,
+    pub tab_approvals: &'static str,
+    pub tab_system: &'static str,
+
+    // Dashboard
+    pub performance_title: &'static str,
+    pub total_pnl: &'static str,
+    pub fees: &'static str,
+    /// {0} fees, {1} per trade, {2} share of gross P&L
+    pub fee_breakdown: &'static str,
+    pub net_pnl: &'static str,
+    pub win_rate: &'static str,
+    pub best_trade: &'static str,
+    pub worst_trade: &'static str,
+    pub cash_balance: &'static str,
+    pub locked: &'static str,
+    pub open: &'static str,
+    pub recent_trades_title: &'static str,
+    pub positions_title: &'static str,
+    pub system_title: &'static str,
+    /// {0} endpoint index, {1} fallbacks configured
+    pub fallback: &'static str,
+    pub primary: &'static str,
+    /// {0} count, {1} time of the last one
+    pub failovers: &'static str,
+    pub no_endpoints: &'static str,
+    pub top_traders_title: &'static str,
+    pub alerts_title: &'static str,
+
+    // Position detail
+    pub venue: &'static str,
+    pub size: &'static str,
+    pub mark: &'static str,
+    pub unrealized: &'static str,
+    pub fees_paid: &'static str,
+    pub stop: &'static str,
+    pub copying: &'static str,
+    pub detail_help: &'static str,
+    pub opened: &'static str,
+    pub shares: &'static str,
+    pub price: &'static str,
+    pub entry_lots_title: &'static str,
+    /// {0} number of marks
+    pub marks_title: &'static str,
+    pub detail_trades_title: &'static str,
+
+    // Markets and Trades tables
+    pub col_market: &'static str,
+    pub col_coin: &'static str,
+    pub col_poly: &'static str,
+    pub col_kalshi: &'static str,
+    pub col_spread: &'static str,
+    pub col_liquidity: &'static str,
+    pub col_time: &'static str,
+    pub col_side: &'static str,
+    pub col_size: &'static str,
+    pub col_entry: &'static str,
+    pub col_exit: &'static str,
+    pub col_pnl: &'static str,
+    pub col_strategy: &'static str,
+    pub col_tags: &'static str,
+    pub markets_title: &'static str,
+    /// {0} event ticker, {1} ladder number, {2} ladders
+    pub ladder_title: &'static str,
+    pub fair_values_title: &'static str,
+    /// {0} events
+    pub outcome_events_title: &'static str,
+    pub col_outcome: &'static str,
+    pub col_yes: &'static str,
+    pub col_strike: &'static str,
+    pub col_bid: &'static str,
+    pub col_ask: &'static str,
+    pub col_above: &'static str,
+    pub col_fair: &'static str,
+    pub col_premium: &'static str,
+    pub trade_history_title: &'static str,
+    /// {0} tag
+    pub trade_history_tagged_title: &'static str,
+
+    // Strategies
+    pub strategies_title: &'static str,
+    /// Trades the bot placed today on the Strategies tab
+    pub bot_trades: &'static str,
+    /// {0} orders placed today, {1} daily cap
+    pub orders_today: &'static str,
+    pub strategy_arbitrage: &'static str,
+    pub strategy_copy_trading: &'static str,
+    pub strategy_manual: &'static str,
+    pub strategy_off: &'static str,
+    pub strategy_scheduled_off: &'static str,
+    pub strategy_shadow: &'static str,
+    pub strategy_on: &'static str,
+    pub strategy_live: &'static str,
+    pub strategy_dry_run: &'static str,
+    pub trades: &'static str,
+    pub help_title: &'static str,
+    pub help_shortcuts: &'static str,
+    pub help_keys: &'static [&'static str],
+    pub help_strategies: &'static str,
+    pub help_strategy_lines: &'static [&'static str],
+    pub conflicts_title: &'static str,
+    pub no_conflicts: &'static str,
+    pub conflict_skipped: &'static str,
+    /// {0} amount allowed
+    pub conflict_netted: &'static str,
+    pub conflict_allowed: &'static str,
+    /// {0} market, {1} opposing strategies
+    pub conflict_versus: &'static str,
+    pub inventory_title: &'static str,
+    pub no_inventory_cap: &'static str,
+    pub flat_inventory: &'static str,
+    /// {0} quote shift
+    pub inventory_shift: &'static str,
+    /// {0} days
+    pub risk_title: &'static str,
+    pub no_risk: &'static str,
+    /// {0} paths, {1} trades per day
+    pub risk_paths: &'static str,
+    pub expected_pnl: &'static str,
+    pub median_pnl: &'static str,
+    pub p5_pnl: &'static str,
+    pub p95_pnl: &'static str,
+    pub var_cvar: &'static str,
+    pub drawdown: &'static str,
+    pub ruin: &'static str,
+    /// {0} ruin level
+    pub ruin_below: &'static str,
+
+    // Heatmap
+    /// {0} family
+    pub heatmap_title: &'static str,
+    pub all_markets: &'static str,
+    /// Monday first
+    pub days: [&'static str; 7],
+    pub windows_title: &'static str,
+    /// {0} count
+    pub window_spreads: &'static str,
+    /// {0} average edge, {1} best edge
+    pub window_edges: &'static str,
+
+    // Approvals
+    pub approvals_empty_title: &'static str,
+    /// {0} pending count
+    pub approvals_title: &'static str,
+
+    // System
+    pub resources_title: &'static str,
+    /// {0} uptime, {1} memory, {2} tokio tasks, {3} workers, {4} snapshot age
+    pub resources_line: &'static str,
+    pub no_system: &'static str,
+    pub venue_down: &'static str,
+    pub subsystems_title: &'static str,
+    pub subsystem_running: &'static str,
+    pub subsystem_stopped: &'static str,
+    /// {0} count
+    pub restarts: &'static str,
+    pub streams_title: &'static str,
+    pub no_streams: &'static str,
+    pub queues_title: &'static str,
+
+    // Status bar
+    pub ready: &'static str,
+    pub tag_prompt: &'static str,
+    pub note_prompt: &'static str,
+    pub pin_prompt: &'static str,
+    pub balance_prompt: &'static str,
+    pub input_hint: &'static str,
+    pub quit: &'static str,
+    pub refresh: &'static str,
+    pub buy: &'static str,
+    pub sell: &'static str,
+
+    // Status messages
+    pub msg_ready: &'static str,
+    pub msg_observer: &'static str,
+    /// {0} speed
+    pub msg_replaying: &'static str,
+    pub msg_replay_only: &'static str,
+    pub msg_read_only: &'static str,
+    pub action_trade: &'static str,
+    pub action_go_live: &'static str,
+    pub action_reset_portfolio: &'static str,
+    pub action_reset_breaker: &'static str,
+    /// {0} action, {1} required role, {2} TUI_ROLE
+    pub msg_not_permitted: &'static str,
+    pub msg_wrong_pin: &'static str,
+    /// {0} portfolio, {1} balance
+    pub msg_portfolio_reset: &'static str,
+    pub msg_system_tab: &'static str,
+    pub msg_invalid_balance: &'static str,
+    /// {0} error
+    pub msg_reset_failed: &'static str,
+    pub msg_breaker_cleared: &'static str,
+    pub msg_breaker_not_tripped: &'static str,
+    pub msg_replay_paused: &'static str,
+    pub msg_replay_resumed: &'static str,
+    /// {0} icon, {1} speed, {2} tape time, {3} progress bar, {4} fills, {5} missed, {6} P&L
+    pub msg_replay_progress: &'static str,
+    /// {0} error
+    pub msg_replay_fill_failed: &'static str,
+    pub msg_reloaded: &'static str,
+    pub msg_refreshing: &'static str,
+    /// {0} percent, {1} market, {2} price, {3} P&L
+    pub msg_closed: &'static str,
+    /// {0} error
+    pub msg_close_failed: &'static str,
+    pub msg_stop_first: &'static str,
+    /// {0} market, {1} stop price
+    pub msg_stop_set: &'static str,
+    /// {0} market
+    pub msg_stop_cleared: &'static str,
+    /// {0} market
+    pub msg_selected: &'static str,
+    /// {0} strategy
+    pub msg_strategy_enabled: &'static str,
+    /// {0} strategy
+    pub msg_strategy_disabled: &'static str,
+    pub msg_approvals_tab: &'static str,
+    pub msg_no_intents: &'static str,
+    /// {0} intent ID
+    pub msg_approved: &'static str,
+    /// {0} intent ID
+    pub msg_rejected: &'static str,
+    pub msg_trades_tab: &'static str,
+    pub msg_no_trade: &'static str,
+    /// {0} tag
+    pub msg_tag_removed: &'static str,
+    /// {0} tag
+    pub msg_tagged: &'static str,
+    pub msg_note_saved: &'static str,
+    pub msg_note_cleared: &'static str,
+    /// {0} portfolio
+    pub msg_portfolio: &'static str,
+    pub msg_no_portfolios: &'static str,
+    /// {0} tag
+    pub msg_filter_tag: &'static str,
+    pub msg_filter_all: &'static str,
+    pub msg_no_tags: &'static str,
+    pub msg_buy_tab: &'static str,
+    pub msg_sell_tab: &'static str,
+    pub msg_mute_tab: &'static str,
+    pub msg_muted: &'static str,
+    pub msg_unmuted: &'static str,
+    pub msg_snoozed: &'static str,
+    pub msg_unsnoozed: &'static str,
+    pub msg_mute_failed: &'static str,
+    pub msg_no_ladders: &'static str,
+    /// {0} event ticker
+    pub msg_ladder: &'static str,
+    /// {0} amount, {1} market, {2} price
+    pub msg_bought: &'static str,
+    /// {0} error
+    pub msg_buy_failed: &'static str,
+    pub msg_bought_book: &'static str,
+    pub msg_sold_book: &'static str,
+    /// {0} icon, {1} market, {2} P&L
+    pub msg_sold: &'static str,
+    /// {0} error
+    pub msg_sell_failed: &'static str,
+}
+
+pub static EN: Strings = Strings {
+    balance: "Balance",
+    pnl: "P&L",
+    all_time_pnl: "All-time P&L",
+    session: "Session",
+    portfolio: "Portfolio",
+    venues: "Venues",
+    replay_badge: "⏪ REPLAY",
+    replay_paused_badge: "⏸️ REPLAY (paused)",
+    observer_badge: "👁 OBSERVER (read-only)",
+
+    navigation_title: " Navigation ",
+    tab_dashboard: "Dashboard",
+    tab_markets: "Markets",
+    tab_trades: "Trades",
+    tab_strategies: "Strategies",
+    tab_heatmap: "Heatmap",
+    tab_approvals: "Approvals",
+    tab_system: "System",
+
+    performance_title: " 📈 Performance ",
+    total_pnl: "Gross P&L:",
+    fees: "Fees:",
+    fee_breakdown: "{0} ({1}/trade, {2} of gross)",
+    net_pnl: "Net P&L:",
+    win_rate: "Win Rate:",
+    best_trade: "Best Trade:",
+    worst_trade: "Worst Trade:",
+    cash_balance: "Cash Balance:",
+    locked: "Locked:",
+    open: "open",
+    recent_trades_title: " 📋 Recent Trades ",
+    positions_title: " 🎯 Active Positions (Enter for detail) ",
+    system_title: " 🖥 System ",
+    fallback: "fallback {0}/{1}",
+    primary: "primary",
+    failovers: "  {0} failover(s), last {1}",
+    no_endpoints: "No endpoint status yet - start the bot",
+    top_traders_title: " 👥 Top Traders ",
+    alerts_title: " 🔔 Alerts ",
+
+    venue: "Venue:",
+    size: "Size:",
+    mark: "Mark:",
+    unrealized: "Unrealized:",
+    fees_paid: "Fees paid:",
+    stop: "Stop:",
+    copying: "Copying:",
+    detail_help: "1/2/3 close 25/50/100%  P set/clear stop  +/- move stop  Esc back",
+    opened: "Opened",
+    shares: "Shares",
+    price: "Price",
+    entry_lots_title: " Entry Lots ",
+    marks_title: " Marks (last {0}) ",
+    detail_trades_title: " Trades ",
+
+    col_market: "Market",
+    col_coin: "Coin",
+    col_poly: "Poly",
+    col_kalshi: "Kalshi",
+    col_spread: "Spread",
+    col_liquidity: "Liquidity",
+    col_time: "Time",
+    col_side: "Side",
+    col_size: "Size",
+    col_entry: "Entry",
+    col_exit: "Exit",
+    col_pnl: "P&L",
+    col_strategy: "Strategy",
+    col_tags: "Tags",
+    markets_title: " 🔄 Live Markets (↑↓ navigate, B=buy, S=sell, M=mute, Z=snooze, L=ladder, R=refresh) ",
+    ladder_title: " 🪜 {0} strike ladder ({1}/{2}) ",
+    fair_values_title: " ⚖️ Polymarket fair values ",
+    outcome_events_title: " 🗳 Multi-outcome events ({0}) ",
+    col_outcome: "Outcome",
+    col_yes: "YES",
+    col_strike: "Strike",
+    col_bid: "Bid",
+    col_ask: "Ask",
+    col_above: "P(above)",
+    col_fair: "Fair",
+    col_premium: "Premium",
+    trade_history_title: " 📜 Trade History ",
+    trade_history_tagged_title: " 📜 Trade History [{0}] ",
+
+    strategies_title: " ⚙️ Strategies (Enter to toggle) ",
+    bot_trades: "Bot today",
+    orders_today: " Orders today {0}/{1} ",
+    strategy_arbitrage: "Arbitrage",
+    strategy_copy_trading: "Copy Trading",
+    strategy_manual: "Manual",
+    strategy_off: "❌ OFF",
+    strategy_scheduled_off: "⏸️ SCHEDULED OFF",
+    strategy_shadow: "🕶️ SHADOW",
+    strategy_on: "✅ ON ",
+    strategy_live: "LIVE",
+    strategy_dry_run: "DRY RUN",
+    trades: "Trades",
+    help_title: " ❓ Help ",
+    help_shortcuts: "Keyboard Shortcuts:",
+    help_keys: &[
+        "  1-7    Switch tabs",
+        "  Tab    Next tab",
+        "  ↑/↓    Navigate list",
+        "  Enter  Select/Toggle, position detail",
+        "  1/2/3  Close 25/50/100% (detail)",
+        "  P, +/- Set/clear, move stop (detail)",
+        "  B      Paper Buy",
+        "  S      Paper Sell",
+        "  M/Z    Mute/Snooze market (Markets)",
+        "  L      Next strike ladder (Markets)",
+        "  R      Refresh data",
+        "  A/X    Approve/Reject intent",
+        "  G/N    Tag/Note trade (Trades)",
+        "  F      Filter trades by tag",
+        "  O      Next paper portfolio",
+        "  W      Reset paper portfolio (System)",
+        "  C      Clear tripped circuit breaker",
+        "  Q      Quit",
+    ],
+    help_strategies: "Strategies:",
+    help_strategy_lines: &[
+        "  Arbitrage:   Price discrepancies",
+        "  Copy Trade:  Mirror top traders",
+        "  Manual:      User-initiated trades",
+    ],
+    conflicts_title: " ⚔️ Strategy Conflicts ",
+    no_conflicts: "No strategies trading against each other",
+    conflict_skipped: "skipped",
+    conflict_netted: "netted to {0}",
+    conflict_allowed: "allowed",
+    conflict_versus: "{0} vs {1}  ",
+    inventory_title: " 📦 Inventory ",
+    no_inventory_cap: "No inventory cap set (INVENTORY_CAP)",
+    flat_inventory: "Flat on every question",
+    inventory_shift: "quotes {0}",
+    risk_title: " 🎲 Risk: {0}-day Monte Carlo ",
+    no_risk: "No closed trades to simulate yet",
+    risk_paths: "{0} paths, {1} trades/day",
+    expected_pnl: "Expected P&L",
+    median_pnl: "Median P&L",
+    p5_pnl: "5th percentile",
+    p95_pnl: "95th percentile",
+    var_cvar: "VaR 95 / CVaR",
+    drawdown: "Drawdown (avg/95)",
+    ruin: "P(ruin)",
+    ruin_below: "  below {0}",
+
+    heatmap_title: " 🔥 Spread Heatmap (ET) - {0} (↑/↓ to change) ",
+    all_markets: "All markets",
+    days: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    windows_title: " ⏰ Most Fruitful Windows ",
+    window_spreads: "{0} spreads  ",
+    window_edges: "avg {0}  max {1}",
+
+    approvals_empty_title: " ✋ Pending Approvals - none (bot queues intents when EXECUTION_MODE=semi-auto) ",
+    approvals_title: " ✋ Pending Approvals ({0}) - A approve, X reject ",
+
+    resources_title: " 🖥 Resources ",
+    resources_line: "Uptime {0}   Memory {1}   Tasks {2} on {3} workers   Updated {4} ago",
+    no_system: "No process health yet - start the bot",
+    venue_down: "⚠ {0} down since {1} - cross-venue detection suspended ({2})",
+    subsystems_title: " ⚙ Subsystems ",
+    subsystem_running: "running",
+    subsystem_stopped: "stopped",
+    restarts: "{0} restart(s)",
+    streams_title: " 📡 Websockets ",
+    no_streams: "No websockets connected",
+    queues_title: " 📥 Queues ",
+
+    ready: "Ready",
+    tag_prompt: "Tag (prefix - to remove)",
+    note_prompt: "Note",
+    pin_prompt: "PIN",
+    balance_prompt: "Reset to starting balance (old trades are archived)",
+    input_hint: "  Enter save  Esc cancel",
+    quit: "quit",
+    refresh: "refresh",
+    buy: "buy",
+    sell: "sell",
+
+    msg_ready: "Ready - Press 'h' for help",
+    msg_observer: "👁 Observer mode (read-only) - Press 'r' to reload",
+    msg_replaying: "⏪ Replaying at {0} - Space to pause",
+    msg_replay_only: "⏪ Replay: state comes from the tape - Space to pause",
+    msg_read_only: "🔒 Read-only observer: trading and settings are disabled",
+    action_trade: "Trading",
+    action_go_live: "Approving live orders",
+    action_reset_portfolio: "Resetting the portfolio",
+    action_reset_breaker: "Clearing the circuit breaker",
+    msg_not_permitted: "🔒 {0} needs the {1} role (TUI_ROLE is {2})",
+    msg_wrong_pin: "🔒 Wrong PIN",
+    msg_portfolio_reset: "🧹 Paper portfolio {0} reset to {1}, old trades archived",
+    msg_system_tab: "Switch to System tab to reset the paper portfolio",
+    msg_invalid_balance: "Starting balance must be a positive number",
+    msg_reset_failed: "⚠️ Portfolio not reset: {0}",
+    msg_breaker_cleared: "🟢 Circuit breaker cleared - restart the bot to resume trading",
+    msg_breaker_not_tripped: "Circuit breaker is not tripped",
+    msg_replay_paused: "⏸️ Replay paused",
+    msg_replay_resumed: "⏪ Replay resumed",
+    msg_replay_progress: "{0} Replay {1} · {2} {3} · {4} fills, {5} missed · P&L {6}",
+    msg_replay_fill_failed: "❌ Replayed fill not paper traded: {0}",
+    msg_reloaded: "👁 Reloaded bot state",
+    msg_refreshing: "Refreshing market data...",
+    msg_closed: "✅ Closed {0}% of {1} @ {2} ({3})",
+    msg_close_failed: "❌ Close failed: {0}",
+    msg_stop_first: "Press 'p' to set a stop first",
+    msg_stop_set: "🛑 Stop for {0} at {1}",
+    msg_stop_cleared: "Stop cleared for {0}",
+    msg_selected: "Selected: {0}",
+    msg_strategy_enabled: "{0} strategy enabled",
+    msg_strategy_disabled: "{0} strategy disabled",
+    msg_approvals_tab: "Switch to Approvals tab to approve or reject",
+    msg_no_intents: "No pending intents",
+    msg_approved: "✅ Approved intent {0}",
+    msg_rejected: "❌ Rejected intent {0}",
+    msg_trades_tab: "Switch to Trades tab to tag or annotate",
+    msg_no_trade: "No trade selected",
+    msg_tag_removed: "🏷 Removed tag {0}",
+    msg_tagged: "🏷 Tagged {0}",
+    msg_note_saved: "📝 Note saved",
+    msg_note_cleared: "📝 Note cleared",
+    msg_portfolio: "📁 Paper portfolio: {0}",
+    msg_no_portfolios: "No other portfolios - create one with `polybot profiles new NAME`",
+    msg_filter_tag: "Showing trades tagged {0}",
+    msg_filter_all: "Showing all trades",
+    msg_no_tags: "No tags yet - press G on a trade to add one",
+    msg_buy_tab: "Switch to Markets tab to buy",
+    msg_sell_tab: "Switch to Markets tab to sell",
+    msg_mute_tab: "Switch to Markets tab to mute or snooze",
+    msg_muted: "🔇 Muted {0}: no more trades or alerts until unmuted",
+    msg_unmuted: "🔊 Unmuted {0}",
+    msg_snoozed: "💤 Alerts for {0} snoozed for {1}h",
+    msg_unsnoozed: "🔔 Alerts for {0} resumed",
+    msg_mute_failed: "❌ Could not save mutes: {0}",
+    msg_no_ladders: "No Kalshi strike ladders yet - the bot reads them every minute",
+    msg_ladder: "🪜 Showing the {0} ladder",
+    msg_bought: "✅ Bought {0} of {1} @ {2}",
+    msg_buy_failed: "❌ Buy failed: {0}",
+    msg_bought_book: "✅ Bought {0} of {1} @ {2} avg across {3} levels ({4} fees)",
+    msg_sold_book: "{0} Sold {1} @ {2} avg across {3} levels for {4} P&L",
+    msg_sold: "{0} Sold {1} for {2} P&L",
+    msg_sell_failed: "❌ Sell failed: {0}",
+};
+
+pub static ES: Strings = Strings {
+    balance: "Saldo",
+    pnl: "G/P",
+    all_time_pnl: "G/P histórica",
+    session: "Sesión",
+    portfolio: "Cartera",
+    venues: "Mercados",
+    replay_badge: "⏪ REPRODUCCIÓN",
+    replay_paused_badge: "⏸️ REPRODUCCIÓN (en pausa)",
+    observer_badge: "👁 OBSERVADOR (solo lectura)",
+
+    navigation_title: " Navegación ",
+    tab_dashboard: "Panel",
+    tab_markets: "Mercados",
+    tab_trades: "Operaciones",
+    tab_strategies: "Estrategias",
+    tab_heatmap: "Mapa de calor",
+    tab_approvals: "Aprobaciones",
+    tab_system: "Sistema",
+
+    performance_title: " 📈 Rendimiento ",
+    total_pnl: "G/P bruta:",
+    fees: "Comisiones:",
+    fee_breakdown: "{0} ({1}/op., {2} de la bruta)",
+    net_pnl: "G/P neta:",
+    win_rate: "Aciertos:",
+    best_trade: "Mejor op.:",
+    worst_trade: "Peor op.:",
+    cash_balance: "Efectivo:",
+    locked: "Bloqueado:",
+    open: "abierta",
+    recent_trades_title: " 📋 Operaciones recientes ",
+    positions_title: " 🎯 Posiciones abiertas (Enter para detalle) ",
+    system_title: " 🖥 Sistema ",
+    fallback: "respaldo {0}/{1}",
+    primary: "principal",
+    failovers: "  {0} conmutación(es), última {1}",
+    no_endpoints: "Sin estado de endpoints todavía - inicia el bot",
+    top_traders_title: " 👥 Mejores traders ",
+    alerts_title: " 🔔 Alertas ",
+
+    venue: "Mercado:",
+    size: "Tamaño:",
+    mark: "Marca:",
+    unrealized: "No realizado:",
+    fees_paid: "Comisiones:",
+    stop: "Stop:",
+    copying: "Copiando:",
+    detail_help: "1/2/3 cerrar 25/50/100%  P poner/quitar stop  +/- mover stop  Esc volver",
+    opened: "Apertura",
+    shares: "Acciones",
+    price: "Precio",
+    entry_lots_title: " Lotes de entrada ",
+    marks_title: " Marcas (últimas {0}) ",
+    detail_trades_title: " Operaciones ",
+
+    col_market: "Mercado",
+    col_coin: "Moneda",
+    col_poly: "Poly",
+    col_kalshi: "Kalshi",
+    col_spread: "Diferencial",
+    col_liquidity: "Liquidez",
+    col_time: "Tiempo",
+    col_side: "Lado",
+    col_size: "Tamaño",
+    col_entry: "Entrada",
+    col_exit: "Salida",
+    col_pnl: "G/P",
+    col_strategy: "Estrategia",
+    col_tags: "Etiquetas",
+    markets_title: " 🔄 Mercados en vivo (↑↓ navegar, B=comprar, S=vender, M=silenciar, Z=posponer, L=escalera, R=actualizar) ",
+    ladder_title: " 🪜 Escalera de strikes de {0} ({1}/{2}) ",
+    fair_values_title: " ⚖️ Valor justo en Polymarket ",
+    outcome_events_title: " 🗳 Eventos con varios resultados ({0}) ",
+    col_outcome: "Resultado",
+    col_yes: "SÍ",
+    col_strike: "Strike",
+    col_bid: "Compra",
+    col_ask: "Venta",
+    col_above: "P(encima)",
+    col_fair: "Justo",
+    col_premium: "Prima",
+    trade_history_title: " 📜 Historial de operaciones ",
+    trade_history_tagged_title: " 📜 Historial de operaciones [{0}] ",
+
+    strategies_title: " ⚙️ Estrategias (Enter para activar/desactivar) ",
+    bot_trades: "Bot hoy",
+    orders_today: " Órdenes hoy {0}/{1} ",
+    strategy_arbitrage: "Arbitraje",
+    strategy_copy_trading: "Copy trading",
+    strategy_manual: "Manual",
+    strategy_off: "❌ NO",
+    strategy_scheduled_off: "⏸️ FUERA DE HORARIO",
+    strategy_shadow: "🕶️ SOMBRA",
+    strategy_on: "✅ SÍ ",
+    strategy_live: "REAL",
+    strategy_dry_run: "SIMULADO",
+    trades: "Operaciones",
+    help_title: " ❓ Ayuda ",
+    help_shortcuts: "Atajos de teclado:",
+    help_keys: &[
+        "  1-7    Cambiar de pestaña",
+        "  Tab    Pestaña siguiente",
+        "  ↑/↓    Moverse por la lista",
+        "  Enter  Seleccionar/Alternar, detalle de posición",
+        "  1/2/3  Cerrar 25/50/100% (detalle)",
+        "  P, +/- Poner/quitar, mover stop (detalle)",
+        "  B      Compra simulada",
+        "  S      Venta simulada",
+        "  M/Z    Silenciar/Posponer mercado (Mercados)",
+        "  L      Siguiente escalera de strikes (Mercados)",
+        "  R      Actualizar datos",
+        "  A/X    Aprobar/Rechazar intención",
+        "  G/N    Etiquetar/Anotar operación (Operaciones)",
+        "  F      Filtrar operaciones por etiqueta",
+        "  O      Siguiente cartera simulada",
+        "  W      Reiniciar cartera simulada (Sistema)",
+        "  C      Rearmar cortacircuitos disparado",
+        "  Q      Salir",
+    ],
+    help_strategies: "Estrategias:",
+    help_strategy_lines: &[
+        "  Arbitraje:     Diferencias de precio",
+        "  Copy trading:  Replicar a los mejores traders",
+        "  Manual:        Operaciones del usuario",
+    ],
+    conflicts_title: " ⚔️ Conflictos entre estrategias ",
+    no_conflicts: "Ninguna estrategia opera contra otra",
+    conflict_skipped: "omitida",
+    conflict_netted: "compensada a {0}",
+    conflict_allowed: "permitida",
+    conflict_versus: "{0} contra {1}  ",
+    inventory_title: " 📦 Inventario ",
+    no_inventory_cap: "Sin límite de inventario (INVENTORY_CAP)",
+    flat_inventory: "Sin inventario en ninguna pregunta",
+    inventory_shift: "cotizaciones {0}",
+    risk_title: " 🎲 Riesgo: Monte Carlo a {0} días ",
+    no_risk: "Aún no hay operaciones cerradas que simular",
+    risk_paths: "{0} trayectorias, {1} operaciones/día",
+    expected_pnl: "G/P esperada",
+    median_pnl: "G/P mediana",
+    p5_pnl: "Percentil 5",
+    p95_pnl: "Percentil 95",
+    var_cvar: "VaR 95 / CVaR",
+    drawdown: "Caída (media/95)",
+    ruin: "P(ruina)",
+    ruin_below: "  por debajo de {0}",
+
+    heatmap_title: " 🔥 Mapa de diferenciales (ET) - {0} (↑/↓ para cambiar) ",
+    all_markets: "Todos los mercados",
+    days: ["Lun", "Mar", "Mié", "Jue", "Vie", "Sáb", "Dom"],
+    windows_title: " ⏰ Franjas más rentables ",
+    window_spreads: "{0} diferenciales  ",
+    window_edges: "media {0}  máx {1}",
+
+    approvals_empty_title: " ✋ Aprobaciones pendientes - ninguna (el bot las encola con EXECUTION_MODE=semi-auto) ",
+    approvals_title: " ✋ Aprobaciones pendientes ({0}) - A aprobar, X rechazar ",
+
+    resources_title: " 🖥 Recursos ",
+    resources_line: "Activo {0}   Memoria {1}   Tareas {2} en {3} hilos   Actualizado hace {4}",
+    no_system: "Sin estado del proceso todavía - inicia el bot",
+    venue_down: "⚠ {0} caído desde {1} - detección entre plataformas suspendida ({2})",
+    subsystems_title: " ⚙ Subsistemas ",
+    subsystem_running: "en marcha",
+    subsystem_stopped: "detenido",
+    restarts: "{0} reinicio(s)",
+    streams_title: " 📡 Websockets ",
+    no_streams: "Sin websockets conectados",
+    queues_title: " 📥 Colas ",
+
+    ready: "Listo",
+    tag_prompt: "Etiqueta (prefijo - para quitar)",
+    note_prompt: "Nota",
+    pin_prompt: "PIN",
+    balance_prompt: "Reiniciar con saldo inicial (las operaciones se archivan)",
+    input_hint: "  Enter guardar  Esc cancelar",
+    quit: "salir",
+    refresh: "actualizar",
+    buy: "comprar",
+    sell: "vender",
+
+    msg_ready: "Listo - Pulsa 'h' para ver la ayuda",
+    msg_observer: "👁 Modo observador (solo lectura) - Pulsa 'r' para recargar",
+    msg_replaying: "⏪ Reproduciendo a {0} - Espacio para pausar",
+    msg_replay_only: "⏪ Reproducción: el estado viene de la grabación - Espacio para pausar",
+    msg_read_only: "🔒 Observador de solo lectura: operaciones y ajustes desactivados",
+    action_trade: "Operar",
+    action_go_live: "Aprobar órdenes reales",
+    action_reset_portfolio: "Reiniciar la cartera",
+    action_reset_breaker: "Rearmar el cortacircuitos",
+    msg_not_permitted: "🔒 {0} requiere el rol {1} (TUI_ROLE es {2})",
+    msg_wrong_pin: "🔒 PIN incorrecto",
+    msg_portfolio_reset: "🧹 Cartera simulada {0} reiniciada a {1}, operaciones archivadas",
+    msg_system_tab: "Ve a la pestaña Sistema para reiniciar la cartera simulada",
+    msg_invalid_balance: "El saldo inicial debe ser un número positivo",
+    msg_reset_failed: "⚠️ Cartera no reiniciada: {0}",
+    msg_breaker_cleared: "🟢 Cortacircuitos rearmado - reinicia el bot para volver a operar",
+    msg_breaker_not_tripped: "El cortacircuitos no está disparado",
+    msg_replay_paused: "⏸️ Reproducción en pausa",
+    msg_replay_resumed: "⏪ Reproducción reanudada",
+    msg_replay_progress: "{0} Reproducción {1} · {2} {3} · {4} ejecuciones, {5} perdidas · G/P {6}",
+    msg_replay_fill_failed: "❌ Ejecución reproducida no simulada: {0}",
+    msg_reloaded: "👁 Estado del bot recargado",
+    msg_refreshing: "Actualizando datos de mercado...",
+    msg_closed: "✅ Cerrado {0}% de {1} @ {2} ({3})",
+    msg_close_failed: "❌ Error al cerrar: {0}",
+    msg_stop_first: "Pulsa 'p' para poner un stop primero",
+    msg_stop_set: "🛑 Stop de {0} en {1}",
+    msg_stop_cleared: "Stop quitado de {0}",
+    msg_selected: "Seleccionado: {0}",
+    msg_strategy_enabled: "Estrategia {0} activada",
+    msg_strategy_disabled: "Estrategia {0} desactivada",
+    msg_approvals_tab: "Ve a la pestaña Aprobaciones para aprobar o rechazar",
+    msg_no_intents: "No hay intenciones pendientes",
+    msg_approved: "✅ Intención {0} aprobada",
+    msg_rejected: "❌ Intención {0} rechazada",
+    msg_trades_tab: "Ve a la pestaña Operaciones para etiquetar o anotar",
+    msg_no_trade: "Ninguna operación seleccionada",
+    msg_tag_removed: "🏷 Etiqueta {0} quitada",
+    msg_tagged: "🏷 Etiquetada {0}",
+    msg_note_saved: "📝 Nota guardada",
+    msg_note_cleared: "📝 Nota borrada",
+    msg_portfolio: "📁 Cartera simulada: {0}",
+    msg_no_portfolios: "No hay otras carteras - crea una con `polybot profiles new NOMBRE`",
+    msg_filter_tag: "Mostrando operaciones con la etiqueta {0}",
+    msg_filter_all: "Mostrando todas las operaciones",
+    msg_no_tags: "Aún no hay etiquetas - pulsa G sobre una operación para añadir una",
+    msg_buy_tab: "Ve a la pestaña Mercados para comprar",
+    msg_sell_tab: "Ve a la pestaña Mercados para vender",
+    msg_mute_tab: "Ve a la pestaña Mercados para silenciar o posponer",
+    msg_muted: "🔇 {0} silenciado: sin operaciones ni alertas hasta reactivarlo",
+    msg_unmuted: "🔊 {0} reactivado",
+    msg_snoozed: "💤 Alertas de {0} pospuestas {1}h",
+    msg_unsnoozed: "🔔 Alertas de {0} reanudadas",
+    msg_mute_failed: "❌ No se pudieron guardar los silencios: {0}",
+    msg_no_ladders: "Aún no hay escaleras de Kalshi - el bot las lee cada minuto",
+    msg_ladder: "🪜 Mostrando la escalera de {0}",
+    msg_bought: "✅ Comprado {0} de {1} @ {2}",
+    msg_buy_failed: "❌ Error al comprar: {0}",
+    msg_bought_book: "✅ Comprado {0} de {1} @ {2} promedio en {3} niveles ({4} en comisiones)",
+    msg_sold_book: "{0} Vendido {1} @ {2} promedio en {3} niveles, G/P {4}",
+    msg_sold: "{0} Vendido {1} con {2} de G/P",
+    msg_sell_failed: "❌ Error al vender: {0}",
+};
+
+/// Use `locale` for the rest of the process. Strings looked up before
+/// this are English.
+pub fn init(locale: Locale) {
+    GLOBAL.set(locale).ok();
+}
+
+/// The strings of the configured locale.
+pub fn strings() -> &'static Strings {
+    GLOBAL.get().copied().unwrap_or_default().strings()
+}
+
+/// Replace `{0}`, `{1}`, ... in `template` with `args`. Placeholders
+/// without a matching argument are left as written.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after
+            .find('}')
+            .and_then(|end| Some((end, after[..end].parse::<usize>().ok()?)))
+            .and_then(|(end, index)| Some((end, args.get(index)?)));
+        match arg {
+            Some((end, arg)) => {
+                out.push_str(&arg.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<String> {
+        let mut found: Vec<String> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|s| s.split_once('}').map(|(p, _)| p.to_string()))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_fill_and_translations_agree() {
+        assert_eq!(fill(EN.msg_closed, &[&50, &"BTC", &"0.505", &"+$1.00"]), "✅ Closed 50% of BTC @ 0.505 (+$1.00)");
+        assert_eq!(fill("{1} before {0}", &[&"a", &"b"]), "b before a");
+        assert_eq!(fill("{0} and {3} {x}", &[&1]), "1 and {3} {x}");
+
+        assert_eq!("es_MX.UTF-8".parse::<Locale>().unwrap(), Locale::Es);
+        assert_eq!("EN".parse::<Locale>().unwrap(), Locale::En);
+        assert!("fr".parse::<Locale>().is_err());
+
+        // Every translation takes the same values as the English template
+        let templates = |s: &'static Strings| [
+            s.fallback, s.failovers, s.marks_title, s.trade_history_tagged_title, s.conflict_netted,
+            s.conflict_versus, s.inventory_shift, s.risk_title, s.risk_paths, s.ruin_below, s.heatmap_title, s.window_spreads,
+            s.window_edges, s.approvals_title, s.msg_replaying, s.msg_replay_progress, s.msg_replay_fill_failed,
+            s.msg_closed, s.msg_close_failed, s.msg_stop_set, s.msg_stop_cleared, s.msg_selected,
+            s.msg_strategy_enabled, s.msg_strategy_disabled, s.msg_approved, s.msg_rejected, s.msg_tag_removed,
+            s.msg_tagged, s.msg_portfolio, s.msg_filter_tag, s.msg_bought, s.msg_buy_failed, s.msg_sold,
+            s.msg_sell_failed, s.msg_bought_book, s.msg_sold_book, s.msg_muted, s.msg_unmuted, s.msg_snoozed, s.msg_unsnoozed, s.msg_mute_failed,
+            s.ladder_title, s.outcome_events_title, s.msg_ladder, s.msg_not_permitted, s.msg_portfolio_reset, s.msg_reset_failed, s.orders_today, s.resources_line, s.venue_down, s.restarts,
+        ];
+        for (en, es) in templates(&EN).into_iter().zip(templates(&ES)) {
+            assert!(!placeholders(en).is_empty(), "{}", en);
+            assert_eq!(placeholders(en), placeholders(es), "{} / {}", en, es);
+        }
+        assert_eq!(EN.help_keys.len(), ES.help_keys.len());
+        assert_eq!(EN.help_strategy_lines.len(), ES.help_strategy_lines.len());
+    }
+}
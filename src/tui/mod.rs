@@ -3,5 +3,54 @@
 pub mod app;
 pub mod ui;
 pub mod events;
+pub mod i18n;
 
 pub use app::App;
+
+use std::io;
+use std::time::Duration;
+
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+/// Run the terminal UI until the user quits, returning the final state.
+pub fn run(mut app: App) -> io::Result<App> {
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Main loop
+    loop {
+        // Draw UI
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        // Handle events with 100ms timeout
+        if let Some(event) = events::poll_event(Duration::from_millis(100)) {
+            events::handle_key_event(&mut app, event);
+        }
+        app.on_tick();
+
+        // Check if we should quit
+        if app.should_quit {
+            break;
+        }
+    }
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    Ok(app)
+}
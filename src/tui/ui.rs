@@ -4,11 +4,19 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, Tabs},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Sparkline, Table, Tabs},
     Frame,
 };
 
-use super::app::{App, Tab};
+use super::app::{App, InputKind, Tab};
+use super::i18n::{self, fill};
+use crate::analysis::ladder::LadderSnapshot;
+use crate::display;
+use crate::money::Money;
+use crate::monitor::system::StreamState;
+use crate::paper_trading::profiles::DEFAULT_PROFILE;
+use crate::risk::ConflictPolicy;
+use crate::utils::timezone;
 
 /// Main UI rendering function.
 pub fn draw(frame: &mut Frame, app: &App) {
@@ -30,21 +38,49 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
 fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
     let summary = app.engine.summary();
+    let s = i18n::strings();
     
     let pnl_color = if summary.total_pnl >= 0.0 { Color::Green } else { Color::Red };
     let pnl_sign = if summary.total_pnl >= 0.0 { "+" } else { "" };
     
-    let header_text = vec![
+    let mut header_text = vec![
         Span::styled("📊 ", Style::default()),
         Span::styled("Polymarket-Kalshi Arbitrage Bot", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-        Span::raw("  │  Balance: "),
-        Span::styled(format!("${:.2}", summary.total_value), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        Span::raw("  │  P&L: "),
+        Span::raw(format!("  │  {}: ", s.balance)),
+        Span::styled(app.money.fmt(summary.total_value), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+        Span::raw(format!("  │  {}: ", s.all_time_pnl)),
         Span::styled(
-            format!("{}${:.2} ({}{:.1}%)", pnl_sign, summary.total_pnl.abs(), pnl_sign, summary.pnl_percent),
+            format!("{} ({}{:.1}%)", app.money.fmt_signed(summary.total_pnl), pnl_sign, summary.pnl_percent),
             Style::default().fg(pnl_color).add_modifier(Modifier::BOLD)
         ),
+        Span::raw(format!("  │  {}: ", s.session)),
+        Span::styled(
+            app.money.fmt_signed(summary.session_pnl),
+            Style::default().fg(if summary.session_pnl >= 0.0 { Color::Green } else { Color::Red }),
+        ),
     ];
+    if app.engine.profile.name != DEFAULT_PROFILE {
+        header_text.push(Span::raw(format!("  │  {}: ", s.portfolio)));
+        header_text.push(Span::styled(app.engine.profile.name.clone(), Style::default().fg(Color::Magenta)));
+    }
+    if app.balances.fetched_at.is_some() {
+        let venue = |balance: Option<Money>| {
+            balance.map(|b| app.money.fmt(b.amount)).unwrap_or_else(|| "-".to_string())
+        };
+        header_text.push(Span::raw(format!("  │  {}: ", s.venues)));
+        header_text.push(Span::styled(
+            format!("P {}  K {}  W {}", venue(app.balances.polymarket), venue(app.balances.kalshi), venue(app.balances.polygon_usdc)),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if app.replay.is_some() {
+        header_text.push(Span::raw("  │  "));
+        let label = if app.replay_paused { s.replay_paused_badge } else { s.replay_badge };
+        header_text.push(Span::styled(label, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+    } else if app.read_only {
+        header_text.push(Span::raw("  │  "));
+        header_text.push(Span::styled(s.observer_badge, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+    }
 
     let header = Paragraph::new(Line::from(header_text))
         .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
@@ -53,7 +89,7 @@ fn draw_header(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let titles: Vec<Line> = [Tab::Dashboard, Tab::Markets, Tab::Trades, Tab::Strategies]
+    let titles: Vec<Line> = [Tab::Dashboard, Tab::Markets, Tab::Trades, Tab::Strategies, Tab::Heatmap, Tab::Approvals, Tab::System]
         .iter()
         .enumerate()
         .map(|(i, t)| {
@@ -67,7 +103,7 @@ fn draw_tabs(frame: &mut Frame, app: &App, area: Rect) {
         .collect();
 
     let tabs = Tabs::new(titles)
-        .block(Block::default().borders(Borders::ALL).title(" Navigation "))
+        .block(Block::default().borders(Borders::ALL).title(i18n::strings().navigation_title))
         .highlight_style(Style::default().fg(Color::Yellow))
         .divider(" │ ");
     
@@ -80,23 +116,31 @@ fn draw_content(frame: &mut Frame, app: &App, area: Rect) {
         Tab::Markets => draw_markets(frame, app, area),
         Tab::Trades => draw_trades(frame, app, area),
         Tab::Strategies => draw_strategies(frame, app, area),
+        Tab::Heatmap => draw_heatmap(frame, app, area),
+        Tab::Approvals => draw_approvals(frame, app, area),
+        Tab::System => draw_system_tab(frame, app, area),
     }
 }
 
 fn draw_dashboard(frame: &mut Frame, app: &App, area: Rect) {
+    if app.position_detail.is_some() {
+        return draw_position_detail(frame, app, area);
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
+    let system_height = app.endpoints.len().max(1) as u16 + 2;
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(50), Constraint::Min(5), Constraint::Length(system_height)])
         .split(chunks[0]);
 
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(30), Constraint::Percentage(30)])
         .split(chunks[1]);
 
     // Performance block
@@ -104,60 +148,92 @@ fn draw_dashboard(frame: &mut Frame, app: &App, area: Rect) {
     
     // Recent trades block
     draw_recent_trades(frame, app, left_chunks[1]);
+
+    // Active endpoints block
+    draw_system(frame, app, left_chunks[2]);
     
     // Active positions block
     draw_positions(frame, app, right_chunks[0]);
     
     // Top traders block
     draw_top_traders(frame, app, right_chunks[1]);
+
+    // Alerts block
+    draw_alerts(frame, app, right_chunks[2]);
 }
 
 fn draw_performance(frame: &mut Frame, app: &App, area: Rect) {
     let summary = app.engine.summary();
+    let s = i18n::strings();
     let pnl_color = if summary.total_pnl >= 0.0 { Color::Green } else { Color::Red };
     
     let text = vec![
         Line::from(vec![
-            Span::raw("Total P&L:     "),
+            Span::raw(format!("{:<15}", s.total_pnl)),
             Span::styled(
-                format!("${:.2} ({:.1}%)", summary.total_pnl, summary.pnl_percent),
+                format!("{} ({:.1}%)", app.money.fmt(summary.total_pnl), summary.pnl_percent),
                 Style::default().fg(pnl_color).add_modifier(Modifier::BOLD)
             ),
         ]),
         Line::from(vec![
-            Span::raw("Win Rate:      "),
+            Span::raw(format!("{:<15}", s.fees)),
+            Span::styled(
+                fill(s.fee_breakdown, &[
+                    &app.money.fmt(summary.fees_paid),
+                    &app.money.fmt(summary.avg_fee_per_trade),
+                    &summary.fee_share().map(display::percent).unwrap_or_else(|| "-".to_string()),
+                ]),
+                Style::default().fg(Color::DarkGray)
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw(format!("{:<15}", s.net_pnl)),
+            Span::styled(
+                app.money.fmt(summary.net_pnl),
+                Style::default().fg(if summary.net_pnl >= 0.0 { Color::Green } else { Color::Red }).add_modifier(Modifier::BOLD)
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw(format!("{:<15}", s.win_rate)),
             Span::styled(
                 format!("{:.0}% ({}/{})", summary.win_rate * 100.0, summary.wins, summary.total_trades),
                 Style::default().fg(Color::Cyan)
             ),
         ]),
         Line::from(vec![
-            Span::raw("Best Trade:    "),
+            Span::raw(format!("{:<15}", s.best_trade)),
             Span::styled(
-                format!("${:.2}", summary.best_trade_pnl.unwrap_or(0.0)),
+                app.money.fmt(summary.best_trade_pnl.unwrap_or(0.0)),
                 Style::default().fg(Color::Green)
             ),
         ]),
         Line::from(vec![
-            Span::raw("Worst Trade:   "),
+            Span::raw(format!("{:<15}", s.worst_trade)),
             Span::styled(
-                format!("${:.2}", summary.worst_trade_pnl.unwrap_or(0.0)),
+                app.money.fmt(summary.worst_trade_pnl.unwrap_or(0.0)),
                 Style::default().fg(Color::Red)
             ),
         ]),
         Line::from(vec![
-            Span::raw("Cash Balance:  "),
+            Span::raw(format!("{:<15}", s.cash_balance)),
             Span::styled(
-                format!("${:.2}", summary.cash_balance),
+                app.money.fmt(summary.cash_balance),
                 Style::default().fg(Color::Yellow)
             ),
         ]),
+        Line::from(vec![
+            Span::raw(format!("{:<15}", s.locked)),
+            Span::styled(
+                app.money.fmt(summary.locked_collateral),
+                Style::default().fg(Color::DarkGray)
+            ),
+        ]),
     ];
 
     let block = Paragraph::new(text)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(" 📈 Performance ")
+            .title(s.performance_title)
             .border_style(Style::default().fg(Color::Blue)));
     
     frame.render_widget(block, area);
@@ -165,6 +241,7 @@ fn draw_performance(frame: &mut Frame, app: &App, area: Rect) {
 
 fn draw_recent_trades(frame: &mut Frame, app: &App, area: Rect) {
     let trades = app.recent_trades();
+    let s = i18n::strings();
     
     let items: Vec<ListItem> = trades.iter().take(5).map(|trade| {
         let (icon, color) = if trade.is_profitable() {
@@ -175,13 +252,13 @@ fn draw_recent_trades(frame: &mut Frame, app: &App, area: Rect) {
             ("⏳", Color::Yellow)
         };
         
-        let pnl_str = trade.pnl.map(|p| format!("{:+.2}", p)).unwrap_or_else(|| "open".to_string());
+        let pnl_str = trade.pnl.map(|p| app.money.fmt_signed(p)).unwrap_or_else(|| s.open.to_string());
         
         ListItem::new(Line::from(vec![
             Span::raw(format!("{} ", icon)),
             Span::styled(&trade.market, Style::default().fg(Color::White)),
             Span::raw(" "),
-            Span::styled(format!("${}", pnl_str), Style::default().fg(color)),
+            Span::styled(pnl_str, Style::default().fg(color)),
             Span::raw(" "),
             Span::styled(&trade.strategy, Style::default().fg(Color::DarkGray)),
         ]))
@@ -190,7 +267,7 @@ fn draw_recent_trades(frame: &mut Frame, app: &App, area: Rect) {
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(" 📋 Recent Trades ")
+            .title(s.recent_trades_title)
             .border_style(Style::default().fg(Color::Magenta)));
     
     frame.render_widget(list, area);
@@ -199,30 +276,147 @@ fn draw_recent_trades(frame: &mut Frame, app: &App, area: Rect) {
 fn draw_positions(frame: &mut Frame, app: &App, area: Rect) {
     let positions = app.open_positions();
     
-    let items: Vec<ListItem> = positions.iter().map(|(_, pos)| {
+    let items: Vec<ListItem> = positions.iter().enumerate().map(|(i, (_, pos))| {
         let pnl_color = if pos.unrealized_pnl >= 0.0 { Color::Green } else { Color::Red };
-        
+        let style = if i == app.selected_index {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+
         ListItem::new(Line::from(vec![
             Span::styled(&pos.market, Style::default().fg(Color::White)),
             Span::raw(": "),
-            Span::styled(format!("${:.0}", pos.size * pos.avg_price), Style::default().fg(Color::Yellow)),
+            Span::styled(app.money.fmt(pos.size * pos.avg_price), Style::default().fg(Color::Yellow)),
             Span::raw(" @ "),
             Span::styled(format!("{:.2}", pos.avg_price), Style::default().fg(Color::Cyan)),
             Span::raw(" ("),
             Span::styled(format!("{:+.2}", pos.unrealized_pnl), Style::default().fg(pnl_color)),
             Span::raw(")"),
-        ]))
+        ])).style(style)
     }).collect();
 
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(" 🎯 Active Positions ")
+            .title(i18n::strings().positions_title)
             .border_style(Style::default().fg(Color::Green)));
     
     frame.render_widget(list, area);
 }
 
+fn draw_position_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let Some((pos, trades)) = app.detail() else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(11), Constraint::Min(5)])
+        .split(chunks[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Min(5)])
+        .split(chunks[1]);
+
+    let s = i18n::strings();
+    let pnl_color = if pos.unrealized_pnl >= 0.0 { Color::Green } else { Color::Red };
+    let field = |label: &str, value: String, color: Color| Line::from(vec![
+        Span::raw(format!("{:<14}", label)),
+        Span::styled(value, Style::default().fg(color)),
+    ]);
+    let summary = vec![
+        field(s.venue, pos.platform.clone(), Color::White),
+        field(s.size, format!("{:.2} @ {}", pos.size, display::price(pos.avg_price)), Color::Cyan),
+        field(s.mark, display::price(pos.current_price), Color::Yellow),
+        field(s.unrealized, app.money.fmt_signed(pos.unrealized_pnl), pnl_color),
+        field(s.fees_paid, app.money.fmt(pos.fees_paid), Color::DarkGray),
+        field(s.stop, pos.stop_price.map(display::price).unwrap_or_else(|| "-".to_string()), Color::Red),
+        field(s.copying, pos.source.clone().unwrap_or_else(|| "-".to_string()), Color::Magenta),
+        Line::from(""),
+        Line::from(Span::styled(
+            s.detail_help,
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    frame.render_widget(
+        Paragraph::new(summary).block(Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" 🎯 {} ", pos.market))
+            .border_style(Style::default().fg(Color::Green))),
+        left[0],
+    );
+
+    let lots: Vec<Row> = pos.lots.iter().map(|lot| Row::new(vec![
+        Cell::from(timezone::format(lot.opened_at, "%m-%d %H:%M")),
+        Cell::from(format!("{:.2}", lot.shares)),
+        Cell::from(display::price(lot.price)),
+    ])).collect();
+    let lots = Table::new(lots, [Constraint::Length(12), Constraint::Length(10), Constraint::Length(8)])
+        .header(Row::new(vec![s.opened, s.shares, s.price]).style(Style::default().fg(Color::Yellow)))
+        .block(Block::default().borders(Borders::ALL).title(s.entry_lots_title));
+    frame.render_widget(lots, left[1]);
+
+    // Sparkline needs integers: plot marks in tenths of a cent
+    let marks: Vec<u64> = pos.mark_history.iter().map(|p| (p * 1000.0).round() as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(fill(s.marks_title, &[&marks.len()])))
+        .data(&marks)
+        .style(Style::default().fg(pnl_color));
+    frame.render_widget(sparkline, right[0]);
+
+    let items: Vec<ListItem> = trades.iter().map(|trade| {
+        let pnl_str = trade.pnl.map(|p| app.money.fmt_signed(p)).unwrap_or_else(|| s.open.to_string());
+        ListItem::new(Line::from(vec![
+            Span::styled(timezone::format(trade.timestamp, "%m-%d %H:%M "), Style::default().fg(Color::DarkGray)),
+            Span::raw(format!("{} {} @ {} ", trade.side, app.money.fmt(trade.filled_size()), display::price(trade.entry_price))),
+            Span::styled(pnl_str, Style::default().fg(Color::Yellow)),
+            Span::styled(format!(" {}", trade.strategy), Style::default().fg(Color::DarkGray)),
+        ]))
+    }).collect();
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title(s.detail_trades_title)),
+        right[1],
+    );
+}
+
+fn draw_system(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let mut items: Vec<ListItem> = app.endpoints.iter().map(|endpoint| {
+        let (label, color) = if endpoint.on_fallback() {
+            (fill(s.fallback, &[&endpoint.index, &(endpoint.count - 1)]), Color::Yellow)
+        } else {
+            (s.primary.to_string(), Color::Green)
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<12}", endpoint.service), Style::default().fg(Color::White)),
+            Span::styled(format!("{:<34}", endpoint.host), Style::default().fg(Color::Cyan)),
+            Span::styled(label, Style::default().fg(color)),
+        ];
+        if let Some(at) = endpoint.last_failover {
+            spans.push(Span::styled(
+                fill(s.failovers, &[&endpoint.failovers, &timezone::format(at, "%m-%d %H:%M")]),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        ListItem::new(Line::from(spans))
+    }).collect();
+    if items.is_empty() {
+        items.push(ListItem::new(Span::styled(s.no_endpoints, Style::default().fg(Color::DarkGray))));
+    }
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(s.system_title)
+            .border_style(Style::default().fg(Color::Blue)));
+
+    frame.render_widget(list, area);
+}
+
 fn draw_top_traders(frame: &mut Frame, app: &App, area: Rect) {
     let items: Vec<ListItem> = app.top_traders.iter().map(|trader| {
         let copy_icon = if trader.is_copying { "📋" } else { "  " };
@@ -232,7 +426,7 @@ fn draw_top_traders(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled(&trader.name, Style::default().fg(Color::White)),
             Span::raw("  "),
             Span::styled(
-                format!("+${:.0}K", trader.monthly_pnl / 1000.0),
+                format!("+{}", app.money.fmt_thousands(trader.monthly_pnl)),
                 Style::default().fg(Color::Green)
             ),
         ]))
@@ -241,21 +435,45 @@ fn draw_top_traders(frame: &mut Frame, app: &App, area: Rect) {
     let list = List::new(items)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(" 👥 Top Traders ")
+            .title(i18n::strings().top_traders_title)
             .border_style(Style::default().fg(Color::Yellow)));
     
     frame.render_widget(list, area);
 }
 
+fn draw_alerts(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app.alerts.iter().map(|alert| {
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                timezone::format(alert.timestamp, "%H:%M "),
+                Style::default().fg(Color::DarkGray)
+            ),
+            Span::raw(format!("{} ", alert.severity.icon())),
+            Span::styled(&alert.title, Style::default().fg(Color::White)),
+            Span::raw("  "),
+            Span::styled(&alert.message, Style::default().fg(Color::Gray)),
+        ]))
+    }).collect();
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(i18n::strings().alerts_title)
+            .border_style(Style::default().fg(Color::Red)));
+
+    frame.render_widget(list, area);
+}
+
 fn draw_markets(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
     let header = Row::new(vec![
-        Cell::from("Market").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Coin").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Poly").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Kalshi").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Spread").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Liquidity").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Time").style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_market).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_coin).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_poly).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_kalshi).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_spread).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_liquidity).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_time).style(Style::default().fg(Color::Yellow)),
     ]).height(1);
 
     let rows: Vec<Row> = app.markets.iter().enumerate().map(|(i, market)| {
@@ -267,14 +485,21 @@ fn draw_markets(frame: &mut Frame, app: &App, area: Rect) {
         
         let spread_color = if market.spread.unwrap_or(0.0) > 0.02 { Color::Green } else { Color::White };
         
+        let now = chrono::Utc::now();
+        let name = match app.mute(&market.name) {
+            Some(m) if m.muted => Cell::from(format!("🔇 {}", market.name)).style(Style::default().fg(Color::DarkGray)),
+            Some(m) if m.is_snoozed(now) => Cell::from(format!("💤 {}", market.name)),
+            _ => Cell::from(market.name.clone()),
+        };
+
         Row::new(vec![
-            Cell::from(market.name.clone()),
+            name,
             Cell::from(market.coin.clone()).style(Style::default().fg(Color::Cyan)),
-            Cell::from(format!("{:.3}", market.poly_price.unwrap_or(0.0))),
-            Cell::from(format!("{:.3}", market.kalshi_price.unwrap_or(0.0))),
-            Cell::from(format!("{:.1}%", market.spread.unwrap_or(0.0) * 100.0))
+            Cell::from(display::price(market.poly_price.unwrap_or(0.0))),
+            Cell::from(display::price(market.kalshi_price.unwrap_or(0.0))),
+            Cell::from(display::price(market.spread.unwrap_or(0.0)))
                 .style(Style::default().fg(spread_color)),
-            Cell::from(format!("${:.0}K", market.liquidity / 1000.0)),
+            Cell::from(app.money.fmt_thousands(market.liquidity)),
             Cell::from(market.time_to_resolve.clone()),
         ]).style(style).height(1)
     }).collect();
@@ -291,63 +516,181 @@ fn draw_markets(frame: &mut Frame, app: &App, area: Rect) {
     .header(header)
     .block(Block::default()
         .borders(Borders::ALL)
-        .title(" 🔄 Live Markets (↑↓ navigate, B=buy, S=sell, R=refresh) ")
+        .title(s.markets_title)
         .border_style(Style::default().fg(Color::Cyan)));
-    
+
+    if app.ladder().is_none() && app.outcome_events.is_empty() {
+        frame.render_widget(table, area);
+        return;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(area);
+    frame.render_widget(table, chunks[0]);
+    match app.ladder() {
+        Some(ladder) if app.outcome_events.is_empty() => draw_ladder(frame, app, ladder, chunks[1]),
+        Some(ladder) => {
+            let bottom = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .split(chunks[1]);
+            draw_ladder(frame, app, ladder, bottom[0]);
+            draw_outcome_events(frame, app, bottom[1]);
+        }
+        None => draw_outcome_events(frame, app, chunks[1]),
+    }
+}
+
+/// Each multi-outcome event with its outcomes' YES prices, highest first.
+/// The YES prices of a negRisk event should sum to about 1.
+fn draw_outcome_events(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let yellow = |text: &'static str| Cell::from(text).style(Style::default().fg(Color::Yellow));
+    let mut rows = Vec::new();
+    for event in &app.outcome_events {
+        let title = if event.neg_risk { format!("{} (negRisk)", event.title) } else { event.title.clone() };
+        rows.push(Row::new(vec![
+            Cell::from(title).style(Style::default().fg(Color::Cyan)),
+            Cell::from(format!("Σ {}", display::price(event.yes_total()))).style(Style::default().fg(Color::Cyan)),
+        ]));
+        rows.extend(event.outcomes().into_iter().map(|(label, price)| Row::new(vec![
+            Cell::from(format!("  {}", label)),
+            Cell::from(price.map(display::price).unwrap_or_else(|| "-".to_string())),
+        ])));
+    }
+    let title = fill(s.outcome_events_title, &[&app.outcome_events.len()]);
+    let table = Table::new(rows, [Constraint::Percentage(75), Constraint::Percentage(25)])
+        .header(Row::new(vec![yellow(s.col_outcome), yellow(s.col_yes)]))
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(Color::Cyan)));
     frame.render_widget(table, area);
 }
 
+/// Kalshi strikes with the implied P(above), next to the Polymarket
+/// strikes priced off them.
+fn draw_ladder(frame: &mut Frame, app: &App, snapshot: &LadderSnapshot, area: Rect) {
+    let s = i18n::strings();
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let yellow = |text: &'static str| Cell::from(text).style(Style::default().fg(Color::Yellow));
+    let quote = |p: Option<f64>| p.map(display::price).unwrap_or_else(|| "-".to_string());
+
+    let rows: Vec<Row> = snapshot.ladder.rungs.iter().filter_map(|rung| {
+        let strike = rung.strike()?;
+        let above = snapshot.cdf.iter().find(|p| p.strike == strike).map(|p| 1.0 - p.below);
+        Some(Row::new(vec![
+            Cell::from(format!("${:.0}", strike)),
+            Cell::from(quote(rung.yes_bid)),
+            Cell::from(quote(rung.yes_ask)),
+            Cell::from(above.map(display::percent).unwrap_or_else(|| "-".to_string())),
+        ]))
+    }).collect();
+    let title = fill(s.ladder_title, &[&snapshot.ladder.event_ticker, &(app.ladder_index % app.ladders.len() + 1), &app.ladders.len()]);
+    let rungs = Table::new(rows, [Constraint::Percentage(34), Constraint::Percentage(22), Constraint::Percentage(22), Constraint::Percentage(22)])
+        .header(Row::new(vec![yellow(s.col_strike), yellow(s.col_bid), yellow(s.col_ask), yellow(s.col_above)]))
+        .block(Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(Color::Cyan)));
+    frame.render_widget(rungs, chunks[0]);
+
+    let rows: Vec<Row> = snapshot.fair_values.iter().map(|fv| {
+        let premium = fv.premium();
+        let color = match premium {
+            Some(p) if p > 0.02 => Color::Red,
+            Some(p) if p < -0.02 => Color::Green,
+            _ => Color::White,
+        };
+        Row::new(vec![
+            Cell::from(fv.market.clone()),
+            Cell::from(format!("${:.0}", fv.strike)),
+            Cell::from(display::price(fv.fair)),
+            Cell::from(quote(fv.poly_mid)),
+            Cell::from(premium.map(|p| format!("{}{}", if p < 0.0 { "-" } else { "+" }, display::price(p.abs()))).unwrap_or_else(|| "-".to_string()))
+                .style(Style::default().fg(color)),
+        ])
+    }).collect();
+    let fair = Table::new(rows, [
+        Constraint::Percentage(36),
+        Constraint::Percentage(16),
+        Constraint::Percentage(16),
+        Constraint::Percentage(16),
+        Constraint::Percentage(16),
+    ])
+    .header(Row::new(vec![yellow(s.col_market), yellow(s.col_strike), yellow(s.col_fair), yellow(s.col_poly), yellow(s.col_premium)]))
+    .block(Block::default().borders(Borders::ALL).title(s.fair_values_title).border_style(Style::default().fg(Color::Cyan)));
+    frame.render_widget(fair, chunks[1]);
+}
+
 fn draw_trades(frame: &mut Frame, app: &App, area: Rect) {
-    let trades = app.engine.trade_log.get_all();
+    let trades = app.visible_trades();
+    let s = i18n::strings();
     
     let header = Row::new(vec![
-        Cell::from("Time").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Market").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Side").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Size").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Entry").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Exit").style(Style::default().fg(Color::Yellow)),
-        Cell::from("P&L").style(Style::default().fg(Color::Yellow)),
-        Cell::from("Strategy").style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_time).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_market).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_side).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_size).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_entry).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_exit).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_pnl).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_strategy).style(Style::default().fg(Color::Yellow)),
+        Cell::from(s.col_tags).style(Style::default().fg(Color::Yellow)),
     ]).height(1);
 
-    let rows: Vec<Row> = trades.iter().rev().take(20).enumerate().map(|(i, trade)| {
+    let rows: Vec<Row> = trades.iter().enumerate().map(|(i, trade)| {
         let style = if i == app.selected_index {
             Style::default().bg(Color::DarkGray)
         } else {
             Style::default()
         };
         
-        let pnl_str = trade.pnl.map(|p| format!("{:+.2}", p)).unwrap_or_else(|| "-".to_string());
+        let pnl_str = trade.pnl.map(|p| app.money.fmt_signed(p)).unwrap_or_else(|| "-".to_string());
         let pnl_color = if trade.is_profitable() { Color::Green } else if trade.pnl.is_some() { Color::Red } else { Color::White };
         let side_color = if matches!(trade.side, crate::paper_trading::Side::Buy) { Color::Green } else { Color::Red };
+        // Partially filled orders show filled/ordered
+        let size = if trade.filled_size() < trade.size - 1e-9 {
+            format!("{}/{}", app.money.fmt(trade.filled_size()), app.money.fmt(trade.size))
+        } else {
+            app.money.fmt(trade.size)
+        };
+        let annotation = app.annotations.get(&trade.id);
+        let mut tags = annotation.map(|a| a.tags.iter().cloned().collect::<Vec<_>>().join(",")).unwrap_or_default();
+        if annotation.is_some_and(|a| a.notes.is_some()) {
+            tags.push_str(" 📝");
+        }
         
         Row::new(vec![
-            Cell::from(trade.timestamp.format("%H:%M:%S").to_string()),
+            Cell::from(timezone::format(trade.timestamp, "%H:%M:%S")),
             Cell::from(trade.market.chars().take(20).collect::<String>()),
             Cell::from(trade.side.to_string()).style(Style::default().fg(side_color)),
-            Cell::from(format!("${:.0}", trade.size)),
-            Cell::from(format!("{:.3}", trade.entry_price)),
-            Cell::from(trade.exit_price.map(|p| format!("{:.3}", p)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(size),
+            Cell::from(display::price(trade.entry_price)),
+            Cell::from(trade.exit_price.map(display::price).unwrap_or_else(|| "-".to_string())),
             Cell::from(pnl_str).style(Style::default().fg(pnl_color)),
             Cell::from(trade.strategy.clone()),
+            Cell::from(tags).style(Style::default().fg(Color::Magenta)),
         ]).style(style).height(1)
     }).collect();
 
+    let title = match &app.trade_filter {
+        Some(tag) => fill(s.trade_history_tagged_title, &[tag]),
+        None => s.trade_history_title.to_string(),
+    };
     let table = Table::new(rows, [
-        Constraint::Percentage(12),
-        Constraint::Percentage(22),
-        Constraint::Percentage(8),
-        Constraint::Percentage(10),
         Constraint::Percentage(10),
+        Constraint::Percentage(18),
+        Constraint::Percentage(6),
+        Constraint::Percentage(9),
+        Constraint::Percentage(8),
+        Constraint::Percentage(8),
         Constraint::Percentage(10),
         Constraint::Percentage(12),
-        Constraint::Percentage(16),
+        Constraint::Percentage(19),
     ])
     .header(header)
     .block(Block::default()
         .borders(Borders::ALL)
-        .title(" 📜 Trade History ")
+        .title(title)
         .border_style(Style::default().fg(Color::Magenta)));
     
     frame.render_widget(table, area);
@@ -358,6 +701,11 @@ fn draw_strategies(frame: &mut Frame, app: &App, area: Rect) {
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(7), Constraint::Length(6), Constraint::Length(11)])
+        .split(chunks[0]);
+    let s = i18n::strings();
 
     // Strategies list
     let items: Vec<ListItem> = app.strategies.iter().enumerate().map(|(i, strategy)| {
@@ -367,76 +715,466 @@ fn draw_strategies(frame: &mut Frame, app: &App, area: Rect) {
             Style::default()
         };
         
-        let status = if strategy.enabled { "✅ ON " } else { "❌ OFF" };
-        let status_color = if strategy.enabled { Color::Green } else { Color::Red };
+        let (status, status_color) = if !strategy.enabled {
+            (s.strategy_off, Color::Red)
+        } else if strategy.scheduled_off {
+            (s.strategy_scheduled_off, Color::Yellow)
+        } else if strategy.shadow {
+            (s.strategy_shadow, Color::Magenta)
+        } else {
+            (s.strategy_on, Color::Green)
+        };
         
+        let (mode, mode_color) = if strategy.dry_run {
+            (s.strategy_dry_run, Color::DarkGray)
+        } else {
+            (s.strategy_live, Color::Red)
+        };
+
         ListItem::new(Line::from(vec![
             Span::styled(status, Style::default().fg(status_color)),
             Span::raw(" "),
             Span::styled(&strategy.name, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-            Span::raw("  │  Trades: "),
+            Span::styled(format!(" [{}]", mode), Style::default().fg(mode_color)),
+            Span::raw(format!("  │  {}: ", s.trades)),
             Span::styled(format!("{}", strategy.trades_today), Style::default().fg(Color::Cyan)),
-            Span::raw("  │  P&L: "),
+            Span::raw(format!("  │  {}: ", s.bot_trades)),
             Span::styled(
-                format!("${:.2}", strategy.pnl_today),
+                match strategy.trade_cap {
+                    Some(cap) => format!("{}/{}", strategy.bot_trades_today, cap),
+                    None => strategy.bot_trades_today.to_string(),
+                },
+                Style::default().fg(if strategy.trade_cap.is_some_and(|cap| strategy.bot_trades_today >= cap) {
+                    Color::Red
+                } else {
+                    Color::Cyan
+                }),
+            ),
+            Span::raw(format!("  │  {}: ", s.pnl)),
+            Span::styled(
+                app.money.fmt(strategy.pnl_today),
                 Style::default().fg(if strategy.pnl_today >= 0.0 { Color::Green } else { Color::Red })
             ),
         ])).style(style)
     }).collect();
 
-    let list = List::new(items)
-        .block(Block::default()
-            .borders(Borders::ALL)
-            .title(" ⚙️ Strategies (Enter to toggle) ")
-            .border_style(Style::default().fg(Color::Yellow)));
-    
-    frame.render_widget(list, chunks[0]);
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(s.strategies_title)
+        .border_style(Style::default().fg(Color::Yellow));
+    if let Some(cap) = app.trade_limits.order_cap() {
+        let orders = app.trade_limits.counts().orders;
+        block = block.title_bottom(i18n::fill(s.orders_today, &[&orders, &cap]));
+    }
+    let list = List::new(items).block(block);
+
+    frame.render_widget(list, left[0]);
+    draw_conflicts(frame, app, left[1]);
+    draw_inventory(frame, app, left[2]);
+    draw_risk(frame, app, left[3]);
 
     // Help text
-    let help_text = vec![
+    let mut help_text = vec![
         Line::from(""),
-        Line::from(Span::styled("Keyboard Shortcuts:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(s.help_shortcuts, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
         Line::from(""),
-        Line::from("  1-4    Switch tabs"),
-        Line::from("  Tab    Next tab"),
-        Line::from("  ↑/↓    Navigate list"),
-        Line::from("  Enter  Select/Toggle"),
-        Line::from("  B      Paper Buy"),
-        Line::from("  S      Paper Sell"),
-        Line::from("  R      Refresh data"),
-        Line::from("  Q      Quit"),
+    ];
+    help_text.extend(s.help_keys.iter().map(|line| Line::from(*line)));
+    help_text.extend([
         Line::from(""),
-        Line::from(Span::styled("Strategies:", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+        Line::from(Span::styled(s.help_strategies, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
         Line::from(""),
-        Line::from("  Arbitrage:   Price discrepancies"),
-        Line::from("  Copy Trade:  Mirror top traders"),
-        Line::from("  Manual:      User-initiated trades"),
-    ];
+    ]);
+    help_text.extend(s.help_strategy_lines.iter().map(|line| Line::from(*line)));
 
     let help = Paragraph::new(help_text)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title(" ❓ Help ")
+            .title(s.help_title)
             .border_style(Style::default().fg(Color::DarkGray)));
     
     frame.render_widget(help, chunks[1]);
 }
 
+fn draw_conflicts(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let items: Vec<ListItem> = if app.conflicts.is_empty() {
+        vec![ListItem::new(Span::styled(s.no_conflicts, Style::default().fg(Color::DarkGray)))]
+    } else {
+        app.conflicts.iter().map(|conflict| {
+            let (outcome, color) = match conflict.policy {
+                ConflictPolicy::Skip => (s.conflict_skipped.to_string(), Color::Red),
+                ConflictPolicy::Net => (fill(s.conflict_netted, &[&app.money.fmt(conflict.allowed_usd)]), Color::Yellow),
+                ConflictPolicy::Allow => (s.conflict_allowed.to_string(), Color::Gray),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(timezone::format(conflict.timestamp, "%H:%M "), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{} {} ", conflict.strategy, conflict.side), Style::default().fg(Color::White)),
+                Span::raw(fill(s.conflict_versus, &[&conflict.market, &conflict.opposing.join(", ")])),
+                Span::styled(outcome, Style::default().fg(color)),
+            ]))
+        }).collect()
+    };
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(s.conflicts_title)
+            .border_style(Style::default().fg(Color::Red)));
+
+    frame.render_widget(list, area);
+}
+
+fn draw_inventory(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let quiet = |text: &str| vec![ListItem::new(Span::styled(text.to_string(), Style::default().fg(Color::DarkGray)))];
+    let items: Vec<ListItem> = match &app.inventory_skew {
+        None => quiet(s.no_inventory_cap),
+        Some(_) if app.inventory.is_empty() => quiet(s.flat_inventory),
+        Some(skew) => app.inventory.iter().map(|(question, inventory)| {
+            let ratio = skew.ratio(*inventory);
+            let color = if ratio.abs() >= 1.0 { Color::Red } else if ratio.abs() >= 0.5 { Color::Yellow } else { Color::Green };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<28} ", question.chars().take(28).collect::<String>()), Style::default().fg(Color::White)),
+                Span::styled(format!("{:+.1} / {:.0} ({:.0}%)  ", inventory, skew.cap, ratio * 100.0), Style::default().fg(color)),
+                Span::styled(fill(s.inventory_shift, &[&format!("{:+.3}", skew.shift(*inventory))]), Style::default().fg(Color::DarkGray)),
+            ]))
+        }).collect(),
+    };
+
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(s.inventory_title)
+            .border_style(Style::default().fg(Color::Blue)));
+
+    frame.render_widget(list, area);
+}
+
+fn draw_risk(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let lines = match &app.risk {
+        None => vec![Line::from(Span::styled(
+            s.no_risk,
+            Style::default().fg(Color::DarkGray),
+        ))],
+        Some(risk) => {
+            let pnl = |label: &str, value: f64| Line::from(vec![
+                Span::raw(format!("{:<18}", label)),
+                Span::styled(
+                    app.money.fmt_signed(value),
+                    Style::default().fg(if value >= 0.0 { Color::Green } else { Color::Red }),
+                ),
+            ]);
+            let ruin_color = if risk.prob_ruin >= 0.05 { Color::Red } else if risk.prob_ruin > 0.0 { Color::Yellow } else { Color::Green };
+            vec![
+                Line::from(Span::styled(
+                    fill(s.risk_paths, &[&risk.paths, &format!("{:.1}", risk.trades_per_day)]),
+                    Style::default().fg(Color::DarkGray),
+                )),
+                pnl(s.expected_pnl, risk.expected_pnl),
+                pnl(s.median_pnl, risk.median_pnl),
+                pnl(s.p5_pnl, risk.p5_pnl),
+                pnl(s.p95_pnl, risk.p95_pnl),
+                Line::from(vec![
+                    Span::raw(format!("{:<18}", s.var_cvar)),
+                    Span::styled(
+                        format!("{} / {}", app.money.fmt(risk.var_95), app.money.fmt(risk.cvar_95)),
+                        Style::default().fg(Color::Red),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw(format!("{:<18}", s.drawdown)),
+                    Span::styled(
+                        format!("{} / {}", app.money.fmt(risk.expected_drawdown), app.money.fmt(risk.p95_drawdown)),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::raw(format!("{:<18}", s.ruin)),
+                    Span::styled(format!("{:.1}%", risk.prob_ruin * 100.0), Style::default().fg(ruin_color)),
+                    Span::styled(fill(s.ruin_below, &[&app.money.fmt(risk.ruin_level)]), Style::default().fg(Color::DarkGray)),
+                ]),
+            ]
+        }
+    };
+    let days = app.risk.as_ref().map(|r| r.days).unwrap_or(crate::risk::monte_carlo::RISK_HORIZON_DAYS);
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(fill(s.risk_title, &[&days]))
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    frame.render_widget(panel, area);
+}
+
+fn draw_heatmap(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let families = app.heatmap.families();
+    let (name, grid) = match app.selected_index.checked_sub(1).and_then(|i| families.get(i)) {
+        Some(family) => (family.to_string(), app.heatmap.grid(family).cloned().unwrap_or_else(|| app.heatmap.combined())),
+        None => (s.all_markets.to_string(), app.heatmap.combined()),
+    };
+    let max = grid.max_count().max(1) as f64;
+
+    let mut header = vec![Cell::from("")];
+    header.extend((0..24).map(|h| Cell::from(format!("{:>2}", h))));
+    let header = Row::new(header).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+    // Shade each hour by how often spreads appeared relative to the busiest hour
+    let rows: Vec<Row> = s.days.iter().enumerate().map(|(day, label)| {
+        let mut cells = vec![Cell::from(*label)];
+        cells.extend((0..24).map(|hour| {
+            let cell = grid.cell(day, hour);
+            let intensity = cell.count as f64 / max;
+            let (glyph, color) = match intensity {
+                _ if cell.count == 0 => ("  ", Color::DarkGray),
+                i if i < 0.25 => ("░░", Color::Blue),
+                i if i < 0.5 => ("▒▒", Color::Cyan),
+                i if i < 0.75 => ("▓▓", Color::Yellow),
+                _ => ("██", Color::Red),
+            };
+            Cell::from(glyph).style(Style::default().fg(color))
+        }));
+        Row::new(cells)
+    }).collect();
+
+    let mut widths = vec![Constraint::Length(4)];
+    widths.extend([Constraint::Length(2); 24]);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(10), Constraint::Min(3)])
+        .split(area);
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(fill(s.heatmap_title, &[&name]))
+            .border_style(Style::default().fg(Color::Red)));
+    frame.render_widget(table, chunks[0]);
+
+    let best: Vec<ListItem> = grid.best_windows(5).into_iter().map(|(day, hour, cell)| {
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                format!("{} {:02}:00  ", s.days[day.num_days_from_monday() as usize], hour),
+                Style::default().fg(Color::White),
+            ),
+            Span::raw(fill(s.window_spreads, &[&cell.count])),
+            Span::styled(
+                fill(s.window_edges, &[&display::percent(cell.avg_edge()), &display::percent(cell.max_edge)]),
+                Style::default().fg(Color::Green)
+            ),
+        ]))
+    }).collect();
+
+    let list = List::new(best)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(s.windows_title)
+            .border_style(Style::default().fg(Color::Yellow)));
+    frame.render_widget(list, chunks[1]);
+}
+
+fn draw_approvals(frame: &mut Frame, app: &App, area: Rect) {
+    let now = chrono::Utc::now();
+    let pending = app.intents.pending();
+
+    let items: Vec<ListItem> = pending.iter().enumerate().map(|(i, intent)| {
+        let style = if i == app.selected_index {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        let left = (intent.expires_at - now).num_seconds().max(0);
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("{}  ", intent.id), Style::default().fg(Color::Cyan)),
+            Span::raw(intent.kind.summary()),
+            Span::styled(
+                format!("  ⌛ {}s", left),
+                Style::default().fg(if left < 30 { Color::Red } else { Color::Yellow })
+            ),
+        ])).style(style)
+    }).collect();
+
+    let s = i18n::strings();
+    let title = if pending.is_empty() {
+        s.approvals_empty_title.to_string()
+    } else {
+        fill(s.approvals_title, &[&pending.len()])
+    };
+    let list = List::new(items)
+        .block(Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Magenta)));
+
+    frame.render_widget(list, area);
+}
+
+fn draw_system_tab(frame: &mut Frame, app: &App, area: Rect) {
+    let s = i18n::strings();
+    let status = &app.system;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3 + status.venues_down.len() as u16),
+            Constraint::Min(5),
+            Constraint::Length(app.endpoints.len().max(1) as u16 + 2),
+        ])
+        .split(area);
+
+    let resources = match status.updated_at {
+        Some(at) => {
+            let age = (chrono::Utc::now() - at).num_seconds().max(0);
+            let memory = status.memory_bytes
+                .map_or("-".to_string(), |b| format!("{:.0} MB", b as f64 / (1024.0 * 1024.0)));
+            // A snapshot older than a few write intervals means the bot is down or stuck
+            let color = if age > 30 { Color::Red } else { Color::White };
+            Span::styled(
+                fill(s.resources_line, &[
+                    &duration(status.uptime_secs()), &memory, &status.tasks, &status.workers, &duration(age),
+                ]),
+                Style::default().fg(color),
+            )
+        }
+        None => Span::styled(s.no_system, Style::default().fg(Color::DarkGray)),
+    };
+    let mut lines = vec![Line::from(resources)];
+    for outage in &status.venues_down {
+        lines.push(Line::from(Span::styled(
+            fill(s.venue_down, &[
+                &outage.venue,
+                &timezone::format(outage.since, "%H:%M:%S"),
+                &outage.last_error.as_deref().unwrap_or("-"),
+            ]),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(s.resources_title).border_style(Style::default().fg(Color::Blue))),
+        chunks[0],
+    );
+
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(chunks[1]);
+
+    let rows: Vec<Row> = status.subsystems.iter().map(|task| {
+        let (state, color) = if task.running {
+            (s.subsystem_running, if task.restarts > 0 { Color::Yellow } else { Color::Green })
+        } else {
+            (s.subsystem_stopped, Color::Red)
+        };
+        Row::new(vec![
+            Cell::from(task.name.clone()),
+            Cell::from(state).style(Style::default().fg(color)),
+            Cell::from(fill(s.restarts, &[&task.restarts])),
+            Cell::from(task.last_error.clone().unwrap_or_default()).style(Style::default().fg(Color::DarkGray)),
+        ])
+    }).collect();
+    let table = Table::new(rows, [Constraint::Length(18), Constraint::Length(10), Constraint::Length(14), Constraint::Min(10)])
+        .block(Block::default().borders(Borders::ALL).title(s.subsystems_title).border_style(Style::default().fg(Color::Blue)));
+    frame.render_widget(table, middle[0]);
+
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(middle[1]);
+
+    let mut streams: Vec<ListItem> = status.streams.iter().map(|(name, stream)| {
+        let color = match stream.state {
+            StreamState::Connected => Color::Green,
+            StreamState::Connecting => Color::Yellow,
+            StreamState::Disconnected => Color::Red,
+        };
+        let mut spans = vec![
+            Span::styled(format!("{:<18}", name), Style::default().fg(Color::White)),
+            Span::styled(format!("{:<14}", stream.state), Style::default().fg(color)),
+            Span::styled(timezone::format(stream.since, "%H:%M:%S"), Style::default().fg(Color::DarkGray)),
+        ];
+        if let Some(error) = stream.last_error.as_ref().filter(|_| stream.state != StreamState::Connected) {
+            spans.push(Span::styled(format!("  {}", error), Style::default().fg(Color::DarkGray)));
+        }
+        ListItem::new(Line::from(spans))
+    }).collect();
+    if streams.is_empty() {
+        streams.push(ListItem::new(Span::styled(s.no_streams, Style::default().fg(Color::DarkGray))));
+    }
+    frame.render_widget(
+        List::new(streams)
+            .block(Block::default().borders(Borders::ALL).title(s.streams_title).border_style(Style::default().fg(Color::Blue))),
+        right[0],
+    );
+
+    let queues: Vec<ListItem> = status.queues.iter().map(|(name, depth)| {
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("{:<18}", name), Style::default().fg(Color::White)),
+            Span::styled(depth.to_string(), Style::default().fg(if *depth > 0 { Color::Yellow } else { Color::Green })),
+        ]))
+    }).collect();
+    frame.render_widget(
+        List::new(queues)
+            .block(Block::default().borders(Borders::ALL).title(s.queues_title).border_style(Style::default().fg(Color::Blue))),
+        right[1],
+    );
+
+    draw_system(frame, app, chunks[2]);
+}
+
+/// Compact duration, e.g. "3d 4h", "12m 5s".
+fn duration(secs: i64) -> String {
+    let (d, h, m, s) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60, secs % 60);
+    if d > 0 {
+        format!("{}d {}h", d, h)
+    } else if h > 0 {
+        format!("{}h {}m", h, m)
+    } else {
+        format!("{}m {}s", m, s)
+    }
+}
+
 fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
-    let status = app.status_message.as_deref().unwrap_or("Ready");
+    let s = i18n::strings();
+    if let Some(input) = &app.input {
+        let prompt = match input.kind {
+            InputKind::Tag => s.tag_prompt,
+            InputKind::Note => s.note_prompt,
+            InputKind::Pin(_) => s.pin_prompt,
+            InputKind::Balance => s.balance_prompt,
+        };
+        let buffer = match input.kind {
+            InputKind::Pin(_) => "•".repeat(input.buffer.chars().count()),
+            _ => input.buffer.clone(),
+        };
+        let text = Line::from(vec![
+            Span::styled(format!(" {}: ", prompt), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}▏", buffer)),
+            Span::styled(s.input_hint, Style::default().fg(Color::DarkGray)),
+        ]);
+        let bar = Paragraph::new(text)
+            .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+        frame.render_widget(bar, area);
+        return;
+    }
+
+    let status = app.status_message.as_deref().unwrap_or(s.ready);
     
     let text = Line::from(vec![
         Span::raw(" "),
         Span::styled(status, Style::default().fg(Color::White)),
         Span::raw("  │  "),
         Span::styled("Q", Style::default().fg(Color::Yellow)),
-        Span::raw("uit  "),
+        Span::raw(format!(" {}  ", s.quit)),
         Span::styled("R", Style::default().fg(Color::Yellow)),
-        Span::raw("efresh  "),
+        Span::raw(format!(" {}  ", s.refresh)),
         Span::styled("B", Style::default().fg(Color::Green)),
-        Span::raw("uy  "),
+        Span::raw(format!(" {}  ", s.buy)),
         Span::styled("S", Style::default().fg(Color::Red)),
-        Span::raw("ell"),
+        Span::raw(format!(" {}", s.sell)),
     ]);
 
     let status_bar = Paragraph::new(text)
@@ -0,0 +1,198 @@
+//! Tags and notes on trades.
+//!
+//! Paper trade IDs and live order IDs share one store, so experiments,
+//! mistakes and strategy variants can be reviewed across both. The file is
+//! shared by the bot and the TUI; every change re-reads it first so neither
+//! overwrites the other.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::warn;
+
+use super::audit_log::AuditEvent;
+use super::state;
+use crate::paper_trading::{PaperTrade, TradeStatus};
+
+/// Default annotation store.
+pub const ANNOTATIONS_FILE: &str = "data/annotations.json";
+
+/// Tags and notes on one trade.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub tags: BTreeSet<String>,
+    pub notes: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Per-tag totals across paper trades and live fills.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TagStats {
+    pub trades: usize,
+    pub closed: usize,
+    pub wins: usize,
+    /// Realized paper P&L
+    pub pnl: f64,
+    pub live_fills: usize,
+    /// Live fill notional (price x size)
+    pub live_notional: f64,
+}
+
+/// Lowercase, hyphenated form so "Late Entry" and "late-entry" match.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace().collect::<Vec<_>>().join("-").to_lowercase()
+}
+
+/// Annotations by trade or order ID.
+#[derive(Debug, Default)]
+pub struct Annotations {
+    entries: BTreeMap<String, Annotation>,
+    /// None for an in-memory store
+    path: Option<String>,
+}
+
+impl Annotations {
+    /// In-memory store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store persisted at `path`.
+    pub fn load(path: &str) -> Self {
+        Self {
+            entries: state::load(path),
+            path: Some(path.to_string()),
+        }
+    }
+
+    /// Pick up changes written by another process.
+    pub fn reload(&mut self) {
+        if let Some(path) = &self.path {
+            self.entries = state::load(path);
+        }
+    }
+
+    fn update(&mut self, id: &str, f: impl FnOnce(&mut Annotation)) {
+        self.reload();
+        let entry = self.entries.entry(id.to_string()).or_default();
+        f(entry);
+        entry.updated_at = Some(Utc::now());
+        if entry.tags.is_empty() && entry.notes.is_none() {
+            self.entries.remove(id);
+        }
+        if let Some(path) = &self.path {
+            if let Err(e) = state::save(path, &self.entries) {
+                warn!("Failed to save annotations: {}", e);
+            }
+        }
+    }
+
+    /// Add a tag to a trade. Returns the normalized tag.
+    pub fn tag(&mut self, id: &str, tag: &str) -> String {
+        let tag = normalize_tag(tag);
+        if !tag.is_empty() {
+            let added = tag.clone();
+            self.update(id, |a| {
+                a.tags.insert(added);
+            });
+        }
+        tag
+    }
+
+    /// Remove a tag from a trade.
+    pub fn untag(&mut self, id: &str, tag: &str) {
+        let tag = normalize_tag(tag);
+        self.update(id, |a| {
+            a.tags.remove(&tag);
+        });
+    }
+
+    /// Replace a trade's notes; blank text clears them.
+    pub fn set_notes(&mut self, id: &str, notes: &str) {
+        let notes = notes.trim();
+        self.update(id, |a| a.notes = (!notes.is_empty()).then(|| notes.to_string()));
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Annotation> {
+        self.entries.get(id)
+    }
+
+    pub fn has_tag(&self, id: &str, tag: &str) -> bool {
+        self.get(id).is_some_and(|a| a.tags.contains(&normalize_tag(tag)))
+    }
+
+    /// Every tag in use.
+    pub fn tags(&self) -> BTreeSet<String> {
+        self.entries.values().flat_map(|a| a.tags.iter().cloned()).collect()
+    }
+
+    /// Totals per tag over paper trades and live fills.
+    pub fn report(&self, trades: &[PaperTrade], fills: &[AuditEvent]) -> BTreeMap<String, TagStats> {
+        let mut report: BTreeMap<String, TagStats> = BTreeMap::new();
+        for trade in trades {
+            for tag in self.get(&trade.id).map(|a| &a.tags).into_iter().flatten() {
+                let stats = report.entry(tag.clone()).or_default();
+                stats.trades += 1;
+                if trade.status == TradeStatus::Closed {
+                    stats.closed += 1;
+                    stats.wins += trade.is_profitable() as usize;
+                }
+                stats.pnl += trade.pnl.unwrap_or(0.0);
+            }
+        }
+        for fill in fills.iter().filter(|e| e.kind == "fill") {
+            for tag in self.get(&fill.order_id).map(|a| &a.tags).into_iter().flatten() {
+                let stats = report.entry(tag.clone()).or_default();
+                stats.live_fills += 1;
+                stats.live_notional += fill.price * fill.size;
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::Side;
+
+    #[test]
+    fn test_tags_notes_and_report() {
+        let mut notes = Annotations::new();
+        let mut win = PaperTrade::new("BTC", "BTC", "Hourly", "kalshi", Side::Buy, 10.0, 0.40, "manual", 0.5);
+        win.close(0.60);
+        let open = PaperTrade::new("ETH", "ETH", "Hourly", "kalshi", Side::Buy, 10.0, 0.40, "manual", 0.5);
+
+        assert_eq!(notes.tag(&win.id, "Late Entry"), "late-entry");
+        notes.tag(&open.id, "late-entry");
+        notes.tag(&open.id, "mistake");
+        notes.tag("live-order-1", "late-entry");
+        notes.set_notes(&win.id, "entered after the move");
+        assert!(notes.has_tag(&open.id, "Mistake"));
+        assert_eq!(notes.get(&win.id).unwrap().notes.as_deref(), Some("entered after the move"));
+
+        let fill = AuditEvent {
+            at: Utc::now(),
+            venue: "polymarket".to_string(),
+            kind: "fill".to_string(),
+            order_id: "live-order-1".to_string(),
+            market: "BTC".to_string(),
+            side: "BUY".to_string(),
+            price: 0.5,
+            size: 20.0,
+            detail: None,
+            run: None,
+        };
+        let report = notes.report(&[win.clone(), open.clone()], &[fill]);
+        let late = &report["late-entry"];
+        assert_eq!((late.trades, late.closed, late.wins, late.live_fills), (2, 1, 1, 1));
+        assert!((late.live_notional - 10.0).abs() < 1e-9);
+        assert_eq!(report["mistake"].trades, 1);
+
+        // Removing the last tag and note drops the entry
+        notes.untag(&open.id, "mistake");
+        notes.untag(&open.id, "late-entry");
+        assert!(notes.get(&open.id).is_none());
+        assert_eq!(notes.tags().into_iter().collect::<Vec<_>>(), vec!["late-entry"]);
+    }
+}
@@ -0,0 +1,139 @@
+//! Append-only audit log of order and fill events.
+//!
+//! One JSON object per line in `data/audit.jsonl`, written as venue events
+//! arrive so there is a record of what actually happened to every order.
+//! Each finished session adds a summary line with `"kind": "session"`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tracing::warn;
+
+use super::run_info::RunStamp;
+use super::state;
+use crate::paper_trading::SessionSummary;
+
+/// Default audit log location.
+pub const AUDIT_FILE: &str = "data/audit.jsonl";
+/// `kind` of session summary lines.
+const SESSION_KIND: &str = "session";
+
+/// One audited event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub at: DateTime<Utc>,
+    pub venue: String,
+    /// e.g. "fill", "cancelled", "filled"
+    pub kind: String,
+    pub order_id: String,
+    pub market: String,
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub detail: Option<String>,
+    /// Process that saw the event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<RunStamp>,
+}
+
+/// Audit log file writer.
+pub struct AuditLog {
+    path: String,
+    /// Serializes appends from concurrent tasks
+    lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// Append to `path`, creating it on first write.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Append an event.
+    pub fn record(&self, event: &AuditEvent) {
+        self.append(serde_json::to_string(event));
+    }
+
+    /// Append the summary of a finished session.
+    pub fn record_session(&self, summary: &SessionSummary) {
+        self.append(serde_json::to_value(summary).and_then(|mut line| {
+            line["kind"] = SESSION_KIND.into();
+            serde_json::to_string(&line)
+        }));
+    }
+
+    /// Most recent `n` events (newest first).
+    pub fn recent(&self, n: usize) -> Vec<AuditEvent> {
+        self.lines().iter().filter_map(|line| serde_json::from_str(line).ok()).take(n).collect()
+    }
+
+    /// Most recent `n` session summaries (newest first).
+    pub fn sessions(&self, n: usize) -> Vec<SessionSummary> {
+        self.lines()
+            .iter()
+            .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+            .filter(|line| line["kind"] == SESSION_KIND)
+            .filter_map(|line| serde_json::from_value(line).ok())
+            .take(n)
+            .collect()
+    }
+
+    /// Lines of the log, newest first.
+    fn lines(&self) -> Vec<String> {
+        let mut lines = state::read_lines(&self.path);
+        lines.reverse();
+        lines
+    }
+
+    fn append(&self, line: serde_json::Result<String>) {
+        let _guard = self.lock.lock().unwrap();
+        let result = line
+            .map_err(anyhow::Error::from)
+            .and_then(|line| state::append_line(&self.path, &line));
+        if let Err(e) = result {
+            warn!("Failed to write audit log {}: {}", self.path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_sit_alongside_events() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let log = AuditLog::new(path.to_str().unwrap());
+        let event = AuditEvent {
+            at: Utc::now(),
+            venue: "kalshi".to_string(),
+            kind: "fill".to_string(),
+            order_id: "o1".to_string(),
+            market: "KXBTCD-25".to_string(),
+            side: "yes".to_string(),
+            price: 0.42,
+            size: 10.0,
+            detail: None,
+            run: None,
+        };
+        let summary = SessionSummary {
+            run_id: "run-1".to_string(),
+            profile: "default".to_string(),
+            started_at: Utc::now(),
+            ended_at: Utc::now(),
+            pnl: 12.5,
+            all_time_pnl: 40.0,
+            trades: 3,
+        };
+        log.record(&event);
+        log.record_session(&summary);
+        log.record(&event);
+
+        assert_eq!(log.recent(10), vec![event.clone(), event]);
+        assert_eq!(log.sessions(10), vec![summary]);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,217 @@
+//! Portable state bundles.
+//!
+//! `polybot state export` packs a paper-trading experiment into one JSON file: the
+//! portfolio and trade log of every profile (archived logs included), the
+//! matched markets, the copy trader's processed trade hashes and the
+//! performance stats. `polybot state import` unpacks it on another machine, so an
+//! experiment can move from a laptop to a VPS with its history. Files are
+//! carried byte for byte: state encrypted at rest needs the same
+//! DATA_ENCRYPTION_KEY where it is imported.
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path};
+
+use super::annotations::ANNOTATIONS_FILE;
+use super::run_info::{self, RunStamp};
+use crate::analysis::attribution::ATTRIBUTION_FILE;
+use crate::analysis::capture::CAPTURE_FILE;
+use crate::analysis::execution::EXECUTIONS_FILE;
+use crate::analysis::heatmap::HEATMAP_FILE;
+use crate::analysis::leaderboard_history::LEADERBOARD_HISTORY_FILE;
+use crate::arbitrage::market_matcher::MATCHED_MARKETS_FILE;
+use crate::arbitrage::mutes::MUTES_FILE;
+use crate::arbitrage::quality::QUALITY_FILE;
+use crate::monitor::resolution::RESOLUTIONS_FILE;
+use crate::paper_trading::engine::PORTFOLIO_FILE;
+use crate::paper_trading::profiles::{PROFILES_DIR, TRADES_FILE};
+use crate::polymarket::discovery::OUTCOME_EVENTS_FILE;
+use crate::strategies::copy_trader::{POSITION_SNAPSHOTS_FILE, PROCESSED_TRADES_FILE};
+
+/// Version of the bundle layout.
+pub const BUNDLE_FORMAT: u32 = 1;
+
+/// Files bundled when present.
+const BUNDLED_FILES: &[&str] = &[
+    PORTFOLIO_FILE,
+    TRADES_FILE,
+    MATCHED_MARKETS_FILE,
+    OUTCOME_EVENTS_FILE,
+    MUTES_FILE,
+    PROCESSED_TRADES_FILE,
+    POSITION_SNAPSHOTS_FILE,
+    EXECUTIONS_FILE,
+    CAPTURE_FILE,
+    ATTRIBUTION_FILE,
+    HEATMAP_FILE,
+    QUALITY_FILE,
+    RESOLUTIONS_FILE,
+    ANNOTATIONS_FILE,
+    LEADERBOARD_HISTORY_FILE,
+];
+
+/// A bundled experiment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bundle {
+    pub format: u32,
+    pub exported_at: DateTime<Utc>,
+    pub exported_by: RunStamp,
+    /// Base64 file contents by path relative to the bot's directory
+    pub files: BTreeMap<String, String>,
+}
+
+impl Bundle {
+    /// Collect the bundled files under `root`, the bot's directory.
+    pub fn collect(root: &Path) -> Result<Self> {
+        let mut paths: Vec<String> = BUNDLED_FILES.iter().map(|p| p.to_string()).collect();
+        // Trade logs archived by a portfolio reset
+        paths.extend(list(root, Path::new(TRADES_FILE).parent().unwrap_or(Path::new(".")))
+            .into_iter()
+            .filter(|p| p.starts_with(&format!("{}.archived-", TRADES_FILE))));
+        // Every named profile, shadow books included
+        paths.extend(list(root, Path::new(PROFILES_DIR)));
+
+        let mut files = BTreeMap::new();
+        for path in paths {
+            let full = root.join(&path);
+            if !full.is_file() {
+                continue;
+            }
+            let content = fs::read(&full).with_context(|| format!("Failed to read {}", full.display()))?;
+            files.insert(path, base64::engine::general_purpose::STANDARD.encode(content));
+        }
+        Ok(Self { format: BUNDLE_FORMAT, exported_at: Utc::now(), exported_by: run_info::current().clone(), files })
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+        let bundle: Self = serde_json::from_str(&content).with_context(|| format!("{} is not a state bundle", path))?;
+        if bundle.format > BUNDLE_FORMAT {
+            anyhow::bail!("{} has bundle format {}, this build reads up to {}", path, bundle.format, BUNDLE_FORMAT);
+        }
+        Ok(bundle)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        if let Some(parent) = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).with_context(|| format!("Failed to write {}", path))
+    }
+
+    /// Bundled files that already exist under `root`.
+    pub fn conflicts(&self, root: &Path) -> Vec<String> {
+        self.files.keys().filter(|p| root.join(p).exists()).cloned().collect()
+    }
+
+    /// Write every file under `root`. Existing files are moved aside to
+    /// `<file>.pre-import-<timestamp>` first. Returns the paths written.
+    pub fn unpack(&self, root: &Path) -> Result<Vec<String>> {
+        let mut decoded = Vec::new();
+        for (path, content) in &self.files {
+            if !is_safe(path) {
+                anyhow::bail!("Refusing to import {}: outside data/", path);
+            }
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(content)
+                .with_context(|| format!("Corrupt content for {}", path))?;
+            decoded.push((path, bytes));
+        }
+
+        let stamp = Utc::now().format("%Y%m%dT%H%M%S");
+        let mut written = Vec::new();
+        for (path, bytes) in decoded {
+            let target = root.join(path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            if target.exists() {
+                let aside = format!("{}.pre-import-{}", target.display(), stamp);
+                fs::rename(&target, &aside).with_context(|| format!("Failed to move {} aside", target.display()))?;
+            }
+            fs::write(&target, bytes).with_context(|| format!("Failed to write {}", target.display()))?;
+            written.push(path.clone());
+        }
+        Ok(written)
+    }
+}
+
+/// Files directly in `dir` under `root`, as paths relative to `root`.
+/// Backups and temporary files are left out.
+fn list(root: &Path, dir: &Path) -> Vec<String> {
+    let mut paths: Vec<String> = fs::read_dir(root.join(dir))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !name.ends_with(".bak") && !name.ends_with(".tmp"))
+        .map(|name| dir.join(name).to_string_lossy().to_string())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// A relative path under `data/` that cannot climb out of it.
+fn is_safe(path: &str) -> bool {
+    let path = Path::new(path);
+    path.starts_with("data") && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_round_trips_between_directories() {
+        let laptop = std::env::temp_dir().join(format!("polybot-laptop-{}", uuid::Uuid::new_v4()));
+        let vps = std::env::temp_dir().join(format!("polybot-vps-{}", uuid::Uuid::new_v4()));
+        let write = |root: &Path, path: &str, content: &str| {
+            let full = root.join(path);
+            fs::create_dir_all(full.parent().unwrap()).unwrap();
+            fs::write(full, content).unwrap();
+        };
+        write(&laptop, PORTFOLIO_FILE, "{\"cash_balance\": 900}");
+        write(&laptop, &format!("{}.archived-20260101T000000", TRADES_FILE), "[]");
+        write(&laptop, &format!("{}/arb-only.trades.json", PROFILES_DIR), "[1]");
+        write(&laptop, &format!("{}/arb-only.json.bak", PROFILES_DIR), "old");
+        write(&laptop, PROCESSED_TRADES_FILE, "[\"0xabc\"]");
+        write(&laptop, "data/books.json", "{}");
+
+        let bundle = Bundle::collect(&laptop).unwrap();
+        let paths: Vec<&str> = bundle.files.keys().map(String::as_str).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "data/paper_trades.json.archived-20260101T000000",
+                "data/portfolio.json",
+                "data/portfolios/arb-only.trades.json",
+                "data/state/copy_trades.json",
+            ]
+        );
+
+        let file = laptop.join("bundle.json").to_string_lossy().to_string();
+        bundle.save(&file).unwrap();
+        write(&vps, PORTFOLIO_FILE, "{\"cash_balance\": 1000}");
+        let bundle = Bundle::load(&file).unwrap();
+        assert_eq!(bundle.conflicts(&vps), vec![PORTFOLIO_FILE.to_string()]);
+        assert_eq!(bundle.unpack(&vps).unwrap().len(), 4);
+        assert_eq!(fs::read_to_string(vps.join(PORTFOLIO_FILE)).unwrap(), "{\"cash_balance\": 900}");
+        assert_eq!(fs::read_to_string(vps.join(PROCESSED_TRADES_FILE)).unwrap(), "[\"0xabc\"]");
+        let aside = fs::read_dir(vps.join("data")).unwrap().flatten().filter(|e| {
+            e.file_name().to_string_lossy().starts_with("portfolio.json.pre-import-")
+        });
+        assert_eq!(aside.count(), 1);
+
+        // Paths escaping data/ are refused
+        let mut evil = bundle.clone();
+        evil.files.insert("data/../.env".to_string(), String::new());
+        assert!(evil.unpack(&vps).is_err());
+        let _ = fs::remove_dir_all(&laptop);
+        let _ = fs::remove_dir_all(&vps);
+    }
+}
@@ -1,11 +1,14 @@
 //! Cache module.
 //!
-//! Basic in-memory cache for market data.
+//! Basic in-memory cache for market data, keyed by venue market.
 
 use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use super::clock::{system_clock, SharedClock};
+use super::MarketId;
+
 struct CacheItem<T> {
     data: T,
     expiry: Instant,
@@ -13,24 +16,31 @@ struct CacheItem<T> {
 
 /// Simple TTL cache.
 pub struct Cache<T> {
-    items: Mutex<HashMap<String, CacheItem<T>>>,
+    items: Mutex<HashMap<MarketId, CacheItem<T>>>,
     ttl: Duration,
+    clock: SharedClock,
 }
 
 impl<T: Clone> Cache<T> {
     /// Create a new cache with specific TTL.
     pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, system_clock())
+    }
+
+    /// Create a cache whose expiry is measured against `clock`.
+    pub fn with_clock(ttl: Duration, clock: SharedClock) -> Self {
         Self {
             items: Mutex::new(HashMap::new()),
             ttl,
+            clock,
         }
     }
 
     /// Get item from cache if not expired.
-    pub fn get(&self, key: &str) -> Option<T> {
+    pub fn get(&self, key: &MarketId) -> Option<T> {
         let mut items = self.items.lock().unwrap();
         if let Some(item) = items.get(key) {
-            if item.expiry > Instant::now() {
+            if item.expiry > self.clock.instant() {
                 return Some(item.data.clone());
             } else {
                 items.remove(key);
@@ -40,11 +50,34 @@ impl<T: Clone> Cache<T> {
     }
 
     /// Insert item into cache.
-    pub fn set(&self, key: &str, data: T) {
+    pub fn set(&self, key: &MarketId, data: T) {
         let mut items = self.items.lock().unwrap();
-        items.insert(key.to_string(), CacheItem {
+        items.insert(key.clone(), CacheItem {
             data,
-            expiry: Instant::now() + self.ttl,
+            expiry: self.clock.instant() + self.ttl,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::clock::MockClock;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_ttl_follows_injected_clock() {
+        let clock = Arc::new(MockClock::new(chrono::Utc::now()));
+        let cache = Cache::with_clock(Duration::from_secs(30), clock.clone());
+
+        let book = MarketId::polymarket("123");
+        cache.set(&book, 42);
+        clock.advance(Duration::from_secs(29));
+        assert_eq!(cache.get(&book), Some(42));
+        // Same ID, other venue
+        assert_eq!(cache.get(&MarketId::kalshi("123")), None);
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(cache.get(&book), None);
+    }
+}
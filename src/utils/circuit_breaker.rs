@@ -2,13 +2,37 @@
 //!
 //! Automatically halts trading if certain conditions (e.g., error rate, large losses) are met.
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tokio::sync::watch;
 use tracing::warn;
 
+use super::state;
+
+/// Default breaker state location.
+pub const BREAKER_FILE: &str = "data/state/circuit_breaker.json";
+
+/// Persisted breaker state, so a trip survives a restart.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BreakerState {
+    pub tripped: bool,
+    pub reason: Option<String>,
+    pub tripped_at: Option<DateTime<Utc>>,
+}
+
 /// Circuit breaker state.
 pub struct CircuitBreaker {
     /// Whether the breaker is tripped (true = halted)
     tripped: AtomicBool,
+    /// Wakes tasks waiting on [`CircuitBreaker::tripped`]
+    halted: watch::Sender<bool>,
+    /// Why and when it last tripped
+    state: Mutex<BreakerState>,
+    /// File the state is persisted to
+    file_path: Option<String>,
 }
 
 impl CircuitBreaker {
@@ -16,19 +40,59 @@ impl CircuitBreaker {
     pub fn new() -> Self {
         Self {
             tripped: AtomicBool::new(false),
+            halted: watch::Sender::new(false),
+            state: Mutex::new(BreakerState::default()),
+            file_path: None,
+        }
+    }
+
+    /// Restore from file; a breaker tripped before a restart stays tripped.
+    /// An unreadable file is moved aside and the breaker starts tripped, since
+    /// it may have been holding trading halted.
+    pub fn load(file_path: &str) -> Self {
+        let exists = Path::new(file_path).exists();
+        let saved = state::read::<BreakerState>(file_path);
+        let breaker = Self {
+            tripped: AtomicBool::new(false),
+            halted: watch::Sender::new(false),
+            state: Mutex::new(BreakerState::default()),
+            file_path: Some(file_path.to_string()),
+        };
+        match saved {
+            Some(saved) => {
+                breaker.tripped.store(saved.tripped, Ordering::SeqCst);
+                breaker.halted.send_replace(saved.tripped);
+                *breaker.state.lock().unwrap() = saved;
+            }
+            None if exists => {
+                if let Err(e) = state::quarantine(file_path) {
+                    warn!("{:#}", e);
+                }
+                breaker.trip(&format!("Unreadable breaker state in {}", file_path));
+            }
+            None => {}
         }
+        breaker
     }
 
     /// Trip the breaker, halting all trades.
     pub fn trip(&self, reason: &str) {
         if !self.tripped.swap(true, Ordering::SeqCst) {
             warn!("🛑 CIRCUIT BREAKER TRIPPED: {}", reason);
+            self.halted.send_replace(true);
+            self.update(BreakerState {
+                tripped: true,
+                reason: Some(reason.to_string()),
+                tripped_at: Some(Utc::now()),
+            });
         }
     }
 
     /// Reset the breaker.
     pub fn reset(&self) {
         self.tripped.store(false, Ordering::SeqCst);
+        self.halted.send_replace(false);
+        self.update(BreakerState::default());
         warn!("🟢 Circuit breaker reset");
     }
 
@@ -36,4 +100,80 @@ impl CircuitBreaker {
     pub fn is_allowed(&self) -> bool {
         !self.tripped.load(Ordering::SeqCst)
     }
+
+    /// Resolves once the breaker is tripped, right away if it already is.
+    /// Tasks that block on a socket, a channel or a long sleep race it so
+    /// they stop with the rest of the bot.
+    pub async fn tripped(&self) {
+        let mut halted = self.halted.subscribe();
+        // The sender lives as long as `self`, so this can't fail
+        let _ = halted.wait_for(|halted| *halted).await;
+    }
+
+    /// Current state, including why it tripped.
+    pub fn state(&self) -> BreakerState {
+        self.state.lock().unwrap().clone()
+    }
+
+    fn update(&self, new_state: BreakerState) {
+        let mut current = self.state.lock().unwrap();
+        *current = new_state;
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &*current) {
+                warn!("Failed to persist circuit breaker state: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_tripped_wakes_waiting_tasks() {
+        let breaker = Arc::new(CircuitBreaker::new());
+        let waiting = tokio::spawn({
+            let breaker = breaker.clone();
+            async move { breaker.tripped().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiting.is_finished());
+
+        breaker.trip("test");
+        tokio::time::timeout(Duration::from_secs(1), waiting).await.unwrap().unwrap();
+        // Already tripped resolves at once
+        tokio::time::timeout(Duration::from_secs(1), breaker.tripped()).await.unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_state_starts_tripped() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-breaker-{}.json", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        assert!(CircuitBreaker::load(&path).is_allowed());
+
+        std::fs::write(&path, "{\"tripped\": tru").unwrap();
+        let breaker = CircuitBreaker::load(&path);
+        assert!(!breaker.is_allowed());
+        assert!(breaker.state().reason.unwrap().contains("Unreadable"));
+
+        // The trip replaced the file, so it survives the next restart too
+        assert!(!CircuitBreaker::load(&path).is_allowed());
+        for entry in std::fs::read_dir(std::env::temp_dir()).unwrap().flatten() {
+            let name = entry.path().to_string_lossy().to_string();
+            if name.starts_with(&path) {
+                let _ = std::fs::remove_file(name);
+            }
+        }
+    }
 }
@@ -0,0 +1,82 @@
+//! Clock abstraction.
+//!
+//! Time-dependent logic (copy-trade staleness, cache TTLs, daily trade
+//! caps, de-risking, market cooldowns, resolution warnings and the window
+//! of markets discovered) reads time through a `Clock` so tests and the
+//! backtester can control it instead of calling `Utc::now()` directly.
+
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Source of wall-clock and monotonic time.
+pub trait Clock: Send + Sync + std::fmt::Debug {
+    /// Current wall-clock time.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic instant, for measuring elapsed time.
+    fn instant(&self) -> Instant;
+}
+
+/// Shared handle to a clock.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real system clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Get a shared handle to the system clock.
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Manually driven clock for tests and replays.
+#[derive(Debug)]
+pub struct MockClock {
+    start_time: DateTime<Utc>,
+    start_instant: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl MockClock {
+    /// Create a clock frozen at `start_time`.
+    pub fn new(start_time: DateTime<Utc>) -> Self {
+        Self {
+            start_time,
+            start_instant: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move time forward.
+    pub fn advance(&self, by: Duration) {
+        *self.elapsed.lock().unwrap() += by;
+    }
+
+    /// Jump to a specific wall-clock time (must not be before the start time).
+    pub fn set(&self, time: DateTime<Utc>) {
+        let elapsed = (time - self.start_time).to_std().unwrap_or(Duration::ZERO);
+        *self.elapsed.lock().unwrap() = elapsed;
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        let elapsed = *self.elapsed.lock().unwrap();
+        self.start_time + chrono::Duration::from_std(elapsed).unwrap_or_default()
+    }
+
+    fn instant(&self) -> Instant {
+        self.start_instant + *self.elapsed.lock().unwrap()
+    }
+}
@@ -0,0 +1,156 @@
+//! Optional at-rest encryption of state files.
+//!
+//! With `DATA_ENCRYPTION_KEY` (or `DATA_ENCRYPTION_KEY_FILE`) set to a
+//! base64 32-byte key, files written through [`super::state`] are sealed
+//! with AES-256-GCM: whole state files, and each line of the append-only
+//! logs (audit, quote tape, resolutions, conflicts, alerts, leaderboard
+//! snapshots, run records and the raw API log). Plaintext files still
+//! load, so existing data is encrypted the next time it is saved; lines
+//! already appended stay as they were. Recorded API fixtures and `.env`
+//! stay plaintext, and bundles copy files byte for byte. The key is read
+//! from the environment on first use so every binary picks it up.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::sync::OnceLock;
+
+/// Prefix marking an encrypted file.
+const MAGIC: &[u8] = b"PBENC1";
+const NONCE_LEN: usize = 12;
+
+/// The configured key, or why it couldn't be read.
+static KEY: OnceLock<std::result::Result<Option<DataKey>, String>> = OnceLock::new();
+
+/// AES-256-GCM key for data files.
+#[derive(Clone)]
+pub struct DataKey(Aes256Gcm);
+
+impl DataKey {
+    /// Key from base64 (32 bytes once decoded).
+    pub fn parse(encoded: &str) -> Result<Self> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .context("Key is not valid base64")?;
+        let cipher = Aes256Gcm::new_from_slice(&bytes)
+            .map_err(|_| anyhow::anyhow!("Key must be 32 bytes, got {}", bytes.len()))?;
+        Ok(Self(cipher))
+    }
+
+    /// Key from the environment, if one is configured.
+    pub fn from_env() -> Result<Option<Self>> {
+        let _ = dotenvy::dotenv();
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.trim().is_empty());
+        if let Some(key) = var("DATA_ENCRYPTION_KEY") {
+            return Self::parse(&key).context("Invalid DATA_ENCRYPTION_KEY").map(Some);
+        }
+        if let Some(path) = var("DATA_ENCRYPTION_KEY_FILE") {
+            let key = std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path))?;
+            return Self::parse(&key).context("Invalid DATA_ENCRYPTION_KEY_FILE").map(Some);
+        }
+        Ok(None)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.0.encrypt(&nonce, plaintext).expect("AES-GCM encryption failed");
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<Vec<u8>> {
+        let body = sealed.strip_prefix(MAGIC).context("Not an encrypted file")?;
+        if body.len() < NONCE_LEN {
+            anyhow::bail!("Encrypted file is truncated");
+        }
+        let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+        self.0
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow::anyhow!("Decryption failed: wrong key or corrupt file"))
+    }
+}
+
+/// Whether `content` was written encrypted.
+pub fn is_encrypted(content: &[u8]) -> bool {
+    content.starts_with(MAGIC)
+}
+
+/// The configured key. An invalid key is an error on every use rather
+/// than a silent fallback to plaintext; [`check`] reports it up front.
+pub fn key() -> Result<Option<&'static DataKey>> {
+    match KEY.get_or_init(|| DataKey::from_env().map_err(|e| format!("{:#}", e))) {
+        Ok(key) => Ok(key.as_ref()),
+        Err(e) => Err(anyhow::anyhow!("{}", e)),
+    }
+}
+
+/// Validate the configured key. Returns whether encryption is enabled.
+pub fn check() -> Result<bool> {
+    Ok(key()?.is_some())
+}
+
+/// Seal `plaintext` when a key is configured.
+pub fn seal(plaintext: &str) -> Result<Vec<u8>> {
+    Ok(match key()? {
+        Some(key) => key.encrypt(plaintext.as_bytes()),
+        None => plaintext.as_bytes().to_vec(),
+    })
+}
+
+/// Open file content, encrypted or not.
+pub fn open(content: &[u8]) -> Result<String> {
+    let plaintext = if is_encrypted(content) {
+        key()?
+            .context("File is encrypted but DATA_ENCRYPTION_KEY is not set")?
+            .decrypt(content)?
+    } else {
+        content.to_vec()
+    };
+    String::from_utf8(plaintext).context("File is not UTF-8")
+}
+
+/// Seal one line of an append-only log: the sealed bytes in base64 when a
+/// key is configured, the line itself otherwise.
+pub fn seal_line(line: &str) -> Result<String> {
+    Ok(match key()? {
+        Some(key) => base64::engine::general_purpose::STANDARD.encode(key.encrypt(line.as_bytes())),
+        None => line.to_string(),
+    })
+}
+
+/// Open a line written by [`seal_line`], encrypted or not.
+pub fn open_line(line: &str) -> Result<String> {
+    // JSON lines are never valid base64, so only sealed lines decode
+    match base64::engine::general_purpose::STANDARD.decode(line.trim()) {
+        Ok(sealed) if is_encrypted(&sealed) => open(&sealed),
+        _ => Ok(line.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_wrong_key() {
+        let key = DataKey::parse(&base64::engine::general_purpose::STANDARD.encode([7u8; 32])).unwrap();
+        let sealed = key.encrypt(br#"{"cash_balance":1000.0}"#);
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.windows(12).any(|w| w == b"cash_balance"));
+        assert_eq!(key.decrypt(&sealed).unwrap(), br#"{"cash_balance":1000.0}"#);
+
+        let other = DataKey::parse(&base64::engine::general_purpose::STANDARD.encode([8u8; 32])).unwrap();
+        assert!(other.decrypt(&sealed).is_err());
+        assert!(DataKey::parse("c2hvcnQ=").is_err());
+    }
+
+    #[test]
+    fn test_plaintext_lines_open_as_is() {
+        let line = r#"{"kind":"fill"}"#;
+        assert_eq!(open_line(line).unwrap(), line);
+        let key = DataKey::parse(&base64::engine::general_purpose::STANDARD.encode([7u8; 32])).unwrap();
+        let sealed = base64::engine::general_purpose::STANDARD.encode(key.encrypt(line.as_bytes()));
+        assert!(!sealed.contains("fill"));
+        assert!(is_encrypted(&base64::engine::general_purpose::STANDARD.decode(&sealed).unwrap()));
+    }
+}
@@ -0,0 +1,260 @@
+//! Endpoint failover.
+//!
+//! Each venue can list fallback base URLs (alternative Kalshi API hosts,
+//! backup Polygon RPCs). Requests go to the active endpoint; after enough
+//! consecutive connection errors the next one takes over, wrapping back to
+//! the first after the last. Which endpoints are active is written to
+//! [`ENDPOINTS_FILE`] for the TUI's System panel.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use tracing::warn;
+
+use super::state;
+use crate::config::Config;
+use crate::kalshi::client::KALSHI_API_URL;
+use crate::polymarket::client::CLOB_API_URL;
+
+/// Active endpoints, shared with the TUI.
+pub const ENDPOINTS_FILE: &str = "data/state/endpoints.json";
+pub const KALSHI: &str = "kalshi";
+pub const POLYMARKET: &str = "polymarket";
+pub const POLYGON_RPC: &str = "polygon_rpc";
+const DEFAULT_FAILOVER_AFTER: u32 = 3;
+
+static GLOBAL: OnceLock<Endpoints> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+struct Group {
+    urls: Vec<String>,
+    active: usize,
+    consecutive_errors: u32,
+    failovers: u32,
+    last_failover: Option<DateTime<Utc>>,
+}
+
+/// Which endpoint a service is using, without credentials.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EndpointStatus {
+    pub service: String,
+    /// Host of the active endpoint (paths can hold API keys)
+    pub host: String,
+    /// Position of the active endpoint in the list; 0 is the primary
+    pub index: usize,
+    pub count: usize,
+    pub consecutive_errors: u32,
+    pub failovers: u32,
+    pub last_failover: Option<DateTime<Utc>>,
+}
+
+impl EndpointStatus {
+    pub fn on_fallback(&self) -> bool {
+        self.index > 0
+    }
+}
+
+/// Endpoint lists per service and which one is active.
+#[derive(Debug)]
+pub struct Endpoints {
+    groups: Mutex<BTreeMap<String, Group>>,
+    failover_after: u32,
+    status_path: Option<String>,
+}
+
+impl Endpoints {
+    /// Fail over after `failover_after` consecutive connection errors.
+    pub fn new(failover_after: u32) -> Self {
+        Self {
+            groups: Mutex::new(BTreeMap::new()),
+            failover_after: failover_after.max(1),
+            status_path: None,
+        }
+    }
+
+    /// Write the active endpoints to `path` whenever they change.
+    pub fn with_status_file(mut self, path: &str) -> Self {
+        self.status_path = Some(path.to_string());
+        self
+    }
+
+    /// Register `service` with its primary URL followed by fallbacks.
+    pub fn register(self, service: &str, urls: Vec<String>) -> Self {
+        let urls: Vec<String> = urls.into_iter().map(|u| u.trim_end_matches('/').to_string()).collect();
+        if !urls.is_empty() {
+            let group = Group { urls, active: 0, consecutive_errors: 0, failovers: 0, last_failover: None };
+            self.groups.lock().unwrap().insert(service.to_string(), group);
+        }
+        self
+    }
+
+    /// The active URL for `service`.
+    pub fn active(&self, service: &str) -> Option<String> {
+        let groups = self.groups.lock().unwrap();
+        groups.get(service).map(|g| g.urls[g.active].clone())
+    }
+
+    /// Record a request's outcome; connection errors count towards
+    /// failover. True when this switched endpoints.
+    pub fn record(&self, service: &str, connection_error: bool) -> bool {
+        let mut groups = self.groups.lock().unwrap();
+        let Some(group) = groups.get_mut(service) else {
+            return false;
+        };
+        if !connection_error {
+            group.consecutive_errors = 0;
+            return false;
+        }
+        group.consecutive_errors += 1;
+        if group.consecutive_errors < self.failover_after || group.urls.len() < 2 {
+            return false;
+        }
+        let from = host(&group.urls[group.active]);
+        group.active = (group.active + 1) % group.urls.len();
+        group.consecutive_errors = 0;
+        group.failovers += 1;
+        group.last_failover = Some(Utc::now());
+        warn!(
+            "🔀 {} failing over from {} to {} after {} connection errors",
+            service, from, host(&group.urls[group.active]), self.failover_after
+        );
+        drop(groups);
+        self.save();
+        true
+    }
+
+    /// Make `url` the active endpoint of `service`, for services that pick
+    /// their own. True when it changed.
+    pub fn set_active(&self, service: &str, url: &str) -> bool {
+        let mut groups = self.groups.lock().unwrap();
+        let Some(group) = groups.get_mut(service) else {
+            return false;
+        };
+        let Some(index) = group.urls.iter().position(|u| u == url.trim_end_matches('/')) else {
+            return false;
+        };
+        if index == group.active {
+            return false;
+        }
+        group.active = index;
+        group.consecutive_errors = 0;
+        group.failovers += 1;
+        group.last_failover = Some(Utc::now());
+        drop(groups);
+        self.save();
+        true
+    }
+
+    /// Record the outcome of a request to `url`, if it went to an active
+    /// endpoint.
+    pub fn observe(&self, url: &str, connection_error: bool) {
+        let service = {
+            let groups = self.groups.lock().unwrap();
+            groups.iter().find(|(_, g)| url.starts_with(&g.urls[g.active])).map(|(s, _)| s.clone())
+        };
+        if let Some(service) = service {
+            self.record(&service, connection_error);
+        }
+    }
+
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        let groups = self.groups.lock().unwrap();
+        groups
+            .iter()
+            .map(|(service, g)| EndpointStatus {
+                service: service.clone(),
+                host: host(&g.urls[g.active]),
+                index: g.active,
+                count: g.urls.len(),
+                consecutive_errors: g.consecutive_errors,
+                failovers: g.failovers,
+                last_failover: g.last_failover,
+            })
+            .collect()
+    }
+
+    fn save(&self) {
+        if let Some(path) = &self.status_path {
+            if let Err(e) = state::save(path, &self.status()) {
+                warn!("Failed to save endpoint status: {}", e);
+            }
+        }
+    }
+}
+
+/// Host (and port) of a URL.
+fn host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?']).next().unwrap_or(rest).to_string()
+}
+
+fn defaults() -> Endpoints {
+    Endpoints::new(DEFAULT_FAILOVER_AFTER)
+        .register(KALSHI, vec![KALSHI_API_URL.to_string()])
+        .register(POLYMARKET, vec![CLOB_API_URL.to_string()])
+}
+
+/// Install the process-wide endpoint lists from config. Call before
+/// creating clients.
+pub fn init(config: &Config) {
+    let with_fallbacks = |primary: &str, fallbacks: &[String]| {
+        std::iter::once(primary.to_string()).chain(fallbacks.iter().cloned()).collect::<Vec<_>>()
+    };
+    let endpoints = Endpoints::new(config.failover_after_errors)
+        .with_status_file(ENDPOINTS_FILE)
+        .register(KALSHI, with_fallbacks(KALSHI_API_URL, &config.kalshi_api_fallbacks))
+        .register(POLYMARKET, with_fallbacks(CLOB_API_URL, &config.polymarket_clob_fallbacks))
+        .register(POLYGON_RPC, with_fallbacks(&config.polygon_rpc_url, &config.polygon_rpc_fallbacks));
+    if GLOBAL.set(endpoints).is_ok() {
+        global().save();
+    }
+}
+
+pub fn global() -> &'static Endpoints {
+    GLOBAL.get_or_init(defaults)
+}
+
+/// Active base URL for `service`, or `default` when it isn't registered.
+pub fn base(service: &str, default: &str) -> String {
+    global().active(service).unwrap_or_else(|| default.to_string())
+}
+
+/// Active endpoints last written by the bot.
+pub fn load_status(path: &str) -> Vec<EndpointStatus> {
+    state::load(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fails_over_after_sustained_errors() {
+        let endpoints = Endpoints::new(2).register(
+            KALSHI,
+            vec!["https://a.example.com/v2/".to_string(), "https://b.example.com/v2".to_string()],
+        );
+        assert_eq!(endpoints.active(KALSHI).unwrap(), "https://a.example.com/v2");
+
+        // A success in between resets the count
+        endpoints.observe("https://a.example.com/v2/markets", true);
+        endpoints.observe("https://a.example.com/v2/markets", false);
+        endpoints.observe("https://a.example.com/v2/markets", true);
+        assert_eq!(endpoints.status()[0].index, 0);
+
+        assert!(endpoints.record(KALSHI, true));
+        let status = &endpoints.status()[0];
+        assert_eq!((status.host.as_str(), status.index, status.failovers), ("b.example.com", 1, 1));
+        assert!(status.on_fallback());
+
+        // Requests to the old endpoint no longer count; the list wraps around
+        endpoints.observe("https://a.example.com/v2/markets", true);
+        endpoints.observe("https://a.example.com/v2/markets", true);
+        assert_eq!(endpoints.status()[0].index, 1);
+        endpoints.record(KALSHI, true);
+        endpoints.record(KALSHI, true);
+        assert_eq!(endpoints.active(KALSHI).unwrap(), "https://a.example.com/v2");
+        assert!(!endpoints.record("unknown", true));
+    }
+}
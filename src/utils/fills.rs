@@ -0,0 +1,315 @@
+//! Fill routing.
+//!
+//! Venue user streams report our own fills and order closures here; the
+//! router updates the position tracker and open order store, writes the
+//! audit log, and broadcasts fills to anyone waiting on an order (e.g. the
+//! executor confirming a leg). Fills that don't match the order on record
+//! open a discrepancy.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::info;
+
+use super::audit_log::{AuditEvent, AuditLog};
+use super::run_info;
+use super::{OpenOrder, OpenOrders, PositionTracker};
+use crate::analysis::capture::CaptureLog;
+use crate::analysis::execution::ExecutionLog;
+use crate::display;
+use crate::risk::discrepancies::DiscrepancyLedger;
+
+/// Buffered fills per subscriber before slow receivers start lagging.
+const FILL_CHANNEL_CAPACITY: usize = 256;
+
+/// One of our orders (partially) filling.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    pub venue: String,
+    pub order_id: String,
+    /// Venue trade/fill ID, unique per fill
+    pub trade_id: String,
+    /// Token ID or ticker
+    pub market: String,
+    /// "BUY" or "SELL"
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub filled_at: DateTime<Utc>,
+}
+
+impl Fill {
+    /// Size signed by side (sells negative).
+    pub fn signed_size(&self) -> f64 {
+        if self.side.eq_ignore_ascii_case("sell") { -self.size } else { self.size }
+    }
+}
+
+/// Applies venue fill events to local state.
+pub struct FillRouter {
+    positions: Arc<PositionTracker>,
+    open_orders: Option<Arc<Mutex<OpenOrders>>>,
+    audit: Option<Arc<AuditLog>>,
+    discrepancies: Option<Arc<DiscrepancyLedger>>,
+    executions: Option<Arc<Mutex<ExecutionLog>>>,
+    capture: Option<Arc<Mutex<CaptureLog>>>,
+    sender: broadcast::Sender<Fill>,
+    /// Fills already applied, so reconnect replays are not double counted
+    seen: Mutex<HashSet<String>>,
+    /// Cumulative filled size per order ID
+    filled: Mutex<HashMap<String, f64>>,
+}
+
+impl FillRouter {
+    /// Route fills into a position tracker.
+    pub fn new(positions: Arc<PositionTracker>) -> Self {
+        let (sender, _) = broadcast::channel(FILL_CHANNEL_CAPACITY);
+        Self {
+            positions,
+            open_orders: None,
+            audit: None,
+            discrepancies: None,
+            executions: None,
+            capture: None,
+            sender,
+            seen: Mutex::new(HashSet::new()),
+            filled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drop orders from the open order store once they close.
+    pub fn with_open_orders(mut self, store: Arc<Mutex<OpenOrders>>) -> Self {
+        self.open_orders = Some(store);
+        self
+    }
+
+    /// Write every event to an audit log.
+    pub fn with_audit(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Open a discrepancy for fills that don't match their order.
+    pub fn with_discrepancies(mut self, ledger: Arc<DiscrepancyLedger>) -> Self {
+        self.discrepancies = Some(ledger);
+        self
+    }
+
+    /// Fills broadcast but not yet read by the slowest subscriber.
+    pub fn backlog(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Add fills to the execution quality log.
+    pub fn with_executions(mut self, log: Arc<Mutex<ExecutionLog>>) -> Self {
+        self.executions = Some(log);
+        self
+    }
+
+    /// Price live arb legs in the capture log from their fills.
+    pub fn with_capture(mut self, log: Arc<Mutex<CaptureLog>>) -> Self {
+        self.capture = Some(log);
+        self
+    }
+
+    /// Receive fills as they are applied.
+    pub fn subscribe(&self) -> broadcast::Receiver<Fill> {
+        self.sender.subscribe()
+    }
+
+    /// The position tracker fills are applied to.
+    pub fn positions(&self) -> &PositionTracker {
+        &self.positions
+    }
+
+    /// Total size filled so far on an order.
+    pub fn filled_size(&self, order_id: &str) -> f64 {
+        self.filled.lock().unwrap().get(order_id).copied().unwrap_or(0.0)
+    }
+
+    /// A still-open order, if the open order store knows it.
+    pub fn open_order(&self, order_id: &str) -> Option<OpenOrder> {
+        let store = self.open_orders.as_ref()?.lock().unwrap();
+        store.all().into_iter().find(|o| o.order_id == order_id).cloned()
+    }
+
+    /// Apply a fill. Returns false if it was already applied.
+    pub fn on_fill(&self, fill: Fill) -> bool {
+        let key = format!("{}:{}:{}", fill.venue, fill.trade_id, fill.order_id);
+        if !self.seen.lock().unwrap().insert(key) {
+            return false;
+        }
+        let order = self.open_order(&fill.order_id);
+        let total = {
+            let mut filled = self.filled.lock().unwrap();
+            let total = filled.entry(fill.order_id.clone()).or_default();
+            *total += fill.size;
+            *total
+        };
+
+        info!(
+            "💥 Fill on {}: {} {} @ {:.2} (order {})",
+            fill.venue, fill.side, fill.size, fill.price, fill.order_id
+        );
+        self.positions.update_position(
+            &fill.venue,
+            &fill.market,
+            fill.signed_size().round() as i32,
+            fill.price,
+        );
+        self.audit(AuditEvent {
+            at: fill.filled_at,
+            venue: fill.venue.clone(),
+            kind: "fill".to_string(),
+            order_id: fill.order_id.clone(),
+            market: fill.market.clone(),
+            side: fill.side.clone(),
+            price: fill.price,
+            size: fill.size,
+            detail: Some(format!("trade {}", fill.trade_id)),
+            run: Some(run_info::current().clone()),
+        });
+        if let (Some(ledger), Some(order)) = (&self.discrepancies, &order) {
+            if let Some(reason) = mismatch(&fill, order, total) {
+                ledger.open_fill(&fill, order, &reason);
+            }
+        }
+        if let Some(executions) = &self.executions {
+            executions.lock().unwrap().record_fill(&fill);
+        }
+        if let Some(capture) = &self.capture {
+            capture.lock().unwrap().record_fill(&fill);
+        }
+        // No receivers is fine: nobody is waiting on this order
+        let _ = self.sender.send(fill);
+        true
+    }
+
+    /// An order stopped resting, e.g. "filled" or "cancelled".
+    pub fn on_order_closed(&self, venue: &str, order_id: &str, reason: &str) {
+        let removed = self.open_orders
+            .as_ref()
+            .and_then(|store| store.lock().unwrap().remove(order_id));
+        info!("📕 Order {} on {} {}", order_id, venue, reason);
+        let (market, side, price, size) = removed
+            .map(|o| (o.market, o.side, o.price, o.size))
+            .unwrap_or_default();
+        self.audit(AuditEvent {
+            at: Utc::now(),
+            venue: venue.to_string(),
+            kind: reason.to_string(),
+            order_id: order_id.to_string(),
+            market,
+            side,
+            price,
+            size,
+            detail: None,
+            run: Some(run_info::current().clone()),
+        });
+    }
+
+    fn audit(&self, event: AuditEvent) {
+        if let Some(audit) = &self.audit {
+            audit.record(&event);
+        }
+    }
+}
+
+/// How a fill disagrees with the order it filled, given the order's total
+/// filled size including it.
+fn mismatch(fill: &Fill, order: &OpenOrder, total: f64) -> Option<String> {
+    const EPSILON: f64 = 1e-6;
+    // Kalshi fills name the side after the ticker
+    if fill.market != order.market && !fill.market.starts_with(&format!("{} ", order.market)) {
+        return Some(format!("filled on {} but the order was for {}", fill.market, order.market));
+    }
+    if total > order.size + EPSILON {
+        return Some(format!("filled {} of a {} order", total, order.size));
+    }
+    // Kalshi orders are recorded at the YES price whichever side they buy
+    if fill.venue == "polymarket" {
+        let buy = !fill.side.eq_ignore_ascii_case("sell");
+        if (buy && fill.price > order.price + EPSILON) || (!buy && fill.price < order.price - EPSILON) {
+            return Some(format!("filled at {} through the {} limit", display::price(fill.price), display::price(order.price)));
+        }
+    }
+    None
+}
+
+/// Wait for the next fill of `order_id`, up to `timeout`.
+pub async fn wait_for_fill(
+    receiver: &mut broadcast::Receiver<Fill>,
+    order_id: &str,
+    timeout: Duration,
+) -> Option<Fill> {
+    tokio::time::timeout(timeout, async {
+        loop {
+            match receiver.recv().await {
+                Ok(fill) if fill.order_id == order_id => return Some(fill),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(trade_id: &str, side: &str, size: f64) -> Fill {
+        Fill {
+            venue: "polymarket".to_string(),
+            order_id: "0xabc".to_string(),
+            trade_id: trade_id.to_string(),
+            market: "token-1".to_string(),
+            side: side.to_string(),
+            price: 0.40,
+            size,
+            filled_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_applies_each_fill_once_and_notifies_waiters() {
+        let router = FillRouter::new(Arc::new(PositionTracker::new()));
+        let mut receiver = router.subscribe();
+
+        assert!(router.on_fill(fill("t1", "BUY", 10.0)));
+        assert!(!router.on_fill(fill("t1", "BUY", 10.0)), "replayed fill");
+        assert!(router.on_fill(fill("t2", "SELL", 4.0)));
+
+        let position = router.positions().get_position("polymarket", "token-1").unwrap();
+        assert_eq!(position.quantity, 6);
+
+        let first = wait_for_fill(&mut receiver, "0xabc", Duration::from_millis(100)).await.unwrap();
+        assert_eq!(first.trade_id, "t1");
+        let mut other = router.subscribe();
+        assert!(wait_for_fill(&mut other, "0xabc", Duration::from_millis(20)).await.is_none());
+    }
+
+    #[test]
+    fn test_flags_fills_that_dont_match_the_order() {
+        let order = OpenOrder {
+            order_id: "0xabc".to_string(),
+            venue: "polymarket".to_string(),
+            market: "token-1".to_string(),
+            side: "BUY".to_string(),
+            price: 0.40,
+            size: 10.0,
+            placed_at: Utc::now(),
+        };
+        assert_eq!(mismatch(&fill("t1", "BUY", 6.0), &order, 6.0), None);
+        assert!(mismatch(&fill("t2", "BUY", 6.0), &order, 12.0).unwrap().contains("filled 12"));
+        let through = Fill { price: 0.45, ..fill("t3", "BUY", 1.0) };
+        assert!(mismatch(&through, &order, 1.0).unwrap().contains("limit"));
+        let elsewhere = Fill { market: "token-2".to_string(), ..fill("t4", "BUY", 1.0) };
+        assert!(mismatch(&elsewhere, &order, 1.0).is_some());
+    }
+}
@@ -0,0 +1,230 @@
+//! Record-and-replay fixtures for API responses.
+//!
+//! In record mode every HTTP response body (and websocket frame) fetched
+//! through a client is written to disk; in replay mode the same payloads are
+//! served back without touching the network. This makes detector, executor
+//! and TUI runs reproducible in tests and offline rehearsals.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::config::Config;
+
+/// How the fixture store interacts with the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Hit the network, store nothing.
+    Off,
+    /// Hit the network and write every response to disk.
+    Record,
+    /// Never hit the network; serve responses from disk.
+    Replay,
+}
+
+impl FixtureMode {
+    /// Parse a mode from a config string ("off", "record", "replay").
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "off" | "none" => Ok(FixtureMode::Off),
+            "record" => Ok(FixtureMode::Record),
+            "replay" => Ok(FixtureMode::Replay),
+            other => anyhow::bail!("Invalid FIXTURE_MODE: {}", other),
+        }
+    }
+}
+
+/// A single recorded HTTP response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    pub body: String,
+}
+
+/// Directory-backed store of recorded responses.
+pub struct FixtureStore {
+    dir: PathBuf,
+    mode: FixtureMode,
+}
+
+impl FixtureStore {
+    /// Create a store rooted at `dir`.
+    pub fn new(dir: impl AsRef<Path>, mode: FixtureMode) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            mode,
+        }
+    }
+
+    /// Build a store from configuration, or `None` when fixtures are off.
+    pub fn from_config(config: &Config) -> Result<Option<Arc<Self>>> {
+        let mode = FixtureMode::parse(&config.fixture_mode)?;
+        if mode == FixtureMode::Off {
+            return Ok(None);
+        }
+        Ok(Some(Arc::new(Self::new(&config.fixture_dir, mode))))
+    }
+
+    /// Current mode.
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    /// File name used for a request.
+    ///
+    /// A readable prefix of the path is kept for browsing; the hash suffix
+    /// keeps long query strings unique.
+    pub fn key(method: &str, url: &str) -> String {
+        let path = url
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(url);
+        let readable: String = path
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .take(60)
+            .collect();
+
+        let mut hasher = Sha256::new();
+        hasher.update(method.to_uppercase().as_bytes());
+        hasher.update(b" ");
+        hasher.update(url.as_bytes());
+        let digest = hasher.finalize();
+        let short: String = digest.iter().take(4).map(|b| format!("{:02x}", b)).collect();
+
+        format!("{}_{}_{}.json", method.to_lowercase(), readable, short)
+    }
+
+    /// Load a recorded response.
+    pub fn load(&self, method: &str, url: &str) -> Result<Fixture> {
+        let path = self.dir.join(Self::key(method, url));
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("No fixture recorded for {} {} ({})", method, url, path.display()))?;
+        serde_json::from_str(&content).context("Failed to parse fixture file")
+    }
+
+    /// Write a response to disk.
+    pub fn save(&self, method: &str, url: &str, status: u16, body: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir).context("Failed to create fixture directory")?;
+        let fixture = Fixture {
+            method: method.to_uppercase(),
+            url: url.to_string(),
+            status,
+            body: body.to_string(),
+        };
+        let path = self.dir.join(Self::key(method, url));
+        fs::write(&path, serde_json::to_string_pretty(&fixture)?)
+            .context("Failed to write fixture file")?;
+        debug!("Recorded fixture {}", path.display());
+        Ok(())
+    }
+
+    /// Append a websocket frame to the recording for `stream`.
+    pub fn record_frame(&self, stream: &str, frame: &str) -> Result<()> {
+        if self.mode != FixtureMode::Record {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir).context("Failed to create fixture directory")?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.frames_path(stream))
+            .context("Failed to open frame recording")?;
+        writeln!(file, "{}", frame.replace('\n', " ")).context("Failed to write frame")?;
+        Ok(())
+    }
+
+    /// Load every recorded frame for `stream`, in arrival order.
+    pub fn load_frames(&self, stream: &str) -> Result<Vec<String>> {
+        let content = fs::read_to_string(self.frames_path(stream))
+            .with_context(|| format!("No frames recorded for stream {}", stream))?;
+        Ok(content.lines().map(|l| l.to_string()).collect())
+    }
+
+    fn frames_path(&self, stream: &str) -> PathBuf {
+        let name: String = stream
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("ws_{}.jsonl", name))
+    }
+}
+
+/// Perform a GET request, honouring the fixture mode if a store is present.
+///
+/// Returns the HTTP status code and the raw body text.
+pub async fn fetch_text(
+    http: &Client,
+    fixtures: Option<&FixtureStore>,
+    url: &str,
+) -> Result<(u16, String)> {
+    fetch_text_with_headers(http, fixtures, url, &[]).await
+}
+
+/// [`fetch_text`] with extra request headers, e.g. auth. Fixtures are keyed
+/// by URL alone, so recorded responses replay without credentials.
+pub async fn fetch_text_with_headers(
+    http: &Client,
+    fixtures: Option<&FixtureStore>,
+    url: &str,
+    headers: &[(String, String)],
+) -> Result<(u16, String)> {
+    if let Some(store) = fixtures {
+        if store.mode() == FixtureMode::Replay {
+            let fixture = store.load("GET", url)?;
+            return Ok((fixture.status, fixture.body));
+        }
+    }
+
+    let mut request = http.get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    let response = super::http_pool::send(request)
+        .await
+        .with_context(|| format!("Request to {} failed", url))?;
+    let status = response.status().as_u16();
+    let body = response.text().await.context("Failed to read response body")?;
+    super::raw_log::record("GET", url, None, status, &body);
+
+    if let Some(store) = fixtures {
+        if store.mode() == FixtureMode::Record {
+            store.save("GET", url, status, &body)?;
+        }
+    }
+
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_then_replay() {
+        let dir = std::env::temp_dir().join(format!("polybot-fixtures-{}", uuid::Uuid::new_v4()));
+        let store = FixtureStore::new(&dir, FixtureMode::Record);
+        let url = "https://clob.polymarket.com/book?token_id=123";
+
+        store.save("GET", url, 200, "{\"bids\":[]}").unwrap();
+        store.record_frame("poly market", "{\"a\":1}").unwrap();
+        store.record_frame("poly market", "{\"a\":2}").unwrap();
+
+        let replay = FixtureStore::new(&dir, FixtureMode::Replay);
+        let fixture = replay.load("GET", url).unwrap();
+        assert_eq!(fixture.status, 200);
+        assert_eq!(fixture.body, "{\"bids\":[]}");
+        assert_eq!(replay.load_frames("poly market").unwrap().len(), 2);
+        assert!(replay.load("GET", "https://example.com/other").is_err());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
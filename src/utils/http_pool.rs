@@ -0,0 +1,272 @@
+//! Shared HTTP client.
+//!
+//! The venue clients, copy trader, analyzer and notifiers all send through
+//! one reqwest client, so connections (and HTTP/2 sessions) to each host
+//! are pooled and kept warm across modules instead of every module holding
+//! its own pool. Requests sent through [`send`] are counted per host for
+//! the pool metrics the bot writes to [`HTTP_POOL_FILE`], and their
+//! connection errors drive endpoint failover.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use super::{endpoints, raw_log};
+use crate::config::Config;
+
+/// Latest pool metrics, written by the running bot.
+pub const HTTP_POOL_FILE: &str = "data/http_pool.json";
+/// Default request timeout; callers needing less set it per request.
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// How long an HTTP/2 keep-alive ping may go unanswered.
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+static SETTINGS: OnceLock<PoolSettings> = OnceLock::new();
+static HOSTS: OnceLock<Mutex<HashMap<String, HostCounters>>> = OnceLock::new();
+
+/// A request built and signed but not sent, as `polybot rehearse` prints it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PreparedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl PreparedRequest {
+    /// Headers with credential values masked.
+    pub fn redacted_headers(&self) -> Vec<(String, String)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if raw_log::is_sensitive(name) { "[REDACTED]".to_string() } else { value.clone() };
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    /// The request as a reqwest builder on `http`.
+    pub fn to_builder(&self, http: &Client) -> RequestBuilder {
+        let method = reqwest::Method::from_bytes(self.method.as_bytes()).unwrap_or(reqwest::Method::POST);
+        let mut request = http.request(method, &self.url).body(self.body.clone());
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+}
+
+/// Connection pool tuning.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PoolSettings {
+    /// Idle connections kept open per host
+    pub max_idle_per_host: usize,
+    /// Idle connections are closed after this many seconds
+    pub idle_timeout_secs: u64,
+    /// TCP keep-alive and HTTP/2 ping interval
+    pub keep_alive_secs: u64,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self { max_idle_per_host: 16, idle_timeout_secs: 90, keep_alive_secs: 30 }
+    }
+}
+
+impl PoolSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_idle_per_host: config.http_pool_size,
+            idle_timeout_secs: config.http_idle_timeout_secs,
+            keep_alive_secs: config.http_keep_alive_secs,
+        }
+    }
+
+    fn build(&self) -> Result<Client> {
+        let keep_alive = Duration::from_secs(self.keep_alive_secs.max(1));
+        Client::builder()
+            .timeout(TIMEOUT)
+            .pool_max_idle_per_host(self.max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(self.idle_timeout_secs))
+            .tcp_nodelay(true)
+            .tcp_keepalive(keep_alive)
+            .http2_keep_alive_interval(keep_alive)
+            .http2_keep_alive_timeout(PING_TIMEOUT)
+            .http2_keep_alive_while_idle(true)
+            .http2_adaptive_window(true)
+            .build()
+            .context("Failed to create HTTP client")
+    }
+}
+
+/// Build the process-wide client from config. Clients created earlier
+/// keep the default settings, so call this first thing.
+pub fn init(config: &Config) -> Result<()> {
+    let settings = PoolSettings::from_config(config);
+    if CLIENT.set(settings.build()?).is_ok() {
+        SETTINGS.set(settings).ok();
+        info!(
+            "🔌 HTTP pool: {} idle connections per host, {}s idle timeout, {}s keep-alive",
+            settings.max_idle_per_host, settings.idle_timeout_secs, settings.keep_alive_secs
+        );
+    }
+    Ok(())
+}
+
+/// The shared client (a cheap handle onto the one pool).
+pub fn client() -> Client {
+    CLIENT
+        .get_or_init(|| PoolSettings::default().build().expect("Failed to create HTTP client"))
+        .clone()
+}
+
+fn settings() -> PoolSettings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Default)]
+struct HostCounters {
+    requests: u64,
+    errors: u64,
+    in_flight: usize,
+    peak_in_flight: usize,
+    over_pool: u64,
+    total_ms: f64,
+}
+
+/// Request counters for one host.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HostMetrics {
+    pub requests: u64,
+    pub errors: u64,
+    pub in_flight: usize,
+    /// Most requests to the host at once
+    pub peak_in_flight: usize,
+    /// Requests started with the idle pool already in use, which likely
+    /// opened a fresh connection
+    pub over_pool: u64,
+    pub avg_ms: f64,
+}
+
+/// Pool settings and per-host request counters since start.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PoolMetrics {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub settings: Option<PoolSettings>,
+    pub hosts: BTreeMap<String, HostMetrics>,
+}
+
+impl PoolMetrics {
+    pub fn requests(&self) -> u64 {
+        self.hosts.values().map(|h| h.requests).sum()
+    }
+
+    pub fn over_pool(&self) -> u64 {
+        self.hosts.values().map(|h| h.over_pool).sum()
+    }
+}
+
+fn hosts() -> &'static Mutex<HashMap<String, HostCounters>> {
+    HOSTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Counts a request in flight until dropped, so cancelled requests are
+/// released too.
+struct InFlight {
+    host: String,
+    started: Instant,
+    ok: bool,
+}
+
+impl InFlight {
+    fn start(host: &str, pool_size: usize) -> Self {
+        let mut hosts = hosts().lock().unwrap();
+        let counters = hosts.entry(host.to_string()).or_default();
+        if counters.in_flight >= pool_size {
+            counters.over_pool += 1;
+        }
+        counters.requests += 1;
+        counters.in_flight += 1;
+        counters.peak_in_flight = counters.peak_in_flight.max(counters.in_flight);
+        Self { host: host.to_string(), started: Instant::now(), ok: false }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut hosts = hosts().lock().unwrap();
+        if let Some(counters) = hosts.get_mut(&self.host) {
+            counters.in_flight = counters.in_flight.saturating_sub(1);
+            counters.total_ms += self.started.elapsed().as_secs_f64() * 1000.0;
+            if !self.ok {
+                counters.errors += 1;
+            }
+        }
+    }
+}
+
+/// Send a request, counting it in the pool metrics.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let (client, request) = request.build_split();
+    let request = request?;
+    let url = request.url().to_string();
+    let host = request.url().host_str().unwrap_or("").to_string();
+    let mut in_flight = InFlight::start(&host, settings().max_idle_per_host);
+    let response = client.execute(request).await;
+    in_flight.ok = response.is_ok();
+    let connection_error = matches!(&response, Err(e) if e.is_connect() || e.is_timeout());
+    endpoints::global().observe(&url, connection_error);
+    response
+}
+
+/// Snapshot of the pool metrics.
+pub fn metrics() -> PoolMetrics {
+    let hosts = hosts().lock().unwrap();
+    PoolMetrics {
+        updated_at: Some(Utc::now()),
+        settings: Some(settings()),
+        hosts: hosts
+            .iter()
+            .map(|(host, c)| {
+                let metrics = HostMetrics {
+                    requests: c.requests,
+                    errors: c.errors,
+                    in_flight: c.in_flight,
+                    peak_in_flight: c.peak_in_flight,
+                    over_pool: c.over_pool,
+                    avg_ms: if c.requests > 0 { c.total_ms / c.requests as f64 } else { 0.0 },
+                };
+                (host.clone(), metrics)
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_concurrency_per_host() {
+        let host = format!("{}.test", uuid::Uuid::new_v4());
+        let first = InFlight::start(&host, 2);
+        let mut second = InFlight::start(&host, 2);
+        let mut third = InFlight::start(&host, 2);
+        second.ok = true;
+        third.ok = true;
+        assert_eq!(metrics().hosts[&host].in_flight, 3);
+        drop((first, second, third));
+
+        let host_metrics = &metrics().hosts[&host];
+        assert_eq!(host_metrics.requests, 3);
+        assert_eq!(host_metrics.peak_in_flight, 3);
+        assert_eq!(host_metrics.over_pool, 1);
+        assert_eq!((host_metrics.in_flight, host_metrics.errors), (0, 1));
+    }
+}
@@ -0,0 +1,164 @@
+//! Venue-qualified market identifiers.
+//!
+//! A Polymarket token ID and a Kalshi ticker are both just strings, and a
+//! token can sit in more than one matched pair, so bare IDs make ambiguous
+//! map keys. A [`MarketId`] carries the venue, the venue's own ID and,
+//! where it matters, the outcome. It reads and serializes as
+//! `venue:id[:outcome]`, e.g. `kalshi:KXBTCD-26JAN0517-T94249.99:no`.
+
+use anyhow::Result;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Trading venue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Venue {
+    Polymarket,
+    Kalshi,
+}
+
+impl Venue {
+    /// Lowercase name, as used for platforms across the bot.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Venue::Polymarket => "polymarket",
+            Venue::Kalshi => "kalshi",
+        }
+    }
+}
+
+impl fmt::Display for Venue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Venue {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "polymarket" => Ok(Venue::Polymarket),
+            "kalshi" => Ok(Venue::Kalshi),
+            other => anyhow::bail!("Unknown venue '{}'", other),
+        }
+    }
+}
+
+/// Side of a binary market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Outcome {
+    Yes,
+    No,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Yes => "yes",
+            Outcome::No => "no",
+        }
+    }
+}
+
+/// A market on one venue, optionally narrowed to one outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MarketId {
+    pub venue: Venue,
+    /// Token ID on Polymarket, ticker on Kalshi
+    pub native: String,
+    pub outcome: Option<Outcome>,
+}
+
+impl MarketId {
+    pub fn new(venue: Venue, native: &str) -> Self {
+        Self { venue, native: native.to_string(), outcome: None }
+    }
+
+    /// Polymarket market by CLOB token ID.
+    pub fn polymarket(token_id: &str) -> Self {
+        Self::new(Venue::Polymarket, token_id)
+    }
+
+    /// Kalshi market by ticker.
+    pub fn kalshi(ticker: &str) -> Self {
+        Self::new(Venue::Kalshi, ticker)
+    }
+
+    /// Market for a platform name, e.g. "kalshi".
+    pub fn on(platform: &str, native: &str) -> Result<Self> {
+        Ok(Self::new(platform.parse()?, native))
+    }
+
+    /// The same market narrowed to one outcome.
+    pub fn with_outcome(mut self, outcome: Outcome) -> Self {
+        self.outcome = Some(outcome);
+        self
+    }
+}
+
+impl fmt::Display for MarketId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.venue, self.native)?;
+        match self.outcome {
+            Some(outcome) => write!(f, ":{}", outcome.as_str()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for MarketId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (venue, rest) = s.split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("Invalid market ID '{}': expected venue:id", s))?;
+        let venue: Venue = venue.parse()?;
+        // Tickers contain dashes and dots but never colons
+        let (native, outcome) = match rest.rsplit_once(':') {
+            Some((native, "yes")) => (native, Some(Outcome::Yes)),
+            Some((native, "no")) => (native, Some(Outcome::No)),
+            _ => (rest, None),
+        };
+        if native.is_empty() {
+            anyhow::bail!("Invalid market ID '{}': empty venue ID", s);
+        }
+        Ok(Self { venue, native: native.to_string(), outcome })
+    }
+}
+
+impl Serialize for MarketId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MarketId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_through_text() {
+        let id = MarketId::kalshi("KXBTCD-26JAN0517-T94249.99").with_outcome(Outcome::No);
+        assert_eq!(id.to_string(), "kalshi:KXBTCD-26JAN0517-T94249.99:no");
+        assert_eq!(id.to_string().parse::<MarketId>().unwrap(), id);
+
+        let token = MarketId::polymarket("1962417");
+        assert_eq!("polymarket:1962417".parse::<MarketId>().unwrap(), token);
+        assert_eq!(serde_json::to_string(&token).unwrap(), "\"polymarket:1962417\"");
+        assert_eq!(MarketId::on("Kalshi", "KX-1").unwrap(), MarketId::kalshi("KX-1"));
+
+        // The same ID on different venues is a different market
+        assert_ne!(MarketId::polymarket("X"), MarketId::kalshi("X"));
+        assert!("1962417".parse::<MarketId>().is_err());
+        assert!("binance:BTC".parse::<MarketId>().is_err());
+        assert!("kalshi:".parse::<MarketId>().is_err());
+    }
+}
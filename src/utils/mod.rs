@@ -1,9 +1,40 @@
 //! Safety and monitoring utilities.
 
+pub mod annotations;
+pub mod bundle;
+pub mod audit_log;
 pub mod circuit_breaker;
 pub mod position_tracker;
 pub mod cache;
+pub mod clock;
+pub mod encryption;
+pub mod endpoints;
+pub mod fills;
+pub mod fixtures;
+pub mod http_pool;
+pub mod market_id;
+pub mod open_orders;
+pub mod permissions;
+pub mod raw_log;
+pub mod run_info;
+pub mod scheduler;
+pub mod state;
+pub mod supervisor;
+pub mod throttle;
+pub mod timezone;
+pub mod tolerant;
+pub mod venue_constraints;
 
+pub use annotations::{Annotation, Annotations};
+pub use audit_log::{AuditEvent, AuditLog};
 pub use circuit_breaker::CircuitBreaker;
 pub use position_tracker::PositionTracker;
 pub use cache::Cache;
+pub use clock::{Clock, SharedClock, SystemClock};
+pub use fills::{Fill, FillRouter};
+pub use fixtures::{FixtureMode, FixtureStore};
+pub use market_id::{MarketId, Outcome, Venue};
+pub use open_orders::{OpenOrder, OpenOrders, SelfTradePolicy};
+pub use scheduler::Scheduler;
+pub use supervisor::{RestartPolicy, Supervisor};
+pub use venue_constraints::{Sizing, VenueConstraints};
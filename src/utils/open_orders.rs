@@ -0,0 +1,245 @@
+//! Open live order store.
+//!
+//! Resting orders placed on either venue are recorded until they are
+//! cancelled, so a restarted bot knows what it still has working, and so a
+//! new order can be checked against them before it crosses our own quotes.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+use super::state;
+
+/// Default open order store location.
+pub const OPEN_ORDERS_FILE: &str = "data/state/open_orders.json";
+
+/// An order resting on a venue.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub order_id: String,
+    pub venue: String,
+    /// Token ID or ticker
+    pub market: String,
+    /// e.g. "BUY", "buy yes"
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub placed_at: DateTime<Utc>,
+}
+
+impl OpenOrder {
+    /// Whether the order bids for YES (or the token).
+    pub fn is_bid(&self) -> bool {
+        is_bid(&self.side)
+    }
+}
+
+/// Whether an order side bids for YES (or the token): "BUY", "buy yes" or
+/// "sell no", since selling NO at a YES price is buying YES.
+pub fn is_bid(side: &str) -> bool {
+    let side = side.to_lowercase();
+    side.starts_with("buy") != side.ends_with(" no")
+}
+
+/// What to do when a new order would trade against our own resting order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelfTradePolicy {
+    /// Cancel the resting order first
+    #[default]
+    Cancel,
+    /// Refuse the new order
+    Skip,
+    /// No check
+    Off,
+}
+
+impl FromStr for SelfTradePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "cancel" => SelfTradePolicy::Cancel,
+            "skip" => SelfTradePolicy::Skip,
+            "off" => SelfTradePolicy::Off,
+            _ => anyhow::bail!("Unknown self-trade policy '{}' (expected cancel, skip or off)", s),
+        })
+    }
+}
+
+impl fmt::Display for SelfTradePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SelfTradePolicy::Cancel => "cancel",
+            SelfTradePolicy::Skip => "skip",
+            SelfTradePolicy::Off => "off",
+        })
+    }
+}
+
+/// Persistent set of open orders keyed by order ID.
+pub struct OpenOrders {
+    orders: BTreeMap<String, OpenOrder>,
+    file_path: Option<String>,
+}
+
+impl OpenOrders {
+    /// In-memory store.
+    pub fn new() -> Self {
+        Self {
+            orders: BTreeMap::new(),
+            file_path: None,
+        }
+    }
+
+    /// Load from file, starting empty if missing.
+    pub fn load(file_path: &str) -> Self {
+        Self {
+            orders: state::load(file_path),
+            file_path: Some(file_path.to_string()),
+        }
+    }
+
+    /// Record a newly placed order.
+    pub fn add(&mut self, order: OpenOrder) {
+        self.orders.insert(order.order_id.clone(), order);
+        self.save();
+    }
+
+    /// Forget an order once cancelled or filled. Returns it if known.
+    pub fn remove(&mut self, order_id: &str) -> Option<OpenOrder> {
+        let removed = self.orders.remove(order_id);
+        if removed.is_some() {
+            self.save();
+        }
+        removed
+    }
+
+    /// All open orders.
+    pub fn all(&self) -> Vec<&OpenOrder> {
+        self.orders.values().collect()
+    }
+
+    /// Our resting orders on `venue` and `market` that a new order on
+    /// `side` at `price` (in YES terms; None for a market order) would
+    /// trade against.
+    pub fn crossing(&self, venue: &str, market: &str, side: &str, price: Option<f64>) -> Vec<OpenOrder> {
+        let bid = is_bid(side);
+        self.orders
+            .values()
+            .filter(|o| o.venue == venue && o.market == market && o.is_bid() != bid)
+            .filter(|o| match price {
+                None => true,
+                Some(price) if bid => price >= o.price,
+                Some(price) => price <= o.price,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Drop the orders on `venue` that are no longer resting there, given
+    /// the IDs the venue reports as open. Returns the dropped orders.
+    pub fn reconcile(&mut self, venue: &str, resting: &[String]) -> Vec<OpenOrder> {
+        let stale: Vec<String> = self
+            .orders
+            .values()
+            .filter(|o| o.venue == venue && !resting.contains(&o.order_id))
+            .map(|o| o.order_id.clone())
+            .collect();
+        let dropped: Vec<OpenOrder> = stale.iter().filter_map(|id| self.orders.remove(id)).collect();
+        if !dropped.is_empty() {
+            self.save();
+        }
+        dropped
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    fn save(&self) {
+        if let Some(ref path) = self.file_path {
+            if let Err(e) = state::save(path, &self.orders) {
+                warn!("Failed to persist open orders: {}", e);
+            }
+        }
+    }
+}
+
+impl Default for OpenOrders {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restores_after_restart() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-orders-{}.json", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+
+        let mut orders = OpenOrders::load(&path);
+        for id in ["a", "b"] {
+            orders.add(OpenOrder {
+                order_id: id.to_string(),
+                venue: "polymarket".to_string(),
+                market: "123".to_string(),
+                side: "BUY".to_string(),
+                price: 0.45,
+                size: 10.0,
+                placed_at: Utc::now(),
+            });
+        }
+        orders.remove("a");
+
+        let restored = OpenOrders::load(&path);
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored.all()[0].order_id, "b");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_finds_resting_orders_a_new_order_would_cross() {
+        let mut orders = OpenOrders::new();
+        let resting = |id: &str, venue: &str, side: &str, price: f64| OpenOrder {
+            order_id: id.to_string(),
+            venue: venue.to_string(),
+            market: "M".to_string(),
+            side: side.to_string(),
+            price,
+            size: 10.0,
+            placed_at: Utc::now(),
+        };
+        orders.add(resting("ask", "polymarket", "SELL", 0.55));
+        orders.add(resting("bid", "polymarket", "BUY", 0.45));
+        // Buying NO at a YES price of 0.60 offers YES there
+        orders.add(resting("no", "kalshi", "buy no", 0.60));
+
+        let ids = |found: Vec<OpenOrder>| found.into_iter().map(|o| o.order_id).collect::<Vec<_>>();
+        assert_eq!(ids(orders.crossing("polymarket", "M", "BUY", Some(0.55))), ["ask"]);
+        assert!(orders.crossing("polymarket", "M", "BUY", Some(0.54)).is_empty());
+        assert_eq!(ids(orders.crossing("polymarket", "M", "SELL", Some(0.40))), ["bid"]);
+        assert!(orders.crossing("polymarket", "OTHER", "SELL", Some(0.40)).is_empty());
+        assert_eq!(ids(orders.crossing("kalshi", "M", "buy yes", Some(0.61))), ["no"]);
+        assert_eq!(ids(orders.crossing("kalshi", "M", "sell no", None)), ["no"]);
+        assert!(orders.crossing("kalshi", "M", "sell yes", None).is_empty());
+        assert_eq!("SKIP".parse::<SelfTradePolicy>().unwrap(), SelfTradePolicy::Skip);
+
+        // Reconciling with Polymarket drops what it no longer has resting there
+        let dropped = orders.reconcile("polymarket", &["bid".to_string()]);
+        assert_eq!(ids(dropped), ["ask"]);
+        assert_eq!(orders.len(), 2);
+    }
+}
@@ -0,0 +1,175 @@
+//! Role-based gating of control actions.
+//!
+//! A viewer only looks, a trader may trade (paper orders, approvals while
+//! dry-running, mutes, strategy toggles, annotations) and an admin may also
+//! take the dangerous actions: approving intents while the bot trades
+//! live, resetting a paper portfolio and clearing a tripped circuit
+//! breaker. With a PIN configured, dangerous actions ask for it too, so a
+//! shared dashboard can't trade by accident.
+//!
+//! The TUI has one role (TUI_ROLE); the dashboard and Telegram commands
+//! give each token or user its own, from `id:role` lists.
+
+use anyhow::Result;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::config::Config;
+
+/// What a user of a control surface may do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Viewer,
+    /// What a surface gets unless configured otherwise, so the dangerous
+    /// actions need an explicit admin
+    #[default]
+    Trader,
+    Admin,
+}
+
+impl FromStr for Role {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.trim().to_lowercase().as_str() {
+            "viewer" => Role::Viewer,
+            "trader" => Role::Trader,
+            "admin" => Role::Admin,
+            _ => anyhow::bail!("Unknown role '{}' (expected viewer, trader or admin)", s),
+        })
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Role::Viewer => "viewer",
+            Role::Trader => "trader",
+            Role::Admin => "admin",
+        })
+    }
+}
+
+/// Roles by identity (a dashboard token, a Telegram user ID), from
+/// comma-separated `id:role` entries.
+pub fn parse_roles(value: &str) -> Result<Vec<(String, Role)>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let Some((id, role)) = entry.rsplit_once(':').filter(|(id, _)| !id.trim().is_empty()) else {
+                anyhow::bail!("Invalid role entry '{}': expected id:role", entry);
+            };
+            Ok((id.trim().to_string(), role.parse()?))
+        })
+        .collect()
+}
+
+/// A gated action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Paper orders, dry-run approvals, mutes, strategy toggles, annotations
+    Trade,
+    /// Approve an intent while the bot trades live
+    GoLive,
+    ResetPortfolio,
+    ResetBreaker,
+}
+
+impl Action {
+    /// Lowest role allowed to take the action.
+    pub fn required_role(&self) -> Role {
+        match self {
+            Action::Trade => Role::Trader,
+            Action::GoLive | Action::ResetPortfolio | Action::ResetBreaker => Role::Admin,
+        }
+    }
+
+    /// Whether the action asks for the PIN.
+    pub fn is_dangerous(&self) -> bool {
+        *self != Action::Trade
+    }
+}
+
+/// The role of one control surface and its PIN.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Permissions {
+    role: Role,
+    pin: Option<String>,
+}
+
+impl Permissions {
+    pub fn new(role: Role) -> Self {
+        Self { role, pin: None }
+    }
+
+    /// Ask for `pin` before dangerous actions.
+    pub fn with_pin(mut self, pin: &str) -> Self {
+        self.pin = Some(pin.to_string());
+        self
+    }
+
+    /// The TUI's permissions, from TUI_ROLE and TUI_PIN.
+    pub fn from_config(config: &Config) -> Self {
+        let permissions = Self::new(config.tui_role);
+        match &config.tui_pin {
+            Some(pin) => permissions.with_pin(pin),
+            None => permissions,
+        }
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn allows(&self, action: Action) -> bool {
+        self.role >= action.required_role()
+    }
+
+    /// Whether `action` must be confirmed with the PIN.
+    pub fn needs_pin(&self, action: Action) -> bool {
+        action.is_dangerous() && self.pin.is_some()
+    }
+
+    pub fn check_pin(&self, pin: &str) -> bool {
+        self.pin.as_deref().is_some_and(|p| p == pin.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_gate_actions() {
+        assert_eq!("Trader".parse::<Role>().unwrap(), Role::Trader);
+        assert!("root".parse::<Role>().is_err());
+
+        let viewer = Permissions::new(Role::Viewer);
+        assert!(!viewer.allows(Action::Trade));
+        let trader = Permissions::new(Role::Trader).with_pin("1234");
+        assert!(trader.allows(Action::Trade));
+        assert!(!trader.allows(Action::GoLive) && !trader.allows(Action::ResetBreaker));
+
+        let admin = Permissions::new(Role::Admin).with_pin("1234");
+        assert!(admin.allows(Action::ResetPortfolio));
+        assert!(admin.needs_pin(Action::ResetPortfolio) && !admin.needs_pin(Action::Trade));
+        assert!(admin.check_pin(" 1234") && !admin.check_pin("4321"));
+        // Without a PIN, dangerous actions only need the role
+        assert!(!Permissions::new(Role::Admin).needs_pin(Action::GoLive));
+
+        // Unconfigured surfaces may trade but not take the dangerous actions
+        assert!(Permissions::default().allows(Action::Trade) && !Permissions::default().allows(Action::GoLive));
+    }
+
+    #[test]
+    fn test_parse_roles() {
+        let roles = parse_roles(" 12345:trader, ab:cd:admin ,").unwrap();
+        assert_eq!(roles, vec![("12345".to_string(), Role::Trader), ("ab:cd".to_string(), Role::Admin)]);
+        assert!(parse_roles("12345").is_err());
+        assert!(parse_roles(":admin").is_err());
+        assert!(parse_roles("12345:root").is_err());
+        assert!(parse_roles("").unwrap().is_empty());
+    }
+}
@@ -11,6 +11,17 @@ pub struct Position {
     pub ticker: String,
     pub quantity: i32,
     pub avg_price: f64,
+    /// Latest mark, if the position has been priced
+    pub mark_price: Option<f64>,
+}
+
+impl Position {
+    /// Unrealized P&L at the latest mark (0 until marked).
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.mark_price
+            .map(|m| self.quantity as f64 * (m - self.avg_price))
+            .unwrap_or(0.0)
+    }
 }
 
 /// Tracks positions across platforms.
@@ -49,9 +60,38 @@ impl PositionTracker {
         }
     }
 
+    /// Record the current market price for a position.
+    pub fn mark(&self, platform: &str, ticker: &str, price: f64) {
+        let mut all_positions = self.positions.lock().unwrap();
+        if let Some(pos) = all_positions.get_mut(platform).and_then(|m| m.get_mut(ticker)) {
+            pos.mark_price = Some(price);
+        }
+    }
+
+    /// All non-flat positions as (platform, position).
+    pub fn open_positions(&self) -> Vec<(String, Position)> {
+        let all_positions = self.positions.lock().unwrap();
+        all_positions
+            .iter()
+            .flat_map(|(platform, m)| m.values().map(move |p| (platform.clone(), p.clone())))
+            .filter(|(_, p)| p.quantity != 0)
+            .collect()
+    }
+
+    /// Unrealized P&L across all marked positions.
+    pub fn unrealized_pnl(&self) -> f64 {
+        self.open_positions().iter().map(|(_, p)| p.unrealized_pnl()).sum()
+    }
+
     /// Get current position.
     pub fn get_position(&self, platform: &str, ticker: &str) -> Option<Position> {
         let all_positions = self.positions.lock().unwrap();
         all_positions.get(platform)?.get(ticker).cloned()
     }
 }
+
+impl Default for PositionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+//! Raw API payload logging.
+//!
+//! When enabled (RAW_LOG=true), every request/response body that passes
+//! through the HTTP helpers is appended as a JSON line to `data/raw/raw.jsonl`,
+//! with credentials redacted. Files rotate by size (`raw.1.jsonl` is the most
+//! recent rotated file) so the capture never grows without bound.
+
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::fs::{self};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::utils::state;
+
+/// Key fragments (and query parameters) whose values are never written.
+const SENSITIVE: &[&str] = &[
+    "secret", "passphrase", "signature", "password", "private", "api_key", "apikey", "auth",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+static LOGGER: OnceLock<RawLogger> = OnceLock::new();
+
+/// Size-capped, rotating payload log.
+pub struct RawLogger {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    lock: Mutex<()>,
+}
+
+impl RawLogger {
+    /// Create a logger writing under `dir`.
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64, max_files: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+            max_files: max_files.max(1),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn path(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            self.dir.join("raw.jsonl")
+        } else {
+            self.dir.join(format!("raw.{}.jsonl", index))
+        }
+    }
+
+    /// Append one exchange to the log.
+    pub fn write(&self, method: &str, url: &str, request: Option<&str>, status: u16, response: &str) -> Result<()> {
+        let entry = json!({
+            "ts": Utc::now().to_rfc3339(),
+            "method": method,
+            "url": redact_url(url),
+            "request": request.map(redact_body),
+            "status": status,
+            "response": redact_body(response),
+        });
+
+        let _guard = self.lock.lock().unwrap();
+        fs::create_dir_all(&self.dir)?;
+        let current = self.path(0);
+        if fs::metadata(&current).map(|m| m.len() >= self.max_bytes).unwrap_or(false) {
+            self.rotate()?;
+        }
+        state::append_line(&current.to_string_lossy(), &entry.to_string())
+    }
+
+    /// Shift raw.jsonl -> raw.1.jsonl -> ... dropping the oldest.
+    fn rotate(&self) -> Result<()> {
+        let oldest = self.path(self.max_files);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for index in (0..self.max_files).rev() {
+            let from = self.path(index);
+            if from.exists() {
+                fs::rename(&from, self.path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Install the process-wide logger if RAW_LOG is enabled.
+pub fn init(config: &Config) {
+    if !config.raw_log {
+        return;
+    }
+    let logger = RawLogger::new(&config.raw_log_dir, config.raw_log_max_mb * 1024 * 1024, config.raw_log_files);
+    if LOGGER.set(logger).is_ok() {
+        info!("📝 Logging raw API payloads to {}", Path::new(&config.raw_log_dir).display());
+    }
+}
+
+/// Record an exchange if raw logging is enabled. Never fails the caller.
+pub fn record(method: &str, url: &str, request: Option<&str>, status: u16, response: &str) {
+    if let Some(logger) = LOGGER.get() {
+        if let Err(e) = logger.write(method, url, request, status, response) {
+            warn!("Raw payload log write failed: {}", e);
+        }
+    }
+}
+
+/// Whether a field, parameter or header named `name` holds a credential.
+pub fn is_sensitive(name: &str) -> bool {
+    let name = name.to_lowercase();
+    // Bare "key"/"token" and *_token are credentials; token_id is not
+    name == "key" || name == "token" || name.ends_with("_token")
+        || name.ends_with("-key") || name.ends_with("_key")
+        || SENSITIVE.iter().any(|s| name.contains(s))
+}
+
+/// Mask sensitive query parameters.
+fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, _)) if is_sensitive(k) => format!("{}={}", k, REDACTED),
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", base, params.join("&"))
+}
+
+/// Mask sensitive fields in a JSON body; non-JSON bodies are kept verbatim.
+fn redact_body(body: &str) -> Value {
+    match serde_json::from_str::<Value>(body) {
+        Ok(mut value) => {
+            redact_value(&mut value);
+            value
+        }
+        Err(_) => Value::String(body.to_string()),
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                if is_sensitive(k) && !v.is_object() && !v.is_array() {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_and_rotates() {
+        assert_eq!(
+            redact_url("https://x.com/a?token_id=1&api_key=abc"),
+            format!("https://x.com/a?token_id=1&api_key={}", REDACTED)
+        );
+        let body = redact_body(r#"{"owner":"me","order":{"signature":"0xabc","price":"0.5"}}"#);
+        assert_eq!(body["order"]["signature"], REDACTED);
+        assert_eq!(body["order"]["price"], "0.5");
+
+        let dir = std::env::temp_dir().join(format!("polybot-raw-{}", uuid::Uuid::new_v4()));
+        let logger = RawLogger::new(&dir, 200, 2);
+        for _ in 0..10 {
+            logger.write("GET", "https://x.com/book", None, 200, r#"{"bids":[],"asks":[]}"#).unwrap();
+        }
+        assert!(dir.join("raw.jsonl").exists());
+        assert!(dir.join("raw.2.jsonl").exists());
+        assert!(!dir.join("raw.3.jsonl").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
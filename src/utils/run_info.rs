@@ -0,0 +1,121 @@
+//! Provenance stamped on trades, alerts, webhooks and reports.
+//!
+//! Every process gets a run ID; together with the git version, a hash of
+//! the settings and the hostname it says exactly which bot produced a
+//! record when results from several machines or configs are compared.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::utils::state;
+
+/// One line per bot or TUI start.
+pub const RUNS_FILE: &str = "data/runs.jsonl";
+/// `git describe` of the build, set by build.rs
+pub const GIT_VERSION: &str = env!("POLYBOT_GIT_VERSION");
+
+static CURRENT: OnceLock<RunStamp> = OnceLock::new();
+
+/// Identity of the process that wrote a record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStamp {
+    pub run_id: String,
+    /// Crate version and git revision, e.g. "0.1.0+3ac935d"
+    pub version: String,
+    /// [`Config::fingerprint`], "unknown" without a config
+    pub config_hash: String,
+    pub hostname: String,
+}
+
+impl RunStamp {
+    /// Fresh stamp for this process.
+    pub fn new(config: Option<&Config>) -> Self {
+        Self {
+            run_id: Utc::now().format("%Y%m%d-%H%M%S-").to_string() + &uuid::Uuid::new_v4().simple().to_string()[..6],
+            version: format!("{}+{}", env!("CARGO_PKG_VERSION"), GIT_VERSION),
+            config_hash: config.map(Config::fingerprint).unwrap_or_else(|| "unknown".to_string()),
+            hostname: hostname::get()
+                .ok()
+                .and_then(|h| h.into_string().ok())
+                .unwrap_or_else(|| "unknown".to_string()),
+        }
+    }
+
+    /// One-line summary for logs and report footers.
+    pub fn summary(&self) -> String {
+        format!("run {} · {} · config {} · {}", self.run_id, self.version, self.config_hash, self.hostname)
+    }
+}
+
+/// A recorded start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub started_at: DateTime<Utc>,
+    /// Binary that started, e.g. "polymarket-kalshi-arbitrage-bot" or "tui"
+    pub program: String,
+    #[serde(flatten)]
+    pub stamp: RunStamp,
+}
+
+/// Stamp this process with `config` and record the start in the runs file.
+pub fn init(config: &Config) -> &'static RunStamp {
+    let stamp = CURRENT.get_or_init(|| RunStamp::new(Some(config)));
+    let record = RunRecord {
+        started_at: Utc::now(),
+        program: std::env::args()
+            .next()
+            .and_then(|p| Path::new(&p).file_name().map(|f| f.to_string_lossy().to_string()))
+            .unwrap_or_default(),
+        stamp: stamp.clone(),
+    };
+    if let Err(e) = append(RUNS_FILE, &record) {
+        warn!("Failed to record run: {}", e);
+    }
+    stamp
+}
+
+/// Stamp of this process. Without [`init`], the settings are read from the
+/// environment on first use.
+pub fn current() -> &'static RunStamp {
+    CURRENT.get_or_init(|| RunStamp::new(Config::from_env().ok().as_ref()))
+}
+
+/// Add the current stamp to JSON output under "run"; anything other than
+/// an object is nested under "results".
+pub fn stamped<T: Serialize>(value: &T) -> serde_json::Result<serde_json::Value> {
+    let run = serde_json::to_value(current())?;
+    Ok(match serde_json::to_value(value)? {
+        serde_json::Value::Object(mut fields) => {
+            fields.insert("run".to_string(), run);
+            serde_json::Value::Object(fields)
+        }
+        other => serde_json::json!({ "run": run, "results": other }),
+    })
+}
+
+fn append(path: &str, record: &RunRecord) -> anyhow::Result<()> {
+    state::append_line(path, &serde_json::to_string(record)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_and_json() {
+        let stamp = RunStamp::new(None);
+        assert_eq!(stamp.config_hash, "unknown");
+        assert!(stamp.version.starts_with(env!("CARGO_PKG_VERSION")));
+        assert_ne!(stamp.run_id, RunStamp::new(None).run_id);
+
+        let object = stamped(&serde_json::json!({ "pnl": 1.5 })).unwrap();
+        assert_eq!(object["pnl"], 1.5);
+        assert_eq!(object["run"]["run_id"], current().run_id.as_str());
+        let list = stamped(&vec![1, 2]).unwrap();
+        assert_eq!(list["results"], serde_json::json!([1, 2]));
+    }
+}
@@ -0,0 +1,177 @@
+//! Refresh scheduler.
+//!
+//! Periodic refreshes (market discovery, the leaderboard, trader scores)
+//! each run on their own interval inside one task instead of each rolling
+//! a sleep loop. Every delay is jittered so refreshes don't line up with
+//! each other or with other bots, and a failing job backs off
+//! exponentially from a few seconds up to a cap before it is tried again,
+//! rather than hammering an API that is already refusing us.
+
+use anyhow::Result;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// Delay before retrying a job after its first failure.
+const RETRY_BASE: Duration = Duration::from_secs(5);
+
+/// Default cap on the failure backoff.
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone)]
+struct Job {
+    name: String,
+    interval: Duration,
+    /// Consecutive failures
+    failures: u32,
+    next_run: Instant,
+    last_error: Option<String>,
+}
+
+/// Named jobs run on independent, jittered intervals.
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    /// Fraction each delay may be randomly stretched or shrunk by
+    jitter: f64,
+    max_backoff: Duration,
+}
+
+impl Scheduler {
+    /// A scheduler without jobs; `jitter` is a fraction, e.g. 0.1 for ±10%.
+    pub fn new(jitter: f64) -> Self {
+        Self {
+            jobs: Vec::new(),
+            jitter: jitter.clamp(0.0, 1.0),
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Add a job run every `interval`, first at `start`.
+    pub fn with_job(mut self, name: &str, interval: Duration, start: Instant) -> Self {
+        self.jobs.push(Job {
+            name: name.to_string(),
+            interval,
+            failures: 0,
+            next_run: start,
+            last_error: None,
+        });
+        self
+    }
+
+    /// Cap the failure backoff at `max_backoff`.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Change a job's interval (e.g. after a config reload); applies from
+    /// its next run.
+    pub fn set_interval(&mut self, name: &str, interval: Duration) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.name == name) {
+            job.interval = interval;
+        }
+    }
+
+    /// Jobs due at `now`, in the order they were added.
+    pub fn due(&self, now: Instant) -> Vec<String> {
+        self.jobs.iter()
+            .filter(|j| j.next_run <= now)
+            .map(|j| j.name.clone())
+            .collect()
+    }
+
+    /// When the next job is due.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.jobs.iter().map(|j| j.next_run).min()
+    }
+
+    /// Record how a run of `name` went and schedule its next run: one
+    /// interval later on success, after the backoff on failure. Returns the
+    /// delay chosen.
+    pub fn finish(&mut self, name: &str, result: &Result<()>, now: Instant) -> Duration {
+        let jitter = self.jitter;
+        let max_backoff = self.max_backoff;
+        let Some(job) = self.jobs.iter_mut().find(|j| j.name == name) else {
+            return Duration::ZERO;
+        };
+        let base = match result {
+            Ok(()) => {
+                job.failures = 0;
+                job.last_error = None;
+                job.interval
+            }
+            Err(e) => {
+                job.failures += 1;
+                job.last_error = Some(e.to_string());
+                backoff(job.failures, max_backoff)
+            }
+        };
+        let delay = jittered(base, jitter);
+        job.next_run = now + delay;
+        delay
+    }
+
+    /// Consecutive failures of `name` and the last error, if any.
+    pub fn failures(&self, name: &str) -> (u32, Option<&str>) {
+        self.jobs.iter()
+            .find(|j| j.name == name)
+            .map(|j| (j.failures, j.last_error.as_deref()))
+            .unwrap_or((0, None))
+    }
+}
+
+/// Delay after `failures` consecutive failures: doubling from
+/// [`RETRY_BASE`], capped at `max`.
+fn backoff(failures: u32, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1).min(16));
+    RETRY_BASE.saturating_mul(factor).min(max)
+}
+
+fn jittered(delay: Duration, jitter: f64) -> Duration {
+    if jitter <= 0.0 {
+        return delay;
+    }
+    let factor = 1.0 + rand::thread_rng().gen_range(-jitter..=jitter);
+    delay.mul_f64(factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_run_on_their_own_intervals_and_back_off() {
+        let start = Instant::now();
+        let mut scheduler = Scheduler::new(0.0)
+            .with_job("discovery", Duration::from_secs(300), start)
+            .with_job("leaderboard", Duration::from_secs(3600), start + Duration::from_secs(10));
+        assert_eq!(scheduler.due(start), vec!["discovery"]);
+
+        scheduler.finish("discovery", &Ok(()), start);
+        assert_eq!(scheduler.next_due(), Some(start + Duration::from_secs(10)));
+        assert_eq!(scheduler.due(start + Duration::from_secs(300)), vec!["discovery", "leaderboard"]);
+
+        // Failures double the retry delay up to the cap, success resets it
+        let mut scheduler = scheduler.with_max_backoff(Duration::from_secs(30));
+        let failed = Err(anyhow::anyhow!("429"));
+        let delays: Vec<_> = (0..4).map(|_| scheduler.finish("leaderboard", &failed, start).as_secs()).collect();
+        assert_eq!(delays, vec![5, 10, 20, 30]);
+        assert_eq!(scheduler.failures("leaderboard"), (4, Some("429")));
+        assert_eq!(scheduler.finish("leaderboard", &Ok(()), start).as_secs(), 3600);
+        assert_eq!(scheduler.failures("leaderboard"), (0, None));
+
+        // Interval changes apply from the next run
+        scheduler.set_interval("discovery", Duration::from_secs(60));
+        assert_eq!(scheduler.finish("discovery", &Ok(()), start).as_secs(), 60);
+    }
+
+    #[test]
+    fn test_jitter_stays_within_bounds() {
+        let start = Instant::now();
+        let mut scheduler = Scheduler::new(0.1).with_job("scores", Duration::from_secs(100), start);
+        for _ in 0..50 {
+            let delay = scheduler.finish("scores", &Ok(()), start).as_secs_f64();
+            assert!((90.0..=110.0).contains(&delay), "delay {}", delay);
+        }
+    }
+}
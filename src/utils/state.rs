@@ -0,0 +1,255 @@
+//! Persistent runtime state.
+//!
+//! Small JSON files under `data/state/` let a restarted bot pick up where it
+//! left off instead of starting from blank in-memory state. Writes go to a
+//! temporary file that is synced to disk and renamed over the original, so
+//! a crash never leaves a half-written file. Files that matter most keep a
+//! `.bak` copy of the previous good version to recover from. Content is
+//! encrypted at rest when a data key is configured (see [`super::encryption`]).
+
+use anyhow::{Context, Result};
+use fs2::FileExt;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use tracing::warn;
+
+use super::encryption;
+
+/// Directory holding restorable state.
+pub const STATE_DIR: &str = "data/state";
+
+/// Backup kept next to `path` by [`save_with_backup`].
+pub fn backup_path(path: &str) -> String {
+    format!("{}.bak", path)
+}
+
+/// Read state from `path`, falling back to the default when the file is
+/// missing or unreadable. A file unreadable for want of the data key is
+/// never overwritten: saving over it fails instead.
+pub fn load<T: DeserializeOwned + Default>(path: &str) -> T {
+    read(path).unwrap_or_default()
+}
+
+/// Read state from `path`, recovering from its backup when the file is
+/// corrupt. None when neither is readable.
+pub fn read<T: DeserializeOwned>(path: &str) -> Option<T> {
+    let content = fs::read(path).ok()?;
+    let err = match decode(&content) {
+        Ok(value) => return Some(value),
+        Err(e) => e,
+    };
+    let backup = backup_path(path);
+    match fs::read(&backup).ok().and_then(|c| decode(&c).ok()) {
+        Some(value) => {
+            warn!("Unreadable state file {} ({:#}), recovered from {}", path, err, backup);
+            Some(value)
+        }
+        None => {
+            warn!("Ignoring unreadable state file {}: {:#}", path, err);
+            None
+        }
+    }
+}
+
+fn decode<T: DeserializeOwned>(content: &[u8]) -> Result<T> {
+    Ok(serde_json::from_str(&encryption::open(content)?)?)
+}
+
+/// Move an unreadable file aside so a fresh one doesn't overwrite it.
+/// Fails instead when the file is only unreadable for want of the key.
+pub fn quarantine(path: &str) -> Result<()> {
+    if is_sealed(path) && encryption::key()?.is_none() {
+        anyhow::bail!("{} is encrypted: set DATA_ENCRYPTION_KEY to open it", path);
+    }
+    let target = format!("{}.corrupt-{}", path, chrono::Utc::now().format("%Y%m%dT%H%M%S"));
+    match fs::rename(path, &target) {
+        Ok(()) => warn!("Moved unreadable {} to {}", path, target),
+        Err(e) => warn!("Failed to move unreadable {} aside: {}", path, e),
+    }
+    Ok(())
+}
+
+/// Atomically replace the state at `path`.
+pub fn save<T: Serialize>(path: &str, value: &T) -> Result<()> {
+    write_atomic(path, &seal_for(path, &serde_json::to_string_pretty(value)?)?)
+}
+
+/// Atomically replace the state at `path`, first copying the current file
+/// to its backup if it is valid JSON.
+pub fn save_with_backup<T: Serialize>(path: &str, value: &T) -> Result<()> {
+    let content = seal_for(path, &serde_json::to_string_pretty(value)?)?;
+    if let Some(current) = fs::read(path).ok().and_then(|c| encryption::open(&c).ok()) {
+        if serde_json::from_str::<IgnoredAny>(&current).is_ok() {
+            write_atomic(&backup_path(path), &encryption::seal(&current)?)?;
+        }
+    }
+    write_atomic(path, &content)
+}
+
+/// Append `line` to the log at `path`, sealed when a data key is
+/// configured.
+pub fn append_line(path: &str, line: &str) -> Result<()> {
+    if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let line = encryption::seal_line(line)?;
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// Lines of the log at `path`, oldest first. Lines that can't be opened
+/// (e.g. sealed with another key) are skipped.
+pub fn read_lines(path: &str) -> Vec<String> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let mut skipped = 0;
+    let lines = content
+        .lines()
+        .filter_map(|line| encryption::open_line(line).map_err(|_| skipped += 1).ok())
+        .collect();
+    if skipped > 0 {
+        warn!("Skipped {} unreadable lines of {}", skipped, path);
+    }
+    lines
+}
+
+/// Seal content for `path`, refusing to replace an encrypted file with
+/// plaintext when the key has gone missing.
+fn seal_for(path: &str, plaintext: &str) -> Result<Vec<u8>> {
+    if encryption::key()?.is_none() && is_sealed(path) {
+        anyhow::bail!("{} is encrypted: set DATA_ENCRYPTION_KEY to write it", path);
+    }
+    encryption::seal(plaintext)
+}
+
+/// Whether the file at `path` was written encrypted.
+fn is_sealed(path: &str) -> bool {
+    let mut prefix = Vec::new();
+    fs::File::open(path)
+        .and_then(|file| file.take(16).read_to_end(&mut prefix))
+        .is_ok_and(|_| encryption::is_encrypted(&prefix))
+}
+
+/// Take an exclusive lock on `path`, held until the returned file is
+/// dropped, so a read-modify-write from one process (the bot, the TUI)
+/// can't interleave with another's. The lock lives on a `.lock` file
+/// beside `path`, since `path` itself is replaced on every save.
+pub fn lock(path: &str) -> Result<fs::File> {
+    if let Some(dir) = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let lock_path = format!("{}.lock", path);
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open {}", lock_path))?;
+    file.lock_exclusive().with_context(|| format!("Failed to lock {}", lock_path))?;
+    Ok(file)
+}
+
+/// Write to a temporary file, fsync it, then rename it over `path`. The
+/// temporary file is named for this write alone, so writers that don't hold
+/// the lock (a CLI beside the bot) never truncate or rename each other's.
+fn write_atomic(path: &str, content: &[u8]) -> Result<()> {
+    let dir = Path::new(path).parent().filter(|d| !d.as_os_str().is_empty());
+    if let Some(dir) = dir {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = format!("{}.{}-{}.tmp", path, std::process::id(), uuid::Uuid::new_v4());
+    let written = fs::File::create(&tmp)
+        .and_then(|mut file| file.write_all(content).and_then(|_| file.sync_all()))
+        .with_context(|| format!("Failed to write {}", tmp))
+        .and_then(|_| fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path)));
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written?;
+    // Persist the rename itself; not supported on every platform
+    if let Some(dir) = dir {
+        if let Ok(dir) = fs::File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovers_from_backup() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-state-{}.json", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+
+        save_with_backup(&path, &vec![1]).unwrap();
+        save_with_backup(&path, &vec![1, 2]).unwrap();
+        assert_eq!(read::<Vec<i32>>(&path), Some(vec![1, 2]));
+
+        // A torn write falls back to the previous good version
+        fs::write(&path, "[1, 2, 3").unwrap();
+        assert_eq!(read::<Vec<i32>>(&path), Some(vec![1]));
+
+        // ...and is never copied over that backup
+        save_with_backup(&path, &vec![4]).unwrap();
+        fs::write(&path, "").unwrap();
+        assert_eq!(read::<Vec<i32>>(&path), Some(vec![1]));
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(backup_path(&path));
+    }
+
+    #[test]
+    fn test_lines_and_encrypted_files_without_the_key() {
+        let path = std::env::temp_dir()
+            .join(format!("polybot-state-{}.jsonl", uuid::Uuid::new_v4()))
+            .to_string_lossy()
+            .to_string();
+        append_line(&path, r#"{"n":1}"#).unwrap();
+        append_line(&path, r#"{"n":2}"#).unwrap();
+        assert_eq!(read_lines(&path), vec![r#"{"n":1}"#.to_string(), r#"{"n":2}"#.to_string()]);
+
+        if encryption::key().unwrap().is_none() {
+            // Without the key an encrypted file loads as the default, but is
+            // neither overwritten nor moved aside
+            fs::write(&path, b"PBENC1sealed").unwrap();
+            assert!(load::<Vec<i32>>(&path).is_empty());
+            assert!(save(&path, &vec![1]).is_err());
+            assert!(quarantine(&path).is_err());
+            assert_eq!(fs::read(&path).unwrap(), b"PBENC1sealed");
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_concurrent_writers_never_share_a_temp_file() {
+        let dir = std::env::temp_dir().join(format!("polybot-state-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("shared.json").to_string_lossy().to_string();
+        let writers: Vec<_> = (0..8)
+            .map(|n| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..20 {
+                        write_atomic(&path, serde_json::to_string(&vec![n; 1000]).unwrap().as_bytes()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        // Whichever write landed last is whole, and no temp file is left over
+        let saved: Vec<i32> = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert!(saved.len() == 1000 && saved.iter().all(|n| *n == saved[0]));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
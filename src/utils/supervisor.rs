@@ -0,0 +1,242 @@
+//! Task supervisor module.
+//!
+//! Runs long-lived subsystems as tokio tasks and restarts them with backoff
+//! when they crash (return an error or panic). Repeated crashes inside the
+//! restart window trip the circuit breaker instead of looping forever.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::circuit_breaker::CircuitBreaker;
+
+/// How long tasks get to stop on their own once the breaker trips.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// How a supervised task is restarted after a crash.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    /// Maximum restarts allowed inside `window` before tripping the breaker.
+    pub max_restarts: u32,
+    /// Window over which restarts are counted.
+    pub window: Duration,
+    /// Delay before the first restart.
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential backoff.
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: 5,
+            window: Duration::from_secs(300),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Restart statistics for one task.
+#[derive(Debug, Clone, Default)]
+pub struct TaskStats {
+    pub restarts: u32,
+    pub last_error: Option<String>,
+    pub running: bool,
+}
+
+/// Supervises a set of named tasks.
+pub struct Supervisor {
+    breaker: Arc<CircuitBreaker>,
+    stats: Arc<Mutex<HashMap<String, TaskStats>>>,
+    handles: Vec<JoinHandle<()>>,
+    shutdown_grace: Duration,
+}
+
+impl Supervisor {
+    /// Create a supervisor that trips `breaker` on crash loops.
+    pub fn new(breaker: Arc<CircuitBreaker>) -> Self {
+        Self {
+            breaker,
+            stats: Arc::new(Mutex::new(HashMap::new())),
+            handles: Vec::new(),
+            shutdown_grace: SHUTDOWN_GRACE,
+        }
+    }
+
+    /// Abort tasks still running this long after the breaker trips.
+    pub fn with_shutdown_grace(mut self, grace: Duration) -> Self {
+        self.shutdown_grace = grace;
+        self
+    }
+
+    /// Spawn a supervised task. `factory` is called again for every restart.
+    ///
+    /// A task that returns `Ok(())` is considered finished and is not restarted.
+    pub fn spawn<F, Fut>(&mut self, name: &str, policy: RestartPolicy, factory: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let name = name.to_string();
+        let breaker = self.breaker.clone();
+        let stats = self.stats.clone();
+        stats.lock().unwrap().insert(name.clone(), TaskStats::default());
+
+        let handle = tokio::spawn(async move {
+            let mut backoff = policy.initial_backoff;
+            let mut recent: Vec<Instant> = Vec::new();
+
+            while breaker.is_allowed() {
+                Self::update(&stats, &name, |s| s.running = true);
+                let outcome = tokio::spawn(factory()).await;
+                Self::update(&stats, &name, |s| s.running = false);
+
+                let failure = match outcome {
+                    Ok(Ok(())) => {
+                        info!("Task '{}' finished", name);
+                        return;
+                    }
+                    Ok(Err(e)) => e.to_string(),
+                    Err(join_err) => format!("panicked: {}", join_err),
+                };
+
+                error!("💥 Task '{}' crashed: {}", name, failure);
+                let now = Instant::now();
+                recent.retain(|t| now.duration_since(*t) < policy.window);
+                recent.push(now);
+                Self::update(&stats, &name, |s| {
+                    s.restarts += 1;
+                    s.last_error = Some(failure.clone());
+                });
+
+                if recent.len() as u32 > policy.max_restarts {
+                    breaker.trip(&format!(
+                        "Task '{}' crashed {} times in {:?}",
+                        name,
+                        recent.len(),
+                        policy.window
+                    ));
+                    return;
+                }
+
+                warn!("Restarting '{}' in {:?}", name, backoff);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+            }
+        });
+
+        self.handles.push(handle);
+    }
+
+    /// Snapshot of per-task statistics.
+    pub fn stats(&self) -> HashMap<String, TaskStats> {
+        self.stats.lock().unwrap().clone()
+    }
+
+    /// Shared handle to the statistics map (e.g. for a status panel).
+    pub fn stats_handle(&self) -> Arc<Mutex<HashMap<String, TaskStats>>> {
+        self.stats.clone()
+    }
+
+    /// Wait for every supervised task to stop. Once the breaker trips, tasks
+    /// still asleep or blocked after the grace period are aborted.
+    pub async fn join(self) {
+        let Self { breaker, handles, shutdown_grace, .. } = self;
+        let deadline = async {
+            breaker.tripped().await;
+            tokio::time::sleep(shutdown_grace).await;
+        };
+        tokio::pin!(deadline);
+
+        let mut handles = handles.into_iter();
+        while let Some(mut handle) = handles.next() {
+            tokio::select! {
+                _ = &mut handle => {}
+                _ = &mut deadline => {
+                    let stuck: Vec<JoinHandle<()>> = std::iter::once(handle).chain(handles).collect();
+                    warn!("Aborting {} tasks still running {:?} after the breaker tripped", stuck.len(), shutdown_grace);
+                    for handle in stuck {
+                        handle.abort();
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    fn update(stats: &Mutex<HashMap<String, TaskStats>>, name: &str, f: impl FnOnce(&mut TaskStats)) {
+        if let Some(entry) = stats.lock().unwrap().get_mut(name) {
+            f(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_restarts: u32) -> RestartPolicy {
+        RestartPolicy {
+            max_restarts,
+            window: Duration::from_secs(60),
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(4),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restarts_until_success() {
+        let breaker = Arc::new(CircuitBreaker::new());
+        let mut supervisor = Supervisor::new(breaker.clone());
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let counter = attempts.clone();
+        supervisor.spawn("flaky", fast_policy(5), move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    anyhow::bail!("boom");
+                }
+                Ok(())
+            }
+        });
+
+        let stats = supervisor.stats_handle();
+        supervisor.join().await;
+        assert_eq!(stats.lock().unwrap()["flaky"].restarts, 2);
+        assert!(breaker.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_crash_loop_trips_breaker() {
+        let breaker = Arc::new(CircuitBreaker::new());
+        let mut supervisor = Supervisor::new(breaker.clone());
+
+        supervisor.spawn("broken", fast_policy(2), || async { panic!("always") });
+        supervisor.join().await;
+
+        assert!(!breaker.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_join_aborts_tasks_that_ignore_the_breaker() {
+        let breaker = Arc::new(CircuitBreaker::new());
+        let mut supervisor = Supervisor::new(breaker.clone()).with_shutdown_grace(Duration::from_millis(20));
+
+        supervisor.spawn("sleepy", fast_policy(2), || async {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(())
+        });
+        breaker.trip("test");
+
+        tokio::time::timeout(Duration::from_secs(5), supervisor.join())
+            .await
+            .expect("join waited on a sleeping task");
+    }
+}
@@ -0,0 +1,229 @@
+//! Order throttling and burst protection.
+//!
+//! Every order and cancel sent to a venue takes a slot from that venue's
+//! per-minute cap (ORDER_RATE_LIMITS, CANCEL_RATE_LIMITS) and orders also
+//! from a per-second burst cap (ORDER_BURST_LIMIT), so the bot stays within
+//! exchange limits and a runaway strategy can't machine-gun orders during a
+//! bug. A request over a cap is refused, not queued: the signal that caused
+//! it is stale by the time a slot frees up. Refusals are counted per venue
+//! and action and reported by the bot as a warning alert. Slots are taken
+//! by the venue clients as a request goes out; dry-run requests take none.
+
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::config::Config;
+
+static GLOBAL: OnceLock<OrderThrottle> = OnceLock::new();
+
+const MINUTE: Duration = Duration::from_secs(60);
+const SECOND: Duration = Duration::from_secs(1);
+
+/// What a throttled request does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OrderAction {
+    Place,
+    Cancel,
+}
+
+impl fmt::Display for OrderAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OrderAction::Place => "order",
+            OrderAction::Cancel => "cancel",
+        })
+    }
+}
+
+type Key = (String, OrderAction);
+
+/// Per-venue order and cancel caps.
+#[derive(Debug, Default)]
+pub struct OrderThrottle {
+    /// Requests per minute by venue and action; missing = unlimited
+    per_minute: HashMap<Key, usize>,
+    /// Orders per second on any one venue; 0 = unlimited
+    burst: usize,
+    /// Times of the requests let through in the last minute
+    sent: Mutex<HashMap<Key, VecDeque<Instant>>>,
+    /// Requests refused since start
+    refused: Mutex<BTreeMap<Key, u64>>,
+    /// Refusals not yet reported
+    unreported: Mutex<BTreeMap<Key, u64>>,
+}
+
+impl OrderThrottle {
+    /// A throttle without caps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow at most `per_minute` requests of `action` on `venue` (0 = no cap).
+    pub fn with_limit(mut self, venue: &str, action: OrderAction, per_minute: u32) -> Self {
+        if per_minute > 0 {
+            self.per_minute.insert((venue.to_lowercase(), action), per_minute as usize);
+        }
+        self
+    }
+
+    /// Allow at most `per_second` orders on any one venue (0 = no cap).
+    pub fn with_burst(mut self, per_second: u32) -> Self {
+        self.burst = per_second as usize;
+        self
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let throttle = Self::new().with_burst(config.order_burst_limit);
+        let throttle = config
+            .order_rate_limits
+            .iter()
+            .fold(throttle, |t, (venue, limit)| t.with_limit(venue, OrderAction::Place, *limit));
+        config
+            .cancel_rate_limits
+            .iter()
+            .fold(throttle, |t, (venue, limit)| t.with_limit(venue, OrderAction::Cancel, *limit))
+    }
+
+    /// Take a slot for one request, or refuse it.
+    pub fn acquire(&self, venue: &str, action: OrderAction) -> Result<()> {
+        self.acquire_all(&[(venue, action)], Instant::now())
+    }
+
+    /// Take slots for several requests sent together at `now`: all of them
+    /// or none.
+    pub fn acquire_all(&self, requests: &[(&str, OrderAction)], now: Instant) -> Result<()> {
+        self.admit(requests, now, true)
+    }
+
+    /// Check that several requests about to be sent one by one (e.g. both
+    /// legs of an arb) would all get a slot at `now`, without taking any, so
+    /// a later one isn't refused after the first went out.
+    pub fn check_all(&self, requests: &[(&str, OrderAction)], now: Instant) -> Result<()> {
+        self.admit(requests, now, false)
+    }
+
+    fn admit(&self, requests: &[(&str, OrderAction)], now: Instant, take: bool) -> Result<()> {
+        let mut sent = self.sent.lock().unwrap();
+        let mut wanted: HashMap<Key, usize> = HashMap::new();
+        for (venue, action) in requests {
+            *wanted.entry((venue.to_lowercase(), *action)).or_default() += 1;
+        }
+        for (key, count) in &wanted {
+            let recent = sent.entry(key.clone()).or_default();
+            while recent.front().is_some_and(|t| now.duration_since(*t) >= MINUTE) {
+                recent.pop_front();
+            }
+            let over = match self.per_minute.get(key) {
+                Some(&cap) if recent.len() + count > cap => Some(format!("{} per minute", cap)),
+                _ if key.1 == OrderAction::Place && self.burst > 0 => {
+                    let last_second = recent.iter().rev().take_while(|t| now.duration_since(**t) < SECOND).count();
+                    (last_second + count > self.burst).then(|| format!("{} per second", self.burst))
+                }
+                _ => None,
+            };
+            if let Some(cap) = over {
+                drop(sent);
+                self.refuse(key, *count);
+                anyhow::bail!("Throttled: {} {} rate over {}", key.0, key.1, cap);
+            }
+        }
+        if take {
+            for (key, count) in wanted {
+                sent.entry(key).or_default().extend(std::iter::repeat_n(now, count));
+            }
+        }
+        Ok(())
+    }
+
+    fn refuse(&self, key: &Key, count: usize) {
+        warn!("🚦 Throttled {} {} {}(s)", count, key.0, key.1);
+        *self.refused.lock().unwrap().entry(key.clone()).or_default() += count as u64;
+        *self.unreported.lock().unwrap().entry(key.clone()).or_default() += count as u64;
+    }
+
+    /// Requests refused since start, as ("venue action", count).
+    pub fn refused(&self) -> Vec<(String, u64)> {
+        label(&self.refused.lock().unwrap())
+    }
+
+    /// Requests refused since the last call, for alerting.
+    pub fn take_unreported(&self) -> Vec<(String, u64)> {
+        label(&std::mem::take(&mut *self.unreported.lock().unwrap()))
+    }
+}
+
+fn label(counts: &BTreeMap<Key, u64>) -> Vec<(String, u64)> {
+    counts.iter().map(|((venue, action), n)| (format!("{} {}", venue, action), *n)).collect()
+}
+
+/// Install the process-wide throttle from config. Before this nothing is capped.
+pub fn init(config: &Config) {
+    GLOBAL.set(OrderThrottle::from_config(config)).ok();
+}
+
+pub fn global() -> &'static OrderThrottle {
+    GLOBAL.get_or_init(OrderThrottle::new)
+}
+
+/// Take a slot from the process-wide throttle.
+pub fn acquire(venue: &str, action: OrderAction) -> Result<()> {
+    global().acquire(venue, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caps_orders_per_minute_and_burst() {
+        let throttle = OrderThrottle::new()
+            .with_limit("polymarket", OrderAction::Place, 4)
+            .with_limit("kalshi", OrderAction::Place, 10)
+            .with_limit("polymarket", OrderAction::Cancel, 1)
+            .with_burst(2);
+        let start = Instant::now();
+        let place = |venue| [(venue, OrderAction::Place)];
+
+        assert!(throttle.acquire_all(&place("polymarket"), start).is_ok());
+        assert!(throttle.acquire_all(&place("polymarket"), start).is_ok());
+        // A third order within the second is a burst
+        assert!(throttle.acquire_all(&place("polymarket"), start).is_err());
+        let later = start + Duration::from_secs(2);
+        assert!(throttle.acquire_all(&place("polymarket"), later).is_ok());
+        assert!(throttle.acquire_all(&place("polymarket"), later + SECOND).is_ok());
+        // Four in the minute is the cap, until the first ones age out
+        assert!(throttle.acquire_all(&place("polymarket"), later + 2 * SECOND).unwrap_err().to_string().contains("4 per minute"));
+        assert!(throttle.acquire_all(&place("polymarket"), start + MINUTE).is_ok());
+        assert!(throttle.acquire_all(&place("polymarket"), start + MINUTE).is_ok());
+
+        // Paired legs go through together or not at all
+        let arb = [("kalshi", OrderAction::Place), ("polymarket", OrderAction::Place)];
+        assert!(throttle.acquire_all(&arb, start + MINUTE).is_err());
+        assert!(throttle.acquire_all(&place("kalshi"), start + MINUTE).is_ok());
+        // Checking ahead of sending doesn't use up the slot
+        let kalshi_later = place("kalshi");
+        let at = start + 3 * MINUTE;
+        assert!(throttle.check_all(&kalshi_later, at).is_ok());
+        assert!(throttle.check_all(&kalshi_later, at).is_ok());
+        assert!(throttle.acquire_all(&kalshi_later, at).is_ok());
+        assert!(throttle.acquire_all(&kalshi_later, at).is_ok());
+        assert!(throttle.check_all(&kalshi_later, at).is_err());
+
+        let cancel = [("polymarket", OrderAction::Cancel)];
+        assert!(throttle.acquire_all(&cancel, start).is_ok());
+        assert!(throttle.acquire_all(&cancel, start).is_err());
+        // Unlisted venues are only burst-capped
+        assert!(throttle.acquire_all(&[("other", OrderAction::Cancel)], start).is_ok());
+
+        assert_eq!(
+            throttle.take_unreported(),
+            [("kalshi order".to_string(), 1), ("polymarket order".to_string(), 3), ("polymarket cancel".to_string(), 1)]
+        );
+        assert!(throttle.take_unreported().is_empty());
+        assert_eq!(throttle.refused().len(), 3);
+    }
+}
@@ -0,0 +1,116 @@
+//! Time zone handling.
+//!
+//! Everything is kept in UTC internally. Wherever a wall clock matters the
+//! bot uses one configured zone (TIMEZONE, US Eastern by default): times
+//! venues publish without an offset, the daily P&L rollover, report
+//! boundaries and displayed timestamps. Published times labelled ET, EST
+//! or EDT are read on the New York clock whatever the label says, since
+//! venues write "EST" all year round.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+use crate::config::Config;
+
+/// Zone used when TIMEZONE is unset.
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::America::New_York;
+
+static GLOBAL: OnceLock<Tz> = OnceLock::new();
+
+/// Parse an IANA zone name, e.g. "America/New_York" or "UTC".
+pub fn parse(name: &str) -> Result<Tz> {
+    name.trim().parse().map_err(|e| anyhow::anyhow!("Unknown time zone '{}': {}", name, e))
+}
+
+/// Install the process-wide zone from config. Before this the default applies.
+pub fn init(config: &Config) {
+    GLOBAL.set(config.timezone).ok();
+}
+
+/// The configured zone.
+pub fn current() -> Tz {
+    *GLOBAL.get_or_init(|| DEFAULT_TIMEZONE)
+}
+
+/// Parse a published time: RFC 3339, or a date and time with an optional
+/// zone label (UTC/Z, ET/EST/EDT) read in `tz` when unlabelled.
+pub fn parse_time(value: &str, tz: Tz) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let (naive, label) = match value.rsplit_once(' ') {
+        Some((rest, label)) if label.chars().all(|c| c.is_ascii_alphabetic()) => (rest, Some(label.to_uppercase())),
+        _ => (value, None),
+    };
+    let naive = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())?;
+    match label.as_deref() {
+        None => local_to_utc(naive, tz),
+        Some("UTC" | "GMT" | "Z") => Some(naive.and_utc()),
+        Some("ET" | "EST" | "EDT") => local_to_utc(naive, chrono_tz::America::New_York),
+        Some(_) => None,
+    }
+}
+
+/// `naive` on the clock in `tz`; the earlier reading when DST repeats it and
+/// the first valid time after when DST skips it.
+fn local_to_utc(naive: NaiveDateTime, tz: Tz) -> Option<DateTime<Utc>> {
+    (0..=2)
+        .find_map(|hours| tz.from_local_datetime(&(naive + Duration::hours(hours))).earliest())
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// The calendar date `at` falls on in `tz`.
+pub fn local_date(at: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    at.with_timezone(&tz).date_naive()
+}
+
+/// Start of the day `at` falls on in `tz`: when daily figures roll over.
+pub fn day_start(at: DateTime<Utc>, tz: Tz) -> DateTime<Utc> {
+    local_to_utc(local_date(at, tz).and_hms_opt(0, 0, 0).unwrap(), tz).unwrap_or(at)
+}
+
+/// The same wall-clock time one calendar day before `at` in `tz`, so a
+/// daily window spans 23 or 25 hours across DST changes.
+pub fn day_before(at: DateTime<Utc>, tz: Tz) -> DateTime<Utc> {
+    local_to_utc(at.with_timezone(&tz).naive_local() - Duration::days(1), tz).unwrap_or(at - Duration::days(1))
+}
+
+/// `at` on the configured clock, e.g. "%m-%d %H:%M".
+pub fn format(at: DateTime<Utc>, format: &str) -> String {
+    at.with_timezone(&current()).format(format).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_times_and_days_on_the_configured_clock() {
+        let ny = chrono_tz::America::New_York;
+        let utc = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert!(parse("Mars/Olympus").is_err());
+
+        assert_eq!(parse_time("2026-01-05T22:00:00Z", ny), Some(utc("2026-01-05T22:00:00Z")));
+        // Unlabelled times are on the configured clock, labelled ones on theirs
+        assert_eq!(parse_time("2026-01-05 17:00", ny), Some(utc("2026-01-05T22:00:00Z")));
+        assert_eq!(parse_time("2026-01-05 17:00", chrono_tz::UTC), Some(utc("2026-01-05T17:00:00Z")));
+        assert_eq!(parse_time("2026-07-05 17:00 EST", chrono_tz::UTC), Some(utc("2026-07-05T21:00:00Z")));
+        assert_eq!(parse_time("2026-07-05 17:00 UTC", ny), Some(utc("2026-07-05T17:00:00Z")));
+        assert_eq!(parse_time("2026-07-05 17:00 PST", ny), None);
+        assert_eq!(parse_time("soon", ny), None);
+
+        // 01:00 UTC is still the previous evening in New York
+        let at = utc("2026-01-06T01:00:00Z");
+        assert_eq!(local_date(at, ny), NaiveDate::from_ymd_opt(2026, 1, 5).unwrap());
+        assert_eq!(day_start(at, ny), utc("2026-01-05T05:00:00Z"));
+        assert_eq!(day_start(at, chrono_tz::UTC), utc("2026-01-06T00:00:00Z"));
+        // The day clocks spring forward is 23 hours long
+        let after_dst = utc("2026-03-08T16:00:00Z");
+        assert_eq!(after_dst - day_before(after_dst, ny), Duration::hours(23));
+    }
+}
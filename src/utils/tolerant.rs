@@ -0,0 +1,212 @@
+//! Tolerant response parsing.
+//!
+//! API payloads are parsed into `serde_json::Value` first and then into the
+//! typed struct. Unknown fields and fields the payload no longer sends are
+//! logged once per schema version (the payload's key set), missing required
+//! text, list and object fields are filled with an empty value, and bad list
+//! items are skipped, so a schema change degrades data instead of failing
+//! the loop. Missing numbers are never filled: a price or amount of 0 would
+//! be read as real, so the object fails to parse instead.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashSet};
+use std::sync::{Mutex, OnceLock};
+use tracing::warn;
+
+/// Upper bound on missing fields filled for one object.
+const MAX_FILLED_FIELDS: usize = 16;
+
+/// Empty values tried, in order, for a missing required field. No number:
+/// prices and amounts must come from the payload.
+fn empty_candidates() -> [Value; 5] {
+    [
+        Value::Null,
+        Value::Array(Vec::new()),
+        Value::String(String::new()),
+        Value::Bool(false),
+        Value::Object(Map::new()),
+    ]
+}
+
+fn warned() -> &'static Mutex<HashSet<String>> {
+    static WARNED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    WARNED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Log a drift warning once per (schema, key set, message).
+fn warn_once(schema: &str, version: &str, message: &str) {
+    let key = format!("{}|{}|{}", schema, version, message);
+    if warned().lock().unwrap().insert(key) {
+        warn!("Schema drift in {}: {}", schema, message);
+    }
+}
+
+fn keys(value: &Value) -> BTreeSet<String> {
+    value.as_object().map(|m| m.keys().cloned().collect()).unwrap_or_default()
+}
+
+fn version(keys: &BTreeSet<String>) -> String {
+    keys.iter().cloned().collect::<Vec<_>>().join(",")
+}
+
+fn missing_field(error: &serde_json::Error) -> Option<String> {
+    let msg = error.to_string();
+    let rest = msg.strip_prefix("missing field `")?;
+    Some(rest[..rest.find('`')?].to_string())
+}
+
+/// Deserialize a number sent either as a JSON number or a numeric string
+/// (websocket feeds send prices and sizes as strings); anything else is an
+/// error rather than a made-up 0.
+pub fn f64_from_any<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    let number = match &value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    };
+    number.ok_or_else(|| serde::de::Error::custom(format!("expected a number, got {}", value)))
+}
+
+/// Parse a response body.
+pub fn parse<T: DeserializeOwned + Serialize>(schema: &str, text: &str) -> Result<T> {
+    let value: Value = serde_json::from_str(text)
+        .with_context(|| format!("{} response is not valid JSON", schema))?;
+    from_value(schema, value)
+}
+
+/// Parse one field of a response object, e.g. `{"market": {...}}`.
+pub fn parse_field<T: DeserializeOwned + Serialize>(schema: &str, text: &str, field: &str) -> Result<T> {
+    let mut value: Value = serde_json::from_str(text)
+        .with_context(|| format!("{} response is not valid JSON", schema))?;
+    let inner = value
+        .get_mut(field)
+        .map(Value::take)
+        .with_context(|| format!("{} response has no `{}` field", schema, field))?;
+    from_value(schema, inner)
+}
+
+/// Parse a response body that is a bare list, skipping unreadable items.
+pub fn parse_items<T: DeserializeOwned + Serialize>(schema: &str, text: &str) -> Result<Vec<T>> {
+    let value: Value = serde_json::from_str(text)
+        .with_context(|| format!("{} response is not valid JSON", schema))?;
+    Ok(parse_list(schema, value))
+}
+
+/// Parse a list, skipping (and reporting once) items that cannot be read.
+pub fn parse_list<T: DeserializeOwned + Serialize>(schema: &str, value: Value) -> Vec<T> {
+    let Value::Array(items) = value else {
+        warn_once(schema, "", "expected a list");
+        return Vec::new();
+    };
+    items
+        .into_iter()
+        .filter_map(|item| match from_value(schema, item) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                warn_once(schema, "item", &format!("skipping unreadable item: {}", e));
+                None
+            }
+        })
+        .collect()
+}
+
+/// Deserialize a value, reporting drift and filling missing fields.
+pub fn from_value<T: DeserializeOwned + Serialize>(schema: &str, mut value: Value) -> Result<T> {
+    let input_keys = keys(&value);
+    let version = version(&input_keys);
+    let mut filled = Vec::new();
+
+    let parsed = loop {
+        match serde_json::from_value::<T>(value.clone()) {
+            Ok(parsed) => break parsed,
+            Err(e) => {
+                let field = missing_field(&e)
+                    .filter(|_| value.is_object() && filled.len() < MAX_FILLED_FIELDS)
+                    .ok_or(e)
+                    .with_context(|| format!("Failed to parse {}", schema))?;
+                let object = value.as_object_mut().expect("checked is_object");
+
+                // Use the first empty value that gets past this field
+                let fill = empty_candidates().into_iter().find(|candidate| {
+                    let mut trial = object.clone();
+                    trial.insert(field.clone(), candidate.clone());
+                    match serde_json::from_value::<T>(Value::Object(trial)) {
+                        Ok(_) => true,
+                        Err(e) => missing_field(&e).is_some_and(|f| f != field),
+                    }
+                });
+                let Some(fill) = fill else {
+                    anyhow::bail!("Failed to parse {}: cannot default missing field `{}`", schema, field);
+                };
+                object.insert(field.clone(), fill);
+                filled.push(field);
+            }
+        }
+    };
+
+    if !filled.is_empty() {
+        warn_once(schema, &version, &format!("missing required fields defaulted: {}", filled.join(", ")));
+    }
+
+    if !input_keys.is_empty() {
+        if let Ok(known) = serde_json::to_value(&parsed) {
+            let known = keys(&known);
+            let unknown: Vec<_> = input_keys.difference(&known).cloned().collect();
+            if !unknown.is_empty() {
+                warn_once(schema, &version, &format!("unknown fields ignored: {}", unknown.join(", ")));
+            }
+            let absent: Vec<_> = known
+                .difference(&input_keys)
+                .filter(|k| !filled.contains(k))
+                .cloned()
+                .collect();
+            if !absent.is_empty() {
+                warn_once(schema, &version, &format!("fields not sent: {}", absent.join(", ")));
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Book {
+        ticker: String,
+        bids: Vec<(i32, i32)>,
+        depth: i64,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_missing_fields_are_filled() {
+        let book: Book = parse("book", r#"{"ticker":"T","yes":[[50,10]],"depth":4}"#).unwrap();
+        assert_eq!(book, Book { ticker: "T".to_string(), bids: vec![], depth: 4, note: None });
+
+        // A missing number is never made up
+        assert!(parse::<Book>("book", r#"{"ticker":"T","bids":[]}"#).is_err());
+
+        let book: Book = parse_field("book", r#"{"orderbook":{"ticker":"T","bids":[[1,2]],"depth":3}}"#, "orderbook").unwrap();
+        assert_eq!(book.bids, vec![(1, 2)]);
+
+        // Wrong types are still an error
+        assert!(parse::<Book>("book", r#"{"ticker":5}"#).is_err());
+    }
+
+    #[test]
+    fn test_list_skips_bad_items() {
+        let items: Vec<Book> = parse_list(
+            "books",
+            serde_json::json!([{"ticker":"A","bids":[],"depth":1}, {"ticker":7}, {"ticker":"B","depth":2}, {"ticker":"C"}]),
+        );
+        assert_eq!(items.iter().map(|b| b.ticker.as_str()).collect::<Vec<_>>(), vec!["A", "B"]);
+    }
+}
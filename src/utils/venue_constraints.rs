@@ -0,0 +1,237 @@
+//! Venue order constraints.
+//!
+//! Polymarket quotes on a price tick (usually 1¢, 0.1¢ on some markets) with
+//! a minimum order size in shares; Kalshi trades whole contracts at 1–99¢.
+//! Orders are rounded and validated here before they reach either venue.
+
+use anyhow::Result;
+
+use crate::polymarket::types::Side;
+
+/// Slack for float comparisons against tick and lot grids.
+const EPSILON: f64 = 1e-9;
+
+/// Price and size rules for one venue.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VenueConstraints {
+    pub venue: &'static str,
+    /// Minimum price increment
+    pub tick_size: f64,
+    pub min_price: f64,
+    pub max_price: f64,
+    /// Size increment (1.0 = whole contracts)
+    pub lot_size: f64,
+    /// Minimum order size
+    pub min_size: f64,
+}
+
+impl VenueConstraints {
+    /// Polymarket CLOB defaults: 1¢ tick, sizes to 0.01 shares, 5 share minimum.
+    pub fn polymarket() -> Self {
+        Self {
+            venue: "polymarket",
+            tick_size: 0.01,
+            min_price: 0.01,
+            max_price: 0.99,
+            lot_size: 0.01,
+            min_size: 5.0,
+        }
+    }
+
+    /// Kalshi: whole contracts priced 1–99¢.
+    pub fn kalshi() -> Self {
+        Self {
+            venue: "kalshi",
+            tick_size: 0.01,
+            min_price: 0.01,
+            max_price: 0.99,
+            lot_size: 1.0,
+            min_size: 1.0,
+        }
+    }
+
+    /// Constraints for a venue by name.
+    pub fn for_venue(venue: &str) -> Result<Self> {
+        match venue.to_lowercase().as_str() {
+            "polymarket" => Ok(Self::polymarket()),
+            "kalshi" => Ok(Self::kalshi()),
+            other => anyhow::bail!("No order constraints for venue {}", other),
+        }
+    }
+
+    /// Override the tick size (Polymarket markets near 0 or 1 use 0.001).
+    pub fn with_tick_size(mut self, tick_size: f64) -> Self {
+        self.tick_size = tick_size;
+        self.min_price = tick_size;
+        self.max_price = snap(1.0 - tick_size);
+        self
+    }
+
+    /// Override the minimum order size.
+    pub fn with_min_size(mut self, min_size: f64) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Round a limit price onto the tick grid, never in the trader's favour:
+    /// buys round down and sells round up so the limit is never exceeded.
+    pub fn round_price(&self, price: f64, side: Side) -> f64 {
+        let ticks = price / self.tick_size;
+        let ticks = match side {
+            Side::Buy => (ticks + EPSILON).floor(),
+            Side::Sell => (ticks - EPSILON).ceil(),
+        };
+        snap(ticks * self.tick_size)
+    }
+
+    /// Round a size down to the venue's lot size.
+    pub fn round_size(&self, size: f64) -> f64 {
+        snap((size / self.lot_size + EPSILON).floor() * self.lot_size)
+    }
+
+    /// Validate an already rounded order.
+    pub fn check(&self, price: f64, size: f64) -> Result<()> {
+        if !on_grid(price, self.tick_size) {
+            anyhow::bail!("{}: price {} is not a multiple of tick {}", self.venue, price, self.tick_size);
+        }
+        if price < self.min_price - EPSILON || price > self.max_price + EPSILON {
+            anyhow::bail!("{}: price {} outside {}-{}", self.venue, price, self.min_price, self.max_price);
+        }
+        if !on_grid(size, self.lot_size) {
+            anyhow::bail!("{}: size {} is not a multiple of lot {}", self.venue, size, self.lot_size);
+        }
+        if size < self.min_size - EPSILON {
+            anyhow::bail!("{}: size {} below minimum {}", self.venue, size, self.min_size);
+        }
+        Ok(())
+    }
+
+    /// Round then validate, returning the submittable (price, size).
+    pub fn normalize(&self, price: f64, size: f64, side: Side) -> Result<(f64, f64)> {
+        let price = self.round_price(price, side);
+        let size = self.round_size(size);
+        self.check(price, size)?;
+        Ok((price, size))
+    }
+}
+
+/// Order size: a share/contract count, or a USD budget converted to
+/// contracts at the order's price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sizing {
+    Contracts(f64),
+    Usd(f64),
+}
+
+impl Sizing {
+    /// Contracts for this size at `cost_per_contract`.
+    pub fn contracts(&self, cost_per_contract: f64) -> f64 {
+        match *self {
+            Sizing::Contracts(n) => n,
+            Sizing::Usd(usd) if cost_per_contract > 0.0 => usd / cost_per_contract,
+            Sizing::Usd(_) => 0.0,
+        }
+    }
+}
+
+/// Both legs of a paired order after rounding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairedOrder {
+    pub price_a: f64,
+    pub price_b: f64,
+    /// Shares/contracts on each leg (equal so the hedge is exact)
+    pub size: f64,
+}
+
+impl PairedOrder {
+    /// USD cost of the first leg.
+    pub fn usd_a(&self) -> f64 {
+        self.size * self.price_a
+    }
+
+    /// USD cost of the second leg.
+    pub fn usd_b(&self) -> f64 {
+        self.size * self.price_b
+    }
+
+    /// USD cost of both legs.
+    pub fn cost(&self) -> f64 {
+        self.usd_a() + self.usd_b()
+    }
+}
+
+/// Fit a two-leg buy to both venues' constraints using one common size.
+///
+/// Fails when no size satisfies both venues' minimums at once, e.g. a
+/// 3-contract trade that Kalshi accepts but Polymarket rejects.
+pub fn fit_pair(
+    a: &VenueConstraints,
+    b: &VenueConstraints,
+    price_a: f64,
+    price_b: f64,
+    size: f64,
+) -> Result<PairedOrder> {
+    let lot = a.lot_size.max(b.lot_size);
+    let size = snap((size / lot + EPSILON).floor() * lot);
+    let min_size = a.min_size.max(b.min_size);
+    if size < min_size - EPSILON {
+        anyhow::bail!(
+            "Size {} cannot satisfy both venues' minimums ({} {}, {} {})",
+            size, a.venue, a.min_size, b.venue, b.min_size
+        );
+    }
+
+    let (price_a, _) = a.normalize(price_a, size, Side::Buy)?;
+    let (price_b, _) = b.normalize(price_b, size, Side::Buy)?;
+    Ok(PairedOrder { price_a, price_b, size })
+}
+
+fn on_grid(value: f64, step: f64) -> bool {
+    let steps = value / step;
+    (steps - steps.round()).abs() < 1e-6
+}
+
+/// Trim float noise such as 0.57000000001.
+fn snap(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_is_conservative() {
+        let poly = VenueConstraints::polymarket();
+        assert_eq!(poly.round_price(0.5749, Side::Buy), 0.57);
+        assert_eq!(poly.round_price(0.5701, Side::Sell), 0.58);
+        assert_eq!(poly.round_price(0.57, Side::Buy), 0.57);
+        assert_eq!(VenueConstraints::kalshi().round_size(12.9), 12.0);
+
+        let fine = VenueConstraints::polymarket().with_tick_size(0.001);
+        assert_eq!(fine.round_price(0.9876, Side::Buy), 0.987);
+        assert!(fine.check(0.999, 10.0).is_ok());
+        assert!(poly.check(0.995, 10.0).is_err());
+    }
+
+    #[test]
+    fn test_fit_pair_uses_common_size() {
+        let poly = VenueConstraints::polymarket();
+        let kalshi = VenueConstraints::kalshi();
+
+        let pair = fit_pair(&poly, &kalshi, 0.456, 0.521, 10.7).unwrap();
+        assert_eq!(pair, PairedOrder { price_a: 0.45, price_b: 0.52, size: 10.0 });
+
+        // Kalshi would accept 3 contracts but Polymarket's minimum is 5
+        assert!(fit_pair(&poly, &kalshi, 0.45, 0.52, 3.0).is_err());
+        // Rounds down to 0¢, which Kalshi does not list
+        assert!(fit_pair(&poly, &kalshi, 0.45, 0.004, 10.0).is_err());
+
+        // $50 at 97¢ a pair buys 51 whole contracts on each leg
+        let contracts = Sizing::Usd(50.0).contracts(0.97);
+        let pair = fit_pair(&poly, &kalshi, 0.45, 0.52, contracts).unwrap();
+        assert_eq!(pair.size, 51.0);
+        assert!(pair.cost() <= 50.0);
+        assert_eq!(Sizing::Contracts(12.0).contracts(0.97), 12.0);
+    }
+}